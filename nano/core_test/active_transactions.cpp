@@ -101,6 +101,35 @@ TEST (active_transactions, confirm_election_by_request)
 }
 }
 
+TEST (active_transactions, confirm_req_stats)
+{
+	nano::test::system system{};
+	nano::node_config node_config = system.default_config ();
+	node_config.confirm_req.max_requests = 1;
+	auto & node = *system.add_node (node_config);
+	system.wallet (0)->insert_adhoc (nano::dev::genesis_key.prv);
+
+	nano::state_block_builder builder{};
+	auto send = builder
+				.account (nano::dev::genesis_key.pub)
+				.representative (nano::dev::genesis_key.pub)
+				.previous (nano::dev::genesis->hash ())
+				.link (nano::public_key ())
+				.balance (nano::dev::constants.genesis_amount - 100)
+				.sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				.work (*system.work.generate (nano::dev::genesis->hash ()))
+				.build_shared ();
+	node.process_active (send);
+	ASSERT_TIMELY (5s, nano::test::confirmed (node, { send }));
+
+	ASSERT_TIMELY_EQ (5s, 1, node.stats->count (nano::stat::type::election, nano::stat::detail::election_confirmed));
+	// The local representative confirms the block without requesting votes from other nodes
+	ASSERT_EQ (0, node.stats->count (nano::stat::type::election, nano::stat::detail::election_confirm_reqs));
+	ASSERT_EQ (std::chrono::milliseconds{ node_config.confirm_req.base_interval_ms }, node.config->confirm_req.request_interval (0));
+	ASSERT_TRUE (node.config->confirm_req.may_request (0));
+	ASSERT_FALSE (node.config->confirm_req.may_request (1));
+}
+
 namespace nano
 {
 TEST (active_transactions, confirm_frontier)
@@ -441,7 +470,7 @@ TEST (active_transactions, inactive_votes_cache_election_start)
 	nano::send_block_builder send_block_builder;
 	nano::state_block_builder state_block_builder;
 	// Enough weight to trigger election hinting but not enough to confirm block on its own
-	auto amount = ((node.online_reps.trended () / 100) * node.config->election_hint_weight_percent) / 2 + 1000 * nano::Gxrb_ratio;
+	auto amount = ((node.online_reps.trended () / 100) * node.config->hinted_scheduler.hinting_threshold_percent) / 2 + 1000 * nano::Gxrb_ratio;
 	auto send1 = send_block_builder.make_block ()
 				 .previous (latest)
 				 .destination (key1.pub)
@@ -1430,7 +1459,7 @@ TEST (active_transactions, limit_vote_hinted_elections)
 
 	// Setup representatives
 	// Enough weight to trigger election hinting but not enough to confirm block on its own
-	const auto amount = ((node.online_reps.trended () / 100) * node.config->election_hint_weight_percent) + 1000 * nano::Gxrb_ratio;
+	const auto amount = ((node.online_reps.trended () / 100) * node.config->hinted_scheduler.hinting_threshold_percent) + 1000 * nano::Gxrb_ratio;
 	nano::keypair rep1 = nano::test::setup_rep (system, node, amount / 2);
 	nano::keypair rep2 = nano::test::setup_rep (system, node, amount / 2);
 
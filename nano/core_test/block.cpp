@@ -93,6 +93,23 @@ TEST (block, difficulty)
 	ASSERT_EQ (nano::dev::network_params.work.difficulty (block), nano::dev::network_params.work.difficulty (block.work_version (), block.root (), block.block_work ()));
 }
 
+TEST (block, balance_delta)
+{
+	nano::keypair key;
+	nano::state_block open (key.pub, 0, key.pub, 10, 1, key.prv, key.pub, 0);
+	auto open_delta (open.balance_delta (nullptr));
+	ASSERT_EQ (1, open_delta.sign);
+	ASSERT_EQ (nano::amount (10), open_delta.amount);
+	nano::state_block send (key.pub, open.hash (), key.pub, 4, 2, key.prv, key.pub, 0);
+	auto send_delta (send.balance_delta (&open));
+	ASSERT_EQ (-1, send_delta.sign);
+	ASSERT_EQ (nano::amount (6), send_delta.amount);
+	nano::state_block change (key.pub, send.hash (), 3, 4, 0, key.prv, key.pub, 0);
+	auto change_delta (change.balance_delta (&send));
+	ASSERT_EQ (0, change_delta.sign);
+	ASSERT_EQ (nano::amount (0), change_delta.amount);
+}
+
 TEST (blocks, work_version)
 {
 	ASSERT_EQ (nano::work_version::work_1, nano::send_block ().work_version ());
@@ -67,4 +67,40 @@ TEST (block_processor, add_blocking_invalid_block)
 
 	ASSERT_TIMELY (5s, background.wait_for (std::chrono::seconds (0)) == std::future_status::ready);
 	ASSERT_FALSE (background.get ().has_value ());
-}
\ No newline at end of file
+}
+TEST (block_processor, dead_letter_gap_previous)
+{
+	nano::test::system system;
+	auto & node = *system.add_node ();
+	nano::state_block_builder builder;
+	auto send1 = builder.make_block ()
+				 .account (nano::dev::genesis_key.pub)
+				 .previous (nano::dev::genesis->hash ())
+				 .representative (nano::dev::genesis_key.pub)
+				 .balance (nano::dev::constants.genesis_amount - nano::Gxrb_ratio)
+				 .link (nano::dev::genesis_key.pub)
+				 .sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				 .work (*system.work.generate (nano::dev::genesis->hash ()))
+				 .build_shared ();
+	auto send2 = builder.make_block ()
+				 .account (nano::dev::genesis_key.pub)
+				 .previous (send1->hash ())
+				 .representative (nano::dev::genesis_key.pub)
+				 .balance (nano::dev::constants.genesis_amount - 2 * nano::Gxrb_ratio)
+				 .link (nano::dev::genesis_key.pub)
+				 .sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				 .work (*system.work.generate (send1->hash ()))
+				 .build_shared ();
+	for (auto i = 0; i < 3; ++i)
+	{
+		auto result = node.process_local (send2);
+		ASSERT_TRUE (result.has_value ());
+		ASSERT_EQ (nano::process_result::gap_previous, result->code);
+	}
+	ASSERT_EQ (3, node.block_processor.dead_letters.attempts (send2->hash ()));
+
+	// The block leaves the queue once it could be processed
+	node.process_active (send1);
+	ASSERT_TIMELY (5s, node.ledger.block_or_pruned_exists (send2->hash ()));
+	ASSERT_EQ (0, node.block_processor.dead_letters.attempts (send2->hash ()));
+}
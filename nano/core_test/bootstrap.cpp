@@ -774,6 +774,22 @@ TEST (bootstrap_processor, lazy_hash)
 	node1->stop ();
 }
 
+TEST (bootstrap_processor, lazy_trigger_deduplication)
+{
+	nano::test::system system;
+	nano::node_flags node_flags;
+	node_flags.set_disable_bootstrap_bulk_push_client (true);
+	auto node0 (system.add_node (node_flags));
+	nano::block_hash hash (42);
+	ASSERT_TRUE (node0->bootstrap_initiator.bootstrap_lazy (hash));
+	// The same hash was triggered recently
+	ASSERT_FALSE (node0->bootstrap_initiator.bootstrap_lazy (hash));
+	ASSERT_EQ (1, node0->bootstrap_initiator.deduplicated_triggers ());
+	// Forced triggers are never deduplicated
+	node0->bootstrap_initiator.bootstrap_lazy (hash, true);
+	ASSERT_EQ (1, node0->bootstrap_initiator.deduplicated_triggers ());
+}
+
 TEST (bootstrap_processor, lazy_hash_bootstrap_id)
 {
 	nano::test::system system;
@@ -44,6 +44,26 @@ TEST (cli, key_create)
 	ASSERT_EQ (vals[2], public_key.to_account ());
 }
 
+TEST (cli, wallet_send_requires_arguments)
+{
+	boost::program_options::variables_map vm;
+	vm.emplace ("wallet_send", boost::program_options::variable_value ());
+	vm.emplace ("wallet", boost::program_options::variable_value (std::string ("000000000000000000000000000000000000000000000000000000000000000A"), false));
+	auto ec = nano::handle_node_options (vm);
+	ASSERT_TRUE (ec == nano::error_cli::invalid_arguments);
+}
+
+TEST (cli, obsolete_udp_flags_are_accepted)
+{
+	boost::program_options::options_description description;
+	nano::add_node_flag_options (description);
+	char const * argv[] = { "nano_node", "--disable_udp", "--enable_udp" };
+	boost::program_options::variables_map vm;
+	ASSERT_NO_THROW (boost::program_options::store (boost::program_options::parse_command_line (3, argv, description), vm));
+	nano::node_flags flags;
+	ASSERT_FALSE (nano::update_flags (flags, vm));
+}
+
 TEST (cli, config_override_parsing)
 {
 	std::vector<nano::config_key_value_pair> key_value_pairs;
@@ -1776,3 +1776,22 @@ TEST (confirmation_height, pruned_source)
 	// Test removed because it could not be ported.
 	// The test should be rewritten in Rust
 }
+
+TEST (cemented_notification_buffer, delivers_in_cementing_order)
+{
+	nano::cemented_notification_buffer buffer;
+	nano::keypair key;
+	std::vector<int> delivered;
+	auto first = buffer.next_sequence (key.pub);
+	auto second = buffer.next_sequence (key.pub);
+	auto third = buffer.next_sequence (key.pub);
+
+	buffer.push (key.pub, third, [&delivered] () { delivered.push_back (3); });
+	buffer.push (key.pub, second, [&delivered] () { delivered.push_back (2); });
+	ASSERT_TRUE (delivered.empty ());
+	ASSERT_EQ (2, buffer.buffered_size ());
+
+	buffer.push (key.pub, first, [&delivered] () { delivered.push_back (1); });
+	ASSERT_EQ ((std::vector<int>{ 1, 2, 3 }), delivered);
+	ASSERT_EQ (0, buffer.buffered_size ());
+}
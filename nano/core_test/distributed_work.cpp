@@ -246,3 +246,29 @@ TEST (distributed_work, fail_resolve)
 	ASSERT_TIMELY (5s, done);
 	ASSERT_GE (nano::dev::network_params.work.difficulty (nano::work_version::work_1, hash, *work), node->network_params.work.get_base ());
 }
+
+TEST (distributed_work, skip_failing_peer)
+{
+	nano::test::system system (1);
+	auto node (system.nodes[0]);
+	ASSERT_TRUE (node->local_work_generation_enabled ());
+	nano::block_hash hash{ 1 };
+	auto peer (std::make_shared<fake_work_peer> (node->work, node->io_ctx, system.get_available_port (), work_peer_type::good));
+	peer->start ();
+	decltype (node->config->work_peers) peers;
+	peers.emplace_back ("::ffff:127.0.0.1", peer->port ());
+	for (auto i = 0; i < 3; ++i)
+	{
+		node->distributed_work.health.record_failure (peers[0]);
+	}
+	ASSERT_FALSE (node->distributed_work.health.is_available (peers[0]));
+	std::atomic<bool> done{ false };
+	ASSERT_FALSE (node->distributed_work.make (nano::work_version::work_1, hash, peers, node->network_params.work.get_base (), [&done] (boost::optional<uint64_t> work_a) {
+		ASSERT_TRUE (work_a.is_initialized ());
+		done = true;
+	},
+	nano::account ()));
+	ASSERT_TIMELY (5s, done);
+	// The work was generated locally, the peer with the open circuit was not asked
+	ASSERT_EQ (0, peer->generations_good);
+}
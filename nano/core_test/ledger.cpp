@@ -10,6 +10,10 @@
 
 #include <gtest/gtest.h>
 
+#include <boost/filesystem.hpp>
+
+#include <fstream>
+
 using namespace std::chrono_literals;
 
 TEST (votes, check_signature)
@@ -699,3 +703,57 @@ TEST (ledger, head_block)
 	auto tx = store.tx_begin_read ();
 	ASSERT_EQ (*nano::dev::genesis, *ledger.head_block (*tx, nano::dev::genesis->account ()));
 }
+
+TEST (ledger, valid_chain_prefix)
+{
+	auto ctx = nano::test::context::ledger_empty ();
+	auto & ledger = ctx.ledger ();
+	nano::work_pool pool{ nano::dev::network_params.network, std::numeric_limits<unsigned>::max () };
+	nano::block_builder builder;
+	auto send1 = builder.state ()
+				 .account (nano::dev::genesis_key.pub)
+				 .previous (nano::dev::genesis->hash ())
+				 .representative (nano::dev::genesis_key.pub)
+				 .balance (nano::dev::constants.genesis_amount - 1)
+				 .link (nano::dev::genesis_key.pub)
+				 .sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				 .work (*pool.generate (nano::dev::genesis->hash ()))
+				 .build_shared ();
+	// Signed with the wrong key
+	nano::keypair key;
+	auto send2 = builder.state ()
+				 .account (nano::dev::genesis_key.pub)
+				 .previous (send1->hash ())
+				 .representative (nano::dev::genesis_key.pub)
+				 .balance (nano::dev::constants.genesis_amount - 2)
+				 .link (nano::dev::genesis_key.pub)
+				 .sign (key.prv, key.pub)
+				 .work (*pool.generate (send1->hash ()))
+				 .build_shared ();
+	auto tx = ctx.store ().tx_begin_read ();
+	ASSERT_EQ (1, ledger.valid_chain_prefix (*tx, { send1 }));
+	ASSERT_EQ (1, ledger.valid_chain_prefix (*tx, { send1, send2 }));
+	ASSERT_FALSE (ledger.block_or_pruned_exists (*tx, send1->hash ()));
+}
+
+TEST (ledger, export_bootstrap_weights)
+{
+	auto ctx = nano::test::context::ledger_empty ();
+	auto & ledger = ctx.ledger ();
+	auto const path = nano::unique_path ();
+	ASSERT_FALSE (ledger.export_bootstrap_weights (1, path));
+
+	std::ifstream stream{ path.string (), std::ios::binary };
+	std::vector<uint8_t> bytes{ std::istreambuf_iterator<char> (stream), std::istreambuf_iterator<char> () };
+	ledger.set_bootstrap_weights ({});
+	ASSERT_FALSE (ledger.load_bootstrap_weights (bytes.data (), bytes.size ()));
+	auto const weights = ledger.get_bootstrap_weights ();
+	ASSERT_EQ (1, weights.size ());
+	ASSERT_EQ (nano::dev::constants.genesis_amount, weights.at (nano::dev::genesis_key.pub));
+	ASSERT_EQ (1, ledger.get_bootstrap_weight_max_blocks ());
+
+	// The genesis representative holds less than the minimum weight
+	ASSERT_FALSE (ledger.export_bootstrap_weights (nano::dev::constants.genesis_amount + 1, path));
+	// Only the 16 byte header with the block count is written
+	ASSERT_EQ (16, boost::filesystem::file_size (path));
+}
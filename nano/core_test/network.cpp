@@ -1024,6 +1024,18 @@ TEST (network, loopback_channel)
 	ASSERT_NE (channel1.get_remote_endpoint (), node1.network->endpoint ());
 }
 
+TEST (network, loopback_channel_flag)
+{
+	nano::test::system system;
+	nano::node_flags node_flags;
+	node_flags.set_enable_loopback_channel (true);
+	auto & node = *system.add_node (node_flags);
+	ASSERT_NE (nullptr, node.network->loopback_channel);
+	ASSERT_EQ (node.network->loopback_channel->get_type (), nano::transport::transport_type::loopback);
+	node.network->flood_block (nano::dev::genesis);
+	ASSERT_TIMELY (5s, node.stats->count (nano::stat::type::message, nano::stat::detail::publish, nano::stat::dir::in) == 1);
+}
+
 // Ensure the network filters messages with the incorrect magic number
 TEST (network, filter_invalid_network_bytes)
 {
@@ -1045,6 +1057,48 @@ TEST (network, filter_invalid_network_bytes)
 	ASSERT_TIMELY (5s, 1 == node1.stats->count (nano::stat::type::error, nano::stat::detail::invalid_network));
 }
 
+// Ensure peers which send messages of another network get excluded
+TEST (network, exclude_peer_on_wrong_network)
+{
+	nano::test::system system{ 2 };
+	auto & node1 = *system.nodes[0];
+	auto & node2 = *system.nodes[1];
+
+	auto channel = node2.network->find_node_id (node1.get_node_id ());
+	ASSERT_NE (nullptr, channel);
+
+	nano::keepalive keepalive{ nano::dev::network_params.network };
+	auto header{ keepalive.get_header () };
+	header.set_network (nano::networks::nano_live_network);
+	keepalive.set_header (header);
+	channel->send (keepalive);
+
+	ASSERT_TIMELY (5s, 1 == node1.stats->count (nano::stat::type::error, nano::stat::detail::invalid_network));
+	ASSERT_TIMELY (5s, 1 == node1.stats->count (nano::stat::type::tcp, nano::stat::detail::excluded_wrong_network));
+}
+
+// Ensure messages of another known network are only counted in warn only mode
+TEST (network, network_id_warn_only)
+{
+	nano::test::system system;
+	nano::node_config config = system.default_config ();
+	config.network_params.network.network_id_warn_only = true;
+	auto & node1 = *system.add_node (config);
+	auto & node2 = *system.add_node ();
+
+	auto channel = node2.network->find_node_id (node1.get_node_id ());
+	ASSERT_NE (nullptr, channel);
+
+	nano::keepalive keepalive{ nano::dev::network_params.network };
+	auto header{ keepalive.get_header () };
+	header.set_network (nano::networks::nano_live_network);
+	keepalive.set_header (header);
+	channel->send (keepalive);
+
+	ASSERT_TIMELY (5s, 1 == node1.stats->count (nano::stat::type::filter, nano::stat::detail::invalid_network_ignored));
+	ASSERT_EQ (0, node1.stats->count (nano::stat::type::error, nano::stat::detail::invalid_network));
+}
+
 // Ensure the network filters messages with the incorrect minimum version
 TEST (network, filter_invalid_version_using)
 {
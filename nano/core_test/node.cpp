@@ -2047,6 +2047,25 @@ TEST (node, online_reps)
 	ASSERT_EQ (node1.config->online_weight_minimum, node1.online_reps.trended ());
 }
 
+TEST (node, online_weight_sampler)
+{
+	nano::test::system system (1);
+	auto & node1 (*system.nodes[0]);
+	auto sampler (node1.online_reps.sampler ());
+	// Only the online weight minimum
+	ASSERT_EQ (node1.config->online_weight_minimum, sampler->calculate_trend ());
+	sampler->sample (nano::dev::constants.genesis_amount);
+	ASSERT_EQ (nano::dev::constants.genesis_amount, sampler->calculate_trend ());
+	// The trend is only taken over by the online reps when they sample
+	ASSERT_EQ (node1.config->online_weight_minimum, node1.online_reps.trended ());
+	node1.online_reps.set_trended (sampler->calculate_trend ());
+	ASSERT_EQ (nano::dev::constants.genesis_amount, node1.online_reps.trended ());
+
+	nano::online_weight_sampler standalone (node1.ledger, node1.config->online_weight_minimum.number (), 1);
+	standalone.sample (nano::dev::constants.genesis_amount);
+	ASSERT_EQ (nano::dev::constants.genesis_amount, standalone.calculate_trend ());
+}
+
 namespace nano
 {
 TEST (node, online_reps_rep_crawler)
@@ -3107,10 +3126,11 @@ TEST (node, peers)
 	{
 		// Add a peer to the database
 		auto transaction (store.tx_begin_write ());
-		store.peer ().put (*transaction, endpoint_key);
+		auto now = nano::seconds_since_epoch ();
+		store.peer ().put (*transaction, endpoint_key, now, now);
 
-		// Add a peer which is not contactable
-		store.peer ().put (*transaction, nano::endpoint_key{ boost::asio::ip::address_v6::any ().to_bytes (), 55555 });
+		// Add a peer which is not contactable and was last seen long ago
+		store.peer ().put (*transaction, nano::endpoint_key{ boost::asio::ip::address_v6::any ().to_bytes (), 55555 }, 0, 0);
 	}
 
 	node2->start ();
@@ -3127,7 +3147,7 @@ TEST (node, peers)
 	ASSERT_EQ (node1->get_node_id (), list2[0]->get_node_id ());
 	ASSERT_EQ (nano::transport::transport_type::tcp, list2[0]->get_type ());
 
-	// Uncontactable peer should not be stored
+	// Outdated peer should be pruned
 	ASSERT_TIMELY_EQ (5s, store.peer ().count (*store.tx_begin_read ()), 1);
 	ASSERT_TRUE (store.peer ().exists (*store.tx_begin_read (), endpoint_key));
 
@@ -3154,7 +3174,8 @@ TEST (node, peer_cache_restart)
 		{
 			// Add a peer to the database
 			auto transaction (store.tx_begin_write ());
-			store.peer ().put (*transaction, endpoint_key);
+			auto now = nano::seconds_since_epoch ();
+			store.peer ().put (*transaction, endpoint_key, now, now);
 		}
 		node2->start ();
 		ASSERT_TIMELY (10s, !node2->network->empty ());
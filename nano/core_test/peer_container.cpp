@@ -166,6 +166,24 @@ TEST (channels, fill_random_part)
 	ASSERT_TRUE (std::all_of (target.begin () + half, target.end (), [] (nano::endpoint const & endpoint_a) { return endpoint_a == nano::endpoint (boost::asio::ip::address_v6::any (), 0); }));
 }
 
+TEST (channels, sample_keepalive_peers_excludes_recipient)
+{
+	nano::test::system system{ 1 };
+	std::vector<nano::endpoint> endpoints;
+	for (int i = 0; i < 2; ++i)
+	{
+		auto outer_node = nano::test::add_outer_node (system);
+		ASSERT_NE (nullptr, nano::test::establish_tcp (system, *system.nodes[0], outer_node->network->endpoint ()));
+		endpoints.push_back (outer_node->network->endpoint ());
+	}
+	ASSERT_TIMELY_EQ (5s, 2, system.nodes[0]->network->tcp_channels->size ());
+	std::array<nano::endpoint, 8> target;
+	system.nodes[0]->network->tcp_channels->sample_keepalive_peers (target, endpoints[0]);
+	ASSERT_EQ (target.end (), std::find (target.begin (), target.end (), endpoints[0]));
+	ASSERT_NE (target.end (), std::find (target.begin (), target.end (), endpoints[1]));
+	ASSERT_TRUE (std::all_of (target.begin () + 1, target.end (), [] (nano::endpoint const & endpoint_a) { return endpoint_a == nano::endpoint (boost::asio::ip::address_v6::any (), 0); }));
+}
+
 // TODO: remove node instantiation requirement for testing with bigger network size
 TEST (peer_container, list_fanout)
 {
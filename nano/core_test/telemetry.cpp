@@ -234,6 +234,47 @@ TEST (telemetry, consolidate_data_remove_outliers_with_zero_bandwidth)
 	ASSERT_EQ (consolidated_telemetry_data2.get_bandwidth_cap (), 0);
 }
 
+TEST (telemetry, histogram)
+{
+	nano::telemetry_data data;
+	data.set_protocol_version (19);
+	data.set_major_version (25);
+	data.set_minor_version (1);
+	data.set_patch_version (0);
+	data.set_bandwidth_cap (0);
+
+	nano::telemetry_data data1 (data);
+	data1.set_bandwidth_cap (1024);
+
+	nano::telemetry_data data2;
+	data2.set_protocol_version (18);
+	data2.set_major_version (24);
+	data2.set_minor_version (0);
+	data2.set_patch_version (2);
+	data2.set_bandwidth_cap (1024);
+
+	auto histogram = nano::make_telemetry_histogram ({ data, data1, data2 });
+	ASSERT_EQ (3, histogram.total);
+	ASSERT_EQ (2, histogram.protocol_versions[19]);
+	ASSERT_EQ (1, histogram.protocol_versions[18]);
+	ASSERT_EQ (2, histogram.node_versions["25.1.0"]);
+	ASSERT_EQ (1, histogram.node_versions["24.0.2"]);
+	ASSERT_EQ (1, histogram.bandwidth_caps[0]);
+	ASSERT_EQ (2, histogram.bandwidth_caps[1024]);
+	ASSERT_EQ (19, histogram.majority_protocol_version ().value ());
+}
+
+TEST (telemetry, histogram_without_majority)
+{
+	nano::telemetry_data data;
+	data.set_protocol_version (19);
+	nano::telemetry_data data1;
+	data1.set_protocol_version (18);
+
+	ASSERT_FALSE (nano::make_telemetry_histogram ({ data, data1 }).majority_protocol_version ());
+	ASSERT_FALSE (nano::make_telemetry_histogram ({}).majority_protocol_version ());
+}
+
 TEST (telemetry, no_peers)
 {
 	nano::test::system system (1);
@@ -173,7 +173,6 @@ TEST (toml, daemon_config_deserialize_defaults)
 	ASSERT_EQ (conf.node.secondary_work_peers, defaults.node.secondary_work_peers);
 	ASSERT_EQ (conf.node.online_weight_minimum, defaults.node.online_weight_minimum);
 	ASSERT_EQ (conf.node.rep_crawler_weight_minimum, defaults.node.rep_crawler_weight_minimum);
-	ASSERT_EQ (conf.node.election_hint_weight_percent, defaults.node.election_hint_weight_percent);
 	ASSERT_EQ (conf.node.password_fanout, defaults.node.password_fanout);
 	ASSERT_EQ (conf.node.peering_port, defaults.node.peering_port);
 	ASSERT_EQ (conf.node.pow_sleep_interval, defaults.node.pow_sleep_interval);
@@ -184,6 +183,19 @@ TEST (toml, daemon_config_deserialize_defaults)
 	ASSERT_EQ (conf.node.tcp_incoming_connections_max, defaults.node.tcp_incoming_connections_max);
 	ASSERT_EQ (conf.node.tcp_io_timeout, defaults.node.tcp_io_timeout);
 	ASSERT_EQ (conf.node.unchecked_cutoff_time, defaults.node.unchecked_cutoff_time);
+	ASSERT_EQ (conf.node.max_unchecked_blocks, defaults.node.max_unchecked_blocks);
+	ASSERT_EQ (conf.node.max_rollback_depth, defaults.node.max_rollback_depth);
+	ASSERT_EQ (conf.node.reject_confirmed_forks, defaults.node.reject_confirmed_forks);
+	ASSERT_EQ (conf.node.max_blocks_per_account_per_batch, defaults.node.max_blocks_per_account_per_batch);
+	ASSERT_EQ (conf.node.max_forks_per_root, defaults.node.max_forks_per_root);
+	ASSERT_EQ (conf.node.peer_history_cutoff_time, defaults.node.peer_history_cutoff_time);
+	ASSERT_EQ (conf.node.min_peer_count, defaults.node.min_peer_count);
+	ASSERT_EQ (conf.node.receivable_cache, defaults.node.receivable_cache);
+	ASSERT_EQ (conf.node.free_disk_warning_mb, defaults.node.free_disk_warning_mb);
+	ASSERT_EQ (conf.node.free_disk_pause_mb, defaults.node.free_disk_pause_mb);
+	ASSERT_EQ (conf.node.free_disk_shutdown_mb, defaults.node.free_disk_shutdown_mb);
+	ASSERT_EQ (conf.node.memory_warning_mb, defaults.node.memory_warning_mb);
+	ASSERT_EQ (conf.node.work_threshold_override, defaults.node.work_threshold_override);
 	ASSERT_EQ (conf.node.use_memory_pools, defaults.node.use_memory_pools);
 	ASSERT_EQ (conf.node.vote_generator_delay, defaults.node.vote_generator_delay);
 	ASSERT_EQ (conf.node.vote_generator_threshold, defaults.node.vote_generator_threshold);
@@ -261,10 +273,20 @@ TEST (toml, daemon_config_deserialize_defaults)
 	ASSERT_EQ (conf.node.lmdb_config.sync, defaults.node.lmdb_config.sync);
 	ASSERT_EQ (conf.node.lmdb_config.max_databases, defaults.node.lmdb_config.max_databases);
 	ASSERT_EQ (conf.node.lmdb_config.map_size, defaults.node.lmdb_config.map_size);
+	ASSERT_EQ (conf.node.lmdb_config.block_height_index, defaults.node.lmdb_config.block_height_index);
 
 	ASSERT_EQ (conf.node.optimistic_scheduler.enabled, defaults.node.optimistic_scheduler.enabled);
 	ASSERT_EQ (conf.node.optimistic_scheduler.gap_threshold, defaults.node.optimistic_scheduler.gap_threshold);
 	ASSERT_EQ (conf.node.optimistic_scheduler.max_size, defaults.node.optimistic_scheduler.max_size);
+
+	ASSERT_EQ (conf.node.hinted_scheduler.enabled, defaults.node.hinted_scheduler.enabled);
+	ASSERT_EQ (conf.node.hinted_scheduler.check_interval_ms, defaults.node.hinted_scheduler.check_interval_ms);
+	ASSERT_EQ (conf.node.hinted_scheduler.hinting_threshold_percent, defaults.node.hinted_scheduler.hinting_threshold_percent);
+
+	ASSERT_EQ (conf.node.confirm_req.base_interval_ms, defaults.node.confirm_req.base_interval_ms);
+	ASSERT_EQ (conf.node.confirm_req.backoff_multiplier, defaults.node.confirm_req.backoff_multiplier);
+	ASSERT_EQ (conf.node.confirm_req.max_interval_ms, defaults.node.confirm_req.max_interval_ms);
+	ASSERT_EQ (conf.node.confirm_req.max_requests, defaults.node.confirm_req.max_requests);
 }
 
 TEST (toml, optional_child)
@@ -418,7 +440,6 @@ TEST (toml, daemon_config_deserialize_no_defaults)
 	background_threads = 999
 	online_weight_minimum = "999"
 	rep_crawler_weight_minimum = "999"
-	election_hint_weight_percent = 19
 	password_fanout = 999
 	peering_port = 999
 	pow_sleep_interval= 999
@@ -429,6 +450,19 @@ TEST (toml, daemon_config_deserialize_no_defaults)
 	tcp_incoming_connections_max = 999
 	tcp_io_timeout = 999
 	unchecked_cutoff_time = 999
+	max_unchecked_blocks = 999
+	max_rollback_depth = 999
+	reject_confirmed_forks = false
+	max_blocks_per_account_per_batch = 999
+	max_forks_per_root = 999
+	peer_history_cutoff_time = 999
+	min_peer_count = 999
+	receivable_cache = true
+	free_disk_warning_mb = 999
+	free_disk_pause_mb = 999
+	free_disk_shutdown_mb = 999
+	memory_warning_mb = 999
+	work_threshold_override = 999
 	use_memory_pools = false
 	vote_generator_delay = 999
 	vote_generator_threshold = 9
@@ -521,12 +555,24 @@ TEST (toml, daemon_config_deserialize_no_defaults)
 	sync = "nosync_safe"
 	max_databases = 999
 	map_size = 999
+	block_height_index = true
 
 	[node.optimistic_scheduler]
 	enabled = false
 	gap_threshold = 999
 	max_size = 999
 
+	[node.hinted_scheduler]
+	enable = false
+	check_interval = 999
+	hinting_threshold = 99
+
+	[node.confirm_req]
+	base_interval = 999
+	backoff_multiplier = 1.5
+	max_interval = 9999
+	max_requests = 99
+
 	[node.experimental]
 	secondary_work_peers = ["dev.org:998"]
 	max_pruning_age = 999
@@ -594,7 +640,6 @@ TEST (toml, daemon_config_deserialize_no_defaults)
 	ASSERT_NE (conf.node.max_pruning_depth, defaults.node.max_pruning_depth);
 	ASSERT_NE (conf.node.online_weight_minimum, defaults.node.online_weight_minimum);
 	ASSERT_NE (conf.node.rep_crawler_weight_minimum, defaults.node.rep_crawler_weight_minimum);
-	ASSERT_NE (conf.node.election_hint_weight_percent, defaults.node.election_hint_weight_percent);
 	ASSERT_NE (conf.node.password_fanout, defaults.node.password_fanout);
 	ASSERT_NE (conf.node.peering_port, defaults.node.peering_port);
 	ASSERT_NE (conf.node.pow_sleep_interval, defaults.node.pow_sleep_interval);
@@ -605,6 +650,19 @@ TEST (toml, daemon_config_deserialize_no_defaults)
 	ASSERT_NE (conf.node.tcp_incoming_connections_max, defaults.node.tcp_incoming_connections_max);
 	ASSERT_NE (conf.node.tcp_io_timeout, defaults.node.tcp_io_timeout);
 	ASSERT_NE (conf.node.unchecked_cutoff_time, defaults.node.unchecked_cutoff_time);
+	ASSERT_NE (conf.node.max_unchecked_blocks, defaults.node.max_unchecked_blocks);
+	ASSERT_NE (conf.node.max_rollback_depth, defaults.node.max_rollback_depth);
+	ASSERT_NE (conf.node.reject_confirmed_forks, defaults.node.reject_confirmed_forks);
+	ASSERT_NE (conf.node.max_blocks_per_account_per_batch, defaults.node.max_blocks_per_account_per_batch);
+	ASSERT_NE (conf.node.max_forks_per_root, defaults.node.max_forks_per_root);
+	ASSERT_NE (conf.node.peer_history_cutoff_time, defaults.node.peer_history_cutoff_time);
+	ASSERT_NE (conf.node.min_peer_count, defaults.node.min_peer_count);
+	ASSERT_NE (conf.node.receivable_cache, defaults.node.receivable_cache);
+	ASSERT_NE (conf.node.free_disk_warning_mb, defaults.node.free_disk_warning_mb);
+	ASSERT_NE (conf.node.free_disk_pause_mb, defaults.node.free_disk_pause_mb);
+	ASSERT_NE (conf.node.free_disk_shutdown_mb, defaults.node.free_disk_shutdown_mb);
+	ASSERT_NE (conf.node.memory_warning_mb, defaults.node.memory_warning_mb);
+	ASSERT_NE (conf.node.work_threshold_override, defaults.node.work_threshold_override);
 	ASSERT_NE (conf.node.use_memory_pools, defaults.node.use_memory_pools);
 	ASSERT_NE (conf.node.vote_generator_delay, defaults.node.vote_generator_delay);
 	ASSERT_NE (conf.node.vote_generator_threshold, defaults.node.vote_generator_threshold);
@@ -682,10 +740,20 @@ TEST (toml, daemon_config_deserialize_no_defaults)
 	ASSERT_NE (conf.node.lmdb_config.sync, defaults.node.lmdb_config.sync);
 	ASSERT_NE (conf.node.lmdb_config.max_databases, defaults.node.lmdb_config.max_databases);
 	ASSERT_NE (conf.node.lmdb_config.map_size, defaults.node.lmdb_config.map_size);
+	ASSERT_NE (conf.node.lmdb_config.block_height_index, defaults.node.lmdb_config.block_height_index);
 
 	ASSERT_NE (conf.node.optimistic_scheduler.enabled, defaults.node.optimistic_scheduler.enabled);
 	ASSERT_NE (conf.node.optimistic_scheduler.gap_threshold, defaults.node.optimistic_scheduler.gap_threshold);
 	ASSERT_NE (conf.node.optimistic_scheduler.max_size, defaults.node.optimistic_scheduler.max_size);
+
+	ASSERT_NE (conf.node.hinted_scheduler.enabled, defaults.node.hinted_scheduler.enabled);
+	ASSERT_NE (conf.node.hinted_scheduler.check_interval_ms, defaults.node.hinted_scheduler.check_interval_ms);
+	ASSERT_NE (conf.node.hinted_scheduler.hinting_threshold_percent, defaults.node.hinted_scheduler.hinting_threshold_percent);
+
+	ASSERT_NE (conf.node.confirm_req.base_interval_ms, defaults.node.confirm_req.base_interval_ms);
+	ASSERT_NE (conf.node.confirm_req.backoff_multiplier, defaults.node.confirm_req.backoff_multiplier);
+	ASSERT_NE (conf.node.confirm_req.max_interval_ms, defaults.node.confirm_req.max_interval_ms);
+	ASSERT_NE (conf.node.confirm_req.max_requests, defaults.node.confirm_req.max_requests);
 }
 
 /** There should be no required values **/
@@ -821,22 +889,7 @@ TEST (toml, daemon_config_deserialize_errors)
 		std::stringstream ss;
 		ss << R"toml(
 		[node]
-		election_hint_weight_percent = 4
-		)toml";
-
-		nano::tomlconfig toml;
-		toml.read (ss);
-		nano::daemon_config conf;
-		conf.deserialize_toml (toml);
-
-		ASSERT_EQ (toml.get_error ().get_message (), "election_hint_weight_percent must be a number between 5 and 50");
-	}
-
-	{
-		std::stringstream ss;
-		ss << R"toml(
-		[node]
-		election_hint_weight_percent = 51
+		bootstrap_frontier_request_count = 1000
 		)toml";
 
 		nano::tomlconfig toml;
@@ -844,14 +897,14 @@ TEST (toml, daemon_config_deserialize_errors)
 		nano::daemon_config conf;
 		conf.deserialize_toml (toml);
 
-		ASSERT_EQ (toml.get_error ().get_message (), "election_hint_weight_percent must be a number between 5 and 50");
+		ASSERT_EQ (toml.get_error ().get_message (), "bootstrap_frontier_request_count must be greater than or equal to 1024");
 	}
 
 	{
 		std::stringstream ss;
 		ss << R"toml(
 		[node]
-		bootstrap_frontier_request_count = 1000
+		max_forks_per_root = 0
 		)toml";
 
 		nano::tomlconfig toml;
@@ -859,7 +912,7 @@ TEST (toml, daemon_config_deserialize_errors)
 		nano::daemon_config conf;
 		conf.deserialize_toml (toml);
 
-		ASSERT_EQ (toml.get_error ().get_message (), "bootstrap_frontier_request_count must be greater than or equal to 1024");
+		ASSERT_EQ (toml.get_error ().get_message (), "max_forks_per_root must be greater than or equal to 1");
 	}
 }
 
@@ -44,6 +44,37 @@ TEST (vote_processor, codes)
 	ASSERT_EQ (nano::vote_code::indeterminate, node.vote_processor.vote_blocking (vote, channel));
 }
 
+TEST (vote_processor, on_vote_results)
+{
+	nano::test::system system (1);
+	auto & node (*system.nodes[0]);
+	nano::keypair key;
+	nano::block_hash unknown{ 1 };
+	auto vote (std::make_shared<nano::vote> (key.pub, key.prv, nano::vote::timestamp_min * 1, 0, std::vector<nano::block_hash>{ nano::dev::genesis->hash (), unknown }));
+	auto vote_invalid = std::make_shared<nano::vote> (*vote);
+	vote_invalid->flip_signature_bit_0 ();
+	auto channel (std::make_shared<nano::transport::inproc::channel> (node, node));
+	std::vector<nano::vote_results> observed;
+	node.on_vote ([&observed, &channel] (std::shared_ptr<nano::vote> const &, std::shared_ptr<nano::transport::channel> const & channel_a, nano::vote_results const & results_a) {
+		ASSERT_EQ (channel, channel_a);
+		observed.push_back (results_a);
+	});
+
+	node.block_confirm (nano::dev::genesis);
+	ASSERT_NE (nullptr, node.active.election (nano::dev::genesis->qualified_root ()));
+	node.vote_processor.vote_blocking (vote, channel);
+	node.vote_processor.vote_blocking (vote, channel);
+	node.vote_processor.vote_blocking (vote_invalid, channel);
+
+	ASSERT_EQ (3, observed.size ());
+	nano::vote_results expected{ { nano::dev::genesis->hash (), nano::vote_code::vote }, { unknown, nano::vote_code::indeterminate } };
+	ASSERT_EQ (expected, observed[0]);
+	expected[0].second = nano::vote_code::replay;
+	ASSERT_EQ (expected, observed[1]);
+	expected = { { nano::dev::genesis->hash (), nano::vote_code::invalid }, { unknown, nano::vote_code::invalid } };
+	ASSERT_EQ (expected, observed[2]);
+}
+
 TEST (vote_processor, flush)
 {
 	nano::test::system system (1);
@@ -120,6 +151,31 @@ TEST (vote_processor, overflow)
 	ASSERT_LT (std::chrono::system_clock::now () - start_time, 10s);
 }
 
+TEST (vote_processor, overflow_principal)
+{
+	nano::test::system system;
+	nano::node_flags node_flags;
+	node_flags.set_vote_processor_capacity (1);
+	auto & node (*system.add_node (node_flags));
+	node.vote_processor.calculate_weights ();
+	auto vote (std::make_shared<nano::vote> (nano::dev::genesis_key.pub, nano::dev::genesis_key.prv, nano::vote::timestamp_min * 1, 0, std::vector<nano::block_hash>{ nano::dev::genesis->hash () }));
+	auto channel (std::make_shared<nano::transport::inproc::channel> (node, node));
+
+	size_t not_processed{ 0 };
+	for (unsigned i = 0; i < 1000; ++i)
+	{
+		if (node.vote_processor.vote (vote, channel))
+		{
+			++not_processed;
+		}
+	}
+	ASSERT_GT (not_processed, 0);
+	ASSERT_EQ (not_processed, node.stats->count (nano::stat::type::vote, nano::stat::detail::vote_overflow_principal));
+	ASSERT_EQ (not_processed, node.vote_processor.dropped (nano::dev::genesis_key.pub));
+	node.vote_processor.flush ();
+	ASSERT_TRUE (node.vote_processor.empty ());
+}
+
 namespace nano
 {
 TEST (vote_processor, weights)
@@ -404,6 +404,26 @@ TEST (wallet, representative)
 	ASSERT_TRUE (wallet.is_representative (*transaction));
 }
 
+TEST (wallet, receive_minimum_override)
+{
+	auto error (false);
+	nano::mdb_env env (error, nano::unique_path ());
+	ASSERT_FALSE (error);
+	auto transaction (env.tx_begin_write ());
+	nano::kdf kdf{ nano::dev::network_params.kdf_work };
+	nano::wallet_store wallet (error, kdf, *transaction, nano::dev::genesis->account (), 1, "0");
+	ASSERT_FALSE (error);
+	ASSERT_FALSE (wallet.receive_minimum (*transaction));
+	wallet.receive_minimum_set (*transaction, nano::amount (42));
+	ASSERT_EQ (nano::amount (42), wallet.receive_minimum (*transaction));
+	wallet.receive_minimum_set (*transaction, nano::amount (0));
+	ASSERT_EQ (nano::amount (0), wallet.receive_minimum (*transaction));
+	wallet.receive_minimum_set (*transaction, std::nullopt);
+	ASSERT_FALSE (wallet.receive_minimum (*transaction));
+	// The override is not a key of the wallet
+	ASSERT_EQ (wallet.end (), wallet.begin (*transaction));
+}
+
 TEST (wallet, serialize_json_empty)
 {
 	auto error (false);
@@ -1175,3 +1195,72 @@ TEST (wallet, receive_pruned)
 	ASSERT_EQ (amount, node2.ledger.balance (*node2.store.tx_begin_read (), open1->hash ()));
 	ASSERT_TIMELY (5s, node2.ledger.cache.cemented_count () == 4);
 }
+
+TEST (wallet, receive_minimum_boundary)
+{
+	nano::test::system system;
+	nano::node_config config = system.default_config ();
+	// Without confirmations there are no automatic receives which interfere with the test
+	config.enable_voting = false;
+	nano::node_flags flags;
+	flags.set_disable_search_pending (true);
+	auto & node (*system.add_node (config, flags));
+	auto & wallet (*system.wallet (0));
+	nano::keypair key;
+	wallet.insert_adhoc (nano::dev::genesis_key.prv, false);
+	auto minimum = node.config->receive_minimum.number ();
+	auto dust = wallet.send_action (nano::dev::genesis_key.pub, key.pub, minimum - 1);
+	ASSERT_NE (nullptr, dust);
+	auto send = wallet.send_action (nano::dev::genesis_key.pub, key.pub, minimum);
+	ASSERT_NE (nullptr, send);
+	wallet.insert_adhoc (key.prv, false);
+
+	// Below the minimum the block is not received, but stays receivable
+	ASSERT_EQ (nullptr, wallet.receive_action (dust->hash (), key.pub, minimum - 1, key.pub));
+	ASSERT_TRUE (node.ledger.pending_info (*node.store.tx_begin_read (), nano::pending_key (key.pub, dust->hash ())));
+
+	// Exactly the minimum is received
+	auto open = wallet.receive_action (send->hash (), key.pub, minimum, key.pub);
+	ASSERT_NE (nullptr, open);
+	ASSERT_EQ (minimum, node.balance (key.pub));
+
+	// A lower per wallet minimum allows receiving the dust
+	wallet.store.receive_minimum_set (*node.wallets.tx_begin_write (), nano::amount (minimum - 1));
+	ASSERT_EQ (minimum - 1, wallet.receive_minimum (*node.wallets.tx_begin_read ()));
+	auto receive = wallet.receive_action (dust->hash (), key.pub, minimum - 1, key.pub);
+	ASSERT_NE (nullptr, receive);
+	ASSERT_EQ (2 * minimum - 1, node.balance (key.pub));
+	ASSERT_FALSE (node.ledger.pending_info (*node.store.tx_begin_read (), nano::pending_key (key.pub, dust->hash ())));
+}
+
+TEST (wallet, search_receivable_skips_dust)
+{
+	nano::test::system system;
+	nano::node_config config = system.default_config ();
+	config.enable_voting = false;
+	config.frontiers_confirmation = nano::frontiers_confirmation_mode::disabled;
+	nano::node_flags flags;
+	flags.set_disable_search_pending (true);
+	auto & node (*system.add_node (config, flags));
+	auto & wallet (*system.wallet (0));
+	wallet.insert_adhoc (nano::dev::genesis_key.prv);
+	nano::block_builder builder;
+	auto send = builder.state ()
+				.account (nano::dev::genesis->account ())
+				.previous (nano::dev::genesis->hash ())
+				.representative (nano::dev::genesis->account ())
+				.balance (nano::dev::constants.genesis_amount - node.config->receive_minimum.number () + 1)
+				.link (nano::dev::genesis->account ())
+				.sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				.work (*system.work.generate (nano::dev::genesis->hash ()))
+				.build ();
+	ASSERT_EQ (nano::process_result::progress, node.process (*send).code);
+
+	// Dust doesn't start an election
+	ASSERT_FALSE (wallet.search_receivable (*wallet.wallets.tx_begin_read ()));
+	ASSERT_EQ (nullptr, node.active.election (send->qualified_root ()));
+
+	wallet.store.receive_minimum_set (*node.wallets.tx_begin_write (), nano::amount (1));
+	ASSERT_FALSE (wallet.search_receivable (*wallet.wallets.tx_begin_read ()));
+	ASSERT_NE (nullptr, node.active.election (send->qualified_root ()));
+}
@@ -254,3 +254,49 @@ TEST (wallets, search_receivable)
 		ASSERT_EQ (send->hash (), receive->link ().as_block_hash ());
 	}
 }
+
+TEST (wallets, export_import)
+{
+	nano::test::system system (1);
+	auto & node (*system.nodes[0]);
+	auto id (nano::random_wallet_id ());
+	auto wallet (node.wallets.create (id));
+	nano::keypair key;
+	wallet->insert_adhoc (key.prv);
+
+	auto exported (node.wallets.export_wallet (id, "passphrase"));
+	ASSERT_TRUE (exported.has_value ());
+	ASSERT_EQ (std::string::npos, exported->find (key.pub.to_string ()));
+	ASSERT_FALSE (node.wallets.export_wallet (nano::random_wallet_id (), "passphrase").has_value ());
+
+	ASSERT_EQ (nullptr, node.wallets.import_wallet (nano::random_wallet_id (), *exported, "wrong"));
+	auto restored_id (nano::random_wallet_id ());
+	auto restored (node.wallets.import_wallet (restored_id, *exported, "passphrase"));
+	ASSERT_NE (nullptr, restored);
+	ASSERT_EQ (restored, node.wallets.open (restored_id));
+	ASSERT_TRUE (restored->exists (key.pub));
+	auto transaction (node.wallets.tx_begin_read ());
+	nano::raw_key prv;
+	ASSERT_FALSE (restored->store.fetch (*transaction, key.pub, prv));
+	ASSERT_EQ (key.prv, prv);
+}
+
+TEST (wallets, rotate_backups)
+{
+	nano::test::system system (1);
+	auto & node (*system.nodes[0]);
+	auto id (nano::random_wallet_id ());
+	auto wallet (node.wallets.create (id));
+	auto path (nano::unique_path () / (id.to_string () + ".json"));
+	boost::filesystem::create_directories (path.parent_path ());
+	auto transaction (node.wallets.tx_begin_write ());
+	wallet->store.write_rotated_backup (*transaction, path, 2);
+	// An unchanged wallet doesn't rotate the backups
+	wallet->store.write_rotated_backup (*transaction, path, 2);
+	ASSERT_TRUE (boost::filesystem::exists (path));
+	ASSERT_FALSE (boost::filesystem::exists (path.string () + ".1"));
+
+	wallet->store.insert_adhoc (*transaction, nano::keypair ().prv);
+	wallet->store.write_rotated_backup (*transaction, path, 2);
+	ASSERT_TRUE (boost::filesystem::exists (path.string () + ".1"));
+}
@@ -1,4 +1,5 @@
 #include <nano/lib/asio.hpp>
+#include <nano/lib/rsnano.hpp>
 
 nano::shared_const_buffer::shared_const_buffer (std::vector<uint8_t> const & data) :
 	m_data (std::make_shared<std::vector<uint8_t>> (data)),
@@ -29,8 +30,15 @@ nano::shared_const_buffer::shared_const_buffer (const uint8_t * data, std::size_
 {
 }
 
+nano::shared_const_buffer::shared_const_buffer (rsnano::SharedBufferHandle * handle_a) :
+	m_handle (handle_a, rsnano::rsn_shared_buffer_destroy),
+	m_buffer (rsnano::rsn_shared_buffer_data (handle_a), rsnano::rsn_shared_buffer_len (handle_a))
+{
+}
+
 nano::shared_const_buffer::shared_const_buffer (nano::shared_const_buffer const & other_a) :
 	m_data (other_a.m_data),
+	m_handle (other_a.m_handle),
 	m_buffer (other_a.m_buffer)
 {
 }
@@ -66,7 +74,17 @@ std::vector<uint8_t> nano::shared_const_buffer::to_bytes () const
 	}
 	return bytes;
 }
+
 const std::uint8_t * nano::shared_const_buffer::data () const
 {
-	return m_data->data ();
+	return static_cast<std::uint8_t const *> (m_buffer.data ());
+}
+
+rsnano::SharedBufferHandle * nano::shared_const_buffer::to_handle () const
+{
+	if (m_handle)
+	{
+		return rsnano::rsn_shared_buffer_clone (m_handle.get ());
+	}
+	return rsnano::rsn_shared_buffer_create (data (), size ());
 }
@@ -2,6 +2,11 @@
 
 #include <nano/boost/asio/write.hpp>
 
+namespace rsnano
+{
+class SharedBufferHandle;
+}
+
 namespace nano
 {
 class shared_const_buffer
@@ -16,6 +21,8 @@ public:
 	explicit shared_const_buffer (std::vector<uint8_t> && data);
 	explicit shared_const_buffer (std::shared_ptr<std::vector<uint8_t>> const & data);
 	explicit shared_const_buffer (const uint8_t * data_a, std::size_t size_a);
+	/** Takes ownership of a buffer which was created in Rust, without copying the bytes */
+	explicit shared_const_buffer (rsnano::SharedBufferHandle * handle_a);
 	shared_const_buffer (nano::shared_const_buffer const & other_a);
 
 	boost::asio::const_buffer const * begin () const;
@@ -24,9 +31,15 @@ public:
 	std::size_t size () const;
 	const std::uint8_t * data () const;
 	std::vector<uint8_t> to_bytes () const;
+	/**
+	 * Returns a new handle to the bytes, which can be passed to Rust.
+	 * The bytes are only copied if the buffer was created in C++
+	 */
+	rsnano::SharedBufferHandle * to_handle () const;
 
 private:
 	std::shared_ptr<std::vector<uint8_t>> m_data;
+	std::shared_ptr<rsnano::SharedBufferHandle> m_handle;
 	boost::asio::const_buffer m_buffer;
 };
 
@@ -173,6 +173,13 @@ nano::amount nano::block::balance () const
 	return amount;
 }
 
+nano::balance_delta nano::block::balance_delta (nano::block const * previous) const
+{
+	nano::balance_delta result;
+	result.sign = rsnano::rsn_block_balance_delta (previous != nullptr ? previous->get_handle () : nullptr, handle, result.amount.bytes.data ());
+	return result;
+}
+
 void nano::block::sign_zero ()
 {
 	signature_set (nano::signature (0));
@@ -1130,7 +1137,7 @@ nano::block_details::block_details (rsnano::BlockDetailsDto dto_a)
 
 bool nano::block_details::operator== (nano::block_details const & other_a) const
 {
-	return dto.epoch == other_a.dto.epoch && dto.is_send == other_a.dto.is_send && dto.is_receive == other_a.dto.is_receive && dto.is_epoch == other_a.dto.is_epoch;
+	return dto.epoch == other_a.dto.epoch && dto.is_send == other_a.dto.is_send && dto.is_receive == other_a.dto.is_receive && dto.is_epoch == other_a.dto.is_epoch && dto.is_pruned_source == other_a.dto.is_pruned_source && dto.is_self_send == other_a.dto.is_self_send;
 }
 
 nano::epoch nano::block_details::epoch () const
@@ -1153,6 +1160,16 @@ bool nano::block_details::is_epoch () const
 	return dto.is_epoch;
 }
 
+bool nano::block_details::is_pruned_source () const
+{
+	return dto.is_pruned_source;
+}
+
+bool nano::block_details::is_self_send () const
+{
+	return dto.is_self_send;
+}
+
 void nano::block_details::serialize (nano::stream & stream_a) const
 {
 	auto result = rsnano::rsn_block_details_serialize (&dto, &stream_a);
@@ -31,12 +31,13 @@ enum class block_type : uint8_t
 class block_details
 {
 	static_assert (std::is_same<std::underlying_type<nano::epoch>::type, uint8_t> (), "Epoch enum is not the proper type");
-	static_assert (static_cast<uint8_t> (nano::epoch::max) < (1 << 5), "Epoch max is too large for the sideband");
+	static_assert (static_cast<uint8_t> (nano::epoch::max) < (1 << 4), "Epoch max is too large for the sideband");
 
 public:
 	block_details ();
 	block_details (nano::epoch const epoch_a, bool const is_send_a, bool const is_receive_a, bool const is_epoch_a);
 	block_details (rsnano::BlockDetailsDto dto_a);
+	/** Size without the extension byte which follows if there are extended flags */
 	constexpr static size_t size ()
 	{
 		return 1;
@@ -48,6 +49,8 @@ public:
 	bool is_send () const;
 	bool is_receive () const;
 	bool is_epoch () const;
+	bool is_pruned_source () const;
+	bool is_self_send () const;
 
 	rsnano::BlockDetailsDto dto;
 };
@@ -84,6 +87,15 @@ private:
 	rsnano::BlockSidebandDto dto;
 };
 
+/** Signed change of the account balance caused by a block */
+class balance_delta
+{
+public:
+	// -1 for a decrease, 0 if the balance is unchanged and 1 for an increase
+	int sign{ 0 };
+	nano::amount amount{ 0 };
+};
+
 class block
 {
 public:
@@ -113,6 +125,8 @@ public:
 	virtual nano::link link () const;
 	virtual nano::account representative () const;
 	virtual nano::amount balance () const;
+	// Balance change relative to the previous block, nullptr for the first block of an account. Legacy blocks need a sideband
+	nano::balance_delta balance_delta (nano::block const * previous) const;
 	virtual void serialize (nano::stream &) const;
 	virtual void serialize_json (std::string &, bool = false) const;
 	virtual void serialize_json (boost::property_tree::ptree &) const;
@@ -1,5 +1,6 @@
 #include <nano/lib/blocks.hpp>
 #include <nano/lib/config.hpp>
+#include <nano/lib/rsnanoutils.hpp>
 
 #include <boost/filesystem/path.hpp>
 #include <boost/format.hpp>
@@ -66,29 +67,42 @@ nano::work_thresholds const nano::work_thresholds::publish_test ()
 	return nano::work_thresholds (dto);
 }
 
+bool nano::work_thresholds::set_override (nano::networks network_a, uint64_t threshold_a)
+{
+	return rsnano::rsn_work_thresholds_set_override (&dto, static_cast<uint16_t> (network_a), threshold_a);
+}
+
+namespace
+{
+uint64_t with_override (rsnano::WorkThresholdsDto const & dto_a, uint64_t threshold_a)
+{
+	return dto_a.threshold_override != 0 ? dto_a.threshold_override : threshold_a;
+}
+}
+
 uint64_t nano::work_thresholds::get_base () const
 {
-	return dto.base;
+	return with_override (dto, dto.base);
 }
 
 uint64_t nano::work_thresholds::get_epoch_2 () const
 {
-	return dto.epoch_2;
+	return with_override (dto, dto.epoch_2);
 }
 
 uint64_t nano::work_thresholds::get_epoch_2_receive () const
 {
-	return dto.epoch_2_receive;
+	return with_override (dto, dto.epoch_2_receive);
 }
 
 uint64_t nano::work_thresholds::get_entry () const
 {
-	return dto.entry;
+	return with_override (dto, dto.entry);
 }
 
 uint64_t nano::work_thresholds::get_epoch_1 () const
 {
-	return dto.epoch_1;
+	return with_override (dto, dto.epoch_1);
 }
 
 uint8_t work_version_to_uint8 (nano::work_version const version_a)
@@ -200,6 +214,7 @@ void nano::network_constants::read_dto (rsnano::NetworkConstantsDto const & dto)
 {
 	work = nano::work_thresholds (dto.work);
 	current_network = static_cast<nano::networks> (dto.current_network);
+	network_id = dto.network_id;
 	protocol_version = dto.protocol_version;
 	protocol_version_min = dto.protocol_version_min;
 	bootstrap_protocol_version_min = dto.bootstrap_protocol_version_min;
@@ -220,6 +235,9 @@ void nano::network_constants::read_dto (rsnano::NetworkConstantsDto const & dto)
 	ipv6_subnetwork_prefix_for_limiting = dto.ipv6_subnetwork_prefix_for_limiting;
 	silent_connection_tolerance_time = std::chrono::seconds (dto.silent_connection_tolerance_time_s);
 	vote_broadcast_interval = dto.vote_broadcast_interval_ms;
+	block_broadcast_interval = std::chrono::milliseconds (dto.block_broadcast_interval_ms);
+	block_broadcast_initial_delay = std::chrono::milliseconds (dto.block_broadcast_initial_delay_ms);
+	network_id_warn_only = dto.network_id_warn_only;
 	telemetry_request_cooldown = std::chrono::milliseconds (dto.telemetry_request_cooldown_ms);
 	telemetry_request_interval = std::chrono::milliseconds (dto.telemetry_request_interval_ms);
 	telemetry_broadcast_interval = std::chrono::milliseconds (dto.telemetry_broadcast_interval_ms);
@@ -232,6 +250,17 @@ bool nano::network_constants::set_active_network (std::string network_a)
 	return rsnano::rsn_network_constants_active_network_set_str (network_a.c_str ()) < 0;
 }
 
+bool nano::network_constants::load_network_definition (std::string const & path_a, std::string & error_a)
+{
+	rsnano::StringDto dto;
+	auto success = rsnano::rsn_network_definition_load (path_a.c_str (), &dto);
+	if (!success)
+	{
+		error_a = rsnano::convert_dto_to_string (dto);
+	}
+	return !success;
+}
+
 std::chrono::milliseconds nano::network_constants::cleanup_period_half () const
 {
 	auto dto{ to_dto () };
@@ -288,6 +317,7 @@ rsnano::NetworkConstantsDto nano::network_constants::to_dto () const
 {
 	rsnano::NetworkConstantsDto dto;
 	dto.current_network = static_cast<uint16_t> (current_network);
+	dto.network_id = network_id;
 	dto.work = work.dto;
 	dto.principal_weight_factor = principal_weight_factor;
 	dto.default_node_port = default_node_port;
@@ -307,6 +337,9 @@ rsnano::NetworkConstantsDto nano::network_constants::to_dto () const
 	dto.peer_dump_interval_s = peer_dump_interval.count ();
 	dto.protocol_version = protocol_version;
 	dto.vote_broadcast_interval_ms = vote_broadcast_interval;
+	dto.block_broadcast_interval_ms = block_broadcast_interval.count ();
+	dto.block_broadcast_initial_delay_ms = block_broadcast_initial_delay.count ();
+	dto.network_id_warn_only = network_id_warn_only;
 	dto.telemetry_request_cooldown_ms = telemetry_request_cooldown.count ();
 	dto.telemetry_request_interval_ms = telemetry_request_interval.count ();
 	dto.telemetry_broadcast_interval_ms = telemetry_broadcast_interval.count ();
@@ -174,6 +174,9 @@ public:
 	static nano::work_thresholds const publish_dev ();
 	static nano::work_thresholds const publish_test ();
 
+	/** Replaces all thresholds of this instance. Zero removes the override. Returns false if the network doesn't allow it */
+	bool set_override (nano::networks network_a, uint64_t threshold_a);
+
 	rsnano::WorkThresholdsDto dto;
 };
 
@@ -190,6 +193,8 @@ public:
 
 	/** The network this param object represents. This may differ from the global active network; this is needed for certain --debug... commands */
 	nano::networks current_network{ nano::network_constants::active_network () };
+	/** Id which is sent in every message header. Differs from current_network for a private network loaded from a network definition */
+	uint16_t network_id{ static_cast<uint16_t> (nano::network_constants::active_network ()) };
 	nano::work_thresholds work;
 
 	unsigned principal_weight_factor;
@@ -217,6 +222,12 @@ public:
 	std::chrono::seconds peer_dump_interval;
 	/** Time to wait before vote rebroadcasts for active elections (milliseconds) */
 	uint64_t vote_broadcast_interval;
+	/** Minimum time between rebroadcasts of the winning block of an election root */
+	std::chrono::milliseconds block_broadcast_interval;
+	/** The block of a new election was just flooded, so it isn't rebroadcast before this delay passed */
+	std::chrono::milliseconds block_broadcast_initial_delay;
+	/** Accept messages with the network id of another known network and only count them. For test setups only */
+	bool network_id_warn_only;
 
 	/** We do not reply to telemetry requests made within cooldown period */
 	std::chrono::milliseconds telemetry_request_cooldown;
@@ -247,6 +258,15 @@ public:
 	 */
 	static bool set_active_network (std::string network_a);
 
+	/**
+	 * Optionally called on startup to run a private network with a custom genesis block,
+	 * epoch signers and work thresholds. Also sets the active network to the base network of the definition.
+	 * @param path_a Path to a TOML or JSON network definition file
+	 * @param error_a Receives the error message if loading failed
+	 * @return true if loading failed
+	 */
+	static bool load_network_definition (std::string const & path_a, std::string & error_a);
+
 	char const * get_current_network_as_string ();
 
 	bool is_live_network () const;
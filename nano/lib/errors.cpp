@@ -225,6 +225,10 @@ std::string nano::error_rpc_messages::message (int ev) const
 			return "Source not found";
 		case nano::error_rpc::stopped:
 			return "Stopped";
+		case nano::error_rpc::work_below_active_difficulty:
+			return "Block work is below the active difficulty";
+		case nano::error_rpc::work_threshold_override_disabled:
+			return "Work thresholds can only be overridden on the dev and test network";
 	}
 
 	return "Invalid error code";
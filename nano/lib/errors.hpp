@@ -124,7 +124,9 @@ enum class error_rpc
 	rpc_control_disabled,
 	sign_hash_disabled,
 	source_not_found,
-	stopped
+	stopped,
+	work_below_active_difficulty,
+	work_threshold_override_disabled
 };
 
 /** process_result related errors */
@@ -32,6 +32,7 @@ void nano::lmdb_config::load_dto (rsnano::LmdbConfigDto & dto)
 	}
 	max_databases = dto.max_databases;
 	map_size = dto.map_size;
+	block_height_index = dto.block_height_index;
 }
 
 rsnano::LmdbConfigDto nano::lmdb_config::to_dto () const
@@ -57,6 +58,7 @@ rsnano::LmdbConfigDto nano::lmdb_config::to_dto () const
 	}
 	dto.max_databases = max_databases;
 	dto.map_size = map_size;
+	dto.block_height_index = block_height_index;
 	return dto;
 }
 
@@ -65,6 +67,7 @@ nano::error nano::lmdb_config::deserialize_toml (nano::tomlconfig & toml)
 	auto default_max_databases = max_databases;
 	toml.get_optional<uint32_t> ("max_databases", max_databases);
 	toml.get_optional<size_t> ("map_size", map_size);
+	toml.get_optional<bool> ("block_height_index", block_height_index);
 
 	if (!toml.get_error ())
 	{
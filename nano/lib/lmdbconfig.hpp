@@ -50,5 +50,7 @@ public:
 	sync_strategy sync{ always };
 	uint32_t max_databases{ 128 };
 	size_t map_size{ 256ULL * 1024 * 1024 * 1024 };
+	/** Maintain the (account, height) -> block hash index of the ledger */
+	bool block_height_index{ false };
 };
 }
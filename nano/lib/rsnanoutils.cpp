@@ -233,3 +233,8 @@ std::chrono::system_clock::time_point rsnano::time_point_from_nanoseconds (uint6
 	std::chrono::nanoseconds result_ns{ nanoseconds };
 	return std::chrono::system_clock::time_point (std::chrono::duration_cast<std::chrono::system_clock::duration> (result_ns));
 }
+
+uint64_t rsnano::time_point_to_nanoseconds (std::chrono::system_clock::time_point const & time_point)
+{
+	return std::chrono::duration_cast<std::chrono::nanoseconds> (time_point.time_since_epoch ()).count ();
+}
@@ -233,5 +233,6 @@ public:
 };
 
 std::chrono::system_clock::time_point time_point_from_nanoseconds (uint64_t nanoseconds);
+uint64_t time_point_to_nanoseconds (std::chrono::system_clock::time_point const & time_point);
 
 }
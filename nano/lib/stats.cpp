@@ -229,3 +229,51 @@ uint64_t nano::stats::count (stat::type type, stat::detail detail, stat::dir dir
 	static_cast<uint8_t> (detail),
 	static_cast<uint8_t> (dir));
 }
+
+void nano::stats::disable_sampling (stat::type type, stat::detail detail, stat::dir dir)
+{
+	rsnano::rsn_stat_disable_sampling (handle,
+	static_cast<uint8_t> (type),
+	static_cast<uint8_t> (detail),
+	static_cast<uint8_t> (dir));
+}
+
+void nano::stats::define_histogram (stat::type type, stat::detail detail, stat::dir dir, std::vector<uint64_t> const & intervals, uint64_t bin_count)
+{
+	rsnano::rsn_stat_define_histogram (handle,
+	static_cast<uint8_t> (type),
+	static_cast<uint8_t> (detail),
+	static_cast<uint8_t> (dir),
+	intervals.data (),
+	intervals.size (),
+	bin_count);
+}
+
+void nano::stats::update_histogram (stat::type type, stat::detail detail, stat::dir dir, uint64_t index, uint64_t addend)
+{
+	rsnano::rsn_stat_update_histogram (handle,
+	static_cast<uint8_t> (type),
+	static_cast<uint8_t> (detail),
+	static_cast<uint8_t> (dir),
+	index,
+	addend);
+}
+
+std::vector<uint64_t> nano::stats::histogram_values (stat::type type, stat::detail detail, stat::dir dir)
+{
+	std::vector<uint64_t> values;
+	auto bin_count = rsnano::rsn_stat_histogram_values (handle,
+	static_cast<uint8_t> (type),
+	static_cast<uint8_t> (detail),
+	static_cast<uint8_t> (dir),
+	nullptr,
+	0);
+	values.resize (bin_count);
+	rsnano::rsn_stat_histogram_values (handle,
+	static_cast<uint8_t> (type),
+	static_cast<uint8_t> (detail),
+	static_cast<uint8_t> (dir),
+	values.data (),
+	values.size ());
+	return values;
+}
@@ -14,6 +14,7 @@
 #include <memory>
 #include <mutex>
 #include <string>
+#include <vector>
 
 namespace nano
 {
@@ -130,6 +131,21 @@ public:
 	 */
 	void add (stat::type type, stat::detail detail, stat::dir dir, uint64_t value, bool detail_only = false);
 
+	/** Disables sampling for a given type/detail/dir combination */
+	void disable_sampling (stat::type type, stat::detail detail, stat::dir dir);
+
+	/**
+	 * Define histogram bins. Values are clamped into the first and last bins.
+	 * If \p bin_count is non-zero, \p intervals must contain the start and end of a uniform histogram.
+	 */
+	void define_histogram (stat::type type, stat::detail detail, stat::dir dir, std::vector<uint64_t> const & intervals, uint64_t bin_count = 0);
+
+	/** Add \p addend to the histogram bin into which \p index falls */
+	void update_histogram (stat::type type, stat::detail detail, stat::dir dir, uint64_t index, uint64_t addend = 1);
+
+	/** Returns the bin values of the histogram, or an empty vector if no histogram is defined */
+	std::vector<uint64_t> histogram_values (stat::type type, stat::detail detail, stat::dir dir);
+
 	/** Returns current value for the given counter at the type level */
 	uint64_t count (stat::type type, stat::dir dir = stat::dir::in);
 
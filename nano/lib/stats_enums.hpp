@@ -50,6 +50,7 @@ enum class type : uint8_t
 	bootstrap_ascending_connections,
 	bootstrap_ascending_thread,
 	bootstrap_ascending_accounts,
+	socks5,
 };
 
 /** Optional detail type */
@@ -104,6 +105,11 @@ enum class detail : uint8_t
 	balance_mismatch,
 	representative_mismatch,
 	block_position,
+	height_overflow,
+	timestamp_in_future,
+	confirmed_fork,
+	account_batch_limit,
+	fork_limit,
 
 	// message specific
 	not_a_type,
@@ -146,6 +152,7 @@ enum class detail : uint8_t
 	vote_indeterminate,
 	vote_invalid,
 	vote_overflow,
+	vote_overflow_principal,
 
 	// election specific
 	vote_new,
@@ -162,6 +169,9 @@ enum class detail : uint8_t
 	generate_vote,
 	generate_vote_normal,
 	generate_vote_final,
+	election_confirmed,
+	election_confirm_reqs,
+	election_confirm_req_limit,
 
 	// election types
 	normal,
@@ -238,6 +248,7 @@ enum class detail : uint8_t
 	empty_payload,
 	cleanup_outdated,
 	cleanup_dead,
+	newer_protocol_majority,
 
 	// vote generator
 	generator_broadcasts,
@@ -247,6 +258,9 @@ enum class detail : uint8_t
 
 	// hinting
 	missing_block,
+	dependent_unconfirmed,
+	already_confirmed,
+	cooldown,
 
 	// bootstrap server
 	response,
@@ -289,6 +303,7 @@ enum class detail : uint8_t
 	track,
 	timeout,
 	nothing_new,
+	invalid_blocks,
 
 	// bootstrap ascending connections
 	connect,
@@ -330,6 +345,29 @@ enum class detail : uint8_t
 	// optimistic
 	pop_gap,
 	pop_leaf,
+
+	// socks5
+	proxy_connected,
+	proxy_connect_error,
+	proxy_handshake_error,
+	proxy_resolve_error,
+	proxy_fallback,
+
+	// election block broadcasting
+	broadcast_block_initial,
+	broadcast_block_repeat,
+	broadcast_block_winner_changed,
+	broadcast_block_confirmed,
+
+	// network id enforcement
+	invalid_network_ignored,
+	excluded_wrong_network,
+
+	// pruning
+	requests_pruned,
+
+	// vote packing
+	requests_packed_votes,
 };
 
 /** Direction of the stat. If the direction is irrelevant, use in */
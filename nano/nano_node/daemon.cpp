@@ -127,6 +127,19 @@ void nano_daemon::daemon::run (boost::filesystem::path const & data_path, nano::
 				config.node.peering_port = network_params.network.default_node_port;
 			}
 
+			auto config_error = false;
+			for (auto const & issue : config.validate ())
+			{
+				auto message = (issue.is_error ? "Config error: " : "Config warning: ") + issue.message;
+				std::cerr << message << std::endl;
+				logger.always_log (message);
+				config_error |= issue.is_error;
+			}
+			if (config_error)
+			{
+				std::exit (1);
+			}
+
 			auto node (std::make_shared<nano::node> (async_rt, data_path, config.node, opencl_work, flags));
 			if (!node->init_error ())
 			{
@@ -144,6 +157,11 @@ void nano_daemon::daemon::run (boost::filesystem::path const & data_path, nano::
 				{
 					std::cout << "Voting with more than one representative can limit performance: " << voting << " representatives are configured" << std::endl;
 				}
+				if (auto issue = node->config->validate_voting (voting))
+				{
+					std::cout << "Config warning: " << issue->message << std::endl;
+					logger.always_log ("Config warning: ", issue->message);
+				}
 				node->start ();
 				nano::ipc::ipc_server ipc_server (*node, config.rpc);
 				std::unique_ptr<boost::process::child> rpc_process;
@@ -193,6 +211,9 @@ void nano_daemon::daemon::run (boost::filesystem::path const & data_path, nano::
 					sig_int_or_term = 1;
 				};
 
+				// Shut down like on SIGTERM if the disk is nearly full, before the ledger runs out of space
+				node->resource_shutdown_action = nano::signal_handler_impl;
+
 				nano::signal_manager sigman;
 
 				// keep trapping Ctrl-C to avoid a second Ctrl-C interrupting tasks started by the first
@@ -63,8 +63,10 @@ int main (int argc, char * const * argv)
 		("rpcconfig", boost::program_options::value<std::vector<nano::config_key_value_pair>>()->multitoken(), "Pass rpc configuration values. This takes precedence over any values in the configuration file. This option can be repeated multiple times.")
 		("daemon", "Start node daemon")
 		("compare_rep_weights", "Display a summarized comparison between the hardcoded bootstrap weights and representative weights from the ledger. Full comparison is output to logs")
+		("export_bootstrap_weights", "Write the representative weights of the ledger as bootstrap weights to <file>. Representatives with less than the optional <amount> in raw are left out")
 		("debug_block_dump", "Display all the blocks in the ledger in text format")
 		("debug_block_count", "Display the number of blocks")
+		("debug_database_stats", "Display the number of entries, pages and the estimated size of each database table")
 		("debug_bootstrap_generate", "Generate bootstrap sequence of blocks")
 		("debug_dump_frontier_unchecked_dependents", "Dump frontiers which have matching unchecked keys")
 		("debug_dump_trended_weight", "Dump trended weights table")
@@ -129,6 +131,17 @@ int main (int argc, char * const * argv)
 		}
 	}
 
+	auto network_definition (vm.find ("network_definition"));
+	if (network_definition != vm.end ())
+	{
+		std::string error;
+		if (nano::network_constants::load_network_definition (network_definition->second.as<std::string> (), error))
+		{
+			std::cerr << "Invalid network definition: " << error << std::endl;
+			std::exit (1);
+		}
+	}
+
 	nano::network_params network_params{ nano::network_constants::active_network () };
 	auto data_path_it = vm.find ("data_path");
 	boost::filesystem::path data_path ((data_path_it != vm.end ()) ? data_path_it->second.as<std::string> () : nano::working_path ());
@@ -159,13 +172,13 @@ int main (int argc, char * const * argv)
 				nano::inactive_node inactive_node (data_path, node_flags);
 				auto node = inactive_node.node;
 
-				auto const bootstrap_weights = node->get_bootstrap_weights ();
-				auto const & hardcoded = bootstrap_weights.second;
-				auto const hardcoded_height = bootstrap_weights.first;
+				node->load_bootstrap_weights ();
+				auto const hardcoded = node->ledger.get_bootstrap_weights ();
+				auto const hardcoded_height = node->ledger.get_bootstrap_weight_max_blocks ();
 				auto const ledger_unfiltered = node->ledger.cache.rep_weights ().get_rep_amounts ();
 				auto const ledger_height = node->ledger.cache.block_count ();
 
-				auto get_total = [] (decltype (bootstrap_weights.second) const & reps) -> nano::uint128_union {
+				auto get_total = [] (decltype (hardcoded) const & reps) -> nano::uint128_union {
 					return std::accumulate (reps.begin (), reps.end (), nano::uint128_t{ 0 }, [] (auto sum, auto const & rep) { return sum + rep.second; });
 				};
 
@@ -299,6 +312,40 @@ int main (int argc, char * const * argv)
 				result = -1;
 			}
 		}
+		else if (vm.count ("export_bootstrap_weights"))
+		{
+			nano::amount min_weight{ 0 };
+			if (vm.count ("file") != 1)
+			{
+				std::cerr << "Missing --file argument\n";
+				result = -1;
+			}
+			else if (vm.count ("amount") == 1 && min_weight.decode_dec (vm["amount"].as<std::string> ()))
+			{
+				std::cerr << "Invalid amount\n";
+				result = -1;
+			}
+			else
+			{
+				auto node_flags = nano::inactive_node_flag_defaults ();
+				nano::update_flags (node_flags, vm);
+				auto gen_cache{ node_flags.generate_cache () };
+				gen_cache.enable_reps (true);
+				node_flags.set_generate_cache (gen_cache);
+				nano::inactive_node inactive_node (data_path, node_flags);
+				auto node = inactive_node.node;
+				boost::filesystem::path const path{ vm["file"].as<std::string> () };
+				if (node->ledger.export_bootstrap_weights (min_weight.number (), path))
+				{
+					std::cerr << "Could not write the bootstrap weights to " << path.string () << "\n";
+					result = -1;
+				}
+				else
+				{
+					std::cout << "Bootstrap weights at " << node->ledger.cache.block_count () << " blocks written to " << path.string () << "\n";
+				}
+			}
+		}
 		else if (vm.count ("debug_block_dump"))
 		{
 			auto inactive_node = nano::default_inactive_node (data_path, vm);
@@ -325,6 +372,19 @@ int main (int argc, char * const * argv)
 			auto node = inactive_node.node;
 			std::cout << boost::str (boost::format ("Block count: %1%\n") % node->ledger.cache.block_count ());
 		}
+		else if (vm.count ("debug_database_stats"))
+		{
+			auto node_flags = nano::inactive_node_flag_defaults ();
+			nano::update_flags (node_flags, vm);
+			nano::inactive_node inactive_node (data_path, node_flags);
+			boost::property_tree::ptree stats;
+			inactive_node.node->store.serialize_memory_stats (stats);
+			std::cout << boost::str (boost::format ("Map size: %1% bytes, used: %2% bytes, page size: %3% bytes\n") % stats.get<std::string> ("map_size") % stats.get<std::string> ("map_used") % stats.get<std::string> ("page_size"));
+			for (auto const & [_, table] : stats.get_child ("tables"))
+			{
+				std::cout << boost::str (boost::format ("%1%: %2% entries, depth %3%, %4% branch pages, %5% leaf pages, %6% overflow pages, %7% bytes\n") % table.get<std::string> ("name") % table.get<std::string> ("entries") % table.get<std::string> ("depth") % table.get<std::string> ("branch_pages") % table.get<std::string> ("leaf_pages") % table.get<std::string> ("overflow_pages") % table.get<std::string> ("size"));
+			}
+		}
 		else if (vm.count ("debug_bootstrap_generate"))
 		{
 			auto key_it = vm.find ("key");
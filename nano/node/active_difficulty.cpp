@@ -0,0 +1,48 @@
+#include <nano/lib/blocks.hpp>
+#include <nano/lib/rsnano.hpp>
+#include <nano/node/active_difficulty.hpp>
+#include <nano/node/blockprocessor.hpp>
+
+nano::active_difficulty::active_difficulty (nano::work_thresholds const & work, std::size_t max_samples) :
+	handle{ rsnano::rsn_active_difficulty_create (&work.dto, max_samples) },
+	max_samples{ max_samples }
+{
+}
+
+nano::active_difficulty::~active_difficulty ()
+{
+	rsnano::rsn_active_difficulty_destroy (handle);
+}
+
+void nano::active_difficulty::connect (nano::block_processor & block_processor)
+{
+	block_processor.processed.add ([this] (auto const & result, auto const & block) {
+		if (result.code == nano::process_result::progress)
+		{
+			rsnano::rsn_active_difficulty_sample (handle, block->get_handle ());
+		}
+	});
+}
+
+double nano::active_difficulty::multiplier () const
+{
+	return rsnano::rsn_active_difficulty_multiplier (handle);
+}
+
+uint64_t nano::active_difficulty::difficulty () const
+{
+	return rsnano::rsn_active_difficulty_difficulty (handle);
+}
+
+uint64_t nano::active_difficulty::receive_difficulty () const
+{
+	return rsnano::rsn_active_difficulty_receive_difficulty (handle);
+}
+
+std::vector<double> nano::active_difficulty::trend () const
+{
+	std::vector<double> result (max_samples);
+	auto len = rsnano::rsn_active_difficulty_trend (handle, result.data (), result.size ());
+	result.resize (len);
+	return result;
+}
@@ -0,0 +1,35 @@
+#pragma once
+
+#include <nano/lib/config.hpp>
+
+#include <cstdint>
+#include <vector>
+
+namespace rsnano
+{
+class ActiveDifficultyHandle;
+}
+
+namespace nano
+{
+class block_processor;
+
+// Tracks the work difficulty of recently processed blocks, which is reported by the active_difficulty RPC
+class active_difficulty final
+{
+public:
+	explicit active_difficulty (nano::work_thresholds const & work, std::size_t max_samples = 20);
+	active_difficulty (nano::active_difficulty const &) = delete;
+	~active_difficulty ();
+	void connect (nano::block_processor & block_processor);
+	// Average normalized multiplier of the recently processed blocks
+	double multiplier () const;
+	uint64_t difficulty () const;
+	uint64_t receive_difficulty () const;
+	std::vector<double> trend () const;
+
+private:
+	rsnano::ActiveDifficultyHandle * handle;
+	std::size_t max_samples;
+};
+}
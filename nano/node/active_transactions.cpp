@@ -18,6 +18,7 @@ nano::active_transactions::active_transactions (nano::node & node_a, nano::confi
 	node{ node_a },
 	recently_confirmed{ 65536 },
 	recently_cemented{ node.config->confirmation_history_size },
+	broadcast_throttle{ node_a.network_params.network.block_broadcast_initial_delay, node_a.network_params.network.block_broadcast_interval },
 	election_time_to_live{ node_a.network_params.network.is_dev_network () ? 0s : 2s }
 {
 	count_by_behavior.fill (0); // Zero initialize array
@@ -67,6 +68,14 @@ void nano::active_transactions::stop ()
 
 void nano::active_transactions::block_cemented_callback (std::shared_ptr<nano::block> const & block_a)
 {
+	node.confirmation_latency.block_cemented (block_a->hash ());
+	if (node.block_tracer.record (block_a->hash (), nano::block_trace_event::cemented))
+	{
+		if (auto summary = node.block_tracer.summary (block_a->hash ()))
+		{
+			node.logger->always_log (*summary);
+		}
+	}
 	auto transaction = node.store.tx_begin_read ();
 
 	boost::optional<nano::election_status_type> election_status_type;
@@ -256,6 +265,12 @@ void nano::active_transactions::request_confirm (nano::unique_lock<nano::mutex>
 		bool const confirmed_l (election_l->confirmed ());
 		unconfirmed_count_l += !confirmed_l;
 
+		if (confirmed_l)
+		{
+			// Peers which lack the winner should get it now that it is confirmed
+			election_l->broadcast_block (solicitor);
+		}
+
 		if (confirmed_l || election_l->transition_time (solicitor))
 		{
 			erase (election_l->qualified_root);
@@ -290,6 +305,7 @@ void nano::active_transactions::cleanup_election (nano::unique_lock<nano::mutex>
 		node.inactive_vote_cache.erase (hash);
 	}
 	roots.get<tag_root> ().erase (roots.get<tag_root> ().find (election->qualified_root));
+	broadcast_throttle.erase (election->qualified_root);
 
 	lock_a.unlock ();
 	vacancy_update ();
@@ -422,11 +438,12 @@ nano::election_insertion_result nano::active_transactions::insert_impl (nano::un
 				count_by_behavior[result.election->behavior ()]++;
 
 				lock_a.unlock ();
-				if (auto const cache = node.inactive_vote_cache.find (hash); cache)
+				if (auto const cache = node.inactive_vote_cache.promote (hash); cache)
 				{
 					result.election->fill_from_cache (*cache);
 				}
 				node.stats->inc (nano::stat::type::active_started, nano::to_stat_detail (election_behavior_a));
+				node.block_tracer.record (hash, nano::block_trace_event::election_started);
 				node.observers->active_started.notify (hash);
 				vacancy_update ();
 			}
@@ -453,8 +470,17 @@ nano::election_insertion_result nano::active_transactions::insert_impl (nano::un
 
 // Validate a vote and apply it to the current election if one exists
 nano::vote_code nano::active_transactions::vote (std::shared_ptr<nano::vote> const & vote_a)
+{
+	nano::vote_results results;
+	return vote (vote_a, results);
+}
+
+nano::vote_code nano::active_transactions::vote (std::shared_ptr<nano::vote> const & vote_a, nano::vote_results & results_a)
 {
 	nano::vote_code result{ nano::vote_code::indeterminate };
+	results_a.clear ();
+	// Hashes without an election are put into the inactive vote cache, so the result is not known yet
+	std::unordered_map<nano::block_hash, nano::vote_code> hash_results;
 	// If all hashes were recently confirmed then it is a replay
 	unsigned recently_confirmed_counter (0);
 
@@ -477,6 +503,7 @@ nano::vote_code nano::active_transactions::vote (std::shared_ptr<nano::vote> con
 			else
 			{
 				++recently_confirmed_counter;
+				hash_results[hash] = nano::vote_code::replay;
 			}
 		}
 	}
@@ -496,6 +523,14 @@ nano::vote_code nano::active_transactions::vote (std::shared_ptr<nano::vote> con
 			auto const result_l = election->vote (vote_a->account (), vote_a->timestamp (), block_hash);
 			processed = processed || result_l.processed;
 			replay = replay || result_l.replay;
+			if (result_l.processed)
+			{
+				hash_results[block_hash] = nano::vote_code::vote;
+			}
+			else if (result_l.replay)
+			{
+				hash_results[block_hash] = nano::vote_code::replay;
+			}
 		}
 
 		// Republish vote if it is new and the node does not host a principal representative (or close to)
@@ -513,6 +548,12 @@ nano::vote_code nano::active_transactions::vote (std::shared_ptr<nano::vote> con
 	{
 		result = nano::vote_code::replay;
 	}
+
+	for (auto const & hash : vote_a->hashes ())
+	{
+		auto existing (hash_results.find (hash));
+		results_a.emplace_back (hash, existing != hash_results.end () ? existing->second : nano::vote_code::indeterminate);
+	}
 	return result;
 }
 
@@ -621,7 +662,7 @@ bool nano::active_transactions::publish (std::shared_ptr<nano::block> const & bl
 			lock.lock ();
 			blocks.emplace (block_a->hash (), election);
 			lock.unlock ();
-			if (auto const cache = node.inactive_vote_cache.find (block_a->hash ()); cache)
+			if (auto const cache = node.inactive_vote_cache.promote (block_a->hash ()); cache)
 			{
 				election->fill_from_cache (*cache);
 			}
@@ -719,10 +760,50 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (ac
 
 	composite->add_component (active_transactions.recently_confirmed.collect_container_info ("recently_confirmed"));
 	composite->add_component (active_transactions.recently_cemented.collect_container_info ("recently_cemented"));
+	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "broadcast_throttle", active_transactions.broadcast_throttle.size (), 0 }));
 
 	return composite;
 }
 
+/*
+ * class block_broadcast_throttle
+ */
+
+nano::block_broadcast_throttle::block_broadcast_throttle (std::chrono::milliseconds initial_delay, std::chrono::milliseconds interval) :
+	handle{ rsnano::rsn_block_broadcast_throttle_create (initial_delay.count (), interval.count ()) }
+{
+}
+
+nano::block_broadcast_throttle::~block_broadcast_throttle ()
+{
+	rsnano::rsn_block_broadcast_throttle_destroy (handle);
+}
+
+std::optional<nano::stat::detail> nano::block_broadcast_throttle::check (nano::qualified_root const & root, nano::block_hash const & winner, std::chrono::milliseconds election_age, bool confirmed) const
+{
+	uint8_t detail;
+	if (rsnano::rsn_block_broadcast_throttle_check (handle, root.bytes.data (), winner.bytes.data (), election_age.count (), confirmed, &detail))
+	{
+		return static_cast<nano::stat::detail> (detail);
+	}
+	return std::nullopt;
+}
+
+void nano::block_broadcast_throttle::flag (nano::qualified_root const & root, nano::block_hash const & winner, bool confirmed)
+{
+	rsnano::rsn_block_broadcast_throttle_flag (handle, root.bytes.data (), winner.bytes.data (), confirmed);
+}
+
+void nano::block_broadcast_throttle::erase (nano::qualified_root const & root)
+{
+	rsnano::rsn_block_broadcast_throttle_erase (handle, root.bytes.data ());
+}
+
+std::size_t nano::block_broadcast_throttle::size () const
+{
+	return rsnano::rsn_block_broadcast_throttle_len (handle);
+}
+
 /*
  * class recently_confirmed
  */
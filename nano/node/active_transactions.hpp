@@ -1,6 +1,7 @@
 #pragma once
 
 #include <nano/lib/numbers.hpp>
+#include <nano/lib/stats_enums.hpp>
 #include <nano/node/election.hpp>
 #include <nano/node/election_insertion_result.hpp>
 #include <nano/node/voting.hpp>
@@ -16,6 +17,7 @@
 #include <condition_variable>
 #include <deque>
 #include <memory>
+#include <optional>
 #include <unordered_map>
 
 namespace mi = boost::multi_index;
@@ -102,6 +104,24 @@ public: // Container info
 	std::unique_ptr<container_info_component> collect_container_info (std::string const &);
 };
 
+/*
+ * Limits how often the winning block of an election root is rebroadcast to peers
+ */
+class block_broadcast_throttle final
+{
+public:
+	block_broadcast_throttle (std::chrono::milliseconds initial_delay, std::chrono::milliseconds interval);
+	block_broadcast_throttle (block_broadcast_throttle const &) = delete;
+	~block_broadcast_throttle ();
+
+	/** Returns the reason for broadcasting the winner or an empty optional if the broadcast should be skipped */
+	std::optional<nano::stat::detail> check (nano::qualified_root const &, nano::block_hash const & winner, std::chrono::milliseconds election_age, bool confirmed) const;
+	void flag (nano::qualified_root const &, nano::block_hash const & winner, bool confirmed);
+	void erase (nano::qualified_root const &);
+	std::size_t size () const;
+	rsnano::BlockBroadcastThrottleHandle * handle;
+};
+
 /**
  * Core class for determining consensus
  * Holds all active blocks i.e. recently added blocks that need confirmation
@@ -149,6 +169,8 @@ public:
 	nano::election_insertion_result insert (std::shared_ptr<nano::block> const & block, nano::election_behavior behavior = nano::election_behavior::normal);
 	// Distinguishes replay votes, cannot be determined if the block is not in any election
 	nano::vote_code vote (std::shared_ptr<nano::vote> const &);
+	/** Also writes the result for each hash of the vote to \p results */
+	nano::vote_code vote (std::shared_ptr<nano::vote> const &, nano::vote_results & results);
 	// Is the root of this block in the roots container
 	bool active (nano::block const &) const;
 	bool active (nano::qualified_root const &) const;
@@ -211,6 +233,7 @@ private: // Dependencies
 public:
 	recently_confirmed_cache recently_confirmed;
 	recently_cemented_cache recently_cemented;
+	block_broadcast_throttle broadcast_throttle;
 
 	// TODO: This mutex is currently public because many tests access it
 	// TODO: This is bad. Remove the need to explicitly lock this from any code outside of this class
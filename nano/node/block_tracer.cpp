@@ -0,0 +1,35 @@
+#include <nano/lib/rsnano.hpp>
+#include <nano/lib/rsnanoutils.hpp>
+#include <nano/node/block_tracer.hpp>
+
+#include <boost/property_tree/ptree.hpp>
+
+nano::block_tracer::block_tracer (uint64_t sample_interval) :
+	handle{ rsnano::rsn_block_tracer_create (sample_interval) }
+{
+}
+
+nano::block_tracer::~block_tracer ()
+{
+	rsnano::rsn_block_tracer_destroy (handle);
+}
+
+bool nano::block_tracer::record (nano::block_hash const & hash_a, nano::block_trace_event event_a)
+{
+	return rsnano::rsn_block_tracer_record (handle, hash_a.bytes.data (), static_cast<uint8_t> (event_a));
+}
+
+std::optional<std::string> nano::block_tracer::summary (nano::block_hash const & hash_a)
+{
+	rsnano::StringDto dto;
+	if (!rsnano::rsn_block_tracer_summary (handle, hash_a.bytes.data (), &dto))
+	{
+		return std::nullopt;
+	}
+	return rsnano::convert_dto_to_string (dto);
+}
+
+void nano::block_tracer::serialize_json (boost::property_tree::ptree & tree_a)
+{
+	rsnano::rsn_block_tracer_serialize_json (handle, &tree_a);
+}
@@ -0,0 +1,50 @@
+#pragma once
+
+#include <nano/lib/numbers.hpp>
+
+#include <boost/property_tree/ptree_fwd.hpp>
+
+#include <cstdint>
+#include <optional>
+#include <string>
+
+namespace rsnano
+{
+class BlockTracerHandle;
+}
+
+namespace nano
+{
+// Has to match BlockTraceEvent in rsnano
+enum class block_trace_event : uint8_t
+{
+	first_seen,
+	validated,
+	inserted,
+	election_started,
+	quorum,
+	cemented,
+	broadcast,
+};
+
+// Records timestamped events for a sample of blocks, from the first time a block is seen until it is cemented.
+// Disabled unless the `block_trace_sample_interval` node flag is set.
+class block_tracer final
+{
+public:
+	explicit block_tracer (uint64_t sample_interval);
+	block_tracer (nano::block_tracer const &) = delete;
+	block_tracer (nano::block_tracer &&) = delete;
+	~block_tracer ();
+	nano::block_tracer & operator= (nano::block_tracer const &) = delete;
+	nano::block_tracer & operator= (nano::block_tracer &&) = delete;
+	// Returns true if the block is traced
+	bool record (nano::block_hash const &, nano::block_trace_event);
+	// One line description of the events of a traced block
+	std::optional<std::string> summary (nano::block_hash const &);
+	void serialize_json (boost::property_tree::ptree &);
+
+private:
+	rsnano::BlockTracerHandle * handle;
+};
+}
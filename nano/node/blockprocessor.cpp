@@ -46,9 +46,12 @@ nano::block_processor::block_processor (nano::node & node_a, nano::write_databas
 	store (node_a.store),
 	stats (*node_a.stats),
 	block_arrival (node_a.block_arrival),
+	confirmation_latency (node_a.confirmation_latency),
+	block_tracer (node_a.block_tracer),
 	unchecked (node_a.unchecked),
 	gap_cache (node_a.gap_cache),
-	write_database_queue (write_database_queue_a)
+	write_database_queue (write_database_queue_a),
+	resource_watchdog (node_a.resource_watchdog)
 {
 	blocks_rolled_back =
 	[&node_a] (std::vector<std::shared_ptr<nano::block>> const & rolled_back, std::shared_ptr<nano::block> const & initial_block) {
@@ -71,6 +74,11 @@ nano::block_processor::block_processor (nano::node & node_a, nano::write_databas
 		for (auto const & item : items)
 		{
 			auto const & [result, block] = item;
+			if (result.code == nano::process_result::progress)
+			{
+				// The write transaction of the batch is committed at this point
+				block_tracer.record (block->hash (), nano::block_trace_event::inserted);
+			}
 			processed.notify (result, block);
 		}
 	});
@@ -308,6 +316,8 @@ void nano::block_processor::process_verified_state_blocks (std::deque<nano::stat
 
 void nano::block_processor::add_impl (std::shared_ptr<nano::block> block)
 {
+	confirmation_latency.block_arrived (block->hash ());
+	block_tracer.record (block->hash (), nano::block_trace_event::first_seen);
 	if (block->type () == nano::block_type::state || block->type () == nano::block_type::open)
 	{
 		state_block_signature_verification.add ({ block });
@@ -335,6 +345,9 @@ auto nano::block_processor::process_batch (nano::block_processor_lock & lock_a)
 	auto deadline_reached = [&timer_l, deadline = config.block_processor_batch_max_time] { return timer_l.after_deadline (deadline); };
 	auto processor_batch_reached = [&number_of_blocks_processed, max = flags.block_processor_batch_size ()] { return number_of_blocks_processed >= max; };
 	auto store_batch_reached = [&number_of_blocks_processed, max = store.max_block_write_batch_num ()] { return number_of_blocks_processed >= max; };
+	// State blocks per account in this batch. Blocks above the limit are deferred to the next batch
+	std::unordered_map<nano::account, std::size_t> blocks_per_account;
+	std::deque<std::shared_ptr<nano::block>> deferred;
 	while (have_blocks_ready () && (!deadline_reached () || !processor_batch_reached ()) && !store_batch_reached ())
 	{
 		if ((blocks.size () + state_block_signature_verification.size () + forced.size () > 64) && should_log ())
@@ -348,6 +361,13 @@ auto nano::block_processor::process_batch (nano::block_processor_lock & lock_a)
 		{
 			block = blocks.front ();
 			blocks.pop_front ();
+			if (block->type () == nano::block_type::state && ++blocks_per_account[block->account ()] > config.max_blocks_per_account_per_batch)
+			{
+				// Not reported to the batch observers, the block gets its final result in a later batch
+				stats.inc (nano::stat::type::blockprocessor, nano::to_stat_detail (nano::process_result::account_batch_limit));
+				deferred.push_back (block);
+				continue;
+			}
 			hash = block->hash ();
 		}
 		else
@@ -368,6 +388,8 @@ auto nano::block_processor::process_batch (nano::block_processor_lock & lock_a)
 		processed.emplace_back (result, block);
 		lock_a.lock (handle);
 	}
+	// Deferred blocks keep their order and are processed first in the next batch
+	blocks.insert (blocks.begin (), deferred.begin (), deferred.end ());
 	lock_a.unlock ();
 
 	if (config.logging.timing_logging () && number_of_blocks_processed != 0 && timer_l.stop () > std::chrono::milliseconds (100))
@@ -386,6 +408,7 @@ nano::process_return nano::block_processor::process_one (nano::write_transaction
 	{
 		case nano::process_result::progress:
 		{
+			block_tracer.record (hash, nano::block_trace_event::validated);
 			if (config.logging.ledger_logging ())
 			{
 				std::string block_string;
@@ -410,7 +433,7 @@ nano::process_return nano::block_processor::process_one (nano::write_transaction
 			{
 				logger.try_log (boost::str (boost::format ("Gap previous for: %1%") % hash.to_string ()));
 			}
-			unchecked.put (block->previous (), block);
+			put_unchecked (block->previous (), block);
 			stats.inc (nano::stat::type::ledger, nano::stat::detail::gap_previous);
 			break;
 		}
@@ -420,7 +443,7 @@ nano::process_return nano::block_processor::process_one (nano::write_transaction
 			{
 				logger.try_log (boost::str (boost::format ("Gap source for: %1%") % hash.to_string ()));
 			}
-			unchecked.put (ledger.block_source (transaction_a, *block), block);
+			put_unchecked (ledger.block_source (transaction_a, *block), block);
 			stats.inc (nano::stat::type::ledger, nano::stat::detail::gap_source);
 			break;
 		}
@@ -430,7 +453,7 @@ nano::process_return nano::block_processor::process_one (nano::write_transaction
 			{
 				logger.try_log (boost::str (boost::format ("Gap pending entries for epoch open: %1%") % hash.to_string ()));
 			}
-			unchecked.put (block->account (), block); // Specific unchecked key starting with epoch open block account public key
+			put_unchecked (block->account (), block); // Specific unchecked key starting with epoch open block account public key
 			stats.inc (nano::stat::type::ledger, nano::stat::detail::gap_source);
 			break;
 		}
@@ -516,9 +539,35 @@ nano::process_return nano::block_processor::process_one (nano::write_transaction
 			}
 			break;
 		}
+		case nano::process_result::height_overflow:
+		{
+			if (config.logging.ledger_logging ())
+			{
+				logger.try_log (boost::str (boost::format ("Height overflow for %1%") % hash.to_string ()));
+			}
+			break;
+		}
+		case nano::process_result::timestamp_in_future:
+		{
+			if (config.logging.ledger_logging ())
+			{
+				logger.try_log (boost::str (boost::format ("Timestamp of %1% is too far in the future") % hash.to_string ()));
+			}
+			break;
+		}
+		case nano::process_result::confirmed_fork:
+		{
+			stats.inc (nano::stat::type::ledger, nano::stat::detail::confirmed_fork);
+			if (config.logging.ledger_logging ())
+			{
+				logger.try_log (boost::str (boost::format ("Rejecting fork of confirmed block: %1% root: %2%") % hash.to_string () % block->root ().to_string ()));
+			}
+			break;
+		}
 	}
 
 	stats.inc (nano::stat::type::blockprocessor, nano::to_stat_detail (result.code));
+	dead_letters.record (hash, block->account (), result.code);
 
 	return result;
 }
@@ -529,6 +578,17 @@ void nano::block_processor::queue_unchecked (nano::write_transaction const & tra
 	gap_cache.erase (hash_or_account_a.hash);
 }
 
+void nano::block_processor::put_unchecked (nano::hash_or_account const & dependency, std::shared_ptr<nano::block> const & block)
+{
+	if (resource_watchdog.writes_paused ())
+	{
+		resource_watchdog.write_dropped ();
+		stats.inc (nano::stat::type::unchecked, nano::stat::detail::drop);
+		return;
+	}
+	unchecked.put (dependency, block);
+}
+
 std::unique_ptr<nano::container_info_component> nano::collect_container_info (block_processor & block_processor, std::string const & name)
 {
 	std::size_t blocks_count;
@@ -544,5 +604,6 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (bl
 	composite->add_component (collect_container_info (block_processor.state_block_signature_verification, "state_block_signature_verification"));
 	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "blocks", blocks_count, sizeof (decltype (block_processor.blocks)::value_type) }));
 	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "forced", forced_count, sizeof (decltype (block_processor.forced)::value_type) }));
+	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "dead_letters", block_processor.dead_letters.size (), sizeof (nano::block_hash) }));
 	return composite;
 }
@@ -4,6 +4,7 @@
 
 #include <nano/lib/blocks.hpp>
 #include <nano/node/blocking_observer.hpp>
+#include <nano/node/dead_letter_queue.hpp>
 #include <nano/node/state_block_signature_verification.hpp>
 #include <nano/secure/common.hpp>
 
@@ -20,6 +21,7 @@ class read_transaction;
 class transaction;
 class write_transaction;
 class write_database_queue;
+class resource_watchdog;
 class node_config;
 class ledger;
 class node_flags;
@@ -29,6 +31,8 @@ class local_vote_history;
 class active_transactions;
 class election_scheduler;
 class block_arrival;
+class confirmation_latency;
+class block_tracer;
 class unchecked_map;
 class gap_cache;
 class bootstrap_initiator;
@@ -69,6 +73,8 @@ public:
 
 	std::atomic<bool> flushing{ false };
 	rsnano::BlockProcessorHandle const * get_handle () const;
+	// Blocks which repeatedly failed with gaps or forks
+	nano::dead_letter_queue dead_letters;
 
 public: // Events
 	using processed_t = std::pair<nano::process_return, std::shared_ptr<nano::block>>;
@@ -85,6 +91,8 @@ private:
 	void rollback_competitor (nano::write_transaction const & transaction, nano::block const & block);
 	nano::process_return process_one (nano::write_transaction const &, std::shared_ptr<nano::block> block, bool const = false);
 	void queue_unchecked (nano::write_transaction const &, nano::hash_or_account const &);
+	/** Stores the block as unchecked unless the resource watchdog paused writes, in which case it is dropped and counted */
+	void put_unchecked (nano::hash_or_account const & dependency, std::shared_ptr<nano::block> const & block);
 	std::deque<processed_t> process_batch (nano::block_processor_lock &);
 	void process_verified_state_blocks (std::deque<nano::state_block_signature_verification::value_type> &, std::vector<int> const &, std::vector<nano::block_hash> const &, std::vector<nano::signature> const &);
 	void add_impl (std::shared_ptr<nano::block> block);
@@ -100,6 +108,8 @@ private:
 	nano::state_block_signature_verification state_block_signature_verification; // already ported
 	nano::network_params & network_params; // already ported
 	nano::block_arrival & block_arrival; // already ported
+	nano::confirmation_latency & confirmation_latency;
+	nano::block_tracer & block_tracer;
 
 public:
 	rsnano::BlockProcessorHandle * handle;
@@ -112,6 +122,7 @@ private:
 	nano::unchecked_map & unchecked; // already ported
 	nano::gap_cache & gap_cache; // already ported
 	nano::write_database_queue & write_database_queue; // already ported
+	nano::resource_watchdog & resource_watchdog;
 	std::thread processing_thread;
 	std::function<void (std::vector<std::shared_ptr<nano::block>> const &, std::shared_ptr<nano::block> const &)> blocks_rolled_back;
 
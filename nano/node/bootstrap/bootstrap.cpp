@@ -10,6 +10,7 @@
 
 nano::bootstrap_initiator::bootstrap_initiator (nano::node & node_a) :
 	node (node_a),
+	trigger_filter{ rsnano::rsn_bootstrap_trigger_filter_create (nano::bootstrap_limits::bootstrap_trigger_window_sec, nano::bootstrap_limits::bootstrap_trigger_filter_size) },
 	handle{ rsnano::rsn_bootstrap_initiator_create (this) }
 {
 	connections = std::make_shared<nano::bootstrap_connections> (node);
@@ -29,6 +30,7 @@ nano::bootstrap_initiator::bootstrap_initiator (nano::node & node_a) :
 nano::bootstrap_initiator::~bootstrap_initiator ()
 {
 	stop ();
+	rsnano::rsn_bootstrap_trigger_filter_destroy (trigger_filter);
 	rsnano::rsn_bootstrap_initiator_destroy (handle);
 }
 
@@ -78,6 +80,10 @@ void nano::bootstrap_initiator::bootstrap (nano::endpoint const & endpoint_a, bo
 bool nano::bootstrap_initiator::bootstrap_lazy (nano::hash_or_account const & hash_or_account_a, bool force, std::string id_a)
 {
 	bool key_inserted (false);
+	if (!force && !rsnano::rsn_bootstrap_trigger_filter_check (trigger_filter, hash_or_account_a.bytes.data ()))
+	{
+		return key_inserted;
+	}
 	auto lazy_attempt (current_lazy_attempt ());
 	if (lazy_attempt == nullptr || force)
 	{
@@ -263,6 +269,11 @@ void nano::bootstrap_initiator::clear_pulls (uint64_t bootstrap_id_a)
 	connections->clear_pulls (bootstrap_id_a);
 }
 
+uint64_t nano::bootstrap_initiator::deduplicated_triggers () const
+{
+	return rsnano::rsn_bootstrap_trigger_filter_deduplicated (trigger_filter);
+}
+
 rsnano::BootstrapInitiatorHandle * nano::bootstrap_initiator::get_handle () const
 {
 	return handle;
@@ -292,6 +303,7 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (bo
 	auto sizeof_cache_element = pulls_cache::element_size ();
 	auto composite = std::make_unique<container_info_composite> (name);
 	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "pulls_cache", cache_count, sizeof_cache_element }));
+	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "trigger_filter", rsnano::rsn_bootstrap_trigger_filter_size (bootstrap_initiator.trigger_filter), sizeof (nano::hash_or_account) }));
 	return composite;
 }
 
@@ -112,6 +112,8 @@ public:
 	std::shared_ptr<nano::bootstrap_attempt_lazy> current_lazy_attempt ();
 	std::shared_ptr<nano::bootstrap_attempt_wallet> current_wallet_attempt ();
 	void clear_pulls (uint64_t bootstrap_id_a);
+	/** Number of lazy bootstrap triggers which were ignored because the same hash or account was triggered recently */
+	uint64_t deduplicated_triggers () const;
 	rsnano::BootstrapInitiatorHandle * get_handle () const;
 	nano::pulls_cache cache;
 	nano::bootstrap_attempts attempts;
@@ -126,6 +128,7 @@ private:
 	nano::mutex mutex;
 	nano::condition_variable condition;
 	std::vector<boost::thread> bootstrap_initiator_threads;
+	rsnano::BootstrapTriggerFilterHandle * trigger_filter;
 
 public:
 	rsnano::BootstrapInitiatorHandle * handle;
@@ -151,10 +154,12 @@ public:
 	static constexpr unsigned requeued_pulls_limit = 256;
 	static constexpr unsigned requeued_pulls_limit_dev = 1;
 	static constexpr unsigned requeued_pulls_processed_blocks_factor = 4096;
-	static constexpr unsigned bulk_push_cost_limit = 200;
 	static constexpr std::chrono::seconds lazy_flush_delay_sec = std::chrono::seconds (5);
 	static constexpr uint64_t lazy_batch_pull_count_resize_blocks_limit = 4 * 1024 * 1024;
 	static constexpr double lazy_batch_pull_count_resize_ratio = 2.0;
 	static constexpr std::size_t lazy_blocks_restart_limit = 1024 * 1024;
+	static constexpr unsigned bootstrap_max_pulls_per_attempt = 30000;
+	static constexpr unsigned bootstrap_trigger_window_sec = 30;
+	static constexpr std::size_t bootstrap_trigger_filter_size = 16 * 1024;
 };
 }
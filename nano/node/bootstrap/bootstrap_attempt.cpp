@@ -9,6 +9,7 @@
 
 constexpr unsigned nano::bootstrap_limits::requeued_pulls_limit;
 constexpr unsigned nano::bootstrap_limits::requeued_pulls_limit_dev;
+constexpr unsigned nano::bootstrap_limits::bootstrap_max_pulls_per_attempt;
 
 nano::bootstrap_attempt::bootstrap_attempt (std::shared_ptr<nano::node> const & node_a, nano::bootstrap_mode mode_a, uint64_t incremental_id_a, std::string id_a) :
 	handle (rsnano::rsn_bootstrap_attempt_create (nano::to_logger_handle (node_a->logger), node_a->websocket.server.get (), node_a->block_processor.get_handle (), node_a->bootstrap_initiator.get_handle (), node_a->ledger.get_handle (), id_a.c_str (), static_cast<uint8_t> (mode_a), incremental_id_a))
@@ -145,6 +146,14 @@ std::string nano::bootstrap_attempt::mode_text ()
 	return mode_text;
 }
 
+std::string nano::bootstrap_attempt::state_text ()
+{
+	std::size_t len;
+	auto ptr{ rsnano::rsn_bootstrap_attempt_state_text (handle, &len) };
+	std::string state_text (ptr, len);
+	return state_text;
+}
+
 bool nano::bootstrap_attempt::process_block (std::shared_ptr<nano::block> const & block_a, nano::account const & known_account_a, uint64_t pull_blocks_processed, nano::bulk_pull::count_t max_blocks, bool block_expected, unsigned retry_limit)
 {
 	return rsnano::rsn_bootstrap_attempt_process_block (handle, block_a->get_handle (), known_account_a.bytes.data (), pull_blocks_processed, max_blocks, block_expected, retry_limit);
@@ -28,6 +28,8 @@ public:
 	void pull_finished ();
 	bool should_log ();
 	std::string mode_text ();
+	/** "queued", "running" or "stopped" */
+	std::string state_text ();
 	virtual bool process_block (std::shared_ptr<nano::block> const &, nano::account const &, uint64_t, nano::bulk_pull::count_t, bool, unsigned);
 	virtual void get_information (boost::property_tree::ptree &) = 0;
 	virtual void block_processed (nano::transaction const & tx, nano::process_return const & result, nano::block const & block);
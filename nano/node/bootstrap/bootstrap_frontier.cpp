@@ -13,7 +13,6 @@
 constexpr double nano::bootstrap_limits::bootstrap_connection_warmup_time_sec;
 constexpr double nano::bootstrap_limits::bootstrap_minimum_elapsed_seconds_blockrate;
 constexpr double nano::bootstrap_limits::bootstrap_minimum_frontier_blocks_per_sec;
-constexpr unsigned nano::bootstrap_limits::bulk_push_cost_limit;
 
 constexpr std::size_t nano::frontier_req_client::size_frontier;
 
@@ -28,10 +27,11 @@ void nano::frontier_req_client::run (nano::account const & start_account_a, uint
 	request.set_start ((start_account_a.is_zero () || start_account_a.number () == std::numeric_limits<nano::uint256_t>::max ()) ? start_account_a : start_account_a.number () + 1);
 	request.set_age (frontiers_age_a);
 	request.set_count (count_a);
-	current = start_account_a;
 	frontiers_age = frontiers_age_a;
 	count_limit = count_a;
-	next (); // Load accounts from disk
+	// Bulk pushing is only effective if the peer sends all of its frontiers
+	auto bulk_push_enabled = frontiers_age == std::numeric_limits<decltype (frontiers_age)>::max ();
+	comparer = rsnano::rsn_frontier_comparer_create (start_account_a.bytes.data (), bulk_push_enabled, node->ledger.get_handle ());
 	auto this_l (shared_from_this ());
 	connection->send (
 	request, [this_l] (boost::system::error_code const & ec, std::size_t size_a) {
@@ -59,11 +59,18 @@ nano::frontier_req_client::frontier_req_client (std::shared_ptr<nano::node> cons
 	node_weak (node_a),
 	connection (connection_a),
 	attempt (attempt_a),
-	count (0),
-	bulk_push_cost (0)
+	count (0)
 {
 }
 
+nano::frontier_req_client::~frontier_req_client ()
+{
+	if (comparer != nullptr)
+	{
+		rsnano::rsn_frontier_comparer_destroy (comparer);
+	}
+}
+
 void nano::frontier_req_client::receive_frontier ()
 {
 	auto this_l (shared_from_this ());
@@ -89,27 +96,25 @@ void nano::frontier_req_client::receive_frontier ()
 	});
 }
 
-bool nano::frontier_req_client::bulk_push_available ()
+void nano::frontier_req_client::dispatch_work (nano::bootstrap_attempt_legacy & attempt_a)
 {
-	return bulk_push_cost < nano::bootstrap_limits::bulk_push_cost_limit && frontiers_age == std::numeric_limits<decltype (frontiers_age)>::max ();
-}
-
-void nano::frontier_req_client::unsynced (nano::block_hash const & head, nano::block_hash const & end)
-{
-	auto attempt_l = attempt.lock ();
-	if (!attempt_l)
+	auto node = node_weak.lock ();
+	if (!node)
+	{
 		return;
-
-	if (bulk_push_available ())
+	}
+	rsnano::FrontierWorkItemDto item;
+	while (rsnano::rsn_frontier_comparer_pop_work (comparer, &item))
 	{
-		attempt_l->add_bulk_push_target (head, end);
-		if (end.is_zero ())
+		auto head (nano::block_hash::from_bytes (item.head));
+		auto end (nano::block_hash::from_bytes (item.end));
+		if (item.is_pull)
 		{
-			bulk_push_cost += 2;
+			attempt_a.add_frontier (nano::pull_info (nano::account::from_bytes (item.account), head, end, attempt_a.get_incremental_id (), 0, node->network_params.bootstrap.frontier_retry_limit));
 		}
 		else
 		{
-			bulk_push_cost += 1;
+			attempt_a.add_bulk_push_target (head, end);
 		}
 	}
 }
@@ -160,64 +165,24 @@ void nano::frontier_req_client::received_frontier (boost::system::error_code con
 		if (!account.is_zero () && count <= count_limit)
 		{
 			last_account = account;
-			while (!current.is_zero () && current < account)
-			{
-				// We know about an account they don't.
-				unsynced (frontier, 0);
-				next ();
-			}
-			if (!current.is_zero ())
-			{
-				if (account == current)
-				{
-					if (latest == frontier)
-					{
-						// In sync
-					}
-					else
-					{
-						if (node->ledger.block_or_pruned_exists (latest))
-						{
-							// We know about a block they don't.
-							unsynced (frontier, latest);
-						}
-						else
-						{
-							attempt_l->add_frontier (nano::pull_info (account, latest, frontier, attempt_l->get_incremental_id (), 0, node->network_params.bootstrap.frontier_retry_limit));
-							// Either we're behind or there's a fork we differ on
-							// Either way, bulk pushing will probably not be effective
-							bulk_push_cost += 5;
-						}
-					}
-					next ();
-				}
-				else
-				{
-					debug_assert (account < current);
-					attempt_l->add_frontier (nano::pull_info (account, latest, nano::block_hash (0), attempt_l->get_incremental_id (), 0, node->network_params.bootstrap.frontier_retry_limit));
-				}
-			}
-			else
-			{
-				attempt_l->add_frontier (nano::pull_info (account, latest, nano::block_hash (0), attempt_l->get_incremental_id (), 0, node->network_params.bootstrap.frontier_retry_limit));
-			}
+			rsnano::rsn_frontier_comparer_compare (comparer, account.bytes.data (), latest.bytes.data ());
+			dispatch_work (*attempt_l);
 			receive_frontier ();
 		}
 		else
 		{
 			if (count <= count_limit)
 			{
-				while (!current.is_zero () && bulk_push_available ())
-				{
-					// We know about an account they don't.
-					unsynced (frontier, 0);
-					next ();
-				}
+				// Push the accounts they don't know about
+				rsnano::rsn_frontier_comparer_finish (comparer);
+				dispatch_work (*attempt_l);
 				// Prevent new frontier_req requests
 				attempt_l->set_start_account (std::numeric_limits<nano::uint256_t>::max ());
 				if (node->config->logging.bulk_pull_logging ())
 				{
-					node->logger->try_log ("Bulk push cost: ", bulk_push_cost);
+					rsnano::FrontierComparerStatsDto stats;
+					rsnano::rsn_frontier_comparer_stats (comparer, &stats);
+					node->logger->try_log (boost::str (boost::format ("Frontier comparison: %1% received, %2% in sync, %3% behind, %4% ahead, bulk push cost: %5%") % stats.received % stats.in_sync % stats.behind % stats.ahead % rsnano::rsn_frontier_comparer_bulk_push_cost (comparer)));
 				}
 			}
 			else
@@ -244,38 +209,6 @@ void nano::frontier_req_client::received_frontier (boost::system::error_code con
 	}
 }
 
-void nano::frontier_req_client::next ()
-{
-	auto node = node_weak.lock ();
-	if (!node || node->is_stopped ())
-	{
-		return;
-	}
-	// Filling accounts deque to prevent often read transactions
-	if (accounts.empty ())
-	{
-		std::size_t max_size (128);
-		auto transaction (node->store.tx_begin_read ());
-		for (auto i (node->store.account ().begin (*transaction, current.number () + 1)), n (node->store.account ().end ()); i != n && accounts.size () != max_size; ++i)
-		{
-			nano::account_info const & info (i->second);
-			nano::account const & account (i->first);
-			accounts.emplace_back (account, info.head ());
-		}
-
-		/* If loop breaks before max_size, then accounts_end () is reached. Add empty record */
-		if (accounts.size () != max_size)
-		{
-			accounts.emplace_back (nano::account{}, nano::block_hash (0));
-		}
-	}
-	// Retrieving accounts from deque
-	auto const & account_pair (accounts.front ());
-	current = account_pair.first;
-	frontier = account_pair.second;
-	accounts.pop_front ();
-}
-
 //------------------------------------------------------------------------------
 // frontier_req_server
 //------------------------------------------------------------------------------
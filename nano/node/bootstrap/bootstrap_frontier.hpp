@@ -25,24 +25,22 @@ class frontier_req_client final : public std::enable_shared_from_this<nano::fron
 {
 public:
 	explicit frontier_req_client (std::shared_ptr<nano::node> const &, std::shared_ptr<nano::bootstrap_client> const &, std::shared_ptr<nano::bootstrap_attempt_legacy> const &);
+	frontier_req_client (frontier_req_client const &) = delete;
+	~frontier_req_client ();
 	void run (nano::account const & start_account_a, uint32_t const frontiers_age_a, uint32_t const count_a);
 	void receive_frontier ();
 	void received_frontier (boost::system::error_code const &, std::size_t);
-	bool bulk_push_available ();
-	void unsynced (nano::block_hash const &, nano::block_hash const &);
-	void next ();
+	/** Hands the pulls and bulk push targets found by the frontier comparison over to the attempt */
+	void dispatch_work (nano::bootstrap_attempt_legacy &);
 	std::weak_ptr<nano::node> node_weak;
 	std::shared_ptr<nano::bootstrap_client> connection;
 	std::weak_ptr<nano::bootstrap_attempt_legacy> attempt;
-	nano::account current;
-	nano::block_hash frontier;
 	unsigned count;
 	nano::account last_account{ std::numeric_limits<nano::uint256_t>::max () }; // Using last possible account stop further frontier requests
 	std::chrono::steady_clock::time_point start_time;
 	std::promise<bool> promise;
-	/** A very rough estimate of the cost of `bulk_push`ing missing blocks */
-	uint64_t bulk_push_cost;
-	std::deque<std::pair<nano::account, nano::block_hash>> accounts;
+	/** Compares the received frontiers with the local ones */
+	rsnano::FrontierComparerHandle * comparer{ nullptr };
 	uint32_t frontiers_age{ std::numeric_limits<uint32_t>::max () };
 	uint32_t count_limit{ std::numeric_limits<uint32_t>::max () };
 	static std::size_t constexpr size_frontier = sizeof (nano::account) + sizeof (nano::block_hash);
@@ -10,13 +10,6 @@
 namespace nano
 {
 class node;
-class lazy_state_backlog_item final
-{
-public:
-	nano::link link{ 0 };
-	nano::uint128_t balance{ 0 };
-	unsigned retry_limit{ 0 };
-};
 
 /**
  * Lazy bootstrap session. Started with a block hash, this will "trace down" the blocks obtained to find a connection to the ledger.
@@ -38,28 +31,15 @@ public:
 	uint32_t lazy_batch_size ();
 	rsnano::BootstrapAttemptLockHandle * lazy_pull_flush (rsnano::BootstrapAttemptLockHandle * lock_a);
 	bool process_block_lazy (std::shared_ptr<nano::block> const &, nano::account const &, uint64_t, nano::bulk_pull::count_t, unsigned);
-	void lazy_block_state (std::shared_ptr<nano::block> const &, unsigned);
-	void lazy_block_state_backlog_check (std::shared_ptr<nano::block> const &, nano::block_hash const &);
 	void lazy_backlog_cleanup ();
-	void lazy_blocks_insert (nano::block_hash const &);
-	void lazy_blocks_erase (nano::block_hash const &);
 	bool lazy_blocks_processed (nano::block_hash const &);
 	bool lazy_processed_or_exists (nano::block_hash const &);
+	std::size_t lazy_blocks_count () const;
 	void get_information (boost::property_tree::ptree &) override;
 
 private:
 	std::weak_ptr<nano::node> node_weak;
-	std::unordered_set<std::size_t> lazy_blocks;
-	std::unordered_map<nano::block_hash, nano::lazy_state_backlog_item> lazy_state_backlog;
-	std::unordered_set<nano::block_hash> lazy_undefined_links;
-	std::unordered_map<nano::block_hash, nano::uint128_t> lazy_balances;
-	std::unordered_set<nano::block_hash> lazy_keys;
-	std::deque<std::pair<nano::hash_or_account, unsigned>> lazy_pulls;
 	std::chrono::steady_clock::time_point lazy_start_time;
-	std::atomic<std::size_t> lazy_blocks_count{ 0 };
-	std::size_t peer_count{ 0 };
-	/** The maximum number of records to be read in while iterating over long lazy containers */
-	static uint64_t constexpr batch_read_size = 256;
 };
 
 /**
@@ -183,6 +183,12 @@ bool nano::bootstrap_attempt_legacy::request_frontier (rsnano::BootstrapAttemptL
 			// Add to regular pulls
 			while (!frontier_pulls.empty ())
 			{
+				// Don't flood the connections with more pulls than they can serve
+				rsnano::rsn_bootstrap_attempt_wait_for_pull_slot (handle, *lock_a, nano::bootstrap_limits::bootstrap_max_pulls_per_attempt);
+				if (get_stopped ())
+				{
+					break;
+				}
 				auto pull (frontier_pulls.front ());
 				rsnano::rsn_bootstrap_attempt_unlock (*lock_a);
 				node->bootstrap_initiator.connections->add_pull (pull);
@@ -2,6 +2,7 @@
 
 #include <nano/lib/observer_set.hpp>
 #include <nano/lib/processing_queue.hpp>
+#include <nano/lib/rsnano.hpp>
 #include <nano/node/messages.hpp>
 
 #include <memory>
@@ -46,38 +47,20 @@ public: // Events
 
 private:
 	void process_batch (std::deque<request_t> & batch);
-	nano::asc_pull_ack process (nano::transaction const &, nano::asc_pull_req const & message);
 	void respond (nano::asc_pull_ack &, std::shared_ptr<nano::transport::channel> &);
 
-	nano::asc_pull_ack process (nano::transaction const &, nano::asc_pull_req::id_t id, nano::empty_payload const & request);
-
-	/*
-	 * Blocks response
-	 */
-	nano::asc_pull_ack process (nano::transaction const &, nano::asc_pull_req::id_t id, nano::asc_pull_req::blocks_payload const & request);
-	nano::asc_pull_ack prepare_response (nano::transaction const &, nano::asc_pull_req::id_t id, nano::block_hash start_block, std::size_t count);
-	nano::asc_pull_ack prepare_empty_blocks_response (nano::asc_pull_req::id_t id);
-	std::vector<std::shared_ptr<nano::block>> prepare_blocks (nano::transaction const &, nano::block_hash start_block, std::size_t count) const;
-
-	/*
-	 * Account info response
-	 */
-	nano::asc_pull_ack process (nano::transaction const &, nano::asc_pull_req::id_t id, nano::asc_pull_req::account_info_payload const & request);
-
 	/*
 	 * Checks if the request should be dropped early on
 	 */
 	bool verify (nano::asc_pull_req const & message) const;
-	bool verify_request_type (nano::asc_pull_type) const;
 
 private: // Dependencies
 	nano::store & store;
-	nano::ledger & ledger;
-	nano::network_constants const & network_constants;
 	nano::stats & stats;
 
 private:
 	processing_queue<request_t> request_queue;
+	rsnano::AscendingBootstrapServerHandle * handle;
 
 public: // Config
 	/** Maximum number of blocks to send in a single response, cannot be higher than capacity of a single `asc_pull_ack` message */
@@ -1,24 +1,19 @@
 #pragma once
 
 #include <nano/lib/numbers.hpp>
+#include <nano/lib/rsnano.hpp>
 #include <nano/node/bootstrap/bootstrap_config.hpp>
-#include <nano/node/bootstrap_ascending/common.hpp>
 
-#include <boost/multi_index/hashed_index.hpp>
-#include <boost/multi_index/mem_fun.hpp>
-#include <boost/multi_index/member.hpp>
-#include <boost/multi_index/ordered_index.hpp>
-#include <boost/multi_index/random_access_index.hpp>
-#include <boost/multi_index/sequenced_index.hpp>
-#include <boost/multi_index_container.hpp>
-
-#include <random>
-
-namespace mi = boost::multi_index;
+#include <memory>
+#include <optional>
+#include <string>
+#include <tuple>
+#include <vector>
 
 namespace nano
 {
 class stats;
+class container_info_component;
 
 namespace bootstrap_ascending
 {
@@ -27,6 +22,8 @@ namespace bootstrap_ascending
 	{
 	public:
 		explicit account_sets (nano::stats &, nano::account_sets_config config = {});
+		account_sets (account_sets const &) = delete;
+		~account_sets ();
 
 		/**
 		 * If an account is not blocked, increase its priority.
@@ -59,84 +56,30 @@ namespace bootstrap_ascending
 		std::unique_ptr<nano::container_info_component> collect_container_info (std::string const & name);
 
 	private:
-		void trim_overflow ();
-		bool check_timestamp (nano::account const & account) const;
+		rsnano::AccountSetsHandle * handle;
 
-	private: // Dependencies
-		nano::stats & stats;
+	public: // Consts
+		static float constexpr priority_initial = 8.0f;
+		static float constexpr priority_increase = 2.0f;
+		static float constexpr priority_decrease = 0.5f;
+		static float constexpr priority_max = 32.0f;
+		static float constexpr priority_cutoff = 1.0f;
 
-	private:
+	public:
 		struct priority_entry
 		{
 			nano::account account{ 0 };
 			float priority{ 0 };
-			nano::millis_t timestamp{ 0 };
-			nano::bootstrap_ascending::id_t id{ 0 }; // Uniformly distributed, used for random querying
-
-			priority_entry (nano::account account, float priority);
 		};
 
 		struct blocking_entry
 		{
 			nano::account account{ 0 };
 			nano::block_hash dependency{ 0 };
-			priority_entry original_entry{ 0, 0 };
-
-			float priority () const
-			{
-				return original_entry.priority;
-			}
 		};
 
-		// clang-format off
-		class tag_account {};
-		class tag_priority {};
-		class tag_sequenced {};
-		class tag_id {};
-
-		// Tracks the ongoing account priorities
-		// This only stores account priorities > 1.0f.
-		using ordered_priorities = boost::multi_index_container<priority_entry,
-		mi::indexed_by<
-			mi::sequenced<mi::tag<tag_sequenced>>,
-			mi::ordered_unique<mi::tag<tag_account>,
-				mi::member<priority_entry, nano::account, &priority_entry::account>>,
-			mi::ordered_non_unique<mi::tag<tag_priority>,
-				mi::member<priority_entry, float, &priority_entry::priority>>,
-			mi::ordered_unique<mi::tag<tag_id>,
-				mi::member<priority_entry, nano::bootstrap_ascending::id_t, &priority_entry::id>>
-		>>;
-
-		// A blocked account is an account that has failed to insert a new block because the source block is not currently present in the ledger
-		// An account is unblocked once it has a block successfully inserted
-		using ordered_blocking = boost::multi_index_container<blocking_entry,
-		mi::indexed_by<
-			mi::sequenced<mi::tag<tag_sequenced>>,
-			mi::ordered_unique<mi::tag<tag_account>,
-				mi::member<blocking_entry, nano::account, &blocking_entry::account>>,
-			mi::ordered_non_unique<mi::tag<tag_priority>,
-				mi::const_mem_fun<blocking_entry, float, &blocking_entry::priority>>
-		>>;
-		// clang-format on
-
-		ordered_priorities priorities;
-		ordered_blocking blocking;
-
-		std::default_random_engine rng;
-
-	private:
-		nano::account_sets_config config;
-
-	public: // Consts
-		static float constexpr priority_initial = 8.0f;
-		static float constexpr priority_increase = 2.0f;
-		static float constexpr priority_decrease = 0.5f;
-		static float constexpr priority_max = 32.0f;
-		static float constexpr priority_cutoff = 1.0f;
-
-	public:
-		using info_t = std::tuple<decltype (blocking), decltype (priorities)>; // <blocking, priorities>
+		using info_t = std::tuple<std::vector<blocking_entry>, std::vector<priority_entry>>; // <blocking, priorities>
 		info_t info () const;
 	};
 } // bootstrap_ascending
-} // nano
\ No newline at end of file
+} // nano
@@ -382,9 +382,15 @@ void nano::bootstrap_ascending::service::process (const nano::asc_pull_ack::bloc
 		{
 			stats.add (nano::stat::type::bootstrap_ascending, nano::stat::detail::blocks, nano::stat::dir::in, response.blocks.size ());
 
-			for (auto & block : response.blocks)
+			// Validate the chain in memory first, so that the block processor only gets the part which can be inserted
+			auto const valid = ledger.valid_chain_prefix (*ledger.store.tx_begin_read (), response.blocks);
+			if (valid < response.blocks.size ())
 			{
-				block_processor.add (block);
+				stats.add (nano::stat::type::bootstrap_ascending, nano::stat::detail::invalid_blocks, nano::stat::dir::in, response.blocks.size () - valid);
+			}
+			for (std::size_t i = 0; i < valid; ++i)
+			{
+				block_processor.add (response.blocks[i]);
 			}
 			nano::lock_guard<nano::mutex> lock{ mutex };
 			throttle.add (true);
@@ -6,7 +6,12 @@
 #include <nano/node/daemonconfig.hpp>
 #include <nano/node/node.hpp>
 
+#include <boost/asio/connect.hpp>
+#include <boost/asio/ip/tcp.hpp>
+#include <boost/beast/core/flat_buffer.hpp>
+#include <boost/beast/http.hpp>
 #include <boost/format.hpp>
+#include <boost/property_tree/json_parser.hpp>
 
 namespace
 {
@@ -48,6 +53,7 @@ void nano::add_node_options (boost::program_options::options_description & descr
 	("snapshot", "Compact database and create snapshot, functions similar to vacuum but does not replace the existing database")
 	("data_path", boost::program_options::value<std::string> (), "Use the supplied path as the data directory")
 	("network", boost::program_options::value<std::string> (), "Use the supplied network (live, test, beta or dev)")
+	("network_definition", boost::program_options::value<std::string> (), "Run a private network with the genesis block, epoch signers and work thresholds of the supplied TOML or JSON network definition file")
 	("clear_send_ids", "Remove all send IDs from the database (dangerous: not intended for production use)")
 	("online_weight_clear", "Clear online weight history records")
 	("peer_clear", "Clear online peers database dump")
@@ -69,6 +75,7 @@ void nano::add_node_options (boost::program_options::options_description & descr
 	("wallet_remove", "Remove <account> from <wallet>")
 	("wallet_representative_get", "Prints default representative for <wallet>")
 	("wallet_representative_set", "Set <account> as default representative for <wallet>")
+	("wallet_send", "Creates and signs a state block which sends <amount> raw from <account> in <wallet> to <destination>, based on the local ledger. The block is printed and, if <rpc_address> is given, published with the process RPC")
 	("all", "Only valid with --final_vote_clear")
 	("account", boost::program_options::value<std::string> (), "Defines <account> for other commands")
 	("root", boost::program_options::value<std::string> (), "Defines <root> for other commands")
@@ -77,6 +84,11 @@ void nano::add_node_options (boost::program_options::options_description & descr
 	("seed", boost::program_options::value<std::string> (), "Defines the <seed> for other commands, hex")
 	("password", boost::program_options::value<std::string> (), "Defines <password> for other commands")
 	("wallet", boost::program_options::value<std::string> (), "Defines <wallet> for other commands")
+	("destination", boost::program_options::value<std::string> (), "Defines the <destination> account for other commands")
+	("amount", boost::program_options::value<std::string> (), "Defines the <amount> in raw for other commands")
+	("work", boost::program_options::value<std::string> (), "Defines precomputed <work> for other commands, hex. If missing, work is generated locally")
+	("rpc_address", boost::program_options::value<std::string> (), "Defines the <rpc_address> of a node which publishes created blocks")
+	("rpc_port", boost::program_options::value<uint16_t> ()->default_value (7076), "Defines the <rpc_port> of a node which publishes created blocks")
 	("force", boost::program_options::value<bool>(), "Bool to force command if allowed")
 	("use_defaults", "If present, the generate_config command will generate uncommented entries");
 	// clang-format on
@@ -99,6 +111,7 @@ void nano::add_node_flag_options (boost::program_options::options_description &
 		("disable_unchecked_cleanup", "Disables periodic cleanup of old records from unchecked table")
 		("disable_unchecked_drop", "Disables drop of unchecked table at startup")
 		("disable_providing_telemetry_metrics", "Disable using any node information in the telemetry_ack messages.")
+		("disable_telemetry_upgrade_advisory", "Disable the warning which is logged when the majority of the peers uses a newer protocol version")
 		("disable_block_processor_unchecked_deletion", "Disable deletion of unchecked blocks after processing")
 		("enable_pruning", "Enable experimental ledger pruning")
 		("allow_bootstrap_peers_duplicates", "Allow multiple connections to same peer in bootstrap attempts")
@@ -107,7 +120,11 @@ void nano::add_node_flag_options (boost::program_options::options_description &
 		("block_processor_full_size", boost::program_options::value<std::size_t>(), "Increase block processor allowed blocks queue size before dropping live network packets and holding bootstrap download, default 65536, 1 million for fast_bootstrap")
 		("block_processor_verification_size", boost::program_options::value<std::size_t>(), "Increase batch signature verification size in block processor, default 0 (limited by config signature_checker_threads), unlimited for fast_bootstrap")
 		("inactive_votes_cache_size", boost::program_options::value<std::size_t>(), "Increase cached votes without active elections size, default 16384")
+		("inactive_votes_cache_eviction", boost::program_options::value<std::string>(), "Which cached votes without active elections are evicted first when the cache is full: lowest_tally (default) or oldest")
 		("vote_processor_capacity", boost::program_options::value<std::size_t>(), "Vote processor queue size before dropping votes, default 144k")
+		("block_trace_sample_interval", boost::program_options::value<uint64_t>(), "Trace the propagation of one of N blocks for latency debugging, default 0 (disabled)")
+		("disable_udp", "Obsolete and ignored. The realtime network only uses TCP")
+		("enable_udp", "Obsolete and ignored. The realtime network only uses TCP")
 		;
 	// clang-format on
 }
@@ -129,6 +146,7 @@ std::error_code nano::update_flags (nano::node_flags & flags_a, boost::program_o
 		flags_a.set_disable_bootstrap_listener (vm.count ("disable_bootstrap_listener") > 0);
 	}
 	flags_a.set_disable_providing_telemetry_metrics (vm.count ("disable_providing_telemetry_metrics") > 0);
+	flags_a.set_disable_telemetry_upgrade_advisory (vm.count ("disable_telemetry_upgrade_advisory") > 0);
 	flags_a.set_disable_unchecked_cleanup (vm.count ("disable_unchecked_cleanup") > 0);
 	flags_a.set_disable_unchecked_drop (vm.count ("disable_unchecked_drop") > 0);
 	flags_a.set_disable_block_processor_unchecked_deletion (vm.count ("disable_block_processor_unchecked_deletion") > 0);
@@ -162,11 +180,38 @@ std::error_code nano::update_flags (nano::node_flags & flags_a, boost::program_o
 	{
 		flags_a.set_inactive_votes_cache_size (inactive_votes_cache_size_it->second.as<std::size_t> ());
 	}
+	auto inactive_votes_cache_eviction_it = vm.find ("inactive_votes_cache_eviction");
+	if (inactive_votes_cache_eviction_it != vm.end ())
+	{
+		auto policy (inactive_votes_cache_eviction_it->second.as<std::string> ());
+		if (policy == "lowest_tally")
+		{
+			flags_a.set_inactive_votes_cache_eviction (nano::vote_cache_eviction_policy::lowest_tally);
+		}
+		else if (policy == "oldest")
+		{
+			flags_a.set_inactive_votes_cache_eviction (nano::vote_cache_eviction_policy::oldest);
+		}
+		else
+		{
+			ec = nano::error_cli::invalid_arguments;
+		}
+	}
 	auto vote_processor_capacity_it = vm.find ("vote_processor_capacity");
 	if (vote_processor_capacity_it != vm.end ())
 	{
 		flags_a.set_vote_processor_capacity (vote_processor_capacity_it->second.as<std::size_t> ());
 	}
+	auto block_trace_sample_interval_it = vm.find ("block_trace_sample_interval");
+	if (block_trace_sample_interval_it != vm.end ())
+	{
+		flags_a.set_block_trace_sample_interval (block_trace_sample_interval_it->second.as<uint64_t> ());
+	}
+	// UDP was removed, the flags are only accepted so that existing start scripts keep working
+	if (vm.count ("disable_udp") > 0 || vm.count ("enable_udp") > 0)
+	{
+		std::cerr << "The --disable_udp and --enable_udp flags are obsolete and ignored, because the realtime network only uses TCP. Remove them from the command line.\n";
+	}
 	// Config overriding
 	auto config (vm.find ("config"));
 	if (config != vm.end ())
@@ -199,53 +244,61 @@ void database_write_lock_error (std::error_code & ec)
 	ec = nano::error_cli::database_write_error;
 }
 
+bool needs_to_write (boost::program_options::variables_map const & vm)
+{
+	return vm.count ("unchecked_clear") || vm.count ("clear_send_ids") || vm.count ("online_weight_clear") || vm.count ("peer_clear") || vm.count ("confirmation_height_clear") || vm.count ("final_vote_clear") || vm.count ("rebuild_database");
+}
+
+/** Clears and rebuilds the parts of the database that were requested on the command line */
+void prepare_database (nano::node & node, boost::program_options::variables_map const & vm)
+{
+	auto & store (node.store);
+	if (vm.count ("unchecked_clear"))
+	{
+		node.unchecked.clear ();
+	}
+	if (vm.count ("clear_send_ids"))
+	{
+		auto tx{ node.wallets.tx_begin_write () };
+		node.wallets.clear_send_ids (*tx);
+	}
+	if (vm.count ("online_weight_clear"))
+	{
+		auto tx{ store.tx_begin_write () };
+		store.online_weight ().clear (*tx);
+	}
+	if (vm.count ("peer_clear"))
+	{
+		auto tx{ store.tx_begin_write () };
+		store.peer ().clear (*tx);
+	}
+	if (vm.count ("confirmation_height_clear"))
+	{
+		auto tx{ store.tx_begin_write () };
+		reset_confirmation_heights (*tx, node.network_params.ledger, store);
+	}
+	if (vm.count ("final_vote_clear"))
+	{
+		auto tx{ store.tx_begin_write () };
+		store.final_vote ().clear (*tx);
+	}
+	if (vm.count ("rebuild_database"))
+	{
+		auto tx{ store.tx_begin_write () };
+		store.rebuild_db (*tx);
+	}
+}
+
 bool copy_database (boost::filesystem::path const & data_path, boost::program_options::variables_map const & vm, boost::filesystem::path const & output_path, std::error_code & ec)
 {
 	bool success = false;
-	bool needs_to_write = vm.count ("unchecked_clear") || vm.count ("clear_send_ids") || vm.count ("online_weight_clear") || vm.count ("peer_clear") || vm.count ("confirmation_height_clear") || vm.count ("final_vote_clear") || vm.count ("rebuild_database");
-
 	auto node_flags = nano::inactive_node_flag_defaults ();
-	node_flags.set_read_only (!needs_to_write);
+	node_flags.set_read_only (!needs_to_write (vm));
 	nano::update_flags (node_flags, vm);
 	nano::inactive_node node (data_path, node_flags);
 	if (!node.node->init_error ())
 	{
-		auto & store (node.node->store);
-		if (vm.count ("unchecked_clear"))
-		{
-			node.node->unchecked.clear ();
-		}
-		if (vm.count ("clear_send_ids"))
-		{
-			auto tx{ node.node->wallets.tx_begin_write () };
-			node.node->wallets.clear_send_ids (*tx);
-		}
-		if (vm.count ("online_weight_clear"))
-		{
-			auto tx{ store.tx_begin_write () };
-			node.node->store.online_weight ().clear (*tx);
-		}
-		if (vm.count ("peer_clear"))
-		{
-			auto tx{ store.tx_begin_write () };
-			node.node->store.peer ().clear (*tx);
-		}
-		if (vm.count ("confirmation_height_clear"))
-		{
-			auto tx{ store.tx_begin_write () };
-			reset_confirmation_heights (*tx, node.node->network_params.ledger, store);
-		}
-		if (vm.count ("final_vote_clear"))
-		{
-			auto tx{ store.tx_begin_write () };
-			node.node->store.final_vote ().clear (*tx);
-		}
-		if (vm.count ("rebuild_database"))
-		{
-			auto tx{ store.tx_begin_write () };
-			node.node->store.rebuild_db (*tx);
-		}
-
+		prepare_database (*node.node, vm);
 		success = node.node->copy_with_compaction (output_path);
 	}
 	else
@@ -254,6 +307,68 @@ bool copy_database (boost::filesystem::path const & data_path, boost::program_op
 	}
 	return success;
 }
+
+/** Replaces the database file with a verified and compacted copy of itself. The original file is kept in backup_path */
+bool vacuum_database (boost::filesystem::path const & data_path, boost::program_options::variables_map const & vm, boost::filesystem::path const & database_path, boost::filesystem::path const & backup_path, std::error_code & ec)
+{
+	bool success = false;
+	auto node_flags = nano::inactive_node_flag_defaults ();
+	node_flags.set_read_only (!needs_to_write (vm));
+	nano::update_flags (node_flags, vm);
+	nano::inactive_node node (data_path, node_flags);
+	if (!node.node->init_error ())
+	{
+		prepare_database (*node.node, vm);
+		success = !node.node->store.vacuum (database_path, backup_path, false);
+	}
+	else
+	{
+		database_write_lock_error (ec);
+	}
+	return success;
+}
+
+/** Publishes the block with the process RPC of the node at \p address_a and returns the response body */
+std::string publish_with_rpc (nano::block const & block_a, std::string const & address_a, uint16_t port_a, boost::system::error_code & ec)
+{
+	boost::property_tree::ptree request;
+	request.put ("action", "process");
+	request.put ("json_block", "true");
+	request.put ("subtype", "send");
+	boost::property_tree::ptree block_l;
+	block_a.serialize_json (block_l);
+	request.add_child ("block", block_l);
+	std::stringstream body;
+	boost::property_tree::write_json (body, request);
+
+	boost::asio::io_context io_ctx;
+	boost::asio::ip::tcp::resolver resolver (io_ctx);
+	boost::asio::ip::tcp::socket socket (io_ctx);
+	auto endpoints (resolver.resolve (address_a, std::to_string (port_a), ec));
+	if (!ec)
+	{
+		boost::asio::connect (socket, endpoints, ec);
+	}
+	boost::beast::http::response<boost::beast::http::string_body> response;
+	if (!ec)
+	{
+		boost::beast::http::request<boost::beast::http::string_body> req;
+		req.method (boost::beast::http::verb::post);
+		req.target ("/");
+		req.version (11);
+		req.insert (boost::beast::http::field::host, address_a);
+		req.insert (boost::beast::http::field::content_type, "application/json");
+		req.body () = body.str ();
+		req.prepare_payload ();
+		boost::beast::http::write (socket, req, ec);
+	}
+	if (!ec)
+	{
+		boost::beast::flat_buffer buffer;
+		boost::beast::http::read (socket, buffer, response, ec);
+	}
+	return response.body ();
+}
 }
 
 std::error_code nano::handle_node_options (boost::program_options::variables_map const & vm)
@@ -348,25 +463,18 @@ std::error_code nano::handle_node_options (boost::program_options::variables_map
 		{
 			if (!ec)
 			{
-				std::cout << "Vacuuming database copy in ";
+				std::cout << "Vacuuming database in ";
 				boost::filesystem::path source_path;
 				boost::filesystem::path backup_path;
-				boost::filesystem::path vacuum_path;
 				source_path = data_path / "data.ldb";
 				backup_path = data_path / "backup.vacuum.ldb";
-				vacuum_path = data_path / "vacuumed.ldb";
 				std::cout << data_path << "\n";
 				std::cout << "This may take a while..." << std::endl;
 
-				bool success = copy_database (data_path, vm, vacuum_path, ec);
+				bool success = vacuum_database (data_path, vm, source_path, backup_path, ec);
 				if (success)
 				{
-					// Note that these throw on failure
-					std::cout << "Finalizing" << std::endl;
-					boost::filesystem::remove (backup_path);
-					boost::filesystem::rename (source_path, backup_path);
-					boost::filesystem::rename (vacuum_path, source_path);
-					std::cout << "Vacuum completed" << std::endl;
+					std::cout << "Vacuum completed. The original database was kept in " << backup_path << std::endl;
 				}
 				else
 				{
@@ -893,6 +1001,133 @@ std::error_code nano::handle_node_options (boost::program_options::variables_map
 			}
 		}
 	}
+	else if (vm.count ("wallet_send"))
+	{
+		if (vm.count ("wallet") == 1 && vm.count ("account") == 1 && vm.count ("destination") == 1 && vm.count ("amount") == 1)
+		{
+			nano::wallet_id wallet_id;
+			nano::account source;
+			nano::account destination;
+			nano::amount amount;
+			uint64_t work (0);
+			if (wallet_id.decode_hex (vm["wallet"].as<std::string> ()))
+			{
+				std::cerr << "Invalid wallet id\n";
+				ec = nano::error_cli::invalid_arguments;
+			}
+			else if (source.decode_account (vm["account"].as<std::string> ()))
+			{
+				std::cerr << "Invalid account\n";
+				ec = nano::error_cli::invalid_arguments;
+			}
+			else if (destination.decode_account (vm["destination"].as<std::string> ()))
+			{
+				std::cerr << "Invalid destination\n";
+				ec = nano::error_cli::invalid_arguments;
+			}
+			else if (amount.decode_dec (vm["amount"].as<std::string> ()) || amount.is_zero ())
+			{
+				std::cerr << "Invalid amount\n";
+				ec = nano::error_cli::invalid_arguments;
+			}
+			else if (vm.count ("work") == 1 && nano::from_string_hex (vm["work"].as<std::string> (), work))
+			{
+				std::cerr << "Invalid work\n";
+				ec = nano::error_cli::invalid_arguments;
+			}
+			if (!ec)
+			{
+				std::string password;
+				if (vm.count ("password") > 0)
+				{
+					password = vm["password"].as<std::string> ();
+				}
+				// The ledger is opened read-only, so this works while the node is stopped or on an offline copy of the data folder
+				auto inactive_node = nano::default_inactive_node (data_path, vm);
+				auto node = inactive_node->node;
+				auto wallet (node->wallets.open (wallet_id));
+				nano::raw_key prv;
+				if (wallet == nullptr)
+				{
+					std::cerr << "Wallet doesn't exist\n";
+					ec = nano::error_cli::invalid_arguments;
+				}
+				else
+				{
+					auto wallet_transaction (wallet->wallets.tx_begin_write ());
+					if (wallet->enter_password (*wallet_transaction, password))
+					{
+						std::cerr << "Invalid password\n";
+						ec = nano::error_cli::invalid_arguments;
+					}
+					else if (wallet->store.fetch (*wallet_transaction, source, prv))
+					{
+						std::cerr << "Account not found in wallet\n";
+						ec = nano::error_cli::invalid_arguments;
+					}
+				}
+				if (!ec)
+				{
+					auto transaction (node->store.tx_begin_read ());
+					auto info (node->ledger.account_info (*transaction, source));
+					if (!info)
+					{
+						std::cerr << "Account not found in the local ledger\n";
+						ec = nano::error_cli::invalid_arguments;
+					}
+					else if (info->balance ().number () < amount.number ())
+					{
+						std::cerr << "Insufficient balance\n";
+						ec = nano::error_cli::invalid_arguments;
+					}
+					else
+					{
+						nano::block_builder builder;
+						std::error_code ec_build;
+						auto block = builder.state ()
+									 .account (source)
+									 .previous (info->head ())
+									 .representative (info->representative ())
+									 .balance (info->balance ().number () - amount.number ())
+									 .link (destination)
+									 .sign (prv, source)
+									 .work (work)
+									 .build (ec_build);
+						if (vm.count ("work") == 0 && !node->work_generate_blocking (*block, node->default_difficulty (nano::work_version::work_1)).is_initialized ())
+						{
+							std::cerr << "Work generation failed\n";
+							ec = nano::error_cli::generic;
+						}
+						if (!ec)
+						{
+							std::string block_json;
+							block->serialize_json (block_json);
+							std::cout << block_json << std::endl;
+							if (vm.count ("rpc_address") == 1)
+							{
+								boost::system::error_code rpc_ec;
+								auto response (publish_with_rpc (*block, vm["rpc_address"].as<std::string> (), vm["rpc_port"].as<uint16_t> (), rpc_ec));
+								if (!rpc_ec)
+								{
+									std::cout << response << std::endl;
+								}
+								else
+								{
+									std::cerr << boost::str (boost::format ("Publishing failed: %1%\n") % rpc_ec.message ());
+									ec = nano::error_cli::generic;
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+		else
+		{
+			std::cerr << "wallet_send command requires one <wallet>, <account>, <destination> and <amount> option and optionally one <password>, <work>, <rpc_address> and <rpc_port> option\n";
+			ec = nano::error_cli::invalid_arguments;
+		}
+	}
 	else if (vm.count ("wallet_decrypt_unsafe"))
 	{
 		if (vm.count ("wallet") == 1)
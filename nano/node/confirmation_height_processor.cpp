@@ -69,6 +69,11 @@ void nano::confirmation_height_processor::add (std::shared_ptr<nano::block> cons
 	rsnano::rsn_confirmation_height_processor_add (handle, block_a->get_handle ());
 }
 
+bool nano::confirmation_height_processor::add (nano::block_hash const & hash_a)
+{
+	return rsnano::rsn_confirmation_height_processor_add_hash (handle, hash_a.bytes.data ());
+}
+
 namespace
 {
 void block_callback (void * context_a, rsnano::BlockHandle * block_handle)
@@ -98,6 +103,47 @@ void delete_block_hash_callback_context (void * context_a)
 }
 }
 
+namespace
+{
+void execute_notification (void * context_a)
+{
+	auto action = static_cast<std::function<void ()> *> (context_a);
+	(*action) ();
+}
+
+void delete_notification_context (void * context_a)
+{
+	auto action = static_cast<std::function<void ()> *> (context_a);
+	delete action;
+}
+}
+
+nano::cemented_notification_buffer::cemented_notification_buffer () :
+	handle{ rsnano::rsn_cemented_notification_buffer_create () }
+{
+}
+
+nano::cemented_notification_buffer::~cemented_notification_buffer ()
+{
+	rsnano::rsn_cemented_notification_buffer_destroy (handle);
+}
+
+uint64_t nano::cemented_notification_buffer::next_sequence (nano::account const & account_a)
+{
+	return rsnano::rsn_cemented_notification_buffer_next_sequence (handle, account_a.bytes.data ());
+}
+
+void nano::cemented_notification_buffer::push (nano::account const & account_a, uint64_t sequence_a, std::function<void ()> const & action_a)
+{
+	auto context = new std::function<void ()> (action_a);
+	rsnano::rsn_cemented_notification_buffer_push (handle, account_a.bytes.data (), sequence_a, execute_notification, context, delete_notification_context);
+}
+
+std::size_t nano::cemented_notification_buffer::buffered_size () const
+{
+	return rsnano::rsn_cemented_notification_buffer_buffered_len (handle);
+}
+
 // Not thread-safe, only call before this processor has begun cementing
 void nano::confirmation_height_processor::set_cemented_observer (std::function<void (std::shared_ptr<nano::block> const &)> const & callback_a)
 {
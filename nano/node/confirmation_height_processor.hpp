@@ -34,6 +34,8 @@ public:
 	void unpause ();
 	void stop ();
 	void add (std::shared_ptr<nano::block> const &);
+	/** Loads the block from the ledger and queues it. Returns false if the block doesn't exist */
+	bool add (nano::block_hash const &);
 	std::size_t awaiting_processing_size () const;
 	bool is_processing_added_block (nano::block_hash const & hash_a) const;
 	bool is_processing_block (nano::block_hash const &) const;
@@ -68,4 +70,27 @@ private: // Tests
 	friend class request_aggregator_cannot_vote_Test;
 	friend class active_transactions_pessimistic_elections_Test;
 };
+
+/*
+ * Delivers the cemented block notifications of an account in cementing order,
+ * even if they are prepared concurrently in the worker pool
+ */
+class cemented_notification_buffer final
+{
+public:
+	cemented_notification_buffer ();
+	cemented_notification_buffer (cemented_notification_buffer const &) = delete;
+	~cemented_notification_buffer ();
+	/*
+	 * Has to be called in cementing order, i.e. from the block observers
+	 */
+	uint64_t next_sequence (nano::account const &);
+	/*
+	 * Calls the action as soon as all notifications of the account with a lower sequence number were delivered
+	 */
+	void push (nano::account const &, uint64_t sequence, std::function<void ()> const & action);
+	std::size_t buffered_size () const;
+
+	rsnano::CementedNotificationBufferHandle * handle;
+};
 }
@@ -0,0 +1,29 @@
+#include <nano/lib/rsnano.hpp>
+#include <nano/node/confirmation_latency.hpp>
+
+#include <boost/property_tree/ptree.hpp>
+
+nano::confirmation_latency::confirmation_latency () :
+	handle{ rsnano::rsn_confirmation_latency_create () }
+{
+}
+
+nano::confirmation_latency::~confirmation_latency ()
+{
+	rsnano::rsn_confirmation_latency_destroy (handle);
+}
+
+void nano::confirmation_latency::block_arrived (nano::block_hash const & hash_a)
+{
+	rsnano::rsn_confirmation_latency_block_arrived (handle, hash_a.bytes.data ());
+}
+
+int64_t nano::confirmation_latency::block_cemented (nano::block_hash const & hash_a)
+{
+	return rsnano::rsn_confirmation_latency_block_cemented (handle, hash_a.bytes.data ());
+}
+
+void nano::confirmation_latency::serialize_json (boost::property_tree::ptree & tree_a)
+{
+	rsnano::rsn_confirmation_latency_serialize_json (handle, &tree_a);
+}
@@ -0,0 +1,35 @@
+#pragma once
+
+#include <nano/lib/numbers.hpp>
+
+#include <boost/property_tree/ptree_fwd.hpp>
+
+#include <cstdint>
+
+namespace rsnano
+{
+class ConfirmationLatencyHandle;
+}
+
+namespace nano
+{
+// Measures the time between the arrival of a block in the block processor and its cementation.
+// The latencies are aggregated into percentiles per minute.
+class confirmation_latency final
+{
+public:
+	confirmation_latency ();
+	confirmation_latency (nano::confirmation_latency const &) = delete;
+	confirmation_latency (nano::confirmation_latency &&) = delete;
+	~confirmation_latency ();
+	nano::confirmation_latency & operator= (nano::confirmation_latency const &) = delete;
+	nano::confirmation_latency & operator= (nano::confirmation_latency &&) = delete;
+	void block_arrived (nano::block_hash const &);
+	// Returns the latency in milliseconds or -1 if the arrival of the block wasn't recorded
+	int64_t block_cemented (nano::block_hash const &);
+	void serialize_json (boost::property_tree::ptree &);
+
+private:
+	rsnano::ConfirmationLatencyHandle * handle;
+};
+}
@@ -1,8 +1,11 @@
 #include <nano/lib/config.hpp>
 #include <nano/lib/jsonconfig.hpp>
+#include <nano/lib/rsnanoutils.hpp>
 #include <nano/lib/tomlconfig.hpp>
 #include <nano/node/daemonconfig.hpp>
 
+#include <algorithm>
+#include <array>
 #include <sstream>
 #include <vector>
 
@@ -41,6 +44,20 @@ nano::error nano::daemon_config::serialize_toml (nano::tomlconfig & toml)
 	return toml.get_error ();
 }
 
+std::vector<nano::config_issue> nano::daemon_config::validate () const
+{
+	auto dto{ to_daemon_config_dto (*this) };
+	std::array<rsnano::ConfigIssueDto, 64> issues;
+	auto count = rsnano::rsn_daemon_config_validate (&dto, issues.data (), issues.size ());
+	std::vector<nano::config_issue> result;
+	result.reserve (count);
+	for (std::size_t i = 0; i < std::min (count, issues.size ()); ++i)
+	{
+		result.push_back (nano::config_issue{ rsnano::convert_dto_to_string (issues[i].message), issues[i].is_error });
+	}
+	return result;
+}
+
 nano::error nano::daemon_config::deserialize_toml (nano::tomlconfig & toml)
 {
 	auto rpc_l (toml.get_optional_child ("rpc"));
@@ -18,6 +18,8 @@ public:
 	daemon_config (boost::filesystem::path const & data_path, nano::network_params & network_params);
 	nano::error deserialize_toml (nano::tomlconfig &);
 	nano::error serialize_toml (nano::tomlconfig &);
+	/** Checks constraints between config values. The node must not be started if one of the issues is an error */
+	std::vector<nano::config_issue> validate () const;
 	bool rpc_enable{ false };
 	nano::node_rpc_config rpc;
 	nano::node_config node;
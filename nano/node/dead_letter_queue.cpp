@@ -0,0 +1,34 @@
+#include <nano/lib/rsnano.hpp>
+#include <nano/node/dead_letter_queue.hpp>
+
+#include <boost/property_tree/ptree.hpp>
+
+nano::dead_letter_queue::dead_letter_queue () :
+	handle{ rsnano::rsn_dead_letter_queue_create () }
+{
+}
+
+nano::dead_letter_queue::~dead_letter_queue ()
+{
+	rsnano::rsn_dead_letter_queue_destroy (handle);
+}
+
+void nano::dead_letter_queue::record (nano::block_hash const & hash_a, nano::account const & account_a, nano::process_result result_a)
+{
+	rsnano::rsn_dead_letter_queue_record (handle, hash_a.bytes.data (), account_a.bytes.data (), static_cast<uint8_t> (result_a));
+}
+
+uint32_t nano::dead_letter_queue::attempts (nano::block_hash const & hash_a)
+{
+	return rsnano::rsn_dead_letter_queue_attempts (handle, hash_a.bytes.data ());
+}
+
+std::size_t nano::dead_letter_queue::size ()
+{
+	return rsnano::rsn_dead_letter_queue_size (handle);
+}
+
+void nano::dead_letter_queue::serialize_json (boost::property_tree::ptree & tree_a)
+{
+	rsnano::rsn_dead_letter_queue_serialize_json (handle, &tree_a);
+}
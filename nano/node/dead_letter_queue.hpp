@@ -0,0 +1,38 @@
+#pragma once
+
+#include <nano/lib/numbers.hpp>
+#include <nano/secure/common.hpp>
+
+#include <boost/property_tree/ptree_fwd.hpp>
+
+#include <cstdint>
+
+namespace rsnano
+{
+class DeadLetterQueueHandle;
+}
+
+namespace nano
+{
+// Tracks blocks which repeatedly failed with a transient result (gaps and forks), so that they don't just vanish.
+// The buffer is bounded and entries expire when the block didn't fail for a while.
+class dead_letter_queue final
+{
+public:
+	dead_letter_queue ();
+	dead_letter_queue (nano::dead_letter_queue const &) = delete;
+	dead_letter_queue (nano::dead_letter_queue &&) = delete;
+	~dead_letter_queue ();
+	nano::dead_letter_queue & operator= (nano::dead_letter_queue const &) = delete;
+	nano::dead_letter_queue & operator= (nano::dead_letter_queue &&) = delete;
+	// Any result which isn't transient removes the block from the queue
+	void record (nano::block_hash const &, nano::account const &, nano::process_result);
+	// Returns 0 if the block isn't tracked
+	uint32_t attempts (nano::block_hash const &);
+	std::size_t size ();
+	void serialize_json (boost::property_tree::ptree &);
+
+private:
+	rsnano::DeadLetterQueueHandle * handle;
+};
+}
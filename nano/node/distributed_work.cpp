@@ -76,7 +76,7 @@ void nano::distributed_work::start ()
 		auto parsed_address (boost::asio::ip::make_address_v6 (peer.first, ec));
 		if (!ec)
 		{
-			do_request (nano::tcp_endpoint (parsed_address, peer.second));
+			do_request (nano::tcp_endpoint (parsed_address, peer.second), peer);
 		}
 		else
 		{
@@ -84,17 +84,18 @@ void nano::distributed_work::start ()
 			node.network->resolver.async_resolve (boost::asio::ip::udp::resolver::query (peer.first, std::to_string (peer.second)), [peer, this_l, &extra = resolved_extra] (boost::system::error_code const & ec, boost::asio::ip::udp::resolver::iterator i_a) {
 				if (!ec)
 				{
-					this_l->do_request (nano::tcp_endpoint (i_a->endpoint ().address (), i_a->endpoint ().port ()));
+					this_l->do_request (nano::tcp_endpoint (i_a->endpoint ().address (), i_a->endpoint ().port ()), peer);
 					++i_a;
 					for (auto & i : boost::make_iterator_range (i_a, {}))
 					{
 						++extra;
-						this_l->do_request (nano::tcp_endpoint (i.endpoint ().address (), i.endpoint ().port ()));
+						this_l->do_request (nano::tcp_endpoint (i.endpoint ().address (), i.endpoint ().port ()), peer);
 					}
 				}
 				else
 				{
 					this_l->node.logger->try_log (boost::str (boost::format ("Error resolving work peer: %1%:%2%: %3%") % peer.first % peer.second % ec.message ()));
+					this_l->node.distributed_work.health.record_failure (peer);
 					this_l->failure ();
 				}
 			});
@@ -123,10 +124,10 @@ void nano::distributed_work::start_local ()
 	});
 }
 
-void nano::distributed_work::do_request (nano::tcp_endpoint const & endpoint_a)
+void nano::distributed_work::do_request (nano::tcp_endpoint const & endpoint_a, std::pair<std::string, uint16_t> const & peer_a)
 {
 	auto this_l (shared_from_this ());
-	auto connection (std::make_shared<peer_request> (node.io_ctx, endpoint_a));
+	auto connection (std::make_shared<peer_request> (node.io_ctx, endpoint_a, peer_a));
 	{
 		nano::lock_guard<nano::mutex> lock{ mutex };
 		connections.emplace_back (connection);
@@ -162,7 +163,7 @@ void nano::distributed_work::do_request (nano::tcp_endpoint const & endpoint_a)
 						{
 							if (connection->response.result () == boost::beast::http::status::ok)
 							{
-								this_l->success (connection->response.body (), connection->endpoint);
+								this_l->success (connection->response.body (), *connection);
 							}
 							else if (ec)
 							{
@@ -173,6 +174,7 @@ void nano::distributed_work::do_request (nano::tcp_endpoint const & endpoint_a)
 						}
 						else if (ec)
 						{
+							this_l->node.distributed_work.health.record_failure (connection->peer);
 							this_l->do_cancel (connection->endpoint);
 							this_l->failure ();
 						}
@@ -181,6 +183,7 @@ void nano::distributed_work::do_request (nano::tcp_endpoint const & endpoint_a)
 				else if (ec && ec != boost::system::errc::operation_canceled)
 				{
 					this_l->node.logger->try_log (boost::str (boost::format ("Unable to write to work_peer %1% %2%: %3% (%4%)") % connection->endpoint.address () % connection->endpoint.port () % ec.message () % ec.value ()));
+					this_l->node.distributed_work.health.record_failure (connection->peer);
 					this_l->add_bad_peer (connection->endpoint);
 					this_l->failure ();
 				}
@@ -189,6 +192,7 @@ void nano::distributed_work::do_request (nano::tcp_endpoint const & endpoint_a)
 		else if (ec && ec != boost::system::errc::operation_canceled)
 		{
 			this_l->node.logger->try_log (boost::str (boost::format ("Unable to connect to work_peer %1% %2%: %3% (%4%)") % connection->endpoint.address () % connection->endpoint.port () % ec.message () % ec.value ()));
+			this_l->node.distributed_work.health.record_failure (connection->peer);
 			this_l->add_bad_peer (connection->endpoint);
 			this_l->failure ();
 		}
@@ -226,8 +230,9 @@ void nano::distributed_work::do_cancel (nano::tcp_endpoint const & endpoint_a)
 	}));
 }
 
-void nano::distributed_work::success (std::string const & body_a, nano::tcp_endpoint const & endpoint_a)
+void nano::distributed_work::success (std::string const & body_a, peer_request const & connection_a)
 {
+	auto const & endpoint_a (connection_a.endpoint);
 	bool error = true;
 	try
 	{
@@ -242,6 +247,7 @@ void nano::distributed_work::success (std::string const & body_a, nano::tcp_endp
 			{
 				error = false;
 				node.unresponsive_work_peers = false;
+				node.distributed_work.health.record_success (connection_a.peer, std::chrono::duration_cast<std::chrono::milliseconds> (std::chrono::steady_clock::now () - connection_a.start));
 				set_once (work, boost::str (boost::format ("%1%:%2%") % endpoint_a.address () % endpoint_a.port ()));
 				stop_once (true);
 			}
@@ -261,6 +267,7 @@ void nano::distributed_work::success (std::string const & body_a, nano::tcp_endp
 	}
 	if (error)
 	{
+		node.distributed_work.health.record_failure (connection_a.peer);
 		add_bad_peer (endpoint_a);
 		failure ();
 	}
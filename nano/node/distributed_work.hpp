@@ -50,13 +50,17 @@ class distributed_work final : public std::enable_shared_from_this<nano::distrib
 	class peer_request final
 	{
 	public:
-		peer_request (boost::asio::io_context & io_ctx_a, nano::tcp_endpoint const & endpoint_a) :
+		peer_request (boost::asio::io_context & io_ctx_a, nano::tcp_endpoint const & endpoint_a, std::pair<std::string, uint16_t> const & peer_a = {}) :
 			endpoint (endpoint_a),
+			peer (peer_a),
 			socket (io_ctx_a)
 		{
 		}
 		std::shared_ptr<request_type> get_prepared_json_request (std::string const &) const;
 		nano::tcp_endpoint const endpoint;
+		/** The configured work peer, which can resolve to multiple endpoints */
+		std::pair<std::string, uint16_t> const peer;
+		std::chrono::steady_clock::time_point const start{ std::chrono::steady_clock::now () };
 		boost::beast::flat_buffer buffer;
 		boost::beast::http::response<boost::beast::http::string_body> response;
 		boost::asio::ip::tcp::socket socket;
@@ -70,12 +74,12 @@ public:
 
 private:
 	void start_local ();
-	/** Send a work_generate message to \p endpoint_a and handle a response */
-	void do_request (nano::tcp_endpoint const & endpoint_a);
+	/** Send a work_generate message to \p endpoint_a of \p peer_a and handle a response */
+	void do_request (nano::tcp_endpoint const & endpoint_a, std::pair<std::string, uint16_t> const & peer_a);
 	/** Send a work_cancel message using a new connection to \p endpoint_a */
 	void do_cancel (nano::tcp_endpoint const & endpoint_a);
 	/** Called on a successful peer response, validates the reply */
-	void success (std::string const &, nano::tcp_endpoint const &);
+	void success (std::string const &, peer_request const &);
 	/** Send a work_cancel message to all remaining connections */
 	void stop_once (bool const);
 	void set_once (uint64_t const, std::string const & source_a = "local");
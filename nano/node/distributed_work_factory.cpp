@@ -1,3 +1,5 @@
+#include <nano/boost/asio/ip/tcp.hpp>
+#include <nano/boost/asio/steady_timer.hpp>
 #include <nano/node/distributed_work.hpp>
 #include <nano/node/distributed_work_factory.hpp>
 #include <nano/node/node.hpp>
@@ -23,12 +25,19 @@ bool nano::distributed_work_factory::make (std::chrono::seconds const & backoff_
 	if (!stopped)
 	{
 		cleanup_finished ();
-		if (node.work_generation_enabled (request_a.peers))
+		// Skip failing peers. If all of them fail, they are still asked when local work generation is disabled
+		auto peers_l (health.available (request_a.peers));
+		if (peers_l.empty () && !node.local_work_generation_enabled ())
 		{
-			auto distributed (std::make_shared<nano::distributed_work> (node, request_a, backoff_a));
+			peers_l = request_a.peers;
+		}
+		nano::work_request request_l{ request_a.version, request_a.root, request_a.difficulty, request_a.account, request_a.callback, peers_l };
+		if (node.work_generation_enabled (request_l.peers))
+		{
+			auto distributed (std::make_shared<nano::distributed_work> (node, request_l, backoff_a));
 			{
 				nano::lock_guard<nano::mutex> guard (mutex);
-				items.emplace (request_a.root, distributed);
+				items.emplace (request_l.root, distributed);
 			}
 			distributed->start ();
 			error_l = false;
@@ -80,6 +89,53 @@ std::size_t nano::distributed_work_factory::size () const
 	return items.size ();
 }
 
+void nano::distributed_work_factory::check_peers (std::vector<std::pair<std::string, uint16_t>> const & peers_a)
+{
+	for (auto const & peer : health.available (peers_a))
+	{
+		std::weak_ptr<nano::node> node_w (node.shared ());
+		node.network->resolver.async_resolve (boost::asio::ip::udp::resolver::query (peer.first, std::to_string (peer.second)), [node_w, peer] (boost::system::error_code const & ec, boost::asio::ip::udp::resolver::iterator i_a) {
+			auto node_l = node_w.lock ();
+			if (!node_l)
+			{
+				return;
+			}
+			if (ec)
+			{
+				node_l->distributed_work.health.record_failure (peer);
+				return;
+			}
+			auto socket = std::make_shared<boost::asio::ip::tcp::socket> (node_l->io_ctx);
+			auto timer = std::make_shared<boost::asio::steady_timer> (node_l->io_ctx);
+			timer->expires_after (std::chrono::seconds (5));
+			timer->async_wait ([socket] (boost::system::error_code const & ec) {
+				if (!ec)
+				{
+					boost::system::error_code ignored;
+					socket->close (ignored);
+				}
+			});
+			nano::tcp_endpoint endpoint (i_a->endpoint ().address (), i_a->endpoint ().port ());
+			socket->async_connect (endpoint, [node_w, peer, socket, timer] (boost::system::error_code const & ec) {
+				timer->cancel ();
+				if (auto node_l = node_w.lock ())
+				{
+					if (!ec)
+					{
+						node_l->distributed_work.health.record_success (peer);
+					}
+					else
+					{
+						node_l->distributed_work.health.record_failure (peer);
+					}
+				}
+				boost::system::error_code ignored;
+				socket->close (ignored);
+			});
+		});
+	}
+}
+
 std::unique_ptr<nano::container_info_component> nano::collect_container_info (distributed_work_factory & distributed_work, std::string const & name)
 {
 	auto item_count = distributed_work.size ();
@@ -1,6 +1,7 @@
 #pragma once
 
 #include <nano/lib/numbers.hpp>
+#include <nano/node/work_peer_health.hpp>
 
 #include <atomic>
 #include <functional>
@@ -26,6 +27,10 @@ public:
 	void cleanup_finished ();
 	void stop ();
 	std::size_t size () const;
+	/** Connects to each available work peer to find out whether it is reachable */
+	void check_peers (std::vector<std::pair<std::string, uint16_t>> const &);
+
+	nano::work_peer_health health;
 
 private:
 	std::unordered_multimap<nano::root, std::weak_ptr<nano::distributed_work>> items;
@@ -54,6 +54,10 @@ void nano::election::confirm_once (nano::unique_lock<nano::mutex> & lock_a, nano
 		auto const status_l = status;
 		lock_a.unlock ();
 
+		node.block_tracer.record (status_l.get_winner ()->hash (), nano::block_trace_event::quorum);
+		node.stats->inc (nano::stat::type::election, nano::stat::detail::election_confirmed);
+		node.stats->add (nano::stat::type::election, nano::stat::detail::election_confirm_reqs, nano::stat::dir::in, status_l.get_confirmation_request_count ());
+
 		node.background ([node_l = node.shared (), status_l, confirmation_action_l = confirmation_action] () {
 			node_l->process_confirmed (status_l);
 
@@ -135,13 +139,14 @@ bool nano::election::confirmed (nano::unique_lock<nano::mutex> & lock) const
 
 std::chrono::milliseconds nano::election::confirm_req_time () const
 {
+	auto interval = node.config->confirm_req.request_interval (confirmation_request_count);
 	switch (behavior ())
 	{
 		case election_behavior::normal:
 		case election_behavior::hinted:
-			return base_latency () * 5;
+			return interval;
 		case election_behavior::optimistic:
-			return base_latency () * 2;
+			return interval * 2 / 5;
 	}
 	debug_assert (false);
 	return {};
@@ -149,6 +154,10 @@ std::chrono::milliseconds nano::election::confirm_req_time () const
 
 void nano::election::send_confirm_req (nano::confirmation_solicitor & solicitor_a)
 {
+	if (!node.config->confirm_req.may_request (confirmation_request_count))
+	{
+		return;
+	}
 	if (confirm_req_time () < (std::chrono::steady_clock::now () - last_req))
 	{
 		nano::lock_guard<nano::mutex> guard{ mutex };
@@ -156,6 +165,10 @@ void nano::election::send_confirm_req (nano::confirmation_solicitor & solicitor_
 		{
 			last_req = std::chrono::steady_clock::now ();
 			++confirmation_request_count;
+			if (!node.config->confirm_req.may_request (confirmation_request_count))
+			{
+				node.stats->inc (nano::stat::type::election, nano::stat::detail::election_confirm_req_limit);
+			}
 		}
 	}
 }
@@ -183,13 +196,15 @@ bool nano::election::failed () const
 
 void nano::election::broadcast_block (nano::confirmation_solicitor & solicitor_a)
 {
-	if (base_latency () * 15 < std::chrono::steady_clock::now () - last_block)
+	auto const confirmed_l = confirmed ();
+	nano::lock_guard<nano::mutex> guard{ mutex };
+	auto const winner_hash = status.get_winner ()->hash ();
+	auto const election_age = std::chrono::duration_cast<std::chrono::milliseconds> (std::chrono::steady_clock::now () - election_start);
+	auto const reason = node.active.broadcast_throttle.check (qualified_root, winner_hash, election_age, confirmed_l);
+	if (reason && !solicitor_a.broadcast (*this))
 	{
-		nano::lock_guard<nano::mutex> guard{ mutex };
-		if (!solicitor_a.broadcast (*this))
-		{
-			last_block = std::chrono::steady_clock::now ();
-		}
+		node.active.broadcast_throttle.flag (qualified_root, winner_hash, confirmed_l);
+		node.stats->inc (nano::stat::type::election, *reason);
 	}
 }
 
@@ -473,11 +488,12 @@ bool nano::election::publish (std::shared_ptr<nano::block> const & block_a)
 
 	// Do not insert new blocks if already confirmed
 	auto result = confirmed (lock);
-	if (!result && last_blocks.size () >= max_blocks && last_blocks.find (block_a->hash ()) == last_blocks.end ())
+	if (!result && last_blocks.size () >= node.config->max_forks_per_root && last_blocks.find (block_a->hash ()) == last_blocks.end ())
 	{
 		if (!replace_by_weight (lock, block_a->hash ()))
 		{
 			result = true;
+			node.stats->inc (nano::stat::type::election, nano::stat::detail::fork_limit);
 			node.network->tcp_channels->publish_filter->clear (block_a);
 		}
 		debug_assert (lock.owns_lock ());
@@ -601,9 +617,9 @@ bool nano::election::replace_by_weight (nano::unique_lock<nano::mutex> & lock_a,
 	// Replace if lowest tally is below inactive cache new block weight
 	auto inactive_existing = node.inactive_vote_cache.find (hash_a);
 	auto inactive_tally = inactive_existing ? inactive_existing->tally : 0;
-	if (inactive_tally > 0 && sorted.size () < max_blocks)
+	if (inactive_tally > 0 && sorted.size () < node.config->max_forks_per_root)
 	{
-		// If count of tally items is less than 10, remove any block without tally
+		// If count of tally items is less than the fork limit, remove any block without tally
 		for (auto const & [hash, block] : blocks ())
 		{
 			if (std::find_if (sorted.begin (), sorted.end (), [&hash = hash] (auto const & item_a) { return item_a.first == hash; }) == sorted.end () && hash != winner_hash)
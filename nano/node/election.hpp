@@ -104,7 +104,6 @@ private: // State management
 	std::atomic<std::chrono::steady_clock::duration> state_start{ std::chrono::steady_clock::now ().time_since_epoch () };
 
 	// These are modified while not holding the mutex from transition_time only
-	std::chrono::steady_clock::time_point last_block = { std::chrono::steady_clock::now () };
 	std::chrono::steady_clock::time_point last_req = {};
 	/** The last time vote for this election was generated */
 	std::chrono::steady_clock::time_point last_vote = {};
@@ -207,9 +206,6 @@ private:
 
 	mutable nano::mutex mutex;
 
-private: // Constants
-	static std::size_t constexpr max_blocks{ 10 };
-
 	friend class active_transactions;
 	friend class confirmation_solicitor;
 
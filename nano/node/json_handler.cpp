@@ -625,33 +625,28 @@ void nano::json_handler::account_info ()
 		bool const include_confirmed = request.get<bool> ("include_confirmed", false);
 		auto transaction (node.store.tx_begin_read ());
 		auto info (account_info_impl (*transaction, account));
-		nano::confirmation_height_info confirmation_height_info;
-		node.store.confirmation_height ().get (*transaction, account, confirmation_height_info);
 		if (!ec)
 		{
+			auto receivable_details = nano::receivable_details::skip;
+			if (receivable)
+			{
+				receivable_details = include_confirmed ? nano::receivable_details::total_and_confirmed : nano::receivable_details::total;
+			}
+			auto details = node.ledger.account_details (*transaction, account, receivable_details).value_or (nano::account_details{});
+			auto const & confirmation_height_info = details.confirmation_height;
 			response_l.put ("frontier", info.head ().to_string ());
 			response_l.put ("open_block", info.open_block ().to_string ());
-			response_l.put ("representative_block", node.ledger.representative (*transaction, info.head ()).to_string ());
+			response_l.put ("representative_block", details.representative_block.to_string ());
 			nano::amount balance_l (info.balance ());
 			std::string balance;
 			balance_l.encode_dec (balance);
 
 			response_l.put ("balance", balance);
 
-			nano::amount confirmed_balance_l;
 			if (include_confirmed)
 			{
-				if (info.block_count () != confirmation_height_info.height ())
-				{
-					confirmed_balance_l = node.ledger.balance (*transaction, confirmation_height_info.frontier ());
-				}
-				else
-				{
-					// block_height and confirmed height are the same, so can just reuse balance
-					confirmed_balance_l = balance_l;
-				}
 				std::string confirmed_balance;
-				confirmed_balance_l.encode_dec (confirmed_balance);
+				details.confirmed_balance.encode_dec (confirmed_balance);
 				response_l.put ("confirmed_balance", confirmed_balance);
 			}
 
@@ -671,28 +666,12 @@ void nano::json_handler::account_info ()
 				response_l.put ("confirmation_height_frontier", confirmed_frontier);
 			}
 
-			std::shared_ptr<nano::block> confirmed_frontier_block;
-			if (include_confirmed && confirmation_height_info.height () > 0)
-			{
-				confirmed_frontier_block = node.store.block ().get (*transaction, confirmation_height_info.frontier ());
-			}
-
 			if (representative)
 			{
 				response_l.put ("representative", info.representative ().to_account ());
 				if (include_confirmed)
 				{
-					nano::account confirmed_representative{};
-					if (confirmed_frontier_block)
-					{
-						confirmed_representative = confirmed_frontier_block->representative ();
-						if (confirmed_representative.is_zero ())
-						{
-							confirmed_representative = node.store.block ().get (*transaction, node.ledger.representative (*transaction, confirmation_height_info.frontier ()))->representative ();
-						}
-					}
-
-					response_l.put ("confirmed_representative", confirmed_representative.to_account ());
+					response_l.put ("confirmed_representative", details.confirmed_representative.to_account ());
 				}
 			}
 			if (weight)
@@ -702,15 +681,15 @@ void nano::json_handler::account_info ()
 			}
 			if (receivable)
 			{
-				auto account_receivable = node.ledger.account_receivable (*transaction, account);
-				response_l.put ("pending", account_receivable.convert_to<std::string> ());
-				response_l.put ("receivable", account_receivable.convert_to<std::string> ());
+				auto account_receivable = details.receivable.to_string_dec ();
+				response_l.put ("pending", account_receivable);
+				response_l.put ("receivable", account_receivable);
 
 				if (include_confirmed)
 				{
-					auto account_receivable = node.ledger.account_receivable (*transaction, account, true);
-					response_l.put ("confirmed_pending", account_receivable.convert_to<std::string> ());
-					response_l.put ("confirmed_receivable", account_receivable.convert_to<std::string> ());
+					auto confirmed_receivable = details.confirmed_receivable.to_string_dec ();
+					response_l.put ("confirmed_pending", confirmed_receivable);
+					response_l.put ("confirmed_receivable", confirmed_receivable);
 				}
 			}
 		}
@@ -1106,25 +1085,30 @@ void nano::json_handler::accounts_receivable ()
 void nano::json_handler::active_difficulty ()
 {
 	auto include_trend (request.get<bool> ("include_trend", false));
-	auto const multiplier_active = 1.0;
+	auto const multiplier_active = node.active_difficulty.multiplier ();
 	auto const default_difficulty (node.default_difficulty (nano::work_version::work_1));
 	auto const default_receive_difficulty (node.default_receive_difficulty (nano::work_version::work_1));
-	auto const receive_current_denormalized (node.network_params.work.denormalized_multiplier (multiplier_active, node.network_params.work.get_epoch_2_receive ()));
 	response_l.put ("deprecated", "1");
 	response_l.put ("network_minimum", nano::to_string_hex (default_difficulty));
 	response_l.put ("network_receive_minimum", nano::to_string_hex (default_receive_difficulty));
-	response_l.put ("network_current", nano::to_string_hex (nano::difficulty::from_multiplier (multiplier_active, default_difficulty)));
-	response_l.put ("network_receive_current", nano::to_string_hex (nano::difficulty::from_multiplier (receive_current_denormalized, default_receive_difficulty)));
-	response_l.put ("multiplier", 1.0);
+	response_l.put ("network_current", nano::to_string_hex (node.active_difficulty.difficulty ()));
+	response_l.put ("network_receive_current", nano::to_string_hex (node.active_difficulty.receive_difficulty ()));
+	response_l.put ("multiplier", multiplier_active);
 	if (include_trend)
 	{
 		boost::property_tree::ptree difficulty_trend_l;
-
-		// To keep this RPC backwards-compatible
-		boost::property_tree::ptree entry;
-		entry.put ("", "1.000000000000000");
-		difficulty_trend_l.push_back (std::make_pair ("", entry));
-
+		auto trend = node.active_difficulty.trend ();
+		if (trend.empty ())
+		{
+			// To keep this RPC backwards-compatible
+			trend.push_back (multiplier_active);
+		}
+		for (auto multiplier : trend)
+		{
+			boost::property_tree::ptree entry;
+			entry.put ("", nano::to_string (multiplier));
+			difficulty_trend_l.push_back (std::make_pair ("", entry));
+		}
 		response_l.add_child ("difficulty_trend", difficulty_trend_l);
 	}
 	response_errors ();
@@ -1311,24 +1295,20 @@ void nano::json_handler::blocks_info ()
 			if (!hash.decode_hex (hash_text))
 			{
 				auto block (node.store.block ().get (*transaction, hash));
-				if (block != nullptr)
+				auto info (node.ledger.block_info (*transaction, hash));
+				if (block != nullptr && info.has_value ())
 				{
 					boost::property_tree::ptree entry;
-					nano::account account (block->account ().is_zero () ? block->sideband ().account () : block->account ());
-					entry.put ("block_account", account.to_account ());
-					bool error_or_pruned (false);
-					auto amount (node.ledger.amount_safe (*transaction, hash, error_or_pruned));
-					if (!error_or_pruned)
+					entry.put ("block_account", info->account.to_account ());
+					if (info->amount.has_value ())
 					{
-						entry.put ("amount", amount.convert_to<std::string> ());
+						entry.put ("amount", info->amount->to_string_dec ());
 					}
-					auto balance (node.ledger.balance (*transaction, hash));
-					entry.put ("balance", balance.convert_to<std::string> ());
-					entry.put ("height", std::to_string (block->sideband ().height ()));
-					entry.put ("local_timestamp", std::to_string (block->sideband ().timestamp ()));
-					entry.put ("successor", block->sideband ().successor ().to_string ());
-					auto confirmed (node.ledger.block_confirmed (*transaction, hash));
-					entry.put ("confirmed", confirmed);
+					entry.put ("balance", info->balance.to_string_dec ());
+					entry.put ("height", std::to_string (info->height));
+					entry.put ("local_timestamp", std::to_string (info->local_timestamp));
+					entry.put ("successor", info->successor.to_string ());
+					entry.put ("confirmed", info->confirmed);
 
 					if (json_block_l)
 					{
@@ -1342,10 +1322,9 @@ void nano::json_handler::blocks_info ()
 						block->serialize_json (contents);
 						entry.put ("contents", contents);
 					}
-					if (block->type () == nano::block_type::state)
+					if (!info->subtype.empty ())
 					{
-						auto subtype (nano::state_subtype (block->sideband ().details ()));
-						entry.put ("subtype", subtype);
+						entry.put ("subtype", info->subtype);
 					}
 					if (receivable || receive_hash)
 					{
@@ -1383,9 +1362,7 @@ void nano::json_handler::blocks_info ()
 							}
 							if (receive_hash)
 							{
-								std::shared_ptr<nano::block> receive_block = node.ledger.find_receive_block_by_send_hash (*transaction, destination, hash);
-								std::string receive_hash = receive_block ? receive_block->hash ().to_string () : nano::block_hash (0).to_string ();
-								entry.put ("receive_hash", receive_hash);
+								entry.put ("receive_hash", node.ledger.receive_hash_for_send (*transaction, hash).to_string ());
 							}
 						}
 					}
@@ -1908,6 +1885,8 @@ void nano::json_handler::bootstrap_status ()
 	response_l.put ("bootstrap_threads", std::to_string (node.config->bootstrap_initiator_threads));
 	response_l.put ("running_attempts_count", std::to_string (attempts_count));
 	response_l.put ("total_attempts_count", std::to_string (node.bootstrap_initiator.attempts.total_attempts ()));
+	response_l.put ("max_pulls_per_attempt", std::to_string (nano::bootstrap_limits::bootstrap_max_pulls_per_attempt));
+	response_l.put ("deduplicated_triggers", std::to_string (node.bootstrap_initiator.deduplicated_triggers ()));
 	boost::property_tree::ptree connections;
 	{
 		nano::lock_guard<nano::mutex> connections_lock (node.bootstrap_initiator.connections->mutex);
@@ -1927,6 +1906,7 @@ void nano::json_handler::bootstrap_status ()
 			entry.put ("id", attempt->id ());
 			entry.put ("mode", attempt->mode_text ());
 			entry.put ("started", static_cast<bool> (attempt->get_started ()));
+			entry.put ("state", attempt->state_text ());
 			entry.put ("pulling", std::to_string (attempt->get_pulling ()));
 			entry.put ("total_blocks", std::to_string (attempt->total_blocks ()));
 			entry.put ("requeued_pulls", std::to_string (attempt->get_requeued_pulls ()));
@@ -1978,6 +1958,8 @@ void nano::json_handler::chain (bool successors)
 
 void nano::json_handler::confirmation_active ()
 {
+	// Upper bound of the number of elections in one response
+	uint64_t const max_count (4096);
 	uint64_t announcements (0);
 	uint64_t confirmed (0);
 	boost::optional<std::string> announcements_text (request.get_optional<std::string> ("announcements"));
@@ -1985,27 +1967,70 @@ void nano::json_handler::confirmation_active ()
 	{
 		announcements = strtoul (announcements_text.get ().c_str (), NULL, 10);
 	}
-	boost::property_tree::ptree elections;
-	auto active_elections = node.active.list_active ();
-	for (auto const & election : active_elections)
+	auto count (std::min (count_optional_impl (max_count), max_count));
+	auto offset (offset_optional_impl (0));
+	bool const details = request.get<bool> ("details", false);
+	if (!ec)
 	{
-		if (election->confirmation_request_count >= announcements)
+		std::vector<std::pair<std::string, std::shared_ptr<nano::election>>> unconfirmed;
+		auto active_elections = node.active.list_active ();
+		for (auto const & election : active_elections)
 		{
-			if (!election->confirmed ())
+			if (election->confirmation_request_count >= announcements)
 			{
-				boost::property_tree::ptree entry;
-				entry.put ("", election->qualified_root.to_string ());
-				elections.push_back (std::make_pair ("", entry));
+				if (!election->confirmed ())
+				{
+					unconfirmed.emplace_back (election->qualified_root.to_string (), election);
+				}
+				else
+				{
+					++confirmed;
+				}
+			}
+		}
+		// Sorted by root, so that the pages are stable while the election set doesn't change
+		std::sort (unconfirmed.begin (), unconfirmed.end (), [] (auto const & a, auto const & b) { return a.first < b.first; });
+		boost::property_tree::ptree elections;
+		for (auto i = std::min<uint64_t> (offset, unconfirmed.size ()), n = std::min<uint64_t> (offset + count, unconfirmed.size ()); i < n; ++i)
+		{
+			auto const & [root, election] = unconfirmed[i];
+			boost::property_tree::ptree entry;
+			if (details)
+			{
+				auto info = election->current_status ();
+				entry.put ("root", root);
+				entry.put ("announcements", std::to_string (info.status.get_confirmation_request_count ()));
+				entry.put ("voters", std::to_string (info.votes.size ()));
+				entry.put ("winner", info.status.get_winner ()->hash ().to_string ());
+				entry.put ("final_tally", info.status.get_final_tally ().to_string_dec ());
+				entry.put ("quorum", !info.tally.empty () && election->have_quorum (info.tally));
+				nano::uint128_t total (0);
+				boost::property_tree::ptree blocks;
+				for (auto const & [tally, block] : info.tally)
+				{
+					total += tally;
+					boost::property_tree::ptree block_entry;
+					block_entry.put ("hash", block->hash ().to_string ());
+					block_entry.put ("tally", tally.convert_to<std::string> ());
+					blocks.push_back (std::make_pair ("", block_entry));
+				}
+				entry.put ("total_tally", total.convert_to<std::string> ());
+				entry.add_child ("blocks", blocks);
 			}
 			else
 			{
-				++confirmed;
+				entry.put ("", root);
 			}
+			elections.push_back (std::make_pair ("", entry));
+		}
+		response_l.add_child ("confirmations", elections);
+		response_l.put ("unconfirmed", unconfirmed.size ());
+		response_l.put ("confirmed", confirmed);
+		if (offset + count < unconfirmed.size ())
+		{
+			response_l.put ("next_offset", offset + count);
 		}
 	}
-	response_l.add_child ("confirmations", elections);
-	response_l.put ("unconfirmed", elections.size ());
-	response_l.put ("confirmed", confirmed);
 	response_errors ();
 }
 
@@ -2124,6 +2149,7 @@ void nano::json_handler::confirmation_info ()
 			}
 			response_l.put ("total_tally", total.convert_to<std::string> ());
 			response_l.put ("final_tally", info.status.get_final_tally ().to_string_dec ());
+			response_l.put ("quorum", !info.tally.empty () && election->have_quorum (info.tally));
 			response_l.add_child ("blocks", blocks);
 		}
 		else
@@ -2162,6 +2188,12 @@ void nano::json_handler::confirmation_quorum ()
 	response_errors ();
 }
 
+void nano::json_handler::database_stats ()
+{
+	node.store.serialize_memory_stats (response_l);
+	response_errors ();
+}
+
 void nano::json_handler::database_txn_tracker ()
 {
 	boost::property_tree::ptree json;
@@ -2353,6 +2385,20 @@ void nano::json_handler::epoch_upgrade ()
 	response_errors ();
 }
 
+void nano::json_handler::epoch_upgrade_progress ()
+{
+	auto transaction (node.store.tx_begin_read ());
+	auto progress (node.ledger.epoch_upgrade_progress (*transaction));
+	boost::property_tree::ptree epochs;
+	epochs.put ("epoch_0", std::to_string (progress.epoch_0));
+	epochs.put ("epoch_1", std::to_string (progress.epoch_1));
+	epochs.put ("epoch_2", std::to_string (progress.epoch_2));
+	response_l.add_child ("accounts_per_epoch", epochs);
+	response_l.put ("unopened_with_receivables", std::to_string (progress.unopened_with_receivables));
+	response_l.put ("unupgraded", std::to_string (progress.unupgraded));
+	response_errors ();
+}
+
 void nano::json_handler::frontiers ()
 {
 	auto start (account_impl ());
@@ -2649,6 +2695,27 @@ void nano::json_handler::account_history ()
 		boost::property_tree::ptree history;
 		bool output_raw (request.get_optional<bool> ("raw") == true);
 		response_l.put ("account", account.to_account ());
+		if (!reverse && !output_raw && accounts_to_filter.empty () && offset == 0)
+		{
+			// The common case is handled by the ledger, which streams the entries without visiting each block here
+			nano::block_hash next;
+			std::stringstream lines (node.ledger.account_history (*transaction, account, hash, count, next));
+			std::string line;
+			while (std::getline (lines, line))
+			{
+				std::stringstream line_stream (line);
+				boost::property_tree::ptree entry;
+				boost::property_tree::read_json (line_stream, entry);
+				history.push_back (std::make_pair ("", entry));
+			}
+			response_l.add_child ("history", history);
+			if (!next.is_zero ())
+			{
+				response_l.put ("previous", next.to_string ());
+			}
+			response_errors ();
+			return;
+		}
 		auto block (node.store.block ().get (*transaction, hash));
 		while (block != nullptr && count > 0)
 		{
@@ -2861,6 +2928,12 @@ void nano::json_handler::ledger ()
 	response_errors ();
 }
 
+void nano::json_handler::ledger_stats ()
+{
+	node.ledger.serialize_stats_report (response_l);
+	response_errors ();
+}
+
 void nano::json_handler::mnano_from_raw (nano::uint128_t ratio)
 {
 	auto amount (amount_impl ());
@@ -2924,6 +2997,12 @@ void nano::json_handler::raw_to_nano ()
 /*
  * @warning This is an internal/diagnostic RPC, do not rely on its interface being stable
  */
+void nano::json_handler::network_params ()
+{
+	node.network_params.serialize_json (response_l);
+	response_errors ();
+}
+
 void nano::json_handler::node_id ()
 {
 	if (!ec)
@@ -3030,6 +3109,11 @@ void nano::json_handler::peers ()
 			}
 			debug_assert (channel->get_type () == nano::transport::transport_type::tcp);
 			pending_tree.put ("type", "tcp");
+			if (auto telemetry = node.telemetry->get_telemetry (channel->get_remote_endpoint ()))
+			{
+				pending_tree.put ("node_version", std::to_string (telemetry->get_major_version ()) + "." + std::to_string (telemetry->get_minor_version ()) + "." + std::to_string (telemetry->get_patch_version ()));
+				pending_tree.put ("bandwidth_cap", std::to_string (telemetry->get_bandwidth_cap ()));
+			}
 			peers_l.push_back (boost::property_tree::ptree::value_type (text.str (), pending_tree));
 		}
 		else
@@ -3038,6 +3122,12 @@ void nano::json_handler::peers ()
 		}
 	}
 	response_l.add_child ("peers", peers_l);
+	if (peer_details)
+	{
+		boost::property_tree::ptree histogram_l;
+		node.telemetry->histogram ().serialize_json (histogram_l);
+		response_l.add_child ("histogram", histogram_l);
+	}
 	response_errors ();
 }
 
@@ -3202,66 +3292,46 @@ void nano::json_handler::process ()
 			std::string subtype_text (rpc_l->request.get<std::string> ("subtype", ""));
 			if (!subtype_text.empty ())
 			{
-				std::shared_ptr<nano::state_block> block_state (std::static_pointer_cast<nano::state_block> (block));
 				auto transaction (rpc_l->node.store.tx_begin_read ());
-				if (!block_state->previous ().is_zero () && !rpc_l->node.store.block ().exists (*transaction, block_state->previous ()))
+				switch (rpc_l->node.ledger.check_subtype (*transaction, *block, subtype_text))
 				{
-					rpc_l->ec = nano::error_process::gap_previous;
+					case nano::subtype_check_result::ok:
+						break;
+					case nano::subtype_check_result::invalid_subtype:
+						rpc_l->ec = nano::error_rpc::invalid_subtype;
+						break;
+					case nano::subtype_check_result::gap_previous:
+						rpc_l->ec = nano::error_process::gap_previous;
+						break;
+					case nano::subtype_check_result::balance_mismatch:
+						rpc_l->ec = nano::error_rpc::invalid_subtype_balance;
+						break;
+					case nano::subtype_check_result::previous_mismatch:
+						rpc_l->ec = nano::error_rpc::invalid_subtype_previous;
+						break;
+					case nano::subtype_check_result::epoch_link_mismatch:
+						rpc_l->ec = nano::error_rpc::invalid_subtype_epoch_link;
+						break;
 				}
-				else
+			}
+		}
+		// Optionally reject blocks whose work wouldn't be competitive under the current network load
+		if (!rpc_l->ec && rpc_l->request.get<bool> ("require_active_difficulty", false))
+		{
+			auto required_difficulty (rpc_l->node.active_difficulty.difficulty ());
+			if (block->type () == nano::block_type::state)
+			{
+				auto transaction (rpc_l->node.store.tx_begin_read ());
+				auto subtype (rpc_l->node.ledger.state_block_subtype (*transaction, *block));
+				if (subtype && *subtype != nano::block_subtype::send && *subtype != nano::block_subtype::change)
 				{
-					auto balance (rpc_l->node.ledger.account_balance (*transaction, block_state->account ()));
-					if (subtype_text == "send")
-					{
-						if (balance <= block_state->balance ().number ())
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_balance;
-						}
-						// Send with previous == 0 fails balance check. No previous != 0 check required
-					}
-					else if (subtype_text == "receive")
-					{
-						if (balance > block_state->balance ().number ())
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_balance;
-						}
-						// Receive can be point to open block. No previous != 0 check required
-					}
-					else if (subtype_text == "open")
-					{
-						if (!block_state->previous ().is_zero ())
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_previous;
-						}
-					}
-					else if (subtype_text == "change")
-					{
-						if (balance != block_state->balance ().number ())
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_balance;
-						}
-						else if (block_state->previous ().is_zero ())
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_previous;
-						}
-					}
-					else if (subtype_text == "epoch")
-					{
-						if (balance != block_state->balance ().number ())
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_balance;
-						}
-						else if (!rpc_l->node.ledger.is_epoch_link (block_state->link ()))
-						{
-							rpc_l->ec = nano::error_rpc::invalid_subtype_epoch_link;
-						}
-					}
-					else
-					{
-						rpc_l->ec = nano::error_rpc::invalid_subtype;
-					}
+					required_difficulty = rpc_l->node.active_difficulty.receive_difficulty ();
 				}
 			}
+			if (rpc_l->node.network_params.work.difficulty (*block) < required_difficulty)
+			{
+				rpc_l->ec = nano::error_rpc::work_below_active_difficulty;
+			}
 		}
 		if (!rpc_l->ec)
 		{
@@ -3345,6 +3415,12 @@ void nano::json_handler::process ()
 								}
 								break;
 							}
+							case nano::process_result::confirmed_fork:
+							{
+								// Forcing can't roll back a cemented block
+								rpc_l->ec = nano::error_process::fork;
+								break;
+							}
 							case nano::process_result::insufficient_work:
 							{
 								rpc_l->ec = nano::error_process::insufficient_work;
@@ -3947,7 +4023,7 @@ void nano::json_handler::stats ()
 	}
 	else if (type == "objects")
 	{
-		construct_json (collect_container_info (node, "node").get (), response_l);
+		construct_json (node.collect_container_info ("node").get (), response_l);
 	}
 	else if (type == "samples")
 	{
@@ -3958,6 +4034,22 @@ void nano::json_handler::stats ()
 	{
 		node.store.serialize_memory_stats (response_l);
 	}
+	else if (type == "confirmation_latency")
+	{
+		node.confirmation_latency.serialize_json (response_l);
+	}
+	else if (type == "block_traces")
+	{
+		node.block_tracer.serialize_json (response_l);
+	}
+	else if (type == "dead_letters")
+	{
+		node.block_processor.dead_letters.serialize_json (response_l);
+	}
+	else if (type == "work_peers")
+	{
+		node.distributed_work.health.serialize_json (response_l);
+	}
 	else
 	{
 		ec = nano::error_rpc::invalid_missing_type;
@@ -4857,6 +4949,36 @@ void nano::json_handler::wallet_receivable ()
 	response_errors ();
 }
 
+void nano::json_handler::wallet_receive_minimum ()
+{
+	auto wallet (wallet_impl ());
+	if (!ec)
+	{
+		auto transaction (node.wallets.tx_begin_read ());
+		response_l.put ("amount", nano::amount (wallet->receive_minimum (*transaction)).to_string_dec ());
+		response_l.put ("default", wallet->store.receive_minimum (*transaction) ? "0" : "1");
+	}
+	response_errors ();
+}
+
+void nano::json_handler::wallet_receive_minimum_set ()
+{
+	auto wallet (wallet_impl ());
+	// Without an amount the wallet uses the receive minimum of the node config again
+	std::optional<nano::amount> minimum;
+	if (request.count ("amount"))
+	{
+		minimum = amount_impl ();
+	}
+	if (!ec)
+	{
+		auto transaction (node.wallets.tx_begin_write ());
+		wallet->store.receive_minimum_set (*transaction, minimum);
+		response_l.put ("success", "");
+	}
+	response_errors ();
+}
+
 void nano::json_handler::wallet_representative ()
 {
 	auto wallet (wallet_impl ());
@@ -5244,6 +5366,25 @@ void nano::json_handler::work_peers_clear ()
 	response_errors ();
 }
 
+void nano::json_handler::work_threshold_set ()
+{
+	uint64_t threshold (0);
+	boost::optional<std::string> threshold_text (request.get_optional<std::string> ("threshold"));
+	if (threshold_text.is_initialized () && nano::from_string_hex (threshold_text.get (), threshold))
+	{
+		ec = nano::error_rpc::bad_difficulty_format;
+	}
+	if (!ec && !node.network_params.set_work_threshold_override (threshold))
+	{
+		ec = nano::error_rpc::work_threshold_override_disabled;
+	}
+	if (!ec)
+	{
+		response_l.put ("success", "");
+	}
+	response_errors ();
+}
+
 void nano::json_handler::populate_backlog ()
 {
 	node.backlog.trigger ();
@@ -5373,17 +5514,21 @@ ipc_json_handler_no_arg_func_map create_ipc_json_handler_no_arg_func_map ()
 	no_arg_funcs.emplace ("confirmation_history", &nano::json_handler::confirmation_history);
 	no_arg_funcs.emplace ("confirmation_info", &nano::json_handler::confirmation_info);
 	no_arg_funcs.emplace ("confirmation_quorum", &nano::json_handler::confirmation_quorum);
+	no_arg_funcs.emplace ("database_stats", &nano::json_handler::database_stats);
 	no_arg_funcs.emplace ("database_txn_tracker", &nano::json_handler::database_txn_tracker);
 	no_arg_funcs.emplace ("delegators", &nano::json_handler::delegators);
 	no_arg_funcs.emplace ("delegators_count", &nano::json_handler::delegators_count);
 	no_arg_funcs.emplace ("deterministic_key", &nano::json_handler::deterministic_key);
 	no_arg_funcs.emplace ("epoch_upgrade", &nano::json_handler::epoch_upgrade);
+	no_arg_funcs.emplace ("epoch_upgrade_progress", &nano::json_handler::epoch_upgrade_progress);
 	no_arg_funcs.emplace ("frontiers", &nano::json_handler::frontiers);
 	no_arg_funcs.emplace ("frontier_count", &nano::json_handler::account_count);
 	no_arg_funcs.emplace ("keepalive", &nano::json_handler::keepalive);
 	no_arg_funcs.emplace ("key_create", &nano::json_handler::key_create);
 	no_arg_funcs.emplace ("key_expand", &nano::json_handler::key_expand);
 	no_arg_funcs.emplace ("ledger", &nano::json_handler::ledger);
+	no_arg_funcs.emplace ("ledger_stats", &nano::json_handler::ledger_stats);
+	no_arg_funcs.emplace ("network_params", &nano::json_handler::network_params);
 	no_arg_funcs.emplace ("node_id", &nano::json_handler::node_id);
 	no_arg_funcs.emplace ("node_id_delete", &nano::json_handler::node_id_delete);
 	no_arg_funcs.emplace ("password_change", &nano::json_handler::password_change);
@@ -5437,6 +5582,8 @@ ipc_json_handler_no_arg_func_map create_ipc_json_handler_no_arg_func_map ()
 	no_arg_funcs.emplace ("wallet_lock", &nano::json_handler::wallet_lock);
 	no_arg_funcs.emplace ("wallet_pending", &nano::json_handler::wallet_pending);
 	no_arg_funcs.emplace ("wallet_receivable", &nano::json_handler::wallet_receivable);
+	no_arg_funcs.emplace ("wallet_receive_minimum", &nano::json_handler::wallet_receive_minimum);
+	no_arg_funcs.emplace ("wallet_receive_minimum_set", &nano::json_handler::wallet_receive_minimum_set);
 	no_arg_funcs.emplace ("wallet_representative", &nano::json_handler::wallet_representative);
 	no_arg_funcs.emplace ("wallet_representative_set", &nano::json_handler::wallet_representative_set);
 	no_arg_funcs.emplace ("wallet_republish", &nano::json_handler::wallet_republish);
@@ -5449,6 +5596,7 @@ ipc_json_handler_no_arg_func_map create_ipc_json_handler_no_arg_func_map ()
 	no_arg_funcs.emplace ("work_peer_add", &nano::json_handler::work_peer_add);
 	no_arg_funcs.emplace ("work_peers", &nano::json_handler::work_peers);
 	no_arg_funcs.emplace ("work_peers_clear", &nano::json_handler::work_peers_clear);
+	no_arg_funcs.emplace ("work_threshold_set", &nano::json_handler::work_threshold_set);
 	no_arg_funcs.emplace ("populate_backlog", &nano::json_handler::populate_backlog);
 	no_arg_funcs.emplace ("debug_bootstrap_priority_info", &nano::json_handler::debug_bootstrap_priority_info);
 	return no_arg_funcs;
@@ -66,20 +66,24 @@ public:
 	void confirmation_quorum ();
 	void confirmation_height_currently_processing ();
 	void debug_bootstrap_priority_info ();
+	void database_stats ();
 	void database_txn_tracker ();
 	void delegators ();
 	void delegators_count ();
 	void deterministic_key ();
 	void epoch_upgrade ();
+	void epoch_upgrade_progress ();
 	void frontiers ();
 	void keepalive ();
 	void key_create ();
 	void key_expand ();
 	void ledger ();
+	void ledger_stats ();
 	void mnano_to_raw (nano::uint128_t = nano::Mxrb_ratio);
 	void mnano_from_raw (nano::uint128_t = nano::Mxrb_ratio);
 	void nano_to_raw ();
 	void raw_to_nano ();
+	void network_params ();
 	void node_id ();
 	void node_id_delete ();
 	void password_change ();
@@ -133,6 +137,8 @@ public:
 	void wallet_lock ();
 	void wallet_pending ();
 	void wallet_receivable ();
+	void wallet_receive_minimum ();
+	void wallet_receive_minimum_set ();
 	void wallet_representative ();
 	void wallet_representative_set ();
 	void wallet_republish ();
@@ -144,6 +150,7 @@ public:
 	void work_peer_add ();
 	void work_peers ();
 	void work_peers_clear ();
+	void work_threshold_set ();
 	void work_set ();
 	void work_validate ();
 	std::string body;
@@ -99,6 +99,11 @@ bool nano::lmdb::store::copy_db (boost::filesystem::path const & destination_fil
 	return !rsnano::rsn_lmdb_store_copy_db (handle, reinterpret_cast<const int8_t *> (destination_file.string ().c_str ()));
 }
 
+bool nano::lmdb::store::vacuum (boost::filesystem::path const & target, boost::filesystem::path const & backup, bool clear_peers)
+{
+	return !rsnano::rsn_lmdb_store_vacuum (handle, reinterpret_cast<const int8_t *> (target.string ().c_str ()), reinterpret_cast<const int8_t *> (backup.string ().c_str ()), clear_peers);
+}
+
 void nano::lmdb::store::rebuild_db (nano::write_transaction const & transaction_a)
 {
 	rsnano::rsn_lmdb_store_rebuild_db (handle, transaction_a.get_rust_handle ());
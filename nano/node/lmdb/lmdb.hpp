@@ -93,6 +93,7 @@ namespace lmdb
 		nano::final_vote_store & final_vote () override;
 		nano::version_store & version () override;
 		bool copy_db (boost::filesystem::path const & destination_file) override;
+		bool vacuum (boost::filesystem::path const & target, boost::filesystem::path const & backup, bool clear_peers) override;
 		void rebuild_db (nano::write_transaction const & transaction_a) override;
 		bool init_error () const override;
 		rsnano::LmdbStoreHandle * get_handle () const override;
@@ -1,5 +1,6 @@
 #include <nano/node/lmdb/lmdb.hpp>
 #include <nano/node/lmdb/peer_store.hpp>
+#include <nano/lib/rsnanoutils.hpp>
 
 namespace
 {
@@ -25,9 +26,9 @@ nano::lmdb::peer_store::~peer_store ()
 		rsnano::rsn_lmdb_peer_store_destroy (handle);
 }
 
-void nano::lmdb::peer_store::put (nano::write_transaction const & transaction, nano::endpoint_key const & endpoint)
+void nano::lmdb::peer_store::put (nano::write_transaction const & transaction, nano::endpoint_key const & endpoint, uint64_t last_seen, uint64_t last_handshake)
 {
-	rsnano::rsn_lmdb_peer_store_put (handle, transaction.get_rust_handle (), endpoint.address_bytes ().data (), endpoint.port ());
+	rsnano::rsn_lmdb_peer_store_put (handle, transaction.get_rust_handle (), endpoint.address_bytes ().data (), endpoint.port (), last_seen, last_handshake);
 }
 
 void nano::lmdb::peer_store::del (nano::write_transaction const & transaction, nano::endpoint_key const & endpoint)
@@ -40,6 +41,32 @@ bool nano::lmdb::peer_store::exists (nano::transaction const & transaction, nano
 	return rsnano::rsn_lmdb_peer_store_exists (handle, transaction.get_rust_handle (), endpoint.address_bytes ().data (), endpoint.port ());
 }
 
+bool nano::lmdb::peer_store::get (nano::transaction const & transaction, nano::endpoint_key const & endpoint, uint64_t & last_seen, uint64_t & last_handshake) const
+{
+	return rsnano::rsn_lmdb_peer_store_get (handle, transaction.get_rust_handle (), endpoint.address_bytes ().data (), endpoint.port (), &last_seen, &last_handshake);
+}
+
+std::vector<nano::endpoint> nano::lmdb::peer_store::prioritized (nano::transaction const & transaction, std::size_t max) const
+{
+	auto list_handle = rsnano::rsn_lmdb_peer_store_prioritized (handle, transaction.get_rust_handle (), max);
+	auto len = rsnano::rsn_endpoint_list_len (list_handle);
+	std::vector<nano::endpoint> endpoints;
+	endpoints.reserve (len);
+	for (auto i = 0; i < len; ++i)
+	{
+		rsnano::EndpointDto dto;
+		rsnano::rsn_endpoint_list_get (list_handle, i, &dto);
+		endpoints.push_back (rsnano::dto_to_udp_endpoint (dto));
+	}
+	rsnano::rsn_endpoint_list_destroy (list_handle);
+	return endpoints;
+}
+
+std::size_t nano::lmdb::peer_store::prune (nano::write_transaction const & transaction, uint64_t cutoff)
+{
+	return rsnano::rsn_lmdb_peer_store_prune (handle, transaction.get_rust_handle (), cutoff);
+}
+
 size_t nano::lmdb::peer_store::count (nano::transaction const & transaction) const
 {
 	return rsnano::rsn_lmdb_peer_store_count (handle, transaction.get_rust_handle ());
@@ -17,9 +17,12 @@ namespace lmdb
 		~peer_store ();
 		peer_store (peer_store const &) = delete;
 		peer_store (peer_store &&) = delete;
-		void put (nano::write_transaction const & transaction_a, nano::endpoint_key const & endpoint_a) override;
+		void put (nano::write_transaction const & transaction_a, nano::endpoint_key const & endpoint_a, uint64_t last_seen_a, uint64_t last_handshake_a) override;
 		void del (nano::write_transaction const & transaction_a, nano::endpoint_key const & endpoint_a) override;
 		bool exists (nano::transaction const & transaction_a, nano::endpoint_key const & endpoint_a) const override;
+		bool get (nano::transaction const & transaction_a, nano::endpoint_key const & endpoint_a, uint64_t & last_seen_a, uint64_t & last_handshake_a) const override;
+		std::vector<nano::endpoint> prioritized (nano::transaction const & transaction_a, std::size_t max_a) const override;
+		std::size_t prune (nano::write_transaction const & transaction_a, uint64_t cutoff_a) override;
 		size_t count (nano::transaction const & transaction_a) const override;
 		void clear (nano::write_transaction const & transaction_a) override;
 		nano::store_iterator<nano::endpoint_key, nano::no_value> begin (nano::transaction const & transaction_a) const override;
@@ -111,7 +111,12 @@ void nano::message_header::flag_set (uint8_t flag_a, bool enable)
 
 nano::networks nano::message_header::get_network () const
 {
-	return static_cast<nano::networks> (rsnano::rsn_message_header_network (handle));
+	return static_cast<nano::networks> (get_network_id ());
+}
+
+uint16_t nano::message_header::get_network_id () const
+{
+	return rsnano::rsn_message_header_network (handle);
 }
 
 void nano::message_header::set_network (nano::networks network)
@@ -173,7 +178,7 @@ std::shared_ptr<std::vector<uint8_t>> nano::message::to_bytes () const
 
 nano::shared_const_buffer nano::message::to_shared_const_buffer () const
 {
-	return shared_const_buffer (to_bytes ());
+	return shared_const_buffer (rsnano::rsn_message_to_shared_buffer (handle));
 }
 
 nano::message_header nano::message::get_header () const
@@ -1566,6 +1571,12 @@ bool nano::telemetry_data::operator!= (nano::telemetry_data const & data_a) cons
 	return !(*this == data_a);
 }
 
+void nano::telemetry_data::remove_unsupported_fields (nano::network_constants const & constants, uint8_t peer_version)
+{
+	auto constants_dto{ constants.to_dto () };
+	rsnano::rsn_telemetry_data_remove_unsupported_fields (handle, &constants_dto, peer_version);
+}
+
 void nano::telemetry_data::sign (nano::keypair const & node_id_a)
 {
 	if (!rsnano::rsn_telemetry_data_sign (handle, node_id_a.prv.bytes.data ()))
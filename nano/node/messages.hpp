@@ -75,6 +75,7 @@ public:
 	static uint8_t constexpr frontier_req_only_confirmed = 1;
 
 	nano::networks get_network () const;
+	uint16_t get_network_id () const;
 	void set_network (nano::networks network);
 	uint8_t get_version_using () const;
 	void set_version_using (uint8_t version_a);
@@ -259,6 +260,8 @@ public:
 	void deserialize (nano::stream &, uint16_t);
 	nano::error serialize_json (nano::jsonconfig &, bool) const;
 	nano::error deserialize_json (nano::jsonconfig &, bool);
+	/** Removes the data which a peer with the given protocol version doesn't understand. Has to be called before signing */
+	void remove_unsupported_fields (nano::network_constants const &, uint8_t peer_version);
 	void sign (nano::keypair const &);
 	bool validate_signature () const;
 	bool operator== (nano::telemetry_data const &) const;
@@ -7,6 +7,7 @@
 #include <nano/node/network.hpp>
 #include <nano/node/node.hpp>
 #include <nano/node/telemetry.hpp>
+#include <nano/node/transport/inproc.hpp>
 
 #include <boost/format.hpp>
 
@@ -20,7 +21,7 @@ nano::network::network (nano::node & node_a, uint16_t port_a) :
 	id (nano::network_constants::active_network ()),
 	syn_cookies{ std::make_shared<nano::syn_cookies> (node_a.network_params.network.max_peers_per_ip) },
 	inbound{ [this] (nano::message const & message, std::shared_ptr<nano::transport::channel> const & channel) {
-		debug_assert (message.get_header ().get_network () == node.network_params.network.current_network);
+		debug_assert (message.get_header ().get_network_id () == node.network_params.network.network_id);
 		debug_assert (message.get_header ().get_version_using () >= node.network_params.network.protocol_version_min);
 		process_message (message, channel);
 	} },
@@ -95,6 +96,10 @@ void nano::network::start ()
 	{
 		tcp_channels->start ();
 	}
+	if (node.flags.enable_loopback_channel ())
+	{
+		loopback_channel = std::make_shared<nano::transport::inproc::channel> (node);
+	}
 	ongoing_keepalive ();
 }
 
@@ -117,7 +122,7 @@ void nano::network::send_keepalive (std::shared_ptr<nano::transport::channel> co
 {
 	nano::keepalive message{ node.network_params.network };
 	std::array<nano::endpoint, 8> peers;
-	tcp_channels->random_fill (peers);
+	tcp_channels->sample_keepalive_peers (peers, channel_a->get_remote_endpoint ());
 	message.set_peers (peers);
 	channel_a->send (message);
 }
@@ -169,7 +174,7 @@ void nano::network::flood_keepalive (float const scale_a)
 {
 	nano::keepalive message{ node.network_params.network };
 	auto peers{ message.get_peers () };
-	tcp_channels->random_fill (peers);
+	tcp_channels->sample_keepalive_peers (peers);
 	message.set_peers (peers);
 	flood_message (message, nano::transport::buffer_drop_policy::limiter, scale_a);
 }
@@ -185,12 +190,15 @@ void nano::network::flood_keepalive_self (float const scale_a)
 
 void nano::network::flood_block (std::shared_ptr<nano::block> const & block_a, nano::transport::buffer_drop_policy const drop_policy_a)
 {
+	node.block_tracer.record (block_a->hash (), nano::block_trace_event::broadcast);
 	nano::publish message (node.network_params.network, block_a);
 	flood_message (message, drop_policy_a);
+	send_loopback (message);
 }
 
 void nano::network::flood_block_initial (std::shared_ptr<nano::block> const & block_a)
 {
+	node.block_tracer.record (block_a->hash (), nano::block_trace_event::broadcast);
 	nano::publish message (node.network_params.network, block_a);
 	for (auto const & i : node.rep_crawler.principal_representatives ())
 	{
@@ -200,6 +208,7 @@ void nano::network::flood_block_initial (std::shared_ptr<nano::block> const & bl
 	{
 		i->send (message, nullptr, nano::transport::buffer_drop_policy::no_limiter_drop);
 	}
+	send_loopback (message);
 }
 
 void nano::network::flood_vote (std::shared_ptr<nano::vote> const & vote_a, float scale)
@@ -209,6 +218,7 @@ void nano::network::flood_vote (std::shared_ptr<nano::vote> const & vote_a, floa
 	{
 		i->send (message, nullptr);
 	}
+	send_loopback (message);
 }
 
 void nano::network::flood_vote_pr (std::shared_ptr<nano::vote> const & vote_a)
@@ -218,6 +228,15 @@ void nano::network::flood_vote_pr (std::shared_ptr<nano::vote> const & vote_a)
 	{
 		i.get_channel ()->send (message, nullptr, nano::transport::buffer_drop_policy::no_limiter_drop);
 	}
+	send_loopback (message);
+}
+
+void nano::network::send_loopback (nano::message & message_a)
+{
+	if (loopback_channel)
+	{
+		loopback_channel->send (message_a, nullptr, nano::transport::buffer_drop_policy::no_limiter_drop);
+	}
 }
 
 void nano::network::flood_block_many (std::deque<std::shared_ptr<nano::block>> blocks_a, std::function<void ()> callback_a, unsigned delay_a)
@@ -482,7 +501,7 @@ public:
 		nano::telemetry_ack telemetry_ack{ node.network_params.network };
 		if (!node.flags.disable_providing_telemetry_metrics ())
 		{
-			auto telemetry_data = node.local_telemetry ();
+			auto telemetry_data = node.local_telemetry (channel->get_network_version ());
 			telemetry_ack = nano::telemetry_ack{ node.network_params.network, telemetry_data };
 		}
 		channel->send (telemetry_ack, nullptr, nano::transport::buffer_drop_policy::no_socket_drop);
@@ -592,7 +611,7 @@ std::vector<std::shared_ptr<nano::transport::channel>> nano::network::random_cha
 
 void nano::network::fill_keepalive_self (std::array<nano::endpoint, 8> & target_a) const
 {
-	tcp_channels->random_fill (target_a);
+	tcp_channels->sample_keepalive_peers (target_a);
 	// We will clobber values in index 0 and 1 and if there are only 2 nodes in the system, these are the only positions occupied
 	// Move these items to index 2 and 3 so they propagate
 	target_a[2] = target_a[0];
@@ -105,6 +105,7 @@ public:
 
 private:
 	void process_message (nano::message const &, std::shared_ptr<nano::transport::channel> const &);
+	void send_loopback (nano::message &);
 
 public:
 	std::function<void (nano::message const &, std::shared_ptr<nano::transport::channel> const &)> inbound;
@@ -112,6 +113,8 @@ public:
 	std::vector<boost::thread> packet_processing_threads;
 	nano::node & node;
 	std::shared_ptr<nano::transport::tcp_channels> tcp_channels;
+	/** Delivers own flooded blocks and votes to this node. Only set with the `enable_loopback_channel` node flag */
+	std::shared_ptr<nano::transport::channel> loopback_channel;
 	std::function<void ()> disconnect_observer;
 
 public:
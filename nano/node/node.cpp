@@ -53,9 +53,31 @@ nano::vote_cache::config nano::nodeconfig_to_vote_cache_config (node_config cons
 {
 	vote_cache::config cfg{};
 	cfg.max_size = flags.inactive_votes_cache_size ();
+	cfg.eviction_policy = flags.inactive_votes_cache_eviction ();
+	cfg.retain_promoted = flags.inactive_votes_cache_retain ();
 	return cfg;
 }
 
+nano::generate_cache nano::ledger_generate_cache (nano::node_config const & config, nano::node_flags const & flags)
+{
+	auto generate_cache = flags.generate_cache ();
+	if (config.receivable_cache)
+	{
+		generate_cache.enable_receivable (true);
+	}
+	return generate_cache;
+}
+
+std::shared_ptr<nano::node_config> nano::node_config_with_work_override (nano::node_config const & config_a)
+{
+	auto config = std::make_shared<nano::node_config> (config_a);
+	if (config->work_threshold_override != 0)
+	{
+		config->network_params.set_work_threshold_override (config->work_threshold_override);
+	}
+	return config;
+}
+
 nano::outbound_bandwidth_limiter::config nano::outbound_bandwidth_limiter_config (const nano::node_config & config)
 {
 	outbound_bandwidth_limiter::config cfg{};
@@ -150,8 +172,8 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 	io_ctx (async_rt_a.io_ctx),
 	node_initialized_latch (1),
 	observers{ std::make_shared<nano::node_observers> () },
-	config{ std::make_shared<nano::node_config> (config_a) },
-	network_params{ config_a.network_params },
+	config{ nano::node_config_with_work_override (config_a) },
+	network_params{ config->network_params },
 	logger{ std::make_shared<nano::logger_mt> (config_a.logging.min_time_between_log_output) },
 	node_id{ nano::load_or_create_node_id (application_path_a, *logger) },
 	stats{ std::make_shared<nano::stats> (config_a.stats_config) },
@@ -162,10 +184,10 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 	distributed_work (*this),
 	store_impl (nano::make_store (logger, application_path_a, network_params.ledger, flags.read_only (), true, config_a.diagnostics_config.txn_tracking, config_a.block_processor_batch_max_time, config_a.lmdb_config, config_a.backup_before_upgrade)),
 	store (*store_impl),
-	unchecked{ *stats, flags.disable_block_processor_unchecked_deletion () },
+	unchecked{ *stats, flags.disable_block_processor_unchecked_deletion (), config_a.max_unchecked_blocks },
 	wallets_store_impl (std::make_unique<nano::mdb_wallets_store> (application_path_a / "wallets.ldb", config_a.lmdb_config)),
 	wallets_store (*wallets_store_impl),
-	ledger (store, *stats, network_params.ledger, flags_a.generate_cache ()),
+	ledger (store, *stats, network_params.ledger, nano::ledger_generate_cache (config_a, flags_a)),
 	checker (config_a.signature_checker_threads),
 	outbound_limiter{ outbound_bandwidth_limiter_config (config_a) },
 	// empty `config.peering_port` means the user made no port choice at all;
@@ -186,9 +208,11 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 	application_path (application_path_a),
 	port_mapping (*this),
 	rep_crawler (*this),
-	vote_processor (checker, active, *observers, *stats, *config, flags, *logger, online_reps, rep_crawler, ledger, network_params),
+	vote_processor (active, *observers, *stats, *config, flags, *logger, online_reps, rep_crawler, ledger, network_params),
 	warmed_up (0),
 	block_arrival{},
+	confirmation_latency{},
+	block_tracer{ flags_a.block_trace_sample_interval () },
 	block_processor (*this, write_database_queue),
 	gap_cache (*this),
 	online_reps (ledger, *config),
@@ -212,7 +236,9 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 	block_broadcast{ *network, block_arrival, !flags.disable_block_processor_republishing () },
 	block_publisher{ active },
 	gap_tracker{ gap_cache },
-	process_live_dispatcher{ ledger, scheduler.buckets, inactive_vote_cache, websocket }
+	active_difficulty{ network_params.work },
+	process_live_dispatcher{ ledger, scheduler.buckets, inactive_vote_cache, websocket },
+	resource_watchdog{ application_path, *config, logger }
 {
 	logger->always_log ("Node ID: ", node_id.pub.to_node_id ());
 	network->tcp_channels->set_observer (tcp_listener);
@@ -223,6 +249,7 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 	block_broadcast.connect (block_processor);
 	block_publisher.connect (block_processor);
 	gap_tracker.connect (block_processor);
+	active_difficulty.connect (block_processor);
 	process_live_dispatcher.connect (block_processor);
 	unchecked.set_satisfied_observer ([this] (nano::unchecked_info const & info) {
 		this->block_processor.add (info.get_block ());
@@ -259,7 +286,9 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 				if ((status_a.get_election_status_type () == nano::election_status_type::active_confirmed_quorum || status_a.get_election_status_type () == nano::election_status_type::active_confirmation_height) && this->block_arrival.recent (block_a->hash ()))
 				{
 					auto node_l (shared_from_this ());
-					background ([node_l, block_a, account_a, amount_a, is_state_send_a, is_state_epoch_a] () {
+					// The events are serialized in the worker pool, so they have to be put back into cementing order
+					auto sequence (callback_notification_buffer.next_sequence (account_a));
+					background ([node_l, block_a, account_a, amount_a, is_state_send_a, is_state_epoch_a, sequence] () {
 						boost::property_tree::ptree event;
 						event.add ("account", account_a.to_account ());
 						event.add ("hash", block_a->hash ().to_string ());
@@ -297,19 +326,21 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 						auto port (node_l->config->callback_port);
 						auto target (std::make_shared<std::string> (node_l->config->callback_target));
 						auto resolver (std::make_shared<boost::asio::ip::tcp::resolver> (node_l->io_ctx));
-						resolver->async_resolve (boost::asio::ip::tcp::resolver::query (address, std::to_string (port)), [node_l, address, port, target, body, resolver] (boost::system::error_code const & ec, boost::asio::ip::tcp::resolver::iterator i_a) {
-							if (!ec)
-							{
-								node_l->do_rpc_callback (i_a, address, port, target, body, resolver);
-							}
-							else
-							{
-								if (node_l->config->logging.callback_logging ())
+						node_l->callback_notification_buffer.push (account_a, sequence, [node_l, address, port, target, body, resolver] () {
+							resolver->async_resolve (boost::asio::ip::tcp::resolver::query (address, std::to_string (port)), [node_l, address, port, target, body, resolver] (boost::system::error_code const & ec, boost::asio::ip::tcp::resolver::iterator i_a) {
+								if (!ec)
 								{
-									node_l->logger->always_log (boost::str (boost::format ("Error resolving callback: %1%:%2%: %3%") % address % port % ec.message ()));
+									node_l->do_rpc_callback (i_a, address, port, target, body, resolver);
 								}
-								node_l->stats->inc (nano::stat::type::error, nano::stat::detail::http_callback, nano::stat::dir::out);
-							}
+								else
+								{
+									if (node_l->config->logging.callback_logging ())
+									{
+										node_l->logger->always_log (boost::str (boost::format ("Error resolving callback: %1%:%2%: %3%") % address % port % ec.message ()));
+									}
+									node_l->stats->inc (nano::stat::type::error, nano::stat::detail::http_callback, nano::stat::dir::out);
+								}
+							});
 						});
 					});
 				}
@@ -358,6 +389,28 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 			this->distributed_work.cancel (root_a);
 		});
 
+		ledger.set_rep_weight_observer (
+		[this] (nano::account const & account_a, nano::account const & old_representative_a, nano::account const & new_representative_a) {
+			this->observers->representative_changed.notify (account_a, old_representative_a, new_representative_a);
+		},
+		[this] (nano::account const & representative_a, nano::uint128_t const & threshold_a, nano::uint128_t const & weight_a, bool above_a) {
+			this->observers->rep_weight_threshold_crossed.notify (representative_a, threshold_a, weight_a, above_a);
+		});
+		ledger.set_rep_weight_thresholds ({ minimum_principal_weight () });
+
+		// Re-evaluate which representatives matter when one becomes or stops being a principal representative
+		observers->rep_weight_threshold_crossed.add ([this] (nano::account const & representative_a, nano::uint128_t const &, nano::uint128_t const &, bool above_a) {
+			if (above_a)
+			{
+				this->telemetry->trigger ();
+			}
+			else
+			{
+				this->rep_crawler.evict (representative_a);
+			}
+		});
+
+		rsnano::rsn_log_panics (nano::to_logger_handle (logger));
 		logger->always_log ("Node starting, version: ", NANO_VERSION_STRING);
 		logger->always_log ("Build information: ", BUILD_INFO);
 		logger->always_log ("Database backend: ", store.vendor_get ());
@@ -395,6 +448,17 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 			std::exit (1);
 		}
 
+		auto const integrity_problems = ledger.check_integrity (flags.generate_cache ());
+		if (!integrity_problems.empty ())
+		{
+			auto const str = "Ledger integrity check failed:\n" + integrity_problems;
+			logger->always_log (str);
+			std::cerr << str << std::endl;
+			std::exit (1);
+		}
+
+		logger->always_log (ledger.stats_report ());
+
 		if (config->enable_voting)
 		{
 			std::ostringstream stream;
@@ -411,20 +475,43 @@ nano::node::node (rsnano::async_runtime & async_rt_a, boost::filesystem::path co
 			logger->always_log (stream.str ());
 		}
 
+		ledger.set_max_rollback_depth (config->max_rollback_depth);
+		ledger.set_reject_confirmed_forks (config->reject_confirmed_forks);
+
+		if (config->work_threshold_override != 0)
+		{
+			if (network_params.work.dto.threshold_override != 0)
+			{
+				logger->always_log (boost::str (boost::format ("Work thresholds are overridden with %1%") % nano::to_string_hex (config->work_threshold_override)));
+			}
+			else
+			{
+				logger->always_log ("Ignoring work_threshold_override, because only the dev and test network allow it");
+			}
+		}
+
+		if (!flags.read_only ())
+		{
+			auto const transaction (store.tx_begin_write ());
+			auto const recovered = ledger.recover_rollback_journal (*transaction);
+			if (recovered > 0)
+			{
+				logger->always_log (boost::str (boost::format ("Completed %1% interrupted rollback(s)") % recovered));
+			}
+		}
+
 		if ((network_params.network.is_live_network () || network_params.network.is_beta_network ()) && !flags.inactive_node ())
 		{
-			auto const bootstrap_weights = get_bootstrap_weights ();
+			load_bootstrap_weights ();
 			// Use bootstrap weights if initial bootstrap is not completed
-			const bool use_bootstrap_weight = ledger.cache.block_count () < bootstrap_weights.first;
+			const bool use_bootstrap_weight = !ledger.bootstrap_weight_reached ();
 			if (use_bootstrap_weight)
 			{
-				ledger.set_bootstrap_weights (bootstrap_weights.second);
 				for (auto const & rep : ledger.get_bootstrap_weights ())
 				{
 					logger->always_log ("Using bootstrap rep weight: ", rep.first.to_account (), " -> ", nano::uint128_union (rep.second).format_balance (Mxrb_ratio, 0, true), " XRB");
 				}
 			}
-			ledger.set_bootstrap_weight_max_blocks (bootstrap_weights.first);
 
 			// Drop unchecked blocks if initial bootstrap is completed
 			if (!flags.disable_unchecked_drop () && !use_bootstrap_weight && !flags.read_only ())
@@ -549,37 +636,40 @@ bool nano::node::copy_with_compaction (boost::filesystem::path const & destinati
 	return store.copy_db (destination);
 }
 
-std::unique_ptr<nano::container_info_component> nano::collect_container_info (node & node, std::string const & name)
+std::unique_ptr<nano::container_info_component> nano::node::collect_container_info (std::string const & name)
 {
 	auto composite = std::make_unique<container_info_composite> (name);
-	composite->add_component (collect_container_info (node.work, "work"));
-	composite->add_component (collect_container_info (node.gap_cache, "gap_cache"));
-	composite->add_component (collect_container_info (node.ledger, "ledger"));
-	composite->add_component (collect_container_info (node.active, "active"));
-	composite->add_component (collect_container_info (node.bootstrap_initiator, "bootstrap_initiator"));
-	composite->add_component (collect_container_info (*node.tcp_listener, "tcp_listener"));
-	composite->add_component (collect_container_info (*node.network, "network"));
-	composite->add_component (node.telemetry->collect_container_info ("telemetry"));
-	composite->add_component (collect_container_info (*node.workers, "workers"));
-	composite->add_component (collect_container_info (*node.observers, "observers"));
-	composite->add_component (collect_container_info (node.wallets, "wallets"));
-	composite->add_component (collect_container_info (node.vote_processor, "vote_processor"));
-	composite->add_component (collect_container_info (node.rep_crawler, "rep_crawler"));
-	composite->add_component (collect_container_info (node.block_processor, "block_processor"));
-	composite->add_component (collect_container_info (node.block_arrival, "block_arrival"));
-	composite->add_component (collect_container_info (node.online_reps, "online_reps"));
-	composite->add_component (collect_container_info (node.history, "history"));
-	composite->add_component (collect_container_info (node.block_uniquer, "block_uniquer"));
-	composite->add_component (collect_container_info (node.vote_uniquer, "vote_uniquer"));
-	composite->add_component (node.confirmation_height_processor.collect_container_info ("confirmation_height_processor"));
-	composite->add_component (collect_container_info (node.distributed_work, "distributed_work"));
-	composite->add_component (collect_container_info (node.aggregator, "request_aggregator"));
-	composite->add_component (node.scheduler.buckets.collect_container_info ("election_scheduler"));
-	composite->add_component (node.inactive_vote_cache.collect_container_info ("inactive_vote_cache"));
-	composite->add_component (collect_container_info (node.generator, "vote_generator"));
-	composite->add_component (collect_container_info (node.final_generator, "vote_generator_final"));
-	composite->add_component (node.ascendboot.collect_container_info ("bootstrap_ascending"));
-	composite->add_component (node.unchecked.collect_container_info ("unchecked"));
+	composite->add_component (nano::collect_container_info (work, "work"));
+	composite->add_component (nano::collect_container_info (gap_cache, "gap_cache"));
+	composite->add_component (nano::collect_container_info (ledger, "ledger"));
+	composite->add_component (nano::collect_container_info (active, "active"));
+	composite->add_component (nano::collect_container_info (bootstrap_initiator, "bootstrap_initiator"));
+	composite->add_component (nano::collect_container_info (*tcp_listener, "tcp_listener"));
+	composite->add_component (nano::collect_container_info (*network, "network"));
+	composite->add_component (telemetry->collect_container_info ("telemetry"));
+	composite->add_component (nano::collect_container_info (*workers, "workers"));
+	composite->add_component (nano::collect_container_info (*bootstrap_workers, "bootstrap_workers"));
+	composite->add_component (nano::collect_container_info (*observers, "observers"));
+	composite->add_component (nano::collect_container_info (wallets, "wallets"));
+	composite->add_component (nano::collect_container_info (vote_processor, "vote_processor"));
+	composite->add_component (nano::collect_container_info (rep_crawler, "rep_crawler"));
+	composite->add_component (nano::collect_container_info (block_processor, "block_processor"));
+	composite->add_component (nano::collect_container_info (block_arrival, "block_arrival"));
+	composite->add_component (nano::collect_container_info (online_reps, "online_reps"));
+	composite->add_component (nano::collect_container_info (history, "history"));
+	composite->add_component (nano::collect_container_info (block_uniquer, "block_uniquer"));
+	composite->add_component (nano::collect_container_info (vote_uniquer, "vote_uniquer"));
+	composite->add_component (confirmation_height_processor.collect_container_info ("confirmation_height_processor"));
+	composite->add_component (nano::collect_container_info (distributed_work, "distributed_work"));
+	composite->add_component (nano::collect_container_info (aggregator, "request_aggregator"));
+	composite->add_component (scheduler.buckets.collect_container_info ("election_scheduler"));
+	composite->add_component (scheduler.hinted.collect_container_info ("hinted_scheduler"));
+	composite->add_component (inactive_vote_cache.collect_container_info ("inactive_vote_cache"));
+	composite->add_component (nano::collect_container_info (generator, "vote_generator"));
+	composite->add_component (nano::collect_container_info (final_generator, "vote_generator_final"));
+	composite->add_component (ascendboot.collect_container_info ("bootstrap_ascending"));
+	composite->add_component (unchecked.collect_container_info ("unchecked"));
+	composite->add_component (write_database_queue.collect_container_info ("write_database_queue"));
 	return composite;
 }
 
@@ -615,6 +705,11 @@ void nano::node::process_local_async (std::shared_ptr<nano::block> const & block
 	block_processor.add (block_a);
 }
 
+void nano::node::on_vote (std::function<void (std::shared_ptr<nano::vote> const &, std::shared_ptr<nano::transport::channel> const &, nano::vote_results const &)> callback_a)
+{
+	observers->vote_results.add (callback_a);
+}
+
 void nano::node::start ()
 {
 	long_inactivity_cleanup ();
@@ -645,6 +740,8 @@ void nano::node::start ()
 	ongoing_rep_calculation ();
 	ongoing_peer_store ();
 	ongoing_online_weight_calculation_queue ();
+	ongoing_resource_check ();
+	ongoing_work_peer_health_check ();
 	bool tcp_enabled (false);
 	if (config->tcp_incoming_connections_max > 0 && !(flags.disable_bootstrap_listener () && flags.disable_tcp_realtime ()))
 	{
@@ -750,10 +847,18 @@ void nano::node::keepalive_preconfigured (std::vector<std::string> const & peers
 {
 	for (auto i (peers_a.begin ()), n (peers_a.end ()); i != n; ++i)
 	{
-		// can't use `network.port` here because preconfigured peers are referenced
+		// can't use `network.port` here because preconfigured peers are usually referenced
 		// just by their address, so we rely on them listening on the default port
 		//
-		keepalive (*i, network_params.network.default_node_port);
+		rsnano::PeerDto peer;
+		if (rsnano::rsn_peer_parse (reinterpret_cast<uint8_t const *> (i->data ()), i->size (), network_params.network.default_node_port, &peer))
+		{
+			keepalive (std::string (reinterpret_cast<char const *> (peer.address), peer.address_len), peer.port);
+		}
+		else
+		{
+			logger->try_log (boost::str (boost::format ("Invalid preconfigured peer: %1%") % *i));
+		}
 	}
 }
 
@@ -906,17 +1011,22 @@ void nano::node::ongoing_bootstrap ()
 void nano::node::ongoing_peer_store ()
 {
 	auto endpoints{ network->tcp_channels->get_peers () };
-	bool stored (false);
-	if (!endpoints.empty ())
+	auto now = nano::seconds_since_epoch ();
 	{
-		// Clear all peers then refresh with the current list of peers
+		// Connected peers have completed the node id handshake, so both times are refreshed.
+		// Peers which were not seen for a while are dropped from the history.
 		auto transaction (store.tx_begin_write ({ tables::peers }));
-		store.peer ().clear (*transaction);
 		for (auto const & endpoint : endpoints)
 		{
-			store.peer ().put (*transaction, nano::endpoint_key{ endpoint.address ().to_v6 ().to_bytes (), endpoint.port () });
+			store.peer ().put (*transaction, nano::endpoint_key{ endpoint.address ().to_v6 ().to_bytes (), endpoint.port () }, now, now);
 		}
-		stored = true;
+		auto cutoff = now - std::min<uint64_t> (now, config->peer_history_cutoff_time.count ());
+		store.peer ().prune (*transaction, cutoff);
+	}
+
+	if (network->size () < config->min_peer_count && !flags.disable_add_initial_peers ())
+	{
+		reconnect_historic_peers ();
 	}
 
 	std::weak_ptr<nano::node> node_w (shared_from_this ());
@@ -938,7 +1048,7 @@ void nano::node::backup_wallet ()
 
 		boost::filesystem::create_directories (backup_path);
 		nano::set_secure_perm_directory (backup_path, error_chmod);
-		i->second->store.write_backup (*transaction, backup_path / (i->first.to_string () + ".json"));
+		i->second->store.write_rotated_backup (*transaction, backup_path / (i->first.to_string () + ".json"), nano::wallets::backup_count);
 	}
 	auto this_l (shared ());
 	workers->add_timed_task (std::chrono::steady_clock::now () + network_params.node.backup_interval, [this_l] () {
@@ -984,39 +1094,17 @@ void nano::node::bootstrap_wallet ()
 void nano::node::unchecked_cleanup ()
 {
 	std::vector<nano::uint128_t> digests;
-	std::deque<nano::unchecked_key> cleaning_list;
 	auto const attempt (bootstrap_initiator.current_attempt ());
 	const bool long_attempt (attempt != nullptr && attempt->duration ().count () > config->unchecked_cutoff_time.count ());
-	// Collect old unchecked keys
+	// Remove old unchecked blocks
 	if (ledger.cache.block_count () >= ledger.get_bootstrap_weight_max_blocks () && !long_attempt)
 	{
-		auto const now (nano::seconds_since_epoch ());
-		auto const transaction (store.tx_begin_read ());
-		// Max 1M records to clean, max 2 minutes reading to prevent slow i/o systems issues
-		unchecked.for_each (
-		[this, &digests, &cleaning_list, &now] (nano::unchecked_key const & key, nano::unchecked_info const & info) {
-			if ((now - info.modified ()) > static_cast<uint64_t> (config->unchecked_cutoff_time.count ()))
-			{
-				digests.push_back (network->tcp_channels->publish_filter->hash (info.get_block ()));
-				cleaning_list.push_back (key);
-			} }, [iterations = 0, count = 1024 * 1024] () mutable { return iterations++ < count; });
-	}
-	if (!cleaning_list.empty ())
-	{
-		logger->always_log (boost::str (boost::format ("Deleting %1% old unchecked blocks") % cleaning_list.size ()));
-	}
-	// Delete old unchecked keys in batches
-	while (!cleaning_list.empty ())
-	{
-		std::size_t deleted_count (0);
-		while (deleted_count++ < 2 * 1024 && !cleaning_list.empty ())
+		auto const removed = unchecked.remove_older_than (config->unchecked_cutoff_time, [this, &digests] (nano::unchecked_key const &, nano::unchecked_info const & info) {
+			digests.push_back (network->tcp_channels->publish_filter->hash (info.get_block ()));
+		});
+		if (removed > 0)
 		{
-			auto key (cleaning_list.front ());
-			cleaning_list.pop_front ();
-			if (unchecked.exists (key))
-			{
-				unchecked.del (key);
-			}
+			logger->always_log (boost::str (boost::format ("Deleted %1% old unchecked blocks") % removed));
 		}
 	}
 	// Delete from the duplicate filter
@@ -1136,10 +1224,41 @@ void nano::node::ledger_pruning (uint64_t const batch_size_a, bool bootstrap_wei
 	}
 }
 
+void nano::node::ongoing_resource_check ()
+{
+	if (resource_watchdog.check () == nano::resource_status::disk_full && resource_shutdown_action)
+	{
+		resource_shutdown_action ();
+		return;
+	}
+	std::weak_ptr<nano::node> node_w (shared_from_this ());
+	workers->add_timed_task (std::chrono::steady_clock::now () + std::chrono::seconds (10), [node_w] () {
+		if (auto node_l = node_w.lock ())
+		{
+			node_l->ongoing_resource_check ();
+		}
+	});
+}
+
+void nano::node::ongoing_work_peer_health_check ()
+{
+	distributed_work.check_peers (config->work_peers);
+	std::weak_ptr<nano::node> node_w (shared_from_this ());
+	workers->add_timed_task (std::chrono::steady_clock::now () + std::chrono::minutes (1), [node_w] () {
+		if (auto node_l = node_w.lock ())
+		{
+			node_l->ongoing_work_peer_health_check ();
+		}
+	});
+}
+
 void nano::node::ongoing_ledger_pruning ()
 {
 	auto bootstrap_weight_reached (ledger.cache.block_count () >= ledger.get_bootstrap_weight_max_blocks ());
-	ledger_pruning (flags.block_processor_batch_size () != 0 ? flags.block_processor_batch_size () : 2 * 1024, bootstrap_weight_reached, false);
+	if (!resource_watchdog.writes_paused ())
+	{
+		ledger_pruning (flags.block_processor_batch_size () != 0 ? flags.block_processor_batch_size () : 2 * 1024, bootstrap_weight_reached, false);
+	}
 	auto const ledger_pruning_interval (bootstrap_weight_reached ? config->max_pruning_age : std::min (config->max_pruning_age, std::chrono::seconds (15 * 60)));
 	auto this_l (shared ());
 	workers->add_timed_task (std::chrono::steady_clock::now () + ledger_pruning_interval, [this_l] () {
@@ -1270,10 +1389,18 @@ void nano::node::add_initial_peers ()
 		return;
 	}
 
-	auto transaction (store.tx_begin_read ());
-	for (auto i (store.peer ().begin (*transaction)), n (store.peer ().end ()); i != n; ++i)
+	reconnect_historic_peers ();
+}
+
+void nano::node::reconnect_historic_peers ()
+{
+	std::vector<nano::endpoint> endpoints;
+	{
+		auto transaction (store.tx_begin_read ());
+		endpoints = store.peer ().prioritized (*transaction, std::numeric_limits<std::size_t>::max ());
+	}
+	for (auto const & endpoint : endpoints)
 	{
-		nano::endpoint endpoint (boost::asio::ip::address_v6 (i->first.address_bytes ()), i->first.port ());
 		if (!network->reachout (endpoint, config->allow_local_peers))
 		{
 			network->tcp_channels->start_tcp (endpoint);
@@ -1324,6 +1451,7 @@ bool nano::node::online () const
 void nano::node::ongoing_online_weight_calculation ()
 {
 	online_reps.sample ();
+	ledger.set_rep_weight_thresholds ({ minimum_principal_weight () });
 	ongoing_online_weight_calculation_queue ();
 }
 
@@ -1411,15 +1539,9 @@ void nano::node::process_confirmed (nano::election_status const & status_a, uint
 {
 	auto hash (status_a.get_winner ()->hash ());
 	decltype (iteration_a) const num_iters = (config->block_processor_batch_max_time / network_params.node.process_confirmed_interval) * 4;
-	std::shared_ptr<nano::block> block_l;
+	if (confirmation_height_processor.add (hash))
 	{
-		auto tx{ ledger.store.tx_begin_read () };
-		block_l = ledger.store.block ().get (*tx, hash);
-	}
-	if (block_l)
-	{
-		active.recently_confirmed.put (block_l->qualified_root (), hash);
-		confirmation_height_processor.add (block_l);
+		active.recently_confirmed.put (status_a.get_winner ()->qualified_root (), hash);
 	}
 	else if (iteration_a < num_iters)
 	{
@@ -1455,33 +1577,14 @@ bool nano::node::init_error () const
 	return store.init_error () || wallets_store.init_error ();
 }
 
-std::pair<uint64_t, std::unordered_map<nano::account, nano::uint128_t>> nano::node::get_bootstrap_weights () const
+void nano::node::load_bootstrap_weights ()
 {
-	std::unordered_map<nano::account, nano::uint128_t> weights;
 	uint8_t const * weight_buffer = network_params.network.is_live_network () ? nano_bootstrap_weights_live : nano_bootstrap_weights_beta;
 	std::size_t weight_size = network_params.network.is_live_network () ? nano_bootstrap_weights_live_size : nano_bootstrap_weights_beta_size;
-	nano::bufferstream weight_stream ((uint8_t const *)weight_buffer, weight_size);
-	nano::uint128_union block_height;
-	uint64_t max_blocks = 0;
-	if (!nano::try_read (weight_stream, block_height))
+	if (ledger.load_bootstrap_weights (weight_buffer, weight_size))
 	{
-		max_blocks = nano::narrow_cast<uint64_t> (block_height.number ());
-		while (true)
-		{
-			nano::account account;
-			if (nano::try_read (weight_stream, account.bytes))
-			{
-				break;
-			}
-			nano::amount weight;
-			if (nano::try_read (weight_stream, weight.bytes))
-			{
-				break;
-			}
-			weights[account] = weight.number ();
-		}
+		logger->always_log ("Could not load the bootstrap weights");
 	}
-	return { max_blocks, weights };
 }
 
 void nano::node::bootstrap_block (const nano::block_hash & hash)
@@ -1508,6 +1611,11 @@ nano::account nano::node::get_node_id () const
 };
 
 nano::telemetry_data nano::node::local_telemetry () const
+{
+	return local_telemetry (network_params.network.protocol_version);
+}
+
+nano::telemetry_data nano::node::local_telemetry (uint8_t peer_version) const
 {
 	nano::telemetry_data telemetry_data;
 	telemetry_data.set_node_id (node_id.pub);
@@ -1527,6 +1635,7 @@ nano::telemetry_data nano::node::local_telemetry () const
 	telemetry_data.set_maker (static_cast<std::underlying_type_t<telemetry_maker>> (ledger.pruning_enabled () ? telemetry_maker::nf_pruned_node : telemetry_maker::nf_node));
 	telemetry_data.set_timestamp (std::chrono::system_clock::now ());
 	telemetry_data.set_active_difficulty (default_difficulty (nano::work_version::work_1));
+	telemetry_data.remove_unsupported_fields (network_params.network, peer_version);
 	// Make sure this is the final operation!
 	telemetry_data.sign (node_id);
 	return telemetry_data;
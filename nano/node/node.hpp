@@ -6,15 +6,18 @@
 #include <nano/node/active_transactions.hpp>
 #include <nano/node/backlog_population.hpp>
 #include <nano/node/bandwidth_limiter.hpp>
+#include <nano/node/active_difficulty.hpp>
 #include <nano/node/block_arrival.hpp>
 #include <nano/node/block_broadcast.hpp>
 #include <nano/node/block_publisher.hpp>
+#include <nano/node/block_tracer.hpp>
 #include <nano/node/blockprocessor.hpp>
 #include <nano/node/bootstrap/bootstrap.hpp>
 #include <nano/node/bootstrap/bootstrap_attempt.hpp>
 #include <nano/node/bootstrap/bootstrap_server.hpp>
 #include <nano/node/bootstrap_ascending/service.hpp>
 #include <nano/node/confirmation_height_processor.hpp>
+#include <nano/node/confirmation_latency.hpp>
 #include <nano/node/distributed_work_factory.hpp>
 #include <nano/node/election.hpp>
 #include <nano/node/epoch_upgrader.hpp>
@@ -28,6 +31,7 @@
 #include <nano/node/process_live_dispatcher.hpp>
 #include <nano/node/repcrawler.hpp>
 #include <nano/node/request_aggregator.hpp>
+#include <nano/node/resource_watchdog.hpp>
 #include <nano/node/signatures.hpp>
 #include <nano/node/telemetry.hpp>
 #include <nano/node/transport/tcp_server.hpp>
@@ -63,6 +67,9 @@ std::unique_ptr<container_info_component> collect_container_info (rep_crawler &
 backlog_population::config backlog_population_config (node_config const &);
 vote_cache::config nodeconfig_to_vote_cache_config (node_config const &, node_flags const &);
 outbound_bandwidth_limiter::config outbound_bandwidth_limiter_config (node_config const &);
+nano::generate_cache ledger_generate_cache (node_config const &, node_flags const &);
+/** Copy of the config where the work threshold override is applied to the network params */
+std::shared_ptr<node_config> node_config_with_work_override (node_config const &);
 
 class node final : public std::enable_shared_from_this<nano::node>
 {
@@ -79,6 +86,8 @@ public:
 	}
 	bool copy_with_compaction (boost::filesystem::path const &);
 	void keepalive (std::string const &, uint16_t);
+	/** Registers a callback which is called for every processed vote with the result per hash, e.g. for consensus monitoring */
+	void on_vote (std::function<void (std::shared_ptr<nano::vote> const &, std::shared_ptr<nano::transport::channel> const &, nano::vote_results const &)> callback);
 	void start ();
 	void stop ();
 	bool is_stopped () const;
@@ -107,6 +116,10 @@ public:
 	bool collect_ledger_pruning_targets (std::deque<nano::block_hash> &, nano::account &, uint64_t const, uint64_t const, uint64_t const);
 	void ledger_pruning (uint64_t const, bool, bool);
 	void ongoing_ledger_pruning ();
+	void ongoing_resource_check ();
+	void ongoing_work_peer_health_check ();
+	/** Composes the container info of every subsystem, used to diagnose the memory usage of the node */
+	std::unique_ptr<nano::container_info_component> collect_container_info (std::string const & name);
 	int price (nano::uint128_t const &, int);
 	// The default difficulty updates to base only when the first epoch_2 block is processed
 	uint64_t default_difficulty (nano::work_version const) const;
@@ -119,6 +132,8 @@ public:
 	boost::optional<uint64_t> work_generate_blocking (nano::work_version const, nano::root const &, uint64_t, boost::optional<nano::account> const & = boost::none);
 	void work_generate (nano::work_version const, nano::root const &, uint64_t, std::function<void (boost::optional<uint64_t>)>, boost::optional<nano::account> const & = boost::none, bool const = false);
 	void add_initial_peers ();
+	/** Contacts the peers from the peer history, the most recently reachable ones first */
+	void reconnect_historic_peers ();
 	/*
 	 * Starts an election for the block, DOES NOT confirm it
 	 * TODO: Rename to `start_election`
@@ -131,7 +146,8 @@ public:
 	void ongoing_online_weight_calculation_queue ();
 	bool online () const;
 	bool init_error () const;
-	std::pair<uint64_t, std::unordered_map<nano::account, nano::uint128_t>> get_bootstrap_weights () const;
+	/** Loads the bundled bootstrap weights of the active network into the ledger */
+	void load_bootstrap_weights ();
 	uint64_t get_confirmation_height (nano::transaction const &, nano::account &);
 	/*
 	 * Attempts to bootstrap block. This is the best effort, there is no guarantee that the block will be bootstrapped.
@@ -139,6 +155,8 @@ public:
 	void bootstrap_block (nano::block_hash const &);
 	nano::account get_node_id () const;
 	nano::telemetry_data local_telemetry () const;
+	/** Telemetry data without the fields which a peer with the given protocol version doesn't understand */
+	nano::telemetry_data local_telemetry (uint8_t peer_version) const;
 
 public:
 	nano::write_database_queue write_database_queue;
@@ -180,8 +198,11 @@ public:
 	unsigned warmed_up;
 	nano::local_vote_history history;
 	nano::block_arrival block_arrival;
+	nano::confirmation_latency confirmation_latency;
+	nano::block_tracer block_tracer;
 	nano::block_processor block_processor;
 	nano::confirmation_height_processor confirmation_height_processor;
+	nano::cemented_notification_buffer callback_notification_buffer;
 	nano::vote_cache inactive_vote_cache;
 	nano::vote_generator generator;
 	nano::vote_generator final_generator;
@@ -201,7 +222,11 @@ public:
 	nano::block_broadcast block_broadcast;
 	nano::block_publisher block_publisher;
 	nano::gap_tracker gap_tracker;
+	nano::active_difficulty active_difficulty;
 	nano::process_live_dispatcher process_live_dispatcher;
+	nano::resource_watchdog resource_watchdog;
+	/** Called from a worker thread if the free disk space dropped below the shutdown threshold */
+	std::function<void ()> resource_shutdown_action;
 
 	std::chrono::steady_clock::time_point const startup_time;
 	std::chrono::seconds unchecked_cutoff = std::chrono::seconds (7 * 24 * 60 * 60); // Week
@@ -233,7 +258,6 @@ private:
 };
 
 nano::keypair load_or_create_node_id (boost::filesystem::path const & application_path, nano::logger_mt & logger);
-std::unique_ptr<container_info_component> collect_container_info (node & node, std::string const & name);
 
 nano::node_flags const & inactive_node_flag_defaults ();
 
@@ -6,11 +6,14 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (na
 	composite->add_component (node_observers.blocks.collect_container_info ("blocks"));
 	composite->add_component (node_observers.wallet.collect_container_info ("wallet"));
 	composite->add_component (node_observers.vote.collect_container_info ("vote"));
+	composite->add_component (node_observers.vote_results.collect_container_info ("vote_results"));
 	composite->add_component (node_observers.active_started.collect_container_info ("active_started"));
 	composite->add_component (node_observers.active_stopped.collect_container_info ("active_stopped"));
 	composite->add_component (node_observers.account_balance.collect_container_info ("account_balance"));
 	composite->add_component (node_observers.endpoint.collect_container_info ("endpoint"));
 	composite->add_component (node_observers.disconnect.collect_container_info ("disconnect"));
 	composite->add_component (node_observers.work_cancel.collect_container_info ("work_cancel"));
+	composite->add_component (node_observers.representative_changed.collect_container_info ("representative_changed"));
+	composite->add_component (node_observers.rep_weight_threshold_crossed.collect_container_info ("rep_weight_threshold_crossed"));
 	return composite;
 }
@@ -15,6 +15,8 @@ public:
 	blocks_t blocks;
 	nano::observer_set<bool> wallet;
 	nano::observer_set<std::shared_ptr<nano::vote>, std::shared_ptr<nano::transport::channel>, nano::vote_code> vote;
+	/** Fired for every processed vote, including invalid ones, with the result per hash */
+	nano::observer_set<std::shared_ptr<nano::vote> const &, std::shared_ptr<nano::transport::channel> const &, nano::vote_results const &> vote_results;
 	nano::observer_set<nano::block_hash const &> active_started;
 	nano::observer_set<nano::block_hash const &> active_stopped;
 	nano::observer_set<nano::account const &, bool> account_balance;
@@ -22,6 +24,10 @@ public:
 	nano::observer_set<> disconnect;
 	nano::observer_set<nano::root const &> work_cancel;
 	nano::observer_set<nano::telemetry_data const &, std::shared_ptr<nano::transport::channel> const &> telemetry;
+	/** account, old representative, new representative */
+	nano::observer_set<nano::account const &, nano::account const &, nano::account const &> representative_changed;
+	/** representative, threshold, new weight, true if the weight rose above the threshold */
+	nano::observer_set<nano::account const &, nano::uint128_t const &, nano::uint128_t const &, bool> rep_weight_threshold_crossed;
 
 	nano::observer_set<std::shared_ptr<nano::transport::socket>> socket_connected;
 	nano::observer_set<nano::transport::socket &> socket_accepted;
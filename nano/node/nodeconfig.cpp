@@ -24,7 +24,6 @@ rsnano::NodeConfigDto to_node_config_dto (nano::node_config const & config)
 	dto.bootstrap_fraction_numerator = config.bootstrap_fraction_numerator;
 	std::copy (std::begin (config.receive_minimum.bytes), std::end (config.receive_minimum.bytes), std::begin (dto.receive_minimum));
 	std::copy (std::begin (config.online_weight_minimum.bytes), std::end (config.online_weight_minimum.bytes), std::begin (dto.online_weight_minimum));
-	dto.election_hint_weight_percent = config.election_hint_weight_percent;
 	dto.password_fanout = config.password_fanout;
 	dto.io_threads = config.io_threads;
 	dto.network_threads = config.network_threads;
@@ -39,10 +38,30 @@ rsnano::NodeConfigDto to_node_config_dto (nano::node_config const & config)
 	dto.bootstrap_frontier_request_count = config.bootstrap_frontier_request_count;
 	dto.block_processor_batch_max_time_ms = config.block_processor_batch_max_time.count ();
 	dto.allow_local_peers = config.allow_local_peers;
+	dto.allow_ipv4_peers = config.allow_ipv4_peers;
+	dto.allow_ipv6_peers = config.allow_ipv6_peers;
+	dto.socks5_proxy = config.socks5_proxy.to_dto ();
+	dto.hinted_scheduler = config.hinted_scheduler.to_dto ();
+	dto.confirm_req = config.confirm_req.to_dto ();
+	dto.max_peers_per_ip = config.max_peers_per_ip;
+	dto.max_peers_per_subnetwork = config.max_peers_per_subnetwork;
+	dto.peer_history_cutoff_time_s = config.peer_history_cutoff_time.count ();
+	dto.min_peer_count = config.min_peer_count;
 	std::copy (std::begin (config.vote_minimum.bytes), std::end (config.vote_minimum.bytes), std::begin (dto.vote_minimum));
 	dto.vote_generator_delay_ms = config.vote_generator_delay.count ();
 	dto.vote_generator_threshold = config.vote_generator_threshold;
 	dto.unchecked_cutoff_time_s = config.unchecked_cutoff_time.count ();
+	dto.max_unchecked_blocks = config.max_unchecked_blocks;
+	dto.max_rollback_depth = config.max_rollback_depth;
+	dto.reject_confirmed_forks = config.reject_confirmed_forks;
+	dto.max_blocks_per_account_per_batch = config.max_blocks_per_account_per_batch;
+	dto.max_forks_per_root = config.max_forks_per_root;
+	dto.receivable_cache = config.receivable_cache;
+	dto.free_disk_warning_mb = config.free_disk_warning_mb;
+	dto.free_disk_pause_mb = config.free_disk_pause_mb;
+	dto.free_disk_shutdown_mb = config.free_disk_shutdown_mb;
+	dto.memory_warning_mb = config.memory_warning_mb;
+	dto.work_threshold_override = config.work_threshold_override;
 	dto.block_process_timeout_s = config.block_process_timeout.count ();
 	dto.tcp_io_timeout_s = config.tcp_io_timeout.count ();
 	dto.pow_sleep_interval_ns = config.pow_sleep_interval.count ();
@@ -132,6 +151,17 @@ rsnano::NodeConfigDto nano::node_config::to_dto () const
 	return to_node_config_dto (*this);
 }
 
+std::optional<nano::config_issue> nano::node_config::validate_voting (std::size_t voting_representatives) const
+{
+	auto dto{ to_dto () };
+	rsnano::ConfigIssueDto issue;
+	if (!rsnano::rsn_node_config_validate_voting (&dto, voting_representatives, &issue))
+	{
+		return std::nullopt;
+	}
+	return nano::config_issue{ rsnano::convert_dto_to_string (issue.message), issue.is_error };
+}
+
 void nano::node_config::load_dto (rsnano::NodeConfigDto & dto)
 {
 	if (dto.peering_port_defined)
@@ -143,10 +173,11 @@ void nano::node_config::load_dto (rsnano::NodeConfigDto & dto)
 		peering_port = std::nullopt;
 	}
 	optimistic_scheduler.load_dto (dto.optimistic_scheduler);
+	hinted_scheduler.load_dto (dto.hinted_scheduler);
+	confirm_req.load_dto (dto.confirm_req);
 	bootstrap_fraction_numerator = dto.bootstrap_fraction_numerator;
 	std::copy (std::begin (dto.receive_minimum), std::end (dto.receive_minimum), std::begin (receive_minimum.bytes));
 	std::copy (std::begin (dto.online_weight_minimum), std::end (dto.online_weight_minimum), std::begin (online_weight_minimum.bytes));
-	election_hint_weight_percent = dto.election_hint_weight_percent;
 	password_fanout = dto.password_fanout;
 	io_threads = dto.io_threads;
 	network_threads = dto.network_threads;
@@ -161,10 +192,28 @@ void nano::node_config::load_dto (rsnano::NodeConfigDto & dto)
 	bootstrap_frontier_request_count = dto.bootstrap_frontier_request_count;
 	block_processor_batch_max_time = std::chrono::milliseconds (dto.block_processor_batch_max_time_ms);
 	allow_local_peers = dto.allow_local_peers;
+	allow_ipv4_peers = dto.allow_ipv4_peers;
+	allow_ipv6_peers = dto.allow_ipv6_peers;
+	socks5_proxy.load_dto (dto.socks5_proxy);
+	max_peers_per_ip = dto.max_peers_per_ip;
+	max_peers_per_subnetwork = dto.max_peers_per_subnetwork;
+	peer_history_cutoff_time = std::chrono::seconds (dto.peer_history_cutoff_time_s);
+	min_peer_count = dto.min_peer_count;
 	std::copy (std::begin (dto.vote_minimum), std::end (dto.vote_minimum), std::begin (vote_minimum.bytes));
 	vote_generator_delay = std::chrono::milliseconds (dto.vote_generator_delay_ms);
 	vote_generator_threshold = dto.vote_generator_threshold;
 	unchecked_cutoff_time = std::chrono::seconds (dto.unchecked_cutoff_time_s);
+	max_unchecked_blocks = dto.max_unchecked_blocks;
+	max_rollback_depth = dto.max_rollback_depth;
+	reject_confirmed_forks = dto.reject_confirmed_forks;
+	max_blocks_per_account_per_batch = dto.max_blocks_per_account_per_batch;
+	max_forks_per_root = dto.max_forks_per_root;
+	receivable_cache = dto.receivable_cache;
+	free_disk_warning_mb = dto.free_disk_warning_mb;
+	free_disk_pause_mb = dto.free_disk_pause_mb;
+	free_disk_shutdown_mb = dto.free_disk_shutdown_mb;
+	memory_warning_mb = dto.memory_warning_mb;
+	work_threshold_override = dto.work_threshold_override;
 	block_process_timeout = std::chrono::seconds (dto.block_process_timeout_s);
 	tcp_io_timeout = std::chrono::seconds (dto.tcp_io_timeout_s);
 	pow_sleep_interval = std::chrono::nanoseconds (dto.pow_sleep_interval_ns);
@@ -288,6 +337,24 @@ nano::error nano::node_config::deserialize_toml (nano::tomlconfig & toml)
 			bootstrap_ascending.deserialize (config_l);
 		}
 
+		if (toml.has_key ("hinted_scheduler"))
+		{
+			auto config_l = toml.get_required_child ("hinted_scheduler");
+			hinted_scheduler.deserialize (config_l);
+		}
+
+		if (toml.has_key ("confirm_req"))
+		{
+			auto config_l = toml.get_required_child ("confirm_req");
+			confirm_req.deserialize (config_l);
+		}
+
+		if (toml.has_key ("socks5_proxy"))
+		{
+			auto config_l = toml.get_required_child ("socks5_proxy");
+			socks5_proxy.deserialize (config_l);
+		}
+
 		if (toml.has_key ("work_peers"))
 		{
 			work_peers.clear ();
@@ -369,6 +436,17 @@ nano::error nano::node_config::deserialize_toml (nano::tomlconfig & toml)
 		auto unchecked_cutoff_time_l = static_cast<unsigned long> (unchecked_cutoff_time.count ());
 		toml.get ("unchecked_cutoff_time", unchecked_cutoff_time_l);
 		unchecked_cutoff_time = std::chrono::seconds (unchecked_cutoff_time_l);
+		toml.get<std::size_t> ("max_unchecked_blocks", max_unchecked_blocks);
+		toml.get<std::size_t> ("max_rollback_depth", max_rollback_depth);
+		toml.get<bool> ("reject_confirmed_forks", reject_confirmed_forks);
+		toml.get<std::size_t> ("max_blocks_per_account_per_batch", max_blocks_per_account_per_batch);
+		toml.get<std::size_t> ("max_forks_per_root", max_forks_per_root);
+		toml.get<bool> ("receivable_cache", receivable_cache);
+		toml.get<uint64_t> ("free_disk_warning_mb", free_disk_warning_mb);
+		toml.get<uint64_t> ("free_disk_pause_mb", free_disk_pause_mb);
+		toml.get<uint64_t> ("free_disk_shutdown_mb", free_disk_shutdown_mb);
+		toml.get<uint64_t> ("memory_warning_mb", memory_warning_mb);
+		toml.get<uint64_t> ("work_threshold_override", work_threshold_override);
 
 		auto tcp_io_timeout_l = static_cast<unsigned long> (tcp_io_timeout.count ());
 		toml.get ("tcp_io_timeout", tcp_io_timeout_l);
@@ -382,7 +460,6 @@ nano::error nano::node_config::deserialize_toml (nano::tomlconfig & toml)
 		}
 
 		toml.get<unsigned> ("bootstrap_fraction_numerator", bootstrap_fraction_numerator);
-		toml.get<unsigned> ("election_hint_weight_percent", election_hint_weight_percent);
 		toml.get<unsigned> ("password_fanout", password_fanout);
 		toml.get<unsigned> ("io_threads", io_threads);
 		toml.get<unsigned> ("work_threads", work_threads);
@@ -395,6 +472,15 @@ nano::error nano::node_config::deserialize_toml (nano::tomlconfig & toml)
 		toml.get<uint32_t> ("bootstrap_frontier_request_count", bootstrap_frontier_request_count);
 		toml.get<bool> ("enable_voting", enable_voting);
 		toml.get<bool> ("allow_local_peers", allow_local_peers);
+		toml.get<bool> ("allow_ipv4_peers", allow_ipv4_peers);
+		toml.get<bool> ("allow_ipv6_peers", allow_ipv6_peers);
+		toml.get<std::size_t> ("max_peers_per_ip", max_peers_per_ip);
+		toml.get<std::size_t> ("max_peers_per_subnetwork", max_peers_per_subnetwork);
+
+		auto peer_history_cutoff_time_l = peer_history_cutoff_time.count ();
+		toml.get ("peer_history_cutoff_time", peer_history_cutoff_time_l);
+		peer_history_cutoff_time = std::chrono::seconds (peer_history_cutoff_time_l);
+		toml.get<std::size_t> ("min_peer_count", min_peer_count);
 		toml.get<unsigned> (signature_checker_threads_key, signature_checker_threads);
 
 		if (toml.has_key ("lmdb"))
@@ -468,10 +554,6 @@ nano::error nano::node_config::deserialize_toml (nano::tomlconfig & toml)
 		}
 
 		// Validate ranges
-		if (election_hint_weight_percent < 5 || election_hint_weight_percent > 50)
-		{
-			toml.get_error ().set ("election_hint_weight_percent must be a number between 5 and 50");
-		}
 		if (password_fanout < 16 || password_fanout > 1024 * 1024)
 		{
 			toml.get_error ().set ("password_fanout must be a number between 16 and 1048576");
@@ -512,6 +594,10 @@ nano::error nano::node_config::deserialize_toml (nano::tomlconfig & toml)
 		{
 			toml.get_error ().set ("bootstrap_frontier_request_count must be greater than or equal to 1024");
 		}
+		if (max_forks_per_root < 1)
+		{
+			toml.get_error ().set ("max_forks_per_root must be greater than or equal to 1");
+		}
 	}
 	catch (std::runtime_error const & ex)
 	{
@@ -805,6 +891,14 @@ void nano::node_flags::set_disable_ongoing_telemetry_requests (bool value)
 {
 	set_flag ([value] (rsnano::NodeFlagsDto & dto) { dto.disable_ongoing_telemetry_requests = value; });
 }
+bool nano::node_flags::disable_telemetry_upgrade_advisory () const
+{
+	return flags_dto ().disable_telemetry_upgrade_advisory;
+}
+void nano::node_flags::set_disable_telemetry_upgrade_advisory (bool value)
+{
+	set_flag ([value] (rsnano::NodeFlagsDto & dto) { dto.disable_telemetry_upgrade_advisory = value; });
+}
 bool nano::node_flags::disable_block_processor_unchecked_deletion () const
 {
 	return flags_dto ().disable_block_processor_unchecked_deletion;
@@ -893,6 +987,14 @@ void nano::node_flags::set_disable_connection_cleanup (bool value)
 {
 	set_flag ([value] (rsnano::NodeFlagsDto & dto) { dto.disable_connection_cleanup = value; });
 }
+bool nano::node_flags::enable_loopback_channel () const
+{
+	return flags_dto ().enable_loopback_channel;
+}
+void nano::node_flags::set_enable_loopback_channel (bool value)
+{
+	set_flag ([value] (rsnano::NodeFlagsDto & dto) { dto.enable_loopback_channel = value; });
+}
 nano::generate_cache nano::node_flags::generate_cache () const
 {
 	return nano::generate_cache{ rsnano::rsn_node_flags_generate_cache (handle) };
@@ -941,6 +1043,22 @@ void nano::node_flags::set_inactive_votes_cache_size (std::size_t size)
 {
 	set_flag ([size] (rsnano::NodeFlagsDto & dto) { dto.inactive_votes_cache_size = size; });
 }
+nano::vote_cache_eviction_policy nano::node_flags::inactive_votes_cache_eviction () const
+{
+	return static_cast<nano::vote_cache_eviction_policy> (flags_dto ().inactive_votes_cache_eviction);
+}
+void nano::node_flags::set_inactive_votes_cache_eviction (nano::vote_cache_eviction_policy policy)
+{
+	set_flag ([policy] (rsnano::NodeFlagsDto & dto) { dto.inactive_votes_cache_eviction = static_cast<uint8_t> (policy); });
+}
+bool nano::node_flags::inactive_votes_cache_retain () const
+{
+	return flags_dto ().inactive_votes_cache_retain;
+}
+void nano::node_flags::set_inactive_votes_cache_retain (bool value)
+{
+	set_flag ([value] (rsnano::NodeFlagsDto & dto) { dto.inactive_votes_cache_retain = value; });
+}
 std::size_t nano::node_flags::vote_processor_capacity () const
 {
 	return flags_dto ().vote_processor_capacity;
@@ -949,6 +1067,14 @@ void nano::node_flags::set_vote_processor_capacity (std::size_t size)
 {
 	set_flag ([size] (rsnano::NodeFlagsDto & dto) { dto.vote_processor_capacity = size; });
 }
+uint64_t nano::node_flags::block_trace_sample_interval () const
+{
+	return flags_dto ().block_trace_sample_interval;
+}
+void nano::node_flags::set_block_trace_sample_interval (uint64_t interval)
+{
+	set_flag ([interval] (rsnano::NodeFlagsDto & dto) { dto.block_trace_sample_interval = interval; });
+}
 std::size_t nano::node_flags::bootstrap_interval () const
 {
 	return flags_dto ().bootstrap_interval;
@@ -957,3 +1083,73 @@ void nano::node_flags::set_bootstrap_interval (std::size_t size)
 {
 	set_flag ([size] (rsnano::NodeFlagsDto & dto) { dto.bootstrap_interval = size; });
 }
+
+void nano::socks5_proxy_config::load_dto (rsnano::Socks5ProxyConfigDto const & dto_a)
+{
+	address = std::string (reinterpret_cast<const char *> (dto_a.address), std::min<std::size_t> (dto_a.address_len, sizeof (dto_a.address)));
+	port = dto_a.port;
+	fallback_to_direct = dto_a.fallback_to_direct;
+}
+
+rsnano::Socks5ProxyConfigDto nano::socks5_proxy_config::to_dto () const
+{
+	rsnano::Socks5ProxyConfigDto dto;
+	// Longer addresses are rejected by deserialize ()
+	auto len = std::min<std::size_t> (address.size (), sizeof (dto.address));
+	std::copy (address.begin (), address.begin () + len, std::begin (dto.address));
+	dto.address_len = len;
+	dto.port = port;
+	dto.fallback_to_direct = fallback_to_direct;
+	return dto;
+}
+
+nano::error nano::socks5_proxy_config::deserialize (nano::tomlconfig & toml)
+{
+	toml.get ("address", address);
+	toml.get ("port", port);
+	toml.get ("fallback_to_direct", fallback_to_direct);
+	if (address.size () > sizeof (rsnano::Socks5ProxyConfigDto::address))
+	{
+		toml.get_error ().set ("socks5_proxy.address is too long");
+	}
+	return toml.get_error ();
+}
+
+void nano::confirm_req_config::load_dto (rsnano::ConfirmReqConfigDto const & dto_a)
+{
+	base_interval_ms = dto_a.base_interval_ms;
+	backoff_multiplier = dto_a.backoff_multiplier;
+	max_interval_ms = dto_a.max_interval_ms;
+	max_requests = dto_a.max_requests;
+}
+
+rsnano::ConfirmReqConfigDto nano::confirm_req_config::to_dto () const
+{
+	rsnano::ConfirmReqConfigDto dto;
+	dto.base_interval_ms = base_interval_ms;
+	dto.backoff_multiplier = backoff_multiplier;
+	dto.max_interval_ms = max_interval_ms;
+	dto.max_requests = max_requests;
+	return dto;
+}
+
+nano::error nano::confirm_req_config::deserialize (nano::tomlconfig & toml)
+{
+	toml.get ("base_interval", base_interval_ms);
+	toml.get ("backoff_multiplier", backoff_multiplier);
+	toml.get ("max_interval", max_interval_ms);
+	toml.get ("max_requests", max_requests);
+	return toml.get_error ();
+}
+
+std::chrono::milliseconds nano::confirm_req_config::request_interval (unsigned rounds_a) const
+{
+	auto dto{ to_dto () };
+	return std::chrono::milliseconds{ rsnano::rsn_confirm_req_config_request_interval_ms (&dto, rounds_a) };
+}
+
+bool nano::confirm_req_config::may_request (unsigned rounds_a) const
+{
+	auto dto{ to_dto () };
+	return rsnano::rsn_confirm_req_config_may_request (&dto, rounds_a);
+}
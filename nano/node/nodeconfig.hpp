@@ -10,7 +10,9 @@
 #include <nano/node/bootstrap/bootstrap_config.hpp>
 #include <nano/node/ipc/ipc_config.hpp>
 #include <nano/node/logging.hpp>
+#include <nano/node/scheduler/hinted.hpp>
 #include <nano/node/scheduler/optimistic.hpp>
+#include <nano/node/vote_cache.hpp>
 #include <nano/node/websocketconfig.hpp>
 #include <nano/secure/common.hpp>
 
@@ -30,6 +32,56 @@ enum class frontiers_confirmation_mode : uint8_t
 	invalid
 };
 
+/**
+ * Outbound peer connections are made through this SOCKS5 proxy if an address is configured
+ */
+class socks5_proxy_config final
+{
+public:
+	socks5_proxy_config () = default;
+	void load_dto (rsnano::Socks5ProxyConfigDto const & dto_a);
+	rsnano::Socks5ProxyConfigDto to_dto () const;
+	nano::error deserialize (nano::tomlconfig & toml);
+
+	std::string address;
+	uint16_t port{ 0 };
+	bool fallback_to_direct{ false };
+};
+
+/**
+ * Controls how often an election asks the representatives for votes with confirm_req messages
+ */
+class confirm_req_config final
+{
+public:
+	confirm_req_config () = default;
+	void load_dto (rsnano::ConfirmReqConfigDto const & dto_a);
+	rsnano::ConfirmReqConfigDto to_dto () const;
+	nano::error deserialize (nano::tomlconfig & toml);
+	/** Time to wait after the previous confirm_req round when \p rounds_a rounds were sent already */
+	std::chrono::milliseconds request_interval (unsigned rounds_a) const;
+	bool may_request (unsigned rounds_a) const;
+
+	/** Minimum time between the first two confirm_req rounds of an election */
+	uint64_t base_interval_ms{ 5000 };
+	/** Factor by which the interval grows after every confirm_req round. 1.0 disables the backoff */
+	double backoff_multiplier{ 1.0 };
+	/** Upper bound for the interval between two confirm_req rounds */
+	uint64_t max_interval_ms{ 60000 };
+	/** Maximum number of confirm_req rounds per election. 0 means unlimited */
+	uint32_t max_requests{ 0 };
+};
+
+/**
+ * A problem in the configuration which is reported at startup
+ */
+class config_issue final
+{
+public:
+	std::string message;
+	bool is_error{ false };
+};
+
 /**
  * Node configuration
  */
@@ -41,6 +93,8 @@ public:
 
 	void load_dto (rsnano::NodeConfigDto & dto);
 	rsnano::NodeConfigDto to_dto () const;
+	/** Voting needs the representative keys of the wallets, so this is checked after the wallets were loaded */
+	std::optional<nano::config_issue> validate_voting (std::size_t voting_representatives) const;
 
 	nano::error serialize_toml (nano::tomlconfig &) const;
 	nano::error deserialize_toml (nano::tomlconfig &);
@@ -50,6 +104,8 @@ public:
 	nano::network_params network_params;
 	std::optional<uint16_t> peering_port{};
 	nano::scheduler::optimistic_config optimistic_scheduler;
+	nano::scheduler::hinted_config hinted_scheduler;
+	nano::confirm_req_config confirm_req;
 	nano::logging logging;
 	std::vector<std::pair<std::string, uint16_t>> work_peers;
 	std::vector<std::pair<std::string, uint16_t>> secondary_work_peers;
@@ -62,7 +118,6 @@ public:
 	std::chrono::milliseconds vote_generator_delay;
 	unsigned vote_generator_threshold;
 	nano::amount online_weight_minimum{ 60000 * nano::Gxrb_ratio };
-	unsigned election_hint_weight_percent{ 50 };
 	unsigned password_fanout{ 1024 };
 	unsigned io_threads{ std::max (4u, nano::hardware_concurrency ()) };
 	unsigned network_threads{ std::max (4u, nano::hardware_concurrency ()) };
@@ -83,6 +138,15 @@ public:
 	uint16_t callback_port;
 	std::string callback_target;
 	bool allow_local_peers;
+	bool allow_ipv4_peers;
+	bool allow_ipv6_peers;
+	nano::socks5_proxy_config socks5_proxy;
+	std::size_t max_peers_per_ip;
+	std::size_t max_peers_per_subnetwork;
+	/** Peers which were not seen for this long are removed from the peer history */
+	std::chrono::seconds peer_history_cutoff_time;
+	/** Historic peers are contacted again while the node has fewer peers than this */
+	std::size_t min_peer_count;
 	nano::stats_config stats_config;
 	nano::ipc::ipc_config ipc_config;
 	std::string external_address;
@@ -91,6 +155,24 @@ public:
 	/** Time to wait for block processing result */
 	std::chrono::seconds block_process_timeout;
 	std::chrono::seconds unchecked_cutoff_time;
+	/** Maximum number of unchecked blocks. The oldest entries are evicted first */
+	std::size_t max_unchecked_blocks;
+	/** Maximum number of blocks a single rollback may remove */
+	std::size_t max_rollback_depth;
+	/** Reject forks of cemented blocks instead of starting an election */
+	bool reject_confirmed_forks;
+	std::size_t max_blocks_per_account_per_batch;
+	std::size_t max_forks_per_root;
+	/** Keep the receivable count and amount of every account in memory */
+	bool receivable_cache;
+	/** Free disk space thresholds of the resource watchdog */
+	uint64_t free_disk_warning_mb;
+	uint64_t free_disk_pause_mb;
+	uint64_t free_disk_shutdown_mb;
+	/** Resident memory above which the resource watchdog logs a warning. 0 disables the warning */
+	uint64_t memory_warning_mb;
+	/** Replaces all work thresholds of the node when not 0. Only the dev and test network allow it */
+	uint64_t work_threshold_override;
 	/** Timeout for initiated async operations */
 	std::chrono::seconds tcp_io_timeout;
 	std::chrono::nanoseconds pow_sleep_interval;
@@ -175,6 +257,8 @@ public:
 	void set_disable_providing_telemetry_metrics (bool value);
 	bool disable_ongoing_telemetry_requests () const;
 	void set_disable_ongoing_telemetry_requests (bool value);
+	bool disable_telemetry_upgrade_advisory () const;
+	void set_disable_telemetry_upgrade_advisory (bool value);
 	bool disable_block_processor_unchecked_deletion () const;
 	void set_disable_block_processor_unchecked_deletion (bool value);
 	bool disable_block_processor_republishing () const;
@@ -197,6 +281,9 @@ public:
 	void set_read_only (bool value);
 	bool disable_connection_cleanup () const;
 	void set_disable_connection_cleanup (bool value);
+	/** Deliver own flooded blocks and votes to the node itself through an in-process loopback channel */
+	bool enable_loopback_channel () const; // For testing only
+	void set_enable_loopback_channel (bool value);
 	nano::generate_cache generate_cache () const;
 	void set_generate_cache (nano::generate_cache const & cache);
 	bool inactive_node () const;
@@ -209,8 +296,15 @@ public:
 	void set_block_processor_verification_size (std::size_t size);
 	std::size_t inactive_votes_cache_size () const;
 	void set_inactive_votes_cache_size (std::size_t size);
+	nano::vote_cache_eviction_policy inactive_votes_cache_eviction () const;
+	void set_inactive_votes_cache_eviction (nano::vote_cache_eviction_policy policy);
+	/** Keep cached votes after they were transferred into a newly started election */
+	bool inactive_votes_cache_retain () const;
+	void set_inactive_votes_cache_retain (bool value);
 	std::size_t vote_processor_capacity () const;
 	void set_vote_processor_capacity (std::size_t size);
+	uint64_t block_trace_sample_interval () const;
+	void set_block_trace_sample_interval (uint64_t interval);
 	std::size_t bootstrap_interval () const; // For testing only
 	void set_bootstrap_interval (std::size_t size);
 	rsnano::NodeFlagsHandle * handle;
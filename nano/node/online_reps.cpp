@@ -7,6 +7,35 @@
 
 #include <iostream>
 
+nano::online_weight_sampler::online_weight_sampler (nano::ledger & ledger_a, nano::uint128_t const & online_weight_minimum_a, uint64_t max_samples_a)
+{
+	nano::amount minimum{ online_weight_minimum_a };
+	handle = rsnano::rsn_online_weight_sampler_create (ledger_a.get_handle (), minimum.bytes.data (), max_samples_a);
+}
+
+nano::online_weight_sampler::online_weight_sampler (rsnano::OnlineWeightSamplerHandle * handle_a) :
+	handle{ handle_a }
+{
+}
+
+nano::online_weight_sampler::~online_weight_sampler ()
+{
+	rsnano::rsn_online_weight_sampler_destroy (handle);
+}
+
+void nano::online_weight_sampler::sample (nano::uint128_t const & online_weight_a)
+{
+	nano::amount online_weight{ online_weight_a };
+	rsnano::rsn_online_weight_sampler_sample (handle, online_weight.bytes.data ());
+}
+
+nano::uint128_t nano::online_weight_sampler::calculate_trend ()
+{
+	nano::amount trend;
+	rsnano::rsn_online_weight_sampler_calculate_trend (handle, trend.bytes.data ());
+	return trend.number ();
+}
+
 nano::online_reps::online_reps (nano::ledger & ledger_a, nano::node_config const & config_a) :
 	handle{ rsnano::rsn_online_reps_create (
 	ledger_a.get_handle (),
@@ -51,6 +80,17 @@ void nano::online_reps::set_online (nano::uint128_t online_a)
 	rsnano::rsn_online_reps_set_online (handle, online_weight.bytes.data ());
 }
 
+void nano::online_reps::set_trended (nano::uint128_t trended_a)
+{
+	nano::amount trended{ trended_a };
+	rsnano::rsn_online_reps_set_trended (handle, trended.bytes.data ());
+}
+
+std::unique_ptr<nano::online_weight_sampler> nano::online_reps::sampler () const
+{
+	return std::make_unique<nano::online_weight_sampler> (rsnano::rsn_online_reps_sampler (handle));
+}
+
 uint8_t nano::online_reps::online_weight_quorum ()
 {
 	return rsnano::rsn_online_reps_online_weight_quorum ();
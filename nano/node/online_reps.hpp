@@ -15,6 +15,24 @@ class ledger;
 class node_config;
 class transaction;
 
+/** Stores samples of the online weight in the ledger and calculates the trend from them */
+class online_weight_sampler final
+{
+public:
+	online_weight_sampler (nano::ledger & ledger_a, nano::uint128_t const & online_weight_minimum_a, uint64_t max_samples_a);
+	explicit online_weight_sampler (rsnano::OnlineWeightSamplerHandle * handle_a);
+	online_weight_sampler (online_weight_sampler const &) = delete;
+	online_weight_sampler (online_weight_sampler &&) = delete;
+	~online_weight_sampler ();
+	/** Stores a sample and deletes the oldest samples if there are too many */
+	void sample (nano::uint128_t const & online_weight_a);
+	/** Median of the stored samples and the online weight minimum */
+	nano::uint128_t calculate_trend ();
+
+private:
+	rsnano::OnlineWeightSamplerHandle * handle;
+};
+
 /** Track online representatives and trend online weight */
 class online_reps final
 {
@@ -38,6 +56,9 @@ public:
 	void clear ();
 	static uint8_t online_weight_quorum ();
 	void set_online (nano::uint128_t);
+	void set_trended (nano::uint128_t);
+	/** The sampler which is used by sample () */
+	std::unique_ptr<nano::online_weight_sampler> sampler () const;
 	rsnano::OnlineRepsHandle * get_handle () const;
 
 private:
@@ -26,7 +26,6 @@ public:
 	std::size_t size () const;
 	std::unique_ptr<container_info_component> collect_container_info (std::string const & name);
 
-private:
 	rsnano::PeerExclusionHandle * handle;
 };
 }
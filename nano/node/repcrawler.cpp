@@ -359,6 +359,17 @@ void nano::rep_crawler::on_rep_request (std::shared_ptr<nano::transport::channel
 	}
 }
 
+void nano::rep_crawler::evict (nano::account const & account_a)
+{
+	auto minimum = std::min (node.minimum_principal_weight (), node.config->rep_crawler_weight_minimum.number ());
+	if (node.ledger.weight (account_a) >= minimum)
+	{
+		return;
+	}
+	nano::lock_guard<nano::mutex> lock{ probable_reps_mutex };
+	probable_reps.get<tag_account> ().erase (account_a);
+}
+
 void nano::rep_crawler::cleanup_reps ()
 {
 	// Check known rep channels
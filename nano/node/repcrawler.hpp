@@ -89,6 +89,9 @@ public:
 	/** Remove block hash from list of active rep queries */
 	void remove (nano::block_hash const &);
 
+	/** Stops tracking a representative whose weight fell below the minimum weight of the crawler */
+	void evict (nano::account const &);
+
 	/** Remove block hash from with delay depending on vote processor size */
 	void throttled_remove (nano::block_hash const &, uint64_t const);
 
@@ -270,6 +270,13 @@ std::pair<std::vector<std::shared_ptr<nano::block>>, std::vector<std::shared_ptr
 				}
 			}
 
+			// 6. Roots with pruned blocks cannot be voted on
+			if (block != nullptr && !ledger.pruning_safe_to_vote (*transaction, block->root ()))
+			{
+				stats.inc (nano::stat::type::requests, nano::stat::detail::requests_pruned, stat::dir::in);
+				continue;
+			}
+
 			if (block)
 			{
 				// Generate new vote
@@ -307,6 +314,15 @@ std::pair<std::vector<std::shared_ptr<nano::block>>, std::vector<std::shared_ptr
 	}),
 	cached_votes.end ());
 
+	// Drop cached votes whose hashes are already covered by other votes, so fewer confirm_acks are sent
+	if (cached_votes.size () > 1)
+	{
+		nano::vote_packer packer;
+		auto unpacked_count = cached_votes.size ();
+		cached_votes = packer.pack (cached_votes);
+		stats.add (nano::stat::type::requests, nano::stat::detail::requests_packed_votes, stat::dir::in, unpacked_count - cached_votes.size ());
+	}
+
 	for (auto const & vote : cached_votes)
 	{
 		reply_action (vote, channel_a);
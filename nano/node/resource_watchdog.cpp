@@ -0,0 +1,29 @@
+#include <nano/lib/logger_mt.hpp>
+#include <nano/lib/rsnano.hpp>
+#include <nano/node/nodeconfig.hpp>
+#include <nano/node/resource_watchdog.hpp>
+
+nano::resource_watchdog::resource_watchdog (boost::filesystem::path const & data_path, nano::node_config const & config, std::shared_ptr<nano::logger_mt> const & logger) :
+	handle{ rsnano::rsn_resource_watchdog_create (data_path.string ().c_str (), nano::to_logger_handle (logger), config.free_disk_warning_mb, config.free_disk_pause_mb, config.free_disk_shutdown_mb, config.memory_warning_mb) }
+{
+}
+
+nano::resource_watchdog::~resource_watchdog ()
+{
+	rsnano::rsn_resource_watchdog_destroy (handle);
+}
+
+nano::resource_status nano::resource_watchdog::check ()
+{
+	return static_cast<nano::resource_status> (rsnano::rsn_resource_watchdog_check (handle));
+}
+
+bool nano::resource_watchdog::writes_paused () const
+{
+	return rsnano::rsn_resource_watchdog_writes_paused (handle);
+}
+
+void nano::resource_watchdog::write_dropped ()
+{
+	rsnano::rsn_resource_watchdog_write_dropped (handle);
+}
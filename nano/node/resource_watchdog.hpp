@@ -0,0 +1,46 @@
+#pragma once
+
+#include <boost/filesystem/path.hpp>
+
+#include <cstdint>
+#include <memory>
+
+namespace rsnano
+{
+class ResourceWatchdogHandle;
+}
+
+namespace nano
+{
+class logger_mt;
+class node_config;
+
+enum class resource_status : uint8_t
+{
+	ok,
+	/** The free disk space is below the warning threshold */
+	disk_low,
+	/** Non-essential writes are paused */
+	disk_nearly_full,
+	/** The node should shut down before the ledger runs out of disk space */
+	disk_full,
+};
+
+/** Monitors the free disk space in the data path and the memory usage of the node */
+class resource_watchdog final
+{
+public:
+	resource_watchdog (boost::filesystem::path const & data_path, nano::node_config const & config, std::shared_ptr<nano::logger_mt> const & logger);
+	resource_watchdog (resource_watchdog const &) = delete;
+	~resource_watchdog ();
+	/** Samples the resource usage and logs the status if it changed */
+	nano::resource_status check ();
+	/** True if non-essential writes like pruning and new unchecked blocks should be skipped */
+	bool writes_paused () const;
+	/** Counts a write which was skipped because writes are paused */
+	void write_dropped ();
+
+private:
+	rsnano::ResourceWatchdogHandle * handle;
+};
+}
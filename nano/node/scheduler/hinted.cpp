@@ -1,134 +1,98 @@
 #include <nano/lib/stats.hpp>
+#include <nano/lib/tomlconfig.hpp>
 #include <nano/node/node.hpp>
 #include <nano/node/scheduler/hinted.hpp>
 
-nano::scheduler::hinted::config::config (nano::node_config const & config) :
-	vote_cache_check_interval_ms{ config.network_params.network.is_dev_network () ? 100u : 1000u }
+namespace
 {
+bool insert_hinted (void * context, rsnano::BlockHandle * block_handle)
+{
+	auto node = static_cast<nano::node *> (context);
+	auto block = nano::block_handle_to_block (block_handle);
+	auto result = node->active.insert (block, nano::election_behavior::hinted);
+	return result.inserted;
 }
 
-nano::scheduler::hinted::hinted (config const & config_a, nano::node & node_a, nano::vote_cache & inactive_vote_cache_a, nano::active_transactions & active_a, nano::online_reps & online_reps_a, nano::stats & stats_a) :
-	config_m{ config_a },
-	node{ node_a },
-	inactive_vote_cache{ inactive_vote_cache_a },
-	active{ active_a },
-	online_reps{ online_reps_a },
-	stats{ stats_a }
+bool election_active (void * context, uint8_t const * hash_bytes)
 {
+	auto node = static_cast<nano::node *> (context);
+	nano::block_hash hash;
+	std::copy (hash_bytes, hash_bytes + 32, std::begin (hash.bytes));
+	return node->active.active (hash);
 }
 
-nano::scheduler::hinted::~hinted ()
+void missing_block (void * context, uint8_t const * hash_bytes)
 {
-	// Thread must be stopped before destruction
-	debug_assert (!thread.joinable ());
+	auto node = static_cast<nano::node *> (context);
+	nano::block_hash hash;
+	std::copy (hash_bytes, hash_bytes + 32, std::begin (hash.bytes));
+	node->bootstrap_block (hash);
 }
 
-void nano::scheduler::hinted::start ()
+int64_t vacancy (void * context)
 {
-	debug_assert (!thread.joinable ());
-
-	thread = std::thread{ [this] () {
-		nano::thread_role::set (nano::thread_role::name::election_hinting);
-		run ();
-	} };
+	auto node = static_cast<nano::node *> (context);
+	return node->active.vacancy (nano::election_behavior::hinted);
 }
 
-void nano::scheduler::hinted::stop ()
+void delete_context (void *)
 {
-	{
-		nano::lock_guard<nano::mutex> lock{ mutex };
-		stopped = true;
-	}
-	notify ();
-	nano::join_or_pass (thread);
+	// The node outlives the scheduler, so there is nothing to delete
+}
 }
 
-void nano::scheduler::hinted::notify ()
+nano::scheduler::hinted::hinted (hinted_config const & config_a, nano::node & node_a, nano::vote_cache & vote_cache_a, nano::online_reps & online_reps_a, nano::stats & stats_a)
 {
-	condition.notify_all ();
+	auto config_dto{ config_a.to_dto () };
+	handle = rsnano::rsn_hinted_scheduler_create (&config_dto, node_a.ledger.get_handle (), vote_cache_a.handle, online_reps_a.get_handle (), stats_a.handle, &node_a, insert_hinted, election_active, missing_block, vacancy, delete_context);
 }
 
-bool nano::scheduler::hinted::predicate (nano::uint128_t const & minimum_tally) const
+nano::scheduler::hinted::~hinted ()
 {
-	// Check if there is space inside AEC for a new hinted election
-	if (active.vacancy (nano::election_behavior::hinted) > 0)
-	{
-		// Check if there is any vote cache entry surpassing our minimum vote tally threshold
-		if (inactive_vote_cache.peek (minimum_tally))
-		{
-			return true;
-		}
-	}
-	return false;
+	rsnano::rsn_hinted_scheduler_destroy (handle);
 }
 
-bool nano::scheduler::hinted::run_one (nano::uint128_t const & minimum_tally)
+void nano::scheduler::hinted::start ()
 {
-	if (auto top = inactive_vote_cache.pop (minimum_tally); top)
-	{
-		const auto hash = top->hash; // Hash of block we want to hint
-
-		// Check if block exists
-		auto block = node.block (hash);
-		if (block != nullptr)
-		{
-			// Ensure block is not already confirmed
-			if (!node.block_confirmed_or_being_confirmed (hash))
-			{
-				// Try to insert it into AEC as hinted election
-				// We check for AEC vacancy inside our predicate
-				auto result = node.active.insert (block, nano::election_behavior::hinted);
-
-				stats.inc (nano::stat::type::hinting, result.inserted ? nano::stat::detail::insert : nano::stat::detail::insert_failed);
-
-				return result.inserted; // Return whether block was inserted
-			}
-		}
-		else
-		{
-			// Missing block in ledger to start an election
-			node.bootstrap_block (hash);
-
-			stats.inc (nano::stat::type::hinting, nano::stat::detail::missing_block);
-		}
-	}
-	return false;
+	rsnano::rsn_hinted_scheduler_start (handle);
 }
 
-void nano::scheduler::hinted::run ()
+void nano::scheduler::hinted::stop ()
 {
-	nano::unique_lock<nano::mutex> lock{ mutex };
-	while (!stopped)
-	{
-		// It is possible that if we are waiting long enough this tally becomes outdated due to changes in trended online weight
-		// However this is only used here for hinting, election does independent tally calculation, so there is no need to ensure it's always up-to-date
-		const auto minimum_tally = tally_threshold ();
-
-		// Periodically wakeup for condition checking
-		// We are not notified every time new vote arrives in inactive vote cache as that happens too often
-		condition.wait_for (lock, std::chrono::milliseconds (config_m.vote_cache_check_interval_ms), [this, minimum_tally] () {
-			return stopped || predicate (minimum_tally);
-		});
+	rsnano::rsn_hinted_scheduler_stop (handle);
+}
 
-		debug_assert ((std::this_thread::yield (), true)); // Introduce some random delay in debug builds
+void nano::scheduler::hinted::notify ()
+{
+	rsnano::rsn_hinted_scheduler_notify (handle);
+}
 
-		if (!stopped)
-		{
-			// We don't need the lock when running main loop
-			lock.unlock ();
+std::unique_ptr<nano::container_info_component> nano::scheduler::hinted::collect_container_info (std::string const & name)
+{
+	auto info_handle = rsnano::rsn_hinted_scheduler_collect_container_info (handle, name.c_str ());
+	return std::make_unique<nano::container_info_composite> (info_handle);
+}
 
-			if (predicate (minimum_tally))
-			{
-				run_one (minimum_tally);
-			}
+void nano::scheduler::hinted_config::load_dto (rsnano::HintedSchedulerConfigDto const & dto_a)
+{
+	enabled = dto_a.enabled;
+	check_interval_ms = dto_a.check_interval_ms;
+	hinting_threshold_percent = dto_a.hinting_threshold_percent;
+}
 
-			lock.lock ();
-		}
-	}
+rsnano::HintedSchedulerConfigDto nano::scheduler::hinted_config::to_dto () const
+{
+	rsnano::HintedSchedulerConfigDto dto;
+	dto.enabled = enabled;
+	dto.check_interval_ms = check_interval_ms;
+	dto.hinting_threshold_percent = hinting_threshold_percent;
+	return dto;
 }
 
-nano::uint128_t nano::scheduler::hinted::tally_threshold () const
+nano::error nano::scheduler::hinted_config::deserialize (nano::tomlconfig & toml)
 {
-	auto min_tally = (online_reps.trended () / 100) * node.config->election_hint_weight_percent;
-	return min_tally;
+	toml.get ("enable", enabled);
+	toml.get ("check_interval", check_interval_ms);
+	toml.get ("hinting_threshold", hinting_threshold_percent);
+	return toml.get_error ();
 }
@@ -1,37 +1,48 @@
 #pragma once
 
-#include <nano/lib/locks.hpp>
-#include <nano/lib/numbers.hpp>
-#include <nano/secure/common.hpp>
+#include <nano/lib/errors.hpp>
+#include <nano/lib/rsnano.hpp>
+#include <nano/lib/utility.hpp>
 
-#include <condition_variable>
-#include <thread>
+#include <cstddef>
+#include <cstdint>
 
 namespace nano
 {
 class node;
-class node_config;
-class active_transactions;
 class vote_cache;
 class online_reps;
+class stats;
+class tomlconfig;
 }
 namespace nano::scheduler
 {
+class hinted_config final
+{
+public:
+	hinted_config () = default;
+	void load_dto (rsnano::HintedSchedulerConfigDto const & dto_a);
+	rsnano::HintedSchedulerConfigDto to_dto () const;
+	nano::error deserialize (nano::tomlconfig & toml);
+
+public:
+	bool enabled{ true };
+
+	/** Interval of wakeup to check the vote cache for candidates */
+	uint64_t check_interval_ms{ 1000 };
+
+	/** Percentage of the quorum delta which the cached vote tally of a block has to reach for a hinted election to be started */
+	uint32_t hinting_threshold_percent{ 10 };
+};
+
 /*
  * Monitors inactive vote cache and schedules elections with the highest observed vote tally.
  */
 class hinted final
 {
-public: // Config
-	struct config final
-	{
-		explicit config (node_config const & config);
-		// Interval of wakeup to check inactive vote cache when idle
-		uint64_t vote_cache_check_interval_ms;
-	};
-
 public:
-	hinted (config const &, nano::node &, nano::vote_cache &, nano::active_transactions &, nano::online_reps &, nano::stats &);
+	hinted (hinted_config const &, nano::node &, nano::vote_cache &, nano::online_reps &, nano::stats &);
+	hinted (hinted const &) = delete;
 	~hinted ();
 
 	void start ();
@@ -42,26 +53,9 @@ public:
 	 */
 	void notify ();
 
-private:
-	bool predicate (nano::uint128_t const & minimum_tally) const;
-	void run ();
-	bool run_one (nano::uint128_t const & minimum_tally);
-
-	nano::uint128_t tally_threshold () const;
-
-private: // Dependencies
-	nano::node & node;
-	nano::vote_cache & inactive_vote_cache;
-	nano::active_transactions & active;
-	nano::online_reps & online_reps;
-	nano::stats & stats;
+	std::unique_ptr<container_info_component> collect_container_info (std::string const & name);
 
 private:
-	config const config_m;
-
-	bool stopped{ false };
-	nano::condition_variable condition;
-	mutable nano::mutex mutex;
-	std::thread thread;
+	rsnano::HintedSchedulerHandle * handle;
 };
 }
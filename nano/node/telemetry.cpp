@@ -8,12 +8,15 @@
 #include <nano/secure/ledger.hpp>
 
 #include <boost/algorithm/string.hpp>
+#include <boost/format.hpp>
+#include <boost/property_tree/ptree.hpp>
 
 #include <algorithm>
 #include <cstdint>
 #include <future>
 #include <numeric>
 #include <set>
+#include <unordered_map>
 
 using namespace std::chrono_literals;
 
@@ -176,6 +179,11 @@ void nano::telemetry::run ()
 
 		cleanup ();
 
+		if (config_m.enable_upgrade_advisory)
+		{
+			check_upgrade_advisory ();
+		}
+
 		if (request_predicate ())
 		{
 			triggered = false;
@@ -221,12 +229,19 @@ void nano::telemetry::request (std::shared_ptr<nano::transport::channel> & chann
 
 void nano::telemetry::run_broadcasts ()
 {
-	auto telemetry = node.local_telemetry ();
+	// Peers with an older protocol version get telemetry without the fields they don't understand
+	std::unordered_map<uint8_t, nano::telemetry_data> telemetry_by_version;
 	auto peers = network.list ();
 
 	for (auto & channel : peers)
 	{
-		broadcast (channel, telemetry);
+		auto version = channel->get_network_version ();
+		auto existing = telemetry_by_version.find (version);
+		if (existing == telemetry_by_version.end ())
+		{
+			existing = telemetry_by_version.emplace (version, node.local_telemetry (version)).first;
+		}
+		broadcast (channel, existing->second);
 	}
 }
 
@@ -254,6 +269,33 @@ void nano::telemetry::cleanup ()
 	});
 }
 
+void nano::telemetry::check_upgrade_advisory ()
+{
+	debug_assert (!mutex.try_lock ());
+
+	std::vector<nano::telemetry_data> telemetry_datas;
+	for (auto const & entry : telemetries)
+	{
+		if (check_timeout (entry))
+		{
+			telemetry_datas.push_back (entry.data);
+		}
+	}
+	if (telemetry_datas.size () < upgrade_advisory_min_peers)
+	{
+		return;
+	}
+
+	auto const own_version = network_params.network.protocol_version;
+	auto const majority = make_telemetry_histogram (telemetry_datas).majority_protocol_version ();
+	if (majority && *majority > own_version && *majority != advised_protocol_version)
+	{
+		advised_protocol_version = *majority;
+		stats.inc (nano::stat::type::telemetry, nano::stat::detail::newer_protocol_majority);
+		node.logger->always_log (boost::str (boost::format ("The majority of the peers uses protocol version %1%, but this node uses version %2%. Please upgrade the node") % static_cast<unsigned> (*majority) % static_cast<unsigned> (own_version)));
+	}
+}
+
 bool nano::telemetry::check_timeout (const entry & entry) const
 {
 	return entry.last_updated + network_params.network.telemetry_cache_cutoff >= std::chrono::steady_clock::now ();
@@ -288,6 +330,16 @@ std::unordered_map<nano::endpoint, nano::telemetry_data> nano::telemetry::get_al
 	return result;
 }
 
+nano::telemetry_histogram nano::telemetry::histogram () const
+{
+	std::vector<nano::telemetry_data> telemetry_datas;
+	for (auto const & [endpoint, data] : get_all_telemetries ())
+	{
+		telemetry_datas.push_back (data);
+	}
+	return make_telemetry_histogram (telemetry_datas);
+}
+
 std::unique_ptr<nano::container_info_component> nano::telemetry::collect_container_info (const std::string & name)
 {
 	nano::lock_guard<nano::mutex> guard{ mutex };
@@ -439,3 +491,50 @@ nano::telemetry_data nano::consolidate_telemetry_data (std::vector<nano::telemet
 
 	return consolidated_data;
 }
+
+nano::telemetry_histogram nano::make_telemetry_histogram (std::vector<nano::telemetry_data> const & telemetry_datas)
+{
+	nano::telemetry_histogram histogram;
+	for (auto const & telemetry_data : telemetry_datas)
+	{
+		++histogram.protocol_versions[telemetry_data.get_protocol_version ()];
+		auto node_version = std::to_string (telemetry_data.get_major_version ()) + "." + std::to_string (telemetry_data.get_minor_version ()) + "." + std::to_string (telemetry_data.get_patch_version ());
+		++histogram.node_versions[node_version];
+		++histogram.bandwidth_caps[telemetry_data.get_bandwidth_cap ()];
+		++histogram.total;
+	}
+	return histogram;
+}
+
+std::optional<uint8_t> nano::telemetry_histogram::majority_protocol_version () const
+{
+	for (auto const & [version, count] : protocol_versions)
+	{
+		if (count * 2 > total)
+		{
+			return version;
+		}
+	}
+	return std::nullopt;
+}
+
+void nano::telemetry_histogram::serialize_json (boost::property_tree::ptree & tree) const
+{
+	auto add_counts = [&tree] (std::string const & name, auto const & counts) {
+		boost::property_tree::ptree child;
+		for (auto const & [key, count] : counts)
+		{
+			std::ostringstream key_text;
+			key_text << key;
+			// push_back doesn't treat the dots of node versions as path separators
+			child.push_back (boost::property_tree::ptree::value_type (key_text.str (), boost::property_tree::ptree (std::to_string (count))));
+		}
+		tree.add_child (name, child);
+	};
+
+	std::map<unsigned, std::size_t> protocol_versions_l (protocol_versions.begin (), protocol_versions.end ());
+	add_counts ("protocol_versions", protocol_versions_l);
+	add_counts ("node_versions", node_versions);
+	add_counts ("bandwidth_caps", bandwidth_caps);
+	tree.put ("total", total);
+}
@@ -10,8 +10,10 @@
 #include <boost/multi_index/member.hpp>
 #include <boost/multi_index/ordered_index.hpp>
 #include <boost/multi_index_container.hpp>
+#include <boost/property_tree/ptree_fwd.hpp>
 
 #include <functional>
+#include <map>
 #include <memory>
 #include <optional>
 #include <thread>
@@ -32,6 +34,24 @@ namespace transport
 	class channel;
 }
 
+/**
+ * Number of peers per protocol version, node version and bandwidth cap, derived from the telemetry of the peer set
+ */
+class telemetry_histogram
+{
+public:
+	std::map<uint8_t, std::size_t> protocol_versions;
+	/** Keyed by "major.minor.patch" */
+	std::map<std::string, std::size_t> node_versions;
+	/** A bandwidth cap of 0 means unlimited */
+	std::map<uint64_t, std::size_t> bandwidth_caps;
+	std::size_t total{ 0 };
+
+	/** The protocol version which is used by more than half of the peers */
+	std::optional<uint8_t> majority_protocol_version () const;
+	void serialize_json (boost::property_tree::ptree &) const;
+};
+
 /**
  * This class periodically broadcasts and requests telemetry from peers.
  * Those intervals are configurable via `telemetry_request_interval` & `telemetry_broadcast_interval` network constants
@@ -39,6 +59,7 @@ namespace transport
  *
  * Requests can be disabled via `disable_ongoing_telemetry_requests` node flag
  * Broadcasts can be disabled via `disable_providing_telemetry_metrics` node flag
+ * A warning is logged when the majority of the peers uses a newer protocol version, unless the `disable_telemetry_upgrade_advisory` node flag is set
  *
  */
 class telemetry
@@ -48,10 +69,12 @@ public:
 	{
 		bool enable_ongoing_requests{ true };
 		bool enable_ongoing_broadcasts{ true };
+		bool enable_upgrade_advisory{ true };
 
 		config (nano::node_config const & config, nano::node_flags const & flags) :
 			enable_ongoing_requests{ !flags.disable_ongoing_telemetry_requests () },
-			enable_ongoing_broadcasts{ !flags.disable_providing_telemetry_metrics () }
+			enable_ongoing_broadcasts{ !flags.disable_providing_telemetry_metrics () },
+			enable_upgrade_advisory{ !flags.disable_telemetry_upgrade_advisory () }
 		{
 		}
 	};
@@ -85,6 +108,11 @@ public:
 	 */
 	std::unordered_map<nano::endpoint, nano::telemetry_data> get_all_telemetries () const;
 
+	/**
+	 * Returns the version and bandwidth histogram of all available telemetry
+	 */
+	nano::telemetry_histogram histogram () const;
+
 public: // Container info
 	std::unique_ptr<nano::container_info_component> collect_container_info (std::string const & name);
 
@@ -114,6 +142,7 @@ private:
 	void run_requests ();
 	void run_broadcasts ();
 	void cleanup ();
+	void check_upgrade_advisory ();
 
 	void request (std::shared_ptr<nano::transport::channel> &);
 	void broadcast (std::shared_ptr<nano::transport::channel> &, nano::telemetry_data const &);
@@ -139,6 +168,8 @@ private:
 	bool triggered{ false };
 	std::chrono::steady_clock::time_point last_request{};
 	std::chrono::steady_clock::time_point last_broadcast{};
+	/** Protocol version for which the upgrade advisory was logged last */
+	uint8_t advised_protocol_version{ 0 };
 
 	bool stopped{ false };
 	mutable nano::mutex mutex{ mutex_identifier (mutexes::telemetry) };
@@ -147,7 +178,10 @@ private:
 
 private:
 	static std::size_t constexpr max_size = 1024;
+	/** The upgrade advisory needs telemetry of at least this many peers */
+	static std::size_t constexpr upgrade_advisory_min_peers = 10;
 };
 
 nano::telemetry_data consolidate_telemetry_data (std::vector<telemetry_data> const & telemetry_data);
+nano::telemetry_histogram make_telemetry_histogram (std::vector<telemetry_data> const & telemetry_data);
 }
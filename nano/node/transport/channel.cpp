@@ -55,7 +55,7 @@ void nano::transport::channel::set_last_packet_sent ()
 
 void nano::transport::channel::set_last_packet_sent (std::chrono::system_clock::time_point time)
 {
-	rsnano::rsn_channel_set_last_packet_sent2 (handle, time.time_since_epoch ().count ());
+	rsnano::rsn_channel_set_last_packet_sent2 (handle, rsnano::time_point_to_nanoseconds (time));
 }
 
 std::chrono::system_clock::time_point nano::transport::channel::get_last_packet_sent () const
@@ -73,6 +73,31 @@ nano::account destination_node_id)
 	source_node_id.bytes.data (),
 	destination_node_id.bytes.data ());
 }
+
+rsnano::ChannelHandle * create_loopback_handle (nano::node & node)
+{
+	auto context = new std::function<void (nano::message const &, std::shared_ptr<nano::transport::channel> const &)> (node.network->inbound);
+	auto network_dto{ node.config->network_params.network.to_dto () };
+	auto endpoint_dto = rsnano::udp_endpoint_to_dto (node.network->endpoint ());
+
+	return rsnano::rsn_channel_inproc_create_loopback (
+	node.network->tcp_channels->get_next_channel_id (),
+	&network_dto,
+	node.network->tcp_channels->publish_filter->handle,
+	node.stats->handle,
+	node.outbound_limiter.handle,
+	inbound_wrapper,
+	context,
+	delete_inbound_context,
+	&node.io_ctx,
+	&endpoint_dto,
+	node.node_id.pub.bytes.data ());
+}
+}
+
+nano::transport::inproc::channel::channel (nano::node & node_a) :
+	transport::channel{ create_loopback_handle (node_a) }
+{
 }
 
 nano::transport::inproc::channel::channel (nano::node & node_a, nano::node & destination) :
@@ -214,7 +239,7 @@ void delete_callback_context (void * context)
 void nano::transport::inproc::channel::send_buffer (nano::shared_const_buffer const & buffer_a, std::function<void (boost::system::error_code const &, std::size_t)> const & callback_a, nano::transport::buffer_drop_policy drop_policy_a, nano::transport::traffic_type traffic_type)
 {
 	auto callback_pointer = new std::function<void (boost::system::error_code const &, std::size_t)> (callback_a);
-	rsnano::rsn_channel_inproc_send_buffer (handle, buffer_a.data (), buffer_a.size (), nano::transport::channel_tcp_send_callback, nano::transport::delete_send_buffer_callback, callback_pointer, static_cast<uint8_t> (drop_policy_a), static_cast<uint8_t> (traffic_type));
+	rsnano::rsn_channel_inproc_send_buffer (handle, buffer_a.to_handle (), nano::transport::channel_tcp_send_callback, nano::transport::delete_send_buffer_callback, callback_pointer, static_cast<uint8_t> (drop_policy_a), static_cast<uint8_t> (traffic_type));
 }
 
 std::string nano::transport::inproc::channel::to_string () const
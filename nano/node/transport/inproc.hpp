@@ -20,6 +20,8 @@ namespace transport
 		class channel final : public nano::transport::channel
 		{
 		public:
+			/** Creates a loopback channel, which delivers messages back to `node` itself */
+			explicit channel (nano::node & node);
 			explicit channel (nano::node & node, nano::node & destination);
 			explicit channel (rsnano::ChannelHandle * handle_a);
 
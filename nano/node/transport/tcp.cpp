@@ -148,7 +148,7 @@ void nano::transport::channel_tcp::send (nano::message & message_a, std::functio
 void nano::transport::channel_tcp::send_buffer (nano::shared_const_buffer const & buffer_a, std::function<void (boost::system::error_code const &, std::size_t)> const & callback_a, nano::transport::buffer_drop_policy policy_a, nano::transport::traffic_type traffic_type)
 {
 	auto callback_pointer = new std::function<void (boost::system::error_code const &, std::size_t)> (callback_a);
-	rsnano::rsn_channel_tcp_send_buffer (handle, buffer_a.data (), buffer_a.size (), nano::transport::channel_tcp_send_callback, nano::transport::delete_send_buffer_callback, callback_pointer, static_cast<uint8_t> (policy_a), static_cast<uint8_t> (traffic_type));
+	rsnano::rsn_channel_tcp_send_buffer (handle, buffer_a.to_handle (), nano::transport::channel_tcp_send_callback, nano::transport::delete_send_buffer_callback, callback_pointer, static_cast<uint8_t> (policy_a), static_cast<uint8_t> (traffic_type));
 }
 
 std::string nano::transport::channel_tcp::to_string () const
@@ -300,6 +300,22 @@ std::vector<nano::endpoint> nano::transport::tcp_channels::get_peers () const
 	return endpoints;
 }
 
+void nano::transport::tcp_channels::sample_keepalive_peers (std::array<nano::endpoint, 8> & target_a, std::optional<nano::endpoint> const & recipient_a) const
+{
+	std::optional<rsnano::EndpointDto> recipient_dto;
+	if (recipient_a)
+	{
+		recipient_dto = rsnano::udp_endpoint_to_dto (*recipient_a);
+	}
+	std::array<rsnano::EndpointDto, 8> dtos;
+	rsnano::rsn_tcp_channels_sample_keepalive_peers (handle, recipient_dto ? &*recipient_dto : nullptr, dtos.data ());
+	auto j{ target_a.begin () };
+	for (auto i{ dtos.begin () }, n{ dtos.end () }; i != n; ++i, ++j)
+	{
+		*j = rsnano::dto_to_udp_endpoint (*i);
+	}
+}
+
 void nano::transport::tcp_channels::random_fill (std::array<nano::endpoint, 8> & target_a) const
 {
 	std::array<rsnano::EndpointDto, 8> dtos;
@@ -394,8 +410,7 @@ std::unique_ptr<nano::container_info_component> nano::transport::tcp_channels::c
 
 void nano::transport::tcp_channels::purge (std::chrono::system_clock::time_point const & cutoff_a)
 {
-	uint64_t cutoff_ns = std::chrono::duration_cast<std::chrono::nanoseconds> (cutoff_a.time_since_epoch ()).count ();
-	rsnano::rsn_tcp_channels_purge (handle, cutoff_ns);
+	rsnano::rsn_tcp_channels_purge (handle, rsnano::time_point_to_nanoseconds (cutoff_a));
 }
 
 void nano::transport::tcp_channels::ongoing_keepalive ()
@@ -418,8 +433,7 @@ void nano::transport::tcp_channels::list (std::deque<std::shared_ptr<nano::trans
 void nano::transport::tcp_channels::modify_last_packet_sent (nano::endpoint const & endpoint_a, std::chrono::system_clock::time_point const & time_a)
 {
 	auto endpoint_dto{ rsnano::udp_endpoint_to_dto (endpoint_a) };
-	auto time_ns = std::chrono::duration_cast<std::chrono::nanoseconds> (time_a.time_since_epoch ()).count ();
-	rsnano::rsn_tcp_channels_set_last_packet_sent (handle, &endpoint_dto, time_ns);
+	rsnano::rsn_tcp_channels_set_last_packet_sent (handle, &endpoint_dto, rsnano::time_point_to_nanoseconds (time_a));
 }
 
 void nano::transport::tcp_channels::update (nano::tcp_endpoint const & endpoint_a)
@@ -13,6 +13,7 @@
 #include <cstddef>
 #include <cstdint>
 #include <memory>
+#include <optional>
 #include <unordered_set>
 
 namespace nano
@@ -164,6 +165,8 @@ namespace transport
 
 		std::vector<nano::endpoint> get_peers () const;
 		void random_fill (std::array<nano::endpoint, 8> &) const;
+		// Random realtime peers for a keepalive message, filled up with preconfigured peers. Without recipient the message is sent to several channels
+		void sample_keepalive_peers (std::array<nano::endpoint, 8> &, std::optional<nano::endpoint> const & recipient = std::nullopt) const;
 		void set_port (uint16_t port_a);
 		void set_observer (std::shared_ptr<nano::tcp_server_observer> observer_a);
 		void set_message_visitor_factory (nano::transport::request_response_visitor_factory & visitor_factory);
@@ -201,7 +201,8 @@ void nano::transport::tcp_listener::tcp_server_exited (nano::transport::socket::
 
 void nano::transport::tcp_listener::accept_action (boost::system::error_code const & ec, std::shared_ptr<nano::transport::socket> const & socket_a)
 {
-	if (!network->tcp_channels->excluded_peers ().check (socket_a->remote_endpoint ()))
+	auto excluded_peers{ network->tcp_channels->excluded_peers () };
+	if (!excluded_peers.check (socket_a->remote_endpoint ()))
 	{
 		auto req_resp_visitor_factory = std::make_shared<nano::transport::request_response_visitor_factory> (node);
 		auto server (std::make_shared<nano::transport::tcp_server> (
@@ -218,7 +219,8 @@ void nano::transport::tcp_listener::accept_action (boost::system::error_code con
 		node.block_processor,
 		node.bootstrap_initiator,
 		node.node_id,
-		true));
+		true,
+		&excluded_peers));
 		nano::lock_guard<nano::mutex> lock{ mutex };
 		connections[server->unique_id ()] = nano::tcp_server_weak_wrapper (server);
 		server->start ();
@@ -274,7 +276,8 @@ nano::ledger & ledger_a,
 nano::block_processor & block_processor_a,
 nano::bootstrap_initiator & bootstrap_initiator_a,
 nano::keypair & node_id_a,
-bool allow_bootstrap_a)
+bool allow_bootstrap_a,
+nano::peer_exclusion * excluded_peers_a)
 {
 	auto config_dto{ config_a.to_dto () };
 	auto observer_handle = new std::weak_ptr<nano::tcp_server_observer> (observer_a);
@@ -298,6 +301,7 @@ bool allow_bootstrap_a)
 	params.vote_uniquer = vote_uniquer_a.handle;
 	params.tcp_message_manager = tcp_message_manager_a.handle;
 	params.allow_bootstrap = allow_bootstrap_a;
+	params.excluded_peers = excluded_peers_a != nullptr ? excluded_peers_a->handle : nullptr;
 	handle = rsnano::rsn_bootstrap_server_create (&params);
 	debug_assert (socket_a != nullptr);
 }
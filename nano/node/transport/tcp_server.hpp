@@ -134,7 +134,8 @@ public:
 	nano::block_processor & block_processor_a,
 	nano::bootstrap_initiator & bootstrap_initiator_a,
 	nano::keypair & node_id_a,
-	bool allow_bootstrap_a = true);
+	bool allow_bootstrap_a = true,
+	nano::peer_exclusion * excluded_peers_a = nullptr);
 	explicit tcp_server (rsnano::TcpServerHandle * handle_a);
 	tcp_server (nano::transport::tcp_server const &) = delete;
 	tcp_server (nano::transport::tcp_server &&) = delete;
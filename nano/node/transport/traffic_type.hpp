@@ -9,6 +9,8 @@ enum class traffic_type
 {
 	generic,
 	/** For bootstrap (asc_pull_ack, asc_pull_req) traffic */
-	bootstrap
+	bootstrap,
+	/** For liveness traffic (handshake, keepalive) which must not be starved by bulk transfers */
+	priority
 };
 }
\ No newline at end of file
@@ -37,9 +37,9 @@ void drop_predicate_callback (void * context_a)
 }
 }
 
-nano::unchecked_map::unchecked_map (nano::stats & stats, bool disable_delete)
+nano::unchecked_map::unchecked_map (nano::stats & stats, bool disable_delete, std::size_t max_entries)
 {
-	handle = rsnano::rsn_unchecked_map_create (stats.handle, disable_delete);
+	handle = rsnano::rsn_unchecked_map_create (stats.handle, disable_delete, max_entries);
 }
 
 nano::unchecked_map::~unchecked_map ()
@@ -74,6 +74,14 @@ void nano::unchecked_map::for_each (nano::hash_or_account const & dependency, st
 	drop_predicate_callback);
 }
 
+std::size_t nano::unchecked_map::remove_older_than (std::chrono::seconds max_age, std::function<void (nano::unchecked_key const &, nano::unchecked_info const &)> action)
+{
+	return rsnano::rsn_unchecked_map_remove_older_than (handle, max_age.count (),
+	action_callback_wrapper,
+	new std::function<void (nano::unchecked_key const &, nano::unchecked_info const &)>{ action },
+	drop_action_callback);
+}
+
 std::vector<nano::unchecked_info> nano::unchecked_map::get (nano::block_hash const & hash)
 {
 	std::vector<nano::unchecked_info> result;
@@ -20,7 +20,7 @@ class stats;
 class unchecked_map
 {
 public:
-	unchecked_map (nano::stats &, bool do_delete);
+	unchecked_map (nano::stats &, bool do_delete, std::size_t max_entries = 64 * 1024);
 	~unchecked_map ();
 
 	void put (nano::hash_or_account const & dependency, nano::unchecked_info const & info);
@@ -32,6 +32,11 @@ public:
 	bool exists (nano::unchecked_key const & key) const;
 	void del (nano::unchecked_key const & key);
 	void clear ();
+	/**
+	 * Removes entries which were added more than \p max_age ago and calls \p action for each of them
+	 * @return number of removed entries
+	 */
+	std::size_t remove_older_than (std::chrono::seconds max_age, std::function<void (nano::unchecked_key const &, nano::unchecked_info const &)> action);
 	std::size_t count () const;
 	std::size_t buffer_count () const;
 	void stop ();
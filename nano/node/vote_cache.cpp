@@ -37,7 +37,7 @@ void delete_rep_weight_query (void * handle_a)
 }
 
 nano::vote_cache::vote_cache (const config config_a) :
-	handle{ rsnano::rsn_vote_cache_create (config_a.max_size) }
+	handle{ rsnano::rsn_vote_cache_create (config_a.max_size, static_cast<uint8_t> (config_a.eviction_policy), config_a.retain_promoted) }
 {
 }
 
@@ -108,6 +108,16 @@ bool nano::vote_cache::erase (const nano::block_hash & hash)
 	return rsnano::rsn_vote_cache_erase (handle, hash.bytes.data ());
 }
 
+std::optional<nano::vote_cache::entry> nano::vote_cache::promote (const nano::block_hash & hash)
+{
+	rsnano::VoteCacheEntryDto result{};
+	if (rsnano::rsn_vote_cache_promote (handle, hash.bytes.data (), &result))
+	{
+		return entry_from_dto (result);
+	}
+	return {};
+}
+
 std::optional<nano::vote_cache::entry> nano::vote_cache::pop (nano::uint128_t const & min_tally)
 {
 	nano::amount min_tally_amount{ min_tally };
@@ -130,6 +140,24 @@ std::optional<nano::vote_cache::entry> nano::vote_cache::peek (nano::uint128_t c
 	return {};
 }
 
+std::vector<nano::vote_cache::top_entry> nano::vote_cache::top (nano::uint128_t const & min_tally, std::size_t max_count) const
+{
+	nano::amount min_tally_amount{ min_tally };
+	auto entries_handle = rsnano::rsn_vote_cache_top (handle, min_tally_amount.bytes.data (), max_count);
+	auto len = rsnano::rsn_vote_cache_top_entries_len (entries_handle);
+	std::vector<top_entry> result;
+	result.reserve (len);
+	for (auto i = 0; i < len; ++i)
+	{
+		nano::block_hash hash;
+		nano::amount tally;
+		rsnano::rsn_vote_cache_top_entries_get (entries_handle, i, hash.bytes.data (), tally.bytes.data ());
+		result.push_back (top_entry{ hash, tally.number () });
+	}
+	rsnano::rsn_vote_cache_top_entries_destroy (entries_handle);
+	return result;
+}
+
 void nano::vote_cache::trigger (const nano::block_hash & hash)
 {
 	rsnano::rsn_vote_cache_trigger (handle, hash.bytes.data ());
@@ -16,16 +16,25 @@ class node;
 class active_transactions;
 class vote;
 
+/*
+ * Decides which entry is removed when the vote cache is full
+ */
+enum class vote_cache_eviction_policy : uint8_t
+{
+	oldest,
+	lowest_tally, // Of entries with the same tally the oldest is evicted
+};
+
 /**
  *	A container holding votes that do not match any active or recently finished elections.
  *	It keeps track of votes in two internal structures: cache and queue
  *
  *	Cache: Stores votes associated with a particular block hash with a bounded maximum number of votes per hash.
- *			When cache size exceeds `max_size` oldest entries are evicted first.
+ *			When cache size exceeds `max_size` entries are evicted according to the eviction policy.
  *
  *	Queue: Keeps track of block hashes ordered by total cached vote tally.
  *			When inserting a new vote into cache, the queue is atomically updated.
- *			When queue size exceeds `max_size` oldest entries are evicted first.
+ *			When queue size exceeds `max_size` entries are evicted according to the eviction policy.
  */
 class vote_cache final
 {
@@ -34,6 +43,9 @@ public:
 	{
 	public:
 		std::size_t max_size;
+		nano::vote_cache_eviction_policy eviction_policy{ nano::vote_cache_eviction_policy::lowest_tally };
+		/** Keep the votes of a block after they were transferred into its election */
+		bool retain_promoted{ true };
 	};
 
 	/**
@@ -56,6 +68,16 @@ public:
 		std::size_t size () const;
 	};
 
+	/**
+	 * A queued block hash together with its vote tally
+	 */
+	class top_entry final
+	{
+	public:
+		nano::block_hash hash;
+		nano::uint128_t tally;
+	};
+
 	explicit vote_cache (const config);
 	vote_cache (vote_cache const &) = delete;
 	vote_cache (vote_cache &&) = delete;
@@ -74,6 +96,11 @@ public:
 	 * @return true if hash existed and was erased, false otherwise
 	 */
 	bool erase (nano::block_hash const & hash);
+	/**
+	 * Returns the cached votes of a block for which an election was just started.
+	 * The entry is removed from the cache unless promoted votes are retained
+	 */
+	std::optional<entry> promote (nano::block_hash const & hash);
 	/**
 	 * Returns an entry with the highest tally.
 	 * @param min_tally minimum tally threshold, entries below with their voting weight below this will be ignored
@@ -84,6 +111,11 @@ public:
 	 * @param min_tally minimum tally threshold, entries below with their voting weight below this will be ignored
 	 */
 	std::optional<entry> pop (nano::uint128_t const & min_tally = 0);
+	/**
+	 * Returns up to `max_count` queued entries with a tally of at least `min_tally`, ordered by descending tally.
+	 * In contrast to `pop` the entries stay in the queue.
+	 */
+	std::vector<top_entry> top (nano::uint128_t const & min_tally, std::size_t max_count) const;
 	/**
 	 * Reinserts a block into the queue.
 	 * It is possible that we dequeue a hash that doesn't have a received block yet (for eg. if publish message was lost).
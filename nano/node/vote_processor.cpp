@@ -7,7 +7,6 @@
 #include <nano/node/nodeconfig.hpp>
 #include <nano/node/online_reps.hpp>
 #include <nano/node/repcrawler.hpp>
-#include <nano/node/signatures.hpp>
 #include <nano/node/vote_processor.hpp>
 #include <nano/secure/common.hpp>
 #include <nano/secure/ledger.hpp>
@@ -17,8 +16,7 @@
 #include <chrono>
 using namespace std::chrono_literals;
 
-nano::vote_processor::vote_processor (nano::signature_checker & checker_a, nano::active_transactions & active_a, nano::node_observers & observers_a, nano::stats & stats_a, nano::node_config & config_a, nano::node_flags & flags_a, nano::logger_mt & logger_a, nano::online_reps & online_reps_a, nano::rep_crawler & rep_crawler_a, nano::ledger & ledger_a, nano::network_params & network_params_a) :
-	checker (checker_a),
+nano::vote_processor::vote_processor (nano::active_transactions & active_a, nano::node_observers & observers_a, nano::stats & stats_a, nano::node_config & config_a, nano::node_flags & flags_a, nano::logger_mt & logger_a, nano::online_reps & online_reps_a, nano::rep_crawler & rep_crawler_a, nano::ledger & ledger_a, nano::network_params & network_params_a) :
 	active (active_a),
 	observers (observers_a),
 	stats (stats_a),
@@ -127,6 +125,14 @@ bool nano::vote_processor::vote (std::shared_ptr<nano::vote> const & vote_a, std
 		else
 		{
 			stats.inc (nano::stat::type::vote, nano::stat::detail::vote_overflow);
+			if (representatives_1.find (vote_a->account ()) != representatives_1.end ())
+			{
+				++dropped_votes[vote_a->account ()];
+			}
+			if (principal_representatives.find (vote_a->account ()) != principal_representatives.end ())
+			{
+				stats.inc (nano::stat::type::vote, nano::stat::detail::vote_overflow_principal);
+			}
 		}
 	}
 	return !process;
@@ -159,8 +165,8 @@ void nano::vote_processor::verify_votes (decltype (votes) const & votes_a)
 		pub_keys.push_back (tmp_accounts.back ().bytes.data ());
 		signatures.push_back (tmp_signatures.back ().bytes.data ());
 	}
-	nano::signature_check_set check = { size, messages.data (), lengths.data (), pub_keys.data (), signatures.data (), verifications.data () };
-	checker.verify (check);
+	// Verified as one ed25519 batch on this thread instead of going through the shared signature checker pool
+	nano::validate_message_batch (messages.data (), lengths.data (), pub_keys.data (), signatures.data (), size, verifications.data ());
 	auto i (0);
 	for (auto const & vote : votes_a)
 	{
@@ -176,11 +182,20 @@ void nano::vote_processor::verify_votes (decltype (votes) const & votes_a)
 nano::vote_code nano::vote_processor::vote_blocking (std::shared_ptr<nano::vote> const & vote_a, std::shared_ptr<nano::transport::channel> const & channel_a, bool validated)
 {
 	auto result (nano::vote_code::invalid);
+	nano::vote_results results;
 	if (validated || !vote_a->validate ())
 	{
-		result = active.vote (vote_a);
+		result = active.vote (vote_a, results);
 		observers.vote.notify (vote_a, channel_a, result);
 	}
+	else
+	{
+		for (auto const & hash : vote_a->hashes ())
+		{
+			results.emplace_back (hash, nano::vote_code::invalid);
+		}
+	}
+	observers.vote_results.notify (vote_a, channel_a, results);
 	std::string status;
 	switch (result)
 	{
@@ -252,6 +267,13 @@ bool nano::vote_processor::half_full ()
 	return size () >= max_votes / 2;
 }
 
+uint64_t nano::vote_processor::dropped (nano::account const & representative_a)
+{
+	nano::lock_guard<nano::mutex> guard{ mutex };
+	auto existing = dropped_votes.find (representative_a);
+	return existing == dropped_votes.end () ? 0 : existing->second;
+}
+
 void nano::vote_processor::calculate_weights ()
 {
 	nano::unique_lock<nano::mutex> lock{ mutex };
@@ -260,12 +282,17 @@ void nano::vote_processor::calculate_weights ()
 		representatives_1.clear ();
 		representatives_2.clear ();
 		representatives_3.clear ();
+		principal_representatives.clear ();
 		auto supply (online_reps.trended ());
 		auto rep_amounts = ledger.cache.rep_weights ().get_rep_amounts ();
 		for (auto const & rep_amount : rep_amounts)
 		{
 			nano::account const & representative (rep_amount.first);
 			auto weight (ledger.weight (representative));
+			if (weight > supply / network_params.network.principal_weight_factor)
+			{
+				principal_representatives.insert (representative);
+			}
 			if (weight > supply / 1000) // 0.1% or above (level 1)
 			{
 				representatives_1.insert (representative);
@@ -288,6 +315,7 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (vo
 	std::size_t representatives_1_count;
 	std::size_t representatives_2_count;
 	std::size_t representatives_3_count;
+	std::size_t dropped_votes_count;
 
 	{
 		nano::lock_guard<nano::mutex> guard{ vote_processor.mutex };
@@ -295,6 +323,7 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (vo
 		representatives_1_count = vote_processor.representatives_1.size ();
 		representatives_2_count = vote_processor.representatives_2.size ();
 		representatives_3_count = vote_processor.representatives_3.size ();
+		dropped_votes_count = vote_processor.dropped_votes.size ();
 	}
 
 	auto composite = std::make_unique<container_info_composite> (name);
@@ -302,5 +331,6 @@ std::unique_ptr<nano::container_info_component> nano::collect_container_info (vo
 	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "representatives_1", representatives_1_count, sizeof (decltype (vote_processor.representatives_1)::value_type) }));
 	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "representatives_2", representatives_2_count, sizeof (decltype (vote_processor.representatives_2)::value_type) }));
 	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "representatives_3", representatives_3_count, sizeof (decltype (vote_processor.representatives_3)::value_type) }));
+	composite->add_component (std::make_unique<container_info_leaf> (container_info{ "dropped_votes", dropped_votes_count, sizeof (decltype (vote_processor.dropped_votes)::value_type) }));
 	return composite;
 }
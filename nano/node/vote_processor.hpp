@@ -7,11 +7,11 @@
 #include <deque>
 #include <memory>
 #include <thread>
+#include <unordered_map>
 #include <unordered_set>
 
 namespace nano
 {
-class signature_checker;
 class active_transactions;
 class store;
 class node_observers;
@@ -34,7 +34,7 @@ namespace transport
 class vote_processor final
 {
 public:
-	vote_processor (nano::signature_checker & checker_a, nano::active_transactions & active_a, nano::node_observers & observers_a, nano::stats & stats_a, nano::node_config & config_a, nano::node_flags & flags_a, nano::logger_mt & logger_a, nano::online_reps & online_reps_a, nano::rep_crawler & rep_crawler_a, nano::ledger & ledger_a, nano::network_params & network_params_a);
+	vote_processor (nano::active_transactions & active_a, nano::node_observers & observers_a, nano::stats & stats_a, nano::node_config & config_a, nano::node_flags & flags_a, nano::logger_mt & logger_a, nano::online_reps & online_reps_a, nano::rep_crawler & rep_crawler_a, nano::ledger & ledger_a, nano::network_params & network_params_a);
 
 	/** Returns false if the vote was processed */
 	bool vote (std::shared_ptr<nano::vote> const &, std::shared_ptr<nano::transport::channel> const &);
@@ -47,6 +47,9 @@ public:
 	std::size_t size ();
 	bool empty ();
 	bool half_full ();
+	/** Number of votes of a representative which were dropped because the queue was full.
+	 * Drops are only counted for representatives with at least 0.1% of the online weight */
+	uint64_t dropped (nano::account const &);
 	void calculate_weights ();
 	void stop ();
 	std::atomic<uint64_t> total_processed{ 0 };
@@ -54,7 +57,6 @@ public:
 private:
 	void process_loop ();
 
-	nano::signature_checker & checker;
 	nano::active_transactions & active;
 	nano::node_observers & observers;
 	nano::stats & stats;
@@ -70,6 +72,8 @@ private:
 	std::unordered_set<nano::account> representatives_1;
 	std::unordered_set<nano::account> representatives_2;
 	std::unordered_set<nano::account> representatives_3;
+	std::unordered_set<nano::account> principal_representatives;
+	std::unordered_map<nano::account, uint64_t> dropped_votes;
 	nano::condition_variable condition;
 	nano::mutex mutex{ mutex_identifier (mutexes::vote_processor) };
 	bool started;
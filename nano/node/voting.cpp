@@ -94,6 +94,35 @@ std::size_t nano::local_vote_history::size () const
 	return rsnano::rsn_local_vote_history_size (handle);
 }
 
+nano::vote_packer::vote_packer () :
+	handle{ rsnano::rsn_vote_packer_create () }
+{
+}
+
+nano::vote_packer::~vote_packer ()
+{
+	rsnano::rsn_vote_packer_destroy (handle);
+}
+
+std::vector<std::shared_ptr<nano::vote>> nano::vote_packer::pack (std::vector<std::shared_ptr<nano::vote>> const & votes_a) const
+{
+	std::vector<rsnano::VoteHandle const *> vote_handles;
+	vote_handles.reserve (votes_a.size ());
+	for (auto const & vote : votes_a)
+	{
+		vote_handles.push_back (vote->get_handle ());
+	}
+	LocalVotesResultWrapper result_wrapper;
+	rsnano::rsn_vote_packer_pack (handle, vote_handles.data (), vote_handles.size (), &result_wrapper.result);
+	std::vector<std::shared_ptr<nano::vote>> votes;
+	votes.reserve (result_wrapper.result.count);
+	for (auto i (0); i < result_wrapper.result.count; ++i)
+	{
+		votes.push_back (std::make_shared<nano::vote> (result_wrapper.result.votes[i]));
+	}
+	return votes;
+}
+
 std::unique_ptr<nano::container_info_component> nano::collect_container_info (nano::local_vote_history & history, std::string const & name)
 {
 	std::size_t sizeof_element;
@@ -145,13 +174,13 @@ void nano::vote_generator::process (nano::write_transaction const & transaction,
 	if (is_final)
 	{
 		auto block (ledger.store.block ().get (transaction, hash_a));
-		should_vote = block != nullptr && ledger.dependents_confirmed (transaction, *block) && ledger.store.final_vote ().put (transaction, block->qualified_root (), hash_a);
+		should_vote = block != nullptr && ledger.dependents_confirmed (transaction, *block) && ledger.pruning_safe_to_vote (transaction, root_a) && ledger.store.final_vote ().put (transaction, block->qualified_root (), hash_a);
 		debug_assert (block == nullptr || root_a == block->root ());
 	}
 	else
 	{
 		auto block (ledger.store.block ().get (transaction, hash_a));
-		should_vote = block != nullptr && ledger.dependents_confirmed (transaction, *block);
+		should_vote = block != nullptr && ledger.dependents_confirmed (transaction, *block) && ledger.pruning_safe_to_vote (transaction, root_a);
 	}
 	if (should_vote)
 	{
@@ -210,7 +239,7 @@ std::size_t nano::vote_generator::generate (std::vector<std::shared_ptr<nano::bl
 	{
 		auto transaction (ledger.store.tx_begin_read ());
 		auto dependents_confirmed = [&transaction, this] (auto const & block_a) {
-			return this->ledger.dependents_confirmed (*transaction, *block_a);
+			return this->ledger.dependents_confirmed (*transaction, *block_a) && this->ledger.pruning_safe_to_vote (*transaction, block_a->root ());
 		};
 		auto as_candidate = [] (auto const & block_a) {
 			return candidate_t{ block_a->root (), block_a->hash () };
@@ -65,6 +65,19 @@ private:
 	friend class local_vote_history_basic_Test;
 };
 
+/** Selects fewer votes per representative which still cover all hashes, without signing new votes */
+class vote_packer final
+{
+public:
+	vote_packer ();
+	vote_packer (vote_packer const &) = delete;
+	~vote_packer ();
+	std::vector<std::shared_ptr<nano::vote>> pack (std::vector<std::shared_ptr<nano::vote>> const & votes_a) const;
+
+private:
+	rsnano::VotePackerHandle * handle;
+};
+
 std::unique_ptr<container_info_component> collect_container_info (local_vote_history & history, std::string const & name);
 
 /** Floods a vote to the network and calls the vote processor. */
@@ -100,7 +100,7 @@ void nano::wallet_store::derive_key (nano::raw_key & prv_a, nano::transaction co
 	rsnano::rsn_lmdb_wallet_store_derive_key (rust_handle, prv_a.bytes.data (), transaction_a.get_rust_handle (), password_a.c_str ());
 }
 
-int const nano::wallet_store::special_count (7);
+int const nano::wallet_store::special_count (8);
 
 nano::wallet_store::wallet_store (bool & init_a, nano::kdf & kdf_a, nano::transaction & transaction_a, nano::account representative_a, unsigned fanout_a, std::string const & wallet_a, std::string const & json_a) :
 	kdf (kdf_a),
@@ -118,6 +118,14 @@ nano::wallet_store::wallet_store (bool & init_a, nano::kdf & kdf_a, nano::transa
 	init_a = rust_handle == nullptr;
 }
 
+nano::wallet_store::wallet_store (bool & init_a, nano::kdf & kdf_a, nano::transaction & transaction_a, unsigned fanout_a, std::string const & wallet_a, std::string const & export_a, std::string const & passphrase_a) :
+	kdf (kdf_a),
+	rust_handle{ rsnano::rsn_lmdb_wallet_store_create_from_export (fanout_a, kdf_a.handle, transaction_a.get_rust_handle (), wallet_a.c_str (), export_a.c_str (), passphrase_a.c_str ()) },
+	fanout{ fanout_a }
+{
+	init_a = rust_handle == nullptr;
+}
+
 nano::wallet_store::~wallet_store ()
 {
 	if (rust_handle != nullptr)
@@ -177,6 +185,21 @@ nano::account nano::wallet_store::representative (nano::transaction const & tran
 	return rep;
 }
 
+std::optional<nano::amount> nano::wallet_store::receive_minimum (nano::transaction const & transaction_a)
+{
+	nano::amount minimum;
+	if (rsnano::rsn_lmdb_wallet_store_receive_minimum (rust_handle, transaction_a.get_rust_handle (), minimum.bytes.data ()))
+	{
+		return minimum;
+	}
+	return std::nullopt;
+}
+
+void nano::wallet_store::receive_minimum_set (nano::transaction const & transaction_a, std::optional<nano::amount> const & minimum_a)
+{
+	rsnano::rsn_lmdb_wallet_store_receive_minimum_set (rust_handle, transaction_a.get_rust_handle (), minimum_a ? minimum_a->bytes.data () : nullptr);
+}
+
 nano::public_key nano::wallet_store::insert_adhoc (nano::transaction const & transaction_a, nano::raw_key const & prv)
 {
 	nano::public_key pub;
@@ -222,6 +245,18 @@ void nano::wallet_store::write_backup (nano::transaction const & transaction_a,
 	rsnano::rsn_lmdb_wallet_store_write_backup (rust_handle, transaction_a.get_rust_handle (), path_a.c_str ());
 }
 
+void nano::wallet_store::write_rotated_backup (nano::transaction const & transaction_a, boost::filesystem::path const & path_a, std::size_t keep_a)
+{
+	rsnano::rsn_lmdb_wallet_store_write_rotated_backup (rust_handle, transaction_a.get_rust_handle (), path_a.c_str (), keep_a);
+}
+
+std::string nano::wallet_store::export_encrypted (nano::transaction const & transaction_a, std::string const & passphrase_a)
+{
+	rsnano::StringDto dto;
+	rsnano::rsn_lmdb_wallet_store_export (rust_handle, transaction_a.get_rust_handle (), passphrase_a.c_str (), &dto);
+	return rsnano::convert_dto_to_string (dto);
+}
+
 bool nano::wallet_store::move (nano::transaction const & transaction_a, nano::wallet_store & other_a, std::vector<nano::public_key> const & keys)
 {
 	return !rsnano::rsn_lmdb_wallet_store_move (rust_handle, transaction_a.get_rust_handle (), other_a.rust_handle, reinterpret_cast<const uint8_t *> (keys.data ()), keys.size ());
@@ -274,6 +309,12 @@ nano::wallet::wallet (bool & init_a, nano::transaction & transaction_a, nano::wa
 {
 }
 
+nano::wallet::wallet (bool & init_a, nano::transaction & transaction_a, nano::wallets & wallets_a, std::string const & wallet_a, std::string const & export_a, std::string const & passphrase_a) :
+	store (init_a, wallets_a.kdf, transaction_a, wallets_a.node.config->password_fanout, wallet_a, export_a, passphrase_a),
+	wallets (wallets_a)
+{
+}
+
 void nano::wallet::enter_initial_password ()
 {
 	nano::raw_key password_l;
@@ -433,7 +474,7 @@ std::shared_ptr<nano::block> nano::wallet::receive_action (nano::block_hash cons
 {
 	std::shared_ptr<nano::block> block;
 	nano::epoch epoch = nano::epoch::epoch_0;
-	if (wallets.node.config->receive_minimum.number () <= amount_a.number ())
+	if (receive_minimum (*wallets.tx_begin_read ()) <= amount_a.number ())
 	{
 		auto block_transaction (wallets.node.ledger.store.tx_begin_read ());
 		auto transaction (wallets.tx_begin_read ());
@@ -770,7 +811,7 @@ bool nano::wallet::search_receivable (nano::transaction const & wallet_transacti
 					auto hash (key.hash);
 					nano::pending_info pending (j->second);
 					auto amount (pending.amount.number ());
-					if (wallets.node.config->receive_minimum.number () <= amount)
+					if (receive_minimum (wallet_transaction_a) <= amount)
 					{
 						wallets.node.logger->try_log (boost::str (boost::format ("Found a receivable block %1% for account %2%") % hash.to_string () % pending.source.to_account ()));
 						if (wallets.node.ledger.block_confirmed (*block_transaction, hash))
@@ -801,6 +842,12 @@ bool nano::wallet::search_receivable (nano::transaction const & wallet_transacti
 	return result;
 }
 
+nano::uint128_t nano::wallet::receive_minimum (nano::transaction const & transaction_a)
+{
+	auto minimum (store.receive_minimum (transaction_a));
+	return minimum ? minimum->number () : wallets.node.config->receive_minimum.number ();
+}
+
 uint32_t nano::wallet::deterministic_check (nano::transaction const & transaction_a, uint32_t index)
 {
 	auto block_transaction (wallets.node.store.tx_begin_read ());
@@ -1010,6 +1057,37 @@ std::shared_ptr<nano::wallet> nano::wallets::create (nano::wallet_id const & id_
 	return result;
 }
 
+std::optional<std::string> nano::wallets::export_wallet (nano::wallet_id const & id_a, std::string const & passphrase_a)
+{
+	nano::lock_guard<nano::mutex> lock{ mutex };
+	auto existing (items.find (id_a));
+	if (existing == items.end ())
+	{
+		return std::nullopt;
+	}
+	auto transaction (tx_begin_read ());
+	return existing->second->store.export_encrypted (*transaction, passphrase_a);
+}
+
+std::shared_ptr<nano::wallet> nano::wallets::import_wallet (nano::wallet_id const & id_a, std::string const & export_a, std::string const & passphrase_a)
+{
+	nano::lock_guard<nano::mutex> lock{ mutex };
+	debug_assert (items.find (id_a) == items.end ());
+	std::shared_ptr<nano::wallet> result;
+	bool error;
+	{
+		auto transaction (tx_begin_write ());
+		result = std::make_shared<nano::wallet> (error, *transaction, *this, id_a.to_string (), export_a, passphrase_a);
+	}
+	if (error)
+	{
+		return nullptr;
+	}
+	items[id_a] = result;
+	result->enter_initial_password ();
+	return result;
+}
+
 bool nano::wallets::search_receivable (nano::wallet_id const & wallet_a)
 {
 	auto result (false);
@@ -11,6 +11,7 @@
 
 #include <atomic>
 #include <mutex>
+#include <optional>
 #include <thread>
 #include <unordered_set>
 namespace rsnano
@@ -44,6 +45,8 @@ class wallet_store final
 public:
 	wallet_store (bool &, nano::kdf &, nano::transaction &, nano::account, unsigned, std::string const &);
 	wallet_store (bool &, nano::kdf &, nano::transaction &, nano::account, unsigned, std::string const &, std::string const &);
+	/** Restores a wallet from an encrypted export */
+	wallet_store (bool &, nano::kdf &, nano::transaction &, unsigned, std::string const & wallet_a, std::string const & export_a, std::string const & passphrase_a);
 	~wallet_store ();
 	wallet_store (wallet_store const &) = delete;
 	bool is_open () const;
@@ -69,6 +72,9 @@ public:
 	bool is_representative (nano::transaction const &);
 	nano::account representative (nano::transaction const &);
 	void representative_set (nano::transaction const &, nano::account const &);
+	/** Returns an empty value if the wallet uses the receive minimum of the node config */
+	std::optional<nano::amount> receive_minimum (nano::transaction const &);
+	void receive_minimum_set (nano::transaction const &, std::optional<nano::amount> const &);
 	nano::public_key insert_adhoc (nano::transaction const &, nano::raw_key const &);
 	bool insert_watch (nano::transaction const &, nano::account const &);
 	void erase (nano::transaction const &, nano::account const &);
@@ -82,6 +88,10 @@ public:
 	void derive_key (nano::raw_key &, nano::transaction const &, std::string const &);
 	void serialize_json (nano::transaction const &, std::string &);
 	void write_backup (nano::transaction const &, boost::filesystem::path const &);
+	/** Keeps the previous backups as path.1 to path.<keep>. Nothing is written if the wallet didn't change */
+	void write_rotated_backup (nano::transaction const &, boost::filesystem::path const &, std::size_t keep);
+	/** The serialized wallet encrypted with the passphrase. The keys stay encrypted by the wallet password */
+	std::string export_encrypted (nano::transaction const &, std::string const & passphrase_a);
 	bool move (nano::transaction const &, nano::wallet_store &, std::vector<nano::public_key> const &);
 	bool import (nano::transaction const &, nano::wallet_store &);
 	bool work_get (nano::transaction const &, nano::public_key const &, uint64_t &);
@@ -107,6 +117,7 @@ public:
 	bool action_complete (std::shared_ptr<nano::block> const &, nano::account const &, bool const, nano::block_details const &);
 	wallet (bool &, nano::transaction &, nano::wallets &, std::string const &);
 	wallet (bool &, nano::transaction &, nano::wallets &, std::string const &, std::string const &);
+	wallet (bool &, nano::transaction &, nano::wallets &, std::string const & wallet_a, std::string const & export_a, std::string const & passphrase_a);
 	void enter_initial_password ();
 	bool enter_password (nano::transaction const &, std::string const &);
 	nano::public_key insert_adhoc (nano::raw_key const &, bool = true);
@@ -128,6 +139,8 @@ public:
 	// Schedule work generation after a few seconds
 	void work_ensure (nano::account const &, nano::root const &);
 	bool search_receivable (nano::transaction const &);
+	/** Blocks below this amount are not received automatically, but stay receivable */
+	nano::uint128_t receive_minimum (nano::transaction const &);
 	uint32_t deterministic_check (nano::transaction const & transaction_a, uint32_t index);
 	/** Changes the wallet seed and returns the first account */
 	nano::public_key change_seed (nano::transaction const & transaction_a, nano::raw_key const & prv_a, uint32_t count = 0);
@@ -173,6 +186,10 @@ public:
 	~wallets ();
 	std::shared_ptr<nano::wallet> open (nano::wallet_id const &);
 	std::shared_ptr<nano::wallet> create (nano::wallet_id const &);
+	/** Returns an empty value if the wallet doesn't exist */
+	std::optional<std::string> export_wallet (nano::wallet_id const &, std::string const & passphrase_a);
+	/** Creates a wallet from an export. Returns nullptr if the passphrase is wrong or the export is invalid */
+	std::shared_ptr<nano::wallet> import_wallet (nano::wallet_id const &, std::string const & export_a, std::string const & passphrase_a);
 	bool search_receivable (nano::wallet_id const &);
 	void search_receivable_all ();
 	void destroy (nano::wallet_id const &);
@@ -207,6 +224,8 @@ public:
 	std::thread thread;
 	static nano::uint128_t const generate_priority;
 	static nano::uint128_t const high_priority;
+	/** Number of previous wallet backups which are kept */
+	static std::size_t constexpr backup_count = 5;
 	/** Start read-write transaction */
 	std::unique_ptr<nano::write_transaction> tx_begin_write ();
 
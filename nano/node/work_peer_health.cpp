@@ -0,0 +1,59 @@
+#include <nano/lib/rsnano.hpp>
+#include <nano/node/work_peer_health.hpp>
+
+#include <boost/property_tree/ptree.hpp>
+
+namespace
+{
+std::string peer_key (std::pair<std::string, uint16_t> const & peer_a)
+{
+	return peer_a.first + ":" + std::to_string (peer_a.second);
+}
+}
+
+nano::work_peer_health::work_peer_health () :
+	handle{ rsnano::rsn_work_peer_health_create () }
+{
+}
+
+nano::work_peer_health::~work_peer_health ()
+{
+	rsnano::rsn_work_peer_health_destroy (handle);
+}
+
+bool nano::work_peer_health::is_available (std::pair<std::string, uint16_t> const & peer_a)
+{
+	auto key{ peer_key (peer_a) };
+	return rsnano::rsn_work_peer_health_is_available (handle, reinterpret_cast<const int8_t *> (key.c_str ()));
+}
+
+std::vector<std::pair<std::string, uint16_t>> nano::work_peer_health::available (std::vector<std::pair<std::string, uint16_t>> const & peers_a)
+{
+	std::vector<std::pair<std::string, uint16_t>> result;
+	for (auto const & peer : peers_a)
+	{
+		if (is_available (peer))
+		{
+			result.push_back (peer);
+		}
+	}
+	return result;
+}
+
+void nano::work_peer_health::record_success (std::pair<std::string, uint16_t> const & peer_a, std::optional<std::chrono::milliseconds> latency_a)
+{
+	auto key{ peer_key (peer_a) };
+	int64_t latency_ms = latency_a.has_value () ? latency_a->count () : -1;
+	rsnano::rsn_work_peer_health_record_success (handle, reinterpret_cast<const int8_t *> (key.c_str ()), latency_ms);
+}
+
+void nano::work_peer_health::record_failure (std::pair<std::string, uint16_t> const & peer_a)
+{
+	auto key{ peer_key (peer_a) };
+	rsnano::rsn_work_peer_health_record_failure (handle, reinterpret_cast<const int8_t *> (key.c_str ()));
+}
+
+void nano::work_peer_health::serialize_json (boost::property_tree::ptree & tree_a)
+{
+	rsnano::rsn_work_peer_health_serialize_json (handle, &tree_a);
+}
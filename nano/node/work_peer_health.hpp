@@ -0,0 +1,41 @@
+#pragma once
+
+#include <boost/property_tree/ptree_fwd.hpp>
+
+#include <chrono>
+#include <cstdint>
+#include <optional>
+#include <string>
+#include <utility>
+#include <vector>
+
+namespace rsnano
+{
+class WorkPeerHealthHandle;
+}
+
+namespace nano
+{
+// Circuit breaker for work peers. Peers which fail repeatedly are skipped until a cooldown has passed,
+// then a single request probes whether the peer recovered. Also collects the work latency per peer.
+class work_peer_health final
+{
+public:
+	work_peer_health ();
+	work_peer_health (nano::work_peer_health const &) = delete;
+	work_peer_health (nano::work_peer_health &&) = delete;
+	~work_peer_health ();
+	nano::work_peer_health & operator= (nano::work_peer_health const &) = delete;
+	nano::work_peer_health & operator= (nano::work_peer_health &&) = delete;
+	bool is_available (std::pair<std::string, uint16_t> const & peer);
+	// Returns the peers which should be asked for work
+	std::vector<std::pair<std::string, uint16_t>> available (std::vector<std::pair<std::string, uint16_t>> const & peers);
+	// Health checks succeed without a latency
+	void record_success (std::pair<std::string, uint16_t> const & peer, std::optional<std::chrono::milliseconds> latency = std::nullopt);
+	void record_failure (std::pair<std::string, uint16_t> const & peer);
+	void serialize_json (boost::property_tree::ptree &);
+
+private:
+	rsnano::WorkPeerHealthHandle * handle;
+};
+}
@@ -79,3 +79,9 @@ nano::write_guard nano::write_database_queue::pop ()
 {
 	return nano::write_guard (rsnano::rsn_write_database_queue_pop (handle));
 }
+
+std::unique_ptr<nano::container_info_component> nano::write_database_queue::collect_container_info (std::string const & name)
+{
+	auto info_handle = rsnano::rsn_write_database_queue_collect_container_info (handle, name.c_str ());
+	return std::make_unique<nano::container_info_composite> (info_handle);
+}
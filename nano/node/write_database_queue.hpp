@@ -1,6 +1,7 @@
 #pragma once
 
 #include <nano/lib/locks.hpp>
+#include <nano/lib/utility.hpp>
 
 #include <condition_variable>
 #include <deque>
@@ -60,6 +61,8 @@ public:
 	/** Doesn't actually pop anything until the returned write_guard is out of scope */
 	write_guard pop ();
 
+	std::unique_ptr<nano::container_info_component> collect_container_info (std::string const & name);
+
 	rsnano::WriteDatabaseQueueHandle * handle;
 };
 }
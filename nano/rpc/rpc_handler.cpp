@@ -181,6 +181,7 @@ std::unordered_set<std::string> create_rpc_control_impls ()
 	set.emplace ("wallet_create");
 	set.emplace ("wallet_destroy");
 	set.emplace ("wallet_lock");
+	set.emplace ("wallet_receive_minimum_set");
 	set.emplace ("wallet_representative_set");
 	set.emplace ("wallet_republish");
 	set.emplace ("wallet_work_get");
@@ -191,6 +192,7 @@ std::unordered_set<std::string> create_rpc_control_impls ()
 	set.emplace ("work_peer_add");
 	set.emplace ("work_peers");
 	set.emplace ("work_peers_clear");
+	set.emplace ("work_threshold_set");
 	set.emplace ("wallet_seed");
 	return set;
 }
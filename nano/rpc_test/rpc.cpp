@@ -520,6 +520,36 @@ TEST (rpc, wallet_representative_set)
 	ASSERT_EQ (key.pub, node->wallets.items.begin ()->second->store.representative (*transaction));
 }
 
+TEST (rpc, wallet_receive_minimum_set)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	boost::property_tree::ptree request;
+	std::string wallet;
+	node->wallets.items.begin ()->first.encode_hex (wallet);
+	request.put ("wallet", wallet);
+	request.put ("action", "wallet_receive_minimum");
+	auto response (wait_response (system, rpc_ctx, request));
+	ASSERT_EQ (node->config->receive_minimum.to_string_dec (), response.get<std::string> ("amount"));
+	ASSERT_EQ ("1", response.get<std::string> ("default"));
+
+	request.put ("action", "wallet_receive_minimum_set");
+	request.put ("amount", "1000");
+	wait_response (system, rpc_ctx, request);
+	request.erase ("amount");
+	request.put ("action", "wallet_receive_minimum");
+	auto response2 (wait_response (system, rpc_ctx, request));
+	ASSERT_EQ ("1000", response2.get<std::string> ("amount"));
+	ASSERT_EQ ("0", response2.get<std::string> ("default"));
+
+	// Without an amount the override is removed
+	request.put ("action", "wallet_receive_minimum_set");
+	wait_response (system, rpc_ctx, request);
+	auto transaction (node->wallets.tx_begin_read ());
+	ASSERT_FALSE (node->wallets.items.begin ()->second->store.receive_minimum (*transaction));
+}
+
 TEST (rpc, wallet_representative_set_force)
 {
 	nano::test::system system;
@@ -1672,6 +1702,40 @@ TEST (rpc, process_ledger_insufficient_work)
 	ASSERT_EQ (response.get<std::string> ("error"), ec.message ());
 }
 
+TEST (rpc, process_require_active_difficulty)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	auto latest (node->latest (nano::dev::genesis_key.pub));
+	auto min_difficulty = node->network_params.work.get_entry ();
+	auto max_difficulty = node->network_params.work.get_epoch_1 ();
+	nano::block_builder builder;
+	auto send = builder
+				.state ()
+				.account (nano::dev::genesis->account ())
+				.previous (latest)
+				.representative (nano::dev::genesis->account ())
+				.balance (nano::dev::constants.genesis_amount - nano::Gxrb_ratio)
+				.link (nano::dev::genesis_key.pub)
+				.sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				.work (system.work_generate_limited (latest, min_difficulty, max_difficulty))
+				.build ();
+	ASSERT_LT (nano::dev::network_params.work.difficulty (*send), node->active_difficulty.difficulty ());
+	boost::property_tree::ptree request;
+	request.put ("action", "process");
+	std::string json;
+	send->serialize_json (json);
+	request.put ("block", json);
+	request.put ("subtype", "send");
+	request.put ("require_active_difficulty", "true");
+	auto response (wait_response (system, rpc_ctx, request));
+	std::error_code ec (nano::error_rpc::work_below_active_difficulty);
+	ASSERT_EQ (1, response.count ("error"));
+	ASSERT_EQ (response.get<std::string> ("error"), ec.message ());
+	ASSERT_EQ (latest, node->latest (nano::dev::genesis_key.pub));
+}
+
 TEST (rpc, keepalive)
 {
 	nano::test::system system;
@@ -1731,6 +1795,28 @@ TEST (rpc, peers_node_id)
 	// The previous version of this test had an UDP connection to an arbitrary IP address, so it could check for two peers. This doesn't work with TCP.
 }
 
+TEST (rpc, peers_histogram)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const node2 = system.add_node ();
+	auto const rpc_ctx = add_rpc (system, node);
+	auto channel = node->network->find_node_id (node2->get_node_id ());
+	ASSERT_NE (nullptr, channel);
+	std::optional<nano::telemetry_data> telemetry_data;
+	ASSERT_TIMELY (10s, telemetry_data = node->telemetry->get_telemetry (channel->get_remote_endpoint ()));
+	boost::property_tree::ptree request;
+	request.put ("action", "peers");
+	request.put ("peer_details", true);
+	auto response (wait_response (system, rpc_ctx, request));
+	auto & peers_node (response.get_child ("peers"));
+	auto tree1 (peers_node.get_child (channel->to_string ()));
+	ASSERT_EQ (std::to_string (telemetry_data->get_bandwidth_cap ()), tree1.get<std::string> ("bandwidth_cap"));
+	auto & histogram (response.get_child ("histogram"));
+	ASSERT_EQ ("1", histogram.get<std::string> ("total"));
+	ASSERT_EQ ("1", histogram.get_child ("protocol_versions").get<std::string> (std::to_string (node->network_params.network.protocol_version)));
+}
+
 TEST (rpc, version)
 {
 	nano::test::system system;
@@ -4407,6 +4493,29 @@ TEST (rpc, work_peers_all)
 	ASSERT_EQ (0, peers_node.size ());
 }
 
+TEST (rpc, work_threshold_set)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	boost::property_tree::ptree request;
+	request.put ("action", "work_threshold_set");
+	request.put ("threshold", "8000000000000000");
+	auto response (wait_response (system, rpc_ctx, request));
+	ASSERT_TRUE (response.get<std::string> ("success", "-").empty ());
+	ASSERT_EQ (0x8000000000000000, node->network_params.work.get_base ());
+	ASSERT_EQ (0x8000000000000000, node->network_params.work.threshold_entry (nano::work_version::work_1, nano::block_type::state));
+	// Only the thresholds of this node are overridden
+	ASSERT_EQ (nano::dev::network_params.work.dto.base, nano::dev::network_params.work.get_base ());
+
+	// Without a threshold the override is removed
+	boost::property_tree::ptree request2;
+	request2.put ("action", "work_threshold_set");
+	auto response2 (wait_response (system, rpc_ctx, request2));
+	ASSERT_TRUE (response2.get<std::string> ("success", "-").empty ());
+	ASSERT_EQ (nano::dev::network_params.work.dto.base, node->network_params.work.get_base ());
+}
+
 TEST (rpc, populate_backlog)
 {
 	nano::test::system system;
@@ -5821,6 +5930,143 @@ TEST (rpc, memory_stats)
 	}
 }
 
+TEST (rpc, stats_confirmation_latency)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	auto send = nano::send_block_builder ()
+				.previous (nano::dev::genesis->hash ())
+				.destination (nano::dev::genesis_key.pub)
+				.balance (nano::dev::constants.genesis_amount - 1)
+				.sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				.work (*system.work.generate (nano::dev::genesis->hash ()))
+				.build_shared ();
+	node->process_active (send);
+	ASSERT_TIMELY (5s, node->block (send->hash ()) != nullptr);
+	ASSERT_TIMELY (5s, nano::test::confirm (*node, { send }));
+	ASSERT_TIMELY (5s, node->block_confirmed (send->hash ()));
+	boost::property_tree::ptree request;
+	request.put ("action", "stats");
+	request.put ("type", "confirmation_latency");
+	auto response (wait_response (system, rpc_ctx, request));
+	auto & buckets (response.get_child ("buckets"));
+	ASSERT_EQ (1, buckets.size ());
+	ASSERT_EQ ("1", buckets.front ().second.get<std::string> ("count"));
+	ASSERT_EQ ("0", response.get<std::string> ("tracked"));
+}
+
+TEST (rpc, stats_block_traces)
+{
+	nano::test::system system;
+	nano::node_config node_config = system.default_config ();
+	nano::node_flags node_flags;
+	node_flags.set_block_trace_sample_interval (1);
+	auto node = add_ipc_enabled_node (system, node_config, node_flags);
+	auto const rpc_ctx = add_rpc (system, node);
+	auto send = nano::send_block_builder ()
+				.previous (nano::dev::genesis->hash ())
+				.destination (nano::dev::genesis_key.pub)
+				.balance (nano::dev::constants.genesis_amount - 1)
+				.sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				.work (*system.work.generate (nano::dev::genesis->hash ()))
+				.build_shared ();
+	node->process_active (send);
+	ASSERT_TIMELY (5s, nano::test::confirm (*node, { send }));
+	// The cemented event is recorded by the cementation callback, which runs after the confirmation height was written
+	ASSERT_TIMELY (5s, node->block_tracer.summary (send->hash ()).value_or ("").find ("cemented") != std::string::npos);
+	boost::property_tree::ptree request;
+	request.put ("action", "stats");
+	request.put ("type", "block_traces");
+	auto response (wait_response (system, rpc_ctx, request));
+	ASSERT_EQ ("1", response.get<std::string> ("sample_interval"));
+	auto & traces (response.get_child ("traces"));
+	auto trace = std::find_if (traces.begin (), traces.end (), [&send] (auto const & trace) {
+		return trace.second.template get<std::string> ("hash") == send->hash ().to_string ();
+	});
+	ASSERT_NE (traces.end (), trace);
+	auto & events (trace->second.get_child ("events"));
+	ASSERT_EQ ("0", events.get<std::string> ("first_seen"));
+	ASSERT_TRUE (events.get_optional<std::string> ("inserted").is_initialized ());
+	ASSERT_TRUE (events.get_optional<std::string> ("cemented").is_initialized ());
+}
+
+TEST (rpc, stats_work_peers)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	std::pair<std::string, uint16_t> good_peer ("127.0.0.1", 7000);
+	std::pair<std::string, uint16_t> bad_peer ("127.0.0.1", 7001);
+	node->distributed_work.health.record_success (good_peer, std::chrono::milliseconds (100));
+	node->distributed_work.health.record_success (good_peer, std::chrono::milliseconds (300));
+	for (auto i = 0; i < 3; ++i)
+	{
+		node->distributed_work.health.record_failure (bad_peer);
+	}
+	boost::property_tree::ptree request;
+	request.put ("action", "stats");
+	request.put ("type", "work_peers");
+	auto response (wait_response (system, rpc_ctx, request));
+	auto & peers (response.get_child ("work_peers"));
+	ASSERT_EQ (2, peers.size ());
+	auto good = peers.begin ()->second;
+	ASSERT_EQ ("127.0.0.1:7000", good.get<std::string> ("peer"));
+	ASSERT_EQ ("closed", good.get<std::string> ("state"));
+	ASSERT_EQ ("2", good.get<std::string> ("successes"));
+	ASSERT_EQ ("200", good.get<std::string> ("average_latency_ms"));
+	auto bad = std::next (peers.begin ())->second;
+	ASSERT_EQ ("127.0.0.1:7001", bad.get<std::string> ("peer"));
+	ASSERT_EQ ("open", bad.get<std::string> ("state"));
+	ASSERT_EQ ("3", bad.get<std::string> ("failures"));
+}
+
+TEST (rpc, stats_dead_letters)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	nano::block_hash dead (1);
+	nano::block_hash retried (2);
+	for (auto i = 0; i < 3; ++i)
+	{
+		node->block_processor.dead_letters.record (dead, nano::dev::genesis_key.pub, nano::process_result::gap_previous);
+	}
+	node->block_processor.dead_letters.record (dead, nano::dev::genesis_key.pub, nano::process_result::fork);
+	node->block_processor.dead_letters.record (retried, nano::dev::genesis_key.pub, nano::process_result::gap_source);
+	boost::property_tree::ptree request;
+	request.put ("action", "stats");
+	request.put ("type", "dead_letters");
+	auto response (wait_response (system, rpc_ctx, request));
+	auto & letters (response.get_child ("dead_letters"));
+	// Blocks which failed only once aren't dead letters yet
+	ASSERT_EQ (1, letters.size ());
+	auto letter = letters.begin ()->second;
+	ASSERT_EQ (dead.to_string (), letter.get<std::string> ("hash"));
+	ASSERT_EQ (nano::dev::genesis_key.pub.to_account (), letter.get<std::string> ("account"));
+	ASSERT_EQ ("fork", letter.get<std::string> ("last_result"));
+	ASSERT_EQ ("4", letter.get<std::string> ("attempts"));
+	ASSERT_NE ("0", letter.get<std::string> ("first_failure"));
+}
+
+TEST (rpc, database_stats)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	auto const rpc_ctx = add_rpc (system, node);
+	boost::property_tree::ptree request;
+	request.put ("action", "database_stats");
+	auto response (wait_response (system, rpc_ctx, request));
+	ASSERT_NE ("0", response.get<std::string> ("map_used"));
+	auto & tables (response.get_child ("tables"));
+	auto blocks = std::find_if (tables.begin (), tables.end (), [] (auto const & table) {
+		return table.second.template get<std::string> ("name") == "blocks";
+	});
+	ASSERT_NE (tables.end (), blocks);
+	ASSERT_EQ ("1", blocks->second.get<std::string> ("entries"));
+	ASSERT_NE ("0", blocks->second.get<std::string> ("size"));
+}
+
 TEST (rpc, block_confirmed)
 {
 	nano::test::system system;
@@ -5995,6 +6241,7 @@ TEST (rpc, active_difficulty)
 	auto expected_multiplier{ 1.0 };
 	{
 		auto response (wait_response (system, rpc_ctx, request));
+		ASSERT_EQ ("1", response.get<std::string> ("deprecated"));
 		auto network_minimum_text (response.get<std::string> ("network_minimum"));
 		uint64_t network_minimum;
 		ASSERT_FALSE (nano::from_string_hex (network_minimum_text, network_minimum));
@@ -6284,6 +6531,34 @@ TEST (rpc, epoch_upgrade)
 	}
 }
 
+TEST (rpc, epoch_upgrade_progress)
+{
+	nano::test::system system;
+	auto node = add_ipc_enabled_node (system);
+	nano::keypair key1;
+	nano::block_builder builder;
+	auto send1 = builder
+				 .state ()
+				 .account (nano::dev::genesis_key.pub)
+				 .previous (nano::dev::genesis->hash ())
+				 .representative (nano::dev::genesis_key.pub)
+				 .balance (nano::dev::constants.genesis_amount - 1)
+				 .link (key1.pub)
+				 .sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				 .work (*system.work.generate (nano::dev::genesis->hash ()))
+				 .build_shared (); // to unopened account (pending)
+	ASSERT_EQ (nano::process_result::progress, node->process (*send1).code);
+	auto const rpc_ctx = add_rpc (system, node);
+	boost::property_tree::ptree request;
+	request.put ("action", "epoch_upgrade_progress");
+	auto response (wait_response (system, rpc_ctx, request));
+	ASSERT_EQ ("1", response.get<std::string> ("accounts_per_epoch.epoch_0"));
+	ASSERT_EQ ("0", response.get<std::string> ("accounts_per_epoch.epoch_1"));
+	ASSERT_EQ ("0", response.get<std::string> ("accounts_per_epoch.epoch_2"));
+	ASSERT_EQ ("1", response.get<std::string> ("unopened_with_receivables"));
+	ASSERT_EQ ("2", response.get<std::string> ("unupgraded"));
+}
+
 TEST (rpc, epoch_upgrade_multithreaded)
 {
 	nano::test::system system;
@@ -6896,6 +7171,70 @@ TEST (rpc, confirmation_active)
 	}
 }
 
+TEST (rpc, confirmation_active_pages_and_details)
+{
+	nano::test::system system;
+	nano::node_config node_config;
+	node_config.ipc_config.transport_tcp.enabled = true;
+	node_config.ipc_config.transport_tcp.port = system.get_available_port ();
+	nano::node_flags node_flags;
+	node_flags.set_disable_request_loop (true);
+	auto node1 (system.add_node (node_config, node_flags));
+	auto const rpc_ctx = add_rpc (system, node1);
+
+	nano::block_builder builder;
+	auto send1 = builder
+				 .send ()
+				 .previous (nano::dev::genesis->hash ())
+				 .destination (nano::public_key ())
+				 .balance (nano::dev::constants.genesis_amount - 100)
+				 .sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				 .work (*system.work.generate (nano::dev::genesis->hash ()))
+				 .build_shared ();
+	auto send2 = builder
+				 .send ()
+				 .previous (send1->hash ())
+				 .destination (nano::public_key ())
+				 .balance (nano::dev::constants.genesis_amount - 200)
+				 .sign (nano::dev::genesis_key.prv, nano::dev::genesis_key.pub)
+				 .work (*system.work.generate (send1->hash ()))
+				 .build_shared ();
+	node1->process_active (send1);
+	node1->process_active (send2);
+	nano::test::start_elections (system, *node1, { send1, send2 });
+	ASSERT_EQ (2, node1->active.size ());
+
+	boost::property_tree::ptree request;
+	request.put ("action", "confirmation_active");
+	request.put ("count", "1");
+	request.put ("details", "true");
+	std::vector<std::string> roots;
+	{
+		auto response (wait_response (system, rpc_ctx, request));
+		auto & confirmations (response.get_child ("confirmations"));
+		ASSERT_EQ (1, confirmations.size ());
+		ASSERT_EQ (2, response.get<unsigned> ("unconfirmed"));
+		ASSERT_EQ ("1", response.get<std::string> ("next_offset"));
+		auto & entry (confirmations.front ().second);
+		roots.push_back (entry.get<std::string> ("root"));
+		ASSERT_EQ (1, entry.get_child ("blocks").size ());
+		ASSERT_EQ ("0", entry.get<std::string> ("announcements"));
+		ASSERT_FALSE (entry.get<bool> ("quorum"));
+	}
+	request.put ("offset", "1");
+	{
+		auto response (wait_response (system, rpc_ctx, request));
+		auto & confirmations (response.get_child ("confirmations"));
+		ASSERT_EQ (1, confirmations.size ());
+		ASSERT_FALSE (response.get_optional<std::string> ("next_offset").is_initialized ());
+		roots.push_back (confirmations.front ().second.get<std::string> ("root"));
+	}
+	ASSERT_LT (roots[0], roots[1]);
+	std::vector<std::string> expected{ send1->qualified_root ().to_string (), send2->qualified_root ().to_string () };
+	std::sort (expected.begin (), expected.end ());
+	ASSERT_EQ (expected, roots);
+}
+
 TEST (rpc, confirmation_info)
 {
 	nano::test::system system;
@@ -79,6 +79,25 @@ rsnano::NetworkParamsDto nano::network_params::to_dto () const
 	return dto;
 }
 
+bool nano::network_params::set_work_threshold_override (uint64_t threshold_a)
+{
+	auto const current_network = network.current_network;
+	if (!work.set_override (current_network, threshold_a))
+	{
+		return false;
+	}
+	network.work.set_override (current_network, threshold_a);
+	ledger.work.set_override (current_network, threshold_a);
+	return true;
+}
+
+void nano::network_params::serialize_json (boost::property_tree::ptree & tree) const
+{
+	auto dto{ to_dto () };
+	[[maybe_unused]] auto serialized = rsnano::rsn_network_params_serialize_json (&dto, &tree);
+	debug_assert (serialized);
+}
+
 nano::ledger_constants::ledger_constants (nano::work_thresholds work_a, nano::networks network_a) :
 	work (nano::work_thresholds (0, 0, 0))
 {
@@ -1026,6 +1045,14 @@ void nano::generate_cache::enable_block_count (bool enable)
 {
 	rsnano::rsn_generate_cache_set_account_count (handle, enable);
 }
+bool nano::generate_cache::receivable () const
+{
+	return rsnano::rsn_generate_cache_receivable (handle);
+}
+void nano::generate_cache::enable_receivable (bool enable)
+{
+	rsnano::rsn_generate_cache_set_receivable (handle, enable);
+}
 
 nano::stat::detail nano::to_stat_detail (nano::process_result process_result)
 {
@@ -1059,6 +1086,14 @@ nano::stat::detail nano::to_stat_detail (nano::process_result process_result)
 			return nano::stat::detail::block_position;
 		case process_result::insufficient_work:
 			return nano::stat::detail::insufficient_work;
+		case process_result::height_overflow:
+			return nano::stat::detail::height_overflow;
+		case process_result::timestamp_in_future:
+			return nano::stat::detail::timestamp_in_future;
+		case process_result::confirmed_fork:
+			return nano::stat::detail::confirmed_fork;
+		case process_result::account_batch_limit:
+			return nano::stat::detail::account_batch_limit;
 	}
 	debug_assert (false && "There should be always a defined nano::stat::detail that is not _last");
 	throw std::runtime_error ("There should be always a defined nano::stat::detail that is not _last");
@@ -342,6 +342,9 @@ enum class vote_code
 	indeterminate // Unknown if replay or vote
 };
 
+/** Result of a vote for each of its hashes, in the order of the hashes in the vote */
+using vote_results = std::vector<std::pair<nano::block_hash, nano::vote_code>>;
+
 enum class process_result
 {
 	progress, // Hasn't been seen before, signed correctly
@@ -357,7 +360,11 @@ enum class process_result
 	balance_mismatch, // Balance and amount delta don't match
 	representative_mismatch, // Representative is changed when it is not allowed
 	block_position, // This block cannot follow the previous block
-	insufficient_work // Insufficient work for this block, even though it passed the minimal validation
+	insufficient_work, // Insufficient work for this block, even though it passed the minimal validation
+	height_overflow, // The height of the block would not fit into the sideband
+	timestamp_in_future, // The local timestamp of the block is too far in the future
+	confirmed_fork, // Fork of a block which is already cemented
+	account_batch_limit // Too many blocks of the account in this batch, deferred to the next batch
 };
 class process_return final
 {
@@ -501,6 +508,10 @@ public:
 	network_params (rsnano::NetworkParamsDto const & dto);
 
 	rsnano::NetworkParamsDto to_dto () const;
+	/** Overrides the work thresholds of these params. Zero removes the override. Returns false if the network doesn't allow it */
+	bool set_work_threshold_override (uint64_t threshold_a);
+	/** Epochs, work thresholds, protocol versions and voting constants, as returned by the network_params RPC */
+	void serialize_json (boost::property_tree::ptree & tree) const;
 
 	unsigned kdf_work;
 	nano::work_thresholds work;
@@ -540,6 +551,8 @@ public:
 	void enable_account_count (bool enable);
 	bool block_count () const;
 	void enable_block_count (bool enable);
+	bool receivable () const;
+	void enable_receivable (bool enable);
 	void enable_all ();
 	rsnano::GenerateCacheHandle * handle;
 };
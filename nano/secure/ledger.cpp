@@ -59,6 +59,135 @@ nano::uint128_t nano::ledger::account_receivable (nano::transaction const & tran
 	return result.number ();
 }
 
+std::optional<nano::account_details> nano::ledger::account_details (nano::transaction const & transaction_a, nano::account const & account_a, nano::receivable_details receivable_details_a)
+{
+	rsnano::AccountDetailsDto dto;
+	if (!rsnano::rsn_ledger_account_details (handle, transaction_a.get_rust_handle (), account_a.bytes.data (), static_cast<uint8_t> (receivable_details_a), &dto))
+	{
+		return std::nullopt;
+	}
+	nano::account_details result;
+	result.confirmation_height = nano::confirmation_height_info{ dto.confirmation_height };
+	std::copy (std::begin (dto.representative_block), std::end (dto.representative_block), std::begin (result.representative_block.bytes));
+	std::copy (std::begin (dto.confirmed_balance), std::end (dto.confirmed_balance), std::begin (result.confirmed_balance.bytes));
+	std::copy (std::begin (dto.confirmed_representative), std::end (dto.confirmed_representative), std::begin (result.confirmed_representative.bytes));
+	std::copy (std::begin (dto.receivable), std::end (dto.receivable), std::begin (result.receivable.bytes));
+	std::copy (std::begin (dto.confirmed_receivable), std::end (dto.confirmed_receivable), std::begin (result.confirmed_receivable.bytes));
+	return result;
+}
+
+nano::epoch_upgrade_progress nano::ledger::epoch_upgrade_progress (nano::transaction const & transaction_a)
+{
+	rsnano::EpochUpgradeProgressDto dto;
+	rsnano::rsn_ledger_epoch_upgrade_progress (handle, transaction_a.get_rust_handle (), &dto);
+	nano::epoch_upgrade_progress result;
+	result.epoch_0 = dto.epoch_0;
+	result.epoch_1 = dto.epoch_1;
+	result.epoch_2 = dto.epoch_2;
+	result.unopened_with_receivables = dto.unopened_with_receivables;
+	result.unupgraded = dto.unupgraded;
+	return result;
+}
+
+namespace
+{
+class rep_weight_observer_context
+{
+public:
+	nano::ledger::representative_changed_callback representative_changed;
+	nano::ledger::rep_weight_threshold_crossed_callback threshold_crossed;
+};
+
+void representative_changed (void * context, uint8_t const * account_bytes, uint8_t const * old_rep_bytes, uint8_t const * new_rep_bytes)
+{
+	auto observer = static_cast<rep_weight_observer_context *> (context);
+	nano::account account;
+	nano::account old_representative;
+	nano::account new_representative;
+	std::copy (account_bytes, account_bytes + 32, std::begin (account.bytes));
+	std::copy (old_rep_bytes, old_rep_bytes + 32, std::begin (old_representative.bytes));
+	std::copy (new_rep_bytes, new_rep_bytes + 32, std::begin (new_representative.bytes));
+	observer->representative_changed (account, old_representative, new_representative);
+}
+
+void rep_weight_threshold_crossed (void * context, uint8_t const * rep_bytes, uint8_t const * threshold_bytes, uint8_t const * weight_bytes, bool above)
+{
+	auto observer = static_cast<rep_weight_observer_context *> (context);
+	nano::account representative;
+	nano::amount threshold;
+	nano::amount weight;
+	std::copy (rep_bytes, rep_bytes + 32, std::begin (representative.bytes));
+	std::copy (threshold_bytes, threshold_bytes + 16, std::begin (threshold.bytes));
+	std::copy (weight_bytes, weight_bytes + 16, std::begin (weight.bytes));
+	observer->threshold_crossed (representative, threshold.number (), weight.number (), above);
+}
+
+void delete_rep_weight_observer_context (void * context)
+{
+	delete static_cast<rep_weight_observer_context *> (context);
+}
+
+std::string block_subtype_to_string (uint8_t subtype)
+{
+	switch (subtype)
+	{
+		case 0:
+			return "send";
+		case 1:
+		case 2:
+			return "receive";
+		case 4:
+			return "epoch";
+		default:
+			return "change";
+	}
+}
+}
+
+std::optional<nano::block_info> nano::ledger::block_info (nano::transaction const & transaction_a, nano::block_hash const & hash_a)
+{
+	rsnano::BlockInfoDto dto;
+	if (!rsnano::rsn_ledger_block_info (handle, transaction_a.get_rust_handle (), hash_a.bytes.data (), &dto))
+	{
+		return std::nullopt;
+	}
+	nano::block_info result;
+	std::copy (std::begin (dto.account), std::end (dto.account), std::begin (result.account.bytes));
+	if (dto.amount_valid)
+	{
+		nano::amount amount;
+		std::copy (std::begin (dto.amount), std::end (dto.amount), std::begin (amount.bytes));
+		result.amount = amount;
+	}
+	std::copy (std::begin (dto.balance), std::end (dto.balance), std::begin (result.balance.bytes));
+	result.height = dto.height;
+	result.local_timestamp = dto.local_timestamp;
+	std::copy (std::begin (dto.successor), std::end (dto.successor), std::begin (result.successor.bytes));
+	if (dto.has_subtype)
+	{
+		result.subtype = block_subtype_to_string (dto.subtype);
+	}
+	result.confirmed = dto.confirmed;
+	return result;
+}
+
+std::string nano::ledger::account_history (nano::transaction const & transaction_a, nano::account const & account_a, nano::block_hash const & head_a, std::size_t max_a, nano::block_hash & next_a)
+{
+	rsnano::StringDto dto;
+	if (!rsnano::rsn_ledger_account_history (handle, transaction_a.get_rust_handle (), account_a.bytes.data (), head_a.is_zero () ? nullptr : head_a.bytes.data (), max_a, &dto, next_a.bytes.data ()))
+	{
+		next_a.clear ();
+	}
+	return rsnano::convert_dto_to_string (dto);
+}
+
+nano::block_hash nano::ledger::receive_hash_for_send (nano::transaction const & transaction_a, nano::block_hash const & send_hash_a)
+{
+	nano::block_hash result;
+	rsnano::rsn_ledger_receive_hash_for_send (handle, transaction_a.get_rust_handle (), send_hash_a.bytes.data (), result.bytes.data ());
+	return result;
+}
+
 std::optional<nano::pending_info> nano::ledger::pending_info (nano::transaction const & transaction, nano::pending_key const & key) const
 {
 	nano::pending_info result;
@@ -78,6 +207,17 @@ nano::process_return nano::ledger::process (nano::write_transaction const & tran
 	return result;
 }
 
+std::size_t nano::ledger::valid_chain_prefix (nano::transaction const & transaction_a, std::vector<std::shared_ptr<nano::block>> const & blocks_a) const
+{
+	std::vector<rsnano::BlockHandle *> block_handles;
+	block_handles.reserve (blocks_a.size ());
+	for (auto const & block : blocks_a)
+	{
+		block_handles.push_back (block->get_handle ());
+	}
+	return rsnano::rsn_ledger_valid_chain_prefix (handle, transaction_a.get_rust_handle (), block_handles.data (), block_handles.size ());
+}
+
 nano::block_hash nano::ledger::representative (nano::transaction const & transaction_a, nano::block_hash const & hash_a)
 {
 	nano::block_hash result;
@@ -107,6 +247,25 @@ std::string nano::ledger::block_text (nano::block_hash const & hash_a)
 	return rsnano::convert_dto_to_string (dto);
 }
 
+std::string nano::ledger::check_integrity (nano::generate_cache const & generate_cache_a) const
+{
+	rsnano::StringDto dto;
+	rsnano::rsn_ledger_check_integrity (handle, generate_cache_a.handle, &dto);
+	return rsnano::convert_dto_to_string (dto);
+}
+
+std::string nano::ledger::stats_report () const
+{
+	rsnano::StringDto dto;
+	rsnano::rsn_ledger_stats_report (handle, &dto);
+	return rsnano::convert_dto_to_string (dto);
+}
+
+void nano::ledger::serialize_stats_report (boost::property_tree::ptree & tree_a) const
+{
+	rsnano::rsn_ledger_stats_report_serialize_json (handle, &tree_a);
+}
+
 bool nano::ledger::is_send (nano::transaction const & transaction_a, nano::block const & block_a) const
 {
 	return rsnano::rsn_ledger_is_send (handle, transaction_a.get_rust_handle (), block_a.get_handle ());
@@ -142,6 +301,20 @@ nano::uint128_t nano::ledger::weight (nano::account const & account_a)
 	return result.number ();
 }
 
+nano::uint128_t nano::ledger::weight_exact (nano::transaction const & transaction_a, nano::account const & representative_a)
+{
+	nano::amount result;
+	rsnano::rsn_ledger_weight_exact (handle, transaction_a.get_rust_handle (), representative_a.bytes.data (), result.bytes.data ());
+	return result.number ();
+}
+
+nano::uint256_union nano::ledger::ledger_checksum (nano::transaction const & transaction_a)
+{
+	nano::uint256_union result;
+	rsnano::rsn_ledger_checksum (handle, transaction_a.get_rust_handle (), result.bytes.data ());
+	return result;
+}
+
 // Rollback blocks until `block_a' doesn't exist or it tries to penetrate the confirmation height
 bool nano::ledger::rollback (nano::write_transaction const & transaction_a, nano::block_hash const & block_a, std::vector<std::shared_ptr<nano::block>> & list_a)
 {
@@ -157,6 +330,14 @@ bool nano::ledger::rollback (nano::write_transaction const & transaction_a, nano
 	return rollback (transaction_a, block_a, rollback_list);
 }
 
+bool nano::ledger::rollback_journaled (nano::write_transaction const & transaction_a, nano::block_hash const & block_a, std::vector<std::shared_ptr<nano::block>> & list_a)
+{
+	rsnano::BlockArrayDto list_dto;
+	auto error = rsnano::rsn_ledger_rollback_journaled (handle, transaction_a.get_rust_handle (), block_a.bytes.data (), &list_dto);
+	rsnano::read_block_array_dto (list_dto, list_a);
+	return error;
+}
+
 nano::account nano::ledger::account (nano::transaction const & transaction_a, nano::block_hash const & hash_a) const
 {
 	nano::account result;
@@ -237,11 +418,31 @@ bool nano::ledger::dependents_confirmed (nano::transaction const & transaction_a
 	return rsnano::rsn_ledger_dependents_confirmed (handle, transaction_a.get_rust_handle (), block_a.get_handle ());
 }
 
+bool nano::ledger::pruning_safe_to_vote (nano::transaction const & transaction_a, nano::root const & root_a) const
+{
+	return rsnano::rsn_ledger_pruning_safe_to_vote (handle, transaction_a.get_rust_handle (), root_a.bytes.data ());
+}
+
 bool nano::ledger::is_epoch_link (nano::link const & link_a) const
 {
 	return rsnano::rsn_ledger_is_epoch_link (handle, link_a.bytes.data ());
 }
 
+std::optional<nano::block_subtype> nano::ledger::state_block_subtype (nano::transaction const & transaction_a, nano::block const & block_a) const
+{
+	uint8_t subtype;
+	if (!rsnano::rsn_ledger_state_block_subtype (handle, transaction_a.get_rust_handle (), block_a.get_handle (), &subtype))
+	{
+		return std::nullopt;
+	}
+	return static_cast<nano::block_subtype> (subtype);
+}
+
+nano::subtype_check_result nano::ledger::check_subtype (nano::transaction const & transaction_a, nano::block const & block_a, std::string const & subtype_a) const
+{
+	return static_cast<nano::subtype_check_result> (rsnano::rsn_ledger_check_subtype (handle, transaction_a.get_rust_handle (), block_a.get_handle (), subtype_a.c_str ()));
+}
+
 std::array<nano::block_hash, 2> nano::ledger::dependent_blocks (nano::transaction const & transaction_a, nano::block const & block_a) const
 {
 	std::array<nano::block_hash, 2> result;
@@ -377,6 +578,17 @@ void nano::ledger::set_bootstrap_weights (std::unordered_map<nano::account, nano
 	rsnano::rsn_ledger_set_bootstrap_weights (handle, dtos.data (), dtos.size ());
 }
 
+bool nano::ledger::load_bootstrap_weights (uint8_t const * data_a, std::size_t size_a)
+{
+	return !rsnano::rsn_ledger_load_bootstrap_weights (handle, data_a, size_a);
+}
+
+bool nano::ledger::export_bootstrap_weights (nano::uint128_t const & min_weight_a, boost::filesystem::path const & path_a) const
+{
+	nano::amount min_weight{ min_weight_a };
+	return !rsnano::rsn_ledger_export_bootstrap_weights (handle, min_weight.bytes.data (), path_a.string ().c_str ());
+}
+
 uint64_t nano::ledger::get_bootstrap_weight_max_blocks () const
 {
 	return rsnano::rsn_ledger_bootstrap_weight_max_blocks (handle);
@@ -387,6 +599,39 @@ void nano::ledger::set_bootstrap_weight_max_blocks (uint64_t max_a)
 	rsnano::rsn_ledger_set_bootstrap_weight_max_blocks (handle, max_a);
 }
 
+void nano::ledger::set_max_rollback_depth (std::size_t max_depth_a)
+{
+	rsnano::rsn_ledger_set_max_rollback_depth (handle, max_depth_a);
+}
+
+void nano::ledger::set_reject_confirmed_forks (bool reject_a)
+{
+	rsnano::rsn_ledger_set_reject_confirmed_forks (handle, reject_a);
+}
+
+void nano::ledger::set_rep_weight_observer (representative_changed_callback representative_changed_a, rep_weight_threshold_crossed_callback threshold_crossed_a)
+{
+	auto context = new rep_weight_observer_context{ std::move (representative_changed_a), std::move (threshold_crossed_a) };
+	rsnano::rsn_ledger_set_rep_weight_observer (handle, context, representative_changed, rep_weight_threshold_crossed, delete_rep_weight_observer_context);
+}
+
+void nano::ledger::set_rep_weight_thresholds (std::vector<nano::uint128_t> const & thresholds_a)
+{
+	std::vector<uint8_t> bytes;
+	bytes.reserve (thresholds_a.size () * 16);
+	for (auto const & threshold : thresholds_a)
+	{
+		nano::amount amount{ threshold };
+		bytes.insert (bytes.end (), std::begin (amount.bytes), std::end (amount.bytes));
+	}
+	rsnano::rsn_ledger_set_rep_weight_thresholds (handle, bytes.data (), thresholds_a.size ());
+}
+
+std::size_t nano::ledger::recover_rollback_journal (nano::write_transaction const & transaction_a)
+{
+	return rsnano::rsn_ledger_recover_rollback_journal (handle, transaction_a.get_rust_handle ());
+}
+
 nano::uncemented_info::uncemented_info (nano::block_hash const & cemented_frontier, nano::block_hash const & frontier, nano::account const & account) :
 	cemented_frontier (cemented_frontier), frontier (frontier), account (account)
 {
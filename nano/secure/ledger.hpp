@@ -4,6 +4,9 @@
 #include <nano/lib/timer.hpp>
 #include <nano/secure/common.hpp>
 
+#include <boost/filesystem/path.hpp>
+
+#include <functional>
 #include <map>
 
 namespace nano
@@ -24,6 +27,72 @@ public:
 	nano::account account;
 };
 
+class account_details
+{
+public:
+	nano::confirmation_height_info confirmation_height;
+	nano::block_hash representative_block;
+	nano::amount confirmed_balance;
+	nano::account confirmed_representative;
+	nano::amount receivable;
+	nano::amount confirmed_receivable;
+};
+
+/** Which receivable amounts ledger::account_details sums up */
+enum class receivable_details : uint8_t
+{
+	skip,
+	total,
+	total_and_confirmed
+};
+
+class epoch_upgrade_progress
+{
+public:
+	uint64_t epoch_0{ 0 };
+	uint64_t epoch_1{ 0 };
+	uint64_t epoch_2{ 0 };
+	/** Unopened accounts with receivables below the latest epoch. Included in unupgraded */
+	uint64_t unopened_with_receivables{ 0 };
+	uint64_t unupgraded{ 0 };
+};
+
+class block_info
+{
+public:
+	nano::account account;
+	/** Empty if the previous block was pruned */
+	std::optional<nano::amount> amount;
+	nano::amount balance;
+	uint64_t height;
+	uint64_t local_timestamp;
+	nano::block_hash successor;
+	/** Empty for legacy blocks */
+	std::string subtype;
+	bool confirmed;
+};
+
+/** Subtype of a state block, derived from the balance of its previous block */
+enum class block_subtype : uint8_t
+{
+	send,
+	receive,
+	open,
+	change,
+	epoch
+};
+
+/** Result of checking a state block against the subtype a client claimed for it */
+enum class subtype_check_result : uint8_t
+{
+	ok,
+	invalid_subtype,
+	gap_previous,
+	balance_mismatch,
+	previous_mismatch,
+	epoch_link_mismatch
+};
+
 class ledger final
 {
 public:
@@ -53,7 +122,20 @@ public:
 	nano::uint128_t balance_safe (nano::transaction const &, nano::block_hash const &, bool &) const;
 	nano::uint128_t account_balance (nano::transaction const &, nano::account const &, bool = false);
 	nano::uint128_t account_receivable (nano::transaction const &, nano::account const &, bool = false);
+	/** Everything the account_info RPC shows about an account. Returns nullopt if the account is not opened */
+	std::optional<nano::account_details> account_details (nano::transaction const &, nano::account const &, nano::receivable_details);
+	nano::epoch_upgrade_progress epoch_upgrade_progress (nano::transaction const &);
+	std::optional<nano::block_info> block_info (nano::transaction const &, nano::block_hash const &);
+	/** Up to `max` entries of the account history as JSON lines, beginning at `head` or at the frontier if `head` is zero.
+	 * `next` is set to the first block of the next page or to zero if the end of the chain was reached */
+	std::string account_history (nano::transaction const &, nano::account const &, nano::block_hash const & head, std::size_t max, nano::block_hash & next);
+	/** Returns the hash of the confirmed block which received the given send block, or zero if there is none */
+	nano::block_hash receive_hash_for_send (nano::transaction const &, nano::block_hash const & send_hash);
 	nano::uint128_t weight (nano::account const &);
+	/** Weight of a representative computed from cemented blocks only. Not cached, walks all confirmed accounts */
+	nano::uint128_t weight_exact (nano::transaction const &, nano::account const &);
+	/** Order independent digest over all account heads and confirmation heights, computed from the store */
+	nano::uint256_union ledger_checksum (nano::transaction const &);
 	std::shared_ptr<nano::block> successor (nano::transaction const &, nano::qualified_root const &);
 	std::shared_ptr<nano::block> head_block (nano::transaction const &, nano::account const &);
 	bool block_confirmed (nano::transaction const &, nano::block_hash const &) const;
@@ -64,19 +146,32 @@ public:
 	bool block_or_pruned_exists (nano::transaction const &, nano::block_hash const &) const;
 	std::string block_text (char const *);
 	std::string block_text (nano::block_hash const &);
+	/** Fast sanity check of the store. Returns one line with a repair hint per problem, or an empty string if the ledger is consistent */
+	std::string check_integrity (nano::generate_cache const &) const;
+	std::string stats_report () const;
+	void serialize_stats_report (boost::property_tree::ptree &) const;
 	bool is_send (nano::transaction const &, nano::block const &) const;
 	nano::account block_destination (nano::transaction const &, nano::block const &);
 	nano::block_hash block_source (nano::transaction const &, nano::block const &);
 	std::pair<nano::block_hash, nano::block_hash> hash_root_random (nano::transaction const &) const;
 	std::optional<nano::pending_info> pending_info (nano::transaction const & transaction, nano::pending_key const & key) const;
 	nano::process_return process (nano::write_transaction const &, nano::block &);
+	/** Number of leading blocks of a chain which are valid, checked in memory without writing */
+	std::size_t valid_chain_prefix (nano::transaction const &, std::vector<std::shared_ptr<nano::block>> const &) const;
 	bool rollback (nano::write_transaction const &, nano::block_hash const &, std::vector<std::shared_ptr<nano::block>> &);
 	bool rollback (nano::write_transaction const &, nano::block_hash const &);
+	/** Rollback for more blocks than fit into one transaction. The transaction is committed before, during and after the rollback */
+	bool rollback_journaled (nano::write_transaction const &, nano::block_hash const &, std::vector<std::shared_ptr<nano::block>> &);
 	void update_account (nano::write_transaction const &, nano::account const &, nano::account_info const &, nano::account_info const &);
 	uint64_t pruning_action (nano::write_transaction &, nano::block_hash const &, uint64_t const);
 	bool could_fit (nano::transaction const &, nano::block const &) const;
 	bool dependents_confirmed (nano::transaction const &, nano::block const &) const;
+	/** Returns false if the root block or its successor was pruned, because votes for such roots cannot be verified */
+	bool pruning_safe_to_vote (nano::transaction const &, nano::root const &) const;
 	bool is_epoch_link (nano::link const &) const;
+	/** Empty if the block is not a state block or its previous block is unknown */
+	std::optional<nano::block_subtype> state_block_subtype (nano::transaction const &, nano::block const &) const;
+	nano::subtype_check_result check_subtype (nano::transaction const &, nano::block const &, std::string const & subtype) const;
 	std::array<nano::block_hash, 2> dependent_blocks (nano::transaction const &, nano::block const &) const;
 	std::shared_ptr<nano::block> find_receive_block_by_send_hash (nano::transaction const & transaction, nano::account const & destination, nano::block_hash const & send_block_hash);
 	nano::account epoch_signer (nano::link const &) const;
@@ -89,8 +184,22 @@ public:
 	bool pruning_enabled () const;
 	std::unordered_map<nano::account, nano::uint128_t> get_bootstrap_weights () const;
 	void set_bootstrap_weights (std::unordered_map<nano::account, nano::uint128_t> const & weights_a);
+	/** Loads bootstrap weights in the format of the rep_weights_*.bin files. Returns true on error */
+	bool load_bootstrap_weights (uint8_t const * data_a, std::size_t size_a);
+	/** Writes the representatives with at least \p min_weight_a in the format of the rep_weights_*.bin files. Returns true on error */
+	bool export_bootstrap_weights (nano::uint128_t const & min_weight_a, boost::filesystem::path const & path_a) const;
 	void set_bootstrap_weight_max_blocks (uint64_t max_a);
 	uint64_t get_bootstrap_weight_max_blocks () const;
+	/** Rollbacks which would remove more blocks than this fail without changing the ledger */
+	void set_max_rollback_depth (std::size_t max_depth_a);
+	void set_reject_confirmed_forks (bool reject_a);
+	using representative_changed_callback = std::function<void (nano::account const & account, nano::account const & old_representative, nano::account const & new_representative)>;
+	using rep_weight_threshold_crossed_callback = std::function<void (nano::account const & representative, nano::uint128_t const & threshold, nano::uint128_t const & weight, bool above)>;
+	/** Notifies about representative changes of accounts and about representatives whose weight crossed one of the thresholds */
+	void set_rep_weight_observer (representative_changed_callback, rep_weight_threshold_crossed_callback);
+	void set_rep_weight_thresholds (std::vector<nano::uint128_t> const & thresholds_a);
+	/** Completes the rollbacks which were interrupted by a crash. Returns the number of completed rollbacks */
+	std::size_t recover_rollback_journal (nano::write_transaction const &);
 	static nano::uint128_t const unit;
 	nano::store & store;
 	nano::ledger_cache cache;
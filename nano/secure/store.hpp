@@ -538,9 +538,16 @@ public:
 class peer_store
 {
 public:
-	virtual void put (nano::write_transaction const & transaction_a, nano::endpoint_key const & endpoint_a) = 0;
+	/** Stores the peer together with the time (seconds since epoch) it was last seen and the time of the last successful handshake */
+	virtual void put (nano::write_transaction const & transaction_a, nano::endpoint_key const & endpoint_a, uint64_t last_seen_a, uint64_t last_handshake_a) = 0;
 	virtual void del (nano::write_transaction const & transaction_a, nano::endpoint_key const & endpoint_a) = 0;
 	virtual bool exists (nano::transaction const & transaction_a, nano::endpoint_key const & endpoint_a) const = 0;
+	/** Returns true and sets the times if the peer is known */
+	virtual bool get (nano::transaction const & transaction_a, nano::endpoint_key const & endpoint_a, uint64_t & last_seen_a, uint64_t & last_handshake_a) const = 0;
+	/** Up to `max_a` peers, the ones with the most recent successful handshake first */
+	virtual std::vector<nano::endpoint> prioritized (nano::transaction const & transaction_a, std::size_t max_a) const = 0;
+	/** Deletes all peers which were last seen before `cutoff_a` and returns their count */
+	virtual std::size_t prune (nano::write_transaction const & transaction_a, uint64_t cutoff_a) = 0;
 	virtual size_t count (nano::transaction const & transaction_a) const = 0;
 	virtual void clear (nano::write_transaction const & transaction_a) = 0;
 	virtual nano::store_iterator<nano::endpoint_key, nano::no_value> begin (nano::transaction const & transaction_a) const = 0;
@@ -688,6 +695,8 @@ public:
 	virtual unsigned max_block_write_batch_num () const = 0;
 
 	virtual bool copy_db (boost::filesystem::path const & destination) = 0;
+	/** Replaces target with a verified, compacted copy of the database. An existing target is moved to backup and kept. Returns true on error */
+	virtual bool vacuum (boost::filesystem::path const & target, boost::filesystem::path const & backup, bool clear_peers) = 0;
 	virtual void rebuild_db (nano::write_transaction const & transaction_a) = 0;
 
 	/** Not applicable to all sub-classes */
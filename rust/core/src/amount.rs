@@ -1,7 +1,10 @@
 use crate::utils::{Deserialize, Serialize, Stream};
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use once_cell::sync::Lazy;
 
+/// Number of decimal places of 1 nano in raw
+const NANO_DECIMALS: usize = 30;
+
 #[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
 pub struct Amount {
     raw: u128, // native endian!
@@ -95,6 +98,77 @@ impl Amount {
         }
     }
 
+    /// Formats the amount in nano without thousands separators, for example "1.234".
+    /// The decimals are cut off after `decimals` places, so the result is never rounded up.
+    /// Trailing zeros are removed
+    pub fn to_nano_string(&self, decimals: usize) -> String {
+        let whole = self.raw / *MXRB_RATIO;
+        let fraction = format!("{:0width$}", self.raw % *MXRB_RATIO, width = NANO_DECIMALS);
+        let fraction = fraction[..decimals.min(NANO_DECIMALS)].trim_end_matches('0');
+        if fraction.is_empty() {
+            whole.to_string()
+        } else {
+            format!("{}.{}", whole, fraction)
+        }
+    }
+
+    /// Parses an amount in nano like "1.234" without going through floating point numbers.
+    /// Fails if the amount has more decimal places than can be represented in raw
+    pub fn from_nano_str(s: impl AsRef<str>) -> Result<Self> {
+        let s = s.as_ref();
+        let (whole, fraction) = match s.split_once('.') {
+            Some((whole, fraction)) if !fraction.is_empty() => (whole, fraction),
+            Some(_) => bail!("invalid nano amount: {}", s),
+            None => (s, ""),
+        };
+
+        let is_number = |digits: &str| digits.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_number(whole) || !is_number(fraction) {
+            bail!("invalid nano amount: {}", s);
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > NANO_DECIMALS {
+            bail!(
+                "nano amount has more than {} decimal places and cannot be converted to raw without precision loss: {}",
+                NANO_DECIMALS,
+                s
+            );
+        }
+
+        let fraction_raw = if fraction.is_empty() {
+            0
+        } else {
+            format!("{:0<width$}", fraction, width = NANO_DECIMALS).parse::<u128>()?
+        };
+
+        whole
+            .parse::<u128>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(*MXRB_RATIO))
+            .and_then(|raw| raw.checked_add(fraction_raw))
+            .map(Amount::raw)
+            .ok_or_else(|| anyhow!("nano amount is too large: {}", s))
+    }
+
+    /// Multiplies by an integer factor, for example a unit ratio like `MXRB_RATIO`.
+    /// Returns `None` on overflow
+    pub fn checked_mul(&self, factor: u128) -> Option<Amount> {
+        self.raw.checked_mul(factor).map(Amount::raw)
+    }
+
+    /// Returns `None` if `divisor` is zero
+    pub fn checked_div(&self, divisor: u128) -> Option<Amount> {
+        self.raw.checked_div(divisor).map(Amount::raw)
+    }
+
+    /// Splits the amount into whole units of `ratio` and the raw remainder, which
+    /// converts to another unit without losing anything.
+    /// Panics if `ratio` is zero
+    pub fn div_rem(&self, ratio: u128) -> (u128, Amount) {
+        (self.raw / ratio, Amount::raw(self.raw % ratio))
+    }
+
     pub fn wrapping_add(&self, other: Amount) -> Amount {
         self.raw.wrapping_add(other.raw).into()
     }
@@ -276,4 +350,91 @@ mod tests {
             Amount::raw(*MXRB_RATIO * 123456789 + *KXRB_RATIO * 123).format_balance(2)
         );
     }
+
+    #[test]
+    fn to_nano_string() {
+        assert_eq!(Amount::zero().to_nano_string(30), "0");
+        assert_eq!(Amount::nano(1234567).to_nano_string(30), "1234567");
+        assert_eq!(
+            Amount::raw(*MXRB_RATIO + *KXRB_RATIO * 234).to_nano_string(30),
+            "1.234"
+        );
+        assert_eq!(
+            Amount::raw(*MXRB_RATIO + *KXRB_RATIO * 239).to_nano_string(2),
+            "1.23"
+        );
+        assert_eq!(Amount::raw(*MXRB_RATIO + 1).to_nano_string(29), "1");
+        assert_eq!(
+            Amount::raw(1).to_nano_string(30),
+            "0.000000000000000000000000000001"
+        );
+        assert_eq!(
+            Amount::MAX.to_nano_string(100),
+            "340282366.920938463463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn from_nano_str() {
+        assert_eq!(Amount::from_nano_str("0").unwrap(), Amount::zero());
+        assert_eq!(Amount::from_nano_str("42").unwrap(), Amount::nano(42));
+        assert_eq!(
+            Amount::from_nano_str("1.234").unwrap(),
+            Amount::raw(*MXRB_RATIO + *KXRB_RATIO * 234)
+        );
+        assert_eq!(
+            Amount::from_nano_str("0.000000000000000000000000000001").unwrap(),
+            Amount::raw(1)
+        );
+        assert_eq!(
+            Amount::from_nano_str("1.5000000000000000000000000000000000").unwrap(),
+            Amount::raw(*MXRB_RATIO + *MXRB_RATIO / 2)
+        );
+        assert_eq!(
+            Amount::from_nano_str("340282366.920938463463374607431768211455").unwrap(),
+            Amount::MAX
+        );
+    }
+
+    #[test]
+    fn nano_string_roundtrip() {
+        let amount = Amount::raw(123456789012345678901234567890123);
+        assert_eq!(
+            Amount::from_nano_str(amount.to_nano_string(30)).unwrap(),
+            amount
+        );
+    }
+
+    #[test]
+    fn invalid_nano_str() {
+        for s in ["", ".", "1.", ".5", "-1", "+1", "1,5", " 1", "1.2.3", "abc"] {
+            assert!(Amount::from_nano_str(s).is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn nano_str_with_precision_loss() {
+        let error = Amount::from_nano_str("0.0000000000000000000000000000001").unwrap_err();
+        assert!(error.to_string().contains("precision loss"));
+    }
+
+    #[test]
+    fn nano_str_too_large() {
+        assert!(Amount::from_nano_str("340282366.920938463463374607431768211456").is_err());
+        assert!(Amount::from_nano_str("1000000000").is_err());
+        assert!(Amount::from_nano_str("1000000000000000000000000000000000000000000").is_err());
+    }
+
+    #[test]
+    fn unit_ratio_helpers() {
+        let amount = Amount::raw(*MXRB_RATIO * 3 + 7);
+        assert_eq!(amount.div_rem(*MXRB_RATIO), (3, Amount::raw(7)));
+        assert_eq!(
+            Amount::raw(3).checked_mul(*MXRB_RATIO),
+            Some(Amount::nano(3))
+        );
+        assert_eq!(Amount::MAX.checked_mul(2), None);
+        assert_eq!(Amount::nano(3).checked_div(3), Some(Amount::nano(1)));
+        assert_eq!(Amount::nano(3).checked_div(0), None);
+    }
 }
@@ -1,8 +1,10 @@
 use blake2::digest::Update;
 use blake2::digest::VariableOutput;
+use blake2::digest::VariableOutputReset;
 use blake2::Blake2bVar;
 use rand::thread_rng;
 use rand::Rng;
+use std::cell::RefCell;
 
 use crate::u256_struct;
 
@@ -28,14 +30,23 @@ impl From<Account> for BlockHash {
     }
 }
 
+thread_local! {
+    /// Blake2b states which were reset after use, so that they can be reused
+    static HASHER_POOL: RefCell<Vec<Blake2bVar>> = const { RefCell::new(Vec::new()) };
+}
+
+const MAX_POOLED_HASHERS: usize = 4;
+
 pub struct BlockHashBuilder {
     blake: Blake2bVar,
+    pooled: bool,
 }
 
 impl Default for BlockHashBuilder {
     fn default() -> Self {
         Self {
             blake: Blake2bVar::new(32).unwrap(),
+            pooled: false,
         }
     }
 }
@@ -45,14 +56,59 @@ impl BlockHashBuilder {
         Default::default()
     }
 
+    /// Takes a Blake2b state from a thread local pool instead of initializing a new one.
+    /// The state is returned to the pool by `build`
+    pub fn reuse() -> Self {
+        let blake = HASHER_POOL.with(|pool| pool.borrow_mut().pop());
+        Self {
+            blake: blake.unwrap_or_else(|| Blake2bVar::new(32).unwrap()),
+            pooled: true,
+        }
+    }
+
     pub fn update(mut self, data: impl AsRef<[u8]>) -> Self {
         self.blake.update(data.as_ref());
         self
     }
 
-    pub fn build(self) -> BlockHash {
+    pub fn build(mut self) -> BlockHash {
         let mut hash_bytes = [0u8; 32];
-        self.blake.finalize_variable(&mut hash_bytes).unwrap();
+        if self.pooled {
+            self.blake.finalize_variable_reset(&mut hash_bytes).unwrap();
+            HASHER_POOL.with(|pool| {
+                let mut pool = pool.borrow_mut();
+                if pool.len() < MAX_POOLED_HASHERS {
+                    pool.push(self.blake);
+                }
+            });
+        } else {
+            self.blake.finalize_variable(&mut hash_bytes).unwrap();
+        }
         BlockHash::from_bytes(hash_bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reused_hasher_yields_same_hash() {
+        let expected = BlockHashBuilder::new().update([1, 2, 3]).build();
+        for _ in 0..MAX_POOLED_HASHERS + 2 {
+            assert_eq!(
+                BlockHashBuilder::reuse().update([1, 2, 3]).build(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn reused_hasher_is_reset() {
+        BlockHashBuilder::reuse().update([4, 5, 6]).build();
+        assert_eq!(
+            BlockHashBuilder::reuse().update([1]).build(),
+            BlockHashBuilder::new().update([1]).build()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::Amount;
+
+/// Signed change of an account balance caused by a block
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BalanceDelta {
+    Unchanged,
+    Increase(Amount),
+    Decrease(Amount),
+}
+
+impl BalanceDelta {
+    pub fn new(previous_balance: Amount, balance: Amount) -> Self {
+        if balance > previous_balance {
+            BalanceDelta::Increase(balance - previous_balance)
+        } else if balance < previous_balance {
+            BalanceDelta::Decrease(previous_balance - balance)
+        } else {
+            BalanceDelta::Unchanged
+        }
+    }
+
+    /// The absolute amount by which the balance changed
+    pub fn amount(&self) -> Amount {
+        match self {
+            BalanceDelta::Unchanged => Amount::zero(),
+            BalanceDelta::Increase(amount) | BalanceDelta::Decrease(amount) => *amount,
+        }
+    }
+
+    /// -1 for a decrease, 0 if unchanged and 1 for an increase
+    pub fn signum(&self) -> i8 {
+        match self {
+            BalanceDelta::Unchanged => 0,
+            BalanceDelta::Increase(_) => 1,
+            BalanceDelta::Decrease(_) => -1,
+        }
+    }
+
+    pub fn is_increase(&self) -> bool {
+        matches!(self, BalanceDelta::Increase(_))
+    }
+
+    pub fn is_decrease(&self) -> bool {
+        matches!(self, BalanceDelta::Decrease(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increase() {
+        let delta = BalanceDelta::new(Amount::raw(3), Amount::raw(10));
+        assert_eq!(delta, BalanceDelta::Increase(Amount::raw(7)));
+        assert_eq!(delta.amount(), Amount::raw(7));
+        assert_eq!(delta.signum(), 1);
+    }
+
+    #[test]
+    fn decrease() {
+        let delta = BalanceDelta::new(Amount::raw(10), Amount::raw(3));
+        assert_eq!(delta, BalanceDelta::Decrease(Amount::raw(7)));
+        assert_eq!(delta.amount(), Amount::raw(7));
+        assert_eq!(delta.signum(), -1);
+    }
+
+    #[test]
+    fn unchanged() {
+        let delta = BalanceDelta::new(Amount::raw(10), Amount::raw(10));
+        assert_eq!(delta, BalanceDelta::Unchanged);
+        assert_eq!(delta.amount(), Amount::zero());
+        assert_eq!(delta.signum(), 0);
+    }
+
+    #[test]
+    fn full_range() {
+        let delta = BalanceDelta::new(Amount::MAX, Amount::zero());
+        assert_eq!(delta.amount(), Amount::MAX);
+        assert!(delta.is_decrease());
+    }
+}
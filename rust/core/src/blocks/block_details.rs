@@ -1,13 +1,29 @@
-use crate::{utils::Stream, Epoch};
+use crate::{utils::Stream, BlockSubType, Epoch};
 use anyhow::Result;
 use num::FromPrimitive;
 
+const SEND_FLAG: u8 = 0b1000_0000;
+const RECEIVE_FLAG: u8 = 0b0100_0000;
+const EPOCH_FLAG: u8 = 0b0010_0000;
+/// Set if an extension byte with additional flags follows. Epoch values never reach
+/// this bit, so details which were serialized before the extension existed stay valid.
+const EXTENDED_FLAG: u8 = 0b0001_0000;
+const EPOCH_MASK: u8 = 0b0000_1111;
+
+const PRUNED_SOURCE_FLAG: u8 = 0b0000_0001;
+const SELF_SEND_FLAG: u8 = 0b0000_0010;
+const KNOWN_EXTENDED_FLAGS: u8 = PRUNED_SOURCE_FLAG | SELF_SEND_FLAG;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BlockDetails {
     pub epoch: Epoch,
     pub is_send: bool,
     pub is_receive: bool,
     pub is_epoch: bool,
+    /// The source block of this receive was pruned when the block was processed
+    pub is_pruned_source: bool,
+    /// The block sends to its own account
+    pub is_self_send: bool,
 }
 
 impl BlockDetails {
@@ -17,50 +33,103 @@ impl BlockDetails {
             is_send,
             is_receive,
             is_epoch,
+            is_pruned_source: false,
+            is_self_send: false,
+        }
+    }
+
+    /// The subtype of a state block with these details
+    pub fn subtype(&self) -> BlockSubType {
+        if self.is_epoch {
+            BlockSubType::Epoch
+        } else if self.is_send {
+            BlockSubType::Send
+        } else if self.is_receive {
+            BlockSubType::Receive
+        } else {
+            BlockSubType::Change
         }
     }
 
+    /// Size of the details without the optional extension byte
     pub const fn serialized_size() -> usize {
         1
     }
 
     pub fn serialize(&self, stream: &mut impl Stream) -> Result<()> {
-        stream.write_u8(self.packed())
+        stream.write_u8(self.packed())?;
+        if self.has_extended_flags() {
+            stream.write_u8(self.packed_extended())?;
+        }
+        Ok(())
     }
 
     pub fn deserialize(stream: &mut dyn Stream) -> Result<BlockDetails> {
-        BlockDetails::unpack(stream.read_u8()?)
+        let value = stream.read_u8()?;
+        let mut details = BlockDetails::unpack(value)?;
+        if value & EXTENDED_FLAG != 0 {
+            details.unpack_extended(stream.read_u8()?)?;
+        }
+        Ok(details)
+    }
+
+    pub fn has_extended_flags(&self) -> bool {
+        self.packed_extended() != 0
     }
 
     pub fn packed(&self) -> u8 {
         let mut result = self.epoch as u8;
         if self.is_send {
-            result |= 0b1000_0000;
+            result |= SEND_FLAG;
         }
         if self.is_receive {
-            result |= 0b0100_0000;
+            result |= RECEIVE_FLAG;
         }
         if self.is_epoch {
-            result |= 0b0010_0000;
+            result |= EPOCH_FLAG;
+        }
+        if self.has_extended_flags() {
+            result |= EXTENDED_FLAG;
         }
 
         result
     }
 
+    fn packed_extended(&self) -> u8 {
+        let mut result = 0;
+        if self.is_pruned_source {
+            result |= PRUNED_SOURCE_FLAG;
+        }
+        if self.is_self_send {
+            result |= SELF_SEND_FLAG;
+        }
+        result
+    }
+
+    /// Unpacks the first byte. The extended flags are left unset even if the byte
+    /// announces an extension byte.
     pub fn unpack(value: u8) -> Result<Self> {
-        let epoch_mask = 0b0001_1111u8;
-        let epoch_value = value & epoch_mask;
+        let epoch_value = value & EPOCH_MASK;
         let epoch = match FromPrimitive::from_u8(epoch_value) {
             Some(e) => e,
             None => bail!("unknown epoch value: {}", epoch_value),
         };
 
-        Ok(BlockDetails {
+        Ok(BlockDetails::new(
             epoch,
-            is_send: (0b1000_0000 & value) != 0,
-            is_receive: (0b0100_0000 & value) != 0,
-            is_epoch: (0b0010_0000 & value) != 0,
-        })
+            (SEND_FLAG & value) != 0,
+            (RECEIVE_FLAG & value) != 0,
+            (EPOCH_FLAG & value) != 0,
+        ))
+    }
+
+    fn unpack_extended(&mut self, value: u8) -> Result<()> {
+        if value & !KNOWN_EXTENDED_FLAGS != 0 {
+            bail!("unknown block details flags: {:#010b}", value);
+        }
+        self.is_pruned_source = (PRUNED_SOURCE_FLAG & value) != 0;
+        self.is_self_send = (SELF_SEND_FLAG & value) != 0;
+        Ok(())
     }
 }
 
@@ -133,7 +202,54 @@ mod test {
         let details = BlockDetails::new(Epoch::Epoch2, false, true, false);
         let mut stream = MemoryStream::new();
         details.serialize(&mut stream).unwrap();
+        assert_eq!(stream.bytes_written(), BlockDetails::serialized_size());
         let deserialized = BlockDetails::deserialize(&mut stream).unwrap();
         assert_eq!(deserialized, details);
     }
+
+    #[test]
+    fn serialize_extended_flags() {
+        let mut details = BlockDetails::new(Epoch::Epoch2, true, false, false);
+        details.is_self_send = true;
+        let mut stream = MemoryStream::new();
+        details.serialize(&mut stream).unwrap();
+        assert_eq!(stream.as_bytes(), [0b1001_0100, 0b0000_0010]);
+        let deserialized = BlockDetails::deserialize(&mut stream).unwrap();
+        assert_eq!(deserialized, details);
+
+        let mut details = BlockDetails::new(Epoch::Epoch1, false, true, false);
+        details.is_pruned_source = true;
+        let mut stream = MemoryStream::new();
+        details.serialize(&mut stream).unwrap();
+        assert_eq!(stream.as_bytes(), [0b0101_0011, 0b0000_0001]);
+        assert_eq!(BlockDetails::deserialize(&mut stream).unwrap(), details);
+    }
+
+    #[test]
+    fn legacy_bytes_have_no_extension() {
+        for epoch in [
+            Epoch::Unspecified,
+            Epoch::Epoch0,
+            Epoch::Epoch1,
+            Epoch::Epoch2,
+        ] {
+            for value in [0b1000_0000, 0b0100_0000, 0b0010_0000, 0] {
+                let legacy = value | epoch as u8;
+                let mut stream = MemoryStream::new();
+                stream.write_u8(legacy).unwrap();
+                let details = BlockDetails::deserialize(&mut stream).unwrap();
+                assert!(!details.has_extended_flags());
+                assert!(stream.at_end());
+                assert_eq!(details.packed(), legacy);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_extended_flags_are_rejected() {
+        let mut stream = MemoryStream::new();
+        stream.write_u8(0b0001_0010).unwrap();
+        stream.write_u8(0b1000_0000).unwrap();
+        assert!(BlockDetails::deserialize(&mut stream).is_err());
+    }
 }
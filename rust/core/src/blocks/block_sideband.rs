@@ -37,6 +37,7 @@ impl BlockSideband {
         }
     }
 
+    /// Size of the sideband if the block details have no extended flags
     pub fn serialized_size(block_type: BlockType) -> usize {
         let mut size = BlockHash::serialized_size(); // successor
 
@@ -153,12 +154,7 @@ impl BlockSideband {
             successor: BlockHash::from(3),
             account: Account::from(1),
             balance: Amount::raw(42),
-            details: BlockDetails {
-                epoch: Epoch::Epoch2,
-                is_send: true,
-                is_receive: false,
-                is_epoch: false,
-            },
+            details: BlockDetails::new(Epoch::Epoch2, true, false, false),
             source_epoch: Epoch::Epoch2,
         }
     }
@@ -190,4 +186,51 @@ mod tests {
             BlockSideband::from_stream(&mut stream, BlockType::LegacyReceive).unwrap();
         assert_eq!(deserialized, sideband);
     }
+
+    #[test]
+    fn serialize_all_block_types() {
+        for block_type in [
+            BlockType::LegacySend,
+            BlockType::LegacyReceive,
+            BlockType::LegacyOpen,
+            BlockType::LegacyChange,
+            BlockType::State,
+        ] {
+            let mut sideband = BlockSideband::create_test_instance();
+            if block_type == BlockType::LegacyOpen {
+                sideband.height = 1;
+            }
+            let mut stream = MemoryStream::new();
+            sideband.serialize(&mut stream, block_type).unwrap();
+            assert_eq!(
+                stream.bytes_written(),
+                BlockSideband::serialized_size(block_type)
+            );
+            let deserialized = BlockSideband::from_stream(&mut stream, block_type).unwrap();
+            assert!(stream.at_end());
+            if block_type == BlockType::State {
+                assert_eq!(deserialized.details, sideband.details);
+            }
+            assert_eq!(deserialized.successor, sideband.successor);
+            assert_eq!(deserialized.height, sideband.height);
+            assert_eq!(deserialized.timestamp, sideband.timestamp);
+        }
+    }
+
+    #[test]
+    fn serialize_extended_details_of_state_block() {
+        let mut sideband = BlockSideband::create_test_instance();
+        sideband.details.is_self_send = true;
+        sideband.details.is_pruned_source = true;
+        let mut stream = MemoryStream::new();
+        sideband.serialize(&mut stream, BlockType::State).unwrap();
+        assert_eq!(
+            stream.bytes_written(),
+            BlockSideband::serialized_size(BlockType::State) + 1
+        );
+        let deserialized = BlockSideband::from_stream(&mut stream, BlockType::State).unwrap();
+        assert!(stream.at_end());
+        assert_eq!(deserialized.details, sideband.details);
+        assert_eq!(deserialized.source_epoch, sideband.source_epoch);
+    }
 }
@@ -71,12 +71,7 @@ impl LegacyChangeBlockBuilder {
         );
 
         if self.build_sideband {
-            let details = BlockDetails {
-                epoch: Epoch::Epoch0,
-                is_send: false,
-                is_receive: true,
-                is_epoch: false,
-            };
+            let details = BlockDetails::new(Epoch::Epoch0, false, true, false);
             block.set_sideband(BlockSideband::new(
                 block.account(),
                 BlockHash::zero(),
@@ -80,12 +80,7 @@ impl LegacyOpenBlockBuilder {
             work,
         );
 
-        let details = BlockDetails {
-            epoch: Epoch::Epoch0,
-            is_send: false,
-            is_receive: true,
-            is_epoch: false,
-        };
+        let details = BlockDetails::new(Epoch::Epoch0, false, true, false);
 
         if self.build_sideband || self.height.is_some() {
             let height = self.height.unwrap_or(1);
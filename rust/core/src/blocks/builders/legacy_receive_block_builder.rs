@@ -63,12 +63,7 @@ impl LegacyReceiveBlockBuilder {
             work,
         );
 
-        let details = BlockDetails {
-            epoch: Epoch::Epoch0,
-            is_send: false,
-            is_receive: true,
-            is_epoch: false,
-        };
+        let details = BlockDetails::new(Epoch::Epoch0, false, true, false);
 
         if self.build_sideband {
             block.set_sideband(BlockSideband::new(
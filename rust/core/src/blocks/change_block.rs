@@ -22,7 +22,7 @@ impl ChangeHashables {
 
 impl From<&ChangeHashables> for BlockHash {
     fn from(hashables: &ChangeHashables) -> Self {
-        BlockHashBuilder::new()
+        BlockHashBuilder::reuse()
             .update(hashables.previous.as_bytes())
             .update(hashables.representative.as_bytes())
             .build()
@@ -1,3 +1,6 @@
+mod balance_delta;
+pub use balance_delta::BalanceDelta;
+
 mod block_details;
 pub use block_details::BlockDetails;
 
@@ -8,7 +11,7 @@ mod change_block;
 pub use change_block::{valid_change_block_predecessor, ChangeBlock, ChangeHashables};
 
 mod open_block;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 pub use open_block::{OpenBlock, OpenHashables};
 
 mod receive_block;
@@ -28,13 +31,13 @@ use crate::{
         Deserialize, MemoryStream, PropertyTreeReader, PropertyTreeWriter, SerdePropertyTree,
         Stream, StreamAdapter,
     },
-    Account, Amount, BlockHash, BlockHashBuilder, Epoch, FullHash, KeyPair, Link, QualifiedRoot,
-    Root, Signature, WorkVersion,
+    validate_message, Account, Amount, BlockHash, BlockHashBuilder, Epoch, FullHash, KeyPair, Link,
+    QualifiedRoot, Root, Signature, WorkVersion,
 };
 use num::FromPrimitive;
 use std::{
     ops::{Deref, DerefMut},
-    sync::{Arc, RwLock},
+    str::FromStr,
 };
 
 #[repr(u8)]
@@ -66,35 +69,54 @@ pub enum BlockSubType {
     Epoch,
 }
 
+impl BlockSubType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockSubType::Send => "send",
+            BlockSubType::Receive => "receive",
+            BlockSubType::Open => "open",
+            BlockSubType::Change => "change",
+            BlockSubType::Epoch => "epoch",
+        }
+    }
+}
+
+impl FromStr for BlockSubType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "send" => Ok(BlockSubType::Send),
+            "receive" => Ok(BlockSubType::Receive),
+            "open" => Ok(BlockSubType::Open),
+            "change" => Ok(BlockSubType::Change),
+            "epoch" => Ok(BlockSubType::Epoch),
+            _ => Err(anyhow!("invalid block subtype: {}", s)),
+        }
+    }
+}
+
+/// The hash of a block, which is calculated when it is needed for the first time.
+/// A cloned block gets its own copy of the cached hash.
 #[derive(Clone, Default, Debug)]
 pub struct LazyBlockHash {
-    // todo: Remove Arc<RwLock>? Maybe remove lazy hash calculation?
-    hash: Arc<RwLock<BlockHash>>,
+    hash: OnceCell<BlockHash>,
 }
 
 impl LazyBlockHash {
     pub fn new() -> Self {
         Self {
-            hash: Arc::new(RwLock::new(BlockHash::zero())),
+            hash: OnceCell::new(),
         }
     }
-    pub fn hash(&'_ self, factory: impl Into<BlockHash>) -> BlockHash {
-        let mut value = self.hash.read().unwrap();
-        if value.is_zero() {
-            drop(value);
-            let mut x = self.hash.write().unwrap();
-            let block_hash: BlockHash = factory.into();
-            *x = block_hash;
-            drop(x);
-            value = self.hash.read().unwrap();
-        }
 
-        *value
+    pub fn hash(&self, factory: impl Into<BlockHash>) -> BlockHash {
+        *self.hash.get_or_init(|| factory.into())
     }
 
-    pub fn clear(&self) {
-        let mut x = self.hash.write().unwrap();
-        *x = BlockHash::zero();
+    /// Has to be called whenever one of the hashables changes
+    pub fn clear(&mut self) {
+        self.hash.take();
     }
 }
 
@@ -137,6 +159,11 @@ pub trait Block: FullHash {
         QualifiedRoot::new(self.root(), self.previous())
     }
     fn valid_predecessor(&self, block_type: BlockType) -> bool;
+
+    /// Checks the signature against `signer`, which is the account for all blocks except epoch blocks
+    fn verify_signature(&self, signer: &Account) -> bool {
+        validate_message(signer, self.hash().as_bytes(), self.block_signature()).is_ok()
+    }
 }
 
 impl<T: Block> FullHash for T {
@@ -220,6 +247,13 @@ impl BlockEnum {
         }
     }
 
+    /// The change of the account balance caused by this block. `previous` is `None` for
+    /// the first block of an account. Legacy blocks need a sideband for this calculation.
+    pub fn balance_delta(&self, previous: Option<&BlockEnum>) -> BalanceDelta {
+        let previous_balance = previous.map(|b| b.balance_calculated()).unwrap_or_default();
+        BalanceDelta::new(previous_balance, self.balance_calculated())
+    }
+
     pub fn is_open(&self) -> bool {
         match &self {
             BlockEnum::LegacyOpen(_) => true,
@@ -228,6 +262,12 @@ impl BlockEnum {
         }
     }
 
+    /// The root which the proof of work of this block is generated for.
+    /// This is the account for the first block of an account and the previous block otherwise.
+    pub fn root_for_work(&self) -> Root {
+        self.root()
+    }
+
     pub fn is_legacy(&self) -> bool {
         !matches!(self, BlockEnum::State(_))
     }
@@ -280,13 +320,18 @@ impl BlockEnum {
 
     pub fn serialize_with_sideband(&self) -> Vec<u8> {
         let mut stream = MemoryStream::new();
+        self.serialize_with_sideband_into(&mut stream);
+        stream.to_vec()
+    }
+
+    /// Appends the block and its sideband to the stream
+    pub fn serialize_with_sideband_into(&self, stream: &mut MemoryStream) {
         stream.write_u8(self.block_type() as u8).unwrap();
-        self.serialize(&mut stream).unwrap();
+        self.serialize(stream).unwrap();
         self.sideband()
             .unwrap()
-            .serialize(&mut stream, self.block_type())
+            .serialize(stream, self.block_type())
             .unwrap();
-        stream.to_vec()
     }
 
     pub fn deserialize_with_sideband(bytes: &[u8]) -> anyhow::Result<BlockEnum> {
@@ -422,6 +467,60 @@ pub static DEV_GENESIS_KEY: Lazy<KeyPair> =
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_block_subtype() {
+        for subtype in [
+            BlockSubType::Send,
+            BlockSubType::Receive,
+            BlockSubType::Open,
+            BlockSubType::Change,
+            BlockSubType::Epoch,
+        ] {
+            assert_eq!(subtype.as_str().parse::<BlockSubType>().unwrap(), subtype);
+        }
+        assert!("foo".parse::<BlockSubType>().is_err());
+    }
+
+    #[test]
+    fn root_for_work() {
+        let account = Account::from(1);
+        let previous = BlockHash::from(2);
+        let open = BlockBuilder::state()
+            .account(account)
+            .previous(BlockHash::zero())
+            .build();
+        let send = BlockBuilder::state()
+            .account(account)
+            .previous(previous)
+            .build();
+        assert_eq!(open.root_for_work(), Root::from(&account));
+        assert_eq!(send.root_for_work(), Root::from(&previous));
+        assert_eq!(
+            send.qualified_root(),
+            QualifiedRoot::for_block(&account, &previous)
+        );
+    }
+
+    #[test]
+    fn balance_delta() {
+        let open = BlockBuilder::state()
+            .previous(BlockHash::zero())
+            .balance(10)
+            .build();
+        let send = BlockBuilder::state().balance(5).build();
+        // The sideband of the change block contains a balance of 5
+        let change = BlockBuilder::legacy_change().with_sideband().build();
+        assert_eq!(
+            open.balance_delta(None),
+            BalanceDelta::Increase(Amount::raw(10))
+        );
+        assert_eq!(
+            send.balance_delta(Some(&open)),
+            BalanceDelta::Decrease(Amount::raw(5))
+        );
+        assert_eq!(change.balance_delta(Some(&send)), BalanceDelta::Unchanged);
+    }
+
     #[test]
     fn serialize_legacy_open() {
         let block = BlockBuilder::legacy_open().with_sideband().build();
@@ -458,4 +557,23 @@ mod tests {
 
         assert_eq!(deserialized, block);
     }
+
+    #[test]
+    fn lazy_hash_is_calculated_once() {
+        let lazy = LazyBlockHash::new();
+        assert_eq!(lazy.hash(BlockHash::from(1)), BlockHash::from(1));
+        assert_eq!(lazy.hash(BlockHash::from(2)), BlockHash::from(1));
+    }
+
+    #[test]
+    fn clear_lazy_hash() {
+        let mut lazy = LazyBlockHash::new();
+        lazy.hash(BlockHash::from(1));
+        let cloned = lazy.clone();
+
+        lazy.clear();
+
+        assert_eq!(lazy.hash(BlockHash::from(2)), BlockHash::from(2));
+        assert_eq!(cloned.hash(BlockHash::from(3)), BlockHash::from(1));
+    }
 }
@@ -24,7 +24,7 @@ impl OpenHashables {
 
 impl From<&OpenHashables> for BlockHash {
     fn from(hashables: &OpenHashables) -> Self {
-        BlockHashBuilder::new()
+        BlockHashBuilder::reuse()
             .update(hashables.source.as_bytes())
             .update(hashables.representative.as_bytes())
             .update(hashables.account.as_bytes())
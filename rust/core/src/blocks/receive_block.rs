@@ -22,7 +22,7 @@ impl ReceiveHashables {
 
 impl From<&ReceiveHashables> for BlockHash {
     fn from(hashables: &ReceiveHashables) -> Self {
-        BlockHashBuilder::new()
+        BlockHashBuilder::reuse()
             .update(hashables.previous.as_bytes())
             .update(hashables.source.as_bytes())
             .build()
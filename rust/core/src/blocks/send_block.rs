@@ -56,7 +56,7 @@ impl SendHashables {
 
 impl From<&SendHashables> for BlockHash {
     fn from(hashables: &SendHashables) -> Self {
-        BlockHashBuilder::new()
+        BlockHashBuilder::reuse()
             .update(hashables.previous.as_bytes())
             .update(hashables.destination.as_bytes())
             .update(hashables.balance.to_be_bytes())
@@ -1,8 +1,8 @@
 use crate::{
     sign_message, to_hex_string, u64_from_hex_str,
     utils::{Deserialize, PropertyTreeReader, PropertyTreeWriter, Serialize, Stream},
-    Account, Amount, BlockHash, BlockHashBuilder, LazyBlockHash, Link, PublicKey, RawKey, Root,
-    Signature,
+    Account, Amount, BlockHash, BlockHashBuilder, KeyPair, LazyBlockHash, Link, PublicKey, RawKey,
+    Root, Signature,
 };
 use anyhow::Result;
 
@@ -30,17 +30,26 @@ pub struct StateHashables {
     pub link: Link,
 }
 
-impl From<&StateHashables> for BlockHash {
-    fn from(hashables: &StateHashables) -> Self {
+impl StateHashables {
+    /// The bytes which are hashed with Blake2b to get the block hash
+    pub fn hash_preimage(&self) -> Vec<u8> {
         let mut preamble = [0u8; 32];
         preamble[31] = BlockType::State as u8;
-        BlockHashBuilder::new()
-            .update(preamble)
-            .update(hashables.account.as_bytes())
-            .update(hashables.previous.as_bytes())
-            .update(hashables.representative.as_bytes())
-            .update(hashables.balance.to_be_bytes())
-            .update(hashables.link.as_bytes())
+        let mut preimage = Vec::with_capacity(176);
+        preimage.extend_from_slice(&preamble);
+        preimage.extend_from_slice(self.account.as_bytes());
+        preimage.extend_from_slice(self.previous.as_bytes());
+        preimage.extend_from_slice(self.representative.as_bytes());
+        preimage.extend_from_slice(&self.balance.to_be_bytes());
+        preimage.extend_from_slice(self.link.as_bytes());
+        preimage
+    }
+}
+
+impl From<&StateHashables> for BlockHash {
+    fn from(hashables: &StateHashables) -> Self {
+        BlockHashBuilder::reuse()
+            .update(hashables.hash_preimage())
             .build()
     }
 }
@@ -110,6 +119,22 @@ impl StateBlock {
         }
     }
 
+    /// Returns a copy of this block which is signed with `key`
+    pub fn sign_with(&self, key: &KeyPair) -> Self {
+        let mut signed = self.clone();
+        signed.signature = sign_message(
+            &key.private_key(),
+            &key.public_key(),
+            self.hash().as_bytes(),
+        );
+        signed
+    }
+
+    /// The bytes which are hashed with Blake2b to get the block hash
+    pub fn hash_preimage(&self) -> Vec<u8> {
+        self.hashables.hash_preimage()
+    }
+
     pub fn source(&self) -> BlockHash {
         BlockHash::zero()
     }
@@ -359,4 +384,34 @@ mod tests {
         assert_different_hash(BlockBuilder::state().balance(Amount::from(1000)));
         assert_different_hash(BlockBuilder::state().link(Link::from(1000)));
     }
+
+    #[test]
+    fn sign_with_key() {
+        let key = KeyPair::new();
+        let BlockEnum::State(block) = BlockBuilder::state().account(key.public_key()).build()
+        else {
+            panic!("not a state block");
+        };
+
+        let signed = block.sign_with(&key);
+
+        assert_eq!(signed.hash(), block.hash());
+        assert!(signed.verify_signature(&key.public_key()));
+        assert!(!signed.verify_signature(&Account::from(42)));
+        assert!(!block.verify_signature(&key.public_key()));
+    }
+
+    #[test]
+    fn hash_preimage() {
+        let block = BlockBuilder::state().build();
+        let BlockEnum::State(state) = &block else {
+            panic!("not a state block");
+        };
+        let preimage = state.hash_preimage();
+        assert_eq!(preimage.len(), 176);
+        assert_eq!(
+            BlockHashBuilder::new().update(&preimage).build(),
+            block.hash()
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+
 use crate::utils::{Deserialize, MutStreamAdapter, Serialize, Stream};
 
 #[derive(Default, PartialEq, Eq, Debug, Clone)]
@@ -22,6 +24,10 @@ impl EndpointKey {
         buffer
     }
 
+    pub fn to_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V6(Ipv6Addr::from(self.address)), self.port)
+    }
+
     pub fn create_test_instance() -> Self {
         EndpointKey::new([1; 16], 123)
     }
@@ -1,4 +1,5 @@
-use crate::BlockEnum;
+use crate::{derive_nano_key, mnemonic_to_seed, BlockEnum};
+use curve25519_dalek_ng::edwards::CompressedEdwardsY;
 
 use super::{PublicKey, RawKey, Signature};
 
@@ -44,6 +45,22 @@ impl KeyPair {
         Self::from_priv_key_bytes(&bytes)
     }
 
+    /// Recovers the key of the account at `index` from a BIP39 mnemonic phrase without passphrase,
+    /// the way hardware wallets derive Nano accounts
+    pub fn from_mnemonic(phrase: &str, index: u32) -> anyhow::Result<Self> {
+        Self::from_mnemonic_with_passphrase(phrase, "", index)
+    }
+
+    pub fn from_mnemonic_with_passphrase(
+        phrase: &str,
+        passphrase: &str,
+        index: u32,
+    ) -> anyhow::Result<Self> {
+        let seed = mnemonic_to_seed(phrase, passphrase)?;
+        let private_key = derive_nano_key(&seed, index)?;
+        Self::from_priv_key_bytes(private_key.as_bytes())
+    }
+
     pub fn public_key(&self) -> PublicKey {
         PublicKey::from_bytes(self.keypair.public.to_bytes())
     }
@@ -84,14 +101,87 @@ pub fn validate_message_batch(
     signatures: &[Signature],
     valid: &mut [i32],
 ) {
+    assert!(valid.len() == messages.len());
+    for (result, is_valid) in
+        valid
+            .iter_mut()
+            .zip(validate_batch(messages, public_keys, signatures))
+    {
+        *result = is_valid as i32;
+    }
+}
+
+/// Verifies many signatures at once with the ed25519 batch equation and falls back to single
+/// verification only if the batch fails. The batch equation doesn't see small order components,
+/// so signatures with a small order A or R are rejected up front, like `validate_message` does,
+/// and points with a torsion component are verified on their own. This way a signature is
+/// valid in a batch if and only if it is valid on its own.
+pub fn validate_batch(
+    messages: &[impl AsRef<[u8]>],
+    public_keys: &[PublicKey],
+    signatures: &[Signature],
+) -> Vec<bool> {
     let len = messages.len();
-    assert!(public_keys.len() == len && signatures.len() == len && valid.len() == len);
+    assert!(public_keys.len() == len && signatures.len() == len);
+    let mut valid = vec![false; len];
+    let mut batch = Vec::with_capacity(len);
     for i in 0..len {
-        valid[i] = match validate_message(&public_keys[i], &messages[i], &signatures[i]) {
-            Ok(_) => 1,
-            Err(_) => 0,
+        let Ok(key) = ed25519_dalek_blake2b::PublicKey::from_bytes(public_keys[i].as_bytes())
+        else {
+            continue;
+        };
+        let Ok(sig) = ed25519_dalek_blake2b::Signature::from_bytes(signatures[i].as_bytes()) else {
+            continue;
+        };
+        match batch_candidate(&public_keys[i], &signatures[i]) {
+            Candidate::Rejected => {}
+            Candidate::Single => valid[i] = key.verify_strict(messages[i].as_ref(), &sig).is_ok(),
+            Candidate::Batch => batch.push((i, key, sig)),
         }
     }
+
+    if batch.is_empty() {
+        return valid;
+    }
+
+    let batch_messages: Vec<&[u8]> = batch
+        .iter()
+        .map(|(i, _, _)| messages[*i].as_ref())
+        .collect();
+    let batch_signatures: Vec<_> = batch.iter().map(|(_, _, sig)| *sig).collect();
+    let batch_keys: Vec<_> = batch.iter().map(|(_, key, _)| *key).collect();
+    if ed25519_dalek_blake2b::verify_batch(&batch_messages, &batch_signatures, &batch_keys).is_ok()
+    {
+        for (i, _, _) in &batch {
+            valid[*i] = true;
+        }
+    } else {
+        for ((i, key, sig), message) in batch.iter().zip(batch_messages) {
+            valid[*i] = key.verify_strict(message, sig).is_ok();
+        }
+    }
+    valid
+}
+
+enum Candidate {
+    Rejected,
+    Single,
+    Batch,
+}
+
+fn batch_candidate(public_key: &PublicKey, signature: &Signature) -> Candidate {
+    let mut r_bytes = [0u8; 32];
+    r_bytes.copy_from_slice(&signature.as_bytes()[..32]);
+    let points = (
+        CompressedEdwardsY(*public_key.as_bytes()).decompress(),
+        CompressedEdwardsY(r_bytes).decompress(),
+    );
+    match points {
+        (Some(a), Some(r)) if a.is_small_order() || r.is_small_order() => Candidate::Rejected,
+        (Some(a), Some(r)) if a.is_torsion_free() && r.is_torsion_free() => Candidate::Batch,
+        (Some(_), Some(_)) => Candidate::Single,
+        _ => Candidate::Rejected,
+    }
 }
 
 pub fn validate_block_signature(block: &BlockEnum) -> anyhow::Result<()> {
@@ -132,6 +222,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn validate_valid_batch() {
+        let keys: Vec<_> = (0..10).map(|_| KeyPair::new()).collect();
+        let messages: Vec<Vec<u8>> = (0..10u8).map(|i| vec![i; 32]).collect();
+        let public_keys: Vec<_> = keys.iter().map(|k| k.public_key()).collect();
+        let signatures: Vec<_> = keys
+            .iter()
+            .zip(&messages)
+            .map(|(k, m)| sign_message(&k.private_key(), &k.public_key(), m))
+            .collect();
+
+        assert_eq!(
+            validate_batch(&messages, &public_keys, &signatures),
+            vec![true; 10]
+        );
+    }
+
+    #[test]
+    fn validate_batch_with_invalid_signature() {
+        let keys: Vec<_> = (0..3).map(|_| KeyPair::new()).collect();
+        let messages: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 32]).collect();
+        let public_keys: Vec<_> = keys.iter().map(|k| k.public_key()).collect();
+        let mut signatures: Vec<_> = keys
+            .iter()
+            .zip(&messages)
+            .map(|(k, m)| sign_message(&k.private_key(), &k.public_key(), m))
+            .collect();
+        signatures[1] = signatures[0].clone();
+
+        assert_eq!(
+            validate_batch(&messages, &public_keys, &signatures),
+            vec![true, false, true]
+        );
+
+        let mut valid = vec![-1; 3];
+        validate_message_batch(&messages, &public_keys, &signatures, &mut valid);
+        assert_eq!(valid, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn batch_rejects_small_order_signature() {
+        let keypair = KeyPair::new();
+        let message = [7u8; 32];
+        let valid = sign_message(&keypair.private_key(), &keypair.public_key(), &message);
+
+        // The identity point as public key and R with s = 0 satisfies the
+        // cofactorless batch equation, but is rejected by the strict verification
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut small_order = [0u8; 64];
+        small_order[0] = 1;
+
+        assert_eq!(
+            validate_batch(
+                &[message, message],
+                &[keypair.public_key(), PublicKey::from_bytes(identity)],
+                &[valid, Signature::from_bytes(small_order)]
+            ),
+            vec![true, false]
+        );
+    }
+
+    #[test]
+    fn validate_empty_batch() {
+        let messages: Vec<Vec<u8>> = Vec::new();
+        assert!(validate_batch(&messages, &[], &[]).is_empty());
+    }
+
     #[test]
     fn signing_same_message_twice_produces_equal_signatures() {
         // the C++ implementation adds random bytes and a padding when signing for extra security and for making side channel attacks more difficult.
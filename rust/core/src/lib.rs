@@ -22,9 +22,13 @@ pub use block_hash::{BlockHash, BlockHashBuilder};
 
 mod key_pair;
 pub use key_pair::{
-    sign_message, validate_block_signature, validate_message, validate_message_batch, KeyPair,
+    sign_message, validate_batch, validate_block_signature, validate_message,
+    validate_message_batch, KeyPair,
 };
 
+mod mnemonic;
+pub use mnemonic::{derive_nano_key, generate_mnemonic, mnemonic_to_seed, NANO_COIN_TYPE};
+
 mod raw_key;
 pub use raw_key::RawKey;
 
@@ -73,6 +77,7 @@ pub use kdf::KeyDerivationFunction;
 
 use std::fmt::Write;
 use std::num::ParseIntError;
+use std::str::FromStr;
 
 pub fn encode_hex(i: u128) -> String {
     let mut result = String::with_capacity(32);
@@ -98,6 +103,7 @@ pub fn u64_from_hex_str(s: impl AsRef<str>) -> Result<u64, ParseIntError> {
 }
 
 u256_struct!(HashOrAccount);
+u256_struct!(LedgerChecksum);
 u256_struct!(Link);
 u256_struct!(PublicKey);
 u256_struct!(Root);
@@ -186,6 +192,12 @@ impl From<&Link> for HashOrAccount {
     }
 }
 
+impl From<BlockHash> for HashOrAccount {
+    fn from(hash: BlockHash) -> Self {
+        HashOrAccount::from_bytes(*hash.as_bytes())
+    }
+}
+
 impl From<PublicKey> for Root {
     fn from(key: PublicKey) -> Self {
         Root::from_bytes(*key.as_bytes())
@@ -210,6 +222,18 @@ impl From<&BlockHash> for Root {
     }
 }
 
+impl Root {
+    /// The root of a block of `account` which follows `previous`.
+    /// This is the account for the first block of an account and the previous block otherwise.
+    pub fn for_block(account: &Account, previous: &BlockHash) -> Self {
+        if previous.is_zero() {
+            account.into()
+        } else {
+            previous.into()
+        }
+    }
+}
+
 impl PublicKey {
     /// IV for Key encryption
     pub fn initialization_vector(&self) -> [u8; 16] {
@@ -264,7 +288,7 @@ pub fn deterministic_key(seed: &RawKey, index: u32) -> RawKey {
  * Network variants with different genesis blocks and network parameters
  */
 #[repr(u16)]
-#[derive(Clone, Copy, FromPrimitive, PartialEq, Eq)]
+#[derive(Clone, Copy, FromPrimitive, PartialEq, Eq, Debug)]
 pub enum Networks {
     Invalid = 0x0,
     // Low work parameters, publicly known genesis key, dev IP ports
@@ -289,6 +313,20 @@ impl Networks {
     }
 }
 
+impl FromStr for Networks {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "live" => Ok(Networks::NanoLiveNetwork),
+            "beta" => Ok(Networks::NanoBetaNetwork),
+            "dev" => Ok(Networks::NanoDevNetwork),
+            "test" => Ok(Networks::NanoTestNetwork),
+            _ => Err(anyhow!("invalid network")),
+        }
+    }
+}
+
 /// A slice of blocks of an account chain
 #[derive(Clone, PartialEq, Eq, Default)]
 pub struct BlockChainSection {
@@ -0,0 +1,125 @@
+//! BIP39 mnemonic phrases and BIP44 key derivation as used by hardware wallets like Ledger and Trezor.
+//! The keys of the Nano chain are derived along the hardened path m/44'/165'/index'
+//! with SLIP-0010, because BIP32 itself doesn't support ed25519.
+
+use crate::RawKey;
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha512;
+
+/// The registered SLIP-0044 coin type of Nano
+pub const NANO_COIN_TYPE: u32 = 165;
+
+const BIP44_PURPOSE: u32 = 44;
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Creates a new random mnemonic phrase with 12, 15, 18, 21 or 24 words
+pub fn generate_mnemonic(word_count: usize) -> anyhow::Result<String> {
+    if ![12, 15, 18, 21, 24].contains(&word_count) {
+        bail!("invalid mnemonic word count: {}", word_count);
+    }
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    rand::thread_rng().fill_bytes(&mut entropy);
+    Ok(Mnemonic::from_entropy(&entropy)?.to_string())
+}
+
+/// Validates the word list and checksum of `phrase` and converts it into a 64 byte BIP39 seed
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> anyhow::Result<[u8; 64]> {
+    let mnemonic = Mnemonic::parse(phrase).map_err(|e| anyhow!("invalid mnemonic: {}", e))?;
+    Ok(mnemonic.to_seed(passphrase))
+}
+
+/// Derives the private key of the account at `index` from a BIP39 seed
+pub fn derive_nano_key(seed: &[u8], index: u32) -> anyhow::Result<RawKey> {
+    if index >= HARDENED_OFFSET {
+        bail!("account index is too large: {}", index);
+    }
+
+    let (mut key, mut chain_code) = split(hmac_sha512(b"ed25519 seed", &[seed]));
+    for segment in [BIP44_PURPOSE, NANO_COIN_TYPE, index] {
+        let child_index = (segment | HARDENED_OFFSET).to_be_bytes();
+        (key, chain_code) = split(hmac_sha512(&chain_code, &[&[0], &key, &child_index]));
+    }
+    Ok(RawKey::from_bytes(key))
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in data {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Splits an extended key into the private key and the chain code
+fn split(extended_key: [u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut key = [0; 32];
+    let mut chain_code = [0; 32];
+    key.copy_from_slice(&extended_key[..32]);
+    chain_code.copy_from_slice(&extended_key[32..]);
+    (key, chain_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeyPair;
+
+    // Test vector from the Nano documentation
+    const PHRASE: &str = "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur";
+    const PASSPHRASE: &str = "some password";
+
+    #[test]
+    fn seed_from_mnemonic() {
+        let seed = mnemonic_to_seed(PHRASE, PASSPHRASE).unwrap();
+        assert_eq!(
+            hex::encode(seed),
+            "0dc285fde768f7ff29b66ce7252d56ed92fe003b605907f7a4f683c3dc8586d34a914d3c71fc099bb38ee4a59e5b081a3497b7a323e90cc68f67b5837690310c"
+        );
+    }
+
+    #[test]
+    fn derive_first_account() {
+        let keys = KeyPair::from_mnemonic_with_passphrase(PHRASE, PASSPHRASE, 0).unwrap();
+        assert_eq!(
+            keys.private_key().encode_hex(),
+            "3BE4FC2EF3F3B7374E6FC4FB6E7BB153F8A2998B3B3DAB50853EABE128024143"
+        );
+        assert_eq!(
+            keys.public_key().encode_account(),
+            "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d"
+        );
+    }
+
+    #[test]
+    fn accounts_differ_by_index() {
+        let first = KeyPair::from_mnemonic(PHRASE, 0).unwrap();
+        let second = KeyPair::from_mnemonic(PHRASE, 1).unwrap();
+        assert_ne!(first.public_key(), second.public_key());
+    }
+
+    #[test]
+    fn reject_hardened_index() {
+        assert!(derive_nano_key(&[0; 64], HARDENED_OFFSET).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_checksum() {
+        let phrase = PHRASE.replace("occur", "edge");
+        assert!(mnemonic_to_seed(&phrase, "").is_err());
+    }
+
+    #[test]
+    fn generated_mnemonic_can_be_recovered() {
+        let phrase = generate_mnemonic(24).unwrap();
+        assert_eq!(phrase.split(' ').count(), 24);
+        assert!(KeyPair::from_mnemonic(&phrase, 0).is_ok());
+    }
+
+    #[test]
+    fn invalid_word_count() {
+        assert!(generate_mnemonic(13).is_err());
+        assert!(generate_mnemonic(27).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
 };
 use primitive_types::U512;
 
-#[derive(Default, PartialEq, Eq, Debug, Clone)]
+#[derive(Default, PartialEq, Eq, Debug, Clone, Hash)]
 pub struct PendingKey {
     pub account: Account,
     pub hash: BlockHash,
@@ -1,10 +1,11 @@
 use crate::{
     utils::{Deserialize, MutStreamAdapter, Serialize, Stream},
-    BlockHash, Root,
+    Account, BlockHash, Root,
 };
+use anyhow::bail;
 use primitive_types::U512;
 
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct QualifiedRoot {
     pub root: Root,
     pub previous: BlockHash,
@@ -15,6 +16,30 @@ impl QualifiedRoot {
         Self { root, previous }
     }
 
+    /// The qualified root of a block of `account` which follows `previous`
+    pub fn for_block(account: &Account, previous: &BlockHash) -> Self {
+        Self::new(Root::for_block(account, previous), *previous)
+    }
+
+    /// Like `new`, but fails if the root can't belong to a block with the given previous block
+    pub fn try_new(root: Root, previous: BlockHash) -> anyhow::Result<Self> {
+        let qualified_root = Self::new(root, previous);
+        if !qualified_root.is_valid() {
+            bail!("root {} doesn't match previous block {}", root, previous);
+        }
+        Ok(qualified_root)
+    }
+
+    /// Only the first block of an account has a root which differs from its previous block
+    pub fn is_valid(&self) -> bool {
+        self.is_open() || self.root == Root::from(&self.previous)
+    }
+
+    /// Whether this is the qualified root of the first block of an account
+    pub fn is_open(&self) -> bool {
+        self.previous.is_zero()
+    }
+
     pub fn to_bytes(&self) -> [u8; 64] {
         let mut buffer = [0; 64];
         let mut stream = MutStreamAdapter::new(&mut buffer);
@@ -63,3 +88,33 @@ impl From<U512> for QualifiedRoot {
         QualifiedRoot { root, previous }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_block_uses_account_as_root() {
+        let account = Account::from(1);
+        let qualified_root = QualifiedRoot::for_block(&account, &BlockHash::zero());
+        assert_eq!(qualified_root.root, Root::from(&account));
+        assert!(qualified_root.is_open());
+        assert!(qualified_root.is_valid());
+    }
+
+    #[test]
+    fn other_blocks_use_previous_as_root() {
+        let previous = BlockHash::from(2);
+        let qualified_root = QualifiedRoot::for_block(&Account::from(1), &previous);
+        assert_eq!(qualified_root.root, Root::from(&previous));
+        assert!(!qualified_root.is_open());
+        assert!(qualified_root.is_valid());
+    }
+
+    #[test]
+    fn reject_root_which_does_not_match_previous() {
+        assert!(QualifiedRoot::try_new(Root::from(1), BlockHash::from(2)).is_err());
+        assert!(QualifiedRoot::try_new(Root::from(2), BlockHash::from(2)).is_ok());
+        assert!(QualifiedRoot::try_new(Root::from(1), BlockHash::zero()).is_ok());
+    }
+}
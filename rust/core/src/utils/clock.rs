@@ -0,0 +1,103 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime},
+};
+
+use super::Timestamp;
+
+/// Source of the current time. Components which check timeouts or ages read the time
+/// through a clock, so that tests can move the time forward instead of sleeping.
+pub trait Clock: Send + Sync {
+    /// The wall clock time, for timestamps which are persisted or sent to peers
+    fn now(&self) -> SystemTime;
+
+    /// The monotonic time, for measuring how much time has passed
+    fn instant(&self) -> Instant;
+
+    fn timestamp(&self) -> Timestamp {
+        self.now().into()
+    }
+
+    fn seconds_since_epoch(&self) -> u64 {
+        self.timestamp().as_secs()
+    }
+}
+
+/// The real wall clock
+#[derive(Default)]
+pub struct SystemClock {}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock which only moves when it is advanced manually
+pub struct TestClock {
+    now: Mutex<(SystemTime, Instant)>,
+}
+
+impl TestClock {
+    /// Creates a clock which starts at the current system time
+    pub fn new() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+
+    pub fn starting_at(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new((now, Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap();
+        now.0 += duration;
+        now.1 += duration;
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> SystemTime {
+        self.now.lock().unwrap().0
+    }
+
+    fn instant(&self) -> Instant {
+        self.now.lock().unwrap().1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_test_clock() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        let clock = TestClock::starting_at(start);
+        let instant = clock.instant();
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.seconds_since_epoch(), 1000);
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(clock.now(), start + Duration::from_secs(5));
+        assert_eq!(clock.seconds_since_epoch(), 1005);
+        assert_eq!(clock.instant(), instant + Duration::from_secs(5));
+    }
+}
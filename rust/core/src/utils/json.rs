@@ -99,6 +99,19 @@ impl SerdePropertyTree {
             value: serde_json::from_str(s)?,
         })
     }
+
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    fn child_value(child: &dyn PropertyTreeWriter) -> Value {
+        child
+            .as_any()
+            .downcast_ref::<Self>()
+            .expect("child must be a SerdePropertyTree")
+            .value
+            .clone()
+    }
 }
 
 impl PropertyTreeReader for SerdePropertyTree {
@@ -135,16 +148,23 @@ impl PropertyTreeWriter for SerdePropertyTree {
         Box::new(Self::new())
     }
 
-    fn push_back(&mut self, _path: &str, _value: &dyn PropertyTreeWriter) {
-        todo!()
+    /// Only unnamed children are supported, which turn this tree into a JSON array
+    fn push_back(&mut self, path: &str, value: &dyn PropertyTreeWriter) {
+        assert!(path.is_empty(), "only unnamed children are supported");
+        if matches!(&self.value, Value::Object(map) if map.is_empty()) {
+            self.value = Value::Array(Vec::new());
+        }
+        let Value::Array(items) = &mut self.value else { panic!("not an array") };
+        items.push(Self::child_value(value));
     }
 
-    fn add_child(&mut self, _path: &str, _value: &dyn PropertyTreeWriter) {
-        todo!()
+    fn add_child(&mut self, path: &str, value: &dyn PropertyTreeWriter) {
+        self.put_child(path, value);
     }
 
-    fn put_child(&mut self, _path: &str, _value: &dyn PropertyTreeWriter) {
-        todo!()
+    fn put_child(&mut self, path: &str, value: &dyn PropertyTreeWriter) {
+        let Value::Object(map) = &mut self.value else { panic!("not an object") };
+        map.insert(path.to_string(), Self::child_value(value));
     }
 
     fn add(&mut self, _path: &str, _value: &str) -> anyhow::Result<()> {
@@ -2,7 +2,7 @@ mod json;
 mod output_tracker;
 mod output_tracker_mt;
 
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
 pub use json::*;
 pub use output_tracker::{OutputListener, OutputTracker};
@@ -17,9 +17,18 @@ pub use toml::*;
 mod logger;
 pub use logger::{ConsoleLogger, Logger, NullLogger};
 
+mod rate_limited_logger;
+pub use rate_limited_logger::RateLimitedLogger;
+
 mod container_info;
 pub use container_info::{ContainerInfo, ContainerInfoComponent};
 
+mod clock;
+pub use clock::{Clock, SystemClock, TestClock};
+
+mod timestamp;
+pub use timestamp::Timestamp;
+
 pub trait Serialize {
     fn serialized_size() -> usize;
     fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()>;
@@ -122,7 +131,7 @@ pub fn seconds_since_epoch() -> u64 {
 }
 
 pub fn system_time_from_nanoseconds(nanos: u64) -> SystemTime {
-    SystemTime::UNIX_EPOCH + Duration::from_nanos(nanos)
+    Timestamp::from_nanos(nanos).into()
 }
 
 pub fn system_time_as_nanoseconds(time: SystemTime) -> u64 {
@@ -0,0 +1,212 @@
+use super::{Clock, Logger};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// Logs at most `max_messages` messages per key and interval. Used for events which can
+/// happen very often during a flood, like dropped packets or invalid signatures.
+/// The number of suppressed messages is logged when the next message of the key passes.
+pub struct RateLimitedLogger {
+    logger: Arc<dyn Logger>,
+    clock: Arc<dyn Clock>,
+    max_messages: usize,
+    interval: Duration,
+    keys: Mutex<HashMap<&'static str, KeyState>>,
+}
+
+#[derive(Default)]
+struct KeyState {
+    window_start: Option<SystemTime>,
+    logged: usize,
+    /// Suppressed messages which weren't reported yet
+    pending_suppressed: u64,
+    suppressed_total: u64,
+}
+
+impl RateLimitedLogger {
+    pub const DEFAULT_MAX_MESSAGES: usize = 10;
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(
+        logger: Arc<dyn Logger>,
+        clock: Arc<dyn Clock>,
+        max_messages: usize,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            logger,
+            clock,
+            max_messages,
+            interval,
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Same as `Logger::try_log`, but the message is suppressed if too many
+    /// messages with the same key were logged in the current interval
+    pub fn try_log(&self, key: &'static str, message: &str) -> bool {
+        match self.pass(key) {
+            Some(suppressed) => self.logger.try_log(&with_note(message, suppressed)),
+            None => false,
+        }
+    }
+
+    /// Same as `Logger::always_log`, but rate limited per key
+    pub fn always_log(&self, key: &'static str, message: &str) {
+        if let Some(suppressed) = self.pass(key) {
+            self.logger.always_log(&with_note(message, suppressed));
+        }
+    }
+
+    /// Number of suppressed messages of a key since the logger was created
+    pub fn suppressed(&self, key: &str) -> u64 {
+        self.keys
+            .lock()
+            .unwrap()
+            .get(key)
+            .map(|state| state.suppressed_total)
+            .unwrap_or_default()
+    }
+
+    /// Number of suppressed messages of all keys since the logger was created
+    pub fn suppressed_total(&self) -> u64 {
+        self.keys
+            .lock()
+            .unwrap()
+            .values()
+            .map(|state| state.suppressed_total)
+            .sum()
+    }
+
+    /// Returns the number of messages which were suppressed since the last logged
+    /// message of the key, or `None` if the message has to be suppressed
+    fn pass(&self, key: &'static str) -> Option<u64> {
+        let now = self.clock.now();
+        let mut keys = self.keys.lock().unwrap();
+        let state = keys.entry(key).or_default();
+
+        let window_expired = match state.window_start {
+            Some(start) => now.duration_since(start).unwrap_or_default() >= self.interval,
+            None => true,
+        };
+        if window_expired {
+            state.window_start = Some(now);
+            state.logged = 0;
+        }
+
+        if state.logged >= self.max_messages {
+            state.pending_suppressed += 1;
+            state.suppressed_total += 1;
+            return None;
+        }
+
+        state.logged += 1;
+        Some(std::mem::take(&mut state.pending_suppressed))
+    }
+}
+
+fn with_note(message: &str, suppressed: u64) -> String {
+    if suppressed > 0 {
+        format!("{} ({} similar messages suppressed)", message, suppressed)
+    } else {
+        message.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TestClock;
+
+    #[derive(Default)]
+    struct RecordingLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl Logger for RecordingLogger {
+        fn try_log(&self, message: &str) -> bool {
+            self.always_log(message);
+            true
+        }
+
+        fn always_log(&self, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+
+        fn handle(&self) -> *mut std::ffi::c_void {
+            std::ptr::null_mut()
+        }
+    }
+
+    struct Fixture {
+        recorder: Arc<RecordingLogger>,
+        clock: Arc<TestClock>,
+        logger: RateLimitedLogger,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let recorder = Arc::new(RecordingLogger::default());
+            let clock = Arc::new(TestClock::new());
+            let logger =
+                RateLimitedLogger::new(recorder.clone(), clock.clone(), 2, Duration::from_secs(60));
+            Self {
+                recorder,
+                clock,
+                logger,
+            }
+        }
+
+        fn messages(&self) -> Vec<String> {
+            self.recorder.messages.lock().unwrap().clone()
+        }
+    }
+
+    #[test]
+    fn log_until_limit_is_reached() {
+        let fixture = Fixture::new();
+        assert!(fixture.logger.try_log("drop", "a"));
+        assert!(fixture.logger.try_log("drop", "b"));
+        assert!(!fixture.logger.try_log("drop", "c"));
+
+        assert_eq!(fixture.messages(), vec!["a", "b"]);
+        assert_eq!(fixture.logger.suppressed("drop"), 1);
+    }
+
+    #[test]
+    fn keys_are_limited_independently() {
+        let fixture = Fixture::new();
+        fixture.logger.always_log("drop", "a");
+        fixture.logger.always_log("drop", "b");
+        fixture.logger.always_log("drop", "c");
+        fixture.logger.always_log("signature", "d");
+
+        assert_eq!(fixture.messages(), vec!["a", "b", "d"]);
+        assert_eq!(fixture.logger.suppressed("signature"), 0);
+        assert_eq!(fixture.logger.suppressed_total(), 1);
+    }
+
+    #[test]
+    fn report_suppressed_messages_in_next_interval() {
+        let fixture = Fixture::new();
+        for _ in 0..5 {
+            fixture.logger.always_log("drop", "dropped");
+        }
+        fixture.clock.advance(Duration::from_secs(60));
+        fixture.logger.always_log("drop", "dropped");
+        fixture.logger.always_log("drop", "dropped");
+
+        assert_eq!(
+            fixture.messages(),
+            vec![
+                "dropped",
+                "dropped",
+                "dropped (3 similar messages suppressed)",
+                "dropped"
+            ]
+        );
+        assert_eq!(fixture.logger.suppressed("drop"), 3);
+    }
+}
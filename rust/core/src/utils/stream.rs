@@ -73,6 +73,12 @@ impl MemoryStream {
         self.bytes
     }
 
+    /// Removes all bytes but keeps the allocated memory, so that the stream can be reused
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.read_index = 0;
+    }
+
     pub fn at_end(&self) -> bool {
         self.bytes.len() - self.read_index == 0
     }
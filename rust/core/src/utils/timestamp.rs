@@ -0,0 +1,134 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Sub},
+    time::{Duration, SystemTime},
+};
+
+/// A point in wall clock time with nanosecond precision, stored as nanoseconds since the
+/// unix epoch. This is the representation that crosses the FFI boundary, so converting
+/// between `SystemTime` and the C++ `system_clock::time_point` never loses precision.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub const EPOCH: Timestamp = Timestamp(0);
+
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs.saturating_mul(1_000_000_000))
+    }
+
+    pub const fn as_nanos(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn as_secs(&self) -> u64 {
+        self.0 / 1_000_000_000
+    }
+
+    /// Time that passed between `earlier` and `self`. Returns zero if `earlier` is later
+    pub fn duration_since(&self, earlier: Timestamp) -> Duration {
+        Duration::from_nanos(self.0.saturating_sub(earlier.0))
+    }
+
+    pub fn saturating_sub(&self, duration: Duration) -> Self {
+        Self(self.0.saturating_sub(duration_as_nanos(duration)))
+    }
+}
+
+fn duration_as_nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl From<SystemTime> for Timestamp {
+    /// Times before the unix epoch are clamped to the epoch
+    fn from(value: SystemTime) -> Self {
+        Self(
+            value
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(duration_as_nanos)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(value: Timestamp) -> Self {
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(value.0)
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0.saturating_add(duration_as_nanos(rhs)))
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}ns", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_time_roundtrip_keeps_nanoseconds() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_nanos(1_234_567_891_234);
+        let timestamp = Timestamp::from(time);
+        assert_eq!(timestamp.as_nanos(), 1_234_567_891_234);
+        assert_eq!(SystemTime::from(timestamp), time);
+    }
+
+    #[test]
+    fn times_before_epoch_are_clamped() {
+        let time = SystemTime::UNIX_EPOCH - Duration::from_secs(1);
+        assert_eq!(Timestamp::from(time), Timestamp::EPOCH);
+    }
+
+    #[test]
+    fn seconds() {
+        let timestamp = Timestamp::from_secs(5) + Duration::from_millis(999);
+        assert_eq!(timestamp.as_secs(), 5);
+        assert_eq!(timestamp.as_nanos(), 5_999_000_000);
+    }
+
+    #[test]
+    fn cutoff_keeps_sub_second_precision() {
+        let now = Timestamp::from_nanos(10_000_000_500);
+        let cutoff = now - Duration::from_secs(10);
+        assert_eq!(cutoff.as_nanos(), 500);
+        assert!(Timestamp::from_nanos(499) < cutoff);
+        assert!(Timestamp::from_nanos(500) >= cutoff);
+    }
+
+    #[test]
+    fn subtraction_saturates() {
+        let timestamp = Timestamp::from_secs(1);
+        assert_eq!(timestamp - Duration::from_secs(2), Timestamp::EPOCH);
+        assert_eq!(Timestamp::EPOCH.duration_since(timestamp), Duration::ZERO);
+        assert_eq!(
+            timestamp.duration_since(Timestamp::EPOCH),
+            Duration::from_secs(1)
+        );
+    }
+}
@@ -31,7 +31,7 @@ impl WorkPool for StubWorkPool {
     }
 
     fn generate_dev2(&self, _root: Root) -> Option<u64> {
-        Some(self.thresholds.base)
+        Some(self.thresholds.threshold_base(WorkVersion::Work1))
     }
 
     fn generate(&self, _version: WorkVersion, _root: Root, difficulty: u64) -> Option<u64> {
@@ -147,7 +147,11 @@ impl WorkPool for WorkPoolImpl {
     }
 
     fn generate_dev2(&self, root: Root) -> Option<u64> {
-        self.generate(WorkVersion::Work1, root, self.work_thresholds.base)
+        self.generate(
+            WorkVersion::Work1,
+            root,
+            self.work_thresholds.threshold_base(WorkVersion::Work1),
+        )
     }
 
     fn generate(&self, version: WorkVersion, root: Root, difficulty: u64) -> Option<u64> {
@@ -263,7 +267,7 @@ mod tests {
     fn work_one() {
         let pool = &WORK_POOL;
         let mut block = BlockBuilder::state().build();
-        let root = block.root();
+        let root = block.root_for_work();
         block.set_work(pool.generate_dev2(root).unwrap());
         assert!(pool.threshold_base(block.work_version()) < difficulty(&block));
     }
@@ -273,7 +277,7 @@ mod tests {
         let pool = &WORK_POOL;
         let mut block = BlockBuilder::legacy_send().work(6).build();
         assert!(difficulty(&block) < pool.threshold_base(block.work_version()));
-        let root = block.root();
+        let root = block.root_for_work();
         block
             .as_block_mut()
             .set_work(pool.generate_dev2(root).unwrap());
@@ -1,7 +1,8 @@
 use crate::{
-    BlockDetails, BlockEnum, BlockType, Difficulty, DifficultyV1, Epoch, Root, StubDifficulty,
-    WorkVersion,
+    BlockDetails, BlockEnum, BlockType, Difficulty, DifficultyV1, Epoch, Networks, Root,
+    StubDifficulty, WorkVersion,
 };
+use anyhow::bail;
 use once_cell::sync::Lazy;
 use std::cmp::{max, min};
 
@@ -25,6 +26,10 @@ pub struct WorkThresholds {
     // Automatically calculated. The entry threshold is the minimum of all thresholds and defines the required work to enter the node, but does not guarantee a block is processed
     pub entry: u64,
     pub difficulty: Box<dyn Difficulty>,
+
+    /// Replaces every threshold while it is set, so that integration tests on the dev
+    /// and test network can run with trivial work
+    pub threshold_override: Option<u64>,
 }
 
 impl Clone for WorkThresholds {
@@ -36,6 +41,7 @@ impl Clone for WorkThresholds {
             base: self.base,
             entry: self.entry,
             difficulty: self.difficulty.clone(),
+            threshold_override: self.threshold_override,
         }
     }
 }
@@ -94,6 +100,73 @@ mod tests {
         assert_eq!(parse_hex_u64("0xFFFFFFC000000000"), Ok(0xffffffc000000000));
         assert_eq!(parse_hex_u64("FFFFFFC000000000"), Ok(0xffffffc000000000));
     }
+
+    #[test]
+    fn multiplier() {
+        let base = 0xff00000000000000;
+        assert_eq!(WorkThresholds::multiplier(base, base), 1.0);
+        assert_eq!(WorkThresholds::multiplier(0xfff0000000000000, base), 16.0);
+        assert_eq!(
+            WorkThresholds::multiplier(0xf000000000000000, base),
+            1.0 / 16.0
+        );
+    }
+
+    #[test]
+    fn difficulty_from_multiplier() {
+        let base = 0xff00000000000000;
+        assert_eq!(WorkThresholds::difficulty_from_multiplier(1.0, base), base);
+        assert_eq!(
+            WorkThresholds::difficulty_from_multiplier(16.0, base),
+            0xfff0000000000000
+        );
+        assert_eq!(
+            WorkThresholds::difficulty_from_multiplier(1.0 / 16.0, base),
+            0xf000000000000000
+        );
+    }
+
+    #[test]
+    fn threshold_override_is_rejected_on_live_network() {
+        let mut thresholds = WorkThresholds::publish_full().clone();
+        assert!(thresholds
+            .set_threshold_override(Networks::NanoLiveNetwork, Some(0x8000000000000000))
+            .is_err());
+        assert_eq!(thresholds.threshold_override, None);
+    }
+
+    #[test]
+    fn threshold_override_on_dev_network() {
+        let mut thresholds = WorkThresholds::publish_dev().clone();
+        let details = BlockDetails::new(Epoch::Epoch2, true, false, false);
+
+        thresholds
+            .set_threshold_override(Networks::NanoDevNetwork, Some(0x8000000000000000))
+            .unwrap();
+        assert_eq!(thresholds.threshold(&details), 0x8000000000000000);
+        assert_eq!(
+            thresholds.threshold_entry(BlockType::State, WorkVersion::Work1),
+            0x8000000000000000
+        );
+        assert_eq!(
+            thresholds.threshold_base(WorkVersion::Work1),
+            0x8000000000000000
+        );
+        assert_eq!(WorkThresholds::publish_dev().threshold_override, None);
+
+        thresholds
+            .set_threshold_override(Networks::NanoDevNetwork, None)
+            .unwrap();
+        assert_eq!(thresholds.threshold(&details), thresholds.epoch_2);
+        assert_eq!(
+            thresholds.threshold_entry(BlockType::State, WorkVersion::Work1),
+            thresholds.entry
+        );
+        assert_eq!(
+            thresholds.threshold_base(WorkVersion::Work1),
+            thresholds.base
+        );
+    }
 }
 
 impl WorkThresholds {
@@ -112,6 +185,26 @@ impl WorkThresholds {
     pub fn publish_test() -> &'static WorkThresholds {
         &PUBLISH_TEST
     }
+
+    /// Overrides the thresholds. `None` restores the thresholds of the network.
+    /// Only the dev and test network allow this.
+    pub fn set_threshold_override(
+        &mut self,
+        network: Networks,
+        threshold: Option<u64>,
+    ) -> anyhow::Result<()> {
+        if !matches!(
+            network,
+            Networks::NanoDevNetwork | Networks::NanoTestNetwork
+        ) {
+            bail!(
+                "work thresholds cannot be overridden on the {} network",
+                network.as_str()
+            );
+        }
+        self.threshold_override = threshold;
+        Ok(())
+    }
 }
 
 impl WorkThresholds {
@@ -137,10 +230,14 @@ impl WorkThresholds {
             base: max(max(epoch_1, epoch_2), epoch_2_receive),
             entry: min(min(epoch_1, epoch_2), epoch_2_receive),
             difficulty,
+            threshold_override: None,
         }
     }
 
     pub fn threshold_entry(&self, block_type: BlockType, work_version: WorkVersion) -> u64 {
+        if let Some(threshold) = self.threshold_override {
+            return threshold;
+        }
         match block_type {
             BlockType::State => match work_version {
                 WorkVersion::Work1 => self.entry,
@@ -154,6 +251,9 @@ impl WorkThresholds {
     }
 
     pub fn threshold(&self, details: &BlockDetails) -> u64 {
+        if let Some(threshold) = self.threshold_override {
+            return threshold;
+        }
         match details.epoch {
             Epoch::Epoch2 => {
                 if details.is_receive || details.is_epoch {
@@ -186,7 +286,7 @@ impl WorkThresholds {
 
     pub fn threshold_base(&self, work_version: WorkVersion) -> u64 {
         match work_version {
-            WorkVersion::Work1 => self.base,
+            WorkVersion::Work1 => self.threshold_override.unwrap_or(self.base),
             _ => {
                 debug_assert!(false, "Invalid version specified to work_threshold_base");
                 u64::MAX
@@ -194,6 +294,16 @@ impl WorkThresholds {
         }
     }
 
+    /// How many times more work is needed to reach `difficulty` than to reach `base_difficulty`
+    pub fn multiplier(difficulty: u64, base_difficulty: u64) -> f64 {
+        DifficultyV1::to_multiplier(difficulty, base_difficulty)
+    }
+
+    /// The difficulty which requires `multiplier` times the work of `base_difficulty`
+    pub fn difficulty_from_multiplier(multiplier: f64, base_difficulty: u64) -> u64 {
+        DifficultyV1::from_multiplier(multiplier, base_difficulty)
+    }
+
     pub fn normalized_multiplier(&self, multiplier: f64, threshold: u64) -> f64 {
         debug_assert!(multiplier >= 1f64);
         /* Normalization rules
@@ -246,7 +356,7 @@ impl WorkThresholds {
     }
 
     pub fn difficulty_block(&self, block: &BlockEnum) -> u64 {
-        self.difficulty(block.work_version(), &block.root(), block.work())
+        self.difficulty(block.work_version(), &block.root_for_work(), block.work())
     }
 
     //todo return true if valid!
@@ -0,0 +1,63 @@
+use rsnano_core::work::WorkThresholds;
+use rsnano_node::block_processing::ActiveDifficulty;
+
+use crate::{core::BlockHandle, work::WorkThresholdsDto};
+
+pub struct ActiveDifficultyHandle(ActiveDifficulty);
+
+#[no_mangle]
+pub extern "C" fn rsn_active_difficulty_create(
+    work: &WorkThresholdsDto,
+    max_samples: usize,
+) -> *mut ActiveDifficultyHandle {
+    Box::into_raw(Box::new(ActiveDifficultyHandle(ActiveDifficulty::new(
+        WorkThresholds::from(work),
+        max_samples,
+    ))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_active_difficulty_destroy(handle: *mut ActiveDifficultyHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_active_difficulty_sample(
+    handle: *mut ActiveDifficultyHandle,
+    block: &BlockHandle,
+) {
+    (*handle).0.sample(&block.block.read().unwrap());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_active_difficulty_multiplier(
+    handle: *mut ActiveDifficultyHandle,
+) -> f64 {
+    (*handle).0.multiplier()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_active_difficulty_difficulty(
+    handle: *mut ActiveDifficultyHandle,
+) -> u64 {
+    (*handle).0.difficulty()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_active_difficulty_receive_difficulty(
+    handle: *mut ActiveDifficultyHandle,
+) -> u64 {
+    (*handle).0.receive_difficulty()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_active_difficulty_trend(
+    handle: *mut ActiveDifficultyHandle,
+    result: *mut f64,
+    max_len: usize,
+) -> usize {
+    let trend = (*handle).0.trend();
+    let len = trend.len().min(max_len);
+    std::slice::from_raw_parts_mut(result, len).copy_from_slice(&trend[..len]);
+    len
+}
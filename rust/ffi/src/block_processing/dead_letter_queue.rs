@@ -0,0 +1,72 @@
+use std::{
+    ffi::c_void,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use num::FromPrimitive;
+use rsnano_core::{Account, BlockHash};
+use rsnano_node::block_processing::DeadLetterQueue;
+
+use crate::FfiPropertyTreeWriter;
+
+pub struct DeadLetterQueueHandle(DeadLetterQueue);
+
+#[no_mangle]
+pub extern "C" fn rsn_dead_letter_queue_create() -> *mut DeadLetterQueueHandle {
+    Box::into_raw(Box::new(DeadLetterQueueHandle(DeadLetterQueue::default())))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_dead_letter_queue_destroy(handle: *mut DeadLetterQueueHandle) {
+    drop(Box::from_raw(handle))
+}
+
+/// `result` is the code of the process result
+#[no_mangle]
+pub unsafe extern "C" fn rsn_dead_letter_queue_record(
+    handle: *mut DeadLetterQueueHandle,
+    hash: *const u8,
+    account: *const u8,
+    result: u8,
+) {
+    let unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+    (*handle).0.record(
+        &BlockHash::from_ptr(hash),
+        &Account::from_ptr(account),
+        FromPrimitive::from_u8(result).unwrap(),
+        Instant::now(),
+        unix_ms,
+    );
+}
+
+/// Returns the number of attempts or 0 if the block isn't tracked
+#[no_mangle]
+pub unsafe extern "C" fn rsn_dead_letter_queue_attempts(
+    handle: *mut DeadLetterQueueHandle,
+    hash: *const u8,
+) -> u32 {
+    (*handle)
+        .0
+        .get(&BlockHash::from_ptr(hash))
+        .map(|letter| letter.attempts)
+        .unwrap_or_default()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_dead_letter_queue_size(handle: *mut DeadLetterQueueHandle) -> usize {
+    (*handle).0.len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_dead_letter_queue_serialize_json(
+    handle: *mut DeadLetterQueueHandle,
+    ptree: *mut c_void,
+) {
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    if let Err(e) = (*handle).0.serialize_json(&mut writer, Instant::now()) {
+        eprintln!("dead letter queue serialization failed: {:?}", e);
+    }
+}
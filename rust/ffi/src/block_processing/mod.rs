@@ -1,4 +1,6 @@
+mod active_difficulty;
 mod block_arrival;
 mod block_processor;
+mod dead_letter_queue;
 pub(crate) use block_processor::*;
 mod backlog_population;
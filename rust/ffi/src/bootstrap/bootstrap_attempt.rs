@@ -123,6 +123,16 @@ pub unsafe extern "C" fn rsn_bootstrap_attempt_bootstrap_mode_text(
     mode_text.as_ptr() as *const c_char
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_state_text(
+    handle: *const BootstrapAttemptHandle,
+    len: *mut usize,
+) -> *const c_char {
+    let state_text = (*handle).0.attempt().state().as_str();
+    *len = state_text.len();
+    state_text.as_ptr() as *const c_char
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_bootstrap_attempt_total_blocks(
     handle: *const BootstrapAttemptHandle,
@@ -198,6 +208,19 @@ pub unsafe extern "C" fn rsn_bootstrap_attempt_wait(
     (*lck).0 = Some(std::mem::transmute::<MutexGuard<u8>, MutexGuard<'static, u8>>(guard));
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_wait_for_pull_slot(
+    handle: *mut BootstrapAttemptHandle,
+    lck: *mut BootstrapAttemptLockHandle,
+    max_pulls: u32,
+) {
+    let guard = (*handle)
+        .0
+        .attempt()
+        .wait_for_pull_slot((*lck).0.take().unwrap(), max_pulls);
+    (*lck).0 = Some(std::mem::transmute::<MutexGuard<u8>, MutexGuard<'static, u8>>(guard));
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_bootstrap_attempt_wait_for(
     handle: *mut BootstrapAttemptHandle,
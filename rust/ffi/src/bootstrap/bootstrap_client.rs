@@ -1,7 +1,7 @@
 use std::{ffi::c_void, ops::Deref, sync::Arc, time::Duration};
 
 use crate::{
-    messages::MessageHandle,
+    messages::{MessageHandle, SharedBufferHandle},
     transport::{
         ChannelHandle, ChannelTcpSendBufferCallback, ChannelTcpSendCallback,
         ChannelTcpSendCallbackWrapper, EndpointDto, ReadCallbackWrapper, SendBufferCallbackWrapper,
@@ -99,15 +99,14 @@ pub unsafe extern "C" fn rsn_bootstrap_client_receive_buffer(
 #[no_mangle]
 pub unsafe extern "C" fn rsn_bootstrap_client_send_buffer(
     handle: *mut BootstrapClientHandle,
-    buffer: *const u8,
-    len: usize,
+    buffer: *mut SharedBufferHandle,
     callback: ChannelTcpSendBufferCallback,
     delete_callback: VoidPointerCallback,
     callback_context: *mut c_void,
     policy: u8,
     traffic_type: u8,
 ) {
-    let buffer = Arc::new(std::slice::from_raw_parts(buffer, len).to_vec());
+    let buffer = SharedBufferHandle::take(buffer);
     let callback_wrapper =
         SendBufferCallbackWrapper::new(callback, callback_context, delete_callback);
     let cb = Box::new(move |ec, size| {
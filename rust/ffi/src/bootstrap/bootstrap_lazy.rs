@@ -4,6 +4,7 @@ use std::{
     sync::Arc,
 };
 
+use rsnano_core::{BlockHash, HashOrAccount};
 use rsnano_node::{
     bootstrap::{BootstrapAttemptLazy, BootstrapStrategy},
     websocket::{Listener, NullListener},
@@ -11,9 +12,10 @@ use rsnano_node::{
 
 use crate::{
     block_processing::BlockProcessorHandle,
+    core::BlockHandle,
     ledger::datastore::LedgerHandle,
     utils::{LoggerHandle, LoggerMT},
-    FfiListener,
+    FfiListener, FfiPropertyTreeWriter,
 };
 
 use super::{
@@ -29,6 +31,8 @@ pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_create(
     ledger: *const LedgerHandle,
     id: *const c_char,
     incremental_id: u64,
+    retry_limit: u32,
+    max_keys: usize,
 ) -> *mut BootstrapAttemptHandle {
     let logger = Arc::new(LoggerMT::new(Box::from_raw(logger)));
     let id_str = CStr::from_ptr(id).to_str().unwrap();
@@ -40,7 +44,7 @@ pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_create(
     let block_processor = Arc::downgrade(&*block_processor);
     let bootstrap_initiator = Arc::downgrade(&*bootstrap_initiator);
     let ledger = Arc::clone(&*ledger);
-    BootstrapAttemptHandle::new(Arc::new(BootstrapStrategy::Lazy(
+    BootstrapAttemptHandle::new(Arc::new(BootstrapStrategy::Lazy(Box::new(
         BootstrapAttemptLazy::new(
             logger,
             websocket_server,
@@ -49,7 +53,148 @@ pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_create(
             ledger,
             id_str,
             incremental_id,
+            retry_limit,
+            max_keys,
         )
         .unwrap(),
-    )))
+    ))))
+}
+
+unsafe fn lazy_attempt(handle: *const BootstrapAttemptHandle) -> &'static BootstrapAttemptLazy {
+    match &***handle {
+        BootstrapStrategy::Lazy(lazy) => lazy,
+        _ => panic!("not a lazy bootstrap attempt"),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_start(
+    handle: *const BootstrapAttemptHandle,
+    hash_or_account: *const u8,
+) -> bool {
+    lazy_attempt(handle)
+        .state
+        .lock()
+        .unwrap()
+        .start(&HashOrAccount::from_ptr(hash_or_account))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_add(
+    handle: *const BootstrapAttemptHandle,
+    hash_or_account: *const u8,
+    retry_limit: u32,
+) {
+    lazy_attempt(handle)
+        .state
+        .lock()
+        .unwrap()
+        .add(&HashOrAccount::from_ptr(hash_or_account), retry_limit)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_requeue(
+    handle: *const BootstrapAttemptHandle,
+    hash: *const u8,
+) -> bool {
+    lazy_attempt(handle)
+        .state
+        .lock()
+        .unwrap()
+        .requeue(&BlockHash::from_ptr(hash))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_blocks_processed(
+    handle: *const BootstrapAttemptHandle,
+    hash: *const u8,
+) -> bool {
+    lazy_attempt(handle)
+        .state
+        .lock()
+        .unwrap()
+        .blocks_processed(&BlockHash::from_ptr(hash))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_blocks_count(
+    handle: *const BootstrapAttemptHandle,
+) -> usize {
+    lazy_attempt(handle).state.lock().unwrap().blocks_len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_pulls_count(
+    handle: *const BootstrapAttemptHandle,
+) -> usize {
+    lazy_attempt(handle).state.lock().unwrap().pulls_len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_next_pull(
+    handle: *const BootstrapAttemptHandle,
+    hash_or_account: *mut u8,
+    retry_limit: *mut u32,
+) -> bool {
+    let lazy = lazy_attempt(handle);
+    match lazy.state.lock().unwrap().next_pull(lazy.ledger.as_ref()) {
+        Some((next, limit)) => {
+            std::slice::from_raw_parts_mut(hash_or_account, 32).copy_from_slice(next.as_bytes());
+            *retry_limit = limit;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_process_block(
+    handle: *const BootstrapAttemptHandle,
+    block: *const BlockHandle,
+    pull_blocks_processed: u64,
+    retry_limit: u32,
+) -> bool {
+    let lazy = lazy_attempt(handle);
+    let block = (*block).block.read().unwrap();
+    lazy.state.lock().unwrap().process_block(
+        &block,
+        pull_blocks_processed,
+        retry_limit,
+        lazy.ledger.as_ref(),
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_backlog_cleanup(
+    handle: *const BootstrapAttemptHandle,
+) {
+    let lazy = lazy_attempt(handle);
+    lazy.state
+        .lock()
+        .unwrap()
+        .backlog_cleanup(lazy.ledger.as_ref())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_finished(
+    handle: *const BootstrapAttemptHandle,
+) -> bool {
+    let lazy = lazy_attempt(handle);
+    lazy.state.lock().unwrap().finished(lazy.ledger.as_ref())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_attempt_lazy_get_information(
+    handle: *const BootstrapAttemptHandle,
+    ptree: *mut c_void,
+) {
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    if let Err(e) = lazy_attempt(handle)
+        .state
+        .lock()
+        .unwrap()
+        .get_information(&mut writer)
+    {
+        eprintln!("lazy bootstrap information serialization failed: {:?}", e);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
     core::BlockUniquerHandle,
-    transport::{EndpointDto, NetworkFilterHandle, SocketHandle, TcpMessageManagerHandle},
+    transport::{
+        EndpointDto, NetworkFilterHandle, PeerExclusionHandle, SocketHandle,
+        TcpMessageManagerHandle,
+    },
     utils::{FfiIoContext, IoContextHandle, LoggerHandle, LoggerMT},
     voting::VoteUniquerHandle,
     NetworkParamsDto, NodeConfigDto, StatHandle, VoidPointerCallback,
@@ -57,6 +60,8 @@ pub struct CreateTcpServerParams {
     pub vote_uniquer: *mut VoteUniquerHandle,
     pub tcp_message_manager: *mut TcpMessageManagerHandle,
     pub allow_bootstrap: bool,
+    /// Optional, peers on the wrong network get excluded if set
+    pub excluded_peers: *mut PeerExclusionHandle,
 }
 
 #[no_mangle]
@@ -94,6 +99,9 @@ pub unsafe extern "C" fn rsn_bootstrap_server_create(
     server.connections_max = params.connections_max;
     server.disable_bootstrap_bulk_pull_server = params.disable_bootstrap_bulk_pull_server;
     server.disable_tcp_realtime = params.disable_tcp_realtime;
+    if !params.excluded_peers.is_null() {
+        server.excluded_peers = Some(Arc::clone(&(*params.excluded_peers).0));
+    }
     TcpServerHandle::new(Arc::new(server))
 }
 
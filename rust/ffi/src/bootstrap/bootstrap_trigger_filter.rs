@@ -0,0 +1,52 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rsnano_core::HashOrAccount;
+use rsnano_node::bootstrap::BootstrapTriggerFilter;
+
+pub struct BootstrapTriggerFilterHandle(Mutex<BootstrapTriggerFilter>);
+
+#[no_mangle]
+pub extern "C" fn rsn_bootstrap_trigger_filter_create(
+    window_s: u64,
+    max_entries: usize,
+) -> *mut BootstrapTriggerFilterHandle {
+    Box::into_raw(Box::new(BootstrapTriggerFilterHandle(Mutex::new(
+        BootstrapTriggerFilter::new(Duration::from_secs(window_s), max_entries),
+    ))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_trigger_filter_destroy(
+    handle: *mut BootstrapTriggerFilterHandle,
+) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_trigger_filter_check(
+    handle: *mut BootstrapTriggerFilterHandle,
+    hash_or_account: *const u8,
+) -> bool {
+    (*handle)
+        .0
+        .lock()
+        .unwrap()
+        .check(&HashOrAccount::from_ptr(hash_or_account), Instant::now())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_trigger_filter_deduplicated(
+    handle: *const BootstrapTriggerFilterHandle,
+) -> u64 {
+    (*handle).0.lock().unwrap().deduplicated()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_bootstrap_trigger_filter_size(
+    handle: *const BootstrapTriggerFilterHandle,
+) -> usize {
+    (*handle).0.lock().unwrap().len()
+}
@@ -0,0 +1,120 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use rsnano_core::{Account, BlockHash};
+use rsnano_ledger::Ledger;
+use rsnano_node::bootstrap::{FrontierComparer, FrontierWorkItem};
+
+use crate::{copy_account_bytes, copy_hash_bytes, ledger::datastore::LedgerHandle};
+
+pub struct FrontierComparerHandle {
+    comparer: FrontierComparer,
+    ledger: Arc<Ledger>,
+    work: VecDeque<FrontierWorkItem>,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_create(
+    start: *const u8,
+    bulk_push_enabled: bool,
+    ledger: *mut LedgerHandle,
+) -> *mut FrontierComparerHandle {
+    let ledger = Arc::clone(&(*ledger).0);
+    let comparer =
+        FrontierComparer::new(Account::from_ptr(start), bulk_push_enabled, ledger.as_ref());
+    Box::into_raw(Box::new(FrontierComparerHandle {
+        comparer,
+        ledger,
+        work: VecDeque::new(),
+    }))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_destroy(handle: *mut FrontierComparerHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_compare(
+    handle: *mut FrontierComparerHandle,
+    account: *const u8,
+    latest: *const u8,
+) {
+    let handle = &mut *handle;
+    let work = handle.comparer.compare(
+        Account::from_ptr(account),
+        BlockHash::from_ptr(latest),
+        handle.ledger.as_ref(),
+    );
+    handle.work.extend(work);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_finish(handle: *mut FrontierComparerHandle) {
+    let handle = &mut *handle;
+    let work = handle.comparer.finish(handle.ledger.as_ref());
+    handle.work.extend(work);
+}
+
+#[repr(C)]
+pub struct FrontierWorkItemDto {
+    pub is_pull: bool,
+    pub account: [u8; 32],
+    pub head: [u8; 32],
+    pub end: [u8; 32],
+}
+
+/// Returns false if there is no more work
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_pop_work(
+    handle: *mut FrontierComparerHandle,
+    result: *mut FrontierWorkItemDto,
+) -> bool {
+    let Some(item) = (*handle).work.pop_front() else {
+        return false;
+    };
+    let result = &mut *result;
+    match item {
+        FrontierWorkItem::Pull { account, head, end } => {
+            result.is_pull = true;
+            copy_account_bytes(account, result.account.as_mut_ptr());
+            copy_hash_bytes(head, result.head.as_mut_ptr());
+            copy_hash_bytes(end, result.end.as_mut_ptr());
+        }
+        FrontierWorkItem::Push { head, end } => {
+            result.is_pull = false;
+            result.account = [0; 32];
+            copy_hash_bytes(head, result.head.as_mut_ptr());
+            copy_hash_bytes(end, result.end.as_mut_ptr());
+        }
+    }
+    true
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_bulk_push_cost(
+    handle: *const FrontierComparerHandle,
+) -> u64 {
+    (*handle).comparer.bulk_push_cost()
+}
+
+#[repr(C)]
+pub struct FrontierComparerStatsDto {
+    pub received: u64,
+    pub in_sync: u64,
+    pub behind: u64,
+    pub ahead: u64,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_frontier_comparer_stats(
+    handle: *const FrontierComparerHandle,
+    result: *mut FrontierComparerStatsDto,
+) {
+    let stats = (*handle).comparer.stats();
+    *result = FrontierComparerStatsDto {
+        received: stats.received,
+        in_sync: stats.in_sync,
+        behind: stats.behind,
+        ahead: stats.ahead,
+    };
+}
@@ -4,8 +4,10 @@ mod bootstrap_client;
 mod bootstrap_initiator;
 mod bootstrap_lazy;
 mod bootstrap_server;
+mod bootstrap_trigger_filter;
 mod bulk_pull_account_server;
 mod bulk_pull_server;
+mod frontier_comparer;
 mod frontier_req_server;
 mod pulls_cache;
 mod request_response_visitor_factory;
@@ -0,0 +1,184 @@
+use std::{
+    ffi::{c_char, CStr},
+    sync::Arc,
+};
+
+use rsnano_core::{Account, BlockHash};
+use rsnano_node::bootstrap_ascending::{AccountSets, AccountSetsInfo};
+
+use crate::{
+    copy_account_bytes, copy_hash_bytes, utils::ContainerInfoComponentHandle, AccountSetsConfigDto,
+    StatHandle,
+};
+
+pub struct AccountSetsHandle(AccountSets);
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_create(
+    config: *const AccountSetsConfigDto,
+    stats: *mut StatHandle,
+) -> *mut AccountSetsHandle {
+    Box::into_raw(Box::new(AccountSetsHandle(AccountSets::new(
+        (&*config).into(),
+        Arc::clone(&(*stats).0),
+    ))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_destroy(handle: *mut AccountSetsHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_priority_up(
+    handle: *mut AccountSetsHandle,
+    account: *const u8,
+) {
+    (*handle).0.priority_up(&Account::from_ptr(account));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_priority_down(
+    handle: *mut AccountSetsHandle,
+    account: *const u8,
+) {
+    (*handle).0.priority_down(&Account::from_ptr(account));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_block(
+    handle: *mut AccountSetsHandle,
+    account: *const u8,
+    dependency: *const u8,
+) {
+    (*handle)
+        .0
+        .block(Account::from_ptr(account), BlockHash::from_ptr(dependency));
+}
+
+/// `hash` may be null, in which case the account is unblocked regardless of its dependency
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_unblock(
+    handle: *mut AccountSetsHandle,
+    account: *const u8,
+    hash: *const u8,
+) {
+    let hash = if hash.is_null() {
+        None
+    } else {
+        Some(BlockHash::from_ptr(hash))
+    };
+    (*handle).0.unblock(Account::from_ptr(account), hash);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_timestamp(
+    handle: *mut AccountSetsHandle,
+    account: *const u8,
+    reset: bool,
+) {
+    (*handle).0.timestamp(&Account::from_ptr(account), reset);
+}
+
+/// Returns false if no account is available
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_next(
+    handle: *const AccountSetsHandle,
+    account: *mut u8,
+) -> bool {
+    match (*handle).0.next() {
+        Some(next) => {
+            copy_account_bytes(next, account);
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_blocked(
+    handle: *const AccountSetsHandle,
+    account: *const u8,
+) -> bool {
+    (*handle).0.blocked(&Account::from_ptr(account))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_priority_size(handle: *const AccountSetsHandle) -> usize {
+    (*handle).0.priority_len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_blocked_size(handle: *const AccountSetsHandle) -> usize {
+    (*handle).0.blocked_len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_priority(
+    handle: *const AccountSetsHandle,
+    account: *const u8,
+) -> f32 {
+    (*handle).0.priority(&Account::from_ptr(account))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_collect_container_info(
+    handle: *const AccountSetsHandle,
+    name: *const c_char,
+) -> *mut ContainerInfoComponentHandle {
+    let container_info = (*handle)
+        .0
+        .collect_container_info(CStr::from_ptr(name).to_str().unwrap().to_owned());
+    Box::into_raw(Box::new(ContainerInfoComponentHandle(container_info)))
+}
+
+pub struct AccountSetsInfoHandle(AccountSetsInfo);
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_info(
+    handle: *const AccountSetsHandle,
+) -> *mut AccountSetsInfoHandle {
+    Box::into_raw(Box::new(AccountSetsInfoHandle((*handle).0.info())))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_info_destroy(handle: *mut AccountSetsInfoHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_info_blocking_len(
+    handle: *const AccountSetsInfoHandle,
+) -> usize {
+    (*handle).0.blocking.len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_info_blocking_get(
+    handle: *const AccountSetsInfoHandle,
+    index: usize,
+    account: *mut u8,
+    dependency: *mut u8,
+) {
+    let (blocked, hash) = (*handle).0.blocking[index];
+    copy_account_bytes(blocked, account);
+    copy_hash_bytes(hash, dependency);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_info_priorities_len(
+    handle: *const AccountSetsInfoHandle,
+) -> usize {
+    (*handle).0.priorities.len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_account_sets_info_priorities_get(
+    handle: *const AccountSetsInfoHandle,
+    index: usize,
+    account: *mut u8,
+) -> f32 {
+    let (prioritized, priority) = (*handle).0.priorities[index];
+    copy_account_bytes(prioritized, account);
+    priority
+}
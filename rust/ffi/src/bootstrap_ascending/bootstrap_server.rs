@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use rsnano_node::{
+    bootstrap_ascending::BootstrapServer, config::NetworkConstants, messages::AscPullReq,
+};
+
+use crate::{
+    ledger::datastore::{LedgerHandle, TransactionHandle},
+    messages::{downcast_message, MessageHandle},
+    NetworkConstantsDto, StatHandle,
+};
+
+pub struct AscendingBootstrapServerHandle(BootstrapServer);
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ascending_bootstrap_server_create(
+    ledger: *mut LedgerHandle,
+    network_constants: *const NetworkConstantsDto,
+    stats: *mut StatHandle,
+) -> *mut AscendingBootstrapServerHandle {
+    let network_constants = NetworkConstants::try_from(&*network_constants).unwrap();
+    Box::into_raw(Box::new(AscendingBootstrapServerHandle(
+        BootstrapServer::new(
+            Arc::clone(&(*ledger).0),
+            network_constants,
+            Arc::clone(&(*stats).0),
+        ),
+    )))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ascending_bootstrap_server_destroy(
+    handle: *mut AscendingBootstrapServerHandle,
+) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ascending_bootstrap_server_verify(
+    handle: *const AscendingBootstrapServerHandle,
+    request: *mut MessageHandle,
+) -> bool {
+    (*handle).0.verify(downcast_message::<AscPullReq>(request))
+}
+
+/// Returns an `asc_pull_ack` message handle
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ascending_bootstrap_server_process(
+    handle: *const AscendingBootstrapServerHandle,
+    txn: *mut TransactionHandle,
+    request: *mut MessageHandle,
+) -> *mut MessageHandle {
+    let response = (*handle)
+        .0
+        .process((*txn).as_txn(), downcast_message::<AscPullReq>(request));
+    MessageHandle::from_message(response)
+}
@@ -0,0 +1,2 @@
+mod account_sets;
+mod bootstrap_server;
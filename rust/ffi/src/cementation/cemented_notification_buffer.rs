@@ -0,0 +1,57 @@
+use std::ffi::c_void;
+
+use rsnano_core::Account;
+use rsnano_node::cementation::CementedNotificationBuffer;
+
+use crate::{utils::ContextWrapper, VoidPointerCallback};
+
+pub struct CementedNotificationBufferHandle(CementedNotificationBuffer<ContextWrapper>);
+
+#[no_mangle]
+pub extern "C" fn rsn_cemented_notification_buffer_create() -> *mut CementedNotificationBufferHandle
+{
+    Box::into_raw(Box::new(CementedNotificationBufferHandle(
+        CementedNotificationBuffer::new(),
+    )))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_cemented_notification_buffer_destroy(
+    handle: *mut CementedNotificationBufferHandle,
+) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_cemented_notification_buffer_next_sequence(
+    handle: *mut CementedNotificationBufferHandle,
+    account: *const u8,
+) -> u64 {
+    (*handle).0.next_sequence(&Account::from_ptr(account))
+}
+
+/// `notify` is called with `context` once all notifications with a lower sequence number
+/// were delivered. `drop_context` is called afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rsn_cemented_notification_buffer_push(
+    handle: *mut CementedNotificationBufferHandle,
+    account: *const u8,
+    sequence: u64,
+    notify: VoidPointerCallback,
+    context: *mut c_void,
+    drop_context: VoidPointerCallback,
+) {
+    let context = ContextWrapper::new(context, drop_context);
+    (*handle)
+        .0
+        .push(&Account::from_ptr(account), sequence, context, &mut |c| {
+            notify(c.get_context())
+        });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_cemented_notification_buffer_buffered_len(
+    handle: *mut CementedNotificationBufferHandle,
+) -> usize {
+    (*handle).0.buffered_len()
+}
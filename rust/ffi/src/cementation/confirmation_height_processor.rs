@@ -74,6 +74,14 @@ pub unsafe extern "C" fn rsn_confirmation_height_processor_add(
         .add(Arc::new((*block).block.read().unwrap().clone()));
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_confirmation_height_processor_add_hash(
+    handle: *mut ConfirmationHeightProcessorHandle,
+    hash: *const u8,
+) -> bool {
+    (*handle).0.add_hash(&BlockHash::from_ptr(hash))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_confirmation_height_processor_current(
     handle: *mut ConfirmationHeightProcessorHandle,
@@ -1 +1,2 @@
+mod cemented_notification_buffer;
 mod confirmation_height_processor;
@@ -0,0 +1,49 @@
+use rsnano_node::config::ConfirmReqConfig;
+
+#[repr(C)]
+pub struct ConfirmReqConfigDto {
+    pub base_interval_ms: u64,
+    pub backoff_multiplier: f64,
+    pub max_interval_ms: u64,
+    pub max_requests: u32,
+}
+
+impl From<&ConfirmReqConfigDto> for ConfirmReqConfig {
+    fn from(value: &ConfirmReqConfigDto) -> Self {
+        Self {
+            base_interval_ms: value.base_interval_ms,
+            backoff_multiplier: value.backoff_multiplier,
+            max_interval_ms: value.max_interval_ms,
+            max_requests: value.max_requests,
+        }
+    }
+}
+
+impl From<&ConfirmReqConfig> for ConfirmReqConfigDto {
+    fn from(value: &ConfirmReqConfig) -> Self {
+        Self {
+            base_interval_ms: value.base_interval_ms,
+            backoff_multiplier: value.backoff_multiplier,
+            max_interval_ms: value.max_interval_ms,
+            max_requests: value.max_requests,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn rsn_confirm_req_config_request_interval_ms(
+    dto: &ConfirmReqConfigDto,
+    rounds: u32,
+) -> u64 {
+    ConfirmReqConfig::from(dto)
+        .request_interval(rounds)
+        .as_millis() as u64
+}
+
+#[no_mangle]
+pub extern "C" fn rsn_confirm_req_config_may_request(
+    dto: &ConfirmReqConfigDto,
+    rounds: u32,
+) -> bool {
+    ConfirmReqConfig::from(dto).may_request(rounds)
+}
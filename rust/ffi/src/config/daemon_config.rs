@@ -2,8 +2,11 @@ use super::{
     fill_node_config_dto, fill_node_rpc_config_dto, fill_opencl_config_dto, NodeConfigDto,
     NodeRpcConfigDto, OpenclConfigDto,
 };
-use crate::{secure::NetworkParamsDto, utils::FfiToml};
-use rsnano_node::{config::DaemonConfig, NetworkParams};
+use crate::{secure::NetworkParamsDto, utils::FfiToml, StringDto};
+use rsnano_node::{
+    config::{ConfigIssue, DaemonConfig, NodeConfig},
+    NetworkParams,
+};
 use std::{
     convert::{TryFrom, TryInto},
     ffi::c_void,
@@ -56,6 +59,57 @@ pub extern "C" fn rsn_daemon_config_serialize_toml(
     }
 }
 
+#[repr(C)]
+pub struct ConfigIssueDto {
+    pub message: StringDto,
+    pub is_error: bool,
+}
+
+impl From<&ConfigIssue> for ConfigIssueDto {
+    fn from(issue: &ConfigIssue) -> Self {
+        Self {
+            message: StringDto::from(&issue.message),
+            is_error: issue.is_error(),
+        }
+    }
+}
+
+/// Writes up to `size` issues to `result` and returns the number of issues found
+#[no_mangle]
+pub unsafe extern "C" fn rsn_daemon_config_validate(
+    dto: &DaemonConfigDto,
+    result: *mut ConfigIssueDto,
+    size: usize,
+) -> usize {
+    let issues = match DaemonConfig::try_from(dto) {
+        Ok(cfg) => cfg.validate(),
+        Err(e) => vec![ConfigIssue::error(e.to_string())],
+    };
+    let result = std::slice::from_raw_parts_mut(result, size);
+    for (target, issue) in result.iter_mut().zip(issues.iter()) {
+        *target = issue.into();
+    }
+    issues.len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_node_config_validate_voting(
+    dto: &NodeConfigDto,
+    voting_representatives: usize,
+    result: *mut ConfigIssueDto,
+) -> bool {
+    let Ok(cfg) = NodeConfig::try_from(dto) else {
+        return false;
+    };
+    match cfg.validate_voting(voting_representatives) {
+        Some(issue) => {
+            *result = (&issue).into();
+            true
+        }
+        None => false,
+    }
+}
+
 impl TryFrom<&DaemonConfigDto> for DaemonConfig {
     type Error = anyhow::Error;
 
@@ -0,0 +1,28 @@
+use rsnano_node::config::HintedSchedulerConfig;
+
+#[repr(C)]
+pub struct HintedSchedulerConfigDto {
+    pub enabled: bool,
+    pub check_interval_ms: u64,
+    pub hinting_threshold_percent: u32,
+}
+
+impl From<&HintedSchedulerConfigDto> for HintedSchedulerConfig {
+    fn from(value: &HintedSchedulerConfigDto) -> Self {
+        Self {
+            enabled: value.enabled,
+            check_interval_ms: value.check_interval_ms,
+            hinting_threshold_percent: value.hinting_threshold_percent,
+        }
+    }
+}
+
+impl From<&HintedSchedulerConfig> for HintedSchedulerConfigDto {
+    fn from(value: &HintedSchedulerConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            check_interval_ms: value.check_interval_ms,
+            hinting_threshold_percent: value.hinting_threshold_percent,
+        }
+    }
+}
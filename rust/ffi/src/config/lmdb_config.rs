@@ -5,6 +5,7 @@ pub struct LmdbConfigDto {
     pub sync: u8,
     pub max_databases: u32,
     pub map_size: usize,
+    pub block_height_index: bool,
 }
 
 #[no_mangle]
@@ -23,6 +24,7 @@ pub fn fill_lmdb_config_dto(dto: &mut LmdbConfigDto, config: &LmdbConfig) {
     };
     dto.max_databases = config.max_databases;
     dto.map_size = config.map_size;
+    dto.block_height_index = config.block_height_index;
 }
 
 impl From<&LmdbConfigDto> for LmdbConfig {
@@ -37,6 +39,7 @@ impl From<&LmdbConfigDto> for LmdbConfig {
             },
             max_databases: dto.max_databases,
             map_size: dto.map_size,
+            block_height_index: dto.block_height_index,
         }
     }
 }
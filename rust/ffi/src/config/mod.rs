@@ -1,6 +1,8 @@
 mod bootstrap_config;
+mod confirm_req_config;
 mod daemon_config;
 mod diagnostics_config;
+mod hinted_scheduler_config;
 mod lmdb_config;
 mod logging;
 mod network_constants;
@@ -10,9 +12,13 @@ mod node_rpc_config;
 mod opencl_config;
 mod optimistic_scheduler_config;
 mod rpc_config;
+mod socks5_proxy_config;
 mod websocket_config;
 
+pub use bootstrap_config::AccountSetsConfigDto;
+pub use confirm_req_config::ConfirmReqConfigDto;
 pub use diagnostics_config::*;
+pub use hinted_scheduler_config::HintedSchedulerConfigDto;
 pub use lmdb_config::LmdbConfigDto;
 pub use logging::*;
 pub use network_constants::*;
@@ -22,4 +28,5 @@ pub use node_rpc_config::*;
 pub use opencl_config::*;
 pub use optimistic_scheduler_config::*;
 pub use rpc_config::*;
+pub use socks5_proxy_config::Socks5ProxyConfigDto;
 pub use websocket_config::*;
@@ -2,13 +2,20 @@ use num::FromPrimitive;
 use rsnano_core::work::WorkThresholds;
 use std::{convert::TryFrom, ffi::CStr, os::raw::c_char, time::Duration};
 
-use rsnano_node::config::{test_node_port, NetworkConstants};
+use rsnano_node::{
+    config::{test_node_port, NetworkConstants, ProtocolInfo},
+    NetworkDefinition,
+};
 
-use crate::work::{fill_work_thresholds_dto, WorkThresholdsDto};
+use crate::{
+    work::{fill_work_thresholds_dto, WorkThresholdsDto},
+    StringDto,
+};
 
 #[repr(C)]
 pub struct NetworkConstantsDto {
     pub current_network: u16,
+    pub network_id: u16,
     pub work: WorkThresholdsDto,
     pub principal_weight_factor: u32,
     pub default_node_port: u16,
@@ -30,6 +37,9 @@ pub struct NetworkConstantsDto {
     pub ipv6_subnetwork_prefix_for_limiting: usize,
     pub silent_connection_tolerance_time_s: i64,
     pub vote_broadcast_interval_ms: i64,
+    pub block_broadcast_interval_ms: i64,
+    pub block_broadcast_initial_delay_ms: i64,
+    pub network_id_warn_only: bool,
     pub telemetry_request_cooldown_ms: i64,
     pub telemetry_request_interval_ms: i64,
     pub telemetry_broadcast_interval_ms: i64,
@@ -55,10 +65,11 @@ pub unsafe extern "C" fn rsn_network_constants_create(
 
 pub fn fill_network_constants_dto(dto: &mut NetworkConstantsDto, constants: &NetworkConstants) {
     dto.current_network = constants.current_network as u16;
+    dto.network_id = constants.network_id;
     fill_work_thresholds_dto(&mut dto.work, &constants.work);
-    dto.protocol_version = constants.protocol_version;
-    dto.protocol_version_min = constants.protocol_version_min;
-    dto.bootstrap_protocol_version_min = constants.bootstrap_protocol_version_min;
+    dto.protocol_version = constants.protocol.version_using;
+    dto.protocol_version_min = constants.protocol.version_min;
+    dto.bootstrap_protocol_version_min = constants.protocol.bootstrap_version_min;
     dto.principal_weight_factor = constants.principal_weight_factor;
     dto.default_node_port = constants.default_node_port;
     dto.default_rpc_port = constants.default_rpc_port;
@@ -76,6 +87,10 @@ pub fn fill_network_constants_dto(dto: &mut NetworkConstantsDto, constants: &Net
     dto.ipv6_subnetwork_prefix_for_limiting = constants.ipv6_subnetwork_prefix_for_limiting;
     dto.silent_connection_tolerance_time_s = constants.silent_connection_tolerance_time_s;
     dto.vote_broadcast_interval_ms = constants.vote_broadcast_interval_ms;
+    dto.block_broadcast_interval_ms = constants.block_broadcast_interval.as_millis() as i64;
+    dto.block_broadcast_initial_delay_ms =
+        constants.block_broadcast_initial_delay.as_millis() as i64;
+    dto.network_id_warn_only = constants.network_id_warn_only;
     dto.telemetry_request_cooldown_ms = constants.telemetry_request_cooldown_ms;
     dto.telemetry_request_interval_ms = constants.telemetry_request_interval_ms;
     dto.telemetry_broadcast_interval_ms = constants.telemetry_broadcast_interval_ms;
@@ -138,6 +153,25 @@ pub unsafe extern "C" fn rsn_network_constants_active_network_set_str(
     }
 }
 
+/// Loads a network definition file and activates it. Returns false and sets `error` if loading failed.
+#[no_mangle]
+pub unsafe extern "C" fn rsn_network_definition_load(
+    path: *const c_char,
+    error: *mut StringDto,
+) -> bool {
+    let path = CStr::from_ptr(path).to_string_lossy();
+    match NetworkDefinition::load(path.as_ref()) {
+        Ok(definition) => {
+            definition.activate();
+            true
+        }
+        Err(e) => {
+            *error = format!("{:#}", e).into();
+            false
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn rsn_test_node_port() -> u16 {
     test_node_port()
@@ -160,9 +194,12 @@ impl TryFrom<&NetworkConstantsDto> for NetworkConstants {
             principal_weight_factor: value.principal_weight_factor,
             current_network: FromPrimitive::from_u16(value.current_network)
                 .ok_or_else(|| anyhow!("invalid current network"))?,
-            protocol_version: value.protocol_version,
-            protocol_version_min: value.protocol_version_min,
-            bootstrap_protocol_version_min: value.bootstrap_protocol_version_min,
+            network_id: value.network_id,
+            protocol: ProtocolInfo {
+                version_using: value.protocol_version,
+                version_min: value.protocol_version_min,
+                bootstrap_version_min: value.bootstrap_protocol_version_min,
+            },
             default_node_port: value.default_node_port,
             default_rpc_port: value.default_rpc_port,
             default_ipc_port: value.default_ipc_port,
@@ -179,6 +216,13 @@ impl TryFrom<&NetworkConstantsDto> for NetworkConstants {
             ipv6_subnetwork_prefix_for_limiting: value.ipv6_subnetwork_prefix_for_limiting,
             silent_connection_tolerance_time_s: value.silent_connection_tolerance_time_s,
             vote_broadcast_interval_ms: value.vote_broadcast_interval_ms,
+            block_broadcast_interval: Duration::from_millis(
+                value.block_broadcast_interval_ms as u64,
+            ),
+            block_broadcast_initial_delay: Duration::from_millis(
+                value.block_broadcast_initial_delay_ms as u64,
+            ),
+            network_id_warn_only: value.network_id_warn_only,
             telemetry_request_cooldown_ms: value.telemetry_request_cooldown_ms,
             telemetry_request_interval_ms: value.telemetry_request_interval_ms,
             telemetry_broadcast_interval_ms: value.telemetry_broadcast_interval_ms,
@@ -19,18 +19,20 @@ use super::{
     bootstrap_config::BootstrapAscendingConfigDto,
     fill_logging_dto, fill_txn_tracking_config_dto, fill_websocket_config_dto,
     lmdb_config::{fill_lmdb_config_dto, LmdbConfigDto},
-    LoggingDto, TxnTrackingConfigDto,
+    ConfirmReqConfigDto, HintedSchedulerConfigDto, LoggingDto, Socks5ProxyConfigDto,
+    TxnTrackingConfigDto,
 };
 
 #[repr(C)]
 pub struct NodeConfigDto {
     pub peering_port: u16,
     pub optimistic_scheduler: OptimisticSchedulerConfigDto,
+    pub hinted_scheduler: HintedSchedulerConfigDto,
+    pub confirm_req: ConfirmReqConfigDto,
     pub peering_port_defined: bool,
     pub bootstrap_fraction_numerator: u32,
     pub receive_minimum: [u8; 16],
     pub online_weight_minimum: [u8; 16],
-    pub election_hint_weight_percent: u32,
     pub password_fanout: u32,
     pub io_threads: u32,
     pub network_threads: u32,
@@ -45,11 +47,29 @@ pub struct NodeConfigDto {
     pub bootstrap_frontier_request_count: u32,
     pub block_processor_batch_max_time_ms: i64,
     pub allow_local_peers: bool,
+    pub allow_ipv4_peers: bool,
+    pub allow_ipv6_peers: bool,
+    pub socks5_proxy: Socks5ProxyConfigDto,
+    pub max_peers_per_ip: usize,
+    pub max_peers_per_subnetwork: usize,
+    pub peer_history_cutoff_time_s: i64,
+    pub min_peer_count: usize,
     pub vote_minimum: [u8; 16],
     pub vote_generator_delay_ms: i64,
     pub vote_generator_threshold: u32,
     block_process_timeout_s: i64,
     pub unchecked_cutoff_time_s: i64,
+    pub max_unchecked_blocks: usize,
+    pub max_rollback_depth: usize,
+    pub reject_confirmed_forks: bool,
+    pub max_blocks_per_account_per_batch: usize,
+    pub max_forks_per_root: usize,
+    pub receivable_cache: bool,
+    pub free_disk_warning_mb: u64,
+    pub free_disk_pause_mb: u64,
+    pub free_disk_shutdown_mb: u64,
+    pub memory_warning_mb: u64,
+    pub work_threshold_override: u64,
     pub tcp_io_timeout_s: i64,
     pub pow_sleep_interval_ns: i64,
     pub external_address: [u8; 128],
@@ -104,6 +124,29 @@ pub struct PeerDto {
     pub port: u16,
 }
 
+/// Parses a preconfigured peer entry (`address`, `address:port`, `[ipv6]:port`).
+/// Returns false if the entry is invalid
+#[no_mangle]
+pub unsafe extern "C" fn rsn_peer_parse(
+    entry: *const u8,
+    entry_len: usize,
+    default_port: u16,
+    result: *mut PeerDto,
+) -> bool {
+    let entry = String::from_utf8_lossy(std::slice::from_raw_parts(entry, entry_len));
+    let Ok(peer) = Peer::parse_with_default_port(&entry, default_port) else {
+        return false;
+    };
+    let bytes = peer.address.as_bytes();
+    if bytes.len() > (*result).address.len() {
+        return false;
+    }
+    (*result).address[..bytes.len()].copy_from_slice(bytes);
+    (*result).address_len = bytes.len();
+    (*result).port = peer.port;
+    true
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_node_config_create(
     dto: *mut NodeConfigDto,
@@ -131,11 +174,12 @@ pub unsafe extern "C" fn rsn_node_config_create(
 pub fn fill_node_config_dto(dto: &mut NodeConfigDto, cfg: &NodeConfig) {
     dto.peering_port = cfg.peering_port.unwrap_or_default();
     dto.optimistic_scheduler = (&cfg.optimistic_scheduler).into();
+    dto.hinted_scheduler = (&cfg.hinted_scheduler).into();
+    dto.confirm_req = (&cfg.confirm_req).into();
     dto.peering_port_defined = cfg.peering_port.is_some();
     dto.bootstrap_fraction_numerator = cfg.bootstrap_fraction_numerator;
     dto.receive_minimum = cfg.receive_minimum.to_be_bytes();
     dto.online_weight_minimum = cfg.online_weight_minimum.to_be_bytes();
-    dto.election_hint_weight_percent = cfg.election_hint_weight_percent;
     dto.password_fanout = cfg.password_fanout;
     dto.io_threads = cfg.io_threads;
     dto.network_threads = cfg.network_threads;
@@ -150,10 +194,28 @@ pub fn fill_node_config_dto(dto: &mut NodeConfigDto, cfg: &NodeConfig) {
     dto.bootstrap_frontier_request_count = cfg.bootstrap_frontier_request_count;
     dto.block_processor_batch_max_time_ms = cfg.block_processor_batch_max_time_ms;
     dto.allow_local_peers = cfg.allow_local_peers;
+    dto.allow_ipv4_peers = cfg.allow_ipv4_peers;
+    dto.allow_ipv6_peers = cfg.allow_ipv6_peers;
+    dto.socks5_proxy = (&cfg.socks5_proxy).into();
+    dto.max_peers_per_ip = cfg.max_peers_per_ip;
+    dto.max_peers_per_subnetwork = cfg.max_peers_per_subnetwork;
+    dto.peer_history_cutoff_time_s = cfg.peer_history_cutoff_time_s;
+    dto.min_peer_count = cfg.min_peer_count;
     dto.vote_minimum = cfg.vote_minimum.to_be_bytes();
     dto.vote_generator_delay_ms = cfg.vote_generator_delay_ms;
     dto.vote_generator_threshold = cfg.vote_generator_threshold;
     dto.unchecked_cutoff_time_s = cfg.unchecked_cutoff_time_s;
+    dto.max_unchecked_blocks = cfg.max_unchecked_blocks;
+    dto.max_rollback_depth = cfg.max_rollback_depth;
+    dto.reject_confirmed_forks = cfg.reject_confirmed_forks;
+    dto.max_blocks_per_account_per_batch = cfg.max_blocks_per_account_per_batch;
+    dto.max_forks_per_root = cfg.max_forks_per_root;
+    dto.receivable_cache = cfg.receivable_cache;
+    dto.free_disk_warning_mb = cfg.free_disk_warning_mb;
+    dto.free_disk_pause_mb = cfg.free_disk_pause_mb;
+    dto.free_disk_shutdown_mb = cfg.free_disk_shutdown_mb;
+    dto.memory_warning_mb = cfg.memory_warning_mb;
+    dto.work_threshold_override = cfg.work_threshold_override;
     dto.block_process_timeout_s = cfg.block_process_timeout_s;
     dto.tcp_io_timeout_s = cfg.tcp_io_timeout_s;
     dto.pow_sleep_interval_ns = cfg.pow_sleep_interval_ns;
@@ -301,10 +363,11 @@ impl TryFrom<&NodeConfigDto> for NodeConfig {
                 None
             },
             optimistic_scheduler: (&value.optimistic_scheduler).into(),
+            hinted_scheduler: (&value.hinted_scheduler).into(),
+            confirm_req: (&value.confirm_req).into(),
             bootstrap_fraction_numerator: value.bootstrap_fraction_numerator,
             receive_minimum: Amount::from_be_bytes(value.receive_minimum),
             online_weight_minimum: Amount::from_be_bytes(value.online_weight_minimum),
-            election_hint_weight_percent: value.election_hint_weight_percent,
             password_fanout: value.password_fanout,
             io_threads: value.io_threads,
             network_threads: value.network_threads,
@@ -319,10 +382,28 @@ impl TryFrom<&NodeConfigDto> for NodeConfig {
             bootstrap_frontier_request_count: value.bootstrap_frontier_request_count,
             block_processor_batch_max_time_ms: value.block_processor_batch_max_time_ms,
             allow_local_peers: value.allow_local_peers,
+            allow_ipv4_peers: value.allow_ipv4_peers,
+            allow_ipv6_peers: value.allow_ipv6_peers,
+            socks5_proxy: (&value.socks5_proxy).into(),
+            max_peers_per_ip: value.max_peers_per_ip,
+            max_peers_per_subnetwork: value.max_peers_per_subnetwork,
+            peer_history_cutoff_time_s: value.peer_history_cutoff_time_s,
+            min_peer_count: value.min_peer_count,
             vote_minimum: Amount::from_be_bytes(value.vote_minimum),
             vote_generator_delay_ms: value.vote_generator_delay_ms,
             vote_generator_threshold: value.vote_generator_threshold,
             unchecked_cutoff_time_s: value.unchecked_cutoff_time_s,
+            max_unchecked_blocks: value.max_unchecked_blocks,
+            max_rollback_depth: value.max_rollback_depth,
+            reject_confirmed_forks: value.reject_confirmed_forks,
+            max_blocks_per_account_per_batch: value.max_blocks_per_account_per_batch,
+            max_forks_per_root: value.max_forks_per_root,
+            receivable_cache: value.receivable_cache,
+            free_disk_warning_mb: value.free_disk_warning_mb,
+            free_disk_pause_mb: value.free_disk_pause_mb,
+            free_disk_shutdown_mb: value.free_disk_shutdown_mb,
+            memory_warning_mb: value.memory_warning_mb,
+            work_threshold_override: value.work_threshold_override,
             block_process_timeout_s: value.block_process_timeout_s,
             tcp_io_timeout_s: value.tcp_io_timeout_s,
             pow_sleep_interval_ns: value.pow_sleep_interval_ns,
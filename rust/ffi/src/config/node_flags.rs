@@ -5,7 +5,7 @@ use std::{
 };
 
 use crate::{ledger::GenerateCacheHandle, StringDto};
-use rsnano_node::config::NodeFlags;
+use rsnano_node::{config::NodeFlags, vote_cache::VoteCacheEvictionPolicy};
 
 pub struct NodeFlagsHandle(pub Arc<Mutex<NodeFlags>>);
 
@@ -132,6 +132,7 @@ pub struct NodeFlagsDto {
     pub disable_unchecked_drop: bool,
     pub disable_providing_telemetry_metrics: bool,
     pub disable_ongoing_telemetry_requests: bool,
+    pub disable_telemetry_upgrade_advisory: bool,
     pub disable_block_processor_unchecked_deletion: bool,
     pub disable_block_processor_republishing: bool,
     pub allow_bootstrap_peers_duplicates: bool,
@@ -143,12 +144,16 @@ pub struct NodeFlagsDto {
     pub fast_bootstrap: bool,
     pub read_only: bool,
     pub disable_connection_cleanup: bool,
+    pub enable_loopback_channel: bool,
     pub inactive_node: bool,
     pub block_processor_batch_size: usize,
     pub block_processor_full_size: usize,
     pub block_processor_verification_size: usize,
     pub inactive_votes_cache_size: usize,
+    pub inactive_votes_cache_eviction: u8,
+    pub inactive_votes_cache_retain: bool,
     pub vote_processor_capacity: usize,
+    pub block_trace_sample_interval: u64,
     pub bootstrap_interval: usize,
 }
 
@@ -176,6 +181,7 @@ pub unsafe extern "C" fn rsn_node_flags_get(
     result.disable_unchecked_drop = lock.disable_unchecked_drop;
     result.disable_providing_telemetry_metrics = lock.disable_providing_telemetry_metrics;
     result.disable_ongoing_telemetry_requests = lock.disable_ongoing_telemetry_requests;
+    result.disable_telemetry_upgrade_advisory = lock.disable_telemetry_upgrade_advisory;
     result.disable_block_processor_unchecked_deletion =
         lock.disable_block_processor_unchecked_deletion;
     result.disable_block_processor_republishing = lock.disable_block_processor_republishing;
@@ -188,12 +194,16 @@ pub unsafe extern "C" fn rsn_node_flags_get(
     result.fast_bootstrap = lock.fast_bootstrap;
     result.read_only = lock.read_only;
     result.disable_connection_cleanup = lock.disable_connection_cleanup;
+    result.enable_loopback_channel = lock.enable_loopback_channel;
     result.inactive_node = lock.inactive_node;
     result.block_processor_batch_size = lock.block_processor_batch_size;
     result.block_processor_full_size = lock.block_processor_full_size;
     result.block_processor_verification_size = lock.block_processor_verification_size;
     result.inactive_votes_cache_size = lock.inactive_votes_cache_size;
+    result.inactive_votes_cache_eviction = lock.inactive_votes_cache_eviction as u8;
+    result.inactive_votes_cache_retain = lock.inactive_votes_cache_retain;
     result.vote_processor_capacity = lock.vote_processor_capacity;
+    result.block_trace_sample_interval = lock.block_trace_sample_interval;
     result.bootstrap_interval = lock.bootstrap_interval;
 }
 
@@ -221,6 +231,7 @@ pub unsafe extern "C" fn rsn_node_flags_set(
     lock.disable_unchecked_drop = flags.disable_unchecked_drop;
     lock.disable_providing_telemetry_metrics = flags.disable_providing_telemetry_metrics;
     lock.disable_ongoing_telemetry_requests = flags.disable_ongoing_telemetry_requests;
+    lock.disable_telemetry_upgrade_advisory = flags.disable_telemetry_upgrade_advisory;
     lock.disable_block_processor_unchecked_deletion =
         flags.disable_block_processor_unchecked_deletion;
     lock.disable_block_processor_republishing = flags.disable_block_processor_republishing;
@@ -233,11 +244,16 @@ pub unsafe extern "C" fn rsn_node_flags_set(
     lock.fast_bootstrap = flags.fast_bootstrap;
     lock.read_only = flags.read_only;
     lock.disable_connection_cleanup = flags.disable_connection_cleanup;
+    lock.enable_loopback_channel = flags.enable_loopback_channel;
     lock.inactive_node = flags.inactive_node;
     lock.block_processor_batch_size = flags.block_processor_batch_size;
     lock.block_processor_full_size = flags.block_processor_full_size;
     lock.block_processor_verification_size = flags.block_processor_verification_size;
     lock.inactive_votes_cache_size = flags.inactive_votes_cache_size;
+    lock.inactive_votes_cache_eviction =
+        VoteCacheEvictionPolicy::from_u8(flags.inactive_votes_cache_eviction).unwrap_or_default();
+    lock.inactive_votes_cache_retain = flags.inactive_votes_cache_retain;
     lock.vote_processor_capacity = flags.vote_processor_capacity;
+    lock.block_trace_sample_interval = flags.block_trace_sample_interval;
     lock.bootstrap_interval = flags.bootstrap_interval;
 }
@@ -0,0 +1,36 @@
+use rsnano_node::config::Socks5ProxyConfig;
+
+#[repr(C)]
+pub struct Socks5ProxyConfigDto {
+    pub address: [u8; 128],
+    pub address_len: usize,
+    pub port: u16,
+    pub fallback_to_direct: bool,
+}
+
+impl From<&Socks5ProxyConfigDto> for Socks5ProxyConfig {
+    fn from(value: &Socks5ProxyConfigDto) -> Self {
+        let len = value.address_len.min(value.address.len());
+        Self {
+            address: String::from_utf8_lossy(&value.address[..len]).to_string(),
+            port: value.port,
+            fallback_to_direct: value.fallback_to_direct,
+        }
+    }
+}
+
+impl From<&Socks5ProxyConfig> for Socks5ProxyConfigDto {
+    fn from(value: &Socks5ProxyConfig) -> Self {
+        let mut address = [0; 128];
+        // Longer addresses are rejected when the config is loaded
+        let bytes = value.address.as_bytes();
+        let len = bytes.len().min(address.len());
+        address[..len].copy_from_slice(&bytes[..len]);
+        Self {
+            address,
+            address_len: len,
+            port: value.port,
+            fallback_to_direct: value.fallback_to_direct,
+        }
+    }
+}
@@ -9,6 +9,8 @@ pub struct BlockDetailsDto {
     pub is_send: bool,
     pub is_receive: bool,
     pub is_epoch: bool,
+    pub is_pruned_source: bool,
+    pub is_self_send: bool,
 }
 
 #[no_mangle]
@@ -62,6 +64,8 @@ unsafe fn set_block_details_dto(details: &BlockDetails, result: *mut BlockDetail
     (*result).is_send = details.is_send;
     (*result).is_receive = details.is_receive;
     (*result).is_epoch = details.is_epoch;
+    (*result).is_pruned_source = details.is_pruned_source;
+    (*result).is_self_send = details.is_self_send;
 }
 
 #[repr(C)]
@@ -167,7 +171,9 @@ impl TryFrom<&BlockDetailsDto> for BlockDetails {
 
     fn try_from(value: &BlockDetailsDto) -> Result<Self, Self::Error> {
         let epoch = Epoch::try_from(value.epoch)?;
-        let details = BlockDetails::new(epoch, value.is_send, value.is_receive, value.is_epoch);
+        let mut details = BlockDetails::new(epoch, value.is_send, value.is_receive, value.is_epoch);
+        details.is_pruned_source = value.is_pruned_source;
+        details.is_self_send = value.is_self_send;
         Ok(details)
     }
 }
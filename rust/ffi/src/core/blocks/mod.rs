@@ -24,7 +24,7 @@ use rsnano_core::{
 pub use send_block::*;
 pub use state_block::*;
 
-use crate::{utils::FfiStream, FfiPropertyTreeReader, FfiPropertyTreeWriter};
+use crate::{copy_amount_bytes, utils::FfiStream, FfiPropertyTreeReader, FfiPropertyTreeWriter};
 use num::FromPrimitive;
 use rsnano_node::utils::deserialize_block;
 
@@ -168,6 +168,26 @@ pub unsafe extern "C" fn rsn_block_previous(handle: &BlockHandle, result: *mut [
     (*result) = *(handle).block.read().unwrap().previous().as_bytes();
 }
 
+/// Writes the absolute balance change to `amount` and returns -1 for a decrease,
+/// 0 if the balance is unchanged and 1 for an increase.
+/// `handle_prev` is null for the first block of an account
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_balance_delta(
+    handle_prev: *const BlockHandle,
+    handle_cur: *const BlockHandle,
+    amount: *mut u8,
+) -> i8 {
+    let current = (*handle_cur).block.read().unwrap();
+    let delta = if handle_prev.is_null() {
+        current.balance_delta(None)
+    } else {
+        let previous = (*handle_prev).block.read().unwrap();
+        current.balance_delta(Some(&previous))
+    };
+    copy_amount_bytes(delta.amount(), amount);
+    delta.signum()
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_block_equals(a: *const BlockHandle, b: *const BlockHandle) -> bool {
     let a_guard = (*a).block.read().unwrap();
@@ -2,12 +2,19 @@ use crate::{
     copy_account_bytes, copy_amount_bytes, copy_hash_bytes, copy_link_bytes, copy_root_bytes,
     core::{copy_block_array_dto, AccountInfoHandle, BlockArrayDto, BlockHandle},
     ledger::{GenerateCacheHandle, LedgerCacheHandle, LedgerConstantsDto},
-    StatHandle, StringDto,
+    utils::ContextWrapper,
+    ConfirmationHeightInfoDto, FfiPropertyTreeWriter, StatHandle, StringDto, VoidPointerCallback,
+};
+use rsnano_core::{
+    Account, Amount, BlockEnum, BlockHash, BlockSubType, Epoch, KeyPair, Link, QualifiedRoot,
+    RawKey, Root,
+};
+use rsnano_ledger::{
+    BootstrapWeights, EpochUpgrader, Ledger, ProcessResult, ReceivableDetails, SubtypeMismatch,
 };
-use rsnano_core::{Account, Amount, BlockHash, Epoch, Link, QualifiedRoot};
-use rsnano_ledger::{Ledger, ProcessResult};
 use rsnano_node::stats::LedgerStats;
 use std::{
+    ffi::{c_char, c_void, CStr},
     ops::Deref,
     ptr::null_mut,
     sync::{Arc, RwLock},
@@ -15,9 +22,15 @@ use std::{
 
 use num_traits::FromPrimitive;
 
-use super::lmdb::{LmdbStoreHandle, TransactionHandle};
+use super::{
+    ledger_observer::{
+        FfiLedgerObserver, RepWeightCallbacks, RepWeightThresholdCrossedCallback,
+        RepresentativeChangedCallback,
+    },
+    lmdb::{LmdbStoreHandle, TransactionHandle},
+};
 
-pub struct LedgerHandle(pub Arc<Ledger>);
+pub struct LedgerHandle(pub Arc<Ledger>, Arc<FfiLedgerObserver>);
 
 impl Deref for LedgerHandle {
     type Target = Arc<Ledger>;
@@ -42,9 +55,38 @@ pub unsafe extern "C" fn rsn_ledger_create(
     )
     .unwrap();
 
-    ledger.set_observer(Arc::new(LedgerStats::new(stats)));
+    let observer = Arc::new(FfiLedgerObserver::new(LedgerStats::new(stats)));
+    ledger.set_observer(observer.clone());
 
-    Box::into_raw(Box::new(LedgerHandle(Arc::new(ledger))))
+    Box::into_raw(Box::new(LedgerHandle(Arc::new(ledger), observer)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_set_rep_weight_observer(
+    handle: *mut LedgerHandle,
+    context: *mut c_void,
+    representative_changed: RepresentativeChangedCallback,
+    threshold_crossed: RepWeightThresholdCrossedCallback,
+    delete_context: VoidPointerCallback,
+) {
+    (*handle).1.set_rep_weight_callbacks(RepWeightCallbacks {
+        context: ContextWrapper::new(context, delete_context),
+        representative_changed,
+        threshold_crossed,
+    });
+}
+
+/// `thresholds` points to `count` big endian amounts
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_set_rep_weight_thresholds(
+    handle: *mut LedgerHandle,
+    thresholds: *const u8,
+    count: usize,
+) {
+    let thresholds = (0..count)
+        .map(|i| Amount::from_ptr(thresholds.add(i * 16)))
+        .collect();
+    (*handle).0.cache.rep_weights.set_thresholds(thresholds);
 }
 
 #[no_mangle]
@@ -75,6 +117,63 @@ pub unsafe extern "C" fn rsn_ledger_set_bootstrap_weight_max_blocks(
     (*handle).0.set_bootstrap_weight_max_blocks(max)
 }
 
+/// Loads bootstrap weights in the format of the `rep_weights_*.bin` files.
+/// Returns false if the data couldn't be parsed
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_load_bootstrap_weights(
+    handle: *mut LedgerHandle,
+    data: *const u8,
+    len: usize,
+) -> bool {
+    let bytes = std::slice::from_raw_parts(data, len);
+    match BootstrapWeights::from_bytes(bytes) {
+        Ok(weights) => {
+            (*handle).0.load_bootstrap_weights(weights);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Writes the current representative weights of at least `min_weight` to `path`,
+/// in the format of the `rep_weights_*.bin` files. Returns false if the file couldn't be written
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_export_bootstrap_weights(
+    handle: *mut LedgerHandle,
+    min_weight: *const u8,
+    path: *const c_char,
+) -> bool {
+    let weights = (*handle)
+        .0
+        .export_bootstrap_weights(Amount::from_ptr(min_weight));
+    let path = CStr::from_ptr(path).to_string_lossy().to_string();
+    std::fs::write(path, weights.to_bytes()).is_ok()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_set_max_rollback_depth(
+    handle: *mut LedgerHandle,
+    max_depth: usize,
+) {
+    (*handle).0.set_max_rollback_depth(max_depth)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_recover_rollback_journal(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+) -> usize {
+    (*handle).0.recover_rollback_journal((*txn).as_write_txn())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_set_reject_confirmed_forks(
+    handle: *mut LedgerHandle,
+    reject: bool,
+) {
+    (*handle).0.set_reject_confirmed_forks(reject)
+}
+
 #[repr(C)]
 pub struct BootstrapWeightsItem {
     pub account: [u8; 32],
@@ -202,6 +301,239 @@ pub unsafe extern "C" fn rsn_ledger_account_receivable(
     copy_amount_bytes(balance, result);
 }
 
+#[repr(C)]
+pub struct AccountDetailsDto {
+    pub confirmation_height: ConfirmationHeightInfoDto,
+    pub representative_block: [u8; 32],
+    pub confirmed_balance: [u8; 16],
+    pub confirmed_representative: [u8; 32],
+    pub receivable: [u8; 16],
+    pub confirmed_receivable: [u8; 16],
+}
+
+/// Returns false if the account is not opened
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_account_details(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    account: *const u8,
+    receivable_details: u8,
+    result: *mut AccountDetailsDto,
+) -> bool {
+    let receivable_details = match receivable_details {
+        0 => ReceivableDetails::Skip,
+        1 => ReceivableDetails::Total,
+        2 => ReceivableDetails::TotalAndConfirmed,
+        _ => panic!("invalid receivable details: {}", receivable_details),
+    };
+    let Some(details) = (*handle).0.account_details(
+        (*txn).as_txn(),
+        &Account::from_ptr(account),
+        receivable_details,
+    ) else {
+        return false;
+    };
+    let result = &mut *result;
+    result.confirmation_height = details.confirmation_height.into();
+    result.representative_block = *details.representative_block.as_bytes();
+    result.confirmed_balance = details.confirmed_balance.to_be_bytes();
+    result.confirmed_representative = *details.confirmed_representative.as_bytes();
+    result.receivable = details.receivable.to_be_bytes();
+    result.confirmed_receivable = details.confirmed_receivable.to_be_bytes();
+    true
+}
+
+#[repr(C)]
+pub struct EpochUpgradeProgressDto {
+    pub epoch_0: u64,
+    pub epoch_1: u64,
+    pub epoch_2: u64,
+    pub unopened_with_receivables: u64,
+    pub unupgraded: u64,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_epoch_upgrade_progress(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    result: *mut EpochUpgradeProgressDto,
+) {
+    let progress = (*handle).0.epoch_upgrade_progress((*txn).as_txn());
+    let count = |epoch| {
+        progress
+            .accounts_per_epoch
+            .get(&epoch)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let result = &mut *result;
+    result.epoch_0 = count(Epoch::Epoch0);
+    result.epoch_1 = count(Epoch::Epoch1);
+    result.epoch_2 = count(Epoch::Epoch2);
+    result.unopened_with_receivables = progress.unopened_with_receivables;
+    result.unupgraded = progress.unupgraded;
+}
+
+/// Generates the work for a root with the given difficulty. Returns false if the work generation was stopped
+pub type EpochUpgradeWorkCallback =
+    unsafe extern "C" fn(*mut c_void, *const u8, u64, *mut u64) -> bool;
+/// Passes an epoch block through the block processor and returns its process result
+pub type EpochUpgradeProcessCallback = unsafe extern "C" fn(*mut c_void, *mut BlockHandle) -> u8;
+
+struct EpochUpgradeContext(*mut c_void);
+unsafe impl Send for EpochUpgradeContext {}
+unsafe impl Sync for EpochUpgradeContext {}
+
+/// Upgrades at most `count_limit` accounts and returns the number of upgraded accounts,
+/// or -1 if `prv_key` isn't the signer of the epoch
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_epoch_upgrade(
+    handle: *mut LedgerHandle,
+    prv_key: *const u8,
+    epoch: u8,
+    count_limit: u64,
+    threads: u64,
+    generate_work: EpochUpgradeWorkCallback,
+    process: EpochUpgradeProcessCallback,
+    context: *mut c_void,
+) -> i64 {
+    let Ok(signer) = KeyPair::from_priv_key_bytes(RawKey::from_ptr(prv_key).as_bytes()) else {
+        return -1;
+    };
+    let Some(epoch) = Epoch::from_u8(epoch) else {
+        return -1;
+    };
+    let context = EpochUpgradeContext(context);
+    let context = &context;
+    let upgrader = EpochUpgrader::new(
+        &(*handle).0,
+        signer,
+        epoch,
+        move |root, difficulty| {
+            let mut work = 0;
+            generate_work(context.0, root.as_bytes().as_ptr(), difficulty, &mut work)
+                .then_some(work)
+        },
+        move |block| {
+            let block_handle =
+                Box::into_raw(Box::new(BlockHandle::new(Arc::new(RwLock::new(block)))));
+            let result = process(context.0, block_handle);
+            drop(Box::from_raw(block_handle));
+            match ProcessResult::from_u8(result) {
+                Some(ProcessResult::Progress) => Ok(()),
+                Some(result) => Err(result),
+                None => Err(ProcessResult::Old),
+            }
+        },
+    );
+    let Ok(mut upgrader) = upgrader else {
+        return -1;
+    };
+    upgrader.set_threads(threads as usize);
+    upgrader.upgrade(count_limit as usize) as i64
+}
+
+#[repr(C)]
+pub struct BlockInfoDto {
+    pub account: [u8; 32],
+    pub amount: [u8; 16],
+    /// false if the amount is unknown, because the previous block was pruned
+    pub amount_valid: bool,
+    pub balance: [u8; 16],
+    pub height: u64,
+    pub local_timestamp: u64,
+    pub successor: [u8; 32],
+    /// 0 = send, 1 = receive, 2 = open, 3 = change, 4 = epoch. Only valid if has_subtype is true
+    pub subtype: u8,
+    pub has_subtype: bool,
+    pub confirmed: bool,
+}
+
+/// Returns false if the block doesn't exist
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_block_info(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    hash: *const u8,
+    result: *mut BlockInfoDto,
+) -> bool {
+    let Some(info) = (*handle)
+        .0
+        .block_info((*txn).as_txn(), &BlockHash::from_ptr(hash))
+    else {
+        return false;
+    };
+    let result = &mut *result;
+    result.account = *info.account.as_bytes();
+    result.amount = info.amount.unwrap_or_default().to_be_bytes();
+    result.amount_valid = info.amount.is_some();
+    result.balance = info.balance.to_be_bytes();
+    result.height = info.height;
+    result.local_timestamp = info.local_timestamp;
+    result.successor = *info.successor.as_bytes();
+    result.subtype = info.subtype.map(|s| s as u8).unwrap_or_default();
+    result.has_subtype = info.subtype.is_some();
+    result.confirmed = info.confirmed;
+    true
+}
+
+/// Writes up to `max` history entries of the account as JSON lines into `result`.
+/// `head` may be null to start at the frontier of the account.
+/// Returns true and writes the pagination token into `next` if there are more entries
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_account_history(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    account: *const u8,
+    head: *const u8,
+    max: usize,
+    result: *mut StringDto,
+    next: *mut u8,
+) -> bool {
+    let head = if head.is_null() {
+        None
+    } else {
+        Some(BlockHash::from_ptr(head))
+    };
+    let mut buffer = Vec::new();
+    let next_hash = match (*handle).0.account_history_page(
+        (*txn).as_txn(),
+        &Account::from_ptr(account),
+        head.as_ref(),
+        max,
+        &mut buffer,
+    ) {
+        Ok(next_hash) => next_hash,
+        Err(_) => {
+            buffer.clear();
+            None
+        }
+    };
+    *result = String::from_utf8(buffer).unwrap_or_default().into();
+    match next_hash {
+        Some(hash) => {
+            copy_hash_bytes(hash, next);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns false if the send block wasn't received yet or if it is no send block
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_receive_hash_for_send(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    send_hash: *const u8,
+    result: *mut u8,
+) -> bool {
+    let receive_hash = (*handle)
+        .0
+        .receive_hash_for_send((*txn).as_txn(), &BlockHash::from_ptr(send_hash));
+    copy_hash_bytes(receive_hash.unwrap_or_default(), result);
+    receive_hash.is_some()
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_ledger_block_confirmed(
     handle: *mut LedgerHandle,
@@ -246,6 +578,23 @@ pub unsafe extern "C" fn rsn_ledger_block_text(
     }
 }
 
+/// Writes one line per problem, each followed by a repair hint. Empty if the ledger is consistent
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_check_integrity(
+    handle: *mut LedgerHandle,
+    generate_cache: *mut GenerateCacheHandle,
+    result: *mut StringDto,
+) {
+    let report = (*handle)
+        .0
+        .check_integrity(&*generate_cache)
+        .iter()
+        .map(|problem| format!("{}. {}", problem, problem.repair_hint()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    *result = report.into();
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_ledger_is_send(
     handle: *mut LedgerHandle,
@@ -309,6 +658,30 @@ pub unsafe extern "C" fn rsn_ledger_weight(
     copy_amount_bytes(weight, result);
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_weight_exact(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    representative: *const u8,
+    result: *mut u8,
+) {
+    let weight = (*handle)
+        .0
+        .weight_exact((*txn).as_txn(), &Account::from_ptr(representative));
+    copy_amount_bytes(weight, result);
+}
+
+/// Computes the checksum from the store. The ledger cache contains the same value
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_checksum(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    result: *mut u8,
+) {
+    let checksum = (*handle).0.ledger_checksum((*txn).as_txn());
+    std::slice::from_raw_parts_mut(result, 32).copy_from_slice(checksum.as_bytes());
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_ledger_account(
     handle: *mut LedgerHandle,
@@ -416,6 +789,68 @@ pub unsafe extern "C" fn rsn_ledger_is_epoch_link(
     (*handle).0.is_epoch_link(&Link::from_ptr(link))
 }
 
+/// Writes the subtype derived from the ledger into `result`. Returns false if the block is
+/// not a state block or its previous block is unknown
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_state_block_subtype(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    block: *const BlockHandle,
+    result: *mut u8,
+) -> bool {
+    let block = (*block).block.read().unwrap();
+    let BlockEnum::State(state) = &*block else {
+        return false;
+    };
+    match (*handle).0.state_block_subtype((*txn).as_txn(), state) {
+        Some(subtype) => {
+            *result = block_subtype_to_u8(subtype);
+            true
+        }
+        None => false,
+    }
+}
+
+fn block_subtype_to_u8(subtype: BlockSubType) -> u8 {
+    match subtype {
+        BlockSubType::Send => 0,
+        BlockSubType::Receive => 1,
+        BlockSubType::Open => 2,
+        BlockSubType::Change => 3,
+        BlockSubType::Epoch => 4,
+    }
+}
+
+/// Checks a state block against the subtype given as text.
+/// Returns 0 on success, 1 if the subtype is invalid or the block is not a state block,
+/// 2 if the previous block is unknown and 3, 4 and 5 if the balance, previous or epoch link
+/// don't fit the subtype
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_check_subtype(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    block: *const BlockHandle,
+    subtype: *const c_char,
+) -> u8 {
+    let Ok(subtype) = CStr::from_ptr(subtype)
+        .to_string_lossy()
+        .parse::<BlockSubType>()
+    else {
+        return 1;
+    };
+    let block = (*block).block.read().unwrap();
+    let BlockEnum::State(state) = &*block else {
+        return 1;
+    };
+    match (*handle).0.check_subtype((*txn).as_txn(), state, subtype) {
+        Ok(()) => 0,
+        Err(SubtypeMismatch::GapPrevious) => 2,
+        Err(SubtypeMismatch::Balance) => 3,
+        Err(SubtypeMismatch::Previous) => 4,
+        Err(SubtypeMismatch::EpochLink) => 5,
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_ledger_find_receive_block_by_send_hash(
     handle: *mut LedgerHandle,
@@ -606,6 +1041,17 @@ pub unsafe extern "C" fn rsn_ledger_dependents_confirmed(
         .dependents_confirmed((*txn).as_txn(), &(*block).block.read().unwrap())
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_pruning_safe_to_vote(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    root: *const u8,
+) -> bool {
+    (*handle)
+        .0
+        .pruning_safe_to_vote((*txn).as_txn(), &Root::from_ptr(root))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_ledger_representative(
     handle: *mut LedgerHandle,
@@ -626,10 +1072,30 @@ pub unsafe extern "C" fn rsn_ledger_rollback(
     hash: *const u8,
     result: *mut BlockArrayDto,
 ) -> bool {
-    match (*handle)
+    let rolled_back = (*handle)
+        .0
+        .rollback((*txn).as_write_txn(), &BlockHash::from_ptr(hash));
+    copy_rolled_back_blocks(rolled_back, result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_rollback_journaled(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    hash: *const u8,
+    result: *mut BlockArrayDto,
+) -> bool {
+    let rolled_back = (*handle)
         .0
-        .rollback((*txn).as_write_txn(), &BlockHash::from_ptr(hash))
-    {
+        .rollback_journaled((*txn).as_write_txn(), &BlockHash::from_ptr(hash));
+    copy_rolled_back_blocks(rolled_back, result)
+}
+
+unsafe fn copy_rolled_back_blocks(
+    rolled_back: anyhow::Result<Vec<BlockEnum>>,
+    result: *mut BlockArrayDto,
+) -> bool {
+    match rolled_back {
         Ok(mut block_list) => {
             let block_list = block_list
                 .drain(..)
@@ -673,3 +1139,36 @@ pub unsafe extern "C" fn rsn_ledger_process(
     };
     (*result) = res.into();
 }
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_valid_chain_prefix(
+    handle: *mut LedgerHandle,
+    txn: *mut TransactionHandle,
+    blocks: *const *const BlockHandle,
+    count: usize,
+) -> usize {
+    let blocks: Vec<BlockEnum> = std::slice::from_raw_parts(blocks, count)
+        .iter()
+        .map(|&b| (*b).block.read().unwrap().clone())
+        .collect();
+    (*handle).0.valid_chain_prefix((*txn).as_txn(), &blocks)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_stats_report(
+    handle: *mut LedgerHandle,
+    result: *mut StringDto,
+) {
+    *result = (*handle).0.stats_report().to_string().into();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_ledger_stats_report_serialize_json(
+    handle: *mut LedgerHandle,
+    ptree: *mut c_void,
+) {
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    if let Err(e) = (*handle).0.stats_report().serialize_json(&mut writer) {
+        eprintln!("ledger stats serialization failed: {:?}", e);
+    }
+}
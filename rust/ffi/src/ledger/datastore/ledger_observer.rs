@@ -0,0 +1,89 @@
+use std::{ffi::c_void, sync::Mutex};
+
+use rsnano_core::{Account, BlockEnum, BlockSubType};
+use rsnano_ledger::{LedgerObserver, RepWeightThresholdCrossed};
+use rsnano_node::stats::LedgerStats;
+
+use crate::utils::ContextWrapper;
+
+pub type RepresentativeChangedCallback =
+    unsafe extern "C" fn(*mut c_void, *const u8, *const u8, *const u8);
+pub type RepWeightThresholdCrossedCallback =
+    unsafe extern "C" fn(*mut c_void, *const u8, *const u8, *const u8, bool);
+
+pub(crate) struct RepWeightCallbacks {
+    pub context: ContextWrapper,
+    pub representative_changed: RepresentativeChangedCallback,
+    pub threshold_crossed: RepWeightThresholdCrossedCallback,
+}
+
+/// Updates the ledger stats and forwards the representative events to the C++ node
+pub(crate) struct FfiLedgerObserver {
+    stats: LedgerStats,
+    rep_weight_callbacks: Mutex<Option<RepWeightCallbacks>>,
+}
+
+impl FfiLedgerObserver {
+    pub fn new(stats: LedgerStats) -> Self {
+        Self {
+            stats,
+            rep_weight_callbacks: Mutex::new(None),
+        }
+    }
+
+    pub fn set_rep_weight_callbacks(&self, callbacks: RepWeightCallbacks) {
+        *self.rep_weight_callbacks.lock().unwrap() = Some(callbacks);
+    }
+}
+
+impl LedgerObserver for FfiLedgerObserver {
+    fn blocks_cemented(&self, cemented_count: u64) {
+        self.stats.blocks_cemented(cemented_count)
+    }
+
+    fn block_rolled_back(&self, block_type: BlockSubType) {
+        self.stats.block_rolled_back(block_type)
+    }
+
+    fn block_rolled_back2(&self, block: &BlockEnum, is_epoch: bool) {
+        self.stats.block_rolled_back2(block, is_epoch)
+    }
+
+    fn block_added(&self, block: &BlockEnum, is_epoch: bool) {
+        self.stats.block_added(block, is_epoch)
+    }
+
+    fn representative_changed(
+        &self,
+        account: &Account,
+        old_representative: &Account,
+        new_representative: &Account,
+    ) {
+        if let Some(callbacks) = self.rep_weight_callbacks.lock().unwrap().as_ref() {
+            unsafe {
+                (callbacks.representative_changed)(
+                    callbacks.context.get_context(),
+                    account.as_bytes().as_ptr(),
+                    old_representative.as_bytes().as_ptr(),
+                    new_representative.as_bytes().as_ptr(),
+                )
+            }
+        }
+    }
+
+    fn rep_weight_threshold_crossed(&self, event: &RepWeightThresholdCrossed) {
+        if let Some(callbacks) = self.rep_weight_callbacks.lock().unwrap().as_ref() {
+            let threshold = event.threshold.to_be_bytes();
+            let weight = event.weight.to_be_bytes();
+            unsafe {
+                (callbacks.threshold_crossed)(
+                    callbacks.context.get_context(),
+                    event.representative.as_bytes().as_ptr(),
+                    threshold.as_ptr(),
+                    weight.as_ptr(),
+                    event.above,
+                )
+            }
+        }
+    }
+}
@@ -39,6 +39,7 @@ pub unsafe extern "C" fn rsn_mdb_env_create(
     let options = EnvOptions {
         config,
         use_no_mem_init,
+        ..Default::default()
     };
     let path_str = CStr::from_ptr(path).to_str().unwrap();
     let path = Path::new(path_str);
@@ -69,6 +70,7 @@ pub unsafe extern "C" fn rsn_mdb_env_create2(
     let options = EnvOptions {
         config,
         use_no_mem_init,
+        ..Default::default()
     };
     let path_str = CStr::from_ptr(path).to_str().unwrap();
     let path = Path::new(path_str);
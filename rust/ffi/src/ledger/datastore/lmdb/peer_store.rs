@@ -1,9 +1,10 @@
 use std::{slice, sync::Arc};
 
 use rsnano_core::EndpointKey;
-use rsnano_store_lmdb::LmdbPeerStore;
+use rsnano_store_lmdb::{LmdbPeerStore, PeerHistory};
 
 use super::{iterator::LmdbIteratorHandle, TransactionHandle};
+use crate::transport::EndpointListHandle;
 
 pub struct LmdbPeerStoreHandle(Arc<LmdbPeerStore>);
 
@@ -24,9 +25,59 @@ pub unsafe extern "C" fn rsn_lmdb_peer_store_put(
     txn: *mut TransactionHandle,
     address: *const u8,
     port: u16,
+    last_seen: u64,
+    last_handshake: u64,
 ) {
     let endpoint = to_endpoint_key(address, port);
-    (*handle).0.put((*txn).as_write_txn(), &endpoint);
+    (*handle).0.put(
+        (*txn).as_write_txn(),
+        &endpoint,
+        &PeerHistory::new(last_seen, last_handshake),
+    );
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_peer_store_get(
+    handle: *mut LmdbPeerStoreHandle,
+    txn: *mut TransactionHandle,
+    address: *const u8,
+    port: u16,
+    last_seen: *mut u64,
+    last_handshake: *mut u64,
+) -> bool {
+    let endpoint = to_endpoint_key(address, port);
+    match (*handle).0.get((*txn).as_txn(), &endpoint) {
+        Some(history) => {
+            *last_seen = history.last_seen;
+            *last_handshake = history.last_handshake;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_peer_store_prioritized(
+    handle: *mut LmdbPeerStoreHandle,
+    txn: *mut TransactionHandle,
+    max: usize,
+) -> *mut EndpointListHandle {
+    let peers = (*handle)
+        .0
+        .prioritized((*txn).as_txn(), max)
+        .iter()
+        .map(|(endpoint, _)| endpoint.to_socket_addr())
+        .collect();
+    Box::into_raw(Box::new(EndpointListHandle(peers)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_peer_store_prune(
+    handle: *mut LmdbPeerStoreHandle,
+    txn: *mut TransactionHandle,
+    cutoff: u64,
+) -> usize {
+    (*handle).0.prune((*txn).as_write_txn(), cutoff)
 }
 
 #[no_mangle]
@@ -9,7 +9,7 @@ use std::{
 
 use rsnano_store_lmdb::{
     create_backup_file, EnvOptions, EnvironmentWrapper, LmdbConfig, LmdbStore,
-    NullTransactionTracker, TransactionTracker,
+    NullTransactionTracker, TransactionTracker, VacuumOptions,
 };
 
 use rsnano_node::{config::DiagnosticsConfig, utils::LongRunningTransactionLogger};
@@ -54,6 +54,7 @@ pub unsafe extern "C" fn rsn_lmdb_store_create(
     let options = EnvOptions {
         config,
         use_no_mem_init,
+        ..Default::default()
     };
     let path_str = CStr::from_ptr(path).to_str().unwrap();
     let path = Path::new(path_str);
@@ -224,6 +225,27 @@ pub unsafe extern "C" fn rsn_lmdb_store_copy_db(
     (*handle).0.copy_db(&path).is_ok()
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_store_vacuum(
+    handle: *mut LmdbStoreHandle,
+    target_path: *const i8,
+    backup_path: *const i8,
+    clear_peers: bool,
+) -> bool {
+    let path = PathBuf::from(CStr::from_ptr(target_path).to_str().unwrap());
+    let options = VacuumOptions {
+        clear_peers,
+        backup_path: Some(PathBuf::from(CStr::from_ptr(backup_path).to_str().unwrap())),
+    };
+    match (*handle).0.vacuum(&path, &options) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("vacuum failed: {:?}", e);
+            false
+        }
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_lmdb_store_rebuild_db(
     handle: *mut LmdbStoreHandle,
@@ -6,10 +6,10 @@ use std::{
 };
 
 use crate::{
-    copy_account_bytes, copy_public_key_bytes, copy_raw_key_bytes, wallet::kdf::KdfHandle,
-    StringDto, U256ArrayDto,
+    copy_account_bytes, copy_amount_bytes, copy_public_key_bytes, copy_raw_key_bytes,
+    wallet::kdf::KdfHandle, StringDto, U256ArrayDto,
 };
-use rsnano_core::{Account, PublicKey, RawKey};
+use rsnano_core::{Account, Amount, PublicKey, RawKey};
 use rsnano_store_lmdb::{EnvironmentWrapper, LmdbWalletStore, WalletValue};
 
 use super::{iterator::LmdbIteratorHandle, TransactionHandle};
@@ -347,6 +347,39 @@ pub unsafe extern "C" fn rsn_lmdb_wallet_store_representative_set(
         .representative_set((*txn).as_write_txn(), &Account::from_ptr(representative));
 }
 
+/// Returns false if the wallet uses the receive minimum of the node config
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_wallet_store_receive_minimum(
+    handle: *mut LmdbWalletStoreHandle,
+    txn: *mut TransactionHandle,
+    amount: *mut u8,
+) -> bool {
+    match (*handle).0.receive_minimum((*txn).as_txn()) {
+        Some(minimum) => {
+            copy_amount_bytes(minimum, amount);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Removes the override if `amount` is null
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_wallet_store_receive_minimum_set(
+    handle: *mut LmdbWalletStoreHandle,
+    txn: *mut TransactionHandle,
+    amount: *const u8,
+) {
+    let minimum = if amount.is_null() {
+        None
+    } else {
+        Some(Amount::from_ptr(amount))
+    };
+    (*handle)
+        .0
+        .receive_minimum_set((*txn).as_write_txn(), minimum);
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_lmdb_wallet_store_insert_adhoc(
     handle: *mut LmdbWalletStoreHandle,
@@ -507,3 +540,52 @@ pub unsafe extern "C" fn rsn_lmdb_wallet_store_set_password(
 pub unsafe extern "C" fn rsn_lmdb_wallet_store_is_open(handle: *mut LmdbWalletStoreHandle) -> bool {
     (*handle).0.is_open()
 }
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_wallet_store_create_from_export(
+    fanout: usize,
+    kdf: *const KdfHandle,
+    txn: *mut TransactionHandle,
+    wallet: *const c_char,
+    export: *const c_char,
+    passphrase: *const c_char,
+) -> *mut LmdbWalletStoreHandle {
+    let wallet = PathBuf::from(CStr::from_ptr(wallet).to_str().unwrap());
+    let export = CStr::from_ptr(export).to_str().unwrap();
+    let passphrase = CStr::from_ptr(passphrase).to_str().unwrap();
+    match LmdbWalletStore::new_from_export(
+        fanout,
+        (*kdf).deref().clone(),
+        (*txn).as_write_txn(),
+        &wallet,
+        export,
+        passphrase,
+    ) {
+        Ok(store) => Box::into_raw(Box::new(LmdbWalletStoreHandle(store))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_wallet_store_export(
+    handle: *mut LmdbWalletStoreHandle,
+    txn: *mut TransactionHandle,
+    passphrase: *const c_char,
+    result: *mut StringDto,
+) {
+    let passphrase = CStr::from_ptr(passphrase).to_str().unwrap();
+    (*result) = (*handle).0.export((*txn).as_txn(), passphrase).into();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_lmdb_wallet_store_write_rotated_backup(
+    handle: *mut LmdbWalletStoreHandle,
+    txn: *mut TransactionHandle,
+    path: *const c_char,
+    keep: usize,
+) {
+    let path = PathBuf::from(CStr::from_ptr(path).to_str().unwrap());
+    let _ = (*handle)
+        .0
+        .write_rotated_backup((*txn).as_txn(), &path, keep);
+}
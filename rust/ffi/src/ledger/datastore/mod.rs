@@ -1,4 +1,5 @@
 mod ledger;
+mod ledger_observer;
 pub mod lmdb;
 mod write_database_queue;
 
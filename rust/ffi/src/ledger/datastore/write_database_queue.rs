@@ -1,8 +1,13 @@
-use std::sync::Arc;
+use std::{
+    ffi::{c_char, CStr},
+    sync::Arc,
+};
 
 use num_traits::FromPrimitive;
 use rsnano_ledger::{WriteDatabaseQueue, WriteGuard, Writer};
 
+use crate::utils::ContainerInfoComponentHandle;
+
 pub struct WriteDatabaseQueueHandle(pub Arc<WriteDatabaseQueue>);
 
 #[no_mangle]
@@ -49,6 +54,17 @@ pub unsafe extern "C" fn rsn_write_database_queue_pop(
     WriteGuardHandle::new((*handle).0.pop())
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_write_database_queue_collect_container_info(
+    handle: *const WriteDatabaseQueueHandle,
+    name: *const c_char,
+) -> *mut ContainerInfoComponentHandle {
+    let container_info = (*handle)
+        .0
+        .collect_container_info(CStr::from_ptr(name).to_str().unwrap().to_owned());
+    Box::into_raw(Box::new(ContainerInfoComponentHandle(container_info)))
+}
+
 pub struct WriteGuardHandle(pub WriteGuard);
 impl WriteGuardHandle {
     pub fn new(guard: WriteGuard) -> *mut WriteGuardHandle {
@@ -95,3 +95,16 @@ pub unsafe extern "C" fn rsn_generate_cache_set_account_count(
 pub unsafe extern "C" fn rsn_generate_cache_block_count(handle: *mut GenerateCacheHandle) -> bool {
     (*handle).0.block_count
 }
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_generate_cache_receivable(handle: *mut GenerateCacheHandle) -> bool {
+    (*handle).0.receivable
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_generate_cache_set_receivable(
+    handle: *mut GenerateCacheHandle,
+    enable: bool,
+) {
+    (*handle).0.receivable = enable;
+}
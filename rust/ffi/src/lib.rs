@@ -5,6 +5,7 @@ extern crate anyhow;
 
 pub mod block_processing;
 pub mod bootstrap;
+mod bootstrap_ascending;
 mod cementation;
 mod config;
 pub mod core;
@@ -15,6 +16,7 @@ pub mod ledger;
 pub mod messages;
 mod property_tree;
 pub mod representatives;
+mod scheduler;
 mod secure;
 mod signatures;
 mod stats;
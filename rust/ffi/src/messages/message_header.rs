@@ -1,5 +1,3 @@
-use num::FromPrimitive;
-use rsnano_core::Networks;
 use std::{ffi::c_void, ops::Deref};
 
 use crate::utils::FfiStream;
@@ -59,7 +57,7 @@ pub unsafe extern "C" fn rsn_message_header_set_version_using(
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_message_header_network(handle: *mut MessageHeaderHandle) -> u16 {
-    (*handle).0.network() as u16
+    (*handle).0.network_id()
 }
 
 #[no_mangle]
@@ -67,9 +65,7 @@ pub unsafe extern "C" fn rsn_message_header_set_network(
     handle: *mut MessageHeaderHandle,
     network: u16,
 ) {
-    (*handle)
-        .0
-        .set_network(Networks::from_u16(network).unwrap())
+    (*handle).0.set_network_id(network)
 }
 
 #[no_mangle]
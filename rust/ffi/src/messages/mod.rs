@@ -4,6 +4,9 @@ pub use message_header::*;
 mod message;
 pub use message::*;
 
+mod shared_buffer;
+pub use shared_buffer::*;
+
 mod asc_pull_ack;
 mod asc_pull_req;
 mod bulk_pull;
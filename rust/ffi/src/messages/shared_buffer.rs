@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use super::MessageHandle;
+
+/// A refcounted, immutable byte buffer which can be passed between C++ and Rust without copying
+pub struct SharedBufferHandle(pub Arc<Vec<u8>>);
+
+impl SharedBufferHandle {
+    pub fn new(buffer: Arc<Vec<u8>>) -> *mut Self {
+        Box::into_raw(Box::new(Self(buffer)))
+    }
+
+    /// Takes ownership of the handle and returns the buffer
+    pub unsafe fn take(handle: *mut Self) -> Arc<Vec<u8>> {
+        Box::from_raw(handle).0
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_shared_buffer_create(
+    data: *const u8,
+    len: usize,
+) -> *mut SharedBufferHandle {
+    let bytes = if len == 0 {
+        Vec::new()
+    } else {
+        std::slice::from_raw_parts(data, len).to_vec()
+    };
+    SharedBufferHandle::new(Arc::new(bytes))
+}
+
+/// Returns a new handle which shares the same bytes
+#[no_mangle]
+pub unsafe extern "C" fn rsn_shared_buffer_clone(
+    handle: *const SharedBufferHandle,
+) -> *mut SharedBufferHandle {
+    SharedBufferHandle::new(Arc::clone(&(*handle).0))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_shared_buffer_destroy(handle: *mut SharedBufferHandle) {
+    drop(Box::from_raw(handle))
+}
+
+/// The returned pointer is valid as long as the handle lives
+#[no_mangle]
+pub unsafe extern "C" fn rsn_shared_buffer_data(handle: *const SharedBufferHandle) -> *const u8 {
+    (*handle).0.as_ptr()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_shared_buffer_len(handle: *const SharedBufferHandle) -> usize {
+    (*handle).0.len()
+}
+
+/// Serializes the message into a new shared buffer
+#[no_mangle]
+pub unsafe extern "C" fn rsn_message_to_shared_buffer(
+    handle: *mut MessageHandle,
+) -> *mut SharedBufferHandle {
+    SharedBufferHandle::new(Arc::new((*handle).to_bytes()))
+}
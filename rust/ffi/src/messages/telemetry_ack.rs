@@ -9,7 +9,10 @@ use crate::{
     copy_account_bytes, copy_hash_bytes, copy_signature_bytes, utils::FfiStream,
     NetworkConstantsDto, StringDto,
 };
-use rsnano_node::messages::{Message, TelemetryAck, TelemetryData};
+use rsnano_node::{
+    config::NetworkConstants,
+    messages::{Message, TelemetryAck, TelemetryData},
+};
 
 use super::{
     create_message_handle, create_message_handle2, downcast_message, downcast_message_mut,
@@ -375,6 +378,18 @@ pub unsafe extern "C" fn rsn_telemetry_data_validate_signature(
     (*handle).0.validate_signature()
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_telemetry_data_remove_unsupported_fields(
+    handle: *mut TelemetryDataHandle,
+    constants: *const NetworkConstantsDto,
+    peer_version: u8,
+) {
+    let constants = NetworkConstants::try_from(&*constants).unwrap();
+    (*handle)
+        .0
+        .remove_unsupported_fields(&constants.protocol, peer_version);
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_message_telemetry_ack_create(
     constants: *mut NetworkConstantsDto,
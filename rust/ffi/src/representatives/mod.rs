@@ -2,4 +2,4 @@ mod online_reps;
 mod rep_crawler;
 mod representative;
 
-pub use online_reps::OnlineRepsHandle;
+pub use online_reps::{OnlineRepsHandle, OnlineWeightSamplerHandle};
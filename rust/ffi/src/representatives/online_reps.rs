@@ -9,7 +9,7 @@ use std::time::Duration;
 
 pub struct OnlineRepsHandle {
     pub online_reps: Arc<Mutex<OnlineReps>>,
-    pub sampler: OnlineWeightSampler,
+    pub sampler: Arc<Mutex<OnlineWeightSampler>>,
 }
 
 impl Deref for OnlineRepsHandle {
@@ -42,7 +42,7 @@ pub unsafe extern "C" fn rsn_online_reps_create(
 
     let handle = OnlineRepsHandle {
         online_reps: Arc::new(Mutex::new(online_reps)),
-        sampler,
+        sampler: Arc::new(Mutex::new(sampler)),
     };
 
     Box::into_raw(Box::new(handle))
@@ -69,8 +69,11 @@ pub unsafe extern "C" fn rsn_online_reps_sample(handle: *mut OnlineRepsHandle) {
         lock.online()
     };
 
-    (*handle).sampler.sample(online);
-    let trend = (*handle).sampler.calculate_trend();
+    let trend = {
+        let mut sampler = (*handle).sampler.lock().unwrap();
+        sampler.sample(online);
+        sampler.calculate_trend()
+    };
 
     let mut lock = (*handle).online_reps.lock().unwrap();
     lock.set_trended(trend);
@@ -94,6 +97,15 @@ pub unsafe extern "C" fn rsn_online_reps_delta(handle: *mut OnlineRepsHandle, re
     copy_amount_bytes(amount, result);
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_online_reps_set_trended(
+    handle: *mut OnlineRepsHandle,
+    trended: *const u8,
+) {
+    let amount = Amount::from_ptr(trended);
+    (*handle).online_reps.lock().unwrap().set_trended(amount);
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_online_reps_set_online(
     handle: *mut OnlineRepsHandle,
@@ -132,3 +144,55 @@ pub unsafe extern "C" fn rsn_online_reps_item_count(handle: *const OnlineRepsHan
 pub unsafe extern "C" fn rsn_online_reps_item_size() -> usize {
     OnlineReps::item_size()
 }
+
+/// The sampler which is used by `rsn_online_reps_sample`
+#[no_mangle]
+pub unsafe extern "C" fn rsn_online_reps_sampler(
+    handle: *mut OnlineRepsHandle,
+) -> *mut OnlineWeightSamplerHandle {
+    Box::into_raw(Box::new(OnlineWeightSamplerHandle(Arc::clone(
+        &(*handle).sampler,
+    ))))
+}
+
+pub struct OnlineWeightSamplerHandle(Arc<Mutex<OnlineWeightSampler>>);
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_online_weight_sampler_create(
+    ledger_handle: *mut LedgerHandle,
+    online_weight_minimum: *const u8,
+    max_samples: u64,
+) -> *mut OnlineWeightSamplerHandle {
+    let mut sampler = OnlineWeightSampler::new((*ledger_handle).clone());
+    sampler.set_online_weight_minimum(Amount::from_ptr(online_weight_minimum));
+    sampler.set_max_samples(max_samples);
+    Box::into_raw(Box::new(OnlineWeightSamplerHandle(Arc::new(Mutex::new(
+        sampler,
+    )))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_online_weight_sampler_destroy(handle: *mut OnlineWeightSamplerHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_online_weight_sampler_sample(
+    handle: *mut OnlineWeightSamplerHandle,
+    online_weight: *const u8,
+) {
+    (*handle)
+        .0
+        .lock()
+        .unwrap()
+        .sample(Amount::from_ptr(online_weight));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_online_weight_sampler_calculate_trend(
+    handle: *mut OnlineWeightSamplerHandle,
+    result: *mut u8,
+) {
+    let trend = (*handle).0.lock().unwrap().calculate_trend();
+    copy_amount_bytes(trend, result);
+}
@@ -0,0 +1,96 @@
+use std::{
+    ffi::{c_char, c_void, CStr},
+    sync::{Arc, RwLock},
+};
+
+use rsnano_core::BlockHash;
+use rsnano_node::scheduler::HintedScheduler;
+
+use crate::{
+    core::BlockHandle,
+    ledger::datastore::LedgerHandle,
+    representatives::OnlineRepsHandle,
+    utils::{ContainerInfoComponentHandle, ContextWrapper},
+    vote_cache::VoteCacheHandle,
+    HintedSchedulerConfigDto, StatHandle, VoidPointerCallback,
+};
+
+pub struct HintedSchedulerHandle(HintedScheduler);
+
+pub type HintedSchedulerInsertCallback =
+    unsafe extern "C" fn(*mut c_void, *mut BlockHandle) -> bool;
+pub type HintedSchedulerActiveCallback = unsafe extern "C" fn(*mut c_void, *const u8) -> bool;
+pub type HintedSchedulerMissingBlockCallback = unsafe extern "C" fn(*mut c_void, *const u8);
+pub type HintedSchedulerVacancyCallback = unsafe extern "C" fn(*mut c_void) -> i64;
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_hinted_scheduler_create(
+    config: *const HintedSchedulerConfigDto,
+    ledger: *mut LedgerHandle,
+    vote_cache: *mut VoteCacheHandle,
+    online_reps: *mut OnlineRepsHandle,
+    stats: *mut StatHandle,
+    context: *mut c_void,
+    insert_hinted: HintedSchedulerInsertCallback,
+    election_active: HintedSchedulerActiveCallback,
+    missing_block: HintedSchedulerMissingBlockCallback,
+    vacancy: HintedSchedulerVacancyCallback,
+    delete_context: VoidPointerCallback,
+) -> *mut HintedSchedulerHandle {
+    let context = Arc::new(ContextWrapper::new(context, delete_context));
+    let insert_context = Arc::clone(&context);
+    let active_context = Arc::clone(&context);
+    let missing_context = Arc::clone(&context);
+    let vacancy_context = context;
+    Box::into_raw(Box::new(HintedSchedulerHandle(HintedScheduler::new(
+        (&*config).into(),
+        Arc::clone(&(*ledger).0),
+        Arc::clone(&(*vote_cache).0),
+        Arc::clone(&(*online_reps).online_reps),
+        Arc::clone(&(*stats).0),
+        Box::new(move |block| {
+            let block_handle = Box::into_raw(Box::new(BlockHandle::new(Arc::new(RwLock::new(
+                block.as_ref().clone(),
+            )))));
+            insert_hinted(insert_context.get_context(), block_handle)
+        }),
+        Box::new(move |hash: &BlockHash| {
+            election_active(active_context.get_context(), hash.as_bytes().as_ptr())
+        }),
+        Box::new(move |hash: &BlockHash| {
+            missing_block(missing_context.get_context(), hash.as_bytes().as_ptr());
+        }),
+        Box::new(move || vacancy(vacancy_context.get_context())),
+    ))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_hinted_scheduler_destroy(handle: *mut HintedSchedulerHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_hinted_scheduler_start(handle: *mut HintedSchedulerHandle) {
+    (*handle).0.start();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_hinted_scheduler_stop(handle: *mut HintedSchedulerHandle) {
+    (*handle).0.stop();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_hinted_scheduler_notify(handle: *mut HintedSchedulerHandle) {
+    (*handle).0.notify();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_hinted_scheduler_collect_container_info(
+    handle: *const HintedSchedulerHandle,
+    name: *const c_char,
+) -> *mut ContainerInfoComponentHandle {
+    let container_info = (*handle)
+        .0
+        .collect_container_info(CStr::from_ptr(name).to_str().unwrap().to_owned());
+    Box::into_raw(Box::new(ContainerInfoComponentHandle(container_info)))
+}
@@ -0,0 +1 @@
+mod hinted_scheduler;
@@ -1,4 +1,7 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    ffi::c_void,
+};
 
 use super::{
     bootstrap_constants::{fill_bootstrap_constants_dto, BootstrapConstantsDto},
@@ -10,7 +13,7 @@ use crate::{
     fill_network_constants_dto,
     ledger::{fill_ledger_constants_dto, LedgerConstantsDto},
     work::{fill_work_thresholds_dto, WorkThresholdsDto},
-    NetworkConstantsDto,
+    FfiPropertyTreeWriter, NetworkConstantsDto,
 };
 use num::FromPrimitive;
 use rsnano_node::NetworkParams;
@@ -42,6 +45,18 @@ pub unsafe extern "C" fn rsn_network_params_create(
     0
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_network_params_serialize_json(
+    dto: *const NetworkParamsDto,
+    ptree: *mut c_void,
+) -> bool {
+    let Ok(params) = NetworkParams::try_from(&*dto) else {
+        return false;
+    };
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    params.serialize_json(&mut writer).is_ok()
+}
+
 pub fn fill_network_params_dto(dto: &mut NetworkParamsDto, params: &NetworkParams) {
     dto.kdf_work = params.kdf_work;
     fill_work_thresholds_dto(&mut dto.work, &params.work);
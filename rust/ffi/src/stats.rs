@@ -2,18 +2,21 @@ use std::{
     ffi::{c_void, CStr},
     ops::Deref,
     sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
 use num::FromPrimitive;
+use rsnano_core::BlockHash;
 
 use rsnano_node::{
     messages::MessageType,
     stats::{
-        DetailType, Direction, FileWriter, JsonWriter, StatType, Stats, StatsConfig, StatsLogSink,
+        BlockTraceEvent, BlockTracer, ConfirmationLatency, DetailType, Direction, FileWriter,
+        JsonWriter, StatType, Stats, StatsConfig, StatsLogSink,
     },
 };
 
-use super::FfiPropertyTreeWriter;
+use super::{FfiPropertyTreeWriter, StringDto};
 
 #[repr(C)]
 pub struct StatConfigDto {
@@ -229,7 +232,198 @@ pub unsafe extern "C" fn rsn_stat_count(
     (*handle).0.count(stat_type, detail, dir)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_stat_disable_sampling(
+    handle: *mut StatHandle,
+    stat_type: u8,
+    detail: u8,
+    dir: u8,
+) {
+    let stat_type = FromPrimitive::from_u8(stat_type).unwrap();
+    let detail = FromPrimitive::from_u8(detail).unwrap();
+    let dir = FromPrimitive::from_u8(dir).unwrap();
+    (*handle).0.disable_sampling(stat_type, detail, dir);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_stat_define_histogram(
+    handle: *mut StatHandle,
+    stat_type: u8,
+    detail: u8,
+    dir: u8,
+    intervals: *const u64,
+    intervals_len: usize,
+    bin_count: u64,
+) {
+    let stat_type = FromPrimitive::from_u8(stat_type).unwrap();
+    let detail = FromPrimitive::from_u8(detail).unwrap();
+    let dir = FromPrimitive::from_u8(dir).unwrap();
+    let intervals = std::slice::from_raw_parts(intervals, intervals_len);
+    (*handle)
+        .0
+        .define_histogram(stat_type, detail, dir, intervals, bin_count);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_stat_update_histogram(
+    handle: *mut StatHandle,
+    stat_type: u8,
+    detail: u8,
+    dir: u8,
+    index: u64,
+    addend: u64,
+) {
+    let stat_type = FromPrimitive::from_u8(stat_type).unwrap();
+    let detail = FromPrimitive::from_u8(detail).unwrap();
+    let dir = FromPrimitive::from_u8(dir).unwrap();
+    (*handle)
+        .0
+        .update_histogram(stat_type, detail, dir, index, addend);
+}
+
+/// Copies up to `max_bins` bin values into `values` and returns the number of bins.
+/// Returns 0 if no histogram is defined for the given key.
+#[no_mangle]
+pub unsafe extern "C" fn rsn_stat_histogram_values(
+    handle: *mut StatHandle,
+    stat_type: u8,
+    detail: u8,
+    dir: u8,
+    values: *mut u64,
+    max_bins: usize,
+) -> usize {
+    let stat_type = FromPrimitive::from_u8(stat_type).unwrap();
+    let detail = FromPrimitive::from_u8(detail).unwrap();
+    let dir = FromPrimitive::from_u8(dir).unwrap();
+    let Some(histogram) = (*handle).0.get_histogram(stat_type, detail, dir) else {
+        return 0;
+    };
+    let bins = histogram.get_bins();
+    if values.is_null() {
+        return bins.len();
+    }
+    let values = std::slice::from_raw_parts_mut(values, max_bins);
+    for (value, bin) in values.iter_mut().zip(bins.iter()) {
+        *value = bin.value;
+    }
+    bins.len()
+}
+
 #[no_mangle]
 pub extern "C" fn rsn_message_type_to_stat_detail(message_type: u8) -> u8 {
     DetailType::from(MessageType::from_u8(message_type).unwrap()) as u8
 }
+
+pub struct ConfirmationLatencyHandle(ConfirmationLatency);
+
+#[no_mangle]
+pub extern "C" fn rsn_confirmation_latency_create() -> *mut ConfirmationLatencyHandle {
+    Box::into_raw(Box::new(ConfirmationLatencyHandle(
+        ConfirmationLatency::default(),
+    )))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_confirmation_latency_destroy(handle: *mut ConfirmationLatencyHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_confirmation_latency_block_arrived(
+    handle: *mut ConfirmationLatencyHandle,
+    hash: *const u8,
+) {
+    (*handle)
+        .0
+        .block_arrived(BlockHash::from_ptr(hash), Instant::now());
+}
+
+/// Returns the latency in milliseconds or -1 if the arrival of the block wasn't recorded
+#[no_mangle]
+pub unsafe extern "C" fn rsn_confirmation_latency_block_cemented(
+    handle: *mut ConfirmationLatencyHandle,
+    hash: *const u8,
+) -> i64 {
+    let unix_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    match (*handle)
+        .0
+        .block_cemented(&BlockHash::from_ptr(hash), Instant::now(), unix_secs)
+    {
+        Some(latency) => latency.as_millis() as i64,
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_confirmation_latency_serialize_json(
+    handle: *mut ConfirmationLatencyHandle,
+    ptree: *mut c_void,
+) {
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    if let Err(e) = (*handle).0.serialize_json(&mut writer) {
+        eprintln!("confirmation latency serialization failed: {:?}", e);
+    }
+}
+
+pub struct BlockTracerHandle(BlockTracer);
+
+#[no_mangle]
+pub extern "C" fn rsn_block_tracer_create(sample_interval: u64) -> *mut BlockTracerHandle {
+    Box::into_raw(Box::new(BlockTracerHandle(BlockTracer::new(
+        sample_interval,
+        BlockTracer::DEFAULT_MAX_TRACES,
+    ))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_tracer_destroy(handle: *mut BlockTracerHandle) {
+    drop(Box::from_raw(handle))
+}
+
+/// Returns true if the block is traced
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_tracer_record(
+    handle: *mut BlockTracerHandle,
+    hash: *const u8,
+    event: u8,
+) -> bool {
+    let unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+    (*handle).0.record(
+        &BlockHash::from_ptr(hash),
+        BlockTraceEvent::from_u8(event).unwrap(),
+        Instant::now(),
+        unix_ms,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_tracer_summary(
+    handle: *mut BlockTracerHandle,
+    hash: *const u8,
+    result: *mut StringDto,
+) -> bool {
+    match (*handle).0.trace(&BlockHash::from_ptr(hash)) {
+        Some(trace) => {
+            *result = trace.summary().into();
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_tracer_serialize_json(
+    handle: *mut BlockTracerHandle,
+    ptree: *mut c_void,
+) {
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    if let Err(e) = (*handle).0.serialize_json(&mut writer) {
+        eprintln!("block tracer serialization failed: {:?}", e);
+    }
+}
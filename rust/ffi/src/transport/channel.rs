@@ -1,14 +1,11 @@
 use crate::{
-    messages::MessageHandle,
+    messages::{MessageHandle, SharedBufferHandle},
     utils::{ContextWrapper, FfiIoContext},
     NetworkConstantsDto, StatHandle, VoidPointerCallback,
 };
 
 use num_traits::FromPrimitive;
-use rsnano_core::{
-    utils::{system_time_as_nanoseconds, system_time_from_nanoseconds},
-    Account,
-};
+use rsnano_core::{utils::Timestamp, Account};
 use rsnano_node::{
     config::NetworkConstants,
     transport::{Channel, ChannelEnum, ChannelFake, ChannelInProc, ChannelTcp, TrafficType},
@@ -80,37 +77,40 @@ pub unsafe extern "C" fn rsn_channel_set_temporary(handle: *mut ChannelHandle, t
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_get_last_bootstrap_attempt(handle: *mut ChannelHandle) -> u64 {
-    system_time_as_nanoseconds(as_channel(handle).get_last_bootstrap_attempt())
+    as_channel(handle).get_last_bootstrap_attempt().as_nanos()
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_set_last_bootstrap_attempt(handle: *mut ChannelHandle) {
-    as_channel(handle).set_last_bootstrap_attempt(SystemTime::now());
+    as_channel(handle).set_last_bootstrap_attempt(Timestamp::now());
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_get_last_packet_received(handle: *mut ChannelHandle) -> u64 {
-    system_time_as_nanoseconds(as_channel(handle).get_last_packet_received())
+    as_channel(handle).get_last_packet_received().as_nanos()
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_set_last_packet_received(handle: *mut ChannelHandle) {
-    as_channel(handle).set_last_packet_received(SystemTime::now());
+    as_channel(handle).set_last_packet_received(Timestamp::now());
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_get_last_packet_sent(handle: *mut ChannelHandle) -> u64 {
-    system_time_as_nanoseconds(as_channel(handle).get_last_packet_sent())
+    as_channel(handle).get_last_packet_sent().as_nanos()
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_set_last_packet_sent(handle: *mut ChannelHandle) {
-    as_channel(handle).set_last_packet_sent(SystemTime::now());
+    as_channel(handle).set_last_packet_sent(Timestamp::now());
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn rsn_channel_set_last_packet_sent2(handle: *mut ChannelHandle, time: u64) {
-    as_channel(handle).set_last_packet_sent(system_time_from_nanoseconds(time));
+pub unsafe extern "C" fn rsn_channel_set_last_packet_sent2(
+    handle: *mut ChannelHandle,
+    time_ns: u64,
+) {
+    as_channel(handle).set_last_packet_sent(Timestamp::from_nanos(time_ns));
 }
 
 #[no_mangle]
@@ -195,6 +195,44 @@ pub unsafe extern "C" fn rsn_channel_inproc_create(
     ))))
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_channel_inproc_create_loopback(
+    channel_id: usize,
+    network_constants: *const NetworkConstantsDto,
+    network_filter: *mut NetworkFilterHandle,
+    stats: *mut StatHandle,
+    limiter: *mut OutboundBandwidthLimiterHandle,
+    inbound_callback: InboundCallback,
+    inbound_context: *mut c_void,
+    delete_context: VoidPointerCallback,
+    io_context: *mut c_void,
+    endpoint: *const EndpointDto,
+    node_id: *const u8,
+) -> *mut ChannelHandle {
+    let network_constants = NetworkConstants::try_from(&*network_constants).unwrap();
+    let network_filter = (*network_filter).deref().clone();
+    let context = ContextWrapper::new(inbound_context, delete_context);
+    let inbound = Arc::new(move |msg, channel| {
+        inbound_callback(
+            context.get_context(),
+            MessageHandle::new(msg),
+            ChannelHandle::new(channel),
+        );
+    });
+    ChannelHandle::new(Arc::new(ChannelEnum::InProc(ChannelInProc::loopback(
+        channel_id,
+        SystemTime::now(),
+        network_constants,
+        network_filter,
+        (*stats).0.clone(),
+        (*limiter).0.clone(),
+        inbound,
+        Arc::new(FfiIoContext::new(io_context)),
+        (&*endpoint).into(),
+        Account::from_ptr(node_id),
+    ))))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_inproc_network_version(handle: *mut ChannelHandle) -> u8 {
     let inproc = as_inproc_channel(handle);
@@ -250,15 +288,14 @@ pub unsafe extern "C" fn rsn_channel_fake_endpoint(
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_inproc_send_buffer(
     handle: *mut ChannelHandle,
-    buffer: *const u8,
-    buffer_len: usize,
+    buffer: *mut SharedBufferHandle,
     callback: ChannelTcpSendBufferCallback,
     delete_callback: VoidPointerCallback,
     callback_context: *mut c_void,
     policy: u8,
     traffic_type: u8,
 ) {
-    let buffer = Arc::new(std::slice::from_raw_parts(buffer, buffer_len).to_vec());
+    let buffer = SharedBufferHandle::take(buffer);
     let callback_wrapper =
         SendBufferCallbackWrapper::new(callback, callback_context, delete_callback);
     let cb = Box::new(move |ec, size| {
@@ -272,15 +309,14 @@ pub unsafe extern "C" fn rsn_channel_inproc_send_buffer(
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_fake_send_buffer(
     handle: *mut ChannelHandle,
-    buffer: *const u8,
-    buffer_len: usize,
+    buffer: *mut SharedBufferHandle,
     callback: ChannelTcpSendBufferCallback,
     delete_callback: VoidPointerCallback,
     callback_context: *mut c_void,
     policy: u8,
     traffic_type: u8,
 ) {
-    let buffer = Arc::new(std::slice::from_raw_parts(buffer, buffer_len).to_vec());
+    let buffer = SharedBufferHandle::take(buffer);
     let callback_wrapper =
         SendBufferCallbackWrapper::new(callback, callback_context, delete_callback);
     let cb = Box::new(move |ec, size| {
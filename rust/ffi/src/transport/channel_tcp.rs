@@ -7,7 +7,11 @@ use super::{
     socket::SocketHandle,
     EndpointDto,
 };
-use crate::{messages::MessageHandle, utils::FfiIoContext, ErrorCodeDto, VoidPointerCallback};
+use crate::{
+    messages::{MessageHandle, SharedBufferHandle},
+    utils::FfiIoContext,
+    ErrorCodeDto, VoidPointerCallback,
+};
 use rsnano_node::{
     transport::{BufferDropPolicy, Channel, ChannelEnum, ChannelTcp, TrafficType},
     utils::ErrorCode,
@@ -115,15 +119,14 @@ unsafe impl Sync for SendBufferCallbackWrapper {}
 #[no_mangle]
 pub unsafe extern "C" fn rsn_channel_tcp_send_buffer(
     handle: *mut ChannelHandle,
-    buffer: *const u8,
-    buffer_len: usize,
+    buffer: *mut SharedBufferHandle,
     callback: ChannelTcpSendBufferCallback,
     delete_callback: VoidPointerCallback,
     callback_context: *mut c_void,
     policy: u8,
     traffic_type: u8,
 ) {
-    let buffer = Arc::new(std::slice::from_raw_parts(buffer, buffer_len).to_vec());
+    let buffer = SharedBufferHandle::take(buffer);
     let callback_wrapper =
         SendBufferCallbackWrapper::new(callback, callback_context, delete_callback);
     let cb = Box::new(move |ec, size| {
@@ -19,10 +19,12 @@ pub use channel_tcp::{
 };
 pub use message_deserializer::*;
 pub use network_filter::NetworkFilterHandle;
+pub use peer_exclusion::PeerExclusionHandle;
 pub use socket::{
     EndpointDto, ReadCallbackWrapper, SocketDestroyContext, SocketHandle, SocketReadCallback,
 };
 pub use syn_cookies::SynCookiesHandle;
+pub use tcp_channels::EndpointListHandle;
 pub use tcp_message_item::TcpMessageItemHandle;
 pub use tcp_message_manager::TcpMessageManagerHandle;
 
@@ -3,7 +3,7 @@ use num::FromPrimitive;
 use rsnano_node::{
     stats::SocketStats,
     transport::{
-        CompositeSocketObserver, Socket, SocketBuilder, SocketExtensions, SocketObserver,
+        CompositeSocketObserver, Endpoint, Socket, SocketBuilder, SocketExtensions, SocketObserver,
         SocketType, TcpSocketFacade, TcpSocketFacadeFactory, WriteCallback,
     },
     utils::{BufferWrapper, ErrorCode},
@@ -914,18 +914,14 @@ impl Default for EndpointDto {
     }
 }
 
-impl From<&EndpointDto> for SocketAddrV6 {
+impl From<&EndpointDto> for Endpoint {
     fn from(dto: &EndpointDto) -> Self {
-        if dto.v6 {
-            SocketAddrV6::new(Ipv6Addr::from(dto.bytes), dto.port, 0, 0)
-        } else {
-            panic!("not a v6 ip address")
-        }
+        Endpoint::from(SocketAddr::from(dto))
     }
 }
 
-impl From<SocketAddrV6> for EndpointDto {
-    fn from(value: SocketAddrV6) -> Self {
+impl From<Endpoint> for EndpointDto {
+    fn from(value: Endpoint) -> Self {
         Self {
             bytes: value.ip().octets(),
             port: value.port(),
@@ -934,6 +930,19 @@ impl From<SocketAddrV6> for EndpointDto {
     }
 }
 
+/// IPv4 endpoints are converted into IPv4-mapped IPv6 endpoints
+impl From<&EndpointDto> for SocketAddrV6 {
+    fn from(dto: &EndpointDto) -> Self {
+        Endpoint::from(dto).as_v6()
+    }
+}
+
+impl From<SocketAddrV6> for EndpointDto {
+    fn from(value: SocketAddrV6) -> Self {
+        EndpointDto::from(Endpoint::from(value))
+    }
+}
+
 impl From<&EndpointDto> for SocketAddr {
     fn from(dto: &EndpointDto) -> Self {
         let ip = if dto.v6 {
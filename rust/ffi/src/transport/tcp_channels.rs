@@ -5,7 +5,10 @@ use std::{
     sync::{atomic::Ordering, Arc},
 };
 
-use rsnano_core::{utils::system_time_from_nanoseconds, KeyPair, PublicKey};
+use rsnano_core::{
+    utils::{SystemClock, Timestamp},
+    KeyPair, PublicKey,
+};
 use rsnano_node::{
     config::NodeConfig,
     transport::{
@@ -100,6 +103,7 @@ impl TryFrom<&TcpChannelsOptionsDto> for TcpChannelsOptions {
                 workers: (*value.workers).0.clone(),
                 tcp_socket_factory,
                 observer,
+                clock: Arc::new(SystemClock::new()),
             })
         }
     }
@@ -208,9 +212,8 @@ pub unsafe extern "C" fn rsn_tcp_channels_attempts_count(handle: *mut TcpChannel
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_tcp_channels_purge(handle: *mut TcpChannelsHandle, cutoff_ns: u64) {
-    let cutoff = system_time_from_nanoseconds(cutoff_ns);
     let mut guard = (*handle).0.tcp_channels.lock().unwrap();
-    guard.purge(cutoff)
+    guard.purge(Timestamp::from_nanos(cutoff_ns))
 }
 
 #[no_mangle]
@@ -269,6 +272,8 @@ pub extern "C" fn rsn_tcp_channels_bootstrap_peer(
     *result = peer.into();
 }
 
+/// Closes all channels gracefully without blocking the caller. Each socket gets
+/// closed as soon as its write queue is flushed, or after `CLOSE_GRACEFULLY_TIMEOUT`.
 #[no_mangle]
 pub extern "C" fn rsn_tcp_channels_close_channels(handle: &mut TcpChannelsHandle) {
     handle.0.tcp_channels.lock().unwrap().close_channels();
@@ -353,7 +358,7 @@ pub unsafe extern "C" fn rsn_tcp_channels_set_last_packet_sent(
         .tcp_channels
         .lock()
         .unwrap()
-        .set_last_packet_sent(&endpoint.into(), system_time_from_nanoseconds(time_ns));
+        .set_last_packet_sent(&endpoint.into(), Timestamp::from_nanos(time_ns));
 }
 
 #[no_mangle]
@@ -449,6 +454,26 @@ pub unsafe extern "C" fn rsn_tcp_channels_random_fill(
         .for_each(|(dto, ep)| *dto = ep.into());
 }
 
+/// `recipient` is null if the keepalive message is sent to several channels
+#[no_mangle]
+pub unsafe extern "C" fn rsn_tcp_channels_sample_keepalive_peers(
+    handle: &TcpChannelsHandle,
+    recipient: *const EndpointDto,
+    endpoints: *mut EndpointDto,
+) {
+    let recipient = if recipient.is_null() {
+        None
+    } else {
+        Some(SocketAddr::from(&*recipient))
+    };
+    let peers = handle.0.sample_keepalive_peers(recipient.as_ref());
+    let endpoints = std::slice::from_raw_parts_mut(endpoints, peers.len());
+    endpoints
+        .iter_mut()
+        .zip(&peers)
+        .for_each(|(dto, ep)| *dto = ep.into());
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_tcp_channels_set_observer(
     handle: &mut TcpChannelsHandle,
@@ -528,7 +553,7 @@ pub unsafe extern "C" fn rsn_tcp_channels_start_tcp(
     handle.0.start_tcp(endpoint.into());
 }
 
-pub struct EndpointListHandle(Vec<SocketAddr>);
+pub struct EndpointListHandle(pub Vec<SocketAddr>);
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_endpoint_list_len(handle: &EndpointListHandle) -> usize {
@@ -1,4 +1,4 @@
-use std::ffi::c_void;
+use std::{ffi::c_void, time::Duration};
 
 use rsnano_core::{BlockHash, HashOrAccount, UncheckedInfo, UncheckedKey};
 use rsnano_node::unchecked_map::UncheckedMap;
@@ -39,8 +39,9 @@ pub struct UncheckedMapHandle(UncheckedMap);
 pub unsafe extern "C" fn rsn_unchecked_map_create(
     stats_handle: *mut StatHandle,
     disable_delete: bool,
+    max_entries: usize,
 ) -> *mut UncheckedMapHandle {
-    let unchecked_map = UncheckedMap::new((*stats_handle).0.clone(), disable_delete);
+    let unchecked_map = UncheckedMap::new((*stats_handle).0.clone(), disable_delete, max_entries);
     Box::into_raw(Box::new(UncheckedMapHandle(unchecked_map)))
 }
 
@@ -202,6 +203,24 @@ pub unsafe extern "C" fn rsn_unchecked_map_for_each2(
     );
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_unchecked_map_remove_older_than(
+    handle: *mut UncheckedMapHandle,
+    max_age_s: u64,
+    action_callback: ActionCallback,
+    action_callback_context: *mut c_void,
+    drop_action_callback: VoidPointerCallback,
+) -> usize {
+    let mut action = wrap_action_callback(
+        action_callback,
+        action_callback_context,
+        drop_action_callback,
+    );
+    (*handle)
+        .0
+        .remove_older_than(Duration::from_secs(max_age_s), &mut action)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_unchecked_map_destroy_dto(vector: *mut InfoVecDto) {
     drop(Box::from_raw((*vector).raw_data))
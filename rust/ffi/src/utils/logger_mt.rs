@@ -1,6 +1,7 @@
-use std::ffi::c_void;
+use std::{ffi::c_void, sync::Arc};
 
 use rsnano_core::utils::Logger;
+use rsnano_node::utils::log_panics;
 
 use crate::VoidPointerCallback;
 
@@ -81,3 +82,9 @@ pub static mut DESTROY_LOGGER_HANDLE: Option<VoidPointerCallback> = None;
 pub unsafe extern "C" fn rsn_callback_logger_destroy(f: VoidPointerCallback) {
     DESTROY_LOGGER_HANDLE = Some(f);
 }
+
+/// Writes the panics of all Rust threads with a backtrace to the given logger
+#[no_mangle]
+pub unsafe extern "C" fn rsn_log_panics(logger: *mut LoggerHandle) {
+    log_panics(Arc::new(LoggerMT::new(Box::from_raw(logger))));
+}
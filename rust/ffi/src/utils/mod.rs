@@ -33,6 +33,8 @@ pub use latch::*;
 mod container_info;
 pub use container_info::*;
 
+mod resource_watchdog;
+
 use crate::{transport::EndpointDto, VoidPointerCallback};
 
 pub struct ContextWrapper {
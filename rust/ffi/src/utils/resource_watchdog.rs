@@ -0,0 +1,55 @@
+use std::{
+    ffi::{c_char, CStr},
+    sync::Arc,
+};
+
+use rsnano_node::utils::{ResourceWatchdog, ResourceWatchdogConfig};
+
+use super::{LoggerHandle, LoggerMT};
+
+pub struct ResourceWatchdogHandle(ResourceWatchdog);
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_resource_watchdog_create(
+    data_path: *const c_char,
+    logger: *mut LoggerHandle,
+    free_disk_warning_mb: u64,
+    free_disk_pause_mb: u64,
+    free_disk_shutdown_mb: u64,
+    memory_warning_mb: u64,
+) -> *mut ResourceWatchdogHandle {
+    let data_path = CStr::from_ptr(data_path).to_string_lossy().to_string();
+    let logger = Arc::new(LoggerMT::new(Box::from_raw(logger)));
+    let config = ResourceWatchdogConfig {
+        free_disk_warning_mb,
+        free_disk_pause_mb,
+        free_disk_shutdown_mb,
+        memory_warning_mb,
+    };
+    Box::into_raw(Box::new(ResourceWatchdogHandle(ResourceWatchdog::new(
+        config, data_path, logger,
+    ))))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_resource_watchdog_destroy(handle: *mut ResourceWatchdogHandle) {
+    drop(Box::from_raw(handle))
+}
+
+/// Samples the resource usage. Returns the `ResourceStatus` as u8
+#[no_mangle]
+pub unsafe extern "C" fn rsn_resource_watchdog_check(handle: *mut ResourceWatchdogHandle) -> u8 {
+    (*handle).0.check() as u8
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_resource_watchdog_write_dropped(handle: *mut ResourceWatchdogHandle) {
+    (*handle).0.write_dropped()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_resource_watchdog_writes_paused(
+    handle: *mut ResourceWatchdogHandle,
+) -> bool {
+    (*handle).0.writes_paused()
+}
@@ -1,18 +1,26 @@
-use crate::{copy_account_bytes, utils::ContainerInfoComponentHandle, voting::VoteHandle};
+use crate::{
+    copy_account_bytes, copy_amount_bytes, copy_hash_bytes, utils::ContainerInfoComponentHandle,
+    voting::VoteHandle,
+};
 use rsnano_core::{Account, Amount, BlockHash};
-use rsnano_node::vote_cache::VoteCache;
+use rsnano_node::vote_cache::{TopEntry, VoteCache, VoteCacheEvictionPolicy};
 use std::{
     ffi::{c_char, CStr},
     sync::{Arc, Mutex},
 };
 
-pub struct VoteCacheHandle(Arc<Mutex<VoteCache>>);
+pub struct VoteCacheHandle(pub Arc<Mutex<VoteCache>>);
 
 #[no_mangle]
-pub extern "C" fn rsn_vote_cache_create(max_size: usize) -> *mut VoteCacheHandle {
-    Box::into_raw(Box::new(VoteCacheHandle(Arc::new(Mutex::new(
-        VoteCache::new(max_size),
-    )))))
+pub extern "C" fn rsn_vote_cache_create(
+    max_size: usize,
+    eviction_policy: u8,
+    retain_promoted: bool,
+) -> *mut VoteCacheHandle {
+    let eviction_policy = VoteCacheEvictionPolicy::from_u8(eviction_policy).unwrap_or_default();
+    let mut cache = VoteCache::with_eviction_policy(max_size, eviction_policy);
+    cache.set_retain_promoted(retain_promoted);
+    Box::into_raw(Box::new(VoteCacheHandle(Arc::new(Mutex::new(cache)))))
 }
 
 #[no_mangle]
@@ -28,7 +36,7 @@ pub unsafe extern "C" fn rsn_vote_cache_vote(
     rep_weight: *const u8,
 ) {
     let hash = BlockHash::from_ptr(hash);
-    let vote = (*vote).0.read().unwrap();
+    let vote = Arc::new((*vote).0.read().unwrap().clone());
     let rep_weight = Amount::from_ptr(rep_weight);
     (*handle).0.lock().unwrap().vote(&hash, &vote, rep_weight);
 }
@@ -91,6 +99,18 @@ pub unsafe extern "C" fn rsn_vote_cache_erase(
     guard.erase(&hash)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_cache_promote(
+    handle: *mut VoteCacheHandle,
+    hash: *const u8,
+    result: *mut VoteCacheEntryDto,
+) -> bool {
+    let hash = BlockHash::from_ptr(hash);
+    let mut guard = (*handle).0.lock().unwrap();
+    let entry = guard.promote(&hash);
+    fill_entry_dto(entry.as_ref(), result)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_vote_cache_pop(
     handle: *mut VoteCacheHandle,
@@ -115,6 +135,45 @@ pub unsafe extern "C" fn rsn_vote_cache_peek(
     fill_entry_dto(entry, result)
 }
 
+pub struct VoteCacheTopEntriesHandle(Vec<TopEntry>);
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_cache_top(
+    handle: *mut VoteCacheHandle,
+    min_tally: *const u8,
+    max_count: usize,
+) -> *mut VoteCacheTopEntriesHandle {
+    let min_tally = Amount::from_ptr(min_tally);
+    let top = (*handle).0.lock().unwrap().top(min_tally, max_count);
+    Box::into_raw(Box::new(VoteCacheTopEntriesHandle(top)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_cache_top_entries_len(
+    handle: *const VoteCacheTopEntriesHandle,
+) -> usize {
+    (*handle).0.len()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_cache_top_entries_get(
+    handle: *const VoteCacheTopEntriesHandle,
+    index: usize,
+    hash: *mut u8,
+    tally: *mut u8,
+) {
+    let entry = &(*handle).0[index];
+    copy_hash_bytes(entry.hash, hash);
+    copy_amount_bytes(entry.tally, tally);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_cache_top_entries_destroy(
+    handle: *mut VoteCacheTopEntriesHandle,
+) {
+    drop(Box::from_raw(handle))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rsn_vote_cache_trigger(handle: *mut VoteCacheHandle, hash: *const u8) {
     let hash = BlockHash::from_ptr(hash);
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use rsnano_core::{BlockHash, QualifiedRoot};
+use rsnano_node::{stats::DetailType, voting::BlockBroadcastThrottle};
+
+pub struct BlockBroadcastThrottleHandle(BlockBroadcastThrottle);
+
+#[no_mangle]
+pub extern "C" fn rsn_block_broadcast_throttle_create(
+    initial_delay_ms: u64,
+    interval_ms: u64,
+) -> *mut BlockBroadcastThrottleHandle {
+    Box::into_raw(Box::new(BlockBroadcastThrottleHandle(
+        BlockBroadcastThrottle::new(
+            Duration::from_millis(initial_delay_ms),
+            Duration::from_millis(interval_ms),
+        ),
+    )))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_broadcast_throttle_destroy(
+    handle: *mut BlockBroadcastThrottleHandle,
+) {
+    drop(Box::from_raw(handle))
+}
+
+/// Returns true if the winner should be broadcast. `stat_detail` receives the reason for the broadcast
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_broadcast_throttle_check(
+    handle: *mut BlockBroadcastThrottleHandle,
+    root: *const u8,
+    winner: *const u8,
+    election_age_ms: u64,
+    confirmed: bool,
+    stat_detail: *mut u8,
+) -> bool {
+    match (*handle).0.check(
+        &QualifiedRoot::from_ptr(root),
+        &BlockHash::from_ptr(winner),
+        Duration::from_millis(election_age_ms),
+        confirmed,
+    ) {
+        Some(reason) => {
+            *stat_detail = DetailType::from(reason) as u8;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_broadcast_throttle_flag(
+    handle: *mut BlockBroadcastThrottleHandle,
+    root: *const u8,
+    winner: *const u8,
+    confirmed: bool,
+) {
+    (*handle).0.flag(
+        &QualifiedRoot::from_ptr(root),
+        &BlockHash::from_ptr(winner),
+        confirmed,
+    );
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_broadcast_throttle_erase(
+    handle: *mut BlockBroadcastThrottleHandle,
+    root: *const u8,
+) {
+    (*handle).0.erase(&QualifiedRoot::from_ptr(root));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_block_broadcast_throttle_len(
+    handle: *mut BlockBroadcastThrottleHandle,
+) -> usize {
+    (*handle).0.len()
+}
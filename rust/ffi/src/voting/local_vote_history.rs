@@ -49,7 +49,7 @@ pub struct LocalVotesResult {
     pub handle: *mut LocalVotesResultHandle,
 }
 
-pub struct LocalVotesResultHandle(Vec<*mut VoteHandle>);
+pub struct LocalVotesResultHandle(pub(crate) Vec<*mut VoteHandle>);
 
 #[no_mangle]
 pub unsafe extern "C" fn rsn_local_vote_history_votes(
@@ -1,5 +1,7 @@
+mod block_broadcast_throttle;
 mod local_vote_history;
 mod vote;
+mod vote_packer;
 mod vote_spacing;
 mod vote_uniquer;
 
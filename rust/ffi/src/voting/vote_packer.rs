@@ -0,0 +1,46 @@
+use std::ops::Deref;
+
+use rsnano_node::voting::VotePacker;
+
+use super::{
+    local_vote_history::{LocalVotesResult, LocalVotesResultHandle},
+    VoteHandle,
+};
+
+pub struct VotePackerHandle(VotePacker);
+
+#[no_mangle]
+pub extern "C" fn rsn_vote_packer_create() -> *mut VotePackerHandle {
+    Box::into_raw(Box::new(VotePackerHandle(VotePacker::new())))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_packer_destroy(handle: *mut VotePackerHandle) {
+    drop(Box::from_raw(handle))
+}
+
+/// The result has to be destroyed with `rsn_local_vote_history_votes_destroy`
+#[no_mangle]
+pub unsafe extern "C" fn rsn_vote_packer_pack(
+    handle: *mut VotePackerHandle,
+    votes: *const *const VoteHandle,
+    count: usize,
+    result: *mut LocalVotesResult,
+) {
+    let votes = std::slice::from_raw_parts(votes, count)
+        .iter()
+        .map(|&v| (*v).deref().clone())
+        .collect();
+    let mut packed = Box::new(LocalVotesResultHandle(
+        (*handle)
+            .0
+            .pack(votes)
+            .drain(..)
+            .map(|vote| Box::into_raw(Box::new(VoteHandle::new(vote))))
+            .collect::<Vec<_>>(),
+    ));
+    let result = &mut *result;
+    result.count = packed.0.len();
+    result.votes = packed.0.as_mut_ptr();
+    result.handle = Box::into_raw(packed);
+}
@@ -1,4 +1,5 @@
 mod work_thresholds;
 pub use work_thresholds::*;
 
+mod work_peer_health;
 mod work_pool;
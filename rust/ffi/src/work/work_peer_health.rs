@@ -0,0 +1,65 @@
+use std::{
+    ffi::{c_void, CStr},
+    time::{Duration, Instant},
+};
+
+use rsnano_node::WorkPeerHealth;
+
+use crate::FfiPropertyTreeWriter;
+
+pub struct WorkPeerHealthHandle(WorkPeerHealth);
+
+#[no_mangle]
+pub extern "C" fn rsn_work_peer_health_create() -> *mut WorkPeerHealthHandle {
+    Box::into_raw(Box::new(WorkPeerHealthHandle(WorkPeerHealth::default())))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_work_peer_health_destroy(handle: *mut WorkPeerHealthHandle) {
+    drop(Box::from_raw(handle))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_work_peer_health_is_available(
+    handle: *mut WorkPeerHealthHandle,
+    peer: *const i8,
+) -> bool {
+    let peer = CStr::from_ptr(peer).to_str().unwrap();
+    (*handle).0.is_available(peer, Instant::now())
+}
+
+/// A negative `latency_ms` means that the success has no latency (health check)
+#[no_mangle]
+pub unsafe extern "C" fn rsn_work_peer_health_record_success(
+    handle: *mut WorkPeerHealthHandle,
+    peer: *const i8,
+    latency_ms: i64,
+) {
+    let peer = CStr::from_ptr(peer).to_str().unwrap();
+    let latency = if latency_ms >= 0 {
+        Some(Duration::from_millis(latency_ms as u64))
+    } else {
+        None
+    };
+    (*handle).0.record_success(peer, latency);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_work_peer_health_record_failure(
+    handle: *mut WorkPeerHealthHandle,
+    peer: *const i8,
+) {
+    let peer = CStr::from_ptr(peer).to_str().unwrap();
+    (*handle).0.record_failure(peer, Instant::now());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rsn_work_peer_health_serialize_json(
+    handle: *mut WorkPeerHealthHandle,
+    ptree: *mut c_void,
+) {
+    let mut writer = FfiPropertyTreeWriter::new_borrowed(ptree);
+    if let Err(e) = (*handle).0.serialize_json(&mut writer) {
+        eprintln!("work peer health serialization failed: {:?}", e);
+    }
+}
@@ -17,6 +17,8 @@ pub struct WorkThresholdsDto {
     pub epoch_2_receive: u64,
     pub base: u64,
     pub entry: u64,
+    /// Zero if no override is active
+    pub threshold_override: u64,
 }
 
 #[no_mangle]
@@ -179,23 +181,54 @@ pub unsafe extern "C" fn rsn_work_thresholds_validate_entry_block(
     thresholds.validate_entry_block(&lk)
 }
 
+/// Returns false if the network doesn't allow overriding the thresholds. Zero removes the override
+#[no_mangle]
+pub unsafe extern "C" fn rsn_work_thresholds_set_override(
+    dto: *mut WorkThresholdsDto,
+    network: u16,
+    threshold: u64,
+) -> bool {
+    let Some(network) = Networks::from_u16(network) else {
+        return false;
+    };
+    let threshold = if threshold == 0 {
+        None
+    } else {
+        Some(threshold)
+    };
+    let mut thresholds = WorkThresholds::from(&*dto);
+    if thresholds
+        .set_threshold_override(network, threshold)
+        .is_err()
+    {
+        return false;
+    }
+    fill_work_thresholds_dto(&mut *dto, &thresholds);
+    true
+}
+
 pub fn fill_work_thresholds_dto(dto: &mut WorkThresholdsDto, thresholds: &WorkThresholds) {
     dto.epoch_1 = thresholds.epoch_1;
     dto.epoch_2 = thresholds.epoch_2;
     dto.epoch_2_receive = thresholds.epoch_2_receive;
     dto.base = thresholds.base;
     dto.entry = thresholds.entry;
+    dto.threshold_override = thresholds.threshold_override.unwrap_or_default();
 }
 
 impl From<WorkThresholdsDto> for WorkThresholds {
     fn from(dto: WorkThresholdsDto) -> Self {
-        WorkThresholds::new(dto.epoch_1, dto.epoch_2, dto.epoch_2_receive)
+        WorkThresholds::from(&dto)
     }
 }
 
 impl From<&WorkThresholdsDto> for WorkThresholds {
     fn from(dto: &WorkThresholdsDto) -> Self {
-        WorkThresholds::new(dto.epoch_1, dto.epoch_2, dto.epoch_2_receive)
+        let mut thresholds = WorkThresholds::new(dto.epoch_1, dto.epoch_2, dto.epoch_2_receive);
+        if dto.threshold_override != 0 {
+            thresholds.threshold_override = Some(dto.threshold_override);
+        }
+        thresholds
     }
 }
 
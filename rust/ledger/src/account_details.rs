@@ -0,0 +1,36 @@
+use rsnano_core::{Account, AccountInfo, Amount, BlockHash, ConfirmationHeightInfo};
+
+/// Everything the `account_info` RPC needs to know about an account, read with a single transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountDetails {
+    pub info: AccountInfo,
+
+    /// Zero height and frontier if no block of the account is confirmed yet
+    pub confirmation_height: ConfirmationHeightInfo,
+
+    /// The latest block which contains representative information
+    pub representative_block: BlockHash,
+
+    /// Balance at the confirmed frontier
+    pub confirmed_balance: Amount,
+
+    /// Representative at the confirmed frontier. Zero if nothing is confirmed yet
+    pub confirmed_representative: Account,
+
+    /// Sum of all receivable amounts, including unconfirmed sends.
+    /// Zero if it wasn't requested
+    pub receivable: Amount,
+
+    /// Sum of the receivable amounts of confirmed sends only. Zero if it wasn't requested
+    pub confirmed_receivable: Amount,
+}
+
+/// Which receivable amounts `Ledger::account_details` sums up. The total is taken from the
+/// receivable cache if it is enabled, the confirmed amount always scans the receivables
+/// of the account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceivableDetails {
+    Skip,
+    Total,
+    TotalAndConfirmed,
+}
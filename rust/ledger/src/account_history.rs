@@ -0,0 +1,91 @@
+use std::io::Write;
+
+use rsnano_core::{Account, Amount, BlockHash, BlockSubType};
+
+/// One entry of the `account_history` RPC. Only sends and receives are part of the history
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountHistoryEntry {
+    pub hash: BlockHash,
+
+    /// Send or receive. Opens are reported as receive.
+    /// `None` if the previous block of a state block was pruned
+    pub subtype: Option<BlockSubType>,
+
+    /// The destination of a send or the sender of a receive. `None` if the source block was pruned
+    pub account: Option<Account>,
+
+    /// `None` if the previous block was pruned
+    pub amount: Option<Amount>,
+    pub height: u64,
+    pub local_timestamp: u64,
+    pub confirmed: bool,
+}
+
+impl AccountHistoryEntry {
+    /// Writes the entry as a single line of JSON with the same fields as the `account_history` RPC
+    pub fn write_json_line(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        let entry_type = match self.subtype {
+            Some(BlockSubType::Send) => "send",
+            Some(_) => "receive",
+            None => "unknown",
+        };
+        write!(writer, "{{\"type\":\"{}\"", entry_type)?;
+        if let Some(account) = &self.account {
+            write!(writer, ",\"account\":\"{}\"", account.encode_account())?;
+        }
+        if let Some(amount) = &self.amount {
+            write!(writer, ",\"amount\":\"{}\"", amount.to_string_dec())?;
+        }
+        writeln!(
+            writer,
+            ",\"local_timestamp\":\"{}\",\"height\":\"{}\",\"hash\":\"{}\",\"confirmed\":\"{}\"}}",
+            self.local_timestamp, self.height, self.hash, self.confirmed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_line() {
+        let entry = AccountHistoryEntry {
+            hash: BlockHash::from(1),
+            subtype: Some(BlockSubType::Send),
+            account: Some(Account::from(2)),
+            amount: Some(Amount::raw(3)),
+            height: 4,
+            local_timestamp: 5,
+            confirmed: true,
+        };
+        let mut buffer = Vec::new();
+        entry.write_json_line(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            format!(
+                "{{\"type\":\"send\",\"account\":\"{}\",\"amount\":\"3\",\"local_timestamp\":\"5\",\"height\":\"4\",\"hash\":\"{}\",\"confirmed\":\"true\"}}\n",
+                Account::from(2).encode_account(),
+                BlockHash::from(1)
+            )
+        );
+    }
+
+    #[test]
+    fn json_line_of_entry_with_pruned_previous() {
+        let entry = AccountHistoryEntry {
+            hash: BlockHash::from(1),
+            subtype: None,
+            account: None,
+            amount: None,
+            height: 4,
+            local_timestamp: 5,
+            confirmed: false,
+        };
+        let mut buffer = Vec::new();
+        entry.write_json_line(&mut buffer).unwrap();
+        assert!(String::from_utf8(buffer)
+            .unwrap()
+            .starts_with("{\"type\":\"unknown\",\"local_timestamp\":\"5\""));
+    }
+}
@@ -0,0 +1,22 @@
+use rsnano_core::{Account, Amount, BlockHash, BlockSubType};
+
+/// Everything the `blocks_info` RPC needs to know about a block, read with a single transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockInfo {
+    pub account: Account,
+
+    /// `None` if the previous block was pruned
+    pub amount: Option<Amount>,
+    pub balance: Amount,
+    pub height: u64,
+
+    /// Seconds since epoch at which the block was added to the local ledger
+    pub local_timestamp: u64,
+
+    /// Zero if the block is the frontier of its account
+    pub successor: BlockHash,
+
+    /// Only state blocks have a subtype. Open state blocks are reported as receive
+    pub subtype: Option<BlockSubType>,
+    pub confirmed: bool,
+}
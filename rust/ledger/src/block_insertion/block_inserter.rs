@@ -43,12 +43,16 @@ impl<'a, T: Environment> BlockInserter<'a, T> {
     pub(crate) fn insert(&mut self) {
         self.set_block_sideband();
         self.ledger.store.block.put(self.txn, self.block);
+        self.update_height_index();
+        self.update_timestamp_index();
+        self.update_destination_index();
         self.update_account();
         self.delete_old_pending_info();
         self.insert_new_pending_info();
         self.delete_old_frontier();
         self.insert_new_frontier();
         self.update_representative_cache();
+        self.notify_representative_change();
         self.ledger
             .observer
             .block_added(self.block, self.instructions.is_epoch_block);
@@ -60,6 +64,38 @@ impl<'a, T: Environment> BlockInserter<'a, T> {
             .set_sideband(self.instructions.set_sideband.clone());
     }
 
+    fn update_height_index(&mut self) {
+        if self.ledger.height_index_enabled() {
+            self.ledger.store.block.put_height(
+                self.txn,
+                &self.instructions.account,
+                self.instructions.set_sideband.height,
+                &self.block.hash(),
+            );
+        }
+    }
+
+    fn update_timestamp_index(&mut self) {
+        if self.ledger.timestamp_index_enabled() {
+            self.ledger.store.block.put_timestamp(
+                self.txn,
+                self.instructions.set_sideband.timestamp,
+                &self.block.hash(),
+            );
+        }
+    }
+
+    fn update_destination_index(&mut self) {
+        if self.ledger.destination_index_enabled() {
+            if let Some((key, _)) = &self.instructions.insert_pending {
+                self.ledger
+                    .store
+                    .block
+                    .put_destination(self.txn, &key.account, &key.hash);
+            }
+        }
+    }
+
     fn update_account(&mut self) {
         self.ledger.update_account(
             self.txn,
@@ -96,30 +132,56 @@ impl<'a, T: Environment> BlockInserter<'a, T> {
 
     fn delete_old_pending_info(&mut self) {
         if let Some(key) = &self.instructions.delete_pending {
+            if self.ledger.cache.receivable.is_enabled() {
+                if let Some(info) = self.ledger.store.pending.get(self.txn, key) {
+                    self.ledger
+                        .cache
+                        .receivable
+                        .remove(&key.account, info.amount);
+                }
+            }
             self.ledger.store.pending.del(self.txn, key);
+            self.ledger.receivable_removed(self.txn, &key.account);
         }
     }
 
     fn insert_new_pending_info(&mut self) {
         if let Some((key, info)) = &self.instructions.insert_pending {
             self.ledger.store.pending.put(self.txn, key, info);
+            self.ledger.cache.receivable.add(&key.account, info.amount);
+            self.ledger.receivable_added(self.txn, key, info);
         }
     }
 
     fn update_representative_cache(&mut self) {
-        if !self.instructions.old_account_info.head.is_zero() {
+        let crossed = if !self.instructions.old_account_info.head.is_zero() {
             // Move existing representation & add in amount delta
             self.ledger.cache.rep_weights.representation_add_dual(
                 self.instructions.old_account_info.representative,
                 Amount::zero().wrapping_sub(self.instructions.old_account_info.balance),
                 self.instructions.set_account_info.representative,
                 self.instructions.set_account_info.balance,
-            );
+            )
         } else {
             // Add in amount delta only
             self.ledger.cache.rep_weights.representation_add(
                 self.instructions.set_account_info.representative,
                 self.instructions.set_account_info.balance,
+            )
+        };
+        for event in &crossed {
+            self.ledger.observer.rep_weight_threshold_crossed(event);
+        }
+    }
+
+    fn notify_representative_change(&self) {
+        let old_info = &self.instructions.old_account_info;
+        let new_info = &self.instructions.set_account_info;
+        if !old_info.head.is_zero() && old_info.representative != new_info.representative {
+            self.ledger.observer.representative_changed(
+                &self.instructions.account,
+                &old_info.representative,
+                &new_info.representative,
             );
         }
     }
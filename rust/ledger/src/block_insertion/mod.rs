@@ -4,4 +4,4 @@ mod validator_factory;
 
 pub(crate) use block_inserter::{BlockInsertInstructions, BlockInserter};
 pub(crate) use validation::BlockValidator;
-pub(crate) use validator_factory::BlockValidatorFactory;
+pub(crate) use validator_factory::{BlockValidatorFactory, LedgerView};
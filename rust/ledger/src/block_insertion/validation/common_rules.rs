@@ -2,6 +2,9 @@ use super::BlockValidator;
 use crate::ProcessResult;
 use rsnano_core::validate_message;
 
+/// How far the local timestamp of a block may be ahead of the clock of this node
+pub(crate) const MAX_TIMESTAMP_DRIFT_S: u64 = 60 * 60;
+
 impl<'a> BlockValidator<'a> {
     pub(crate) fn ensure_frontier_not_missing(&self) -> Result<(), ProcessResult> {
         if self.frontier_missing {
@@ -32,6 +35,39 @@ impl<'a> BlockValidator<'a> {
         result.map_err(|_| ProcessResult::BadSignature)
     }
 
+    pub(crate) fn ensure_block_height_does_not_overflow(&self) -> Result<(), ProcessResult> {
+        let block_count = self
+            .old_account_info
+            .as_ref()
+            .map(|info| info.block_count)
+            .unwrap_or_default();
+        if block_count.checked_add(1).is_none() {
+            Err(ProcessResult::HeightOverflow)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Blocks from the network carry no sideband. A block only has one if it was loaded from
+    /// the local store before, for example a rolled back block which is processed again.
+    /// Its timestamp was set by this node, so it can only lie in the future if the local
+    /// clock was set back since. Such a block is rejected instead of being re-inserted
+    /// with a timestamp that jumps backwards.
+    pub(crate) fn ensure_timestamp_is_not_in_future(&self) -> Result<(), ProcessResult> {
+        let Some(sideband) = self.block.sideband() else {
+            return Ok(());
+        };
+        if sideband.timestamp
+            > self
+                .seconds_since_epoch
+                .saturating_add(MAX_TIMESTAMP_DRIFT_S)
+        {
+            Err(ProcessResult::TimestampInFuture)
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) fn ensure_account_exists_for_none_open_block(&self) -> Result<(), ProcessResult> {
         if !self.block.is_open() && self.is_new_account() {
             Err(ProcessResult::GapPrevious)
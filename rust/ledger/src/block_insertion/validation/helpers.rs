@@ -128,6 +128,8 @@ impl<'a> BlockValidator<'a> {
         }
     }
 
+    /// The epoch of the account after the block was inserted. Receiving a pending entry
+    /// of a higher epoch upgrades the account to that epoch, even if epochs are skipped
     pub(crate) fn epoch(&self) -> Epoch {
         if self.is_epoch_block() {
             self.block_epoch_version()
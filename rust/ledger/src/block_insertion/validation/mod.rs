@@ -40,6 +40,8 @@ impl<'a> BlockValidator<'a> {
         self.ensure_account_exists_for_none_open_block()?;
         self.ensure_no_double_account_open()?;
         self.ensure_previous_block_is_correct()?;
+        self.ensure_block_height_does_not_overflow()?;
+        self.ensure_timestamp_is_not_in_future()?;
         self.ensure_open_block_has_link()?;
         self.ensure_no_reveive_balance_change_without_link()?;
         self.ensure_pending_receive_is_correct()?;
@@ -34,6 +34,7 @@ impl<'a> BlockValidator<'a> {
         Ok(())
     }
 
+    /// Legacy blocks can't upgrade the epoch of an account, so they can only receive epoch 0 sends
     fn ensure_legacy_source_is_epoch_0(&self) -> Result<(), ProcessResult> {
         let is_legacy_receive = matches!(
             self.block,
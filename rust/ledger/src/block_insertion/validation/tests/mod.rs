@@ -1,3 +1,4 @@
+mod validate_common_rules;
 mod validate_epoch_v1;
 mod validate_epoch_v2;
 mod validate_legacy_change;
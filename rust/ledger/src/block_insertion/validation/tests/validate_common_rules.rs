@@ -0,0 +1,59 @@
+use super::{create_test_validator, BlockValidationTest};
+use crate::{block_insertion::validation::common_rules::MAX_TIMESTAMP_DRIFT_S, ProcessResult};
+use rsnano_core::{AccountInfo, BlockEnum, BlockSideband};
+
+#[test]
+fn fails_if_block_height_would_overflow() {
+    let test = BlockValidationTest::for_epoch0_account()
+        .block_to_validate(|chain| chain.new_state_block().representative(12345).build());
+
+    let mut validator = create_test_validator(test.block(), test.chain.account());
+    validator.old_account_info = Some(AccountInfo {
+        block_count: u64::MAX,
+        ..test.chain.account_info()
+    });
+    validator.previous_block = Some(test.chain.latest_block().clone());
+
+    assert_eq!(validator.validate(), Err(ProcessResult::HeightOverflow));
+}
+
+#[test]
+fn skip_timestamp_check_without_sideband() {
+    BlockValidationTest::for_epoch0_account()
+        .block_to_validate(|chain| chain.new_state_block().representative(12345).build())
+        .assert_is_valid();
+}
+
+#[test]
+fn accept_timestamp_within_drift() {
+    let mut test = BlockValidationTest::for_epoch0_account();
+    let timestamp = test.seconds_since_epoch + MAX_TIMESTAMP_DRIFT_S;
+    test = test.block_to_validate(|chain| {
+        with_timestamp(
+            chain.new_state_block().representative(12345).build(),
+            timestamp,
+        )
+    });
+    test.assert_is_valid();
+}
+
+#[test]
+fn fails_if_timestamp_is_too_far_in_the_future() {
+    let mut test = BlockValidationTest::for_epoch0_account();
+    let timestamp = test.seconds_since_epoch + MAX_TIMESTAMP_DRIFT_S + 1;
+    test = test.block_to_validate(|chain| {
+        with_timestamp(
+            chain.new_state_block().representative(12345).build(),
+            timestamp,
+        )
+    });
+    test.assert_validation_fails_with(ProcessResult::TimestampInFuture);
+}
+
+fn with_timestamp(mut block: BlockEnum, timestamp: u64) -> BlockEnum {
+    block.as_block_mut().set_sideband(BlockSideband {
+        timestamp,
+        ..BlockSideband::create_test_instance()
+    });
+    block
+}
@@ -77,3 +77,11 @@ fn fail_if_signature_is_bad() {
         .block_to_validate(|chain| chain.new_legacy_open_block().sign(&KeyPair::new()).build())
         .assert_validation_fails_with(ProcessResult::BadSignature);
 }
+
+#[test]
+fn fail_with_unreceivable_if_source_is_epoch1() {
+    BlockValidationTest::for_unopened_account()
+        .with_pending_receive(Amount::raw(100), Epoch::Epoch1)
+        .block_to_validate(|chain| chain.new_legacy_open_block().build())
+        .assert_validation_fails_with(ProcessResult::Unreceivable);
+}
@@ -77,3 +77,19 @@ fn fails_if_legacy_receive_follows_state_block() {
         .block_to_validate(|chain| chain.new_legacy_receive_block().build())
         .assert_validation_fails_with(ProcessResult::BlockPosition);
 }
+
+#[test]
+fn fails_with_unreceivable_if_source_is_epoch1() {
+    BlockValidationTest::for_epoch0_account()
+        .with_pending_receive(Amount::raw(10), Epoch::Epoch1)
+        .block_to_validate(|chain| chain.new_legacy_receive_block().build())
+        .assert_validation_fails_with(ProcessResult::Unreceivable);
+}
+
+#[test]
+fn fails_with_unreceivable_if_source_is_epoch2() {
+    BlockValidationTest::for_epoch0_account()
+        .with_pending_receive(Amount::raw(10), Epoch::Epoch2)
+        .block_to_validate(|chain| chain.new_legacy_receive_block().build())
+        .assert_validation_fails_with(ProcessResult::Unreceivable);
+}
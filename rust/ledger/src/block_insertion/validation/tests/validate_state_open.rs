@@ -63,3 +63,14 @@ fn fails_with_gap_source_if_link_missing() {
         .block_to_validate(|chain| chain.new_open_block().balance(10).link(0).build())
         .assert_validation_fails_with(ProcessResult::GapSource);
 }
+
+#[test]
+fn open_with_epoch_of_source() {
+    let test = BlockValidationTest::for_unopened_account()
+        .with_pending_receive(Amount::raw(10), Epoch::Epoch1)
+        .block_to_validate(|chain| chain.new_open_block().balance(10).build());
+    let result = test.assert_is_valid();
+
+    assert_eq!(result.set_account_info.epoch, Epoch::Epoch1);
+    assert_eq!(result.set_sideband.details.epoch, Epoch::Epoch1);
+}
@@ -74,3 +74,53 @@ fn fails_with_unreceivable_if_receiving_from_wrong_account() {
         .block_to_validate(|chain| chain.new_receive_block().build())
         .assert_validation_fails_with(ProcessResult::Unreceivable);
 }
+
+#[test]
+fn receive_upgrades_epoch0_account_to_epoch1() {
+    assert_epoch_after_receive(
+        BlockValidationTest::for_epoch0_account(),
+        Epoch::Epoch1,
+        Epoch::Epoch1,
+    );
+}
+
+#[test]
+fn receive_upgrades_epoch0_account_to_epoch2() {
+    assert_epoch_after_receive(
+        BlockValidationTest::for_epoch0_account(),
+        Epoch::Epoch2,
+        Epoch::Epoch2,
+    );
+}
+
+#[test]
+fn receive_upgrades_epoch1_account_to_epoch2() {
+    assert_epoch_after_receive(
+        BlockValidationTest::for_epoch1_account(),
+        Epoch::Epoch2,
+        Epoch::Epoch2,
+    );
+}
+
+#[test]
+fn receive_does_not_downgrade_epoch() {
+    assert_epoch_after_receive(
+        BlockValidationTest::for_epoch2_account(),
+        Epoch::Epoch1,
+        Epoch::Epoch2,
+    );
+}
+
+fn assert_epoch_after_receive(test: BlockValidationTest, source_epoch: Epoch, expected: Epoch) {
+    let test = test
+        .with_pending_receive(Amount::raw(10), source_epoch)
+        .block_to_validate(|chain| chain.new_receive_block().amount_received(10).build());
+    let result = test.assert_is_valid();
+
+    assert_eq!(result.set_account_info.epoch, expected, "account epoch");
+    assert_eq!(result.set_sideband.details.epoch, expected, "block epoch");
+    assert_eq!(
+        result.set_sideband.source_epoch, source_epoch,
+        "source epoch"
+    );
+}
@@ -1,23 +1,78 @@
-use rsnano_core::{utils::seconds_since_epoch, Account, BlockEnum, PendingKey};
+use rsnano_core::{
+    utils::seconds_since_epoch, Account, AccountInfo, BlockEnum, BlockHash, PendingInfo, PendingKey,
+};
 use rsnano_store_lmdb::{Environment, Transaction};
 
 use crate::Ledger;
 
 use super::BlockValidator;
 
-pub(crate) struct BlockValidatorFactory<'a, T: Environment + 'static> {
+/// The ledger state a block is validated against
+pub(crate) trait LedgerView {
+    fn account_info(&self, account: &Account) -> Option<AccountInfo>;
+    fn pending_info(&self, key: &PendingKey) -> Option<PendingInfo>;
+    fn get_block(&self, hash: &BlockHash) -> Option<BlockEnum>;
+    fn get_frontier(&self, hash: &BlockHash) -> Option<Account>;
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool;
+    fn any_pending(&self, account: &Account) -> bool;
+}
+
+/// The state which is committed to the store, as seen by a transaction
+pub(crate) struct TxnLedgerView<'a, T: Environment + 'static> {
     ledger: &'a Ledger<T>,
     txn: &'a dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+}
+
+impl<'a, T: Environment + 'static> LedgerView for TxnLedgerView<'a, T> {
+    fn account_info(&self, account: &Account) -> Option<AccountInfo> {
+        self.ledger.account_info(self.txn, account)
+    }
+
+    fn pending_info(&self, key: &PendingKey) -> Option<PendingInfo> {
+        self.ledger.pending_info(self.txn, key)
+    }
+
+    fn get_block(&self, hash: &BlockHash) -> Option<BlockEnum> {
+        self.ledger.get_block(self.txn, hash)
+    }
+
+    fn get_frontier(&self, hash: &BlockHash) -> Option<Account> {
+        self.ledger.get_frontier(self.txn, hash)
+    }
+
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool {
+        self.ledger.block_or_pruned_exists_txn(self.txn, hash)
+    }
+
+    fn any_pending(&self, account: &Account) -> bool {
+        self.ledger.store.pending.any(self.txn, account)
+    }
+}
+
+pub(crate) struct BlockValidatorFactory<'a, T: Environment + 'static, V: LedgerView> {
+    ledger: &'a Ledger<T>,
+    view: V,
     block: &'a BlockEnum,
 }
 
-impl<'a, T: Environment + 'static> BlockValidatorFactory<'a, T> {
+impl<'a, T: Environment + 'static> BlockValidatorFactory<'a, T, TxnLedgerView<'a, T>> {
     pub(crate) fn new(
         ledger: &'a Ledger<T>,
         txn: &'a dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
         block: &'a BlockEnum,
     ) -> Self {
-        Self { ledger, txn, block }
+        Self::with_view(ledger, TxnLedgerView { ledger, txn }, block)
+    }
+}
+
+impl<'a, T: Environment + 'static, V: LedgerView> BlockValidatorFactory<'a, T, V> {
+    /// Validates against the given view instead of the committed ledger state
+    pub(crate) fn with_view(ledger: &'a Ledger<T>, view: V, block: &'a BlockEnum) -> Self {
+        Self {
+            ledger,
+            view,
+            block,
+        }
     }
 
     pub(crate) fn create_validator(&self) -> BlockValidator<'a> {
@@ -26,16 +81,14 @@ impl<'a, T: Environment + 'static> BlockValidatorFactory<'a, T> {
         let account = account.unwrap_or_default();
         let previous_block = self.load_previous_block();
         let source_block = self.block.source_or_link();
-        let source_block_exists = !source_block.is_zero()
-            && self
-                .ledger
-                .block_or_pruned_exists_txn(self.txn, &source_block);
+        let source_block_exists =
+            !source_block.is_zero() && self.view.block_or_pruned_exists(&source_block);
 
         let pending_receive_info = if source_block.is_zero() {
             None
         } else {
-            self.ledger
-                .pending_info(self.txn, &PendingKey::new(account, source_block))
+            self.view
+                .pending_info(&PendingKey::new(account, source_block))
         };
 
         BlockValidator {
@@ -44,12 +97,10 @@ impl<'a, T: Environment + 'static> BlockValidatorFactory<'a, T> {
             work: &self.ledger.constants.work,
             account,
             frontier_missing,
-            block_exists: self
-                .ledger
-                .block_or_pruned_exists_txn(self.txn, &self.block.hash()),
-            old_account_info: self.ledger.account_info(self.txn, &account),
+            block_exists: self.view.block_or_pruned_exists(&self.block.hash()),
+            old_account_info: self.view.account_info(&account),
             pending_receive_info,
-            any_pending_exists: self.ledger.store.pending.any(self.txn, &account),
+            any_pending_exists: self.view.any_pending(&account),
             source_block_exists,
             previous_block,
             seconds_since_epoch: seconds_since_epoch(),
@@ -59,17 +110,13 @@ impl<'a, T: Environment + 'static> BlockValidatorFactory<'a, T> {
     fn get_account(&self) -> Option<Account> {
         match self.block {
             BlockEnum::LegacyOpen(_) | BlockEnum::State(_) => Some(self.block.account()),
-            _ => self.get_account_from_frontier_table(),
+            _ => self.view.get_frontier(&self.block.previous()),
         }
     }
 
-    fn get_account_from_frontier_table(&self) -> Option<Account> {
-        self.ledger.get_frontier(self.txn, &self.block.previous())
-    }
-
     fn load_previous_block(&self) -> Option<BlockEnum> {
         if !self.block.previous().is_zero() {
-            self.ledger.get_block(self.txn, &self.block.previous())
+            self.view.get_block(&self.block.previous())
         } else {
             None
         }
@@ -79,7 +126,7 @@ impl<'a, T: Environment + 'static> BlockValidatorFactory<'a, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rsnano_core::{AccountInfo, BlockBuilder, BlockHash, Link, PendingInfo};
+    use rsnano_core::{BlockBuilder, Link};
 
     #[test]
     fn block_for_unknown_account() {
@@ -3,7 +3,7 @@ use std::sync::atomic::Ordering;
 use rsnano_core::{Account, Amount};
 use rsnano_store_lmdb::{Environment, LmdbWriteTransaction};
 
-use crate::Ledger;
+use crate::{Ledger, RepWeightThresholdCrossed};
 
 use super::rollback_planner::RollbackInstructions;
 
@@ -49,6 +49,35 @@ impl<'a, T: Environment> RollbackInstructionsExecutor<'a, T> {
         if let Some(hash) = self.instructions.clear_successor {
             self.ledger.store.block.successor_clear(self.txn, &hash);
         }
+
+        if self.ledger.height_index_enabled() {
+            // Only the head block can be rolled back
+            self.ledger.store.block.del_height(
+                self.txn,
+                &self.instructions.account,
+                self.instructions.old_account_info.block_count,
+            );
+        }
+
+        if self.ledger.timestamp_index_enabled() {
+            self.ledger.store.block.del_timestamp(
+                self.txn,
+                self.instructions.block_timestamp,
+                &self.instructions.block_hash,
+            );
+        }
+
+        if self.ledger.destination_index_enabled() {
+            // Rolling back a send removes the pending entry which the send created
+            if let Some(key) = &self.instructions.remove_pending {
+                if key.hash == self.instructions.block_hash {
+                    self.ledger
+                        .store
+                        .block
+                        .del_destination(self.txn, &key.account, &key.hash);
+                }
+            }
+        }
     }
 
     fn update_account_table(&mut self) {
@@ -71,34 +100,61 @@ impl<'a, T: Environment> RollbackInstructionsExecutor<'a, T> {
 
     fn update_pending_table(&mut self) {
         if let Some(pending_key) = &self.instructions.remove_pending {
+            if self.ledger.cache.receivable.is_enabled() {
+                if let Some(info) = self.ledger.store.pending.get(self.txn, pending_key) {
+                    self.ledger
+                        .cache
+                        .receivable
+                        .remove(&pending_key.account, info.amount);
+                }
+            }
             self.ledger.store.pending.del(self.txn, pending_key);
+            self.ledger
+                .receivable_removed(self.txn, &pending_key.account);
         }
         if let Some((key, info)) = &self.instructions.add_pending {
             self.ledger.store.pending.put(self.txn, key, info);
+            self.ledger.cache.receivable.add(&key.account, info.amount);
+            self.ledger.receivable_added(self.txn, key, info);
         }
     }
 
     fn roll_back_representative_cache(&self) {
-        if let Some(previous_rep) = &self.instructions.new_representative {
-            self.roll_back_change_in_representative_cache(previous_rep);
+        let crossed = if let Some(previous_rep) = &self.instructions.new_representative {
+            self.roll_back_change_in_representative_cache(previous_rep)
         } else {
             self.roll_back_receive_in_representative_cache()
+        };
+        for event in &crossed {
+            self.ledger.observer.rep_weight_threshold_crossed(event);
         }
     }
 
-    fn roll_back_change_in_representative_cache(&self, previous_representative: &Account) {
-        self.ledger.cache.rep_weights.representation_add_dual(
-            self.instructions.old_account_info.representative,
+    fn roll_back_change_in_representative_cache(
+        &self,
+        previous_representative: &Account,
+    ) -> Vec<RepWeightThresholdCrossed> {
+        let old_representative = &self.instructions.old_account_info.representative;
+        let crossed = self.ledger.cache.rep_weights.representation_add_dual(
+            *old_representative,
             Amount::zero().wrapping_sub(self.instructions.old_account_info.balance),
             *previous_representative,
             self.instructions.new_balance,
         );
+        if old_representative != previous_representative {
+            self.ledger.observer.representative_changed(
+                &self.instructions.account,
+                old_representative,
+                previous_representative,
+            );
+        }
+        crossed
     }
 
-    fn roll_back_receive_in_representative_cache(&self) {
+    fn roll_back_receive_in_representative_cache(&self) -> Vec<RepWeightThresholdCrossed> {
         self.ledger.cache.rep_weights.representation_add(
             self.instructions.old_account_info.representative,
             Amount::zero().wrapping_sub(self.instructions.old_account_info.balance),
-        );
+        )
     }
 }
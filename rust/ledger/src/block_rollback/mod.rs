@@ -2,7 +2,10 @@ mod instructions_executor;
 mod planner_factory;
 mod rollback_performer;
 mod rollback_planner;
+mod rollback_simulator;
+mod rollback_walk;
 #[cfg(test)]
 mod tests;
 
 pub(crate) use rollback_performer::BlockRollbackPerformer;
+pub(crate) use rollback_simulator::RollbackSimulator;
@@ -5,7 +5,7 @@ use crate::Ledger;
 
 use super::{
     instructions_executor::RollbackInstructionsExecutor, planner_factory::RollbackPlannerFactory,
-    rollback_planner::RollbackStep,
+    rollback_planner::RollbackStep, rollback_walk::RollbackWalk,
 };
 
 pub(crate) struct BlockRollbackPerformer<'a, T: Environment + 'static> {
@@ -28,34 +28,20 @@ impl<'a, T: Environment> BlockRollbackPerformer<'a, T> {
         Ok(self.rolled_back)
     }
 
-    fn roll_back_block_and_successors(&mut self, block_hash: &BlockHash) -> anyhow::Result<()> {
-        let block = self.load_block(block_hash)?;
-        while self.block_exists(block_hash) {
-            let head_block = self.load_account_head(&block)?;
-            self.roll_back_head_block(head_block)?;
-        }
-        Ok(())
-    }
-
-    fn roll_back_head_block(&mut self, head_block: BlockEnum) -> Result<(), anyhow::Error> {
-        let planner =
-            RollbackPlannerFactory::new(self.ledger, self.txn, &head_block).create_planner()?;
-        let step = planner.roll_back_head_block()?;
-        self.execute(step, head_block)?;
-        Ok(())
+    fn get_account_info(&self, block: &BlockEnum) -> AccountInfo {
+        self.ledger
+            .account_info(self.txn, &block.account_calculated())
+            .unwrap()
     }
+}
 
-    fn execute(&mut self, step: RollbackStep, head_block: BlockEnum) -> Result<(), anyhow::Error> {
-        match step {
-            RollbackStep::RollBackBlock(instructions) => {
-                RollbackInstructionsExecutor::new(self.ledger, self.txn, &instructions).execute();
-                self.rolled_back.push(head_block);
-                Ok(())
-            }
-            RollbackStep::RequestDependencyRollback(dependency_hash) => {
-                self.roll_back_block_and_successors(&dependency_hash)
-            }
-        }
+impl<'a, T: Environment> RollbackWalk for BlockRollbackPerformer<'a, T> {
+    fn load_block(&self, block_hash: &BlockHash) -> anyhow::Result<BlockEnum> {
+        self.ledger
+            .store
+            .block
+            .get(self.txn, block_hash)
+            .ok_or_else(|| anyhow!("block not found"))
     }
 
     fn block_exists(&self, block_hash: &BlockHash) -> bool {
@@ -67,17 +53,16 @@ impl<'a, T: Environment> BlockRollbackPerformer<'a, T> {
         self.load_block(&account_info.head)
     }
 
-    fn get_account_info(&self, block: &BlockEnum) -> AccountInfo {
-        self.ledger
-            .account_info(self.txn, &block.account_calculated())
-            .unwrap()
-    }
-
-    fn load_block(&self, block_hash: &BlockHash) -> anyhow::Result<BlockEnum> {
-        self.ledger
-            .store
-            .block
-            .get(self.txn, block_hash)
-            .ok_or_else(|| anyhow!("block not found"))
+    fn roll_back_head_block(&mut self, head_block: BlockEnum) -> anyhow::Result<Option<BlockHash>> {
+        let planner =
+            RollbackPlannerFactory::new(self.ledger, self.txn, &head_block).create_planner()?;
+        match planner.roll_back_head_block()? {
+            RollbackStep::RollBackBlock(instructions) => {
+                RollbackInstructionsExecutor::new(self.ledger, self.txn, &instructions).execute();
+                self.rolled_back.push(head_block);
+                Ok(None)
+            }
+            RollbackStep::RequestDependencyRollback(dependency_hash) => Ok(Some(dependency_hash)),
+        }
     }
 }
@@ -1,6 +1,6 @@
 use rsnano_core::{
-    Account, AccountInfo, Amount, BlockEnum, BlockHash, BlockSubType, ConfirmationHeightInfo,
-    Epoch, Epochs, PendingInfo, PendingKey,
+    Account, AccountInfo, Amount, BalanceDelta, BlockEnum, BlockHash, BlockSubType,
+    ConfirmationHeightInfo, Epoch, Epochs, PendingInfo, PendingKey,
 };
 
 pub(crate) enum RollbackStep {
@@ -12,6 +12,8 @@ pub(crate) enum RollbackStep {
 /// Describes how to roll back a block
 pub(crate) struct RollbackInstructions {
     pub block_hash: BlockHash,
+    /// Local timestamp of the block, which is needed to remove it from the timestamp index
+    pub block_timestamp: u64,
     pub block_sub_type: BlockSubType,
     pub account: Account,
     pub remove_pending: Option<PendingKey>,
@@ -53,6 +55,7 @@ impl<'a> RollbackPlanner<'a> {
 
         let instructions = RollbackInstructions {
             block_hash: self.head_block.hash(),
+            block_timestamp: self.head_block.sideband().unwrap().timestamp,
             account: self.account,
             old_account_info: self.current_account_info.clone(),
             new_representative: self.previous_representative,
@@ -140,18 +143,22 @@ impl<'a> RollbackPlanner<'a> {
     }
 
     fn block_sub_type(&self) -> BlockSubType {
-        if self.current_account_info.balance < self.previous_balance() {
-            BlockSubType::Send
-        } else if self.current_account_info.balance > self.previous_balance() {
-            if self.head_block.is_open() {
-                BlockSubType::Open
-            } else {
-                BlockSubType::Receive
+        match BalanceDelta::new(self.previous_balance(), self.current_account_info.balance) {
+            BalanceDelta::Decrease(_) => BlockSubType::Send,
+            BalanceDelta::Increase(_) => {
+                if self.head_block.is_open() {
+                    BlockSubType::Open
+                } else {
+                    BlockSubType::Receive
+                }
+            }
+            BalanceDelta::Unchanged => {
+                if self.epochs.is_epoch_link(&self.head_block.link()) {
+                    BlockSubType::Epoch
+                } else {
+                    BlockSubType::Change
+                }
             }
-        } else if self.epochs.is_epoch_link(&self.head_block.link()) {
-            BlockSubType::Epoch
-        } else {
-            BlockSubType::Change
         }
     }
 
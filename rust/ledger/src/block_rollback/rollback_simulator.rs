@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+
+use rsnano_core::{Account, BlockEnum, BlockHash, PendingKey};
+use rsnano_store_lmdb::{Environment, Transaction};
+
+use crate::Ledger;
+
+use super::rollback_walk::RollbackWalk;
+
+/// Computes which blocks a rollback would remove, without changing the ledger.
+/// It walks the dependencies like `BlockRollbackPerformer`, but only records the rolled back blocks.
+pub(crate) struct RollbackSimulator<'a, T: Environment + 'static> {
+    ledger: &'a Ledger<T>,
+    txn: &'a dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    max_depth: usize,
+    /// Account heads after the simulated rollbacks. `None` if all blocks of the account were rolled back
+    heads: HashMap<Account, Option<BlockHash>>,
+    /// Pending entries which were added (`true`) or removed (`false`) by the simulated rollbacks
+    pending: HashMap<PendingKey, bool>,
+    removed: HashSet<BlockHash>,
+    rolled_back: Vec<BlockHash>,
+}
+
+impl<'a, T: Environment + 'static> RollbackSimulator<'a, T> {
+    pub(crate) fn new(
+        ledger: &'a Ledger<T>,
+        txn: &'a dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        max_depth: usize,
+    ) -> Self {
+        Self {
+            ledger,
+            txn,
+            max_depth,
+            heads: HashMap::new(),
+            pending: HashMap::new(),
+            removed: HashSet::new(),
+            rolled_back: Vec::new(),
+        }
+    }
+
+    pub(crate) fn simulate(mut self, block_hash: &BlockHash) -> anyhow::Result<Vec<BlockHash>> {
+        self.roll_back_block_and_successors(block_hash)?;
+        Ok(self.rolled_back)
+    }
+
+    fn ensure_block_is_not_confirmed(
+        &self,
+        account: &Account,
+        head: &BlockEnum,
+    ) -> anyhow::Result<()> {
+        let confirmation_height = self
+            .ledger
+            .store
+            .confirmation_height
+            .get(self.txn, account)
+            .unwrap_or_default();
+        if head.sideband().unwrap().height <= confirmation_height.height {
+            bail!("Only unconfirmed blocks can be rolled back")
+        }
+        Ok(())
+    }
+
+    fn head(&self, account: &Account) -> Option<BlockHash> {
+        match self.heads.get(account) {
+            Some(head) => *head,
+            None => self.ledger.latest(self.txn, account),
+        }
+    }
+
+    fn pending_exists(&self, key: &PendingKey) -> bool {
+        match self.pending.get(key) {
+            Some(exists) => *exists,
+            None => self.ledger.pending_info(self.txn, key).is_some(),
+        }
+    }
+
+    fn load_previous_block(&self, block: &BlockEnum) -> anyhow::Result<Option<BlockEnum>> {
+        let previous = block.previous();
+        Ok(if previous.is_zero() {
+            None
+        } else {
+            Some(self.load_block(&previous)?)
+        })
+    }
+}
+
+impl<'a, T: Environment + 'static> RollbackWalk for RollbackSimulator<'a, T> {
+    fn load_block(&self, block_hash: &BlockHash) -> anyhow::Result<BlockEnum> {
+        self.ledger
+            .store
+            .block
+            .get(self.txn, block_hash)
+            .ok_or_else(|| anyhow!("block not found"))
+    }
+
+    fn block_exists(&self, block_hash: &BlockHash) -> bool {
+        !self.removed.contains(block_hash) && self.ledger.store.block.exists(self.txn, block_hash)
+    }
+
+    fn load_account_head(&self, block: &BlockEnum) -> anyhow::Result<BlockEnum> {
+        let head_hash = self
+            .head(&block.account_calculated())
+            .ok_or_else(|| anyhow!("account not found"))?;
+        self.load_block(&head_hash)
+    }
+
+    fn roll_back_head_block(&mut self, head: BlockEnum) -> anyhow::Result<Option<BlockHash>> {
+        let account = head.account_calculated();
+        self.ensure_block_is_not_confirmed(&account, &head)?;
+        let previous = self.load_previous_block(&head)?;
+        let delta = head.balance_delta(previous.as_ref());
+
+        if delta.is_decrease() {
+            let key = PendingKey::new(head.destination_or_link(), head.hash());
+            if !self.pending_exists(&key) {
+                let destination_head = self
+                    .head(&key.account)
+                    .ok_or_else(|| anyhow!("no latest block for destination"))?;
+                return Ok(Some(destination_head));
+            }
+            self.pending.insert(key, false);
+        } else if delta.is_increase() {
+            self.pending
+                .insert(PendingKey::new(account, head.source_or_link()), true);
+        }
+
+        if self.rolled_back.len() >= self.max_depth {
+            bail!("rollback would remove more than {} blocks", self.max_depth);
+        }
+
+        self.heads
+            .insert(account, previous.as_ref().map(|b| b.hash()));
+        self.removed.insert(head.hash());
+        self.rolled_back.push(head.hash());
+        Ok(None)
+    }
+}
@@ -0,0 +1,25 @@
+use rsnano_core::{BlockEnum, BlockHash};
+
+/// The dependency walk of a rollback, which is shared by `BlockRollbackPerformer` and
+/// `RollbackSimulator`: the head of the account is rolled back until the block is gone,
+/// and a received send rolls back the destination account before the send.
+pub(crate) trait RollbackWalk {
+    fn load_block(&self, block_hash: &BlockHash) -> anyhow::Result<BlockEnum>;
+    fn block_exists(&self, block_hash: &BlockHash) -> bool;
+    fn load_account_head(&self, block: &BlockEnum) -> anyhow::Result<BlockEnum>;
+
+    /// Rolls back the head block of an account. Returns the block that has
+    /// to be rolled back first, if the head block is a received send
+    fn roll_back_head_block(&mut self, head_block: BlockEnum) -> anyhow::Result<Option<BlockHash>>;
+
+    fn roll_back_block_and_successors(&mut self, block_hash: &BlockHash) -> anyhow::Result<()> {
+        let block = self.load_block(block_hash)?;
+        while self.block_exists(block_hash) {
+            let head_block = self.load_account_head(&block)?;
+            if let Some(dependency) = self.roll_back_head_block(head_block)? {
+                self.roll_back_block_and_successors(&dependency)?;
+            }
+        }
+        Ok(())
+    }
+}
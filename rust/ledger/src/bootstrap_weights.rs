@@ -0,0 +1,130 @@
+use rsnano_core::{
+    utils::{Deserialize, MemoryStream, Serialize, Stream, StreamAdapter},
+    Account, Amount,
+};
+use std::{collections::HashMap, path::Path};
+
+/// Representative weights which are used while the ledger is still bootstrapping.
+/// The binary format is the same as the one of the `rep_weights_*.bin` files:
+/// A big endian u128 with the block count, followed by (account, weight) pairs.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct BootstrapWeights {
+    /// The bootstrap weights are used until the ledger contains this many blocks
+    pub max_blocks: u64,
+    pub weights: HashMap<Account, Amount>,
+}
+
+impl BootstrapWeights {
+    pub fn new(max_blocks: u64, weights: HashMap<Account, Amount>) -> Self {
+        Self {
+            max_blocks,
+            weights,
+        }
+    }
+
+    /// Writes the entries sorted by descending weight, so that the output is deterministic
+    pub fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()> {
+        stream.write_bytes(&(self.max_blocks as u128).to_be_bytes())?;
+        for (account, weight) in self.sorted_weights() {
+            account.serialize(stream)?;
+            weight.serialize(stream)?;
+        }
+        Ok(())
+    }
+
+    /// Reads entries until the stream is exhausted. An incomplete trailing entry is ignored.
+    pub fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let mut buffer = [0u8; 16];
+        stream.read_bytes(&mut buffer, 16)?;
+        let max_blocks = u64::try_from(u128::from_be_bytes(buffer))?;
+
+        let mut weights = HashMap::new();
+        while stream.in_avail()? >= Account::serialized_size() + Amount::serialized_size() {
+            let account = Account::deserialize(stream)?;
+            let weight = Amount::deserialize(stream)?;
+            weights.insert(account, weight);
+        }
+
+        Ok(Self::new(max_blocks, weights))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut stream = MemoryStream::new();
+        self.serialize(&mut stream).unwrap();
+        stream.to_vec()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        Self::deserialize(&mut StreamAdapter::new(bytes))
+    }
+
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn read_from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    fn sorted_weights(&self) -> Vec<(&Account, &Amount)> {
+        let mut sorted: Vec<_> = self.weights.iter().collect();
+        sorted.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_empty() {
+        let weights = BootstrapWeights::new(42, HashMap::new());
+        let bytes = weights.to_bytes();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(bytes[15], 42);
+        assert_eq!(BootstrapWeights::from_bytes(&bytes).unwrap(), weights);
+    }
+
+    #[test]
+    fn serialize_sorted_by_weight() {
+        let mut map = HashMap::new();
+        map.insert(Account::from(1), Amount::raw(100));
+        map.insert(Account::from(2), Amount::raw(300));
+        map.insert(Account::from(3), Amount::raw(200));
+        let weights = BootstrapWeights::new(1000, map);
+
+        let bytes = weights.to_bytes();
+
+        assert_eq!(bytes.len(), 16 + 3 * 48);
+        assert_eq!(&bytes[16..48], Account::from(2).as_bytes());
+        assert_eq!(&bytes[64..96], Account::from(3).as_bytes());
+        assert_eq!(&bytes[112..144], Account::from(1).as_bytes());
+        assert_eq!(BootstrapWeights::from_bytes(&bytes).unwrap(), weights);
+    }
+
+    #[test]
+    fn ignore_incomplete_trailing_entry() {
+        let mut map = HashMap::new();
+        map.insert(Account::from(1), Amount::raw(100));
+        let weights = BootstrapWeights::new(7, map);
+        let mut bytes = weights.to_bytes();
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        assert_eq!(BootstrapWeights::from_bytes(&bytes).unwrap(), weights);
+    }
+
+    #[test]
+    fn deserialize_fails_without_header() {
+        assert!(BootstrapWeights::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn load_bundled_live_weights() {
+        let bytes = include_bytes!("../../../rep_weights_live.bin");
+        let weights = BootstrapWeights::from_bytes(bytes).unwrap();
+        assert!(weights.max_blocks > 0);
+        assert!(!weights.weights.is_empty());
+    }
+}
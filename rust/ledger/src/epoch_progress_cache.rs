@@ -0,0 +1,196 @@
+use std::cmp::max;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use rsnano_core::{Account, Epoch};
+
+/// Numbers for `Ledger::epoch_upgrade_progress`, which are kept up to date on every account
+/// and receivable change, so that the progress can be reported without scanning the accounts
+/// and pending tables. The accounts per epoch are counted while generating the account count
+/// on startup, the unopened accounts while generating the receivable cache.
+pub struct EpochProgressCache {
+    accounts_enabled: AtomicBool,
+    accounts_per_epoch: Mutex<BTreeMap<Epoch, u64>>,
+    unopened_enabled: AtomicBool,
+    /// Highest epoch of the receivables of each unopened account
+    unopened: Mutex<HashMap<Account, Epoch>>,
+}
+
+impl EpochProgressCache {
+    pub fn new() -> Self {
+        Self {
+            accounts_enabled: AtomicBool::new(false),
+            accounts_per_epoch: Mutex::new(BTreeMap::new()),
+            unopened_enabled: AtomicBool::new(false),
+            unopened: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn enable_accounts(&self) {
+        self.accounts_enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn enable_unopened(&self) {
+        self.unopened_enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn unopened_enabled(&self) -> bool {
+        self.unopened_enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn merge_accounts(&self, counts: BTreeMap<Epoch, u64>) {
+        let mut guard = self.accounts_per_epoch.lock().unwrap();
+        for (epoch, count) in counts {
+            *guard.entry(epoch).or_default() += count;
+        }
+    }
+
+    pub fn merge_unopened(&self, unopened: HashMap<Account, Epoch>) {
+        let mut guard = self.unopened.lock().unwrap();
+        for (account, epoch) in unopened {
+            let highest = guard.entry(account).or_insert(epoch);
+            *highest = max(*highest, epoch);
+        }
+    }
+
+    /// `None` means the account doesn't exist (anymore)
+    pub fn account_changed(&self, old_epoch: Option<Epoch>, new_epoch: Option<Epoch>) {
+        if old_epoch == new_epoch {
+            return;
+        }
+        let mut guard = self.accounts_per_epoch.lock().unwrap();
+        if let Some(old) = old_epoch {
+            if let Some(count) = guard.get_mut(&old) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    guard.remove(&old);
+                }
+            }
+        }
+        if let Some(new) = new_epoch {
+            *guard.entry(new).or_default() += 1;
+        }
+    }
+
+    /// An unopened account received a receivable of the given epoch
+    pub fn receivable_added(&self, account: &Account, epoch: Epoch) {
+        if !self.unopened_enabled() {
+            return;
+        }
+        let mut guard = self.unopened.lock().unwrap();
+        let highest = guard.entry(*account).or_insert(epoch);
+        *highest = max(*highest, epoch);
+    }
+
+    /// Sets the highest receivable epoch of an unopened account. `None` removes the account,
+    /// because it was opened or has no receivables left.
+    pub fn set_unopened(&self, account: &Account, highest_epoch: Option<Epoch>) {
+        if !self.unopened_enabled() {
+            return;
+        }
+        let mut guard = self.unopened.lock().unwrap();
+        match highest_epoch {
+            Some(epoch) => {
+                guard.insert(*account, epoch);
+            }
+            None => {
+                guard.remove(account);
+            }
+        }
+    }
+
+    pub fn is_unopened(&self, account: &Account) -> bool {
+        self.unopened_enabled() && self.unopened.lock().unwrap().contains_key(account)
+    }
+
+    /// Returns `None` if the accounts per epoch weren't generated on startup
+    pub fn accounts_per_epoch(&self) -> Option<BTreeMap<Epoch, u64>> {
+        if !self.accounts_enabled.load(Ordering::SeqCst) {
+            return None;
+        }
+        Some(self.accounts_per_epoch.lock().unwrap().clone())
+    }
+
+    /// Unopened accounts with receivables ordered by account. Returns `None` if they weren't
+    /// generated on startup
+    pub fn unopened(&self, limit: usize) -> Option<Vec<(Account, Epoch)>> {
+        if !self.unopened_enabled() {
+            return None;
+        }
+        let mut result: Vec<_> = self
+            .unopened
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(account, epoch)| (*account, *epoch))
+            .collect();
+        result.sort_unstable_by_key(|(account, _)| *account);
+        result.truncate(limit);
+        Some(result)
+    }
+}
+
+impl Default for EpochProgressCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_by_default() {
+        let cache = EpochProgressCache::new();
+        cache.receivable_added(&Account::from(1), Epoch::Epoch1);
+        assert_eq!(cache.accounts_per_epoch(), None);
+        assert_eq!(cache.unopened(usize::MAX), None);
+        assert!(!cache.is_unopened(&Account::from(1)));
+    }
+
+    #[test]
+    fn move_account_between_epochs() {
+        let cache = EpochProgressCache::new();
+        cache.enable_accounts();
+        cache.merge_accounts(BTreeMap::from([(Epoch::Epoch0, 2)]));
+
+        cache.account_changed(Some(Epoch::Epoch0), Some(Epoch::Epoch1));
+        cache.account_changed(None, Some(Epoch::Epoch1));
+
+        assert_eq!(
+            cache.accounts_per_epoch(),
+            Some(BTreeMap::from([(Epoch::Epoch0, 1), (Epoch::Epoch1, 2)]))
+        );
+
+        cache.account_changed(Some(Epoch::Epoch0), None);
+        assert_eq!(
+            cache.accounts_per_epoch(),
+            Some(BTreeMap::from([(Epoch::Epoch1, 2)]))
+        );
+    }
+
+    #[test]
+    fn keep_highest_receivable_epoch_of_unopened_accounts() {
+        let cache = EpochProgressCache::new();
+        cache.enable_unopened();
+        cache.receivable_added(&Account::from(2), Epoch::Epoch1);
+        cache.receivable_added(&Account::from(2), Epoch::Epoch0);
+        cache.receivable_added(&Account::from(1), Epoch::Epoch0);
+
+        assert_eq!(
+            cache.unopened(usize::MAX),
+            Some(vec![
+                (Account::from(1), Epoch::Epoch0),
+                (Account::from(2), Epoch::Epoch1)
+            ])
+        );
+
+        cache.set_unopened(&Account::from(2), None);
+        assert_eq!(
+            cache.unopened(1),
+            Some(vec![(Account::from(1), Epoch::Epoch0)])
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    cmp::max,
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use rsnano_core::{
+    Account, AccountInfo, Amount, BlockBuilder, BlockDetails, BlockEnum, BlockHash, Epoch, Epochs,
+    KeyPair, Root, WorkVersion,
+};
+use rsnano_store_lmdb::{Environment, EnvironmentWrapper};
+
+use crate::{Ledger, ProcessResult};
+
+/// Number of accounts per epoch
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct EpochUpgradeProgress {
+    pub accounts_per_epoch: BTreeMap<Epoch, u64>,
+
+    /// Number of unopened accounts with receivables below the latest epoch.
+    /// They are included in `unupgraded`.
+    pub unopened_with_receivables: u64,
+
+    /// Number of accounts which are not yet upgraded to the latest epoch
+    pub unupgraded: u64,
+}
+
+/// Creates epoch blocks for all accounts which are one epoch below the target epoch.
+/// Unopened accounts with receivables of the previous epoch are opened with an epoch block.
+/// The blocks are handed to `process`, which is expected to pass them through the block
+/// processor, so that they are flooded and confirmed like any other local block.
+/// The work of up to `threads` blocks is generated in parallel.
+pub struct EpochUpgrader<'a, T: Environment + 'static = EnvironmentWrapper> {
+    ledger: &'a Ledger<T>,
+    generate_work: Box<dyn Fn(Root, u64) -> Option<u64> + Send + Sync + 'a>,
+    process: Box<dyn Fn(BlockEnum) -> Result<(), ProcessResult> + 'a>,
+    signer: KeyPair,
+    epoch: Epoch,
+    threads: usize,
+    stopped: AtomicBool,
+}
+
+impl<'a, T: Environment + 'static> EpochUpgrader<'a, T> {
+    pub fn new(
+        ledger: &'a Ledger<T>,
+        signer: KeyPair,
+        epoch: Epoch,
+        generate_work: impl Fn(Root, u64) -> Option<u64> + Send + Sync + 'a,
+        process: impl Fn(BlockEnum) -> Result<(), ProcessResult> + 'a,
+    ) -> anyhow::Result<Self> {
+        let Some(expected_signer) = ledger.constants.epochs.signer(epoch) else {
+            bail!("unknown epoch: {:?}", epoch);
+        };
+
+        if signer.public_key() != *expected_signer {
+            bail!("the given key is not the signer of {:?}", epoch);
+        }
+
+        Ok(Self {
+            ledger,
+            generate_work: Box::new(generate_work),
+            process: Box::new(process),
+            signer,
+            epoch,
+            threads: 1,
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = max(threads, 1);
+    }
+
+    /// Upgrades at most `count_limit` accounts and returns the number of accounts that were upgraded
+    pub fn upgrade(&self, count_limit: usize) -> u64 {
+        let accounts = self
+            .eligible_accounts(count_limit)
+            .into_iter()
+            .map(|(account, info)| (account, Some(info)));
+        let (mut upgraded, completed) = self.upgrade_accounts(accounts);
+        if !completed {
+            return upgraded;
+        }
+
+        let remaining = count_limit.saturating_sub(upgraded as usize);
+        let unopened = self
+            .eligible_unopened_accounts(remaining)
+            .into_iter()
+            .map(|account| (account, None));
+        upgraded += self.upgrade_accounts(unopened).0;
+        upgraded
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns the number of upgraded accounts and false if the upgrade was aborted
+    fn upgrade_accounts(
+        &self,
+        accounts: impl Iterator<Item = (Account, Option<AccountInfo>)>,
+    ) -> (u64, bool) {
+        let mut upgraded = 0;
+        let accounts: Vec<_> = accounts.collect();
+        for chunk in accounts.chunks(self.threads) {
+            if self.stopped.load(Ordering::SeqCst) {
+                return (upgraded, false);
+            }
+
+            let blocks = self.create_blocks(chunk);
+            for block in blocks {
+                let Some(block) = block else {
+                    // None means the work generation was stopped
+                    return (upgraded, false);
+                };
+                match (self.process)(block) {
+                    Ok(()) => upgraded += 1,
+                    // The account changed since it was selected. It will be picked up by the next run
+                    Err(ProcessResult::Fork) => {}
+                    Err(_) => return (upgraded, false),
+                }
+            }
+        }
+        (upgraded, true)
+    }
+
+    /// Creates the epoch blocks of the given accounts and generates their work in parallel.
+    /// `None` as the account info creates an open block.
+    fn create_blocks(&self, accounts: &[(Account, Option<AccountInfo>)]) -> Vec<Option<BlockEnum>> {
+        let roots: Vec<Root> = accounts
+            .iter()
+            .map(|(account, info)| match info {
+                Some(info) => info.head.into(),
+                None => (*account).into(),
+            })
+            .collect();
+        let work = self.generate_work(&roots);
+        accounts
+            .iter()
+            .zip(work)
+            .map(|((account, info), work)| Some(self.create_block(account, info, work?)))
+            .collect()
+    }
+
+    fn create_block(&self, account: &Account, info: &Option<AccountInfo>, work: u64) -> BlockEnum {
+        let (previous, representative, balance) = match info {
+            Some(info) => (info.head, info.representative, info.balance),
+            None => (BlockHash::zero(), Account::zero(), Amount::zero()),
+        };
+        BlockBuilder::state()
+            .account(*account)
+            .previous(previous)
+            .representative(representative)
+            .balance(balance)
+            .link(*self.ledger.constants.epochs.link(self.epoch).unwrap())
+            .sign(&self.signer)
+            .work(work)
+            .build()
+    }
+
+    fn eligible_accounts(&self, count_limit: usize) -> Vec<(Account, AccountInfo)> {
+        let txn = self.ledger.read_txn();
+        let mut accounts = Vec::new();
+        let mut it = self.ledger.store.account.begin(&txn);
+        while let Some((account, info)) = it.current() {
+            if accounts.len() >= count_limit {
+                break;
+            }
+            if Epochs::is_sequential(info.epoch, self.epoch) {
+                accounts.push((*account, info.clone()));
+            }
+            it.next();
+        }
+        accounts
+    }
+
+    fn eligible_unopened_accounts(&self, count_limit: usize) -> Vec<Account> {
+        let txn = self.ledger.read_txn();
+        self.ledger
+            .unopened_accounts_with_receivables(&txn, usize::MAX)
+            .into_iter()
+            .filter(|(_, epoch)| Epochs::is_sequential(*epoch, self.epoch))
+            .map(|(account, _)| account)
+            .take(count_limit)
+            .collect()
+    }
+
+    /// `None` means the work generation was stopped
+    fn generate_work(&self, roots: &[Root]) -> Vec<Option<u64>> {
+        let details = BlockDetails::new(self.epoch, false, false, true);
+        let difficulty = self
+            .ledger
+            .constants
+            .work
+            .threshold2(WorkVersion::Work1, &details);
+        let generate_work = &self.generate_work;
+        if roots.len() == 1 {
+            return vec![generate_work(roots[0], difficulty)];
+        }
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = roots
+                .iter()
+                .map(|root| scope.spawn(move || generate_work(*root, difficulty)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+}
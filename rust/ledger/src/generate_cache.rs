@@ -5,6 +5,10 @@ pub struct GenerateCache {
     pub unchecked_count: bool,
     pub account_count: bool,
     pub block_count: bool,
+    /// Sums up the receivable amounts per account. Disabled by default, because it scans the whole pending table
+    pub receivable: bool,
+    /// Order independent digest over the account heads and confirmation heights
+    pub checksum: bool,
 }
 
 impl GenerateCache {
@@ -15,6 +19,8 @@ impl GenerateCache {
             unchecked_count: true,
             account_count: true,
             block_count: true,
+            receivable: false,
+            checksum: true,
         }
     }
 
@@ -23,6 +29,7 @@ impl GenerateCache {
         self.cemented_count = true;
         self.unchecked_count = true;
         self.account_count = true;
+        self.checksum = true;
     }
 }
 
@@ -0,0 +1,81 @@
+use rsnano_store_lmdb::{STORE_VERSION_CURRENT, STORE_VERSION_MINIMUM};
+use std::fmt::Display;
+
+/// An inconsistency in the ledger which was found by the startup sanity check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntegrityProblem {
+    GenesisMissing,
+    /// The version table is missing or contains a version which this node doesn't support
+    UnsupportedVersion(Option<i32>),
+    AccountCountMismatch {
+        cached: u64,
+        stored: u64,
+    },
+    /// The block count of all account infos doesn't match the number of stored and pruned blocks
+    BlockCountMismatch {
+        cached: u64,
+        stored: u64,
+    },
+}
+
+impl IntegrityProblem {
+    /// What the node operator can do to fix the problem
+    pub fn repair_hint(&self) -> &'static str {
+        match self {
+            IntegrityProblem::GenesisMissing => {
+                "Check that the --network and --data_path arguments are correct. If they are, restore the ledger from a backup."
+            }
+            IntegrityProblem::UnsupportedVersion(_) => {
+                "The ledger was written by a different node version. Use a matching node version or restore the ledger from a backup."
+            }
+            IntegrityProblem::AccountCountMismatch { .. }
+            | IntegrityProblem::BlockCountMismatch { .. } => {
+                "The counts are recalculated from the account table on every start, so the account and block tables disagree. Run nano_node --debug_validate_blocks to find the affected accounts and restore the ledger from a backup."
+            }
+        }
+    }
+}
+
+impl Display for IntegrityProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityProblem::GenesisMissing => write!(f, "genesis block not found"),
+            IntegrityProblem::UnsupportedVersion(version) => match version {
+                Some(version) => write!(
+                    f,
+                    "database version {} is not supported, it must be between {} and {}",
+                    version, STORE_VERSION_MINIMUM, STORE_VERSION_CURRENT
+                ),
+                None => write!(f, "database version is missing"),
+            },
+            IntegrityProblem::AccountCountMismatch { cached, stored } => write!(
+                f,
+                "{} accounts were counted, but the account table contains {}",
+                cached, stored
+            ),
+            IntegrityProblem::BlockCountMismatch { cached, stored } => write!(
+                f,
+                "the accounts contain {} blocks, but {} blocks are stored or pruned",
+                cached, stored
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_hint() {
+        let problem = IntegrityProblem::BlockCountMismatch {
+            cached: 10,
+            stored: 8,
+        };
+        assert_eq!(
+            problem.to_string(),
+            "the accounts contain 10 blocks, but 8 blocks are stored or pruned"
+        );
+        assert!(problem.repair_hint().contains("--debug_validate_blocks"));
+    }
+}
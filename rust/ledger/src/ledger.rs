@@ -1,30 +1,41 @@
 use crate::{
+    account_checksum,
     block_insertion::{BlockInserter, BlockValidatorFactory},
-    BlockRollbackPerformer, GenerateCache, LedgerCache, LedgerConstants, RepWeights,
-    RepresentativeBlockFinder,
+    AccountDetails, AccountHistoryEntry, BlockInfo, BlockRollbackPerformer, BootstrapWeights,
+    EpochUpgradeProgress, GenerateCache, IntegrityProblem, LedgerCache, LedgerChecksumCache,
+    LedgerConstants, LedgerOverlay, LedgerReadPool, LedgerStatsReport, ReceivableDetails,
+    ReceivableSummary, RecentlyModifiedAccounts, RepWeightThresholdCrossed, RepWeights,
+    RepresentativeBlockFinder, RollbackSimulator, SubtypeMismatch,
 };
+use once_cell::sync::OnceCell;
 use rand::{thread_rng, Rng};
 use rsnano_core::{
-    utils::seconds_since_epoch, Account, AccountInfo, Amount, Block, BlockChainSection, BlockEnum,
-    BlockHash, BlockSubType, BlockType, ConfirmationHeightInfo, Epoch, Link, PendingInfo,
-    PendingKey, QualifiedRoot, Root,
+    utils::seconds_since_epoch, Account, AccountInfo, Amount, BalanceDelta, Block,
+    BlockChainSection, BlockEnum, BlockHash, BlockSubType, BlockType, ConfirmationHeightInfo,
+    Epoch, LedgerChecksum, Link, PendingInfo, PendingKey, QualifiedRoot, Root, StateBlock,
 };
 use rsnano_store_lmdb::{
-    ConfiguredAccountDatabaseBuilder, ConfiguredBlockDatabaseBuilder,
+    BatchWriteGuard, ConfiguredAccountDatabaseBuilder, ConfiguredBlockDatabaseBuilder,
     ConfiguredFrontierDatabaseBuilder, ConfiguredPendingDatabaseBuilder,
     ConfiguredPrunedDatabaseBuilder, Environment, EnvironmentStub, EnvironmentWrapper,
     LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore,
     LmdbFrontierStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore,
-    LmdbReadTransaction, LmdbStore, LmdbVersionStore, LmdbWriteTransaction, Transaction,
+    LmdbReadTransaction, LmdbRollbackJournalStore, LmdbStore, LmdbVersionStore,
+    LmdbWriteTransaction, PlannedRollback, Transaction, STORE_VERSION_CURRENT,
+    STORE_VERSION_MINIMUM,
 };
 
 use std::{
+    cmp::max,
     collections::{BTreeMap, HashMap},
+    future::Future,
+    io::Write,
     ops::Deref,
     sync::{
-        atomic::{AtomicBool, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc, Mutex,
     },
+    time::Duration,
 };
 
 use super::DependentBlocksFinder;
@@ -36,7 +47,7 @@ pub struct UncementedInfo {
     pub account: Account,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, FromPrimitive)]
 #[repr(u8)]
 pub enum ProcessResult {
     Progress,      // Hasn't been seen before, signed correctly
@@ -55,6 +66,54 @@ pub enum ProcessResult {
     RepresentativeMismatch, // Representative is changed when it is not allowed
     BlockPosition,          // This block cannot follow the previous block
     InsufficientWork, // Insufficient work for this block, even though it passed the minimal validation
+    /// The height of the block would not fit into the sideband
+    HeightOverflow,
+    /// The local timestamp of the block is too far in the future
+    TimestampInFuture,
+    /// Fork of a block which is already cemented.
+    /// Only returned if the rejection of confirmed forks is enabled
+    ConfirmedFork,
+    /// Too many blocks of the account in the current block processor batch.
+    /// The block is deferred to the next batch
+    AccountBatchLimit,
+}
+
+impl ProcessResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessResult::Progress => "progress",
+            ProcessResult::BadSignature => "bad_signature",
+            ProcessResult::Old => "old",
+            ProcessResult::NegativeSpend => "negative_spend",
+            ProcessResult::Fork => "fork",
+            ProcessResult::Unreceivable => "unreceivable",
+            ProcessResult::GapPrevious => "gap_previous",
+            ProcessResult::GapSource => "gap_source",
+            ProcessResult::GapEpochOpenPending => "gap_epoch_open_pending",
+            ProcessResult::OpenedBurnAccount => "opened_burn_account",
+            ProcessResult::BalanceMismatch => "balance_mismatch",
+            ProcessResult::RepresentativeMismatch => "representative_mismatch",
+            ProcessResult::BlockPosition => "block_position",
+            ProcessResult::InsufficientWork => "insufficient_work",
+            ProcessResult::HeightOverflow => "height_overflow",
+            ProcessResult::TimestampInFuture => "timestamp_in_future",
+            ProcessResult::ConfirmedFork => "confirmed_fork",
+            ProcessResult::AccountBatchLimit => "account_batch_limit",
+        }
+    }
+
+    /// The block may be processed successfully later, when the missing
+    /// dependencies arrived or the fork was resolved
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ProcessResult::GapPrevious
+                | ProcessResult::GapSource
+                | ProcessResult::GapEpochOpenPending
+                | ProcessResult::Fork
+                | ProcessResult::AccountBatchLimit
+        )
+    }
 }
 
 pub trait LedgerObserver: Send + Sync {
@@ -62,6 +121,15 @@ pub trait LedgerObserver: Send + Sync {
     fn block_rolled_back(&self, _block_type: BlockSubType) {}
     fn block_rolled_back2(&self, _block: &BlockEnum, _is_epoch: bool) {}
     fn block_added(&self, _block: &BlockEnum, _is_epoch: bool) {}
+    /// The representative of `account` was changed by a new block or by a rollback
+    fn representative_changed(
+        &self,
+        _account: &Account,
+        _old_representative: &Account,
+        _new_representative: &Account,
+    ) {
+    }
+    fn rep_weight_threshold_crossed(&self, _event: &RepWeightThresholdCrossed) {}
 }
 
 pub struct NullLedgerObserver {}
@@ -74,15 +142,26 @@ impl NullLedgerObserver {
 
 impl LedgerObserver for NullLedgerObserver {}
 
+/// Pruning commits after `batch_size` pruned blocks or after this time, whichever comes first
+const PRUNING_BATCH_DURATION: Duration = Duration::from_millis(500);
+
+/// A journaled rollback commits its progress after this many rolled back blocks
+const ROLLBACK_JOURNAL_BATCH_SIZE: usize = 1000;
+
 pub struct Ledger<T: Environment + 'static = EnvironmentWrapper> {
     pub store: Arc<LmdbStore<T>>,
     pub cache: Arc<LedgerCache>,
     pub constants: LedgerConstants,
     pub observer: Arc<dyn LedgerObserver>,
     pruning: AtomicBool,
+    timestamp_index: AtomicBool,
+    destination_index: AtomicBool,
     bootstrap_weight_max_blocks: AtomicU64,
+    max_rollback_depth: AtomicUsize,
+    reject_confirmed_forks: AtomicBool,
     pub check_bootstrap_weights: AtomicBool,
     pub bootstrap_weights: Mutex<HashMap<Account, Amount>>,
+    read_pool: OnceCell<LedgerReadPool<T>>,
 }
 
 impl Ledger<EnvironmentStub> {
@@ -164,12 +243,13 @@ impl NullLedgerBuilder {
         let store = LmdbStore {
             env: env.clone(),
             account: Arc::new(LmdbAccountStore::new(env.clone()).unwrap()),
-            block: Arc::new(LmdbBlockStore::new(env.clone()).unwrap()),
+            block: Arc::new(LmdbBlockStore::new(env.clone(), false).unwrap()),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone()).unwrap()),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone()).unwrap()),
             frontier: Arc::new(LmdbFrontierStore::new(env.clone()).unwrap()),
             online_weight: Arc::new(LmdbOnlineWeightStore::new(env.clone()).unwrap()),
             peer: Arc::new(LmdbPeerStore::new(env.clone()).unwrap()),
+            rollback_journal: Arc::new(LmdbRollbackJournalStore::new(env.clone()).unwrap()),
             pending: Arc::new(LmdbPendingStore::new(env.clone()).unwrap()),
             pruned: Arc::new(LmdbPrunedStore::new(env.clone()).unwrap()),
             version: Arc::new(LmdbVersionStore::new(env.clone()).unwrap()),
@@ -194,9 +274,14 @@ impl<T: Environment + 'static> Ledger<T> {
             constants,
             observer: Arc::new(NullLedgerObserver::new()),
             pruning: AtomicBool::new(false),
+            timestamp_index: AtomicBool::new(false),
+            destination_index: AtomicBool::new(false),
             bootstrap_weight_max_blocks: AtomicU64::new(1),
+            max_rollback_depth: AtomicUsize::new(usize::MAX),
+            reject_confirmed_forks: AtomicBool::new(false),
             check_bootstrap_weights: AtomicBool::new(true),
             bootstrap_weights: Mutex::new(HashMap::new()),
+            read_pool: OnceCell::new(),
         };
 
         ledger.initialize(generate_cache)?;
@@ -216,30 +301,101 @@ impl<T: Environment + 'static> Ledger<T> {
         self.store.tx_begin_write()
     }
 
+    /// Executes `f` with a pooled read transaction on a dedicated thread.
+    /// Async code awaits the result instead of blocking a runtime worker on LMDB page faults.
+    pub fn spawn_read<F, R>(self: &Arc<Self>, f: F) -> impl Future<Output = R>
+    where
+        F: FnOnce(&Ledger<T>, &LmdbReadTransaction<T>) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let ledger = Arc::clone(self);
+        self.read_pool
+            .get_or_init(|| {
+                LedgerReadPool::new(
+                    Arc::clone(&self.store),
+                    LedgerReadPool::<T>::DEFAULT_THREADS,
+                )
+            })
+            .execute(Box::new(move |txn| {
+                let _ = tx.send(f(&ledger, txn));
+            }));
+        async move { rx.await.expect("ledger read panicked") }
+    }
+
     fn initialize(&mut self, generate_cache: &GenerateCache) -> anyhow::Result<()> {
         if self.store.account.begin(&self.read_txn()).is_end() {
             self.add_genesis_block(&mut self.rw_txn());
         }
 
-        if generate_cache.reps || generate_cache.account_count || generate_cache.block_count {
-            self.store.account.for_each_par(&|_txn, mut i, n| {
+        if generate_cache.reps
+            || generate_cache.account_count
+            || generate_cache.block_count
+            || generate_cache.checksum
+        {
+            if generate_cache.account_count {
+                self.cache.epoch_progress.enable_accounts();
+            }
+            self.store.account.for_each_par(&|txn, mut i, n| {
                 let mut block_count = 0;
                 let mut account_count = 0;
+                let mut accounts_per_epoch: BTreeMap<Epoch, u64> = BTreeMap::new();
                 let rep_weights = RepWeights::new();
+                let checksum = LedgerChecksumCache::new();
+                let recently_modified =
+                    RecentlyModifiedAccounts::new(self.cache.recently_modified.capacity());
                 while !i.eq(n.as_ref()) {
-                    let info = i.current().unwrap().1;
+                    let (account, info) = i.current().unwrap();
                     block_count += info.block_count;
                     account_count += 1;
+                    *accounts_per_epoch.entry(info.epoch).or_default() += 1;
+                    recently_modified.update(account, info.modified);
                     rep_weights.representation_add(info.representative, info.balance);
+                    if generate_cache.checksum {
+                        let conf_height = self
+                            .store
+                            .confirmation_height
+                            .get(txn, account)
+                            .unwrap_or_default();
+                        checksum.toggle(&account_checksum(account, &info.head, &conf_height));
+                    }
                     i.next();
                 }
+                self.cache.checksum.toggle(&checksum.get());
                 self.cache
                     .block_count
                     .fetch_add(block_count, Ordering::SeqCst);
                 self.cache
                     .account_count
                     .fetch_add(account_count, Ordering::SeqCst);
+                self.cache.epoch_progress.merge_accounts(accounts_per_epoch);
                 self.cache.rep_weights.copy_from(&rep_weights);
+                self.cache
+                    .recently_modified
+                    .merge(recently_modified.get(0, usize::MAX));
+            });
+        }
+
+        if generate_cache.receivable {
+            self.cache.receivable.enable();
+            self.cache.epoch_progress.enable_unopened();
+            self.store.pending.for_each_par(&|txn, mut i, n| {
+                let mut summaries: HashMap<Account, ReceivableSummary> = HashMap::new();
+                let mut highest_epochs: HashMap<Account, Epoch> = HashMap::new();
+                while !i.eq(n.as_ref()) {
+                    let (key, info) = i.current().unwrap();
+                    let summary = summaries.entry(key.account).or_default();
+                    summary.count += 1;
+                    summary.total = summary.total.wrapping_add(info.amount);
+                    let highest = highest_epochs.entry(key.account).or_insert(info.epoch);
+                    *highest = max(*highest, info.epoch);
+                    i.next();
+                }
+                self.cache.receivable.merge(summaries);
+                highest_epochs.retain(|account, _| {
+                    !account.is_zero() && self.store.account.get(txn, account).is_none()
+                });
+                self.cache.epoch_progress.merge_unopened(highest_epochs);
             });
         }
 
@@ -316,6 +472,33 @@ impl<T: Environment + 'static> Ledger<T> {
         self.pruning.store(true, Ordering::SeqCst);
     }
 
+    /// The (account, height) -> block hash index is maintained if the store was
+    /// opened with `LmdbConfig::block_height_index`
+    pub fn height_index_enabled(&self) -> bool {
+        self.store.block.height_index_enabled()
+    }
+
+    pub fn timestamp_index_enabled(&self) -> bool {
+        self.timestamp_index.load(Ordering::SeqCst)
+    }
+
+    /// Maintains the (local timestamp, block hash) index for all blocks
+    /// that are inserted, rolled back or pruned from now on
+    pub fn enable_timestamp_index(&self) {
+        self.timestamp_index.store(true, Ordering::SeqCst);
+    }
+
+    pub fn destination_index_enabled(&self) -> bool {
+        self.destination_index.load(Ordering::SeqCst)
+    }
+
+    /// Maintains the (destination account, send block hash) index for all send blocks
+    /// that are inserted or rolled back from now on. Pruning keeps the entries,
+    /// so that the sends to an account can still be found on a pruned ledger.
+    pub fn enable_destination_index(&self) {
+        self.destination_index.store(true, Ordering::SeqCst);
+    }
+
     pub fn bootstrap_weight_max_blocks(&self) -> u64 {
         self.bootstrap_weight_max_blocks.load(Ordering::SeqCst)
     }
@@ -325,6 +508,27 @@ impl<T: Environment + 'static> Ledger<T> {
             .store(max, Ordering::SeqCst)
     }
 
+    /// Replaces the bootstrap weights and the block count up to which they are used.
+    /// The new weights are checked again, even if the previous ones were already dropped.
+    pub fn load_bootstrap_weights(&self, weights: BootstrapWeights) {
+        self.set_bootstrap_weight_max_blocks(weights.max_blocks);
+        *self.bootstrap_weights.lock().unwrap() = weights.weights;
+        self.check_bootstrap_weights.store(true, Ordering::SeqCst);
+    }
+
+    /// Snapshot of the current representative weights which can be exported
+    /// as new bootstrap weights. Only representatives with at least `min_weight` are included.
+    pub fn export_bootstrap_weights(&self, min_weight: Amount) -> BootstrapWeights {
+        let weights = self
+            .cache
+            .rep_weights
+            .get_rep_amounts()
+            .into_iter()
+            .filter(|(_, weight)| *weight >= min_weight && !weight.is_zero())
+            .collect();
+        BootstrapWeights::new(self.cache.block_count.load(Ordering::SeqCst), weights)
+    }
+
     pub fn block_or_pruned_exists(&self, block: &BlockHash) -> bool {
         let txn = self.store.tx_begin_read();
         self.block_or_pruned_exists_txn(&txn, block)
@@ -386,38 +590,494 @@ impl<T: Environment + 'static> Ledger<T> {
         }
     }
 
+    /// Up to `limit` accounts which were modified at or after `since` (seconds since epoch),
+    /// most recently modified first. Only the most recently modified accounts are indexed,
+    /// so very old modifications may be missing
+    pub fn recently_modified_accounts(&self, since: u64, limit: usize) -> Vec<(Account, u64)> {
+        self.cache.recently_modified.get(since, limit)
+    }
+
     pub fn account_receivable(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
         account: &Account,
         only_confirmed: bool,
     ) -> Amount {
-        let mut result = Amount::zero();
-        let end = Account::from(account.number() + 1);
-        let mut i = self
-            .store
-            .pending
-            .begin_at_key(txn, &PendingKey::new(*account, BlockHash::zero()));
-        let n = self
-            .store
+        if !only_confirmed {
+            if let Some(summary) = self.cache.receivable.get(account) {
+                return summary.total;
+            }
+        }
+
+        self.store
             .pending
-            .begin_at_key(txn, &PendingKey::new(end, BlockHash::zero()));
-        while !i.eq(n.as_ref()) {
-            if let Some((key, info)) = i.current() {
-                if only_confirmed {
-                    if self.block_confirmed(txn, &key.hash) {
-                        result += info.amount;
-                    }
-                } else {
-                    result += info.amount;
+            .iter_account(txn, account)
+            .filter(|(key, _)| !only_confirmed || self.block_confirmed(txn, &key.hash))
+            .fold(Amount::zero(), |sum, (_, info)| sum + info.amount)
+    }
+
+    /// Collects the ledger and table sizes for the startup log and the `ledger_stats` RPC
+    pub fn stats_report(&self) -> LedgerStatsReport {
+        let txn = self.read_txn();
+        let mut tables = vec![
+            ("accounts", self.store.account.count(&txn)),
+            ("blocks", self.store.block.count(&txn)),
+            (
+                "confirmation_height",
+                self.store.confirmation_height.count(&txn),
+            ),
+            ("final_votes", self.store.final_vote.count(&txn)),
+            ("online_weight", self.store.online_weight.count(&txn)),
+            ("peers", self.store.peer.count(&txn)),
+            ("pending", self.store.pending.count(&txn)),
+            ("pruned", self.store.pruned.count(&txn)),
+        ];
+        tables.sort_by_key(|(_, entries)| std::cmp::Reverse(*entries));
+        let map_usage = self.store.map_usage().unwrap_or_default();
+
+        LedgerStatsReport {
+            block_count: self.cache.block_count.load(Ordering::SeqCst),
+            cemented_count: self.cache.cemented_count.load(Ordering::SeqCst),
+            account_count: self.cache.account_count.load(Ordering::SeqCst),
+            pruned_count: self.cache.pruned_count.load(Ordering::SeqCst),
+            checksum: self.cache.checksum.get(),
+            version: self.store.version.get(&txn),
+            map_size: map_usage.map_size,
+            map_used: map_usage.used,
+            tables,
+        }
+    }
+
+    /// Computes the order independent checksum over all account heads and confirmation
+    /// heights from the store. The same value is maintained incrementally in the ledger cache
+    pub fn ledger_checksum(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> LedgerChecksum {
+        let checksum = LedgerChecksumCache::new();
+        let mut it = self.store.account.begin(txn);
+        while let Some((account, info)) = it.current() {
+            let conf_height = self
+                .store
+                .confirmation_height
+                .get(txn, account)
+                .unwrap_or_default();
+            checksum.toggle(&account_checksum(account, &info.head, &conf_height));
+            it.next();
+        }
+        checksum.get()
+    }
+
+    /// A fast sanity check of the store, which is meant to run on startup before the node accepts traffic.
+    /// The cached counts are only compared if they were generated on startup.
+    pub fn check_integrity(&self, generate_cache: &GenerateCache) -> Vec<IntegrityProblem> {
+        let mut problems = Vec::new();
+        let txn = self.read_txn();
+
+        let genesis_hash = self.constants.genesis.read().unwrap().hash();
+        if !self.block_or_pruned_exists_txn(&txn, &genesis_hash) {
+            problems.push(IntegrityProblem::GenesisMissing);
+        }
+
+        let version = self.store.version.get(&txn);
+        if !version
+            .map(|v| (STORE_VERSION_MINIMUM..=STORE_VERSION_CURRENT).contains(&v))
+            .unwrap_or(false)
+        {
+            problems.push(IntegrityProblem::UnsupportedVersion(version));
+        }
+
+        if generate_cache.account_count {
+            let cached = self.cache.account_count.load(Ordering::SeqCst);
+            let stored = self.store.account.count(&txn);
+            if cached != stored {
+                problems.push(IntegrityProblem::AccountCountMismatch { cached, stored });
+            }
+        }
+
+        if generate_cache.block_count {
+            let cached = self.cache.block_count.load(Ordering::SeqCst);
+            let stored = self.store.block.count(&txn) + self.store.pruned.count(&txn);
+            if cached != stored {
+                problems.push(IntegrityProblem::BlockCountMismatch { cached, stored });
+            }
+        }
+
+        problems
+    }
+
+    /// Collects all information about an account that is shown by the `account_info` RPC.
+    /// Returns `None` if the account is not opened
+    pub fn account_details(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        receivable_details: ReceivableDetails,
+    ) -> Option<AccountDetails> {
+        let info = self.account_info(txn, account)?;
+        let confirmation_height = self
+            .get_confirmation_height(txn, account)
+            .unwrap_or_default();
+        let representative_block = self.representative_block_hash(txn, &info.head);
+
+        let (confirmed_balance, confirmed_representative) = if confirmation_height.height > 0 {
+            let confirmed_rep_block =
+                self.representative_block_hash(txn, &confirmation_height.frontier);
+            let confirmed_representative = self
+                .get_block(txn, &confirmed_rep_block)
+                .and_then(|block| block.representative())
+                .unwrap_or_default();
+            (
+                self.balance(txn, &confirmation_height.frontier),
+                confirmed_representative,
+            )
+        } else {
+            (Amount::zero(), Account::zero())
+        };
+
+        let receivable = match receivable_details {
+            ReceivableDetails::Skip => Amount::zero(),
+            _ => self.account_receivable(txn, account, false),
+        };
+        let confirmed_receivable = match receivable_details {
+            ReceivableDetails::TotalAndConfirmed => self.account_receivable(txn, account, true),
+            _ => Amount::zero(),
+        };
+
+        Some(AccountDetails {
+            info,
+            confirmation_height,
+            representative_block,
+            confirmed_balance,
+            confirmed_representative,
+            receivable,
+            confirmed_receivable,
+        })
+    }
+
+    /// Collects all information about a block that is shown by the `blocks_info` RPC.
+    /// Returns `None` if the block doesn't exist
+    pub fn block_info(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        hash: &BlockHash,
+    ) -> Option<BlockInfo> {
+        let block = self.get_block(txn, hash)?;
+        let sideband = block.sideband().unwrap();
+        let subtype = match block.block_type() {
+            BlockType::State => Some(sideband.details.subtype()),
+            _ => None,
+        };
+
+        Some(BlockInfo {
+            account: block.account_calculated(),
+            amount: self.amount_safe(txn, hash),
+            balance: self.balance(txn, hash),
+            height: sideband.height,
+            local_timestamp: sideband.timestamp,
+            successor: sideband.successor,
+            subtype,
+            confirmed: self.block_confirmed(txn, hash),
+        })
+    }
+
+    /// Loads the blocks of `section` from the bottom upwards, at most `max` blocks.
+    /// If the height index contains the section, the hashes are read with a single cursor walk.
+    /// Otherwise the chain is followed via the successors of the blocks
+    pub fn chain_section_blocks(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        section: &BlockChainSection,
+        max: usize,
+    ) -> Vec<BlockEnum> {
+        let count = section.block_count().min(max as u64);
+        if count == 0 {
+            return Vec::new();
+        }
+
+        if self.height_index_enabled() {
+            let top_height = section.bottom_height + count - 1;
+            let mut hashes = Vec::with_capacity(count as usize);
+            self.store.block.for_each_in_height_range(
+                txn,
+                &section.account,
+                section.bottom_height,
+                top_height,
+                &mut |_, hash| {
+                    hashes.push(*hash);
+                    true
+                },
+            );
+            if hashes.len() as u64 == count && hashes[0] == section.bottom_hash {
+                let blocks: Vec<_> = hashes
+                    .iter()
+                    .filter_map(|hash| self.store.block.get(txn, hash))
+                    .collect();
+                if is_connected_chain(&blocks, count) {
+                    return blocks;
                 }
+            }
+        }
+
+        let mut blocks = Vec::with_capacity(count as usize);
+        let mut hash = section.bottom_hash;
+        while (blocks.len() as u64) < count && !hash.is_zero() {
+            let Some(block) = self.store.block.get(txn, &hash) else {
+                break;
             };
-            i.next();
+            hash = block.sideband().unwrap().successor;
+            blocks.push(block);
         }
+        blocks
+    }
+
+    /// Returns the hash of the block at `height` in the chain of `account`.
+    /// The height index is used if it contains the block. Blocks that were inserted
+    /// while the index was disabled are found by walking the chain back from the head block,
+    /// which fails if the walk reaches a pruned block.
+    pub fn block_at_height(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        height: u64,
+    ) -> Option<BlockHash> {
+        if let Some(hash) = self.store.block.hash_at_height(txn, account, height) {
+            if self.is_block_at_height(txn, &hash, account, height) {
+                return Some(hash);
+            }
+        }
+
+        let info = self.account_info(txn, account)?;
+        if height == 0 || height > info.block_count {
+            return None;
+        }
+
+        let mut hash = info.head;
+        for _ in height..info.block_count {
+            hash = self.store.block.get(txn, &hash)?.previous();
+        }
+        Some(hash)
+    }
+
+    /// Checks that an entry of the height index is still valid. The entry could be
+    /// outdated if the block was rolled back while the index was disabled.
+    /// A pruned block can't be checked, because it can't be loaded anymore. Pruned blocks
+    /// are cemented and therefore can't have been rolled back, so the entry is only trusted
+    /// if its height is cemented
+    fn is_block_at_height(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        hash: &BlockHash,
+        account: &Account,
+        height: u64,
+    ) -> bool {
+        match self.store.block.get(txn, hash) {
+            Some(block) => {
+                let sideband = block.sideband().unwrap();
+                sideband.height == height && block.account_calculated() == *account
+            }
+            None => {
+                self.store.pruned.exists(txn, hash)
+                    && self
+                        .store
+                        .confirmation_height
+                        .get(txn, account)
+                        .map(|info| height <= info.height)
+                        .unwrap_or(false)
+            }
+        }
+    }
+
+    /// Returns the local time in seconds since epoch at which the block was inserted into the ledger
+    pub fn block_local_timestamp(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        hash: &BlockHash,
+    ) -> Option<u64> {
+        self.get_block(txn, hash)
+            .map(|block| block.sideband().unwrap().timestamp)
+    }
+
+    /// Returns up to `max` blocks which were inserted into the ledger at a local time
+    /// with `from <= timestamp < to`, ordered by timestamp.
+    /// Only blocks which were inserted while the timestamp index was enabled are found.
+    pub fn blocks_in_time_range(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        from: u64,
+        to: u64,
+        max: usize,
+    ) -> Vec<BlockHash> {
+        let mut result = Vec::new();
+        if max == 0 {
+            return result;
+        }
+        self.store
+            .block
+            .for_each_in_time_range(txn, from, to, &mut |timestamp, hash| {
+                // The entry is outdated if the block was rolled back while the index was disabled
+                if self.block_local_timestamp(txn, hash) == Some(timestamp) {
+                    result.push(*hash);
+                }
+                result.len() < max
+            });
+        result
+    }
 
+    /// Returns up to `max` hashes of send blocks to `destination`, beginning with the first hash after `start`.
+    /// The hashes are ordered by hash, so that the result can be paged by passing the last hash as `start`.
+    /// Only sends which were inserted while the destination index was enabled are found.
+    /// The send blocks themselves may have been pruned.
+    pub fn incoming_sends(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        destination: &Account,
+        start: &BlockHash,
+        max: usize,
+    ) -> Vec<BlockHash> {
+        let mut result = Vec::new();
+        if max == 0 {
+            return result;
+        }
+        self.store
+            .block
+            .for_each_destination(txn, destination, start, &mut |hash| {
+                // The entry is outdated if the block was rolled back while the index was disabled
+                if self.block_or_pruned_exists_txn(txn, hash) {
+                    result.push(*hash);
+                }
+                result.len() < max
+            });
         result
     }
 
+    /// Writes the whole history of `account` to `writer` as JSON lines, the newest block first.
+    /// The chain is walked block by block, so that the history of large accounts doesn't have to be kept in memory.
+    pub fn account_history_stream(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        writer: &mut dyn Write,
+    ) -> anyhow::Result<()> {
+        self.account_history_page(txn, account, None, usize::MAX, writer)?;
+        Ok(())
+    }
+
+    /// Writes up to `max` history entries of `account` as JSON lines, beginning at `head` or at the
+    /// frontier if `head` is `None`. Returns the pagination token for the next page, which is the hash
+    /// of the block at which the next page starts. `None` is returned if the end of the chain was reached
+    pub fn account_history_page(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        head: Option<&BlockHash>,
+        max: usize,
+        writer: &mut dyn Write,
+    ) -> anyhow::Result<Option<BlockHash>> {
+        let mut hash = match head {
+            Some(head) => *head,
+            None => self
+                .latest(txn, account)
+                .ok_or_else(|| anyhow!("account not found"))?,
+        };
+        if let Some(block) = self.get_block(txn, &hash) {
+            if block.account_calculated() != *account {
+                bail!("block {} does not belong to account", hash);
+            }
+        }
+
+        let mut written = 0;
+        while written < max {
+            // The history ends at the open block or at the first pruned block
+            let Some(block) = self.get_block(txn, &hash) else {
+                return Ok(None);
+            };
+            if let Some(entry) = self.account_history_entry(txn, &block) {
+                entry.write_json_line(writer)?;
+                written += 1;
+            }
+            hash = block.previous();
+            if hash.is_zero() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(hash))
+    }
+
+    /// Returns `None` for blocks which are not part of the history, like change and epoch blocks
+    fn account_history_entry(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        block: &BlockEnum,
+    ) -> Option<AccountHistoryEntry> {
+        let hash = block.hash();
+        let sideband = block.sideband().unwrap();
+        let amount = self.amount_safe(txn, &hash);
+        let (subtype, account) = match block {
+            BlockEnum::LegacySend(send) => {
+                (Some(BlockSubType::Send), Some(send.hashables.destination))
+            }
+            BlockEnum::LegacyReceive(_) | BlockEnum::LegacyOpen(_) => {
+                let source = block.source().unwrap_or_default();
+                if source == self.constants.genesis_account.into() {
+                    (
+                        Some(BlockSubType::Receive),
+                        Some(self.constants.genesis_account),
+                    )
+                } else {
+                    let account = amount.and_then(|_| self.account(txn, &source));
+                    (Some(BlockSubType::Receive), account)
+                }
+            }
+            BlockEnum::LegacyChange(_) => return None,
+            BlockEnum::State(state) => {
+                if amount.is_none() {
+                    (None, None)
+                } else if sideband.details.is_send {
+                    (Some(BlockSubType::Send), Some(state.link().into()))
+                } else if sideband.details.is_receive {
+                    let source = state.link().into();
+                    (Some(BlockSubType::Receive), self.account(txn, &source))
+                } else {
+                    return None;
+                }
+            }
+        };
+
+        Some(AccountHistoryEntry {
+            hash,
+            subtype,
+            account,
+            amount,
+            height: sideband.height,
+            local_timestamp: sideband.timestamp,
+            confirmed: self.block_confirmed(txn, &hash),
+        })
+    }
+
+    /// Returns the hash of the block which received the given send block.
+    /// Returns `None` if the block is no send, if the send is still receivable
+    /// or if the receive block isn't confirmed yet
+    pub fn receive_hash_for_send(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        send_hash: &BlockHash,
+    ) -> Option<BlockHash> {
+        let send = self.get_block(txn, send_hash)?;
+        let destination = self.block_destination(txn, &send);
+        if destination.is_zero()
+            || self
+                .store
+                .pending
+                .exists(txn, &PendingKey::new(destination, *send_hash))
+        {
+            return None;
+        }
+
+        self.find_receive_block_by_send_hash(txn, &destination, send_hash)
+            .map(|block| block.hash())
+    }
+
     pub fn block_confirmed(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
@@ -556,7 +1216,13 @@ impl<T: Environment + 'static> Ledger<T> {
         }
     }
 
-    /// Vote weight of an account
+    /// Vote weight of an account, including unconfirmed blocks.
+    ///
+    /// While the ledger has fewer blocks than `bootstrap_weight_max_blocks`, the weights
+    /// of the loaded bootstrap weights are returned instead, because the ledger of a
+    /// bootstrapping node doesn't know the real weights yet. Representatives which aren't
+    /// part of the bootstrap weights get their weight from the ledger. As soon as the
+    /// threshold is reached once, the bootstrap weights aren't used anymore.
     pub fn weight(&self, account: &Account) -> Amount {
         if self.check_bootstrap_weights.load(Ordering::SeqCst) {
             if self.cache.block_count.load(Ordering::SeqCst) < self.bootstrap_weight_max_blocks() {
@@ -572,6 +1238,44 @@ impl<T: Environment + 'static> Ledger<T> {
         self.cache.rep_weights.representation_get(account)
     }
 
+    /// Vote weight of a representative computed from cemented blocks only. Unlike
+    /// `weight` this ignores bootstrap weights and isn't cached, so it walks all
+    /// accounts with a confirmation height.
+    pub fn weight_exact(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        representative: &Account,
+    ) -> Amount {
+        let mut weight = Amount::zero();
+        for (account, conf_info) in self.store.confirmation_height.iter(txn) {
+            if conf_info.height == 0 {
+                continue;
+            }
+            if self.confirmed_representative(txn, &account, &conf_info.frontier)
+                == Some(*representative)
+            {
+                weight += self.balance(txn, &conf_info.frontier);
+            }
+        }
+        weight
+    }
+
+    /// Representative of an account as of the given confirmed frontier
+    fn confirmed_representative(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        confirmed_frontier: &BlockHash,
+    ) -> Option<Account> {
+        if let Some(info) = self.account_info(txn, account) {
+            if info.head == *confirmed_frontier {
+                return Some(info.representative);
+            }
+        }
+        let rep_block = self.representative_block_hash(txn, confirmed_frontier);
+        self.get_block(txn, &rep_block)?.representative()
+    }
+
     /// Return account containing block hash
     pub fn account(
         &self,
@@ -590,11 +1294,7 @@ impl<T: Environment + 'static> Ledger<T> {
         self.store.block.get(txn, hash).map(|block| {
             let block_balance = self.balance(txn, hash);
             let previous_balance = self.balance(txn, &block.previous());
-            if block_balance > previous_balance {
-                block_balance - previous_balance
-            } else {
-                previous_balance - block_balance
-            }
+            BalanceDelta::new(previous_balance, block_balance).amount()
         })
     }
 
@@ -606,17 +1306,8 @@ impl<T: Environment + 'static> Ledger<T> {
     ) -> Option<Amount> {
         self.store.block.get(txn, hash).and_then(|block| {
             let block_balance = self.balance(txn, hash);
-            let previous_balance = self.balance_safe(txn, &block.previous());
-            match previous_balance {
-                Ok(previous) => {
-                    if block_balance > previous {
-                        Some(block_balance - previous)
-                    } else {
-                        Some(previous - block_balance)
-                    }
-                }
-                Err(_) => None,
-            }
+            let previous_balance = self.balance_safe(txn, &block.previous()).ok()?;
+            Some(BalanceDelta::new(previous_balance, block_balance).amount())
         })
     }
 
@@ -645,6 +1336,67 @@ impl<T: Environment + 'static> Ledger<T> {
         self.constants.epochs.is_epoch_link(link)
     }
 
+    /// Derives the subtype of a state block from the balance of its previous block.
+    /// Returns None if the previous block is unknown
+    pub fn state_block_subtype(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        block: &StateBlock,
+    ) -> Option<BlockSubType> {
+        let previous = block.previous();
+        if !previous.is_zero() && !self.store.block.exists(txn, &previous) {
+            return None;
+        }
+        let previous_balance = self.balance(txn, &previous);
+        let balance = block.balance();
+        let subtype = if balance < previous_balance {
+            BlockSubType::Send
+        } else if balance == previous_balance && self.is_epoch_link(&block.link()) {
+            BlockSubType::Epoch
+        } else if previous.is_zero() {
+            BlockSubType::Open
+        } else if balance > previous_balance {
+            BlockSubType::Receive
+        } else {
+            BlockSubType::Change
+        };
+        Some(subtype)
+    }
+
+    /// Checks if a state block fits the subtype a client claimed for it, by comparing
+    /// its balance with the balance of the previous block. A receive may also open the account
+    pub fn check_subtype(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        block: &StateBlock,
+        subtype: BlockSubType,
+    ) -> Result<(), SubtypeMismatch> {
+        let previous = block.previous();
+        if !previous.is_zero() && !self.store.block.exists(txn, &previous) {
+            return Err(SubtypeMismatch::GapPrevious);
+        }
+        let previous_balance = self.balance(txn, &previous);
+        let balance = block.balance();
+        match subtype {
+            BlockSubType::Send if balance >= previous_balance => Err(SubtypeMismatch::Balance),
+            BlockSubType::Receive
+                if balance < previous_balance
+                    || (!previous.is_zero() && balance == previous_balance) =>
+            {
+                Err(SubtypeMismatch::Balance)
+            }
+            BlockSubType::Open if !previous.is_zero() => Err(SubtypeMismatch::Previous),
+            BlockSubType::Change | BlockSubType::Epoch if balance != previous_balance => {
+                Err(SubtypeMismatch::Balance)
+            }
+            BlockSubType::Change if previous.is_zero() => Err(SubtypeMismatch::Previous),
+            BlockSubType::Epoch if !self.is_epoch_link(&block.link()) => {
+                Err(SubtypeMismatch::EpochLink)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Given the block hash of a send block, find the associated receive block that receives that send.
     /// The send block hash is not checked in any way, it is assumed to be correct.
     /// Return the receive block on success and None on failure
@@ -693,18 +1445,43 @@ impl<T: Environment + 'static> Ledger<T> {
         old_info: &AccountInfo,
         new_info: &AccountInfo,
     ) {
+        let conf_height = self
+            .store
+            .confirmation_height
+            .get(txn, account)
+            .unwrap_or_default();
+        self.cache.checksum.update(
+            account,
+            (&old_info.head, &conf_height),
+            (&new_info.head, &conf_height),
+        );
+        let old_epoch = (!old_info.head.is_zero()).then_some(old_info.epoch);
+        let new_epoch = (!new_info.head.is_zero()).then_some(new_info.epoch);
+        self.cache
+            .epoch_progress
+            .account_changed(old_epoch, new_epoch);
+
         if !new_info.head.is_zero() {
             if old_info.head.is_zero() && new_info.open_block == new_info.head {
                 self.cache.account_count.fetch_add(1, Ordering::SeqCst);
+                self.cache.epoch_progress.set_unopened(account, None);
             }
             if !old_info.head.is_zero() && old_info.epoch != new_info.epoch {
                 // store.account ().put won't erase existing entries if they're in different tables
                 self.store.account.del(txn, account);
             }
             self.store.account.put(txn, account, new_info);
+            self.cache
+                .recently_modified
+                .update(account, new_info.modified);
         } else {
             debug_assert!(!self.store.confirmation_height.exists(txn, account));
             self.store.account.del(txn, account);
+            self.cache.recently_modified.remove(account);
+            if self.cache.epoch_progress.unopened_enabled() {
+                let highest = self.highest_receivable_epoch(txn, account);
+                self.cache.epoch_progress.set_unopened(account, highest);
+            }
             debug_assert!(self.cache.account_count.load(Ordering::SeqCst) > 0);
             self.cache.account_count.fetch_sub(1, Ordering::SeqCst);
         }
@@ -740,19 +1517,21 @@ impl<T: Environment + 'static> Ledger<T> {
         let mut pruned_count = 0;
         let mut hash = *hash;
         let genesis_hash = { self.constants.genesis.read().unwrap().hash() };
+        let mut batch = BatchWriteGuard::new(txn, batch_size as usize, PRUNING_BATCH_DURATION);
 
         while !hash.is_zero() && hash != genesis_hash {
-            if let Some(block) = self.store.block.get(txn, &hash) {
-                self.store.block.del(txn, &hash);
-                self.store.pruned.put(txn, &hash);
+            if let Some(block) = self.store.block.get(&*batch, &hash) {
+                self.store.block.del(&mut batch, &hash);
+                if self.timestamp_index_enabled() {
+                    let timestamp = block.sideband().unwrap().timestamp;
+                    self.store.block.del_timestamp(&mut batch, timestamp, &hash);
+                }
+                self.store.pruned.put(&mut batch, &hash);
                 hash = block.previous();
                 pruned_count += 1;
                 self.cache.pruned_count.fetch_add(1, Ordering::SeqCst);
-                if pruned_count % batch_size == 0 {
-                    txn.commit();
-                    txn.renew();
-                }
-            } else if self.store.pruned.exists(txn, &hash) {
+                batch.operation_done();
+            } else if self.store.pruned.exists(&*batch, &hash) {
                 hash = BlockHash::zero();
             } else {
                 panic!("Error finding block for pruning");
@@ -801,6 +1580,120 @@ impl<T: Environment + 'static> Ledger<T> {
         result.into_inner().unwrap()
     }
 
+    /// Counts the accounts per epoch. The counts come from the epoch progress cache if it
+    /// was generated on startup, otherwise the accounts and pending tables are scanned.
+    pub fn epoch_upgrade_progress(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> EpochUpgradeProgress {
+        let mut progress = EpochUpgradeProgress::default();
+        progress.accounts_per_epoch = match self.cache.epoch_progress.accounts_per_epoch() {
+            Some(counts) => counts,
+            None => {
+                let mut counts: BTreeMap<Epoch, u64> = BTreeMap::new();
+                let mut it = self.store.account.begin(txn);
+                while let Some((_, info)) = it.current() {
+                    *counts.entry(info.epoch).or_default() += 1;
+                    it.next();
+                }
+                counts
+            }
+        };
+        progress.unupgraded = progress
+            .accounts_per_epoch
+            .iter()
+            .filter(|(epoch, _)| **epoch < Epoch::MAX)
+            .map(|(_, count)| count)
+            .sum();
+        for (_, epoch) in self.unopened_accounts_with_receivables(txn, usize::MAX) {
+            if epoch < Epoch::MAX {
+                progress.unopened_with_receivables += 1;
+                progress.unupgraded += 1;
+            }
+        }
+        progress
+    }
+
+    /// Accounts which aren't opened yet but have receivable blocks, together with the
+    /// highest epoch of their receivables. The burn account is skipped.
+    pub fn unopened_accounts_with_receivables(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        limit: usize,
+    ) -> Vec<(Account, Epoch)> {
+        if let Some(unopened) = self.cache.epoch_progress.unopened(limit) {
+            return unopened;
+        }
+        let mut result = Vec::new();
+        let mut it = self
+            .store
+            .pending
+            .begin_at_key(txn, &PendingKey::new(Account::from(1), BlockHash::zero()));
+        while let Some((key, _)) = it.current() {
+            if result.len() >= limit {
+                break;
+            }
+            let account = key.account;
+            if self.store.account.get(txn, &account).is_none() {
+                let epoch = self
+                    .highest_receivable_epoch(txn, &account)
+                    .unwrap_or(Epoch::Epoch0);
+                result.push((account, epoch));
+            }
+            // skip the remaining receivables of this account
+            let Some(next) = account.number().checked_add(1.into()) else {
+                break;
+            };
+            it = self.store.pending.begin_at_key(
+                txn,
+                &PendingKey::new(Account::from(next), BlockHash::zero()),
+            );
+        }
+        result
+    }
+
+    fn highest_receivable_epoch(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+    ) -> Option<Epoch> {
+        self.store
+            .pending
+            .iter_account(txn, account)
+            .map(|(_, info)| info.epoch)
+            .max()
+    }
+
+    /// Keeps the unopened accounts of the epoch progress cache up to date after
+    /// a receivable was put into the pending table
+    pub(crate) fn receivable_added(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        key: &PendingKey,
+        info: &PendingInfo,
+    ) {
+        let epoch_progress = &self.cache.epoch_progress;
+        if epoch_progress.unopened_enabled()
+            && !key.account.is_zero()
+            && self.store.account.get(txn, &key.account).is_none()
+        {
+            epoch_progress.receivable_added(&key.account, info.epoch);
+        }
+    }
+
+    /// Keeps the unopened accounts of the epoch progress cache up to date after
+    /// a receivable was deleted from the pending table
+    pub(crate) fn receivable_removed(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+    ) {
+        if self.cache.epoch_progress.is_unopened(account) {
+            let highest = self.highest_receivable_epoch(txn, account);
+            self.cache.epoch_progress.set_unopened(account, highest);
+        }
+    }
+
     pub fn bootstrap_weight_reached(&self) -> bool {
         self.cache.block_count.load(Ordering::SeqCst) >= self.bootstrap_weight_max_blocks()
     }
@@ -810,25 +1703,36 @@ impl<T: Environment + 'static> Ledger<T> {
         txn: &mut LmdbWriteTransaction<T>,
         section: &BlockChainSection,
     ) {
+        let old_conf_height = self
+            .store
+            .confirmation_height
+            .get(txn, &section.account)
+            .unwrap_or_default();
+
         #[cfg(debug_assertions)]
         {
-            let conf_height = self
-                .store
-                .confirmation_height
-                .get(txn, &section.account)
-                .map(|i| i.height)
-                .unwrap_or_default();
             let block = self.store.block.get(txn, &section.top_hash).unwrap();
             debug_assert_eq!(
                 block.sideband().unwrap().height,
-                conf_height + section.block_count()
+                old_conf_height.height + section.block_count()
             );
         }
 
-        self.store.confirmation_height.put(
-            txn,
+        let new_conf_height = ConfirmationHeightInfo::new(section.top_height, section.top_hash);
+        self.store
+            .confirmation_height
+            .put(txn, &section.account, &new_conf_height);
+
+        let head = self
+            .store
+            .account
+            .get(txn, &section.account)
+            .map(|info| info.head)
+            .unwrap_or_default();
+        self.cache.checksum.update(
             &section.account,
-            &ConfirmationHeightInfo::new(section.top_height, section.top_hash),
+            (&head, &old_conf_height),
+            (&head, &new_conf_height),
         );
 
         self.cache
@@ -838,6 +1742,51 @@ impl<T: Environment + 'static> Ledger<T> {
         self.observer.blocks_cemented(section.block_count());
     }
 
+    /// Cements the chain of `account` up to and including the block at `to_height` and
+    /// returns the newly cemented blocks from the bottom upwards. Nothing is cemented if the
+    /// block at `to_height` is already cemented or doesn't exist.
+    /// Dependencies in other account chains are not cemented; the caller has to ensure
+    /// that they are cemented already
+    pub fn cement_blocks(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        account: &Account,
+        to_height: u64,
+    ) -> Vec<Arc<BlockEnum>> {
+        let conf_height = self
+            .store
+            .confirmation_height
+            .get(txn, account)
+            .unwrap_or_default();
+        if to_height <= conf_height.height {
+            return Vec::new();
+        }
+
+        let bottom_hash = if conf_height.height == 0 {
+            self.account_info(txn, account).map(|info| info.open_block)
+        } else {
+            self.store.block.successor(txn, &conf_height.frontier)
+        };
+        let Some(bottom_hash) = bottom_hash else {
+            return Vec::new();
+        };
+        let Some(top_hash) = self.block_at_height(txn, account, to_height) else {
+            return Vec::new();
+        };
+
+        let section = BlockChainSection {
+            account: *account,
+            bottom_hash,
+            bottom_height: conf_height.height + 1,
+            top_hash,
+            top_height: to_height,
+        };
+        let blocks = self.chain_section_blocks(txn, &section, usize::MAX);
+        debug_assert_eq!(blocks.len() as u64, section.block_count());
+        self.write_confirmation_height(txn, &section);
+        blocks.into_iter().map(Arc::new).collect()
+    }
+
     pub fn dependent_blocks(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
@@ -872,6 +1821,33 @@ impl<T: Environment + 'static> Ledger<T> {
         self.is_dependency_confirmed(txn, &first) && self.is_dependency_confirmed(txn, &second)
     }
 
+    /// Returns false if the root block or the block which follows the root was pruned.
+    /// A pruned node cannot tell which block won such a root, so it must not vote on it.
+    pub fn pruning_safe_to_vote(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        root: &Root,
+    ) -> bool {
+        if !self.pruning_enabled() {
+            return true;
+        }
+
+        let root_hash = BlockHash::from(*root);
+        if self.store.pruned.exists(txn, &root_hash) {
+            return false;
+        }
+
+        let successor = self.store.block.successor(txn, &root_hash).or_else(|| {
+            self.account_info(txn, &Account::from(*root))
+                .map(|info| info.open_block)
+        });
+
+        match successor {
+            Some(successor) => !self.store.pruned.exists(txn, &successor),
+            None => true,
+        }
+    }
+
     fn is_dependency_confirmed(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
@@ -884,15 +1860,140 @@ impl<T: Environment + 'static> Ledger<T> {
         }
     }
 
-    /// Rollback blocks until `block' doesn't exist or it tries to penetrate the confirmation height
+    /// Rolls back the block and all unconfirmed blocks which depend on it within `txn`.
+    /// Nothing is committed, so the rollback becomes visible together with the other
+    /// changes of the caller's transaction. If a max rollback depth is configured, the rollback
+    /// is planned first and nothing is changed if it would remove more blocks than allowed.
     pub fn rollback(
         &self,
         txn: &mut LmdbWriteTransaction<T>,
         block: &BlockHash,
     ) -> anyhow::Result<Vec<BlockEnum>> {
+        let max_depth = self.max_rollback_depth();
+        if max_depth != usize::MAX {
+            self.rollback_planned(txn, block, max_depth)?;
+        }
         BlockRollbackPerformer::new(self, txn).roll_back(block)
     }
 
+    /// Rolls back the block and all unconfirmed blocks which depend on it, for rollbacks
+    /// which are too large for a single transaction. The planned rollback is recorded in the
+    /// rollback journal and `txn` is committed and renewed before the rollback starts, after
+    /// every batch of rolled back blocks and at the end. So pending changes of the caller are
+    /// committed as well. An interrupted rollback is completed by `recover_rollback_journal`.
+    pub fn rollback_journaled(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        block: &BlockHash,
+    ) -> anyhow::Result<Vec<BlockEnum>> {
+        let planned = self.rollback_planned(txn, block, self.max_rollback_depth())?;
+        self.store
+            .rollback_journal
+            .put(txn, block, &PlannedRollback(planned.clone()));
+        txn.refresh();
+        self.roll_back_journaled(txn, block, planned)
+    }
+
+    /// Completes the rollbacks which were interrupted by a crash. A rollback which
+    /// can't be completed anymore is aborted and removed from the journal.
+    /// Returns the number of completed rollbacks
+    pub fn recover_rollback_journal(&self, txn: &mut LmdbWriteTransaction<T>) -> usize {
+        self.store
+            .rollback_journal
+            .entries(txn)
+            .into_iter()
+            .filter(|(target, planned)| {
+                self.roll_back_journaled(txn, target, planned.0.clone())
+                    .is_ok()
+            })
+            .count()
+    }
+
+    /// Rolls back the planned blocks in order and keeps the journal entry of `target`
+    /// up to date. Blocks which were already rolled back are skipped.
+    /// The journal entry is removed when the rollback is finished or aborted.
+    fn roll_back_journaled(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        target: &BlockHash,
+        planned: Vec<BlockHash>,
+    ) -> anyhow::Result<Vec<BlockEnum>> {
+        let result = self.roll_back_planned_blocks(txn, target, &planned);
+        self.store.rollback_journal.del(txn, target);
+        txn.refresh();
+        result
+    }
+
+    fn roll_back_planned_blocks(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        target: &BlockHash,
+        planned: &[BlockHash],
+    ) -> anyhow::Result<Vec<BlockEnum>> {
+        let mut rolled_back = Vec::new();
+        let mut in_batch = 0;
+        for (i, hash) in planned.iter().enumerate() {
+            if self.store.block.exists(txn, hash) {
+                rolled_back.extend(BlockRollbackPerformer::new(self, txn).roll_back(hash)?);
+                in_batch += 1;
+            }
+            if in_batch >= ROLLBACK_JOURNAL_BATCH_SIZE {
+                self.store.rollback_journal.put(
+                    txn,
+                    target,
+                    &PlannedRollback(planned[i + 1..].to_vec()),
+                );
+                txn.refresh();
+                in_batch = 0;
+            }
+        }
+        Ok(rolled_back)
+    }
+
+    /// Returns the hashes of all blocks that a rollback of `block` would remove, in rollback order.
+    /// Fails if the rollback is impossible or if it would remove more than `max_depth` blocks.
+    pub fn rollback_planned(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        block: &BlockHash,
+        max_depth: usize,
+    ) -> anyhow::Result<Vec<BlockHash>> {
+        RollbackSimulator::new(self, txn, max_depth).simulate(block)
+    }
+
+    pub fn max_rollback_depth(&self) -> usize {
+        self.max_rollback_depth.load(Ordering::SeqCst)
+    }
+
+    pub fn set_max_rollback_depth(&self, max_depth: usize) {
+        self.max_rollback_depth.store(max_depth, Ordering::SeqCst);
+    }
+
+    pub fn reject_confirmed_forks(&self) -> bool {
+        self.reject_confirmed_forks.load(Ordering::SeqCst)
+    }
+
+    /// If enabled, `process` returns `ConfirmedFork` instead of `Fork` for forks which
+    /// can't win an election anymore, so that they don't start one
+    pub fn set_reject_confirmed_forks(&self, reject: bool) {
+        self.reject_confirmed_forks.store(reject, Ordering::SeqCst);
+    }
+
+    /// A fork is confirmed if the block it competes with is cemented. A final vote of this node
+    /// doesn't count, because the network may still confirm the other block.
+    pub fn is_confirmed_fork(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        block: &BlockEnum,
+    ) -> bool {
+        match self.successor(txn, &block.qualified_root()) {
+            Some(existing) => {
+                existing.hash() != block.hash() && self.block_confirmed(txn, &existing.hash())
+            }
+            None => false,
+        }
+    }
+
     /// Returns the latest block with representative information
     pub fn representative_block_hash(
         &self,
@@ -910,11 +2011,38 @@ impl<T: Environment + 'static> Ledger<T> {
         block: &mut BlockEnum,
     ) -> Result<(), ProcessResult> {
         let validator = BlockValidatorFactory::new(self, txn, block).create_validator();
-        let instructions = validator.validate()?;
+        let instructions = match validator.validate() {
+            Err(ProcessResult::Fork)
+                if self.reject_confirmed_forks() && self.is_confirmed_fork(txn, block) =>
+            {
+                return Err(ProcessResult::ConfirmedFork);
+            }
+            result => result?,
+        };
         BlockInserter::new(self, txn, block, &instructions).insert();
         Ok(())
     }
 
+    /// Validates a chain of dependent blocks in memory, without writing anything.
+    /// Returns the number of leading blocks which are valid. Blocks which are already
+    /// in the ledger are skipped. If a block can't be judged yet, because a dependency
+    /// is missing or it is a fork, the rest of the chain is left to the block processor.
+    pub fn valid_chain_prefix(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        blocks: &[BlockEnum],
+    ) -> usize {
+        let mut overlay = LedgerOverlay::new(self, txn);
+        for (i, block) in blocks.iter().enumerate() {
+            match overlay.validate(&mut block.clone()) {
+                Ok(()) | Err(ProcessResult::Old) => {}
+                Err(result) if result.is_transient() => return blocks.len(),
+                Err(_) => return i,
+            }
+        }
+        blocks.len()
+    }
+
     pub fn get_block(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
@@ -956,3 +2084,11 @@ impl<T: Environment + 'static> Ledger<T> {
         self.store.pending.get(txn, key)
     }
 }
+
+/// Outdated entries of the height index are detected by checking that every block follows its predecessor
+fn is_connected_chain(blocks: &[BlockEnum], expected_len: u64) -> bool {
+    blocks.len() as u64 == expected_len
+        && blocks
+            .windows(2)
+            .all(|pair| pair[1].previous() == pair[0].hash())
+}
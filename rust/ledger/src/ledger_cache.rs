@@ -3,7 +3,9 @@ use std::sync::{
     Arc,
 };
 
-use crate::RepWeights;
+use crate::{
+    EpochProgressCache, LedgerChecksumCache, ReceivableCache, RecentlyModifiedAccounts, RepWeights,
+};
 
 pub struct LedgerCache {
     pub rep_weights: Arc<RepWeights>,
@@ -12,6 +14,10 @@ pub struct LedgerCache {
     pub pruned_count: AtomicU64,
     pub account_count: AtomicU64,
     pub final_votes_confirmation_canary: AtomicBool,
+    pub receivable: Arc<ReceivableCache>,
+    pub recently_modified: RecentlyModifiedAccounts,
+    pub checksum: LedgerChecksumCache,
+    pub epoch_progress: EpochProgressCache,
 }
 
 impl LedgerCache {
@@ -24,6 +30,10 @@ impl LedgerCache {
             pruned_count: AtomicU64::new(0),
             account_count: AtomicU64::new(0),
             final_votes_confirmation_canary: AtomicBool::new(false),
+            receivable: Arc::new(ReceivableCache::new()),
+            recently_modified: RecentlyModifiedAccounts::default(),
+            checksum: LedgerChecksumCache::new(),
+            epoch_progress: EpochProgressCache::new(),
         }
     }
 }
@@ -0,0 +1,134 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rsnano_core::{Account, BlockHash, BlockHashBuilder, ConfirmationHeightInfo, LedgerChecksum};
+
+/// The digest of a single account, which is combined into the ledger checksum
+pub fn account_checksum(
+    account: &Account,
+    head: &BlockHash,
+    confirmation_height: &ConfirmationHeightInfo,
+) -> LedgerChecksum {
+    let hash = BlockHashBuilder::new()
+        .update(account.as_bytes())
+        .update(head.as_bytes())
+        .update(confirmation_height.height.to_be_bytes())
+        .update(confirmation_height.frontier.as_bytes())
+        .build();
+    LedgerChecksum::from_bytes(*hash.as_bytes())
+}
+
+/// XOR of the digests of all accounts. Because XOR is commutative, two nodes with the
+/// same account heads and confirmation heights have the same checksum, no matter in which
+/// order they processed the blocks. Adding and removing an account digest are the same
+/// operation, so the checksum can be updated without locking.
+pub struct LedgerChecksumCache {
+    parts: [AtomicU64; 4],
+}
+
+impl LedgerChecksumCache {
+    pub fn new() -> Self {
+        Self {
+            parts: Default::default(),
+        }
+    }
+
+    pub fn get(&self) -> LedgerChecksum {
+        let mut bytes = [0; 32];
+        for (chunk, part) in bytes.chunks_exact_mut(8).zip(&self.parts) {
+            chunk.copy_from_slice(&part.load(Ordering::SeqCst).to_be_bytes());
+        }
+        LedgerChecksum::from_bytes(bytes)
+    }
+
+    /// Adds the digest if it isn't contained yet, otherwise removes it
+    pub fn toggle(&self, digest: &LedgerChecksum) {
+        for (chunk, part) in digest.as_bytes().chunks_exact(8).zip(&self.parts) {
+            part.fetch_xor(
+                u64::from_be_bytes(chunk.try_into().unwrap()),
+                Ordering::SeqCst,
+            );
+        }
+    }
+
+    /// Replaces the account state `old` with `new`. A zero head means that the account
+    /// doesn't exist
+    pub fn update(
+        &self,
+        account: &Account,
+        old: (&BlockHash, &ConfirmationHeightInfo),
+        new: (&BlockHash, &ConfirmationHeightInfo),
+    ) {
+        if !old.0.is_zero() {
+            self.toggle(&account_checksum(account, old.0, old.1));
+        }
+        if !new.0.is_zero() {
+            self.toggle(&account_checksum(account, new.0, new.1));
+        }
+    }
+}
+
+impl Default for LedgerChecksumCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(LedgerChecksumCache::new().get(), LedgerChecksum::zero());
+    }
+
+    #[test]
+    fn order_independent() {
+        let a = LedgerChecksum::from(1);
+        let b = LedgerChecksum::from_bytes([0xAB; 32]);
+
+        let first = LedgerChecksumCache::new();
+        first.toggle(&a);
+        first.toggle(&b);
+        let second = LedgerChecksumCache::new();
+        second.toggle(&b);
+        second.toggle(&a);
+
+        assert_eq!(first.get(), second.get());
+        assert_ne!(first.get(), LedgerChecksum::zero());
+    }
+
+    #[test]
+    fn toggle_twice_removes_digest() {
+        let cache = LedgerChecksumCache::new();
+        let digest = LedgerChecksum::from_bytes([7; 32]);
+        cache.toggle(&digest);
+        assert_eq!(cache.get(), digest);
+        cache.toggle(&digest);
+        assert_eq!(cache.get(), LedgerChecksum::zero());
+    }
+
+    #[test]
+    fn update_account() {
+        let account = Account::from(1);
+        let unconfirmed = ConfirmationHeightInfo::default();
+        let confirmed = ConfirmationHeightInfo::new(1, BlockHash::from(2));
+        let cache = LedgerChecksumCache::new();
+
+        cache.update(
+            &account,
+            (&BlockHash::zero(), &unconfirmed),
+            (&BlockHash::from(2), &unconfirmed),
+        );
+        cache.update(
+            &account,
+            (&BlockHash::from(2), &unconfirmed),
+            (&BlockHash::from(2), &confirmed),
+        );
+
+        assert_eq!(
+            cache.get(),
+            account_checksum(&account, &BlockHash::from(2), &confirmed)
+        );
+    }
+}
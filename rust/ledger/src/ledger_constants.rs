@@ -297,4 +297,20 @@ impl LedgerConstants {
     pub fn unit_test() -> Self {
         Self::new(WORK_THRESHOLDS_STUB.clone(), Networks::NanoDevNetwork)
     }
+
+    /// Replaces the genesis block, which is used by private networks with a custom genesis
+    pub fn set_genesis(&mut self, mut genesis: BlockEnum) {
+        let account = genesis.account();
+        genesis.as_block_mut().set_sideband(BlockSideband::new(
+            account,
+            BlockHash::from(0),
+            self.genesis_amount,
+            1,
+            seconds_since_epoch(),
+            BlockDetails::new(Epoch::Epoch0, false, false, false),
+            Epoch::Epoch0,
+        ));
+        self.genesis_account = account;
+        self.genesis = Arc::new(RwLock::new(genesis));
+    }
 }
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use rsnano_core::{Account, AccountInfo, BlockEnum, BlockHash, BlockType, PendingInfo, PendingKey};
+use rsnano_store_lmdb::{Environment, Transaction};
+
+use crate::{
+    block_insertion::{BlockInsertInstructions, BlockValidatorFactory, LedgerView},
+    Ledger, ProcessResult,
+};
+
+/// Layers uncommitted ledger changes on top of a read transaction.
+/// A batch of dependent blocks (for example a chain segment received during bootstrap)
+/// can be validated in memory block by block, before a single write transaction
+/// is opened to insert them. Nothing is ever written to the store.
+pub struct LedgerOverlay<'a, T: Environment + 'static> {
+    ledger: &'a Ledger<T>,
+    txn: &'a dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    accounts: HashMap<Account, AccountInfo>,
+    /// `None` marks a pending entry that was received in the overlay
+    pending: HashMap<PendingKey, Option<PendingInfo>>,
+    /// Number of pending entries per account which were added in the overlay
+    added_pending: HashMap<Account, usize>,
+    /// `None` marks a frontier that was superseded in the overlay
+    frontiers: HashMap<BlockHash, Option<Account>>,
+    blocks: HashMap<BlockHash, BlockEnum>,
+}
+
+impl<'a, T: Environment + 'static> LedgerOverlay<'a, T> {
+    pub fn new(
+        ledger: &'a Ledger<T>,
+        txn: &'a dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> Self {
+        Self {
+            ledger,
+            txn,
+            accounts: HashMap::new(),
+            pending: HashMap::new(),
+            added_pending: HashMap::new(),
+            frontiers: HashMap::new(),
+            blocks: HashMap::new(),
+        }
+    }
+
+    /// Validates the block against the ledger plus all previously validated blocks.
+    /// On success the block gets its sideband and its changes are added to the overlay.
+    pub fn validate(&mut self, block: &mut BlockEnum) -> Result<(), ProcessResult> {
+        let instructions = BlockValidatorFactory::with_view(self.ledger, &*self, block)
+            .create_validator()
+            .validate()?;
+        self.apply(block, &instructions);
+        Ok(())
+    }
+
+    pub fn account_info(&self, account: &Account) -> Option<AccountInfo> {
+        match self.accounts.get(account) {
+            Some(info) => Some(info.clone()),
+            None => self.ledger.account_info(self.txn, account),
+        }
+    }
+
+    pub fn pending_info(&self, key: &PendingKey) -> Option<PendingInfo> {
+        match self.pending.get(key) {
+            Some(info) => info.clone(),
+            None => self.ledger.pending_info(self.txn, key),
+        }
+    }
+
+    pub fn get_block(&self, hash: &BlockHash) -> Option<BlockEnum> {
+        match self.blocks.get(hash) {
+            Some(block) => Some(block.clone()),
+            None => self.ledger.get_block(self.txn, hash),
+        }
+    }
+
+    /// Number of blocks that were validated in the overlay
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns true if the block was validated in this overlay
+    pub fn contains_block(&self, hash: &BlockHash) -> bool {
+        self.blocks.contains_key(hash)
+    }
+
+    fn apply(&mut self, block: &mut BlockEnum, instructions: &BlockInsertInstructions) {
+        block.set_sideband(instructions.set_sideband.clone());
+
+        self.accounts
+            .insert(instructions.account, instructions.set_account_info.clone());
+
+        if let Some(key) = &instructions.delete_pending {
+            self.set_pending(key, None);
+        }
+
+        if let Some((key, info)) = &instructions.insert_pending {
+            self.set_pending(key, Some(info.clone()));
+        }
+
+        let old_head = instructions.old_account_info.head;
+        if (&*self).get_frontier(&old_head).is_some() {
+            self.frontiers.insert(old_head, None);
+        }
+
+        if block.block_type() != BlockType::State {
+            self.frontiers
+                .insert(block.hash(), Some(instructions.account));
+        }
+
+        self.blocks.insert(block.hash(), block.clone());
+    }
+
+    fn set_pending(&mut self, key: &PendingKey, info: Option<PendingInfo>) {
+        let added = info.is_some();
+        if let Some(Some(_)) = self.pending.insert(key.clone(), info) {
+            if let Some(count) = self.added_pending.get_mut(&key.account) {
+                *count -= 1;
+                if *count == 0 {
+                    self.added_pending.remove(&key.account);
+                }
+            }
+        }
+        if added {
+            *self.added_pending.entry(key.account).or_default() += 1;
+        }
+    }
+}
+
+impl<'o, 'a, T: Environment + 'static> LedgerView for &'o LedgerOverlay<'a, T> {
+    fn account_info(&self, account: &Account) -> Option<AccountInfo> {
+        LedgerOverlay::account_info(self, account)
+    }
+
+    fn pending_info(&self, key: &PendingKey) -> Option<PendingInfo> {
+        LedgerOverlay::pending_info(self, key)
+    }
+
+    fn get_block(&self, hash: &BlockHash) -> Option<BlockEnum> {
+        LedgerOverlay::get_block(self, hash)
+    }
+
+    fn get_frontier(&self, hash: &BlockHash) -> Option<Account> {
+        match self.frontiers.get(hash) {
+            Some(account) => *account,
+            None => self.ledger.get_frontier(self.txn, hash),
+        }
+    }
+
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool {
+        self.blocks.contains_key(hash) || self.ledger.block_or_pruned_exists_txn(self.txn, hash)
+    }
+
+    fn any_pending(&self, account: &Account) -> bool {
+        if self.added_pending.contains_key(account) {
+            return true;
+        }
+
+        // Entries which were received in the overlay are still in the store
+        let mut it = self
+            .ledger
+            .store
+            .pending
+            .begin_at_key(self.txn, &PendingKey::new(*account, BlockHash::zero()));
+        while let Some((key, _)) = it.current() {
+            if key.account != *account {
+                break;
+            }
+            if !self.pending.contains_key(key) {
+                return true;
+            }
+            it.next();
+        }
+        false
+    }
+}
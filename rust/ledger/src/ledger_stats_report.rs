@@ -0,0 +1,125 @@
+use std::fmt::Display;
+
+use rsnano_core::{utils::PropertyTreeWriter, LedgerChecksum};
+
+/// Summary of the ledger size, which is logged on startup and returned by the `ledger_stats` RPC
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct LedgerStatsReport {
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub account_count: u64,
+    pub pruned_count: u64,
+    /// Order independent digest over the account heads and confirmation heights
+    pub checksum: LedgerChecksum,
+    pub version: Option<i32>,
+    /// Size of the LMDB memory map in bytes
+    pub map_size: u64,
+    /// Bytes of the memory map which are in use
+    pub map_used: u64,
+    /// Entry count per table, the largest table first
+    pub tables: Vec<(&'static str, u64)>,
+}
+
+impl LedgerStatsReport {
+    pub fn cemented_percent(&self) -> f64 {
+        if self.block_count == 0 {
+            0.0
+        } else {
+            self.cemented_count as f64 * 100.0 / self.block_count as f64
+        }
+    }
+
+    pub fn map_used_percent(&self) -> f64 {
+        if self.map_size == 0 {
+            0.0
+        } else {
+            self.map_used as f64 * 100.0 / self.map_size as f64
+        }
+    }
+
+    pub fn serialize_json(&self, writer: &mut dyn PropertyTreeWriter) -> anyhow::Result<()> {
+        writer.put_string("block_count", &self.block_count.to_string())?;
+        writer.put_string("cemented_count", &self.cemented_count.to_string())?;
+        writer.put_string(
+            "cemented_percent",
+            &format!("{:.2}", self.cemented_percent()),
+        )?;
+        writer.put_string("account_count", &self.account_count.to_string())?;
+        writer.put_string("pruned_count", &self.pruned_count.to_string())?;
+        writer.put_string("checksum", &self.checksum.encode_hex())?;
+        writer.put_string(
+            "version",
+            &self.version.map(|v| v.to_string()).unwrap_or_default(),
+        )?;
+        writer.put_string("map_size", &self.map_size.to_string())?;
+        writer.put_string("map_used", &self.map_used.to_string())?;
+        let mut tables = writer.new_writer();
+        for (name, entries) in &self.tables {
+            let mut entry = writer.new_writer();
+            entry.put_string("name", name)?;
+            entry.put_string("entries", &entries.to_string())?;
+            tables.push_back("", entry.as_ref());
+        }
+        writer.add_child("tables", tables.as_ref());
+        Ok(())
+    }
+}
+
+impl Display for LedgerStatsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Ledger: {} blocks, {} cemented ({:.2}%), {} accounts, {} pruned, version {}",
+            self.block_count,
+            self.cemented_count,
+            self.cemented_percent(),
+            self.account_count,
+            self.pruned_count,
+            self.version
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )?;
+        writeln!(
+            f,
+            "LMDB map: {} MB of {} MB used ({:.2}%)",
+            self.map_used / (1024 * 1024),
+            self.map_size / (1024 * 1024),
+            self.map_used_percent()
+        )?;
+        let tables = self
+            .tables
+            .iter()
+            .map(|(name, entries)| format!("{} {}", name, entries))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(f, "Largest tables: {}", tables)?;
+        write!(f, "Ledger checksum: {}", self.checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let report = LedgerStatsReport {
+            block_count: 4,
+            cemented_count: 3,
+            account_count: 2,
+            pruned_count: 0,
+            checksum: LedgerChecksum::from(1),
+            version: Some(22),
+            map_size: 100 * 1024 * 1024,
+            map_used: 25 * 1024 * 1024,
+            tables: vec![("blocks", 4), ("accounts", 2)],
+        };
+        assert_eq!(
+            report.to_string(),
+            "Ledger: 4 blocks, 3 cemented (75.00%), 2 accounts, 0 pruned, version 22\n\
+             LMDB map: 25 MB of 100 MB used (25.00%)\n\
+             Largest tables: blocks 4, accounts 2\n\
+             Ledger checksum: 0000000000000000000000000000000000000000000000000000000000000001"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use rsnano_core::{Account, Amount, ConfirmationHeightInfo};
+
+use super::LedgerContext;
+use crate::{ReceivableDetails, DEV_GENESIS_ACCOUNT};
+
+#[test]
+fn unknown_account() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger
+            .account_details(&txn, &Account::from(42), ReceivableDetails::Skip),
+        None
+    );
+}
+
+#[test]
+fn unconfirmed_account() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+
+    let details = ctx
+        .ledger
+        .account_details(&txn, &destination.account(), ReceivableDetails::Skip)
+        .unwrap();
+
+    assert_eq!(details.info, destination.info(&txn).unwrap());
+    assert_eq!(
+        details.confirmation_height,
+        ConfirmationHeightInfo::default()
+    );
+    assert_eq!(details.representative_block, open.hash());
+    assert_eq!(details.confirmed_balance, Amount::zero());
+    assert_eq!(details.confirmed_representative, Account::zero());
+}
+
+#[test]
+fn confirmed_values() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    ctx.inc_confirmation_height(&mut txn, &destination.account());
+
+    let mut change = destination.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    let details = ctx
+        .ledger
+        .account_details(&txn, &destination.account(), ReceivableDetails::Skip)
+        .unwrap();
+
+    assert_eq!(details.confirmation_height.height, 1);
+    assert_eq!(details.representative_block, change.hash());
+    assert_eq!(details.confirmed_balance, Amount::raw(50));
+    assert_eq!(details.confirmed_representative, destination.account());
+}
+
+#[test]
+fn receivable() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send1 = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut open = destination.open(&txn, send1.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+
+    let mut send2 = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    ctx.inc_confirmation_height(&mut txn, &DEV_GENESIS_ACCOUNT);
+    ctx.inc_confirmation_height(&mut txn, &DEV_GENESIS_ACCOUNT);
+
+    let mut send3 = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send3).unwrap();
+
+    let details = ctx
+        .ledger
+        .account_details(
+            &txn,
+            &destination.account(),
+            ReceivableDetails::TotalAndConfirmed,
+        )
+        .unwrap();
+
+    assert_eq!(details.receivable, Amount::raw(100));
+    assert_eq!(details.confirmed_receivable, Amount::raw(50));
+
+    let details = ctx
+        .ledger
+        .account_details(&txn, &destination.account(), ReceivableDetails::Total)
+        .unwrap();
+
+    assert_eq!(details.receivable, Amount::raw(100));
+    assert_eq!(details.confirmed_receivable, Amount::zero());
+}
+
+#[test]
+fn receivable_is_opt_in() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(*DEV_GENESIS_ACCOUNT).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    let details = ctx
+        .ledger
+        .account_details(&txn, &DEV_GENESIS_ACCOUNT, ReceivableDetails::Skip)
+        .unwrap();
+
+    assert_eq!(details.receivable, Amount::zero());
+    assert_eq!(details.confirmed_receivable, Amount::zero());
+}
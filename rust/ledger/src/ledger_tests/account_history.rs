@@ -0,0 +1,170 @@
+use rsnano_core::{Account, Amount, BlockHash};
+
+use super::LedgerContext;
+use crate::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+
+fn lines(buffer: Vec<u8>) -> Vec<String> {
+    String::from_utf8(buffer)
+        .unwrap()
+        .lines()
+        .map(|l| l.to_string())
+        .collect()
+}
+
+#[test]
+fn unknown_account() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let mut buffer = Vec::new();
+    assert!(ctx
+        .ledger
+        .account_history_stream(&txn, &Account::from(42), &mut buffer)
+        .is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn genesis_history() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let timestamp = ctx
+        .ledger
+        .block_local_timestamp(&txn, &DEV_GENESIS_HASH)
+        .unwrap();
+    let mut buffer = Vec::new();
+
+    ctx.ledger
+        .account_history_stream(&txn, &DEV_GENESIS_ACCOUNT, &mut buffer)
+        .unwrap();
+
+    assert_eq!(
+        lines(buffer),
+        vec![format!(
+            "{{\"type\":\"receive\",\"account\":\"{}\",\"amount\":\"{}\",\"local_timestamp\":\"{}\",\"height\":\"1\",\"hash\":\"{}\",\"confirmed\":\"true\"}}",
+            DEV_GENESIS_ACCOUNT.encode_account(),
+            Amount::MAX.to_string_dec(),
+            timestamp,
+            *DEV_GENESIS_HASH
+        )]
+    );
+}
+
+#[test]
+fn send_and_receive() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis
+        .send(&txn)
+        .link(destination.account())
+        .amount_sent(Amount::raw(10))
+        .build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    let mut change = destination.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    let mut buffer = Vec::new();
+    ctx.ledger
+        .account_history_stream(&txn, &destination.account(), &mut buffer)
+        .unwrap();
+    let history = lines(buffer);
+    assert_eq!(
+        history.len(),
+        1,
+        "change blocks are not part of the history"
+    );
+    assert!(history[0].starts_with(&format!(
+        "{{\"type\":\"receive\",\"account\":\"{}\",\"amount\":\"10\"",
+        DEV_GENESIS_ACCOUNT.encode_account()
+    )));
+    assert!(history[0].contains(&format!("\"hash\":\"{}\"", open.hash())));
+
+    let mut buffer = Vec::new();
+    ctx.ledger
+        .account_history_stream(&txn, &DEV_GENESIS_ACCOUNT, &mut buffer)
+        .unwrap();
+    let history = lines(buffer);
+    assert_eq!(history.len(), 2);
+    assert!(history[0].starts_with(&format!(
+        "{{\"type\":\"send\",\"account\":\"{}\",\"amount\":\"10\"",
+        destination.account().encode_account()
+    )));
+    assert!(history[0].contains("\"height\":\"2\""));
+    assert!(history[0].contains("\"confirmed\":\"false\""));
+}
+
+#[test]
+fn pagination() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    let mut send3 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send3).unwrap();
+
+    let mut buffer = Vec::new();
+    let next = ctx
+        .ledger
+        .account_history_page(&txn, &DEV_GENESIS_ACCOUNT, None, 2, &mut buffer)
+        .unwrap();
+    let page = lines(buffer);
+    assert_eq!(page.len(), 2);
+    assert!(page[0].contains(&send3.hash().to_string()));
+    assert!(page[1].contains(&send2.hash().to_string()));
+    assert_eq!(next, Some(send1.hash()));
+
+    let mut buffer = Vec::new();
+    let next = ctx
+        .ledger
+        .account_history_page(&txn, &DEV_GENESIS_ACCOUNT, next.as_ref(), 2, &mut buffer)
+        .unwrap();
+    let page = lines(buffer);
+    assert_eq!(page.len(), 2);
+    assert!(page[0].contains(&send1.hash().to_string()));
+    assert!(page[1].contains(&DEV_GENESIS_HASH.to_string()));
+    assert_eq!(next, None);
+}
+
+#[test]
+fn head_of_other_account() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let mut buffer = Vec::new();
+    assert!(ctx
+        .ledger
+        .account_history_page(
+            &txn,
+            &Account::from(42),
+            Some(&DEV_GENESIS_HASH),
+            10,
+            &mut buffer
+        )
+        .is_err());
+}
+
+#[test]
+fn unknown_head() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let mut buffer = Vec::new();
+    let next = ctx
+        .ledger
+        .account_history_page(
+            &txn,
+            &DEV_GENESIS_ACCOUNT,
+            Some(&BlockHash::from(42)),
+            10,
+            &mut buffer,
+        )
+        .unwrap();
+    assert_eq!(next, None);
+    assert!(buffer.is_empty());
+}
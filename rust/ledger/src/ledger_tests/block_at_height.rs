@@ -0,0 +1,164 @@
+use rsnano_core::{Account, BlockHash};
+
+use super::LedgerContext;
+use crate::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+
+#[test]
+fn unknown_account() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &Account::from(42), 1),
+        None
+    );
+}
+
+#[test]
+fn height_out_of_range() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 0),
+        None
+    );
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        None
+    );
+}
+
+#[test]
+fn walk_chain_without_index() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+
+    assert_eq!(
+        ctx.ledger
+            .store
+            .block
+            .hash_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        None
+    );
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 1),
+        Some(*DEV_GENESIS_HASH)
+    );
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(send1.hash())
+    );
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 3),
+        Some(send2.hash())
+    );
+}
+
+#[test]
+fn insert_into_index() {
+    let ctx = LedgerContext::with_height_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    assert_eq!(
+        ctx.ledger
+            .store
+            .block
+            .hash_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(send.hash())
+    );
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(send.hash())
+    );
+}
+
+#[test]
+fn rollback_removes_index_entry() {
+    let ctx = LedgerContext::with_height_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.rollback(&mut txn, &send.hash()).unwrap();
+
+    assert_eq!(
+        ctx.ledger
+            .store
+            .block
+            .hash_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        None
+    );
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        None
+    );
+}
+
+#[test]
+fn ignore_outdated_index_entry() {
+    let ctx = LedgerContext::with_height_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger
+        .store
+        .block
+        .put_height(&mut txn, &DEV_GENESIS_ACCOUNT, 2, &BlockHash::from(42));
+
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(send.hash())
+    );
+}
+
+#[test]
+fn use_index_entry_of_cemented_pruned_block() {
+    let ctx = LedgerContext::with_height_index();
+    ctx.ledger.enable_pruning();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    ctx.inc_confirmation_height(&mut txn, &DEV_GENESIS_ACCOUNT);
+    assert_eq!(ctx.ledger.pruning_action(&mut txn, &send1.hash(), 1), 1);
+
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(send1.hash())
+    );
+}
+
+#[test]
+fn ignore_index_entry_of_pruned_block_above_confirmation_height() {
+    let ctx = LedgerContext::with_height_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.store.pruned.put(&mut txn, &BlockHash::from(42));
+    ctx.ledger
+        .store
+        .block
+        .put_height(&mut txn, &DEV_GENESIS_ACCOUNT, 2, &BlockHash::from(42));
+
+    assert_eq!(
+        ctx.ledger.block_at_height(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(send.hash())
+    );
+}
@@ -0,0 +1,97 @@
+use rsnano_core::{Amount, BlockHash, BlockSubType};
+
+use super::LedgerContext;
+use crate::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+
+#[test]
+fn unknown_block() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(ctx.ledger.block_info(&txn, &BlockHash::from(42)), None);
+}
+
+#[test]
+fn genesis_block() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+
+    let info = ctx.ledger.block_info(&txn, &DEV_GENESIS_HASH).unwrap();
+
+    assert_eq!(info.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(info.amount, Some(Amount::MAX));
+    assert_eq!(info.balance, Amount::MAX);
+    assert_eq!(info.height, 1);
+    assert_eq!(info.successor, BlockHash::zero());
+    assert_eq!(info.subtype, None);
+    assert!(info.confirmed);
+}
+
+#[test]
+fn state_send() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).amount_sent(Amount::raw(10)).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    let info = ctx.ledger.block_info(&txn, &send.hash()).unwrap();
+
+    assert_eq!(info.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(info.amount, Some(Amount::raw(10)));
+    assert_eq!(info.balance, Amount::MAX - Amount::raw(10));
+    assert_eq!(info.height, 2);
+    assert_eq!(info.local_timestamp, send.sideband().unwrap().timestamp);
+    assert_eq!(info.subtype, Some(BlockSubType::Send));
+    assert!(!info.confirmed);
+
+    let genesis_info = ctx.ledger.block_info(&txn, &DEV_GENESIS_HASH).unwrap();
+    assert_eq!(genesis_info.successor, send.hash());
+}
+
+#[test]
+fn no_receive_hash_for_receivable_send() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    assert_eq!(ctx.ledger.receive_hash_for_send(&txn, &send.hash()), None);
+}
+
+#[test]
+fn no_receive_hash_for_non_send_block() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.receive_hash_for_send(&txn, &DEV_GENESIS_HASH),
+        None
+    );
+}
+
+#[test]
+fn receive_hash_of_confirmed_receive() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    let mut change = destination.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+    ctx.inc_confirmation_height(&mut txn, &destination.account());
+    ctx.inc_confirmation_height(&mut txn, &destination.account());
+
+    assert_eq!(
+        ctx.ledger.receive_hash_for_send(&txn, &send.hash()),
+        Some(open.hash())
+    );
+    let open_info = ctx.ledger.block_info(&txn, &open.hash()).unwrap();
+    assert_eq!(open_info.subtype, Some(BlockSubType::Receive));
+}
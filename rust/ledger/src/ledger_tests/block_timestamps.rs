@@ -0,0 +1,118 @@
+use rsnano_core::{utils::seconds_since_epoch, BlockHash};
+
+use super::LedgerContext;
+
+#[test]
+fn local_timestamp_of_unknown_block() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.block_local_timestamp(&txn, &BlockHash::from(42)),
+        None
+    );
+}
+
+#[test]
+fn local_timestamp_is_stored_in_sideband() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let before = seconds_since_epoch();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    let timestamp = ctx
+        .ledger
+        .block_local_timestamp(&txn, &send.hash())
+        .unwrap();
+    assert!(timestamp >= before && timestamp <= seconds_since_epoch());
+    assert_eq!(timestamp, send.sideband().unwrap().timestamp);
+}
+
+#[test]
+fn blocks_are_only_indexed_when_enabled() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    assert!(ctx
+        .ledger
+        .blocks_in_time_range(&txn, 0, u64::MAX, 10)
+        .is_empty());
+}
+
+#[test]
+fn find_blocks_in_time_range() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_timestamp_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    let timestamp = send1.sideband().unwrap().timestamp;
+
+    let mut found = ctx.ledger.blocks_in_time_range(&txn, 0, u64::MAX, 10);
+    found.sort();
+    let mut expected = vec![send1.hash(), send2.hash()];
+    expected.sort();
+    assert_eq!(found, expected);
+    assert_eq!(
+        ctx.ledger.blocks_in_time_range(&txn, 0, u64::MAX, 1).len(),
+        1
+    );
+    assert!(ctx
+        .ledger
+        .blocks_in_time_range(&txn, 0, timestamp, 10)
+        .is_empty());
+    assert!(ctx
+        .ledger
+        .blocks_in_time_range(&txn, u64::MAX - 1, u64::MAX, 10)
+        .is_empty());
+}
+
+#[test]
+fn rollback_removes_index_entry() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_timestamp_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.rollback(&mut txn, &send.hash()).unwrap();
+
+    assert!(ctx
+        .ledger
+        .blocks_in_time_range(&txn, 0, u64::MAX, 10)
+        .is_empty());
+}
+
+#[test]
+fn ignore_outdated_index_entry() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger
+        .store
+        .block
+        .put_timestamp(&mut txn, 1, &send.hash());
+    ctx.ledger
+        .store
+        .block
+        .put_timestamp(&mut txn, 2, &BlockHash::from(42));
+
+    assert!(ctx
+        .ledger
+        .blocks_in_time_range(&txn, 0, u64::MAX, 10)
+        .is_empty());
+}
@@ -0,0 +1,86 @@
+use std::sync::atomic::Ordering;
+
+use rsnano_core::{BlockEnum, BlockHash};
+
+use super::LedgerContext;
+use crate::DEV_GENESIS_ACCOUNT;
+
+fn process_sends(ctx: &LedgerContext, count: usize) -> Vec<BlockEnum> {
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut sends = Vec::new();
+    for _ in 0..count {
+        let mut send = genesis.send(&txn).build();
+        ctx.ledger.process(&mut txn, &mut send).unwrap();
+        sends.push(send);
+    }
+    txn.commit();
+    sends
+}
+
+fn hashes<'a>(blocks: impl IntoIterator<Item = &'a BlockEnum>) -> Vec<BlockHash> {
+    blocks.into_iter().map(|b| b.hash()).collect()
+}
+
+#[test]
+fn cement_up_to_height() {
+    let ctx = LedgerContext::empty();
+    let sends = process_sends(&ctx, 3);
+    let mut txn = ctx.ledger.rw_txn();
+
+    let cemented = ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 3);
+
+    assert_eq!(
+        hashes(cemented.iter().map(|b| b.as_ref())),
+        hashes(&sends[..2])
+    );
+    let conf_height = ctx
+        .ledger
+        .store
+        .confirmation_height
+        .get(&txn, &DEV_GENESIS_ACCOUNT)
+        .unwrap();
+    assert_eq!(conf_height.height, 3);
+    assert_eq!(conf_height.frontier, sends[1].hash());
+    assert_eq!(ctx.ledger.cache.cemented_count.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn continue_above_confirmation_height() {
+    let ctx = LedgerContext::empty();
+    let sends = process_sends(&ctx, 3);
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 2);
+
+    let cemented = ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 4);
+
+    assert_eq!(
+        hashes(cemented.iter().map(|b| b.as_ref())),
+        hashes(&sends[1..])
+    );
+    assert!(ctx.ledger.block_confirmed(&txn, &sends[2].hash()));
+}
+
+#[test]
+fn already_cemented() {
+    let ctx = LedgerContext::empty();
+    process_sends(&ctx, 1);
+    let mut txn = ctx.ledger.rw_txn();
+
+    let cemented = ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 1);
+
+    assert!(cemented.is_empty());
+    assert_eq!(ctx.ledger.cache.cemented_count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn height_above_account_chain() {
+    let ctx = LedgerContext::empty();
+    process_sends(&ctx, 1);
+    let mut txn = ctx.ledger.rw_txn();
+
+    let cemented = ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 3);
+
+    assert!(cemented.is_empty());
+    assert_eq!(ctx.ledger.cache.cemented_count.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,90 @@
+use rsnano_core::{BlockChainSection, BlockEnum, BlockHash};
+
+use super::LedgerContext;
+use crate::DEV_GENESIS_ACCOUNT;
+
+fn process_sends(ctx: &LedgerContext, count: usize) -> Vec<BlockEnum> {
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut sends = Vec::new();
+    for _ in 0..count {
+        let mut send = genesis.send(&txn).build();
+        ctx.ledger.process(&mut txn, &mut send).unwrap();
+        sends.push(send);
+    }
+    txn.commit();
+    sends
+}
+
+fn section(blocks: &[BlockEnum]) -> BlockChainSection {
+    let bottom = blocks.first().unwrap();
+    let top = blocks.last().unwrap();
+    BlockChainSection {
+        account: *DEV_GENESIS_ACCOUNT,
+        bottom_hash: bottom.hash(),
+        bottom_height: bottom.sideband().unwrap().height,
+        top_hash: top.hash(),
+        top_height: top.sideband().unwrap().height,
+    }
+}
+
+fn hashes(blocks: &[BlockEnum]) -> Vec<BlockHash> {
+    blocks.iter().map(|b| b.hash()).collect()
+}
+
+#[test]
+fn follow_successors_without_index() {
+    let ctx = LedgerContext::empty();
+    let sends = process_sends(&ctx, 3);
+    let txn = ctx.ledger.read_txn();
+
+    let blocks = ctx
+        .ledger
+        .chain_section_blocks(&txn, &section(&sends), usize::MAX);
+
+    assert_eq!(hashes(&blocks), hashes(&sends));
+}
+
+#[test]
+fn walk_height_index() {
+    let ctx = LedgerContext::with_height_index();
+    let sends = process_sends(&ctx, 3);
+    let txn = ctx.ledger.read_txn();
+
+    let blocks = ctx
+        .ledger
+        .chain_section_blocks(&txn, &section(&sends), usize::MAX);
+
+    assert_eq!(hashes(&blocks), hashes(&sends));
+}
+
+#[test]
+fn load_at_most_max_blocks() {
+    let ctx = LedgerContext::with_height_index();
+    let sends = process_sends(&ctx, 3);
+    let txn = ctx.ledger.read_txn();
+
+    let blocks = ctx.ledger.chain_section_blocks(&txn, &section(&sends), 2);
+
+    assert_eq!(hashes(&blocks), hashes(&sends[..2]));
+}
+
+#[test]
+fn ignore_outdated_index_entries() {
+    let ctx = LedgerContext::with_height_index();
+    let sends = process_sends(&ctx, 2);
+    let mut txn = ctx.ledger.rw_txn();
+    let bottom_height = sends[0].sideband().unwrap().height;
+    ctx.ledger.store.block.put_height(
+        &mut txn,
+        &DEV_GENESIS_ACCOUNT,
+        bottom_height + 1,
+        &BlockHash::from(42),
+    );
+
+    let blocks = ctx
+        .ledger
+        .chain_section_blocks(&txn, &section(&sends), usize::MAX);
+
+    assert_eq!(hashes(&blocks), hashes(&sends));
+}
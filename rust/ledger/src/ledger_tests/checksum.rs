@@ -0,0 +1,125 @@
+use rsnano_core::{BlockEnum, ConfirmationHeightInfo};
+
+use super::LedgerContext;
+use crate::{account_checksum, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+
+fn assert_cache_matches_store(ctx: &LedgerContext) {
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.cache.checksum.get(),
+        ctx.ledger.ledger_checksum(&txn)
+    );
+}
+
+#[test]
+fn empty_ledger() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+
+    assert_eq!(
+        ctx.ledger.ledger_checksum(&txn),
+        account_checksum(
+            &DEV_GENESIS_ACCOUNT,
+            &DEV_GENESIS_HASH,
+            &ConfirmationHeightInfo::new(1, *DEV_GENESIS_HASH)
+        )
+    );
+    assert_eq!(
+        ctx.ledger.cache.checksum.get(),
+        ctx.ledger.ledger_checksum(&txn)
+    );
+}
+
+#[test]
+fn update_on_new_blocks_and_cementing() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let before = ctx.ledger.cache.checksum.get();
+
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    txn.commit();
+    assert_ne!(ctx.ledger.cache.checksum.get(), before);
+    assert_cache_matches_store(&ctx);
+
+    let unconfirmed = ctx.ledger.cache.checksum.get();
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .cement_blocks(&mut txn, &destination.account(), 1);
+    txn.commit();
+    assert_ne!(ctx.ledger.cache.checksum.get(), unconfirmed);
+    assert_cache_matches_store(&ctx);
+}
+
+#[test]
+fn rollback_restores_checksum() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let before = ctx.ledger.cache.checksum.get();
+
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    ctx.ledger.rollback(&mut txn, &send.hash()).unwrap();
+    txn.commit();
+
+    assert_eq!(ctx.ledger.cache.checksum.get(), before);
+    assert_cache_matches_store(&ctx);
+}
+
+#[test]
+fn independent_of_processing_order() {
+    let ctx1 = LedgerContext::empty();
+    let genesis = ctx1.genesis_block_factory();
+    let destination1 = ctx1.block_factory();
+    let destination2 = ctx1.block_factory();
+
+    let mut txn = ctx1.ledger.rw_txn();
+    let mut send1 = genesis.send(&txn).link(destination1.account()).build();
+    ctx1.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).link(destination2.account()).build();
+    ctx1.ledger.process(&mut txn, &mut send2).unwrap();
+    let mut open1 = destination1.open(&txn, send1.hash()).build();
+    ctx1.ledger.process(&mut txn, &mut open1).unwrap();
+    let mut open2 = destination2.open(&txn, send2.hash()).build();
+    ctx1.ledger.process(&mut txn, &mut open2).unwrap();
+    txn.commit();
+
+    let ctx2 = LedgerContext::empty();
+    let mut txn = ctx2.ledger.rw_txn();
+    for block in [&send1, &send2, &open2, &open1] {
+        let mut block: BlockEnum = block.clone();
+        ctx2.ledger.process(&mut txn, &mut block).unwrap();
+    }
+    txn.commit();
+
+    assert_eq!(
+        ctx1.ledger.cache.checksum.get(),
+        ctx2.ledger.cache.checksum.get()
+    );
+}
+
+#[test]
+fn generate_on_startup() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    txn.commit();
+
+    let reloaded =
+        crate::Ledger::new(ctx.ledger.store.clone(), ctx.ledger.constants.clone()).unwrap();
+
+    assert_eq!(
+        reloaded.cache.checksum.get(),
+        ctx.ledger.cache.checksum.get()
+    );
+}
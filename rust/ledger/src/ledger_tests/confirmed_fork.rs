@@ -0,0 +1,96 @@
+use rsnano_core::{Account, BlockEnum};
+
+use super::LedgerContext;
+use crate::{ProcessResult, DEV_GENESIS_ACCOUNT};
+
+/// Processes a send from genesis and returns a fork of it
+fn send_and_fork(ctx: &LedgerContext) -> (BlockEnum, BlockEnum) {
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let fork = genesis.send(&txn).link(Account::from(2)).build();
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    txn.commit();
+    (send, fork)
+}
+
+#[test]
+fn disabled_by_default() {
+    let ctx = LedgerContext::empty();
+    let (_, mut fork) = send_and_fork(&ctx);
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 2);
+
+    assert_eq!(
+        ctx.ledger.process(&mut txn, &mut fork),
+        Err(ProcessResult::Fork)
+    );
+}
+
+#[test]
+fn reject_fork_of_cemented_block() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.set_reject_confirmed_forks(true);
+    let (_, mut fork) = send_and_fork(&ctx);
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger.cement_blocks(&mut txn, &DEV_GENESIS_ACCOUNT, 2);
+
+    assert_eq!(
+        ctx.ledger.process(&mut txn, &mut fork),
+        Err(ProcessResult::ConfirmedFork)
+    );
+}
+
+#[test]
+fn fork_of_unconfirmed_block_starts_election() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.set_reject_confirmed_forks(true);
+    let (_, mut fork) = send_and_fork(&ctx);
+    let mut txn = ctx.ledger.rw_txn();
+
+    assert_eq!(
+        ctx.ledger.process(&mut txn, &mut fork),
+        Err(ProcessResult::Fork)
+    );
+}
+
+#[test]
+fn fork_of_block_with_final_vote_starts_election() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.set_reject_confirmed_forks(true);
+    let (send, mut fork) = send_and_fork(&ctx);
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .store
+        .final_vote
+        .put(&mut txn, &send.qualified_root(), &send.hash());
+
+    assert_eq!(
+        ctx.ledger.process(&mut txn, &mut fork),
+        Err(ProcessResult::Fork)
+    );
+}
+
+#[test]
+fn reject_second_open_of_cemented_account() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.set_reject_confirmed_forks(true);
+    let genesis = ctx.genesis_block_factory();
+    let destination = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    let mut fork = destination
+        .open(&txn, send.hash())
+        .representative(Account::from(3))
+        .build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    ctx.ledger
+        .cement_blocks(&mut txn, &destination.account(), 1);
+
+    assert_eq!(
+        ctx.ledger.process(&mut txn, &mut fork),
+        Err(ProcessResult::ConfirmedFork)
+    );
+}
@@ -0,0 +1,126 @@
+use rsnano_core::{Account, BlockHash};
+
+use super::LedgerContext;
+
+#[test]
+fn sends_are_only_indexed_when_enabled() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(Account::from(1)).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    assert!(ctx
+        .ledger
+        .incoming_sends(&txn, &Account::from(1), &BlockHash::zero(), 10)
+        .is_empty());
+}
+
+#[test]
+fn find_incoming_sends() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_destination_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let destination = Account::from(1);
+
+    let mut send1 = genesis.send(&txn).link(destination).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).link(destination).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    let mut other = genesis.send(&txn).link(Account::from(2)).build();
+    ctx.ledger.process(&mut txn, &mut other).unwrap();
+
+    let mut expected = vec![send1.hash(), send2.hash()];
+    expected.sort();
+    assert_eq!(
+        ctx.ledger
+            .incoming_sends(&txn, &destination, &BlockHash::zero(), 10),
+        expected
+    );
+
+    // Paging
+    assert_eq!(
+        ctx.ledger
+            .incoming_sends(&txn, &destination, &BlockHash::zero(), 1),
+        vec![expected[0]]
+    );
+    assert_eq!(
+        ctx.ledger
+            .incoming_sends(&txn, &destination, &expected[0], 10),
+        vec![expected[1]]
+    );
+}
+
+#[test]
+fn receives_are_not_indexed() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_destination_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(genesis.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut receive = genesis.receive(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut receive).unwrap();
+
+    assert_eq!(
+        ctx.ledger
+            .incoming_sends(&txn, &genesis.account(), &BlockHash::zero(), 10),
+        vec![send.hash()]
+    );
+}
+
+#[test]
+fn rollback_removes_index_entry() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_destination_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send = genesis.send(&txn).link(Account::from(1)).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.rollback(&mut txn, &send.hash()).unwrap();
+
+    assert!(ctx
+        .ledger
+        .incoming_sends(&txn, &Account::from(1), &BlockHash::zero(), 10)
+        .is_empty());
+}
+
+#[test]
+fn pruned_sends_are_still_found() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_pruning();
+    ctx.ledger.enable_destination_index();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let mut send1 = genesis.send(&txn).link(Account::from(1)).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).link(Account::from(2)).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    assert_eq!(ctx.ledger.pruning_action(&mut txn, &send1.hash(), 1), 1);
+
+    assert_eq!(
+        ctx.ledger
+            .incoming_sends(&txn, &Account::from(1), &BlockHash::zero(), 10),
+        vec![send1.hash()]
+    );
+}
+
+#[test]
+fn ignore_outdated_index_entry() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .store
+        .block
+        .put_destination(&mut txn, &Account::from(1), &BlockHash::from(42));
+
+    assert!(ctx
+        .ledger
+        .incoming_sends(&txn, &Account::from(1), &BlockHash::zero(), 10)
+        .is_empty());
+}
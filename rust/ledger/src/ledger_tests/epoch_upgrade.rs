@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+use rsnano_core::{
+    work::{StubWorkPool, WorkPool},
+    Epoch, KeyPair, WorkVersion, DEV_GENESIS_KEY,
+};
+
+use super::{
+    setup_legacy_open_block, setup_send_block, upgrade_genesis_to_epoch_v1, LedgerContext,
+};
+use crate::{
+    ledger_constants::LEDGER_CONSTANTS_STUB, EpochUpgradeProgress, EpochUpgrader, GenerateCache,
+    Ledger,
+};
+
+#[test]
+fn progress_of_empty_ledger() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch0, 1)]),
+            unopened_with_receivables: 0,
+            unupgraded: 1,
+        }
+    );
+}
+
+#[test]
+fn progress_counts_accounts_per_epoch() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    setup_legacy_open_block(&ctx, &mut txn);
+    upgrade_genesis_to_epoch_v1(&ctx, &mut txn);
+
+    assert_eq!(
+        ctx.ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch0, 1), (Epoch::Epoch1, 1)]),
+            unopened_with_receivables: 0,
+            unupgraded: 2,
+        }
+    );
+}
+
+#[test]
+fn upgrade_all_accounts() {
+    let ctx = LedgerContext::empty();
+    {
+        let mut txn = ctx.ledger.rw_txn();
+        setup_legacy_open_block(&ctx, &mut txn);
+    }
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let upgrader =
+        create_upgrader(&ctx, &work_pool, DEV_GENESIS_KEY.clone(), Epoch::Epoch1).unwrap();
+
+    assert_eq!(upgrader.upgrade(usize::MAX), 2);
+
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch1, 2)]),
+            unopened_with_receivables: 0,
+            unupgraded: 2,
+        }
+    );
+}
+
+#[test]
+fn progress_counts_unopened_accounts_with_receivables() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    setup_send_block(&ctx, &mut txn);
+
+    assert_eq!(
+        ctx.ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch0, 1)]),
+            unopened_with_receivables: 1,
+            unupgraded: 2,
+        }
+    );
+}
+
+#[test]
+fn open_unopened_accounts_with_receivables() {
+    let ctx = LedgerContext::empty();
+    let destination = {
+        let mut txn = ctx.ledger.rw_txn();
+        setup_send_block(&ctx, &mut txn).destination.account()
+    };
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let upgrader =
+        create_upgrader(&ctx, &work_pool, DEV_GENESIS_KEY.clone(), Epoch::Epoch1).unwrap();
+
+    assert_eq!(upgrader.upgrade(usize::MAX), 2);
+
+    let txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.account_info(&txn, &destination).unwrap().epoch,
+        Epoch::Epoch1
+    );
+    assert_eq!(
+        ctx.ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch1, 2)]),
+            unopened_with_receivables: 0,
+            unupgraded: 2,
+        }
+    );
+}
+
+#[test]
+fn upgrade_is_limited_by_count() {
+    let ctx = LedgerContext::empty();
+    {
+        let mut txn = ctx.ledger.rw_txn();
+        setup_legacy_open_block(&ctx, &mut txn);
+    }
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let upgrader =
+        create_upgrader(&ctx, &work_pool, DEV_GENESIS_KEY.clone(), Epoch::Epoch1).unwrap();
+
+    assert_eq!(upgrader.upgrade(1), 1);
+    assert_eq!(upgrader.upgrade(1), 1);
+    assert_eq!(upgrader.upgrade(1), 0);
+}
+
+#[test]
+fn skip_accounts_that_are_not_sequential() {
+    let ctx = LedgerContext::empty();
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let upgrader =
+        create_upgrader(&ctx, &work_pool, DEV_GENESIS_KEY.clone(), Epoch::Epoch2).unwrap();
+
+    // genesis is still on epoch 0
+    assert_eq!(upgrader.upgrade(usize::MAX), 0);
+}
+
+#[test]
+fn reject_wrong_signer() {
+    let ctx = LedgerContext::empty();
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let result = create_upgrader(&ctx, &work_pool, KeyPair::new(), Epoch::Epoch1);
+    assert!(result.is_err());
+}
+
+#[test]
+fn generate_work_in_parallel() {
+    let ctx = LedgerContext::empty();
+    {
+        let mut txn = ctx.ledger.rw_txn();
+        setup_legacy_open_block(&ctx, &mut txn);
+    }
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let mut upgrader =
+        create_upgrader(&ctx, &work_pool, DEV_GENESIS_KEY.clone(), Epoch::Epoch1).unwrap();
+    upgrader.set_threads(4);
+
+    assert_eq!(upgrader.upgrade(usize::MAX), 2);
+}
+
+#[test]
+fn abort_when_work_generation_is_stopped() {
+    let ctx = LedgerContext::empty();
+    let upgrader = EpochUpgrader::new(
+        &ctx.ledger,
+        DEV_GENESIS_KEY.clone(),
+        Epoch::Epoch1,
+        |_, _| None,
+        |_| panic!("no block expected"),
+    )
+    .unwrap();
+
+    assert_eq!(upgrader.upgrade(usize::MAX), 0);
+}
+
+#[test]
+fn cached_progress_follows_upgrades_and_rollbacks() {
+    let ctx = LedgerContext::empty();
+    let destination = {
+        let mut txn = ctx.ledger.rw_txn();
+        setup_send_block(&ctx, &mut txn).destination.account()
+    };
+    let mut generate_cache = GenerateCache::new();
+    generate_cache.receivable = true;
+    let ledger = Ledger::with_cache(
+        ctx.ledger.store.clone(),
+        LEDGER_CONSTANTS_STUB.clone(),
+        &generate_cache,
+    )
+    .unwrap();
+    let work_pool = StubWorkPool::new(LEDGER_CONSTANTS_STUB.work.clone());
+    let upgrader = EpochUpgrader::new(
+        &ledger,
+        DEV_GENESIS_KEY.clone(),
+        Epoch::Epoch1,
+        |root, difficulty| work_pool.generate(WorkVersion::Work1, root, difficulty),
+        |mut block| {
+            let mut txn = ledger.rw_txn();
+            ledger.process(&mut txn, &mut block)
+        },
+    )
+    .unwrap();
+
+    let txn = ledger.read_txn();
+    assert_eq!(
+        ledger.epoch_upgrade_progress(&txn),
+        ctx.ledger.epoch_upgrade_progress(&txn)
+    );
+    drop(txn);
+
+    assert_eq!(upgrader.upgrade(usize::MAX), 2);
+    let mut txn = ledger.rw_txn();
+    assert_eq!(
+        ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch1, 2)]),
+            unopened_with_receivables: 0,
+            unupgraded: 2,
+        }
+    );
+
+    let open = ledger.account_info(&txn, &destination).unwrap().head;
+    ledger.rollback(&mut txn, &open).unwrap();
+    assert_eq!(
+        ledger.epoch_upgrade_progress(&txn),
+        EpochUpgradeProgress {
+            accounts_per_epoch: BTreeMap::from([(Epoch::Epoch1, 1)]),
+            unopened_with_receivables: 1,
+            unupgraded: 2,
+        }
+    );
+}
+
+/// Processes the epoch blocks directly in the ledger instead of the block processor
+fn create_upgrader<'a>(
+    ctx: &'a LedgerContext,
+    work_pool: &'a StubWorkPool,
+    signer: KeyPair,
+    epoch: Epoch,
+) -> anyhow::Result<EpochUpgrader<'a>> {
+    EpochUpgrader::new(
+        &ctx.ledger,
+        signer,
+        epoch,
+        |root, difficulty| work_pool.generate(WorkVersion::Work1, root, difficulty),
+        |mut block| {
+            let mut txn = ctx.ledger.rw_txn();
+            ctx.ledger.process(&mut txn, &mut block)
+        },
+    )
+}
@@ -0,0 +1,331 @@
+//! Feeds random, but structurally plausible block sequences into `Ledger::process`.
+//! Most of the generated blocks are invalid. The ledger has to reject them without
+//! panicking and its cached counters have to match the store afterwards.
+
+use super::LedgerContext;
+use crate::GenerateCache;
+use proptest::prelude::*;
+use rsnano_core::{
+    Account, Amount, BlockBuilder, BlockEnum, BlockHash, Epoch, KeyPair, Link, DEV_GENESIS_KEY,
+};
+use rsnano_store_lmdb::LmdbWriteTransaction;
+use std::sync::atomic::Ordering;
+
+/// Number of accounts which take part in a sequence. The first one is the genesis account
+const ACCOUNTS: usize = 4;
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    State(BalanceChange),
+    LegacySend,
+    LegacyReceive,
+    LegacyOpen,
+    LegacyChange,
+    Epoch(Epoch),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum BalanceChange {
+    Unchanged,
+    Decrease,
+    Increase,
+    /// Increase by the amount of the source block
+    MatchSource,
+}
+
+/// The block a hash field of a generated block points to
+#[derive(Clone, Copy, Debug)]
+enum Target {
+    /// The head block of the account of the generated block
+    OwnFrontier,
+    /// The head block of an account
+    Frontier(usize),
+    /// One of the most recently generated blocks, counted backwards
+    Recent(usize),
+    /// One of the blocks which were generated before, including the rejected ones
+    Generated(usize),
+    Zero,
+    Random(u64),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Mutation {
+    None,
+    BadSignature,
+    InsufficientWork,
+    /// State blocks only: the block is signed by its account, but contains another account
+    WrongAccount,
+}
+
+#[derive(Clone, Debug)]
+struct BlockRecipe {
+    kind: Kind,
+    account: usize,
+    previous: Target,
+    source: Target,
+    destination: usize,
+    representative: usize,
+    amount: u128,
+    mutation: Mutation,
+}
+
+fn kind() -> impl Strategy<Value = Kind> {
+    prop_oneof![
+        2 => Just(Kind::State(BalanceChange::Decrease)),
+        2 => Just(Kind::State(BalanceChange::MatchSource)),
+        1 => Just(Kind::State(BalanceChange::Increase)),
+        1 => Just(Kind::State(BalanceChange::Unchanged)),
+        2 => Just(Kind::LegacySend),
+        1 => Just(Kind::LegacyReceive),
+        1 => Just(Kind::LegacyOpen),
+        1 => Just(Kind::LegacyChange),
+        1 => Just(Kind::Epoch(Epoch::Epoch1)),
+        1 => Just(Kind::Epoch(Epoch::Epoch2)),
+    ]
+}
+
+/// Only the genesis account has a balance at the beginning, so it is chosen more often
+fn account() -> impl Strategy<Value = usize> {
+    prop_oneof![
+        2 => Just(0),
+        3 => 1..ACCOUNTS,
+    ]
+}
+
+fn previous() -> impl Strategy<Value = Target> {
+    prop_oneof![
+        12 => Just(Target::OwnFrontier),
+        4 => target(),
+    ]
+}
+
+fn source() -> impl Strategy<Value = Target> {
+    prop_oneof![
+        12 => (0..4usize).prop_map(Target::Recent),
+        4 => target(),
+    ]
+}
+
+fn target() -> impl Strategy<Value = Target> {
+    prop_oneof![
+        4 => (0..ACCOUNTS).prop_map(Target::Frontier),
+        4 => (0..64usize).prop_map(Target::Generated),
+        1 => Just(Target::Zero),
+        1 => any::<u64>().prop_map(Target::Random),
+    ]
+}
+
+fn amount() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        4 => 1..1000u128,
+        1 => Just(0u128),
+        1 => any::<u128>(),
+    ]
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        16 => Just(Mutation::None),
+        1 => Just(Mutation::BadSignature),
+        1 => Just(Mutation::InsufficientWork),
+        1 => Just(Mutation::WrongAccount),
+    ]
+}
+
+prop_compose! {
+    fn block_recipe()(
+        kind in kind(),
+        account in account(),
+        previous in previous(),
+        source in source(),
+        destination in 0..ACCOUNTS,
+        representative in 0..ACCOUNTS,
+        amount in amount(),
+        mutation in mutation(),
+    ) -> BlockRecipe {
+        BlockRecipe { kind, account, previous, source, destination, representative, amount, mutation }
+    }
+}
+
+struct BlockFuzzer<'a> {
+    ctx: &'a LedgerContext,
+    keys: Vec<KeyPair>,
+    generated: Vec<BlockHash>,
+}
+
+impl<'a> BlockFuzzer<'a> {
+    fn new(ctx: &'a LedgerContext) -> Self {
+        let mut keys = vec![DEV_GENESIS_KEY.clone()];
+        keys.extend((1..ACCOUNTS).map(|_| KeyPair::new()));
+        Self {
+            ctx,
+            keys,
+            generated: vec![ctx.ledger.constants.genesis.read().unwrap().hash()],
+        }
+    }
+
+    fn account(&self, index: usize) -> Account {
+        self.keys[index].public_key()
+    }
+
+    fn resolve(&self, txn: &LmdbWriteTransaction, target: Target, account: usize) -> BlockHash {
+        match target {
+            Target::OwnFrontier => self.frontier(txn, account),
+            Target::Frontier(index) => self.frontier(txn, index),
+            Target::Recent(index) => {
+                self.generated[self.generated.len() - 1 - index.min(self.generated.len() - 1)]
+            }
+            Target::Generated(index) => self.generated[index % self.generated.len()],
+            Target::Zero => BlockHash::zero(),
+            Target::Random(value) => BlockHash::from(value),
+        }
+    }
+
+    fn frontier(&self, txn: &LmdbWriteTransaction, account: usize) -> BlockHash {
+        self.ctx
+            .ledger
+            .account_info(txn, &self.account(account))
+            .map(|info| info.head)
+            .unwrap_or_default()
+    }
+
+    fn build(&self, txn: &LmdbWriteTransaction, recipe: &BlockRecipe) -> BlockEnum {
+        let ledger = &self.ctx.ledger;
+        let account = self.account(recipe.account);
+        let info = ledger.account_info(txn, &account).unwrap_or_default();
+        let previous = self.resolve(txn, recipe.previous, recipe.account);
+        let source = self.resolve(txn, recipe.source, recipe.account);
+        let destination = self.account(recipe.destination);
+        let representative = self.account(recipe.representative);
+        let amount = Amount::raw(recipe.amount);
+        let key = match recipe.mutation {
+            Mutation::BadSignature => &self.keys[(recipe.account + 1) % ACCOUNTS],
+            _ => &self.keys[recipe.account],
+        };
+        let state_account = match recipe.mutation {
+            Mutation::WrongAccount => self.account((recipe.account + 1) % ACCOUNTS),
+            _ => account,
+        };
+        let work = match recipe.mutation {
+            Mutation::InsufficientWork => Some(0),
+            _ => None,
+        };
+
+        match recipe.kind {
+            Kind::State(change) => {
+                let (balance, link): (Amount, Link) = match change {
+                    BalanceChange::Unchanged => (info.balance, representative.into()),
+                    BalanceChange::Decrease => {
+                        (info.balance.wrapping_sub(amount), destination.into())
+                    }
+                    BalanceChange::Increase => (info.balance.wrapping_add(amount), source.into()),
+                    BalanceChange::MatchSource => {
+                        let received = ledger.amount(txn, &source).unwrap_or(amount);
+                        (info.balance.wrapping_add(received), source.into())
+                    }
+                };
+                let builder = BlockBuilder::state()
+                    .account(state_account)
+                    .previous(previous)
+                    .representative(representative)
+                    .balance(balance)
+                    .link(link)
+                    .sign(key);
+                with_work(builder, work, |b, w| b.work(w)).build()
+            }
+            Kind::LegacySend => {
+                let builder = BlockBuilder::legacy_send()
+                    .previous(previous)
+                    .destination(destination)
+                    .balance(info.balance.wrapping_sub(amount))
+                    .sign(key.clone());
+                with_work(builder, work, |b, w| b.work(w)).build()
+            }
+            Kind::LegacyReceive => {
+                let builder = BlockBuilder::legacy_receive()
+                    .previous(previous)
+                    .source(source)
+                    .sign(key);
+                with_work(builder, work, |b, w| b.work(w)).build()
+            }
+            Kind::LegacyOpen => {
+                let builder = BlockBuilder::legacy_open()
+                    .account(account)
+                    .source(source)
+                    .representative(representative)
+                    .sign(key);
+                with_work(builder, work, |b, w| b.work(w)).build()
+            }
+            Kind::LegacyChange => {
+                let builder = BlockBuilder::legacy_change()
+                    .previous(previous)
+                    .representative(representative)
+                    .sign(key);
+                with_work(builder, work, |b, w| b.work(w)).build()
+            }
+            Kind::Epoch(epoch) => {
+                let signer = match recipe.mutation {
+                    Mutation::BadSignature => key,
+                    _ => &*DEV_GENESIS_KEY,
+                };
+                let builder = BlockBuilder::state()
+                    .account(state_account)
+                    .previous(previous)
+                    .representative(info.representative)
+                    .balance(info.balance)
+                    .link(ledger.epoch_link(epoch).unwrap())
+                    .sign(signer);
+                with_work(builder, work, |b, w| b.work(w)).build()
+            }
+        }
+    }
+
+    /// Sum of the balances of all accounts of the sequence
+    fn total_balance(&self, txn: &LmdbWriteTransaction) -> u128 {
+        (0..ACCOUNTS)
+            .filter_map(|i| self.ctx.ledger.account_info(txn, &self.account(i)))
+            .map(|info| info.balance.number())
+            .sum()
+    }
+}
+
+fn with_work<B>(builder: B, work: Option<u64>, set_work: impl FnOnce(B, u64) -> B) -> B {
+    match work {
+        Some(work) => set_work(builder, work),
+        None => builder,
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn process_random_blocks(recipes in prop::collection::vec(block_recipe(), 1..40)) {
+        let ctx = LedgerContext::empty();
+        let mut fuzzer = BlockFuzzer::new(&ctx);
+        let mut txn = ctx.ledger.rw_txn();
+        let mut processed = 1;
+
+        for recipe in &recipes {
+            let mut block = fuzzer.build(&txn, recipe);
+            fuzzer.generated.push(block.hash());
+            if ctx.ledger.process(&mut txn, &mut block).is_ok() {
+                processed += 1;
+            }
+        }
+
+        prop_assert_eq!(ctx.ledger.cache.block_count.load(Ordering::SeqCst), processed);
+        let total_weight: u128 = ctx
+            .ledger
+            .cache
+            .rep_weights
+            .get_rep_amounts()
+            .values()
+            .map(|weight| weight.number())
+            .sum();
+        prop_assert_eq!(total_weight, fuzzer.total_balance(&txn));
+        prop_assert_eq!(ctx.ledger.cache.checksum.get(), ctx.ledger.ledger_checksum(&txn));
+        txn.commit();
+        prop_assert_eq!(ctx.ledger.check_integrity(&GenerateCache::new()), Vec::new());
+    }
+}
@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use crate::{ledger_constants::LEDGER_CONSTANTS_STUB, Ledger};
 use rsnano_core::{Account, ConfirmationHeightInfo};
-use rsnano_store_lmdb::{EnvironmentWrapper, LmdbStore, LmdbWriteTransaction, TestDbFile};
+use rsnano_store_lmdb::{
+    EnvOptions, EnvironmentWrapper, LmdbConfig, LmdbStore, LmdbWriteTransaction, TestDbFile,
+};
 
 use super::AccountBlockFactory;
 
@@ -13,9 +15,25 @@ pub(crate) struct LedgerContext {
 
 impl LedgerContext {
     pub fn empty() -> Self {
+        Self::with_options(&EnvOptions::default())
+    }
+
+    /// Empty ledger which maintains the block height index
+    pub fn with_height_index() -> Self {
+        Self::with_options(&EnvOptions {
+            config: LmdbConfig {
+                block_height_index: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    fn with_options(options: &EnvOptions) -> Self {
         let db_file = TestDbFile::random();
         let store = Arc::new(
             LmdbStore::<EnvironmentWrapper>::open(&db_file.path)
+                .options(options)
                 .build()
                 .unwrap(),
         );
@@ -0,0 +1,104 @@
+use super::LedgerContext;
+use crate::{
+    ledger_constants::LEDGER_CONSTANTS_STUB, GenerateCache, IntegrityProblem, Ledger,
+    DEV_GENESIS_HASH,
+};
+use rsnano_store_lmdb::STORE_VERSION_CURRENT;
+
+#[test]
+fn fresh_ledger_is_consistent() {
+    let ctx = LedgerContext::empty();
+    assert_eq!(
+        ctx.ledger.check_integrity(&GenerateCache::new()),
+        Vec::new()
+    );
+}
+
+#[test]
+fn pruned_blocks_are_counted() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_pruning();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    assert_eq!(ctx.ledger.pruning_action(&mut txn, &send1.hash(), 1), 1);
+    txn.commit();
+
+    assert_eq!(
+        ctx.ledger.check_integrity(&GenerateCache::new()),
+        Vec::new()
+    );
+}
+
+#[test]
+fn unsupported_version() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger
+        .store
+        .version
+        .put(&mut txn, STORE_VERSION_CURRENT + 1);
+    txn.commit();
+
+    assert_eq!(
+        ctx.ledger.check_integrity(&GenerateCache::new()),
+        vec![IntegrityProblem::UnsupportedVersion(Some(
+            STORE_VERSION_CURRENT + 1
+        ))]
+    );
+}
+
+#[test]
+fn missing_block() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.store.block.del(&mut txn, &send.hash());
+    txn.commit();
+
+    assert_eq!(
+        ctx.ledger.check_integrity(&GenerateCache::new()),
+        vec![IntegrityProblem::BlockCountMismatch {
+            cached: 2,
+            stored: 1
+        }]
+    );
+}
+
+#[test]
+fn missing_genesis() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    ctx.ledger.store.block.del(&mut txn, &DEV_GENESIS_HASH);
+    txn.commit();
+
+    let problems = ctx.ledger.check_integrity(&GenerateCache::new());
+    assert!(problems.contains(&IntegrityProblem::GenesisMissing));
+}
+
+#[test]
+fn skip_counts_that_were_not_generated() {
+    let ctx = LedgerContext::empty();
+    let generate_cache = GenerateCache {
+        reps: false,
+        cemented_count: false,
+        unchecked_count: false,
+        account_count: false,
+        block_count: false,
+        receivable: false,
+        checksum: false,
+    };
+    let ledger = Ledger::with_cache(
+        ctx.ledger.store.clone(),
+        LEDGER_CONSTANTS_STUB.clone(),
+        &generate_cache,
+    )
+    .unwrap();
+
+    assert_eq!(ledger.check_integrity(&generate_cache), Vec::new());
+}
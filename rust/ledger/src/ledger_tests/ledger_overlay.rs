@@ -0,0 +1,228 @@
+use rsnano_core::{
+    Account, Amount, BlockBuilder, BlockEnum, Epoch, KeyPair, PendingKey, Signature,
+    DEV_GENESIS_KEY,
+};
+
+use super::LedgerContext;
+use crate::{LedgerOverlay, ProcessResult, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+
+fn genesis_send(previous: &BlockEnum, destination: Account) -> BlockEnum {
+    BlockBuilder::state()
+        .account(*DEV_GENESIS_ACCOUNT)
+        .previous(previous.hash())
+        .representative(*DEV_GENESIS_ACCOUNT)
+        .balance(previous.balance_calculated() - Amount::raw(100))
+        .link(destination)
+        .sign(&DEV_GENESIS_KEY)
+        .build()
+}
+
+fn open(key: &KeyPair, send: &BlockEnum) -> BlockEnum {
+    BlockBuilder::state()
+        .account(key.public_key())
+        .previous(0)
+        .representative(key.public_key())
+        .balance(Amount::raw(100))
+        .link(send.hash())
+        .sign(key)
+        .build()
+}
+
+#[test]
+fn validate_chained_sends_without_writing() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let mut overlay = LedgerOverlay::new(&ctx.ledger, &txn);
+
+    let mut send1 = genesis_send(&genesis, Account::from(1));
+    overlay.validate(&mut send1).unwrap();
+    let mut send2 = genesis_send(&send1, Account::from(2));
+    overlay.validate(&mut send2).unwrap();
+
+    assert_eq!(overlay.block_count(), 2);
+    assert_eq!(send2.sideband().unwrap().height, 3);
+    assert_eq!(
+        overlay.account_info(&DEV_GENESIS_ACCOUNT).unwrap().head,
+        send2.hash()
+    );
+    assert!(overlay
+        .pending_info(&PendingKey::new(Account::from(2), send2.hash()))
+        .is_some());
+    assert_eq!(
+        ctx.ledger.latest(&txn, &DEV_GENESIS_ACCOUNT),
+        Some(*DEV_GENESIS_HASH)
+    );
+    assert!(!ctx.ledger.block_or_pruned_exists_txn(&txn, &send1.hash()));
+}
+
+#[test]
+fn receive_send_from_overlay() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let mut overlay = LedgerOverlay::new(&ctx.ledger, &txn);
+    let key = KeyPair::new();
+
+    let mut send = genesis_send(&genesis, key.public_key());
+    overlay.validate(&mut send).unwrap();
+    let mut open = open(&key, &send);
+    overlay.validate(&mut open).unwrap();
+
+    assert_eq!(
+        overlay.account_info(&key.public_key()).unwrap().balance,
+        Amount::raw(100)
+    );
+    assert_eq!(
+        overlay.pending_info(&PendingKey::new(key.public_key(), send.hash())),
+        None
+    );
+}
+
+#[test]
+fn receive_twice_in_overlay() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let mut overlay = LedgerOverlay::new(&ctx.ledger, &txn);
+    let key = KeyPair::new();
+
+    let mut send = genesis_send(&genesis, key.public_key());
+    overlay.validate(&mut send).unwrap();
+    let mut open = open(&key, &send);
+    overlay.validate(&mut open).unwrap();
+
+    let mut receive = BlockBuilder::state()
+        .account(key.public_key())
+        .previous(open.hash())
+        .representative(key.public_key())
+        .balance(Amount::raw(200))
+        .link(send.hash())
+        .sign(&key)
+        .build();
+
+    assert_eq!(
+        overlay.validate(&mut receive),
+        Err(ProcessResult::Unreceivable)
+    );
+}
+
+#[test]
+fn epoch_open_with_receivable_from_overlay() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let mut overlay = LedgerOverlay::new(&ctx.ledger, &txn);
+    let key = KeyPair::new();
+
+    let mut epoch_open = BlockBuilder::state()
+        .account(key.public_key())
+        .previous(0)
+        .representative(0)
+        .balance(0)
+        .link(ctx.ledger.epoch_link(Epoch::Epoch1).unwrap())
+        .sign(&DEV_GENESIS_KEY)
+        .build();
+    assert_eq!(
+        overlay.validate(&mut epoch_open.clone()),
+        Err(ProcessResult::GapEpochOpenPending)
+    );
+
+    let mut send = genesis_send(&genesis, key.public_key());
+    overlay.validate(&mut send).unwrap();
+    overlay.validate(&mut epoch_open).unwrap();
+}
+
+#[test]
+fn fork_in_overlay() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let mut overlay = LedgerOverlay::new(&ctx.ledger, &txn);
+
+    let mut send1 = genesis_send(&genesis, Account::from(1));
+    overlay.validate(&mut send1).unwrap();
+    let mut fork = genesis_send(&genesis, Account::from(2));
+
+    assert_eq!(overlay.validate(&mut fork), Err(ProcessResult::Fork));
+    assert_eq!(overlay.block_count(), 1);
+}
+
+#[test]
+fn validated_blocks_can_be_processed() {
+    let ctx = LedgerContext::empty();
+    let key = KeyPair::new();
+    let mut blocks = Vec::new();
+    {
+        let txn = ctx.ledger.read_txn();
+        let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+        let mut overlay = LedgerOverlay::new(&ctx.ledger, &txn);
+        let mut send = genesis_send(&genesis, key.public_key());
+        overlay.validate(&mut send).unwrap();
+        let mut open = open(&key, &send);
+        overlay.validate(&mut open).unwrap();
+        blocks.push(send);
+        blocks.push(open);
+    }
+
+    let mut txn = ctx.ledger.rw_txn();
+    for block in &mut blocks {
+        ctx.ledger.process(&mut txn, block).unwrap();
+    }
+
+    assert_eq!(
+        ctx.ledger.latest(&txn, &key.public_key()),
+        Some(blocks[1].hash())
+    );
+}
+
+#[test]
+fn valid_chain_prefix_of_valid_chain() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let key = KeyPair::new();
+    let send = genesis_send(&genesis, key.public_key());
+    let open = open(&key, &send);
+
+    assert_eq!(ctx.ledger.valid_chain_prefix(&txn, &[send, open]), 2);
+}
+
+#[test]
+fn valid_chain_prefix_skips_old_blocks() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let send = genesis_send(&genesis, Account::from(1));
+
+    assert_eq!(ctx.ledger.valid_chain_prefix(&txn, &[genesis, send]), 2);
+}
+
+#[test]
+fn valid_chain_prefix_stops_at_invalid_block() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let key = KeyPair::new();
+    let send = genesis_send(&genesis, key.public_key());
+    let mut bad_signature = genesis_send(&send, Account::from(2));
+    bad_signature.set_block_signature(&Signature::new());
+    let send3 = genesis_send(&bad_signature, Account::from(3));
+
+    assert_eq!(
+        ctx.ledger
+            .valid_chain_prefix(&txn, &[send, bad_signature, send3]),
+        1
+    );
+}
+
+#[test]
+fn valid_chain_prefix_accepts_chain_with_missing_dependency() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.ledger.get_block(&txn, &DEV_GENESIS_HASH).unwrap();
+    let send1 = genesis_send(&genesis, Account::from(1));
+    let send2 = genesis_send(&send1, Account::from(2));
+
+    assert_eq!(ctx.ledger.valid_chain_prefix(&txn, &[send2]), 1);
+}
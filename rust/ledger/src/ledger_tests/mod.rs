@@ -1,23 +1,46 @@
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::atomic::Ordering;
 
 mod helpers;
 use crate::{
-    ledger_constants::LEDGER_CONSTANTS_STUB, Ledger, LedgerCache, UncementedInfo, DEV_GENESIS,
-    DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH,
+    ledger_constants::LEDGER_CONSTANTS_STUB, BootstrapWeights, Ledger, LedgerCache, UncementedInfo,
+    DEV_GENESIS, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH,
 };
 pub(crate) use helpers::*;
 use rsnano_core::{
-    Account, Amount, BlockBuilder, BlockHash, KeyPair, QualifiedRoot, Root, TestAccountChain,
-    DEV_GENESIS_KEY, GXRB_RATIO,
+    Account, Amount, BlockBuilder, BlockHash, ConfirmationHeightInfo, KeyPair, QualifiedRoot, Root,
+    TestAccountChain, DEV_GENESIS_KEY, GXRB_RATIO,
 };
 
+mod account_details;
+mod account_history;
+mod block_at_height;
+mod block_info;
+mod block_timestamps;
+mod cement_blocks;
+mod chain_section_blocks;
+mod checksum;
+mod confirmed_fork;
+mod destination_index;
 mod empty_ledger;
+mod epoch_upgrade;
+mod fuzz;
+mod integrity;
+mod ledger_overlay;
 mod pruning;
+mod receivable_cache;
+mod recently_modified_accounts;
+mod rep_weight_events;
+mod rollback_journal;
 mod rollback_legacy_change;
 mod rollback_legacy_receive;
 mod rollback_legacy_send;
+mod rollback_planned;
 mod rollback_state;
+mod spawn_read;
+mod stats_report;
+mod subtype_check;
 
 #[test]
 fn ledger_successor() {
@@ -230,6 +253,207 @@ fn bootstrap_rep_weight() {
     assert_eq!(ctx.ledger.weight(&representative_account), Amount::zero());
 }
 
+#[test]
+fn export_bootstrap_weights() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let representative = Account::from(1000);
+    {
+        let mut txn = ctx.ledger.rw_txn();
+        let mut change = genesis
+            .legacy_change(&txn)
+            .representative(representative)
+            .build();
+        ctx.ledger.process(&mut txn, &mut change).unwrap();
+    }
+
+    let weights = ctx.ledger.export_bootstrap_weights(Amount::raw(1));
+
+    assert_eq!(weights.max_blocks, 2);
+    assert_eq!(weights.weights.len(), 1);
+    assert_eq!(
+        weights.weights.get(&representative),
+        Some(&LEDGER_CONSTANTS_STUB.genesis_amount)
+    );
+}
+
+#[test]
+fn export_bootstrap_weights_filters_by_min_weight() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let receiver = ctx.block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis
+        .legacy_send(&txn)
+        .destination(receiver.account())
+        .amount(Amount::raw(100))
+        .build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = receiver.legacy_open(send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+
+    let weights = ctx.ledger.export_bootstrap_weights(Amount::raw(101));
+
+    assert_eq!(weights.weights.len(), 1);
+    assert!(weights.weights.contains_key(&DEV_GENESIS_ACCOUNT));
+}
+
+#[test]
+fn load_bootstrap_weights() {
+    let ctx = LedgerContext::empty();
+    let representative = Account::from(1000);
+    let mut weights = HashMap::new();
+    weights.insert(representative, Amount::raw(1000));
+
+    ctx.ledger
+        .load_bootstrap_weights(BootstrapWeights::new(3, weights));
+
+    assert_eq!(ctx.ledger.bootstrap_weight_max_blocks(), 3);
+    assert_eq!(ctx.ledger.weight(&representative), Amount::raw(1000));
+}
+
+#[test]
+fn bootstrap_weight_falls_back_to_ledger_for_unknown_reps() {
+    let ctx = LedgerContext::empty();
+    let mut weights = HashMap::new();
+    weights.insert(Account::from(1000), Amount::raw(1000));
+    ctx.ledger
+        .load_bootstrap_weights(BootstrapWeights::new(3, weights));
+
+    assert!(!ctx.ledger.bootstrap_weight_reached());
+    assert_eq!(
+        ctx.ledger.weight(&DEV_GENESIS_ACCOUNT),
+        LEDGER_CONSTANTS_STUB.genesis_amount
+    );
+}
+
+#[test]
+fn bootstrap_weights_are_not_used_again_after_threshold_was_reached() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let representative = Account::from(1000);
+    let mut weights = HashMap::new();
+    weights.insert(representative, Amount::raw(1000));
+    ctx.ledger
+        .load_bootstrap_weights(BootstrapWeights::new(2, weights));
+    assert_eq!(ctx.ledger.weight(&representative), Amount::raw(1000));
+
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis
+        .legacy_send(&txn)
+        .destination(representative)
+        .amount(Amount::raw(50))
+        .build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    assert!(ctx.ledger.bootstrap_weight_reached());
+    assert_eq!(ctx.ledger.weight(&representative), Amount::zero());
+
+    ctx.ledger.rollback(&mut txn, &send.hash()).unwrap();
+    assert!(!ctx.ledger.bootstrap_weight_reached());
+    assert_eq!(ctx.ledger.weight(&representative), Amount::zero());
+}
+
+#[test]
+fn loading_bootstrap_weights_enables_them_again() {
+    let ctx = LedgerContext::empty();
+    let representative = Account::from(1000);
+    let mut weights = HashMap::new();
+    weights.insert(representative, Amount::raw(1000));
+    ctx.ledger
+        .load_bootstrap_weights(BootstrapWeights::new(1, weights.clone()));
+    assert_eq!(ctx.ledger.weight(&representative), Amount::zero());
+
+    ctx.ledger
+        .load_bootstrap_weights(BootstrapWeights::new(2, weights));
+
+    assert_eq!(ctx.ledger.weight(&representative), Amount::raw(1000));
+}
+
+#[test]
+fn weight_exact_ignores_unconfirmed_blocks() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let representative = Account::from(1000);
+    let mut txn = ctx.ledger.rw_txn();
+    let mut change = genesis
+        .legacy_change(&txn)
+        .representative(representative)
+        .build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    assert_eq!(
+        ctx.ledger.weight(&representative),
+        LEDGER_CONSTANTS_STUB.genesis_amount
+    );
+    assert_eq!(
+        ctx.ledger.weight_exact(&txn, &representative),
+        Amount::zero()
+    );
+    assert_eq!(
+        ctx.ledger.weight_exact(&txn, &DEV_GENESIS_ACCOUNT),
+        LEDGER_CONSTANTS_STUB.genesis_amount
+    );
+
+    ctx.ledger.store.confirmation_height.put(
+        &mut txn,
+        &DEV_GENESIS_ACCOUNT,
+        &ConfirmationHeightInfo::new(2, change.hash()),
+    );
+
+    assert_eq!(
+        ctx.ledger.weight_exact(&txn, &representative),
+        LEDGER_CONSTANTS_STUB.genesis_amount
+    );
+    assert_eq!(
+        ctx.ledger.weight_exact(&txn, &DEV_GENESIS_ACCOUNT),
+        Amount::zero()
+    );
+}
+
+#[test]
+fn weight_exact_uses_balance_of_confirmed_frontier() {
+    let ctx = LedgerContext::empty();
+    let genesis = ctx.genesis_block_factory();
+    let mut txn = ctx.ledger.rw_txn();
+    let mut send = genesis
+        .legacy_send(&txn)
+        .destination(Account::from(1000))
+        .amount(Amount::raw(50))
+        .build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.store.confirmation_height.put(
+        &mut txn,
+        &DEV_GENESIS_ACCOUNT,
+        &ConfirmationHeightInfo::new(2, send.hash()),
+    );
+    let mut change = genesis
+        .legacy_change(&txn)
+        .representative(Account::from(1000))
+        .build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    assert_eq!(
+        ctx.ledger.weight_exact(&txn, &DEV_GENESIS_ACCOUNT),
+        LEDGER_CONSTANTS_STUB.genesis_amount - Amount::raw(50)
+    );
+}
+
+#[test]
+fn weight_exact_ignores_bootstrap_weights() {
+    let ctx = LedgerContext::empty();
+    let mut weights = HashMap::new();
+    weights.insert(*DEV_GENESIS_ACCOUNT, Amount::raw(1000));
+    ctx.ledger
+        .load_bootstrap_weights(BootstrapWeights::new(3, weights));
+    let txn = ctx.ledger.read_txn();
+
+    assert_eq!(ctx.ledger.weight(&DEV_GENESIS_ACCOUNT), Amount::raw(1000));
+    assert_eq!(
+        ctx.ledger.weight_exact(&txn, &DEV_GENESIS_ACCOUNT),
+        LEDGER_CONSTANTS_STUB.genesis_amount
+    );
+}
+
 #[test]
 fn block_destination_source() {
     let ctx = LedgerContext::empty();
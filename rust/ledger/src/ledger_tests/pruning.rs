@@ -446,3 +446,35 @@ fn hash_root_random() {
         assert!(iteration < 1000);
     }
 }
+
+#[test]
+fn pruning_safe_to_vote() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_pruning();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    assert!(ctx.ledger.pruning_safe_to_vote(&txn, &send2.root()));
+
+    ctx.ledger.pruning_action(&mut txn, &send1.hash(), 1);
+
+    // The root block was pruned
+    assert!(!ctx.ledger.pruning_safe_to_vote(&txn, &send2.root()));
+    // The block which follows the root was pruned
+    assert!(!ctx.ledger.pruning_safe_to_vote(&txn, &send1.root()));
+    // Nothing was pruned at the frontier
+    assert!(ctx.ledger.pruning_safe_to_vote(&txn, &send2.hash().into()));
+}
+
+#[test]
+fn always_safe_to_vote_without_pruning() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    assert!(ctx
+        .ledger
+        .pruning_safe_to_vote(&txn, &(*DEV_GENESIS_HASH).into()));
+}
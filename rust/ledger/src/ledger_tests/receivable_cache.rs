@@ -0,0 +1,85 @@
+use super::LedgerContext;
+use crate::{
+    ledger_constants::LEDGER_CONSTANTS_STUB, ledger_tests::AccountBlockFactory, GenerateCache,
+    Ledger, ReceivableSummary,
+};
+use rsnano_core::Amount;
+
+#[test]
+fn update_on_send_receive_and_rollback() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.cache.receivable.enable();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+
+    let mut send = genesis
+        .send(&txn)
+        .link(destination.account())
+        .amount_sent(100)
+        .build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let expected = ReceivableSummary {
+        count: 1,
+        total: Amount::raw(100),
+    };
+    assert_eq!(
+        ctx.ledger.cache.receivable.get(&destination.account()),
+        Some(expected)
+    );
+    assert_eq!(
+        ctx.ledger
+            .account_receivable(&txn, &destination.account(), false),
+        Amount::raw(100)
+    );
+
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    assert_eq!(
+        ctx.ledger.cache.receivable.get(&destination.account()),
+        Some(ReceivableSummary::default())
+    );
+
+    ctx.ledger.rollback(&mut txn, &open.hash()).unwrap();
+    assert_eq!(
+        ctx.ledger.cache.receivable.get(&destination.account()),
+        Some(expected)
+    );
+
+    ctx.ledger.rollback(&mut txn, &send.hash()).unwrap();
+    assert!(ctx.ledger.cache.receivable.is_empty());
+}
+
+#[test]
+fn generate_on_startup() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+    for _ in 0..2 {
+        let mut send = genesis
+            .send(&txn)
+            .link(destination.account())
+            .amount_sent(10)
+            .build();
+        ctx.ledger.process(&mut txn, &mut send).unwrap();
+    }
+    txn.commit();
+
+    let mut generate_cache = GenerateCache::new();
+    generate_cache.receivable = true;
+    let ledger = Ledger::with_cache(
+        ctx.ledger.store.clone(),
+        LEDGER_CONSTANTS_STUB.clone(),
+        &generate_cache,
+    )
+    .unwrap();
+
+    assert_eq!(
+        ledger.cache.receivable.get(&destination.account()),
+        Some(ReceivableSummary {
+            count: 2,
+            total: Amount::raw(20)
+        })
+    );
+}
@@ -0,0 +1,63 @@
+use super::LedgerContext;
+use crate::{
+    ledger_constants::LEDGER_CONSTANTS_STUB, ledger_tests::AccountBlockFactory, GenerateCache,
+    Ledger,
+};
+
+#[test]
+fn update_on_process_and_rollback() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+
+    let accounts: Vec<_> = ctx
+        .ledger
+        .recently_modified_accounts(0, 10)
+        .into_iter()
+        .map(|(account, _)| account)
+        .collect();
+    assert_eq!(accounts.len(), 2);
+    assert!(accounts.contains(&genesis.account()));
+    assert!(accounts.contains(&destination.account()));
+
+    ctx.ledger.rollback(&mut txn, &open.hash()).unwrap();
+    let accounts = ctx.ledger.recently_modified_accounts(0, 10);
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].0, genesis.account());
+    assert_eq!(
+        ctx.ledger.recently_modified_accounts(accounts[0].1 + 1, 10),
+        Vec::new()
+    );
+}
+
+#[test]
+fn generate_on_startup() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    txn.commit();
+
+    let ledger = Ledger::with_cache(
+        ctx.ledger.store.clone(),
+        LEDGER_CONSTANTS_STUB.clone(),
+        &GenerateCache::new(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ledger.recently_modified_accounts(0, 10).len(),
+        ctx.ledger.recently_modified_accounts(0, 10).len()
+    );
+    assert_eq!(ledger.recently_modified_accounts(0, 1).len(), 1);
+}
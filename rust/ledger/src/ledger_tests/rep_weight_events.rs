@@ -0,0 +1,100 @@
+use std::sync::{Arc, Mutex};
+
+use super::LedgerContext;
+use crate::{LedgerObserver, RepWeightThresholdCrossed, DEV_GENESIS_ACCOUNT};
+use rsnano_core::{Account, Amount};
+
+#[derive(Default)]
+struct RepEventRecorder {
+    representative_changes: Mutex<Vec<(Account, Account, Account)>>,
+    thresholds_crossed: Mutex<Vec<RepWeightThresholdCrossed>>,
+}
+
+impl LedgerObserver for RepEventRecorder {
+    fn representative_changed(
+        &self,
+        account: &Account,
+        old_representative: &Account,
+        new_representative: &Account,
+    ) {
+        self.representative_changes.lock().unwrap().push((
+            *account,
+            *old_representative,
+            *new_representative,
+        ));
+    }
+
+    fn rep_weight_threshold_crossed(&self, event: &RepWeightThresholdCrossed) {
+        self.thresholds_crossed.lock().unwrap().push(event.clone());
+    }
+}
+
+fn context_with_recorder() -> (LedgerContext, Arc<RepEventRecorder>) {
+    let mut ctx = LedgerContext::empty();
+    let recorder = Arc::new(RepEventRecorder::default());
+    ctx.ledger.set_observer(recorder.clone());
+    (ctx, recorder)
+}
+
+#[test]
+fn notify_representative_change() {
+    let (ctx, recorder) = context_with_recorder();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut change = genesis.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    assert_eq!(
+        *recorder.representative_changes.lock().unwrap(),
+        vec![(*DEV_GENESIS_ACCOUNT, *DEV_GENESIS_ACCOUNT, Account::from(1))]
+    );
+}
+
+#[test]
+fn notify_representative_change_on_rollback() {
+    let (ctx, recorder) = context_with_recorder();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let mut change = genesis.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    ctx.ledger.rollback(&mut txn, &change.hash()).unwrap();
+
+    assert_eq!(
+        recorder.representative_changes.lock().unwrap().last(),
+        Some(&(*DEV_GENESIS_ACCOUNT, Account::from(1), *DEV_GENESIS_ACCOUNT))
+    );
+}
+
+#[test]
+fn send_does_not_change_representative() {
+    let (ctx, recorder) = context_with_recorder();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    assert!(recorder.representative_changes.lock().unwrap().is_empty());
+}
+
+#[test]
+fn notify_crossed_threshold_when_weight_moves_to_other_rep() {
+    let (ctx, recorder) = context_with_recorder();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let threshold = Amount::raw(1000);
+    ctx.ledger.cache.rep_weights.set_thresholds(vec![threshold]);
+
+    let mut change = genesis.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    let crossed = recorder.thresholds_crossed.lock().unwrap();
+    assert_eq!(crossed.len(), 2);
+    assert_eq!(crossed[0].representative, *DEV_GENESIS_ACCOUNT);
+    assert!(!crossed[0].above);
+    assert_eq!(crossed[1].representative, Account::from(1));
+    assert!(crossed[1].above);
+    assert_eq!(crossed[1].threshold, threshold);
+}
@@ -0,0 +1,128 @@
+use super::LedgerContext;
+use crate::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsnano_store_lmdb::{PlannedRollback, Transaction};
+
+#[test]
+fn journal_is_empty_after_journaled_rollback() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+
+    let rolled_back = ctx
+        .ledger
+        .rollback_journaled(&mut txn, &send1.hash())
+        .unwrap();
+
+    assert_eq!(rolled_back.len(), 2);
+    assert!(!ctx.ledger.store.block.exists(&txn, &send1.hash()));
+    assert!(!ctx.ledger.store.block.exists(&txn, &send2.hash()));
+    assert_eq!(ctx.ledger.store.rollback_journal.count(&txn), 0);
+}
+
+#[test]
+fn journaled_rollback_commits_the_transaction() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger
+        .rollback_journaled(&mut txn, &send.hash())
+        .unwrap();
+
+    let read_txn = ctx.ledger.read_txn();
+    assert_eq!(
+        ctx.ledger.latest(&read_txn, &DEV_GENESIS_ACCOUNT),
+        Some(*DEV_GENESIS_HASH)
+    );
+}
+
+#[test]
+fn rollback_stays_within_the_callers_transaction() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.set_max_rollback_depth(10);
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    txn.refresh();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+
+    let rolled_back = ctx.ledger.rollback(&mut txn, &send1.hash()).unwrap();
+
+    assert_eq!(rolled_back.len(), 2);
+    assert!(!ctx.ledger.store.block.exists(&txn, &send1.hash()));
+    assert_eq!(ctx.ledger.store.rollback_journal.count(&txn), 0);
+    // Nothing was committed, so other transactions still see the ledger before the rollback
+    let read_txn = ctx.ledger.read_txn();
+    assert!(ctx.ledger.store.block.exists(&read_txn, &send1.hash()));
+    assert!(!ctx.ledger.store.block.exists(&read_txn, &send2.hash()));
+}
+
+#[test]
+fn complete_interrupted_rollback() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    let mut send3 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send3).unwrap();
+
+    // The rollback of send3 was done before the crash
+    ctx.ledger.rollback(&mut txn, &send3.hash()).unwrap();
+    ctx.ledger.store.rollback_journal.put(
+        &mut txn,
+        &send1.hash(),
+        &PlannedRollback(vec![send3.hash(), send2.hash(), send1.hash()]),
+    );
+    txn.refresh();
+
+    let completed = ctx.ledger.recover_rollback_journal(&mut txn);
+
+    assert_eq!(completed, 1);
+    assert!(!ctx.ledger.store.block.exists(&txn, &send1.hash()));
+    assert!(!ctx.ledger.store.block.exists(&txn, &send2.hash()));
+    assert_eq!(
+        ctx.ledger
+            .account_info(&txn, &DEV_GENESIS_ACCOUNT)
+            .unwrap()
+            .head,
+        *DEV_GENESIS_HASH
+    );
+    assert_eq!(ctx.ledger.store.rollback_journal.count(&txn), 0);
+}
+
+#[test]
+fn abort_interrupted_rollback_which_cannot_be_completed() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.inc_confirmation_height(&mut txn, &DEV_GENESIS_ACCOUNT);
+    ctx.inc_confirmation_height(&mut txn, &DEV_GENESIS_ACCOUNT);
+    ctx.ledger.store.rollback_journal.put(
+        &mut txn,
+        &send.hash(),
+        &PlannedRollback(vec![send.hash()]),
+    );
+
+    let completed = ctx.ledger.recover_rollback_journal(&mut txn);
+
+    assert_eq!(completed, 0);
+    assert!(ctx.ledger.store.block.exists(&txn, &send.hash()));
+    assert_eq!(ctx.ledger.store.rollback_journal.count(&txn), 0);
+}
@@ -0,0 +1,95 @@
+use super::LedgerContext;
+use crate::ledger_tests::AccountBlockFactory;
+
+#[test]
+fn plan_rollback_of_received_send_without_changes() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+
+    let planned = ctx
+        .ledger
+        .rollback_planned(&txn, &send.hash(), usize::MAX)
+        .unwrap();
+
+    assert_eq!(planned, vec![open.hash(), send.hash()]);
+    assert!(ctx.ledger.store.block.exists(&txn, &send.hash()));
+    assert!(ctx.ledger.store.block.exists(&txn, &open.hash()));
+
+    let rolled_back: Vec<_> = ctx
+        .ledger
+        .rollback(&mut txn, &send.hash())
+        .unwrap()
+        .iter()
+        .map(|b| b.hash())
+        .collect();
+    assert_eq!(rolled_back, planned);
+}
+
+#[test]
+fn plan_rollback_of_successors() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+
+    let planned = ctx
+        .ledger
+        .rollback_planned(&txn, &send1.hash(), usize::MAX)
+        .unwrap();
+
+    assert_eq!(planned, vec![send2.hash(), send1.hash()]);
+}
+
+#[test]
+fn plan_fails_when_max_depth_is_exceeded() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+
+    assert!(ctx.ledger.rollback_planned(&txn, &send1.hash(), 1).is_err());
+}
+
+#[test]
+fn dont_roll_back_anything_when_max_depth_is_exceeded() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    ctx.ledger.set_max_rollback_depth(1);
+
+    assert!(ctx.ledger.rollback(&mut txn, &send1.hash()).is_err());
+    assert!(ctx.ledger.store.block.exists(&txn, &send1.hash()));
+    assert!(ctx.ledger.store.block.exists(&txn, &send2.hash()));
+}
+
+#[test]
+fn plan_fails_for_confirmed_block() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis_hash = *crate::DEV_GENESIS_HASH;
+
+    assert!(ctx
+        .ledger
+        .rollback_planned(&txn, &genesis_hash, usize::MAX)
+        .is_err());
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{Ledger, DEV_GENESIS_ACCOUNT};
+use rsnano_core::TestAccountChain;
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn read_with_pooled_transaction() {
+    let genesis = TestAccountChain::genesis();
+    let ledger = Arc::new(
+        Ledger::create_null_with()
+            .blocks(genesis.blocks())
+            .account_info(&genesis.account(), &genesis.account_info())
+            .build(),
+    );
+
+    let frontier = genesis.frontier();
+    let block = block_on(ledger.spawn_read(move |ledger, txn| ledger.get_block(txn, &frontier)));
+
+    assert_eq!(block, Some(genesis.latest_block().clone()));
+}
+
+#[test]
+fn many_concurrent_reads() {
+    let ledger = Arc::new(Ledger::create_null());
+    let reads: Vec<_> = (0..20)
+        .map(|_| ledger.spawn_read(|ledger, txn| ledger.account_info(txn, &DEV_GENESIS_ACCOUNT)))
+        .collect();
+
+    for read in reads {
+        assert!(block_on(read).is_none());
+    }
+}
+
+#[test]
+#[should_panic(expected = "ledger read panicked")]
+fn report_panicking_read() {
+    let ledger = Arc::new(Ledger::create_null());
+    block_on(ledger.spawn_read(|_, _| -> u64 { panic!("read failed") }));
+}
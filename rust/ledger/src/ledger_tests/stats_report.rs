@@ -0,0 +1,31 @@
+use super::LedgerContext;
+
+#[test]
+fn empty_ledger() {
+    let ctx = LedgerContext::empty();
+
+    let report = ctx.ledger.stats_report();
+
+    assert_eq!(report.block_count, 1);
+    assert_eq!(report.cemented_count, 1);
+    assert_eq!(report.account_count, 1);
+    assert_eq!(report.pruned_count, 0);
+    assert!(report.version.is_some());
+    assert!(report.map_used > 0);
+    assert!(report.map_used <= report.map_size);
+}
+
+#[test]
+fn tables_are_sorted_by_size() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    txn.commit();
+
+    let report = ctx.ledger.stats_report();
+
+    assert_eq!(report.tables[0], ("blocks", 2));
+    assert!(report.tables.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+}
@@ -0,0 +1,176 @@
+use super::LedgerContext;
+use crate::{ledger_tests::AccountBlockFactory, SubtypeMismatch};
+use rsnano_core::{BlockEnum, BlockHash, BlockSubType, StateBlock};
+
+fn state(block: &BlockEnum) -> &StateBlock {
+    match block {
+        BlockEnum::State(state) => state,
+        _ => panic!("not a state block"),
+    }
+}
+
+#[test]
+fn derive_subtypes() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    assert_eq!(
+        ctx.ledger.state_block_subtype(&txn, state(&send)),
+        Some(BlockSubType::Send)
+    );
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    let mut open = destination.open(&txn, send.hash()).build();
+    assert_eq!(
+        ctx.ledger.state_block_subtype(&txn, state(&open)),
+        Some(BlockSubType::Open)
+    );
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+
+    let change = genesis.change(&txn).build();
+    assert_eq!(
+        ctx.ledger.state_block_subtype(&txn, state(&change)),
+        Some(BlockSubType::Change)
+    );
+
+    let epoch = genesis.epoch_v1(&txn).build();
+    assert_eq!(
+        ctx.ledger.state_block_subtype(&txn, state(&epoch)),
+        Some(BlockSubType::Epoch)
+    );
+}
+
+#[test]
+fn receive_subtype() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let mut send = genesis.send(&txn).link(genesis.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+
+    let receive = genesis.receive(&txn, send.hash()).build();
+    assert_eq!(
+        ctx.ledger.state_block_subtype(&txn, state(&receive)),
+        Some(BlockSubType::Receive)
+    );
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&receive), BlockSubType::Receive),
+        Ok(())
+    );
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&receive), BlockSubType::Send),
+        Err(SubtypeMismatch::Balance)
+    );
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&receive), BlockSubType::Change),
+        Err(SubtypeMismatch::Balance)
+    );
+}
+
+#[test]
+fn check_send() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let send = ctx.genesis_block_factory().send(&txn).build();
+    let send = state(&send);
+
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, send, BlockSubType::Send),
+        Ok(())
+    );
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, send, BlockSubType::Receive),
+        Err(SubtypeMismatch::Balance)
+    );
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, send, BlockSubType::Open),
+        Err(SubtypeMismatch::Previous)
+    );
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, send, BlockSubType::Epoch),
+        Err(SubtypeMismatch::Balance)
+    );
+}
+
+#[test]
+fn check_open() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+    let mut send = ctx
+        .genesis_block_factory()
+        .send(&txn)
+        .link(destination.account())
+        .build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let open = destination.open(&txn, send.hash()).build();
+    let open = state(&open);
+
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, open, BlockSubType::Open),
+        Ok(())
+    );
+    // A receive may open the account
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, open, BlockSubType::Receive),
+        Ok(())
+    );
+    assert_eq!(
+        ctx.ledger.check_subtype(&txn, open, BlockSubType::Change),
+        Err(SubtypeMismatch::Balance)
+    );
+}
+
+#[test]
+fn check_change_and_epoch() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let genesis = ctx.genesis_block_factory();
+    let change = genesis.change(&txn).build();
+    let epoch = genesis.epoch_v1(&txn).build();
+
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&change), BlockSubType::Change),
+        Ok(())
+    );
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&change), BlockSubType::Epoch),
+        Err(SubtypeMismatch::EpochLink)
+    );
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&change), BlockSubType::Receive),
+        Err(SubtypeMismatch::Balance)
+    );
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&epoch), BlockSubType::Epoch),
+        Ok(())
+    );
+}
+
+#[test]
+fn unknown_previous() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+    let send = ctx
+        .genesis_block_factory()
+        .send(&txn)
+        .previous(BlockHash::from(42))
+        .build();
+
+    assert_eq!(ctx.ledger.state_block_subtype(&txn, state(&send)), None);
+    assert_eq!(
+        ctx.ledger
+            .check_subtype(&txn, state(&send), BlockSubType::Send),
+        Err(SubtypeMismatch::GapPrevious)
+    );
+}
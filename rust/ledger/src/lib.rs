@@ -4,26 +4,54 @@ extern crate anyhow;
 #[macro_use]
 extern crate num_derive;
 
+mod account_details;
+mod account_history;
+mod block_info;
 mod block_insertion;
 mod block_rollback;
+mod bootstrap_weights;
 mod dependent_blocks_finder;
+mod epoch_progress_cache;
+mod epoch_upgrader;
 mod generate_cache;
+mod integrity_problem;
 mod ledger;
 mod ledger_cache;
+mod ledger_checksum;
 mod ledger_constants;
+mod ledger_overlay;
+mod ledger_stats_report;
+mod read_pool;
+mod receivable_cache;
+mod recently_modified_accounts;
 mod rep_weights;
 mod representative_block_finder;
+mod subtype_check;
 mod write_database_queue;
 
 #[cfg(test)]
 mod ledger_tests;
 
-pub(crate) use block_rollback::BlockRollbackPerformer;
+pub use account_details::{AccountDetails, ReceivableDetails};
+pub use account_history::AccountHistoryEntry;
+pub use block_info::BlockInfo;
+pub(crate) use block_rollback::{BlockRollbackPerformer, RollbackSimulator};
+pub use bootstrap_weights::BootstrapWeights;
 pub(crate) use dependent_blocks_finder::DependentBlocksFinder;
+pub use epoch_progress_cache::EpochProgressCache;
+pub use epoch_upgrader::{EpochUpgradeProgress, EpochUpgrader};
 pub use generate_cache::GenerateCache;
+pub use integrity_problem::IntegrityProblem;
 pub use ledger::{Ledger, LedgerObserver, ProcessResult, UncementedInfo};
 pub use ledger_cache::LedgerCache;
+pub use ledger_checksum::{account_checksum, LedgerChecksumCache};
 pub use ledger_constants::{LedgerConstants, DEV_GENESIS, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
-pub use rep_weights::RepWeights;
+pub use ledger_overlay::LedgerOverlay;
+pub use ledger_stats_report::LedgerStatsReport;
+pub(crate) use read_pool::LedgerReadPool;
+pub use receivable_cache::{ReceivableCache, ReceivableSummary};
+pub use recently_modified_accounts::{RecentlyModifiedAccounts, RECENTLY_MODIFIED_CAPACITY};
+pub use rep_weights::{RepWeightThresholdCrossed, RepWeights};
 pub(crate) use representative_block_finder::RepresentativeBlockFinder;
+pub use subtype_check::SubtypeMismatch;
 pub use write_database_queue::{WriteDatabaseQueue, WriteGuard, Writer};
@@ -0,0 +1,59 @@
+use rsnano_store_lmdb::{Environment, LmdbReadTransaction, LmdbStore};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+type ReadJob<T> = Box<dyn FnOnce(&LmdbReadTransaction<T>) + Send>;
+
+/// Threads which execute read-only ledger queries outside of the async runtime.
+/// Every thread keeps one read transaction, which is renewed for each job and reset
+/// afterwards, so that an idle thread doesn't pin old database pages.
+pub(crate) struct LedgerReadPool<T: Environment + 'static> {
+    sender: mpsc::Sender<ReadJob<T>>,
+}
+
+impl<T: Environment + 'static> LedgerReadPool<T> {
+    pub(crate) const DEFAULT_THREADS: usize = 4;
+
+    pub(crate) fn new(store: Arc<LmdbStore<T>>, thread_count: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<ReadJob<T>>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..thread_count.max(1) {
+            let store = Arc::clone(&store);
+            let receiver = Arc::clone(&receiver);
+            // The threads are detached. They stop as soon as the pool is dropped and
+            // the queue is empty, which may happen on one of the pool threads itself.
+            thread::Builder::new()
+                .name("Ledger read".to_string())
+                .spawn(move || run(&store, &receiver))
+                .unwrap();
+        }
+        Self { sender }
+    }
+
+    pub(crate) fn execute(&self, job: ReadJob<T>) {
+        self.sender
+            .send(job)
+            .expect("ledger read threads have stopped");
+    }
+}
+
+fn run<T: Environment + 'static>(
+    store: &LmdbStore<T>,
+    receiver: &Mutex<mpsc::Receiver<ReadJob<T>>>,
+) {
+    let mut txn = store.tx_begin_read();
+    txn.reset();
+    loop {
+        let job = match receiver.lock().unwrap().recv() {
+            Ok(job) => job,
+            Err(_) => break,
+        };
+        txn.renew();
+        // A panicking job drops its result sender, which is reported by the waiting future
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| job(&txn)));
+        txn.reset();
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use rsnano_core::{Account, Amount};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ReceivableSummary {
+    pub count: u64,
+    pub total: Amount,
+}
+
+/// Number and sum of the receivable amounts per account, so that the receivable
+/// balance can be reported without scanning the pending table.
+/// The cache is only maintained if it was generated on startup.
+pub struct ReceivableCache {
+    enabled: AtomicBool,
+    summaries: Mutex<HashMap<Account, ReceivableSummary>>,
+}
+
+impl ReceivableCache {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            summaries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn add(&self, account: &Account, amount: Amount) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut summaries = self.summaries.lock().unwrap();
+        let summary = summaries.entry(*account).or_default();
+        summary.count += 1;
+        summary.total = summary.total.wrapping_add(amount);
+    }
+
+    pub fn remove(&self, account: &Account, amount: Amount) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut summaries = self.summaries.lock().unwrap();
+        if let Some(summary) = summaries.get_mut(account) {
+            summary.count = summary.count.saturating_sub(1);
+            summary.total = summary.total.wrapping_sub(amount);
+            if summary.count == 0 {
+                summaries.remove(account);
+            }
+        }
+    }
+
+    /// Returns `None` if the cache isn't enabled
+    pub fn get(&self, account: &Account) -> Option<ReceivableSummary> {
+        if !self.is_enabled() {
+            return None;
+        }
+        Some(
+            self.summaries
+                .lock()
+                .unwrap()
+                .get(account)
+                .cloned()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Number of accounts with receivable amounts
+    pub fn len(&self) -> usize {
+        self.summaries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn merge(&self, other: HashMap<Account, ReceivableSummary>) {
+        let mut summaries = self.summaries.lock().unwrap();
+        for (account, other) in other {
+            let summary = summaries.entry(account).or_default();
+            summary.count += other.count;
+            summary.total = summary.total.wrapping_add(other.total);
+        }
+    }
+}
+
+impl Default for ReceivableCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_by_default() {
+        let cache = ReceivableCache::new();
+        cache.add(&Account::from(1), Amount::raw(10));
+        assert_eq!(cache.get(&Account::from(1)), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let cache = ReceivableCache::new();
+        cache.enable();
+        let account = Account::from(1);
+        cache.add(&account, Amount::raw(10));
+        cache.add(&account, Amount::raw(5));
+        assert_eq!(
+            cache.get(&account),
+            Some(ReceivableSummary {
+                count: 2,
+                total: Amount::raw(15)
+            })
+        );
+
+        cache.remove(&account, Amount::raw(10));
+        cache.remove(&account, Amount::raw(5));
+        assert_eq!(cache.get(&account), Some(ReceivableSummary::default()));
+        assert!(cache.is_empty());
+    }
+}
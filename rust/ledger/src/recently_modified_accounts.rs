@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+use rsnano_core::Account;
+
+/// Maximum number of accounts which are kept in the index by default
+pub const RECENTLY_MODIFIED_CAPACITY: usize = 64 * 1024;
+
+/// The accounts with the most recent `AccountInfo::modified` timestamps, ordered by that
+/// timestamp. Only the latest `capacity` accounts are kept, older ones are evicted.
+pub struct RecentlyModifiedAccounts {
+    capacity: usize,
+    data: Mutex<RecentlyModifiedData>,
+}
+
+#[derive(Default)]
+struct RecentlyModifiedData {
+    by_account: HashMap<Account, u64>,
+    by_modified: BTreeSet<(u64, Account)>,
+}
+
+impl RecentlyModifiedData {
+    fn insert(&mut self, account: Account, modified: u64, capacity: usize) {
+        if let Some(old) = self.by_account.insert(account, modified) {
+            self.by_modified.remove(&(old, account));
+        }
+        self.by_modified.insert((modified, account));
+        while self.by_modified.len() > capacity {
+            let (_, oldest) = self.by_modified.pop_first().unwrap();
+            self.by_account.remove(&oldest);
+        }
+    }
+}
+
+impl RecentlyModifiedAccounts {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            data: Mutex::new(RecentlyModifiedData::default()),
+        }
+    }
+
+    pub fn update(&self, account: &Account, modified: u64) {
+        self.data
+            .lock()
+            .unwrap()
+            .insert(*account, modified, self.capacity);
+    }
+
+    pub fn remove(&self, account: &Account) {
+        let mut data = self.data.lock().unwrap();
+        if let Some(modified) = data.by_account.remove(account) {
+            data.by_modified.remove(&(modified, *account));
+        }
+    }
+
+    /// Up to `limit` accounts which were modified at or after `since` (seconds since epoch),
+    /// most recently modified first
+    pub fn get(&self, since: u64, limit: usize) -> Vec<(Account, u64)> {
+        let data = self.data.lock().unwrap();
+        data.by_modified
+            .range((since, Account::zero())..)
+            .rev()
+            .take(limit)
+            .map(|(modified, account)| (*account, *modified))
+            .collect()
+    }
+
+    /// Number of indexed accounts which were modified at or after `since`
+    pub fn count_since(&self, since: u64) -> usize {
+        let data = self.data.lock().unwrap();
+        data.by_modified.range((since, Account::zero())..).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.lock().unwrap().by_account.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn merge(&self, entries: impl IntoIterator<Item = (Account, u64)>) {
+        let mut data = self.data.lock().unwrap();
+        for (account, modified) in entries {
+            data.insert(account, modified, self.capacity);
+        }
+    }
+}
+
+impl Default for RecentlyModifiedAccounts {
+    fn default() -> Self {
+        Self::new(RECENTLY_MODIFIED_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let index = RecentlyModifiedAccounts::new(10);
+        assert!(index.is_empty());
+        assert_eq!(index.get(0, 10), Vec::new());
+    }
+
+    #[test]
+    fn most_recent_first() {
+        let index = RecentlyModifiedAccounts::new(10);
+        index.update(&Account::from(1), 100);
+        index.update(&Account::from(2), 300);
+        index.update(&Account::from(3), 200);
+
+        assert_eq!(
+            index.get(0, 10),
+            vec![
+                (Account::from(2), 300),
+                (Account::from(3), 200),
+                (Account::from(1), 100)
+            ]
+        );
+        assert_eq!(index.get(0, 1), vec![(Account::from(2), 300)]);
+    }
+
+    #[test]
+    fn filter_by_since() {
+        let index = RecentlyModifiedAccounts::new(10);
+        index.update(&Account::from(1), 100);
+        index.update(&Account::from(2), 200);
+
+        assert_eq!(index.get(200, 10), vec![(Account::from(2), 200)]);
+        assert_eq!(index.get(201, 10), Vec::new());
+        assert_eq!(index.count_since(100), 2);
+        assert_eq!(index.count_since(150), 1);
+    }
+
+    #[test]
+    fn update_replaces_old_timestamp() {
+        let index = RecentlyModifiedAccounts::new(10);
+        index.update(&Account::from(1), 100);
+        index.update(&Account::from(1), 200);
+
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get(0, 10), vec![(Account::from(1), 200)]);
+    }
+
+    #[test]
+    fn evict_oldest_when_full() {
+        let index = RecentlyModifiedAccounts::new(2);
+        index.update(&Account::from(1), 100);
+        index.update(&Account::from(2), 200);
+        index.update(&Account::from(3), 300);
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index.get(0, 10),
+            vec![(Account::from(3), 300), (Account::from(2), 200)]
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let index = RecentlyModifiedAccounts::new(10);
+        index.update(&Account::from(1), 100);
+        index.remove(&Account::from(1));
+        index.remove(&Account::from(2));
+        assert!(index.is_empty());
+        assert_eq!(index.count_since(0), 0);
+    }
+
+    #[test]
+    fn merge_keeps_most_recent() {
+        let index = RecentlyModifiedAccounts::new(2);
+        index.update(&Account::from(1), 500);
+        index.merge(vec![(Account::from(2), 100), (Account::from(3), 300)]);
+
+        assert_eq!(
+            index.get(0, 10),
+            vec![(Account::from(1), 500), (Account::from(3), 300)]
+        );
+    }
+}
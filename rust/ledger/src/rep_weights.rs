@@ -4,14 +4,58 @@ use std::sync::{Mutex, MutexGuard};
 
 use rsnano_core::{Account, Amount};
 
+/// Emitted when the weight of a representative crosses one of the configured thresholds
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepWeightThresholdCrossed {
+    pub representative: Account,
+    pub threshold: Amount,
+    /// The new weight of the representative
+    pub weight: Amount,
+    /// `true` if the weight rose to or above the threshold, `false` if it fell below it
+    pub above: bool,
+}
+
 pub struct RepWeights {
     rep_amounts: Mutex<HashMap<Account, Amount>>,
+    thresholds: Mutex<Vec<Amount>>,
 }
 
 impl RepWeights {
     pub fn new() -> Self {
         RepWeights {
             rep_amounts: Mutex::new(HashMap::new()),
+            thresholds: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the weights for which `RepWeightThresholdCrossed` events are created,
+    /// for example the minimum weight of a principal representative
+    pub fn set_thresholds(&self, thresholds: Vec<Amount>) {
+        *self.thresholds.lock().unwrap() = thresholds;
+    }
+
+    pub fn thresholds(&self) -> Vec<Amount> {
+        self.thresholds.lock().unwrap().clone()
+    }
+
+    fn check_thresholds(
+        &self,
+        representative: Account,
+        old_weight: Amount,
+        new_weight: Amount,
+        crossed: &mut Vec<RepWeightThresholdCrossed>,
+    ) {
+        for threshold in self.thresholds.lock().unwrap().iter() {
+            let was_above = old_weight >= *threshold;
+            let is_above = new_weight >= *threshold;
+            if was_above != is_above {
+                crossed.push(RepWeightThresholdCrossed {
+                    representative,
+                    threshold: *threshold,
+                    weight: new_weight,
+                    above: is_above,
+                });
+            }
         }
     }
 
@@ -41,16 +85,33 @@ impl RepWeights {
         }
     }
 
-    pub fn representation_add(&self, source_rep: Account, amount: Amount) {
+    /// Returns the thresholds which were crossed by this change
+    pub fn representation_add(
+        &self,
+        source_rep: Account,
+        amount: Amount,
+    ) -> Vec<RepWeightThresholdCrossed> {
         let mut guard = self.rep_amounts.lock().unwrap();
         let source_previous = self.get(&guard, &source_rep);
         let new_amount = source_previous.wrapping_add(amount);
-        self.put(&mut guard, source_rep, new_amount)
+        self.put(&mut guard, source_rep, new_amount);
+        let mut crossed = Vec::new();
+        self.check_thresholds(source_rep, source_previous, new_amount, &mut crossed);
+        crossed
     }
 
-    pub fn representation_put(&self, account: Account, representation: Amount) {
+    /// Returns the thresholds which were crossed by this change
+    pub fn representation_put(
+        &self,
+        account: Account,
+        representation: Amount,
+    ) -> Vec<RepWeightThresholdCrossed> {
         let mut guard = self.rep_amounts.lock().unwrap();
+        let previous = self.get(&guard, &account);
         self.put(&mut guard, account, representation);
+        let mut crossed = Vec::new();
+        self.check_thresholds(account, previous, representation, &mut crossed);
+        crossed
     }
 
     pub fn representation_get(&self, account: &Account) -> Amount {
@@ -58,29 +119,28 @@ impl RepWeights {
         self.get(&guard, account)
     }
 
+    /// Returns the thresholds which were crossed by this change
     pub fn representation_add_dual(
         &self,
         source_rep_1: Account,
         amount_1: Amount,
         source_rep_2: Account,
         amount_2: Amount,
-    ) {
+    ) -> Vec<RepWeightThresholdCrossed> {
         if source_rep_1 != source_rep_2 {
             let mut guard = self.rep_amounts.lock().unwrap();
+            let mut crossed = Vec::new();
             let source_previous_1 = self.get(&guard, &source_rep_1);
-            self.put(
-                &mut guard,
-                source_rep_1,
-                source_previous_1.wrapping_add(amount_1),
-            );
+            let new_amount_1 = source_previous_1.wrapping_add(amount_1);
+            self.put(&mut guard, source_rep_1, new_amount_1);
+            self.check_thresholds(source_rep_1, source_previous_1, new_amount_1, &mut crossed);
             let source_previous_2 = self.get(&guard, &source_rep_2);
-            self.put(
-                &mut guard,
-                source_rep_2,
-                source_previous_2.wrapping_add(amount_2),
-            );
+            let new_amount_2 = source_previous_2.wrapping_add(amount_2);
+            self.put(&mut guard, source_rep_2, new_amount_2);
+            self.check_thresholds(source_rep_2, source_previous_2, new_amount_2, &mut crossed);
+            crossed
         } else {
-            self.representation_add(source_rep_1, amount_1.wrapping_add(amount_2));
+            self.representation_add(source_rep_1, amount_1.wrapping_add(amount_2))
         }
     }
 
@@ -109,4 +169,70 @@ mod tests {
         rep_weights.representation_put(account, Amount::from(2));
         assert_eq!(rep_weights.representation_get(&account), Amount::from(2));
     }
+
+    #[test]
+    fn no_threshold_crossed_without_thresholds() {
+        let rep_weights = RepWeights::new();
+        let crossed = rep_weights.representation_add(Account::from(1), Amount::raw(100));
+        assert!(crossed.is_empty());
+    }
+
+    #[test]
+    fn rise_above_threshold() {
+        let rep = Account::from(1);
+        let rep_weights = RepWeights::new();
+        rep_weights.set_thresholds(vec![Amount::raw(10)]);
+
+        assert!(rep_weights
+            .representation_add(rep, Amount::raw(9))
+            .is_empty());
+        let crossed = rep_weights.representation_add(rep, Amount::raw(1));
+
+        assert_eq!(
+            crossed,
+            vec![RepWeightThresholdCrossed {
+                representative: rep,
+                threshold: Amount::raw(10),
+                weight: Amount::raw(10),
+                above: true
+            }]
+        );
+        assert!(rep_weights
+            .representation_add(rep, Amount::raw(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn representative_change_crosses_thresholds_of_both_reps() {
+        let old_rep = Account::from(1);
+        let new_rep = Account::from(2);
+        let rep_weights = RepWeights::new();
+        rep_weights.representation_put(old_rep, Amount::raw(15));
+        rep_weights.set_thresholds(vec![Amount::raw(10)]);
+
+        let crossed = rep_weights.representation_add_dual(
+            old_rep,
+            Amount::zero().wrapping_sub(Amount::raw(10)),
+            new_rep,
+            Amount::raw(10),
+        );
+
+        assert_eq!(
+            crossed,
+            vec![
+                RepWeightThresholdCrossed {
+                    representative: old_rep,
+                    threshold: Amount::raw(10),
+                    weight: Amount::raw(5),
+                    above: false
+                },
+                RepWeightThresholdCrossed {
+                    representative: new_rep,
+                    threshold: Amount::raw(10),
+                    weight: Amount::raw(10),
+                    above: true
+                }
+            ]
+        );
+    }
 }
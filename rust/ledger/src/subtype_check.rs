@@ -0,0 +1,23 @@
+/// Reasons why a state block doesn't fit the subtype a client claimed for it
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SubtypeMismatch {
+    /// The previous block is unknown, so the subtype can't be derived
+    GapPrevious,
+    /// The balance change doesn't fit the subtype
+    Balance,
+    /// The presence or absence of a previous block doesn't fit the subtype
+    Previous,
+    /// The subtype is epoch, but the link is not an epoch link
+    EpochLink,
+}
+
+impl SubtypeMismatch {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubtypeMismatch::GapPrevious => "gap_previous",
+            SubtypeMismatch::Balance => "balance",
+            SubtypeMismatch::Previous => "previous",
+            SubtypeMismatch::EpochLink => "epoch_link",
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use rsnano_core::utils::{ContainerInfo, ContainerInfoComponent};
 use std::{
     collections::VecDeque,
+    mem::size_of,
     sync::{Arc, Condvar, Mutex},
 };
 
@@ -140,6 +142,18 @@ impl WriteDatabaseQueue {
         self.create_write_guard()
     }
 
+    /// Reports the writers which are waiting in the queue for the write lock
+    pub fn collect_container_info(&self, name: String) -> ContainerInfoComponent {
+        ContainerInfoComponent::Composite(
+            name,
+            vec![ContainerInfoComponent::Leaf(ContainerInfo {
+                name: "queue".to_owned(),
+                count: self.data.queue.lock().unwrap().len(),
+                sizeof_element: size_of::<Writer>(),
+            })],
+        )
+    }
+
     fn create_write_guard(&self) -> WriteGuard {
         WriteGuard::new(Arc::clone(&self.guard_finish_callback))
     }
@@ -0,0 +1,137 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use rsnano_core::{work::WorkThresholds, BlockEnum, WorkVersion};
+
+/// Tracks the work difficulty of recently processed blocks, so that wallets can
+/// generate enough work for their blocks to be processed in time.
+/// The multipliers are normalized to the base threshold, so that blocks of all epochs
+/// and subtypes can be compared with each other.
+pub struct ActiveDifficulty {
+    thresholds: WorkThresholds,
+    max_samples: usize,
+    samples: Mutex<VecDeque<f64>>,
+}
+
+impl ActiveDifficulty {
+    pub const DEFAULT_MAX_SAMPLES: usize = 20;
+
+    pub fn new(thresholds: WorkThresholds, max_samples: usize) -> Self {
+        Self {
+            thresholds,
+            max_samples,
+            samples: Mutex::new(VecDeque::with_capacity(max_samples)),
+        }
+    }
+
+    /// Records the difficulty of a block that was added to the ledger.
+    /// Blocks without sideband are ignored, because their threshold is unknown.
+    pub fn sample(&self, block: &BlockEnum) {
+        let Some(sideband) = block.sideband() else {
+            return;
+        };
+        let threshold = self.thresholds.threshold(&sideband.details);
+        let difficulty = self.thresholds.difficulty_block(block);
+        let multiplier = WorkThresholds::multiplier(difficulty, threshold).max(1.0);
+        self.add_multiplier(self.thresholds.normalized_multiplier(multiplier, threshold));
+    }
+
+    fn add_multiplier(&self, multiplier: f64) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() >= self.max_samples {
+            samples.pop_front();
+        }
+        samples.push_back(multiplier);
+    }
+
+    /// The average normalized multiplier of the recent samples. 1.0 if nothing was sampled yet
+    pub fn multiplier(&self) -> f64 {
+        let samples = self.samples.lock().unwrap();
+        if samples.is_empty() {
+            1.0
+        } else {
+            samples.iter().sum::<f64>() / samples.len() as f64
+        }
+    }
+
+    /// The difficulty a send or change block needs to keep up with the recent blocks
+    pub fn difficulty(&self) -> u64 {
+        WorkThresholds::difficulty_from_multiplier(
+            self.multiplier(),
+            self.thresholds.threshold_base(WorkVersion::Work1),
+        )
+    }
+
+    /// The difficulty a receive or epoch block needs to keep up with the recent blocks
+    pub fn receive_difficulty(&self) -> u64 {
+        let receive_threshold = self
+            .thresholds
+            .threshold_override
+            .unwrap_or(self.thresholds.epoch_2_receive);
+        let multiplier = self
+            .thresholds
+            .denormalized_multiplier(self.multiplier(), receive_threshold);
+        WorkThresholds::difficulty_from_multiplier(multiplier, receive_threshold)
+    }
+
+    /// The sampled multipliers, oldest first
+    pub fn trend(&self) -> Vec<f64> {
+        self.samples.lock().unwrap().iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::{BlockBuilder, BlockDetails, Epoch};
+
+    #[test]
+    fn no_samples() {
+        let active = ActiveDifficulty::new(WorkThresholds::publish_dev().clone(), 10);
+        assert_eq!(active.multiplier(), 1.0);
+        assert_eq!(active.difficulty(), WorkThresholds::publish_dev().base);
+        assert_eq!(
+            active.receive_difficulty(),
+            WorkThresholds::publish_dev().epoch_2_receive
+        );
+        assert!(active.trend().is_empty());
+    }
+
+    #[test]
+    fn average_of_samples() {
+        let active = ActiveDifficulty::new(WorkThresholds::publish_dev().clone(), 10);
+        active.add_multiplier(1.0);
+        active.add_multiplier(3.0);
+        assert_eq!(active.multiplier(), 2.0);
+    }
+
+    #[test]
+    fn drop_oldest_sample() {
+        let active = ActiveDifficulty::new(WorkThresholds::publish_dev().clone(), 2);
+        active.add_multiplier(1.0);
+        active.add_multiplier(2.0);
+        active.add_multiplier(3.0);
+        assert_eq!(active.trend(), vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn ignore_block_without_sideband() {
+        let active = ActiveDifficulty::new(WorkThresholds::publish_dev().clone(), 10);
+        active.sample(&BlockBuilder::state().build());
+        assert!(active.trend().is_empty());
+    }
+
+    #[test]
+    fn sample_block() {
+        let active = ActiveDifficulty::new(WorkThresholds::publish_dev().clone(), 10);
+        let mut block = BlockBuilder::state().with_sideband().build();
+        let mut sideband = block.sideband().unwrap().clone();
+        sideband.details = BlockDetails::new(Epoch::Epoch2, true, false, false);
+        block.as_block_mut().set_sideband(sideband);
+
+        active.sample(&block);
+
+        let trend = active.trend();
+        assert_eq!(trend.len(), 1);
+        assert!(trend[0] >= 1.0);
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
     ops::Deref,
     sync::{Arc, Condvar, Mutex},
-    thread::{self, JoinHandle},
     time::Duration,
 };
 
-use crate::stats::{DetailType, Direction, StatType, Stats};
+use crate::{
+    stats::{DetailType, Direction, StatType, Stats},
+    utils::ThreadRunner,
+};
 use primitive_types::U256;
 use rsnano_core::{Account, AccountInfo, ConfirmationHeightInfo};
 use rsnano_ledger::Ledger;
@@ -42,7 +44,7 @@ pub struct BacklogPopulation {
     condition: Arc<Condvar>,
     /** Thread that runs the backlog implementation logic. The thread always runs, even if
      *  backlog population is disabled, so that it can service a manual trigger (e.g. via RPC). */
-    thread: Option<JoinHandle<()>>,
+    thread: ThreadRunner,
 }
 
 pub type ActivateCallback = Box<
@@ -67,7 +69,7 @@ impl BacklogPopulation {
                 triggered: false,
             })),
             condition: Arc::new(Condvar::new()),
-            thread: None,
+            thread: ThreadRunner::new("Backlog"),
         }
     }
 
@@ -77,8 +79,6 @@ impl BacklogPopulation {
     }
 
     pub fn start(&mut self) {
-        debug_assert!(self.thread.is_none());
-
         let thread = BacklogPopulationThread {
             ledger: Arc::clone(&self.ledger),
             stats: Arc::clone(&self.stats),
@@ -88,14 +88,7 @@ impl BacklogPopulation {
             condition: Arc::clone(&self.condition),
         };
 
-        self.thread = Some(
-            thread::Builder::new()
-                .name("Backlog".to_owned())
-                .spawn(move || {
-                    thread.run();
-                })
-                .unwrap(),
-        );
+        self.thread.start(move || thread.run()).unwrap();
     }
 
     pub fn stop(&mut self) {
@@ -103,9 +96,7 @@ impl BacklogPopulation {
         lock.stopped = true;
         drop(lock);
         self.notify();
-        if let Some(handle) = self.thread.take() {
-            handle.join().unwrap()
-        }
+        self.thread.join().unwrap();
     }
 
     /** Manually trigger backlog population */
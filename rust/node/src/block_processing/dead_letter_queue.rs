@@ -0,0 +1,281 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rsnano_core::{utils::PropertyTreeWriter, Account, BlockHash};
+use rsnano_ledger::ProcessResult;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeadLetter {
+    pub hash: BlockHash,
+    pub account: Account,
+    pub last_result: ProcessResult,
+    /// How often the block failed with a transient result
+    pub attempts: u32,
+    /// Milliseconds since the unix epoch
+    pub first_failure_unix_ms: u64,
+    pub last_failure_unix_ms: u64,
+}
+
+/// Keeps track of blocks which failed with a transient result (gaps and forks).
+/// A block which failed `min_attempts` times is a dead letter and can be inspected.
+/// Entries expire after `max_age` without a new failure. If the buffer is full,
+/// the block which failed first is dropped.
+pub struct DeadLetterQueue {
+    min_attempts: u32,
+    max_entries: usize,
+    max_age: Duration,
+    data: Mutex<DeadLetterData>,
+}
+
+#[derive(Default)]
+struct DeadLetterData {
+    entries: HashMap<BlockHash, (Instant, DeadLetter)>,
+    /// Blocks in the order in which they failed first
+    order: VecDeque<BlockHash>,
+}
+
+impl DeadLetterData {
+    fn remove(&mut self, hash: &BlockHash) -> bool {
+        if self.entries.remove(hash).is_some() {
+            self.order.retain(|h| h != hash);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expire(&mut self, now: Instant, max_age: Duration) {
+        let entries = &mut self.entries;
+        entries
+            .retain(|_, (last_failure, _)| now.saturating_duration_since(*last_failure) < max_age);
+        self.order.retain(|hash| entries.contains_key(hash));
+    }
+}
+
+impl DeadLetterQueue {
+    pub const DEFAULT_MIN_ATTEMPTS: u32 = 3;
+    pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+    pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+    pub fn new(min_attempts: u32, max_entries: usize, max_age: Duration) -> Self {
+        Self {
+            min_attempts,
+            max_entries,
+            max_age,
+            data: Mutex::new(DeadLetterData::default()),
+        }
+    }
+
+    /// Records the result of processing a block. Only transient failures are
+    /// tracked. Any other result removes the block from the queue
+    pub fn record(
+        &self,
+        hash: &BlockHash,
+        account: &Account,
+        result: ProcessResult,
+        now: Instant,
+        unix_ms: u64,
+    ) {
+        let mut data = self.data.lock().unwrap();
+        if !result.is_transient() {
+            data.remove(hash);
+            return;
+        }
+
+        data.expire(now, self.max_age);
+        if let Some((last_failure, entry)) = data.entries.get_mut(hash) {
+            *last_failure = now;
+            entry.last_result = result;
+            entry.attempts += 1;
+            entry.last_failure_unix_ms = unix_ms;
+            return;
+        }
+
+        while data.entries.len() >= self.max_entries {
+            let Some(oldest) = data.order.pop_front() else {
+                break;
+            };
+            data.entries.remove(&oldest);
+        }
+        let entry = DeadLetter {
+            hash: *hash,
+            account: *account,
+            last_result: result,
+            attempts: 1,
+            first_failure_unix_ms: unix_ms,
+            last_failure_unix_ms: unix_ms,
+        };
+        data.entries.insert(*hash, (now, entry));
+        data.order.push_back(*hash);
+    }
+
+    pub fn get(&self, hash: &BlockHash) -> Option<DeadLetter> {
+        let data = self.data.lock().unwrap();
+        data.entries.get(hash).map(|(_, entry)| entry.clone())
+    }
+
+    /// Blocks which failed at least `min_attempts` times, the oldest first
+    pub fn dead_letters(&self, now: Instant) -> Vec<DeadLetter> {
+        let mut data = self.data.lock().unwrap();
+        data.expire(now, self.max_age);
+        data.order
+            .iter()
+            .filter_map(|hash| data.entries.get(hash))
+            .map(|(_, entry)| entry)
+            .filter(|entry| entry.attempts >= self.min_attempts)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of tracked blocks, including the ones which aren't dead letters yet
+    pub fn len(&self) -> usize {
+        self.data.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn serialize_json(
+        &self,
+        writer: &mut dyn PropertyTreeWriter,
+        now: Instant,
+    ) -> anyhow::Result<()> {
+        let mut letters = writer.new_writer();
+        for letter in self.dead_letters(now) {
+            let mut entry = writer.new_writer();
+            entry.put_string("hash", &letter.hash.to_string())?;
+            entry.put_string("account", &letter.account.encode_account())?;
+            entry.put_string("last_result", letter.last_result.as_str())?;
+            entry.put_u64("attempts", letter.attempts as u64)?;
+            entry.put_string("first_failure", &letter.first_failure_unix_ms.to_string())?;
+            entry.put_string("last_failure", &letter.last_failure_unix_ms.to_string())?;
+            letters.push_back("", entry.as_ref());
+        }
+        writer.add_child("dead_letters", letters.as_ref());
+        Ok(())
+    }
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_MIN_ATTEMPTS,
+            Self::DEFAULT_MAX_ENTRIES,
+            Self::DEFAULT_MAX_AGE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue() -> DeadLetterQueue {
+        DeadLetterQueue::new(2, 2, Duration::from_secs(60))
+    }
+
+    fn fail(queue: &DeadLetterQueue, hash: u64, result: ProcessResult, now: Instant) {
+        queue.record(
+            &BlockHash::from(hash),
+            &Account::from(hash),
+            result,
+            now,
+            1000,
+        );
+    }
+
+    #[test]
+    fn single_failure_is_no_dead_letter() {
+        let queue = queue();
+        let now = Instant::now();
+        fail(&queue, 1, ProcessResult::GapPrevious, now);
+        assert_eq!(queue.len(), 1);
+        assert!(queue.dead_letters(now).is_empty());
+    }
+
+    #[test]
+    fn repeated_failures() {
+        let queue = queue();
+        let now = Instant::now();
+        fail(&queue, 1, ProcessResult::GapPrevious, now);
+        queue.record(
+            &BlockHash::from(1),
+            &Account::from(1),
+            ProcessResult::Fork,
+            now + Duration::from_secs(1),
+            2000,
+        );
+
+        assert_eq!(
+            queue.dead_letters(now),
+            vec![DeadLetter {
+                hash: BlockHash::from(1),
+                account: Account::from(1),
+                last_result: ProcessResult::Fork,
+                attempts: 2,
+                first_failure_unix_ms: 1000,
+                last_failure_unix_ms: 2000,
+            }]
+        );
+    }
+
+    #[test]
+    fn ignore_permanent_failures() {
+        let queue = queue();
+        fail(&queue, 1, ProcessResult::BadSignature, Instant::now());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn remove_block_when_processed() {
+        let queue = queue();
+        let now = Instant::now();
+        fail(&queue, 1, ProcessResult::GapSource, now);
+        fail(&queue, 1, ProcessResult::Progress, now);
+        assert_eq!(queue.get(&BlockHash::from(1)), None);
+    }
+
+    #[test]
+    fn drop_oldest_when_full() {
+        let queue = queue();
+        let now = Instant::now();
+        for hash in 1..=3 {
+            fail(&queue, hash, ProcessResult::GapPrevious, now);
+        }
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.get(&BlockHash::from(1)), None);
+        assert!(queue.get(&BlockHash::from(3)).is_some());
+    }
+
+    #[test]
+    fn expire_entries() {
+        let queue = queue();
+        let now = Instant::now();
+        fail(&queue, 1, ProcessResult::GapPrevious, now);
+        fail(&queue, 1, ProcessResult::GapPrevious, now);
+        fail(
+            &queue,
+            2,
+            ProcessResult::GapPrevious,
+            now + Duration::from_secs(30),
+        );
+        fail(
+            &queue,
+            2,
+            ProcessResult::GapPrevious,
+            now + Duration::from_secs(30),
+        );
+
+        let hashes: Vec<_> = queue
+            .dead_letters(now + Duration::from_secs(60))
+            .iter()
+            .map(|l| l.hash)
+            .collect();
+        assert_eq!(hashes, vec![BlockHash::from(2)]);
+        assert_eq!(queue.len(), 1);
+    }
+}
@@ -1,7 +1,11 @@
+mod active_difficulty;
 mod backlog_population;
 mod block_arrival;
 mod block_processor;
+mod dead_letter_queue;
 
+pub use active_difficulty::ActiveDifficulty;
 pub use backlog_population::{BacklogPopulation, BacklogPopulationConfig};
 pub use block_arrival::*;
 pub use block_processor::*;
+pub use dead_letter_queue::{DeadLetter, DeadLetterQueue};
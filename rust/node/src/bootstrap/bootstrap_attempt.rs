@@ -9,13 +9,32 @@ use rsnano_ledger::Ledger;
 use std::{
     sync::{
         atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
-        Arc, Condvar, Mutex, RwLock, Weak,
+        Arc, Condvar, Mutex, MutexGuard, RwLock, Weak,
     },
     time::{Duration, Instant},
 };
 
 use super::{bootstrap_limits, BootstrapInitiator, BootstrapMode};
 
+/// Lifecycle of a bootstrap attempt. An attempt is queued until one of the
+/// bootstrap initiator threads picks it up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BootstrapAttemptState {
+    Queued,
+    Running,
+    Stopped,
+}
+
+impl BootstrapAttemptState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BootstrapAttemptState::Queued => "queued",
+            BootstrapAttemptState::Running => "running",
+            BootstrapAttemptState::Stopped => "stopped",
+        }
+    }
+}
+
 pub struct BootstrapAttempt {
     pub incremental_id: u64,
     pub id: String,
@@ -172,6 +191,29 @@ impl BootstrapAttempt {
         self.stopped.store(true, Ordering::SeqCst);
     }
 
+    pub fn state(&self) -> BootstrapAttemptState {
+        if self.stopped() {
+            BootstrapAttemptState::Stopped
+        } else if self.started.load(Ordering::SeqCst) {
+            BootstrapAttemptState::Running
+        } else {
+            BootstrapAttemptState::Queued
+        }
+    }
+
+    /// Blocks until fewer than `max_pulls` pulls of this attempt are in flight
+    pub fn wait_for_pull_slot<'a>(
+        &self,
+        guard: MutexGuard<'a, u8>,
+        max_pulls: u32,
+    ) -> MutexGuard<'a, u8> {
+        self.condition
+            .wait_while(guard, |_| {
+                !self.stopped() && self.pulling.load(Ordering::SeqCst) >= max_pulls
+            })
+            .unwrap()
+    }
+
     pub fn still_pulling(&self) -> bool {
         debug_assert!(self.mutex.try_lock().is_err());
         let running = !self.stopped.load(Ordering::SeqCst);
@@ -1,16 +1,25 @@
 use crate::{block_processing::BlockProcessor, websocket::Listener};
 use anyhow::Result;
-use rsnano_core::utils::Logger;
+use rsnano_core::{
+    utils::{Logger, PropertyTreeWriter},
+    Account, Amount, BlockEnum, BlockHash, BlockType, HashOrAccount, Link,
+};
 use rsnano_ledger::Ledger;
-use std::sync::{Arc, Weak};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex, Weak},
+};
 
 use super::{BootstrapAttempt, BootstrapInitiator, BootstrapMode};
 
 pub struct BootstrapAttemptLazy {
     pub attempt: BootstrapAttempt,
+    pub state: Mutex<LazyBootstrapState>,
+    pub ledger: Arc<Ledger>,
 }
 
 impl BootstrapAttemptLazy {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         logger: Arc<dyn Logger>,
         websocket_server: Arc<dyn Listener>,
@@ -19,6 +28,8 @@ impl BootstrapAttemptLazy {
         ledger: Arc<Ledger>,
         id: &str,
         incremental_id: u64,
+        retry_limit: u32,
+        max_keys: usize,
     ) -> Result<Self> {
         Ok(Self {
             attempt: BootstrapAttempt::new(
@@ -26,11 +37,430 @@ impl BootstrapAttemptLazy {
                 websocket_server,
                 block_processor,
                 bootstrap_initiator,
-                ledger,
+                Arc::clone(&ledger),
                 id,
                 BootstrapMode::Lazy,
                 incremental_id,
             )?,
+            state: Mutex::new(LazyBootstrapState::new(retry_limit, max_keys)),
+            ledger,
         })
     }
 }
+
+/// The ledger queries which are needed to find the dependencies of pulled blocks
+pub trait LazyLedger {
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool;
+    /// Returns None if the block was pruned
+    fn balance_safe(&self, hash: &BlockHash) -> Option<Amount>;
+    fn is_epoch_link(&self, link: &Link) -> bool;
+    fn genesis_account(&self) -> Account;
+}
+
+impl LazyLedger for Ledger {
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool {
+        Ledger::block_or_pruned_exists(self, hash)
+    }
+
+    fn balance_safe(&self, hash: &BlockHash) -> Option<Amount> {
+        let txn = self.read_txn();
+        Ledger::balance_safe(self, &txn, hash).ok()
+    }
+
+    fn is_epoch_link(&self, link: &Link) -> bool {
+        Ledger::is_epoch_link(self, link)
+    }
+
+    fn genesis_account(&self) -> Account {
+        self.constants.genesis_account
+    }
+}
+
+/// A state block whose subtype is unknown until its previous block was processed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct StateBacklogItem {
+    link: Link,
+    balance: Amount,
+    retry_limit: u32,
+}
+
+/// Bookkeeping of a lazy bootstrap attempt. Starting with the requested hashes or accounts,
+/// the pulled chains are traced backwards and their unknown dependencies (sources of
+/// receives and the send blocks which are linked by state blocks) are queued as new pulls.
+/// Blocks are only processed once, so that dependency cycles don't lead to endless pulling.
+pub struct LazyBootstrapState {
+    retry_limit: u32,
+    max_keys: usize,
+    blocks: HashSet<BlockHash>,
+    state_backlog: HashMap<BlockHash, StateBacklogItem>,
+    undefined_links: HashSet<BlockHash>,
+    balances: HashMap<BlockHash, Amount>,
+    keys: HashSet<BlockHash>,
+    pulls: VecDeque<(HashOrAccount, u32)>,
+}
+
+impl LazyBootstrapState {
+    /// `max_keys` limits the number of start hashes or accounts
+    pub fn new(retry_limit: u32, max_keys: usize) -> Self {
+        Self {
+            retry_limit,
+            max_keys,
+            blocks: HashSet::new(),
+            state_backlog: HashMap::new(),
+            undefined_links: HashSet::new(),
+            balances: HashMap::new(),
+            keys: HashSet::new(),
+            pulls: VecDeque::new(),
+        }
+    }
+
+    /// Adds a start hash or account. Returns true if it was inserted
+    pub fn start(&mut self, hash_or_account: &HashOrAccount) -> bool {
+        let hash = BlockHash::from(*hash_or_account);
+        if self.keys.len() < self.max_keys
+            && !self.keys.contains(&hash)
+            && !self.blocks_processed(&hash)
+        {
+            self.keys.insert(hash);
+            self.pulls.push_back((*hash_or_account, self.retry_limit));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Queues a pull, unless the block was already processed
+    pub fn add(&mut self, hash_or_account: &HashOrAccount, retry_limit: u32) {
+        if !self.blocks_processed(&BlockHash::from(*hash_or_account)) {
+            self.pulls.push_back((*hash_or_account, retry_limit));
+        }
+    }
+
+    /// Forgets a processed block so that it can be pulled again.
+    /// Returns false if the block wasn't processed by this attempt
+    pub fn requeue(&mut self, hash: &BlockHash) -> bool {
+        self.blocks.remove(hash)
+    }
+
+    pub fn blocks_processed(&self, hash: &BlockHash) -> bool {
+        self.blocks.contains(hash)
+    }
+
+    pub fn blocks_len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn pulls_len(&self) -> usize {
+        self.pulls.len()
+    }
+
+    /// Returns the next pull of a block which is neither processed nor in the ledger
+    pub fn next_pull(&mut self, ledger: &dyn LazyLedger) -> Option<(HashOrAccount, u32)> {
+        while let Some((hash_or_account, retry_limit)) = self.pulls.pop_front() {
+            let hash = BlockHash::from(hash_or_account);
+            if !self.blocks_processed(&hash) && !ledger.block_or_pruned_exists(&hash) {
+                return Some((hash_or_account, retry_limit));
+            }
+        }
+        None
+    }
+
+    /// Searches the dependencies of a pulled block.
+    /// Returns false if the block was already processed by this attempt.
+    pub fn process_block(
+        &mut self,
+        block: &BlockEnum,
+        pull_blocks_processed: u64,
+        retry_limit: u32,
+        ledger: &dyn LazyLedger,
+    ) -> bool {
+        let hash = block.hash();
+        if self.blocks_processed(&hash) {
+            return false;
+        }
+
+        match block.source() {
+            Some(source)
+                if !source.is_zero()
+                    && !ledger.block_or_pruned_exists(&source)
+                    && Account::from(HashOrAccount::from(source)) != ledger.genesis_account() =>
+            {
+                self.add(&source.into(), retry_limit);
+            }
+            _ => {
+                if let BlockEnum::State(_) = block {
+                    self.block_state(block, retry_limit, ledger);
+                }
+            }
+        }
+        self.blocks.insert(hash);
+        // Adding lazy balances for first processed block in pull
+        if pull_blocks_processed == 1 {
+            if let Some(balance) = block.balance_opt() {
+                self.balances.insert(hash, balance);
+            }
+        }
+        // Clearing lazy balances for previous block
+        let previous = block.previous();
+        if !previous.is_zero() {
+            self.balances.remove(&previous);
+        }
+        self.backlog_check(block, &hash);
+        true
+    }
+
+    fn block_state(&mut self, block: &BlockEnum, retry_limit: u32, ledger: &dyn LazyLedger) {
+        let balance = block.balance();
+        let link = block.link();
+        let link_hash = BlockHash::from(link);
+        // If link is not epoch link or 0. And if block from link is unknown
+        if link.is_zero()
+            || ledger.is_epoch_link(&link)
+            || self.blocks_processed(&link_hash)
+            || ledger.block_or_pruned_exists(&link_hash)
+        {
+            return;
+        }
+
+        let previous = block.previous();
+        if previous.is_zero() {
+            // If state block previous is 0 then source block required
+            self.add(&link_hash.into(), retry_limit);
+        } else if ledger.block_or_pruned_exists(&previous) {
+            // In other cases previous block balance required to find out subtype of state block.
+            // Pruned blocks are ignored
+            if let Some(previous_balance) = ledger.balance_safe(&previous) {
+                if previous_balance <= balance {
+                    self.add(&link_hash.into(), retry_limit);
+                }
+            }
+        } else if self.blocks_processed(&previous) {
+            // Search balance of already processed previous blocks
+            if let Some(previous_balance) = self.balances.remove(&previous) {
+                if previous_balance <= balance {
+                    self.add(&link_hash.into(), retry_limit);
+                }
+            }
+        } else {
+            // Insert in backlog state blocks if previous wasn't already processed
+            self.state_backlog
+                .entry(previous)
+                .or_insert(StateBacklogItem {
+                    link,
+                    balance,
+                    retry_limit,
+                });
+        }
+    }
+
+    fn backlog_check(&mut self, block: &BlockEnum, hash: &BlockHash) {
+        // Search unknown state blocks balances
+        let Some(next_block) = self.state_backlog.remove(hash) else {
+            return;
+        };
+        let link_hash = BlockHash::from(next_block.link);
+        if matches!(block.block_type(), BlockType::State | BlockType::LegacySend) {
+            // Retrieve balance for previous state & send blocks
+            if block.balance() <= next_block.balance {
+                self.add(&link_hash.into(), next_block.retry_limit);
+            }
+        } else if self.undefined_links.insert(link_hash) {
+            // Assumption for other legacy block types.
+            // Head is not confirmed. It can be account or hash or non-existing
+            self.add(&link_hash.into(), self.retry_limit);
+        }
+    }
+
+    /// Resolves the backlog entries whose previous block arrived in the ledger
+    /// and queues pulls for the missing ones
+    pub fn backlog_cleanup(&mut self, ledger: &dyn LazyLedger) {
+        let backlog: Vec<_> = self
+            .state_backlog
+            .iter()
+            .map(|(previous, item)| (*previous, *item))
+            .collect();
+        for (previous, next_block) in backlog {
+            if ledger.block_or_pruned_exists(&previous) {
+                let link_hash = BlockHash::from(next_block.link);
+                match ledger.balance_safe(&previous) {
+                    Some(balance) => {
+                        if balance <= next_block.balance {
+                            self.add(&link_hash.into(), next_block.retry_limit);
+                        }
+                    }
+                    // Not confirmed
+                    None => self.add(&link_hash.into(), self.retry_limit),
+                }
+                self.state_backlog.remove(&previous);
+            } else {
+                self.add(&previous.into(), next_block.retry_limit);
+            }
+        }
+    }
+
+    /// Returns true if all start blocks are in the ledger or if there is nothing left to pull
+    pub fn finished(&mut self, ledger: &dyn LazyLedger) -> bool {
+        self.keys
+            .retain(|hash| !ledger.block_or_pruned_exists(hash));
+        // Finish lazy bootstrap without lazy pulls (in combination with still_pulling ())
+        self.keys.is_empty() || (self.pulls.is_empty() && self.state_backlog.is_empty())
+    }
+
+    pub fn get_information(&self, writer: &mut dyn PropertyTreeWriter) -> Result<()> {
+        writer.put_string("lazy_blocks", &self.blocks.len().to_string())?;
+        writer.put_string("lazy_state_backlog", &self.state_backlog.len().to_string())?;
+        writer.put_string("lazy_balances", &self.balances.len().to_string())?;
+        writer.put_string(
+            "lazy_undefined_links",
+            &self.undefined_links.len().to_string(),
+        )?;
+        writer.put_string("lazy_pulls", &self.pulls.len().to_string())?;
+        writer.put_string("lazy_keys", &self.keys.len().to_string())?;
+        if let Some(key) = self.keys.iter().next() {
+            writer.put_string("lazy_key_1", &key.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::BlockBuilder;
+
+    #[derive(Default)]
+    struct LazyLedgerStub {
+        blocks: HashMap<BlockHash, Amount>,
+    }
+
+    impl LazyLedger for LazyLedgerStub {
+        fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool {
+            self.blocks.contains_key(hash)
+        }
+
+        fn balance_safe(&self, hash: &BlockHash) -> Option<Amount> {
+            self.blocks.get(hash).cloned()
+        }
+
+        fn is_epoch_link(&self, _link: &Link) -> bool {
+            false
+        }
+
+        fn genesis_account(&self) -> Account {
+            Account::from(1000)
+        }
+    }
+
+    fn state_block(previous: impl Into<BlockHash>, balance: u128, link: u64) -> BlockEnum {
+        BlockBuilder::state()
+            .previous(previous)
+            .balance(balance)
+            .link(Link::from(link))
+            .build()
+    }
+
+    fn pulls(state: &mut LazyBootstrapState, ledger: &LazyLedgerStub) -> Vec<HashOrAccount> {
+        let mut result = Vec::new();
+        while let Some((hash_or_account, _)) = state.next_pull(ledger) {
+            result.push(hash_or_account);
+        }
+        result
+    }
+
+    #[test]
+    fn start_is_limited_to_max_keys() {
+        let mut state = LazyBootstrapState::new(3, 2);
+        assert!(state.start(&HashOrAccount::from(1)));
+        assert!(!state.start(&HashOrAccount::from(1)));
+        assert!(state.start(&HashOrAccount::from(2)));
+        assert!(!state.start(&HashOrAccount::from(3)));
+        assert_eq!(state.pulls_len(), 2);
+    }
+
+    #[test]
+    fn pull_source_of_open_state_block() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let ledger = LazyLedgerStub::default();
+        let block = state_block(0, 10, 42);
+
+        assert!(state.process_block(&block, 1, 3, &ledger));
+
+        assert_eq!(pulls(&mut state, &ledger), vec![HashOrAccount::from(42)]);
+    }
+
+    #[test]
+    fn blocks_are_processed_only_once() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let ledger = LazyLedgerStub::default();
+        let block = state_block(0, 10, 42);
+
+        assert!(state.process_block(&block, 1, 3, &ledger));
+        assert!(!state.process_block(&block, 1, 3, &ledger));
+
+        assert_eq!(pulls(&mut state, &ledger).len(), 1);
+        assert_eq!(state.blocks_len(), 1);
+    }
+
+    #[test]
+    fn dont_pull_processed_blocks() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let ledger = LazyLedgerStub::default();
+        let block = state_block(0, 10, 42);
+        state.process_block(&block, 1, 3, &ledger);
+
+        // A dependency cycle leads back to an already processed block
+        state.add(&block.hash().into(), 3);
+
+        assert_eq!(pulls(&mut state, &ledger), vec![HashOrAccount::from(42)]);
+    }
+
+    #[test]
+    fn pull_link_if_previous_balance_is_lower() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let mut ledger = LazyLedgerStub::default();
+        ledger.blocks.insert(BlockHash::from(7), Amount::raw(5));
+
+        // receive
+        state.process_block(&state_block(7, 10, 42), 1, 3, &ledger);
+        // send
+        state.process_block(&state_block(7, 1, 43), 1, 3, &ledger);
+
+        assert_eq!(pulls(&mut state, &ledger), vec![HashOrAccount::from(42)]);
+    }
+
+    #[test]
+    fn resolve_backlog_when_previous_block_is_processed() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let ledger = LazyLedgerStub::default();
+        let previous = state_block(0, 5, 0);
+        let receive = state_block(previous.hash(), 10, 42);
+
+        state.process_block(&receive, 1, 3, &ledger);
+        assert_eq!(pulls(&mut state, &ledger), Vec::new());
+
+        state.process_block(&previous, 2, 3, &ledger);
+        assert_eq!(pulls(&mut state, &ledger), vec![HashOrAccount::from(42)]);
+    }
+
+    #[test]
+    fn backlog_cleanup_pulls_missing_previous_block() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let ledger = LazyLedgerStub::default();
+        state.process_block(&state_block(7, 10, 42), 1, 3, &ledger);
+
+        state.backlog_cleanup(&ledger);
+
+        assert_eq!(pulls(&mut state, &ledger), vec![HashOrAccount::from(7)]);
+    }
+
+    #[test]
+    fn finished_when_start_blocks_are_in_ledger() {
+        let mut state = LazyBootstrapState::new(3, 1024);
+        let mut ledger = LazyLedgerStub::default();
+        state.start(&HashOrAccount::from(1));
+        assert!(!state.finished(&ledger));
+
+        ledger.blocks.insert(BlockHash::from(1), Amount::raw(1));
+        assert!(state.finished(&ledger));
+    }
+}
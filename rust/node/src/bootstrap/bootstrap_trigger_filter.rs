@@ -0,0 +1,118 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use rsnano_core::HashOrAccount;
+
+/// Suppresses repeated bootstrap triggers for the same hash or account.
+/// Without it every RPC request for an unknown account would trigger a new lazy bootstrap.
+pub struct BootstrapTriggerFilter {
+    window: Duration,
+    max_entries: usize,
+    recent: HashMap<HashOrAccount, Instant>,
+    sequenced: VecDeque<HashOrAccount>,
+    deduplicated: u64,
+}
+
+impl BootstrapTriggerFilter {
+    pub fn new(window: Duration, max_entries: usize) -> Self {
+        Self {
+            window,
+            max_entries,
+            recent: HashMap::new(),
+            sequenced: VecDeque::new(),
+            deduplicated: 0,
+        }
+    }
+
+    /// Returns true if a bootstrap should be triggered for `key`, which is
+    /// the case if it wasn't triggered within the last `window`
+    pub fn check(&mut self, key: &HashOrAccount, now: Instant) -> bool {
+        self.remove_expired(now);
+        if self.recent.contains_key(key) {
+            self.deduplicated += 1;
+            return false;
+        }
+        self.recent.insert(*key, now);
+        self.sequenced.push_back(*key);
+        if self.sequenced.len() > self.max_entries {
+            if let Some(oldest) = self.sequenced.pop_front() {
+                self.recent.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// Number of triggers which were suppressed
+    pub fn deduplicated(&self) -> u64 {
+        self.deduplicated
+    }
+
+    pub fn len(&self) -> usize {
+        self.recent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty()
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        while let Some(oldest) = self.sequenced.front() {
+            if now.duration_since(self.recent[oldest]) < self.window {
+                break;
+            }
+            self.recent.remove(oldest);
+            self.sequenced.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_filter() -> BootstrapTriggerFilter {
+        BootstrapTriggerFilter::new(Duration::from_secs(30), 2)
+    }
+
+    #[test]
+    fn first_trigger_passes() {
+        let mut filter = create_filter();
+        assert!(filter.check(&HashOrAccount::from(1), Instant::now()));
+        assert_eq!(filter.len(), 1);
+        assert_eq!(filter.deduplicated(), 0);
+    }
+
+    #[test]
+    fn repeated_trigger_is_suppressed() {
+        let mut filter = create_filter();
+        let now = Instant::now();
+        filter.check(&HashOrAccount::from(1), now);
+
+        assert!(!filter.check(&HashOrAccount::from(1), now + Duration::from_secs(29)));
+        assert!(filter.check(&HashOrAccount::from(2), now));
+        assert_eq!(filter.deduplicated(), 1);
+    }
+
+    #[test]
+    fn trigger_passes_again_after_window() {
+        let mut filter = create_filter();
+        let now = Instant::now();
+        filter.check(&HashOrAccount::from(1), now);
+
+        assert!(filter.check(&HashOrAccount::from(1), now + Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn forget_oldest_when_full() {
+        let mut filter = create_filter();
+        let now = Instant::now();
+        filter.check(&HashOrAccount::from(1), now);
+        filter.check(&HashOrAccount::from(2), now);
+        filter.check(&HashOrAccount::from(3), now);
+
+        assert_eq!(filter.len(), 2);
+        assert!(filter.check(&HashOrAccount::from(1), now));
+    }
+}
@@ -20,6 +20,12 @@ use rsnano_core::{
 use rsnano_ledger::Ledger;
 
 /// Server side of a bulk_push request. Receives blocks and puts them in the block processor to be processed.
+///
+/// A legacy bootstrap attempt pushes the blocks a peer is missing after the frontier comparison
+/// (see `bulk_push_client` in the C++ node). Each block is sent as its block type byte followed by
+/// the serialized block. The stream is terminated by `BlockType::NotABlock`.
+/// Blocks with insufficient work end the transfer. Receiving is throttled while the block processor is half full
+/// and refused while a bootstrap attempt of this node is in progress.
 pub struct BulkPushServer {
     server_impl: Arc<Mutex<BulkPushServerImpl>>,
 }
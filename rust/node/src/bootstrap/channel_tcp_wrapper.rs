@@ -1,13 +1,12 @@
 use std::{
     net::{Ipv6Addr, SocketAddr, SocketAddrV6},
     sync::Arc,
-    time::SystemTime,
 };
 
-use rsnano_core::Account;
+use rsnano_core::{utils::Timestamp, Account};
 
 use crate::{
-    transport::{ChannelEnum, ChannelTcp, Socket, TcpServer},
+    transport::{ChannelEnum, ChannelTcp, Endpoint, Socket, TcpServer},
     utils::{ipv4_address_or_ipv6_subnet, map_address_to_subnetwork},
 };
 
@@ -38,21 +37,18 @@ impl ChannelTcpWrapper {
     }
 
     pub fn endpoint_v6(&self) -> SocketAddrV6 {
-        let SocketAddr::V6(endpoint) = self.tcp_channel().remote_endpoint() else {
-            panic!("not a v6 address");
-        };
-        endpoint
+        Endpoint::from(self.tcp_channel().remote_endpoint()).as_v6()
     }
 
     pub fn endpoint(&self) -> SocketAddr {
         self.tcp_channel().remote_endpoint()
     }
 
-    pub fn last_packet_sent(&self) -> SystemTime {
+    pub fn last_packet_sent(&self) -> Timestamp {
         self.channel.as_channel().get_last_packet_sent()
     }
 
-    pub fn last_bootstrap_attempt(&self) -> SystemTime {
+    pub fn last_bootstrap_attempt(&self) -> Timestamp {
         self.channel.as_channel().get_last_bootstrap_attempt()
     }
 
@@ -0,0 +1,336 @@
+use std::collections::VecDeque;
+
+use rsnano_core::{Account, BlockHash};
+use rsnano_ledger::Ledger;
+
+/// A very rough estimate of the maximum cost of `bulk_push`ing missing blocks
+pub const BULK_PUSH_COST_LIMIT: u64 = 200;
+
+/// Number of local frontiers which are loaded with one read transaction
+const LOCAL_FRONTIERS_BATCH_SIZE: usize = 128;
+
+/// The local side of a frontier comparison
+pub trait LocalFrontiers {
+    /// Returns up to `max` accounts with their head block, beginning with the first account after `start`
+    fn frontiers_after(&self, start: &Account, max: usize) -> Vec<(Account, BlockHash)>;
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool;
+}
+
+impl LocalFrontiers for Ledger {
+    fn frontiers_after(&self, start: &Account, max: usize) -> Vec<(Account, BlockHash)> {
+        let txn = self.read_txn();
+        let start = start.number().overflowing_add(1.into()).0.into();
+        let mut it = self.store.account.begin_account(&txn, &start);
+        let mut result = Vec::new();
+        while let Some((account, info)) = it.current() {
+            if result.len() >= max {
+                break;
+            }
+            result.push((*account, info.head));
+            it.next();
+        }
+        result
+    }
+
+    fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool {
+        Ledger::block_or_pruned_exists(self, hash)
+    }
+}
+
+/// Work which results from comparing the frontiers of a peer with the local ones
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FrontierWorkItem {
+    /// We are behind or on a fork. Pull `account` from `head` down to `end`.
+    /// `end` is zero if we don't know the account
+    Pull {
+        account: Account,
+        head: BlockHash,
+        end: BlockHash,
+    },
+    /// The peer is behind. Push our blocks from `head` down to `end`.
+    /// `end` is zero if the peer doesn't know the account
+    Push { head: BlockHash, end: BlockHash },
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FrontierComparerStats {
+    pub received: u64,
+    pub in_sync: u64,
+    /// Accounts for which we are behind or on a fork
+    pub behind: u64,
+    /// Accounts for which the peer is behind
+    pub ahead: u64,
+}
+
+/// Compares the frontiers which a peer streams in ascending account order
+/// with the local frontiers and classifies each account.
+pub struct FrontierComparer {
+    current: Account,
+    frontier: BlockHash,
+    local_frontiers: VecDeque<(Account, BlockHash)>,
+    bulk_push_enabled: bool,
+    bulk_push_cost: u64,
+    stats: FrontierComparerStats,
+}
+
+impl FrontierComparer {
+    /// `bulk_push_enabled` should be false if the peer only sends frontiers of recently modified accounts
+    pub fn new(start: Account, bulk_push_enabled: bool, local: &dyn LocalFrontiers) -> Self {
+        let mut comparer = Self {
+            current: start,
+            frontier: BlockHash::zero(),
+            local_frontiers: VecDeque::new(),
+            bulk_push_enabled,
+            bulk_push_cost: 0,
+            stats: FrontierComparerStats::default(),
+        };
+        comparer.next(local);
+        comparer
+    }
+
+    /// Compares a frontier received from the peer. The frontiers have to be passed in ascending account order.
+    pub fn compare(
+        &mut self,
+        account: Account,
+        latest: BlockHash,
+        local: &dyn LocalFrontiers,
+    ) -> Vec<FrontierWorkItem> {
+        let mut work = Vec::new();
+        self.stats.received += 1;
+        while !self.current.is_zero() && self.current < account {
+            // We know about an account they don't.
+            self.unsynced(self.frontier, BlockHash::zero(), &mut work);
+            self.next(local);
+        }
+
+        if !self.current.is_zero() && account == self.current {
+            if latest == self.frontier {
+                self.stats.in_sync += 1;
+            } else if local.block_or_pruned_exists(&latest) {
+                // We know about a block they don't.
+                self.unsynced(self.frontier, latest, &mut work);
+            } else {
+                // Either we're behind or there's a fork we differ on
+                // Either way, bulk pushing will probably not be effective
+                self.stats.behind += 1;
+                self.bulk_push_cost += 5;
+                work.push(FrontierWorkItem::Pull {
+                    account,
+                    head: latest,
+                    end: self.frontier,
+                });
+            }
+            self.next(local);
+        } else {
+            // We don't know the account
+            self.stats.behind += 1;
+            work.push(FrontierWorkItem::Pull {
+                account,
+                head: latest,
+                end: BlockHash::zero(),
+            });
+        }
+        work
+    }
+
+    /// Called when the peer has sent all of its frontiers.
+    /// The remaining local accounts are unknown to the peer.
+    pub fn finish(&mut self, local: &dyn LocalFrontiers) -> Vec<FrontierWorkItem> {
+        let mut work = Vec::new();
+        while !self.current.is_zero() && self.bulk_push_available() {
+            self.unsynced(self.frontier, BlockHash::zero(), &mut work);
+            self.next(local);
+        }
+        work
+    }
+
+    pub fn bulk_push_available(&self) -> bool {
+        self.bulk_push_enabled && self.bulk_push_cost < BULK_PUSH_COST_LIMIT
+    }
+
+    pub fn bulk_push_cost(&self) -> u64 {
+        self.bulk_push_cost
+    }
+
+    pub fn stats(&self) -> FrontierComparerStats {
+        self.stats
+    }
+
+    fn unsynced(&mut self, head: BlockHash, end: BlockHash, work: &mut Vec<FrontierWorkItem>) {
+        self.stats.ahead += 1;
+        if self.bulk_push_available() {
+            work.push(FrontierWorkItem::Push { head, end });
+            self.bulk_push_cost += if end.is_zero() { 2 } else { 1 };
+        }
+    }
+
+    fn next(&mut self, local: &dyn LocalFrontiers) {
+        // Load the local frontiers in batches to prevent frequent read transactions
+        if self.local_frontiers.is_empty() {
+            self.local_frontiers
+                .extend(local.frontiers_after(&self.current, LOCAL_FRONTIERS_BATCH_SIZE));
+            if self.local_frontiers.len() != LOCAL_FRONTIERS_BATCH_SIZE {
+                // The end of the local accounts is marked by an empty entry
+                self.local_frontiers
+                    .push_back((Account::zero(), BlockHash::zero()));
+            }
+        }
+        let (account, frontier) = self.local_frontiers.pop_front().unwrap();
+        self.current = account;
+        self.frontier = frontier;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    struct LocalFrontiersStub {
+        frontiers: Vec<(Account, BlockHash)>,
+        blocks: HashSet<BlockHash>,
+    }
+
+    impl LocalFrontiers for LocalFrontiersStub {
+        fn frontiers_after(&self, start: &Account, max: usize) -> Vec<(Account, BlockHash)> {
+            self.frontiers
+                .iter()
+                .filter(|(account, _)| account > start)
+                .take(max)
+                .cloned()
+                .collect()
+        }
+
+        fn block_or_pruned_exists(&self, hash: &BlockHash) -> bool {
+            self.blocks.contains(hash)
+        }
+    }
+
+    fn local(frontiers: &[(u64, u64)], blocks: &[u64]) -> LocalFrontiersStub {
+        LocalFrontiersStub {
+            frontiers: frontiers
+                .iter()
+                .map(|(a, h)| (Account::from(*a), BlockHash::from(*h)))
+                .collect(),
+            blocks: blocks.iter().map(|h| BlockHash::from(*h)).collect(),
+        }
+    }
+
+    #[test]
+    fn in_sync() {
+        let local = local(&[(1, 10)], &[10]);
+        let mut comparer = FrontierComparer::new(Account::zero(), true, &local);
+
+        let work = comparer.compare(Account::from(1), BlockHash::from(10), &local);
+
+        assert!(work.is_empty());
+        assert!(comparer.finish(&local).is_empty());
+        assert_eq!(
+            comparer.stats(),
+            FrontierComparerStats {
+                received: 1,
+                in_sync: 1,
+                behind: 0,
+                ahead: 0
+            }
+        );
+    }
+
+    #[test]
+    fn pull_unknown_account() {
+        let local = local(&[], &[]);
+        let mut comparer = FrontierComparer::new(Account::zero(), true, &local);
+
+        let work = comparer.compare(Account::from(1), BlockHash::from(10), &local);
+
+        assert_eq!(
+            work,
+            vec![FrontierWorkItem::Pull {
+                account: Account::from(1),
+                head: BlockHash::from(10),
+                end: BlockHash::zero()
+            }]
+        );
+        assert_eq!(comparer.stats().behind, 1);
+    }
+
+    #[test]
+    fn pull_if_we_are_behind() {
+        let local = local(&[(1, 10)], &[10]);
+        let mut comparer = FrontierComparer::new(Account::zero(), true, &local);
+
+        let work = comparer.compare(Account::from(1), BlockHash::from(11), &local);
+
+        assert_eq!(
+            work,
+            vec![FrontierWorkItem::Pull {
+                account: Account::from(1),
+                head: BlockHash::from(11),
+                end: BlockHash::from(10)
+            }]
+        );
+        assert_eq!(comparer.bulk_push_cost(), 5);
+    }
+
+    #[test]
+    fn push_if_they_are_behind() {
+        let local = local(&[(1, 11)], &[10, 11]);
+        let mut comparer = FrontierComparer::new(Account::zero(), true, &local);
+
+        let work = comparer.compare(Account::from(1), BlockHash::from(10), &local);
+
+        assert_eq!(
+            work,
+            vec![FrontierWorkItem::Push {
+                head: BlockHash::from(11),
+                end: BlockHash::from(10)
+            }]
+        );
+        assert_eq!(comparer.stats().ahead, 1);
+    }
+
+    #[test]
+    fn push_accounts_the_peer_does_not_know() {
+        let local = local(&[(1, 10), (2, 20), (3, 30)], &[10, 20, 30]);
+        let mut comparer = FrontierComparer::new(Account::zero(), true, &local);
+
+        let work = comparer.compare(Account::from(2), BlockHash::from(20), &local);
+        assert_eq!(
+            work,
+            vec![FrontierWorkItem::Push {
+                head: BlockHash::from(10),
+                end: BlockHash::zero()
+            }]
+        );
+
+        let work = comparer.finish(&local);
+        assert_eq!(
+            work,
+            vec![FrontierWorkItem::Push {
+                head: BlockHash::from(30),
+                end: BlockHash::zero()
+            }]
+        );
+        assert_eq!(comparer.bulk_push_cost(), 4);
+    }
+
+    #[test]
+    fn no_push_if_bulk_push_is_disabled() {
+        let local = local(&[(1, 10)], &[10]);
+        let mut comparer = FrontierComparer::new(Account::zero(), false, &local);
+
+        assert!(comparer.finish(&local).is_empty());
+    }
+
+    #[test]
+    fn stop_pushing_when_cost_limit_is_reached() {
+        let frontiers: Vec<(u64, u64)> = (1..=200).map(|i| (i, i + 1000)).collect();
+        let local = local(&frontiers, &[]);
+        let mut comparer = FrontierComparer::new(Account::zero(), true, &local);
+
+        let work = comparer.finish(&local);
+
+        assert_eq!(work.len(), (BULK_PUSH_COST_LIMIT / 2) as usize);
+        assert!(!comparer.bulk_push_available());
+    }
+}
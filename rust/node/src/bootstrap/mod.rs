@@ -5,15 +5,18 @@ mod bootstrap_initiator;
 mod bootstrap_lazy;
 mod bootstrap_message_visitor;
 mod bootstrap_message_visitor_factory;
+mod bootstrap_trigger_filter;
 mod bulk_pull_account_server;
 mod bulk_pull_server;
 mod bulk_push_server;
 mod channel_tcp_wrapper;
+mod frontier_comparer;
 mod frontier_req_server;
 mod pulls_cache;
 
 pub use bootstrap_attempt::*;
 pub use bootstrap_initiator::*;
+pub use frontier_comparer::*;
 pub use frontier_req_server::FrontierReqServer;
 
 pub use bootstrap_client::{
@@ -24,6 +27,7 @@ pub use bootstrap_attempts::BootstrapAttempts;
 pub use bootstrap_lazy::BootstrapAttemptLazy;
 pub use bootstrap_message_visitor::BootstrapMessageVisitorImpl;
 pub use bootstrap_message_visitor_factory::BootstrapMessageVisitorFactory;
+pub use bootstrap_trigger_filter::BootstrapTriggerFilter;
 pub use bulk_pull_account_server::BulkPullAccountServer;
 pub use bulk_pull_server::BulkPullServer;
 pub use bulk_push_server::BulkPushServer;
@@ -44,7 +48,7 @@ pub enum BootstrapMode {
 }
 
 pub enum BootstrapStrategy {
-    Lazy(BootstrapAttemptLazy),
+    Lazy(Box<BootstrapAttemptLazy>),
     Other(BootstrapAttempt),
 }
 
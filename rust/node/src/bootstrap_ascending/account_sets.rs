@@ -0,0 +1,557 @@
+#[cfg(test)]
+use mock_instant::Instant;
+#[cfg(not(test))]
+use std::time::Instant;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    mem::size_of,
+    sync::Arc,
+};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, thread_rng, Rng};
+use rsnano_core::{
+    utils::{ContainerInfo, ContainerInfoComponent},
+    Account, BlockHash,
+};
+
+use crate::{
+    config::AccountSetsConfig,
+    stats::{DetailType, Direction, StatType, Stats},
+};
+
+/// Tracks the account sets which are shared among the multiple bootstrap threads:
+/// the prioritized accounts and the accounts which are blocked by a missing dependency
+pub struct AccountSets {
+    config: AccountSetsConfig,
+    stats: Arc<Stats>,
+    priorities: OrderedPriorities,
+    blocking: OrderedBlocking,
+}
+
+impl AccountSets {
+    pub const PRIORITY_INITIAL: f32 = 8.0;
+    pub const PRIORITY_INCREASE: f32 = 2.0;
+    pub const PRIORITY_DECREASE: f32 = 0.5;
+    pub const PRIORITY_MAX: f32 = 32.0;
+    pub const PRIORITY_CUTOFF: f32 = 1.0;
+
+    pub fn new(config: AccountSetsConfig, stats: Arc<Stats>) -> Self {
+        Self {
+            config,
+            stats,
+            priorities: OrderedPriorities::default(),
+            blocking: OrderedBlocking::default(),
+        }
+    }
+
+    /// If the account is not blocked, increase its priority.
+    /// If the account does not exist in the priority set and is not blocked, a new entry is inserted.
+    pub fn priority_up(&mut self, account: &Account) {
+        if self.blocked(account) {
+            self.inc_stat(DetailType::PrioritizeFailed);
+            return;
+        }
+
+        self.inc_stat(DetailType::Prioritize);
+        if let Some(entry) = self.priorities.get(account) {
+            let priority = (entry.priority * Self::PRIORITY_INCREASE).min(Self::PRIORITY_MAX);
+            self.priorities.change_priority(account, priority);
+        } else {
+            self.priorities
+                .insert(PriorityEntry::new(*account, Self::PRIORITY_INITIAL));
+            self.inc_stat(DetailType::PriorityInsert);
+            self.trim_overflow();
+        }
+    }
+
+    /// Decreases the account priority and removes the account from the
+    /// priority set when the priority drops to the cutoff
+    pub fn priority_down(&mut self, account: &Account) {
+        let Some(entry) = self.priorities.get(account) else {
+            self.inc_stat(DetailType::DeprioritizeFailed);
+            return;
+        };
+
+        self.inc_stat(DetailType::Deprioritize);
+        let priority = entry.priority - Self::PRIORITY_DECREASE;
+        if priority <= Self::PRIORITY_CUTOFF {
+            self.priorities.remove(account);
+            self.inc_stat(DetailType::PriorityEraseThreshold);
+        } else {
+            self.priorities.change_priority(account, priority);
+        }
+    }
+
+    /// Blocks the account until the block with the hash `dependency` is inserted into the ledger
+    pub fn block(&mut self, account: Account, dependency: BlockHash) {
+        self.inc_stat(DetailType::Block);
+
+        let original_entry = self.priorities.remove(&account);
+        self.inc_stat(DetailType::PriorityEraseBlock);
+
+        self.blocking.insert(BlockingEntry {
+            account,
+            dependency,
+            original_entry,
+        });
+        self.inc_stat(DetailType::BlockingInsert);
+
+        self.trim_overflow();
+    }
+
+    /// Unblocks the account if `hash` is the dependency it is waiting for, or if no hash is given.
+    /// The account regains the priority it had before it was blocked
+    pub fn unblock(&mut self, account: Account, hash: Option<BlockHash>) {
+        let dependency_fulfilled = self
+            .blocking
+            .get(&account)
+            .map(|entry| hash.map(|h| h == entry.dependency).unwrap_or(true))
+            .unwrap_or(false);
+
+        if !dependency_fulfilled {
+            self.inc_stat(DetailType::UnblockFailed);
+            return;
+        }
+
+        self.inc_stat(DetailType::Unblock);
+        let entry = self.blocking.remove(&account).unwrap();
+        debug_assert!(!self.priorities.contains(&account));
+        self.priorities.insert(
+            entry
+                .original_entry
+                .unwrap_or_else(|| PriorityEntry::new(account, Self::PRIORITY_INITIAL)),
+        );
+        self.trim_overflow();
+    }
+
+    /// Marks the account as being currently bootstrapped, so that it is not returned by `next`
+    /// until the cooldown is over. `reset` makes the account available again immediately
+    pub fn timestamp(&mut self, account: &Account, reset: bool) {
+        let timestamp = if reset { None } else { Some(Instant::now()) };
+        self.priorities.set_timestamp(account, timestamp);
+    }
+
+    /// Samples a few random prioritized accounts and picks one of them weighted by priority.
+    /// Returns `None` if there are no prioritized accounts or all sampled accounts are cooling down
+    pub fn next(&self) -> Option<Account> {
+        if self.priorities.is_empty() {
+            return None;
+        }
+
+        let mut rng = thread_rng();
+        let mut candidates = Vec::new();
+        let mut weights = Vec::new();
+        let mut iterations = 0;
+        while candidates.len() < self.config.consideration_count
+            && iterations < self.config.consideration_count * 10
+        {
+            iterations += 1;
+            // Use a dedicated, uniformly distributed field for sampling to avoid
+            // problematic corner cases when accounts in the queue are very close together
+            let entry = self.priorities.sample(rng.gen());
+            if self.check_timestamp(entry) {
+                candidates.push(entry.account);
+                weights.push(entry.priority);
+            }
+        }
+
+        if candidates.is_empty() {
+            return None; // All sampled accounts are busy
+        }
+
+        let distribution = WeightedIndex::new(&weights).unwrap();
+        Some(candidates[distribution.sample(&mut rng)])
+    }
+
+    pub fn blocked(&self, account: &Account) -> bool {
+        self.blocking.contains(account)
+    }
+
+    pub fn priority_len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    pub fn blocked_len(&self) -> usize {
+        self.blocking.len()
+    }
+
+    /// Accounts in the ledger but not in the priority set are assumed to have priority 1.0.
+    /// Blocked accounts are assumed to have priority 0.0
+    pub fn priority(&self, account: &Account) -> f32 {
+        if self.blocked(account) {
+            return 0.0;
+        }
+        self.priorities
+            .get(account)
+            .map(|entry| entry.priority)
+            .unwrap_or(Self::PRIORITY_CUTOFF)
+    }
+
+    pub fn info(&self) -> AccountSetsInfo {
+        AccountSetsInfo {
+            blocking: self
+                .blocking
+                .iter()
+                .map(|entry| (entry.account, entry.dependency))
+                .collect(),
+            priorities: self
+                .priorities
+                .iter()
+                .map(|entry| (entry.account, entry.priority))
+                .collect(),
+        }
+    }
+
+    pub fn collect_container_info(&self, name: String) -> ContainerInfoComponent {
+        ContainerInfoComponent::Composite(
+            name,
+            vec![
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "priorities".to_owned(),
+                    count: self.priorities.len(),
+                    sizeof_element: size_of::<PriorityEntry>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "blocking".to_owned(),
+                    count: self.blocking.len(),
+                    sizeof_element: size_of::<BlockingEntry>(),
+                }),
+            ],
+        )
+    }
+
+    fn check_timestamp(&self, entry: &PriorityEntry) -> bool {
+        match entry.timestamp {
+            Some(timestamp) => timestamp.elapsed() >= self.config.cooldown,
+            None => true,
+        }
+    }
+
+    fn trim_overflow(&mut self) {
+        if self.priorities.len() > self.config.priorities_max {
+            // Evict the lowest priority entry
+            self.priorities.pop_lowest_priority();
+            self.inc_stat(DetailType::PriorityEraseOverflow);
+        }
+        if self.blocking.len() > self.config.blocking_max {
+            // Evict the lowest priority entry
+            self.blocking.pop_lowest_priority();
+            self.inc_stat(DetailType::BlockingEraseOverflow);
+        }
+    }
+
+    fn inc_stat(&self, detail: DetailType) {
+        self.stats
+            .inc(StatType::BootstrapAscendingAccounts, detail, Direction::In);
+    }
+}
+
+/// A snapshot of the account sets
+#[derive(Default)]
+pub struct AccountSetsInfo {
+    /// Blocked accounts and the block hash they are waiting for
+    pub blocking: Vec<(Account, BlockHash)>,
+    pub priorities: Vec<(Account, f32)>,
+}
+
+#[derive(Clone)]
+struct PriorityEntry {
+    account: Account,
+    priority: f32,
+    timestamp: Option<Instant>,
+    /// Uniformly distributed, used for random querying
+    id: u64,
+}
+
+impl PriorityEntry {
+    fn new(account: Account, priority: f32) -> Self {
+        Self {
+            account,
+            priority,
+            timestamp: None,
+            id: thread_rng().gen(),
+        }
+    }
+}
+
+/// Priorities are never negative, so the ordering of their bit patterns
+/// is the same as the ordering of the numbers
+fn priority_key(priority: f32) -> u32 {
+    debug_assert!(priority >= 0.0);
+    priority.to_bits()
+}
+
+/// Tracks the ongoing account priorities.
+/// This only stores account priorities > 1.0
+#[derive(Default)]
+struct OrderedPriorities {
+    by_account: HashMap<Account, PriorityEntry>,
+    by_priority: BTreeSet<(u32, Account)>,
+    by_id: BTreeMap<u64, Account>,
+}
+
+impl OrderedPriorities {
+    fn len(&self) -> usize {
+        self.by_account.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.by_account.is_empty()
+    }
+
+    fn contains(&self, account: &Account) -> bool {
+        self.by_account.contains_key(account)
+    }
+
+    fn get(&self, account: &Account) -> Option<&PriorityEntry> {
+        self.by_account.get(account)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &PriorityEntry> {
+        self.by_account.values()
+    }
+
+    fn insert(&mut self, entry: PriorityEntry) {
+        debug_assert!(!self.contains(&entry.account));
+        self.by_priority
+            .insert((priority_key(entry.priority), entry.account));
+        self.by_id.insert(entry.id, entry.account);
+        self.by_account.insert(entry.account, entry);
+    }
+
+    fn remove(&mut self, account: &Account) -> Option<PriorityEntry> {
+        let entry = self.by_account.remove(account)?;
+        self.by_priority
+            .remove(&(priority_key(entry.priority), entry.account));
+        self.by_id.remove(&entry.id);
+        Some(entry)
+    }
+
+    fn change_priority(&mut self, account: &Account, priority: f32) {
+        if let Some(entry) = self.by_account.get_mut(account) {
+            self.by_priority
+                .remove(&(priority_key(entry.priority), entry.account));
+            entry.priority = priority;
+            self.by_priority
+                .insert((priority_key(entry.priority), entry.account));
+        }
+    }
+
+    fn set_timestamp(&mut self, account: &Account, timestamp: Option<Instant>) {
+        if let Some(entry) = self.by_account.get_mut(account) {
+            entry.timestamp = timestamp;
+        }
+    }
+
+    /// Returns the entry with the next id at or after `search`, wrapping around to the first entry.
+    /// Must not be called on an empty set
+    fn sample(&self, search: u64) -> &PriorityEntry {
+        let account = self
+            .by_id
+            .range(search..)
+            .next()
+            .or_else(|| self.by_id.iter().next())
+            .map(|(_, account)| account)
+            .unwrap();
+        &self.by_account[account]
+    }
+
+    fn pop_lowest_priority(&mut self) {
+        if let Some(&(_, account)) = self.by_priority.iter().next() {
+            self.remove(&account);
+        }
+    }
+}
+
+/// A blocked account is an account that has failed to insert a new block because
+/// the source block is not currently present in the ledger.
+/// An account is unblocked once it has a block successfully inserted
+struct BlockingEntry {
+    account: Account,
+    dependency: BlockHash,
+    /// The priority entry the account had before it was blocked
+    original_entry: Option<PriorityEntry>,
+}
+
+impl BlockingEntry {
+    fn priority(&self) -> f32 {
+        self.original_entry
+            .as_ref()
+            .map(|entry| entry.priority)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct OrderedBlocking {
+    by_account: HashMap<Account, BlockingEntry>,
+    by_priority: BTreeSet<(u32, Account)>,
+}
+
+impl OrderedBlocking {
+    fn len(&self) -> usize {
+        self.by_account.len()
+    }
+
+    fn contains(&self, account: &Account) -> bool {
+        self.by_account.contains_key(account)
+    }
+
+    fn get(&self, account: &Account) -> Option<&BlockingEntry> {
+        self.by_account.get(account)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &BlockingEntry> {
+        self.by_account.values()
+    }
+
+    fn insert(&mut self, entry: BlockingEntry) {
+        if let Some(old) = self.by_account.remove(&entry.account) {
+            self.by_priority
+                .remove(&(priority_key(old.priority()), old.account));
+        }
+        self.by_priority
+            .insert((priority_key(entry.priority()), entry.account));
+        self.by_account.insert(entry.account, entry);
+    }
+
+    fn remove(&mut self, account: &Account) -> Option<BlockingEntry> {
+        let entry = self.by_account.remove(account)?;
+        self.by_priority
+            .remove(&(priority_key(entry.priority()), entry.account));
+        Some(entry)
+    }
+
+    fn pop_lowest_priority(&mut self) {
+        if let Some(&(_, account)) = self.by_priority.iter().next() {
+            self.remove(&account);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_instant::MockClock;
+    use std::time::Duration;
+
+    #[test]
+    fn empty() {
+        let sets = create_account_sets();
+        assert_eq!(sets.priority_len(), 0);
+        assert_eq!(sets.blocked_len(), 0);
+        assert!(!sets.blocked(&Account::from(1)));
+        assert_eq!(
+            sets.priority(&Account::from(1)),
+            AccountSets::PRIORITY_CUTOFF
+        );
+        assert_eq!(sets.next(), None);
+    }
+
+    #[test]
+    fn block_and_unblock() {
+        let mut sets = create_account_sets();
+        let account = Account::from(1);
+        let dependency = BlockHash::from(2);
+        sets.block(account, dependency);
+        assert!(sets.blocked(&account));
+        assert_eq!(sets.priority(&account), 0.0);
+
+        sets.unblock(account, Some(BlockHash::from(3)));
+        assert!(sets.blocked(&account));
+
+        sets.unblock(account, Some(dependency));
+        assert!(!sets.blocked(&account));
+        assert_eq!(sets.priority(&account), AccountSets::PRIORITY_INITIAL);
+    }
+
+    #[test]
+    fn unblock_keeps_priority() {
+        let mut sets = create_account_sets();
+        let account = Account::from(1);
+        sets.priority_up(&account);
+        sets.priority_up(&account);
+        sets.block(account, BlockHash::from(2));
+        sets.unblock(account, None);
+        assert_eq!(
+            sets.priority(&account),
+            AccountSets::PRIORITY_INITIAL * AccountSets::PRIORITY_INCREASE
+        );
+    }
+
+    #[test]
+    fn priority_up_and_down() {
+        let mut sets = create_account_sets();
+        let account = Account::from(1);
+        sets.priority_up(&account);
+        assert_eq!(sets.priority(&account), AccountSets::PRIORITY_INITIAL);
+        sets.priority_down(&account);
+        assert_eq!(
+            sets.priority(&account),
+            AccountSets::PRIORITY_INITIAL - AccountSets::PRIORITY_DECREASE
+        );
+    }
+
+    #[test]
+    fn priority_is_bounded() {
+        let mut sets = create_account_sets();
+        let account = Account::from(1);
+        for _ in 0..100 {
+            sets.priority_up(&account);
+        }
+        assert_eq!(sets.priority(&account), AccountSets::PRIORITY_MAX);
+    }
+
+    #[test]
+    fn blocked_account_cannot_be_prioritized() {
+        let mut sets = create_account_sets();
+        let account = Account::from(1);
+        sets.block(account, BlockHash::from(2));
+        sets.priority_up(&account);
+        assert_eq!(sets.priority(&account), 0.0);
+        assert_eq!(sets.priority_len(), 0);
+    }
+
+    #[test]
+    fn evict_lowest_priority_on_overflow() {
+        let mut sets = AccountSets::new(
+            AccountSetsConfig {
+                priorities_max: 2,
+                ..Default::default()
+            },
+            Arc::new(Stats::default()),
+        );
+        sets.priority_up(&Account::from(1));
+        sets.priority_up(&Account::from(1));
+        sets.priority_up(&Account::from(2));
+        sets.priority_up(&Account::from(2));
+        sets.priority_up(&Account::from(3));
+        assert_eq!(sets.priority_len(), 2);
+        assert_eq!(
+            sets.priority(&Account::from(3)),
+            AccountSets::PRIORITY_CUTOFF
+        );
+    }
+
+    #[test]
+    fn next_skips_accounts_in_cooldown() {
+        let mut sets = create_account_sets();
+        let account = Account::from(1);
+        sets.priority_up(&account);
+        assert_eq!(sets.next(), Some(account));
+
+        sets.timestamp(&account, false);
+        assert_eq!(sets.next(), None);
+
+        MockClock::advance(Duration::from_secs(4));
+        assert_eq!(sets.next(), Some(account));
+
+        sets.timestamp(&account, false);
+        sets.timestamp(&account, true);
+        assert_eq!(sets.next(), Some(account));
+    }
+
+    fn create_account_sets() -> AccountSets {
+        AccountSets::new(AccountSetsConfig::default(), Arc::new(Stats::default()))
+    }
+}
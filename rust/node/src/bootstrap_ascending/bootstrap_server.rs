@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use rsnano_core::{Account, BlockHash};
+use rsnano_ledger::Ledger;
+use rsnano_store_lmdb::{RoCursorWrapper, Transaction};
+
+use crate::{
+    config::NetworkConstants,
+    messages::{
+        AccountInfoAckPayload, AccountInfoReqPayload, AscPullAck, AscPullAckPayload, AscPullReq,
+        AscPullReqPayload, BlocksAckPayload, BlocksReqPayload, HashType,
+    },
+    stats::{DetailType, Direction, StatType, Stats},
+};
+
+/// Answers bootstrap requests (`asc_pull_req` messages) with bootstrap responses (`asc_pull_ack`)
+/// from the ledger. Queueing the requests and sending the responses is up to the caller.
+pub struct BootstrapServer {
+    ledger: Arc<Ledger>,
+    network_constants: NetworkConstants,
+    stats: Arc<Stats>,
+}
+
+impl BootstrapServer {
+    /// Maximum number of blocks to send in a single response, cannot be higher than capacity of a single `asc_pull_ack` message
+    pub const MAX_BLOCKS: usize = BlocksAckPayload::MAX_BLOCKS;
+
+    pub fn new(
+        ledger: Arc<Ledger>,
+        network_constants: NetworkConstants,
+        stats: Arc<Stats>,
+    ) -> Self {
+        Self {
+            ledger,
+            network_constants,
+            stats,
+        }
+    }
+
+    /// Checks if the request should be dropped early on
+    pub fn verify(&self, request: &AscPullReq) -> bool {
+        let valid = is_valid_request(request);
+        if !valid {
+            self.stats.inc(
+                StatType::BootstrapServer,
+                DetailType::Invalid,
+                Direction::In,
+            );
+        }
+        valid
+    }
+
+    pub fn process(
+        &self,
+        txn: &dyn Transaction<Database = lmdb::Database, RoCursor = RoCursorWrapper>,
+        request: &AscPullReq,
+    ) -> AscPullAck {
+        let mut response = AscPullAck::new(&self.network_constants);
+        response.id = request.id;
+        match request.payload() {
+            AscPullReqPayload::Invalid => {
+                // Invalid payloads are filtered by `verify`, but return an empty response anyway
+                debug_assert!(false, "missing payload");
+                response.request_invalid();
+            }
+            AscPullReqPayload::Blocks(payload) => {
+                let blocks = self.prepare_blocks(txn, payload);
+                response.request_blocks(blocks).unwrap();
+            }
+            AscPullReqPayload::AccountInfo(payload) => {
+                let info = self.prepare_account_info(txn, payload);
+                response.request_account_info(info).unwrap();
+            }
+        }
+        self.record_response_stats(&response);
+        response
+    }
+
+    fn prepare_blocks(
+        &self,
+        txn: &dyn Transaction<Database = lmdb::Database, RoCursor = RoCursorWrapper>,
+        request: &BlocksReqPayload,
+    ) -> BlocksAckPayload {
+        let count = (request.count as usize).min(Self::MAX_BLOCKS);
+        let start_block = match request.start_type {
+            HashType::Block => {
+                let hash = BlockHash::from(request.start);
+                self.ledger.get_block(txn, &hash).map(|_| hash)
+            }
+            // Start from open block if pulling by account
+            HashType::Account => self
+                .ledger
+                .account_info(txn, &Account::from(request.start))
+                .map(|info| info.open_block),
+        };
+
+        // If neither block nor account is found, an empty response indicates that
+        let mut blocks = Vec::new();
+        let mut next = start_block;
+        while let Some(hash) = next {
+            if blocks.len() >= count {
+                break;
+            }
+            let Some(block) = self.ledger.get_block(txn, &hash) else {
+                break;
+            };
+            next = block.successor();
+            blocks.push(block);
+        }
+        BlocksAckPayload { blocks }
+    }
+
+    fn prepare_account_info(
+        &self,
+        txn: &dyn Transaction<Database = lmdb::Database, RoCursor = RoCursorWrapper>,
+        request: &AccountInfoReqPayload,
+    ) -> AccountInfoAckPayload {
+        let target = match request.target_type {
+            HashType::Account => Account::from(request.target),
+            // Try to lookup account assuming target is block hash
+            HashType::Block => self
+                .ledger
+                .account(txn, &BlockHash::from(request.target))
+                .unwrap_or_default(),
+        };
+
+        // If the account is missing, all fields except for the account are zero
+        let mut response = AccountInfoAckPayload {
+            account: target,
+            ..Default::default()
+        };
+
+        if let Some(info) = self.ledger.account_info(txn, &target) {
+            response.account_open = info.open_block;
+            response.account_head = info.head;
+            response.account_block_count = info.block_count;
+
+            if let Some(conf_info) = self.ledger.get_confirmation_height(txn, &target) {
+                response.account_conf_frontier = conf_info.frontier;
+                response.account_conf_height = conf_info.height;
+            }
+        }
+        response
+    }
+
+    fn record_response_stats(&self, response: &AscPullAck) {
+        self.stats.inc(
+            StatType::BootstrapServer,
+            DetailType::Response,
+            Direction::Out,
+        );
+
+        match response.payload() {
+            AscPullAckPayload::Invalid => {}
+            AscPullAckPayload::Blocks(payload) => {
+                self.stats.inc(
+                    StatType::BootstrapServer,
+                    DetailType::ResponseBlocks,
+                    Direction::Out,
+                );
+                self.stats.add(
+                    StatType::BootstrapServer,
+                    DetailType::Blocks,
+                    Direction::Out,
+                    payload.blocks.len() as u64,
+                    false,
+                );
+            }
+            AscPullAckPayload::AccountInfo(_) => {
+                self.stats.inc(
+                    StatType::BootstrapServer,
+                    DetailType::ResponseAccountInfo,
+                    Direction::Out,
+                );
+            }
+        }
+    }
+}
+
+fn is_valid_request(request: &AscPullReq) -> bool {
+    match request.payload() {
+        AscPullReqPayload::Invalid => false,
+        AscPullReqPayload::Blocks(payload) => {
+            payload.count > 0 && payload.count as usize <= BootstrapServer::MAX_BLOCKS
+        }
+        AscPullReqPayload::AccountInfo(payload) => !payload.target.is_zero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEV_NETWORK_PARAMS;
+    use rsnano_core::HashOrAccount;
+
+    #[test]
+    fn missing_payload_is_invalid() {
+        let request = AscPullReq::new(&DEV_NETWORK_PARAMS.network);
+        assert!(!is_valid_request(&request));
+    }
+
+    #[test]
+    fn blocks_request_count() {
+        let request = |count| {
+            let mut request = AscPullReq::new(&DEV_NETWORK_PARAMS.network);
+            request
+                .request_blocks(BlocksReqPayload {
+                    start: HashOrAccount::from(1),
+                    count,
+                    start_type: HashType::Account,
+                })
+                .unwrap();
+            request
+        };
+        assert!(!is_valid_request(&request(0)));
+        assert!(is_valid_request(&request(1)));
+        assert!(is_valid_request(&request(
+            BootstrapServer::MAX_BLOCKS as u8
+        )));
+        assert!(!is_valid_request(&request(
+            BootstrapServer::MAX_BLOCKS as u8 + 1
+        )));
+    }
+
+    #[test]
+    fn account_info_request_needs_target() {
+        let request = |target| {
+            let mut request = AscPullReq::new(&DEV_NETWORK_PARAMS.network);
+            request
+                .request_account_info(AccountInfoReqPayload {
+                    target,
+                    target_type: HashType::Account,
+                })
+                .unwrap();
+            request
+        };
+        assert!(!is_valid_request(&request(HashOrAccount::zero())));
+        assert!(is_valid_request(&request(HashOrAccount::from(1))));
+    }
+}
@@ -0,0 +1,5 @@
+mod account_sets;
+mod bootstrap_server;
+
+pub use account_sets::*;
+pub use bootstrap_server::BootstrapServer;
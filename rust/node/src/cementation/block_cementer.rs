@@ -9,7 +9,7 @@ use rsnano_store_lmdb::LmdbWriteTransaction;
 
 use super::{
     BatchWriteSizeManager, BlockCache, BlockCementerContainerInfo, BlockCementerLogic,
-    BlockCementerLogicOptions, CementCallbackRefs, FlushDecision, LedgerAdapter,
+    BlockCementerLogicOptions, BlockPrefetcher, CementCallbackRefs, FlushDecision, LedgerAdapter,
 };
 
 pub struct BlockCementer {
@@ -22,6 +22,7 @@ pub struct BlockCementer {
     enable_timing_logging: bool,
     ledger: Arc<Ledger>,
     logic: BlockCementerLogic,
+    prefetcher: BlockPrefetcher,
 }
 
 impl BlockCementer {
@@ -49,6 +50,7 @@ impl BlockCementer {
             processing_started: Instant::now(),
             write_txn_started: Instant::now(),
             logic,
+            prefetcher: BlockPrefetcher::new(),
         }
     }
 
@@ -116,15 +118,15 @@ impl BlockCementer {
 
     /// This only writes to the confirmation_height table and is the only place to do so in a single process
     fn flush(&mut self, mut write_guard: WriteGuard, callbacks: &mut CementCallbackRefs) {
+        self.prefetch_blocks();
         let mut txn = self.ledger.store.tx_begin_write();
         self.write_txn_started = Instant::now();
 
         // Cement all pending entries, each entry is specific to an account and contains the least amount
         // of blocks to retain consistent cementing across all account chains to genesis.
-        while let Some(section_to_cement) = self
-            .logic
-            .next_write(&mut LedgerAdapter::new_unlimited(&mut txn, &self.ledger))
-        {
+        while let Some(section_to_cement) = self.logic.next_write(
+            &mut LedgerAdapter::new_unlimited(&mut txn, &self.ledger, &mut self.prefetcher),
+        ) {
             self.ledger
                 .write_confirmation_height(&mut txn, &section_to_cement);
 
@@ -134,6 +136,19 @@ impl BlockCementer {
         }
 
         self.commit_batch(&mut txn, &mut write_guard, callbacks);
+        self.prefetcher.clear();
+    }
+
+    /// Loads the blocks of the next batch with a read transaction, walking each account chain once
+    fn prefetch_blocks(&mut self) {
+        let txn = self.ledger.read_txn();
+        let max_blocks = self.logic.batch_write_size().current_size_with_tolerance();
+        let ledger = &self.ledger;
+        self.prefetcher.prefetch(
+            self.logic.pending_sections(),
+            max_blocks,
+            &mut |section, max| ledger.chain_section_blocks(&txn, section, max),
+        );
     }
 
     fn start_new_batch(
@@ -166,6 +166,10 @@ impl BlockCementerLogic {
             || self.is_write_queue_full()
     }
 
+    pub fn pending_sections(&self) -> impl Iterator<Item = &BlockChainSection> {
+        self.write_batcher.pending_sections()
+    }
+
     pub fn next_write<T: LedgerDataRequester>(
         &mut self,
         data_requester: &mut T,
@@ -212,6 +216,7 @@ impl Default for BlockCementerLogic {
 #[cfg(test)]
 mod tests {
     use rsnano_core::{Amount, TestAccountChain};
+    use std::sync::{Arc, Mutex};
 
     use super::*;
     use crate::cementation::{CementCallbacks, LedgerDataRequesterStub};
@@ -441,6 +446,33 @@ mod tests {
         assert_eq!(next_write, None);
     }
 
+    #[test]
+    fn cemented_callback_is_called_in_height_order() {
+        let mut ledger_adapter = LedgerDataRequesterStub::new();
+        let mut genesis_chain = ledger_adapter.add_genesis_block();
+        genesis_chain.add_legacy_send();
+        genesis_chain.add_legacy_send();
+        genesis_chain.add_legacy_send();
+        ledger_adapter.add_uncemented(&genesis_chain);
+
+        let heights = Arc::new(Mutex::new(Vec::new()));
+        let heights_clone = Arc::clone(&heights);
+        let mut callbacks = CementCallbacks {
+            block_cemented: Box::new(move |block| {
+                heights_clone.lock().unwrap().push(block.height())
+            }),
+            ..Default::default()
+        };
+
+        let mut logic = BlockCementerLogic::new(test_options());
+        logic.set_current_block(genesis_chain.latest_block().clone());
+        assert!(logic.process_current_block(&mut ledger_adapter, &mut callbacks.as_refs()));
+        while logic.next_write(&mut ledger_adapter).is_some() {}
+        logic.batch_completed(Duration::ZERO, &mut callbacks.as_refs());
+
+        assert_eq!(*heights.lock().unwrap(), vec![2, 3, 4]);
+    }
+
     fn test_options() -> BlockCementerLogicOptions {
         BlockCementerLogicOptions {
             min_batch_separation: TEST_MIN_BATCH_SEPARATION,
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use rsnano_core::{BlockChainSection, BlockEnum, BlockHash};
+
+/// Loads the blocks of the pending writes into a scratch map before the write transaction
+/// is started, so that cementing doesn't have to do random block reads while it holds the write lock.
+pub(crate) struct BlockPrefetcher {
+    blocks: HashMap<BlockHash, BlockEnum>,
+}
+
+impl BlockPrefetcher {
+    pub fn new() -> Self {
+        Self {
+            blocks: HashMap::new(),
+        }
+    }
+
+    /// Loads at most `max_blocks` blocks of the sections. `load_section` is called once per
+    /// section and returns up to the given number of blocks from the bottom of the section upwards
+    pub fn prefetch<'a>(
+        &mut self,
+        sections: impl Iterator<Item = &'a BlockChainSection>,
+        max_blocks: usize,
+        load_section: &mut dyn FnMut(&BlockChainSection, usize) -> Vec<BlockEnum>,
+    ) {
+        for section in sections {
+            let remaining = max_blocks.saturating_sub(self.blocks.len());
+            if remaining == 0 {
+                break;
+            }
+            for block in load_section(section, remaining) {
+                self.blocks.insert(block.hash(), block);
+            }
+        }
+    }
+
+    pub fn take(&mut self, hash: &BlockHash) -> Option<BlockEnum> {
+        self.blocks.remove(hash)
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::{Account, TestAccountChain};
+
+    fn load_from(
+        chain: &TestAccountChain,
+    ) -> impl FnMut(&BlockChainSection, usize) -> Vec<BlockEnum> + '_ {
+        move |section, max| {
+            (section.bottom_height..=section.top_height)
+                .take(max)
+                .map(|height| chain.block(height).clone())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn prefetch_sections() {
+        let mut chain = TestAccountChain::new_opened_chain();
+        chain.add_state();
+        chain.add_state();
+        let mut prefetcher = BlockPrefetcher::new();
+
+        prefetcher.prefetch([chain.section(2, 3)].iter(), 100, &mut load_from(&chain));
+
+        assert_eq!(prefetcher.len(), 2);
+        assert_eq!(
+            prefetcher.take(&chain.block(2).hash()),
+            Some(chain.block(2).clone())
+        );
+        assert_eq!(prefetcher.take(&chain.block(2).hash()), None);
+        assert_eq!(prefetcher.take(&chain.block(1).hash()), None);
+    }
+
+    #[test]
+    fn stop_when_max_blocks_are_loaded() {
+        let mut chain = TestAccountChain::new_opened_chain();
+        chain.add_state();
+        chain.add_state();
+        let other_chain = TestAccountChain::new_opened_chain();
+        let mut prefetcher = BlockPrefetcher::new();
+        let mut loaded_accounts: Vec<Account> = Vec::new();
+
+        prefetcher.prefetch(
+            [chain.section(1, 3), other_chain.section(1, 1)].iter(),
+            2,
+            &mut |section, max| {
+                loaded_accounts.push(section.account);
+                load_from(&chain)(section, max)
+            },
+        );
+
+        assert_eq!(prefetcher.len(), 2);
+        assert_eq!(loaded_accounts, vec![chain.account()]);
+    }
+
+    #[test]
+    fn clear() {
+        let chain = TestAccountChain::new_opened_chain();
+        let mut prefetcher = BlockPrefetcher::new();
+        prefetcher.prefetch([chain.section(1, 1)].iter(), 100, &mut load_from(&chain));
+
+        prefetcher.clear();
+
+        assert_eq!(prefetcher.len(), 0);
+    }
+}
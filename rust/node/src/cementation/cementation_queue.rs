@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -8,12 +8,15 @@ use std::{
 
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
-    BlockChainSection,
+    Account, BlockChainSection,
 };
 
 pub(crate) struct CementationQueue {
     queue: VecDeque<BlockChainSection>,
     queue_len: Arc<AtomicUsize>,
+    /// The highest top height which was queued for an account and the number of queued
+    /// sections of the account. An account is removed when its last section is popped
+    queued_accounts: HashMap<Account, (u64, usize)>,
 }
 
 impl CementationQueue {
@@ -21,6 +24,7 @@ impl CementationQueue {
         Self {
             queue: VecDeque::new(),
             queue_len: Arc::new(AtomicUsize::new(0)),
+            queued_accounts: HashMap::new(),
         }
     }
 
@@ -32,7 +36,38 @@ impl CementationQueue {
         self.queue.is_empty()
     }
 
+    /// Sections which are already covered by a queued section of the same account are
+    /// ignored, because their blocks will be cemented by the earlier section. A section
+    /// which overlaps or continues the last queued section gets merged into it.
     pub fn push_back(&mut self, details: BlockChainSection) {
+        if let Some((top_height, _)) = self.queued_accounts.get(&details.account) {
+            if details.top_height <= *top_height {
+                return;
+            }
+        }
+
+        if let Some(last) = self.queue.back_mut() {
+            if last.account == details.account && details.bottom_height <= last.top_height + 1 {
+                if details.bottom_height < last.bottom_height {
+                    last.bottom_height = details.bottom_height;
+                    last.bottom_hash = details.bottom_hash;
+                }
+                last.top_height = details.top_height;
+                last.top_hash = details.top_hash;
+                self.queued_accounts
+                    .get_mut(&details.account)
+                    .expect("queued account missing")
+                    .0 = details.top_height;
+                return;
+            }
+        }
+
+        let entry = self
+            .queued_accounts
+            .entry(details.account)
+            .or_insert((0, 0));
+        entry.0 = entry.0.max(details.top_height);
+        entry.1 += 1;
         self.queue.push_back(details);
         self.queue_len.fetch_add(1, Ordering::Relaxed);
     }
@@ -43,12 +78,26 @@ impl CementationQueue {
 
     pub fn pop_front(&mut self) -> Option<BlockChainSection> {
         let item = self.queue.pop_front();
-        if item.is_some() {
+        if let Some(section) = &item {
             self.queue_len.fetch_sub(1, Ordering::Relaxed);
+            if let Some((_, count)) = self.queued_accounts.get_mut(&section.account) {
+                *count -= 1;
+                if *count == 0 {
+                    self.queued_accounts.remove(&section.account);
+                }
+            }
         }
         item
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &BlockChainSection> {
+        self.queue.iter()
+    }
+
+    pub fn accounts(&self) -> impl Iterator<Item = &Account> {
+        self.queue.iter().map(|i| &i.account)
+    }
+
     pub fn total_pending_blocks(&self) -> usize {
         self.queue
             .iter()
@@ -76,3 +125,90 @@ impl CementationQueueContainerInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::TestAccountChain;
+
+    fn chain_with_blocks(blocks: usize) -> TestAccountChain {
+        let mut chain = TestAccountChain::new_opened_chain();
+        for _ in 1..blocks {
+            chain.add_legacy_send();
+        }
+        chain
+    }
+
+    fn sections(queue: &CementationQueue) -> Vec<BlockChainSection> {
+        queue.iter().cloned().collect()
+    }
+
+    #[test]
+    fn merge_adjacent_sections_of_same_account() {
+        let chain = chain_with_blocks(4);
+        let mut queue = CementationQueue::new();
+        queue.push_back(chain.section(1, 2));
+        queue.push_back(chain.section(3, 4));
+
+        assert_eq!(sections(&queue), vec![chain.section(1, 4)]);
+        assert_eq!(queue.container_info().queue_len.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn merge_overlapping_sections() {
+        let chain = chain_with_blocks(4);
+        let mut queue = CementationQueue::new();
+        queue.push_back(chain.section(2, 3));
+        queue.push_back(chain.section(1, 4));
+
+        assert_eq!(sections(&queue), vec![chain.section(1, 4)]);
+    }
+
+    #[test]
+    fn ignore_section_which_is_already_queued() {
+        let chain = chain_with_blocks(4);
+        let other = chain_with_blocks(1);
+        let mut queue = CementationQueue::new();
+        queue.push_back(chain.section(1, 3));
+        queue.push_back(other.section(1, 1));
+        queue.push_back(chain.section(2, 3));
+
+        assert_eq!(
+            sections(&queue),
+            vec![chain.section(1, 3), other.section(1, 1)]
+        );
+    }
+
+    #[test]
+    fn dont_merge_sections_which_are_separated_by_other_accounts() {
+        let chain = chain_with_blocks(4);
+        let other = chain_with_blocks(1);
+        let mut queue = CementationQueue::new();
+        queue.push_back(chain.section(1, 2));
+        queue.push_back(other.section(1, 1));
+        queue.push_back(chain.section(3, 4));
+
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn dont_merge_sections_with_gap() {
+        let chain = chain_with_blocks(4);
+        let mut queue = CementationQueue::new();
+        queue.push_back(chain.section(1, 2));
+        queue.push_back(chain.section(4, 4));
+
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn enqueue_account_again_after_it_was_popped() {
+        let chain = chain_with_blocks(2);
+        let mut queue = CementationQueue::new();
+        queue.push_back(chain.section(1, 2));
+        queue.pop_front();
+        queue.push_back(chain.section(1, 2));
+
+        assert_eq!(queue.len(), 1);
+    }
+}
@@ -6,7 +6,6 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc, Condvar, Mutex, MutexGuard,
     },
-    thread::JoinHandle,
     time::Duration,
 };
 
@@ -16,6 +15,8 @@ use rsnano_core::{
 };
 use rsnano_ledger::{Ledger, WriteDatabaseQueue};
 
+use crate::utils::ThreadRunner;
+
 use super::{
     AwaitingProcessingCountCallback, BatchWriteSizeManager, BlockCache, BlockCallback,
     BlockCementer, BlockCementerContainerInfo, BlockHashCallback, BlockQueue,
@@ -30,8 +31,9 @@ pub struct CementationThread {
     // No mutex needed for the observers as these should be set up during initialization of the node
     cemented_observer: Arc<Mutex<Option<BlockCallback>>>,
     already_cemented_observer: Arc<Mutex<Option<BlockHashCallback>>>,
-    thread: Option<JoinHandle<()>>,
+    thread: ThreadRunner,
     block_cache: Arc<BlockCache>,
+    ledger: Arc<Ledger>,
 
     container_info: BlockCementerContainerInfo,
 }
@@ -52,7 +54,7 @@ impl CementationThread {
         let channel = Arc::new(Mutex::new(CementationLoopChannel::new()));
 
         let block_cementer = BlockCementer::new(
-            ledger,
+            ledger.clone(),
             write_database_queue,
             logger,
             enable_timing_logging,
@@ -74,14 +76,14 @@ impl CementationThread {
             awaiting_processing_count: awaiting_processing_count_callback(channel.clone()),
         };
 
-        let join_handle = {
+        let mut thread = ThreadRunner::new("Conf height");
+        {
             let stopped = stopped.clone();
             let condition = condition.clone();
             let channel = channel.clone();
 
-            std::thread::Builder::new()
-                .name("Conf height".to_owned())
-                .spawn(move || {
+            thread
+                .start(move || {
                     let mut processor_loop = CementationLoop {
                         stopped,
                         condition,
@@ -93,8 +95,8 @@ impl CementationThread {
                     latch.wait();
                     processor_loop.run();
                 })
-                .unwrap()
-        };
+                .unwrap();
+        }
 
         Self {
             channel,
@@ -103,8 +105,9 @@ impl CementationThread {
             stopped,
             cemented_observer,
             already_cemented_observer,
-            thread: Some(join_handle),
+            thread,
             block_cache,
+            ledger,
             container_info: bounded_container_info,
         }
     }
@@ -134,6 +137,22 @@ impl CementationThread {
         self.condition.notify_one();
     }
 
+    /// Loads the block from the ledger and queues it for cementing.
+    /// Returns false if the block is not in the ledger
+    pub fn add_hash(&self, hash: &BlockHash) -> bool {
+        let block = {
+            let txn = self.ledger.read_txn();
+            self.ledger.get_block(&txn, hash)
+        };
+        match block {
+            Some(block) => {
+                self.add(Arc::new(block));
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn current(&self) -> BlockHash {
         let lk = self.channel.lock().unwrap();
         match &lk.current_block {
@@ -174,9 +193,7 @@ impl CementationThread {
             self.stopped.store(true, Ordering::SeqCst);
         }
         self.condition.notify_one();
-        if let Some(handle) = self.thread.take() {
-            handle.join().unwrap();
-        }
+        self.thread.join().unwrap();
     }
 
     pub fn collect_container_info(&self, name: String) -> ContainerInfoComponent {
@@ -359,3 +376,52 @@ pub struct CementCallbackRefs<'a> {
     pub block_already_cemented: &'a mut dyn FnMut(BlockHash),
     pub awaiting_processing_count: &'a mut dyn FnMut() -> u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::utils::{NullLatch, NullLogger};
+    use rsnano_ledger::LedgerConstants;
+    use rsnano_store_lmdb::{EnvironmentWrapper, LmdbStore, TestDbFile};
+
+    #[test]
+    fn add_hash_queues_block_from_ledger() {
+        let db_file = TestDbFile::random();
+        let thread = create_thread(&db_file);
+        let genesis_hash = thread.ledger.constants.genesis.read().unwrap().hash();
+        thread.pause();
+
+        assert!(thread.add_hash(&genesis_hash));
+
+        assert_eq!(thread.awaiting_processing_len(), 1);
+        assert!(thread.is_processing_added_block(&genesis_hash));
+    }
+
+    #[test]
+    fn add_hash_of_unknown_block() {
+        let db_file = TestDbFile::random();
+        let thread = create_thread(&db_file);
+        thread.pause();
+
+        assert!(!thread.add_hash(&BlockHash::from(42)));
+
+        assert_eq!(thread.awaiting_processing_len(), 0);
+    }
+
+    fn create_thread(db_file: &TestDbFile) -> CementationThread {
+        let store = Arc::new(
+            LmdbStore::<EnvironmentWrapper>::open(&db_file.path)
+                .build()
+                .unwrap(),
+        );
+        let ledger = Arc::new(Ledger::new(store, LedgerConstants::unit_test()).unwrap());
+        CementationThread::new(
+            Arc::new(WriteDatabaseQueue::new(false)),
+            Arc::new(NullLogger::new()),
+            false,
+            ledger,
+            Duration::from_millis(50),
+            Box::new(NullLatch::new()),
+        )
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use rsnano_core::Account;
+
+/// Delivers cemented block notifications of an account in cementing order,
+/// even if they are prepared concurrently (for example in the worker pool of the http callback).
+/// A sequence number has to be taken with `next_sequence` in the cementing thread.
+/// Notifications which are pushed before their predecessors are buffered until the gap is closed.
+pub struct CementedNotificationBuffer<T> {
+    accounts: Mutex<HashMap<Account, AccountSequence<T>>>,
+}
+
+struct AccountSequence<T> {
+    next_assigned: u64,
+    next_delivered: u64,
+    buffered: BTreeMap<u64, T>,
+}
+
+impl<T> CementedNotificationBuffer<T> {
+    pub fn new() -> Self {
+        Self {
+            accounts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Has to be called in cementing order
+    pub fn next_sequence(&self, account: &Account) -> u64 {
+        let mut accounts = self.accounts.lock().unwrap();
+        let entry = accounts.entry(*account).or_insert_with(|| AccountSequence {
+            next_assigned: 0,
+            next_delivered: 0,
+            buffered: BTreeMap::new(),
+        });
+        let sequence = entry.next_assigned;
+        entry.next_assigned += 1;
+        sequence
+    }
+
+    /// Buffers the notification and delivers all notifications of the account which are ready.
+    /// `deliver` is called while the buffer is locked, so that no other thread can overtake the delivery.
+    pub fn push(&self, account: &Account, sequence: u64, item: T, deliver: &mut dyn FnMut(T)) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let Some(entry) = accounts.get_mut(account) else {
+            // No sequence number was taken for this account
+            deliver(item);
+            return;
+        };
+        entry.buffered.insert(sequence, item);
+        while let Some(item) = entry.buffered.remove(&entry.next_delivered) {
+            deliver(item);
+            entry.next_delivered += 1;
+        }
+        if entry.next_delivered == entry.next_assigned {
+            accounts.remove(account);
+        }
+    }
+
+    /// Number of notifications waiting for their predecessors
+    pub fn buffered_len(&self) -> usize {
+        self.accounts
+            .lock()
+            .unwrap()
+            .values()
+            .map(|i| i.buffered.len())
+            .sum()
+    }
+
+    /// Number of accounts with notifications in flight
+    pub fn accounts_len(&self) -> usize {
+        self.accounts.lock().unwrap().len()
+    }
+}
+
+impl<T> Default for CementedNotificationBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, thread};
+
+    #[test]
+    fn deliver_in_order() {
+        let buffer = CementedNotificationBuffer::new();
+        let account = Account::from(1);
+        let mut delivered = Vec::new();
+        let first = buffer.next_sequence(&account);
+        let second = buffer.next_sequence(&account);
+
+        buffer.push(&account, first, "a", &mut |i| delivered.push(i));
+        buffer.push(&account, second, "b", &mut |i| delivered.push(i));
+
+        assert_eq!(delivered, vec!["a", "b"]);
+        assert_eq!(buffer.accounts_len(), 0);
+    }
+
+    #[test]
+    fn buffer_until_predecessor_is_pushed() {
+        let buffer = CementedNotificationBuffer::new();
+        let account = Account::from(1);
+        let mut delivered = Vec::new();
+        let first = buffer.next_sequence(&account);
+        let second = buffer.next_sequence(&account);
+        let third = buffer.next_sequence(&account);
+
+        buffer.push(&account, third, "c", &mut |i| delivered.push(i));
+        buffer.push(&account, second, "b", &mut |i| delivered.push(i));
+        assert!(delivered.is_empty());
+        assert_eq!(buffer.buffered_len(), 2);
+
+        buffer.push(&account, first, "a", &mut |i| delivered.push(i));
+        assert_eq!(delivered, vec!["a", "b", "c"]);
+        assert_eq!(buffer.buffered_len(), 0);
+        assert_eq!(buffer.accounts_len(), 0);
+    }
+
+    #[test]
+    fn accounts_are_independent() {
+        let buffer = CementedNotificationBuffer::new();
+        let account1 = Account::from(1);
+        let account2 = Account::from(2);
+        let mut delivered = Vec::new();
+        let first1 = buffer.next_sequence(&account1);
+        let _second1 = buffer.next_sequence(&account1);
+        let first2 = buffer.next_sequence(&account2);
+
+        buffer.push(&account2, first2, "x", &mut |i| delivered.push(i));
+        buffer.push(&account1, first1, "a", &mut |i| delivered.push(i));
+
+        assert_eq!(delivered, vec!["x", "a"]);
+        assert_eq!(buffer.accounts_len(), 1);
+    }
+
+    #[test]
+    fn concurrent_producers_never_deliver_out_of_order() {
+        let buffer = Arc::new(CementedNotificationBuffer::new());
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let account = Account::from(1);
+        let sequences: Vec<u64> = (0..100).map(|_| buffer.next_sequence(&account)).collect();
+
+        let handles: Vec<_> = sequences
+            .chunks(10)
+            .rev()
+            .map(|chunk| {
+                let chunk = chunk.to_vec();
+                let buffer = Arc::clone(&buffer);
+                let delivered = Arc::clone(&delivered);
+                thread::spawn(move || {
+                    for sequence in chunk.into_iter().rev() {
+                        buffer.push(&account, sequence, sequence, &mut |i| {
+                            delivered.lock().unwrap().push(i)
+                        });
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*delivered.lock().unwrap(), sequences);
+    }
+}
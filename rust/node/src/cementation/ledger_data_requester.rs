@@ -5,7 +5,6 @@ use rsnano_core::TestAccountChain;
 use rsnano_store_lmdb::RoCursorWrapper;
 use rsnano_store_lmdb::Transaction;
 use std::cell::Cell;
-#[cfg(test)]
 use std::collections::HashMap;
 #[cfg(test)]
 use std::collections::HashSet;
@@ -13,10 +12,18 @@ use std::collections::HashSet;
 use rsnano_core::{Account, AccountInfo, BlockEnum, BlockHash, ConfirmationHeightInfo};
 use rsnano_ledger::Ledger;
 
+use super::BlockPrefetcher;
+
 pub trait LedgerDataRequester {
     fn get_block(&mut self, block_hash: &BlockHash) -> Option<BlockEnum>;
     fn was_block_pruned(&self, block_hash: &BlockHash) -> bool;
     fn get_confirmation_height(&self, account: &Account) -> Option<ConfirmationHeightInfo>;
+    /// Loads the confirmation heights of many accounts at once.
+    /// Accounts without a confirmation height are missing in the result.
+    fn get_confirmation_heights(
+        &self,
+        accounts: &[Account],
+    ) -> HashMap<Account, ConfirmationHeightInfo>;
     fn get_account_info(&self, account: &Account) -> Option<AccountInfo>;
 }
 
@@ -28,6 +35,7 @@ pub(crate) struct LedgerAdapter<'a> {
     ledger: &'a Ledger,
     block_read_count: Cell<usize>,
     max_block_read_count: usize,
+    prefetched: Option<&'a mut BlockPrefetcher>,
 }
 
 impl<'a> LedgerAdapter<'a> {
@@ -40,18 +48,22 @@ impl<'a> LedgerAdapter<'a> {
             ledger,
             block_read_count: Cell::new(0),
             max_block_read_count: BATCH_READ_SIZE,
+            prefetched: None,
         }
     }
 
+    /// Blocks are taken from `prefetched` if possible
     pub(crate) fn new_unlimited(
         txn: &'a mut dyn Transaction<Database = lmdb::Database, RoCursor = RoCursorWrapper>,
         ledger: &'a Ledger,
+        prefetched: &'a mut BlockPrefetcher,
     ) -> Self {
         Self {
             txn,
             ledger,
             block_read_count: Cell::new(0),
             max_block_read_count: usize::MAX,
+            prefetched: Some(prefetched),
         }
     }
 
@@ -62,6 +74,14 @@ impl<'a> LedgerAdapter<'a> {
 
 impl<'a> LedgerDataRequester for LedgerAdapter<'a> {
     fn get_block(&mut self, block_hash: &BlockHash) -> Option<BlockEnum> {
+        if let Some(block) = self
+            .prefetched
+            .as_mut()
+            .and_then(|prefetched| prefetched.take(block_hash))
+        {
+            return Some(block);
+        }
+
         let read_count = self.block_read_count.get();
         if read_count > 0 && read_count % self.max_block_read_count == 0 {
             // We could be traversing a very large account so we don't want to open read transactions for too long.
@@ -76,6 +96,16 @@ impl<'a> LedgerDataRequester for LedgerAdapter<'a> {
         self.ledger.store.confirmation_height.get(self.txn, account)
     }
 
+    fn get_confirmation_heights(
+        &self,
+        accounts: &[Account],
+    ) -> HashMap<Account, ConfirmationHeightInfo> {
+        self.ledger
+            .store
+            .confirmation_height
+            .get_many(self.txn, accounts)
+    }
+
     fn was_block_pruned(&self, block_hash: &BlockHash) -> bool {
         self.ledger.pruning_enabled() && self.ledger.store.pruned.exists(self.txn, block_hash)
     }
@@ -93,6 +123,7 @@ pub(crate) struct LedgerDataRequesterStub {
     pruned: HashSet<BlockHash>,
     blocks_loaded: Cell<usize>,
     confirmation_heights_loaded: Cell<usize>,
+    confirmation_height_batches_loaded: Cell<usize>,
 }
 
 #[cfg(test)]
@@ -105,6 +136,7 @@ impl LedgerDataRequesterStub {
             pruned: HashSet::new(),
             blocks_loaded: Cell::new(0),
             confirmation_heights_loaded: Cell::new(0),
+            confirmation_height_batches_loaded: Cell::new(0),
         }
     }
 
@@ -182,6 +214,10 @@ impl LedgerDataRequesterStub {
     pub fn confirmation_heights_loaded(&self) -> usize {
         self.confirmation_heights_loaded.get()
     }
+
+    pub fn confirmation_height_batches_loaded(&self) -> usize {
+        self.confirmation_height_batches_loaded.get()
+    }
 }
 
 #[cfg(test)]
@@ -201,6 +237,22 @@ impl LedgerDataRequester for LedgerDataRequesterStub {
         self.confirmation_heights.get(account).cloned()
     }
 
+    fn get_confirmation_heights(
+        &self,
+        accounts: &[Account],
+    ) -> HashMap<Account, ConfirmationHeightInfo> {
+        self.confirmation_height_batches_loaded
+            .set(self.confirmation_height_batches_loaded.get() + 1);
+        accounts
+            .iter()
+            .filter_map(|account| {
+                self.confirmation_heights
+                    .get(account)
+                    .map(|info| (*account, info.clone()))
+            })
+            .collect()
+    }
+
     fn get_account_info(&self, account: &Account) -> Option<AccountInfo> {
         self.account_infos.get(account).cloned()
     }
@@ -3,10 +3,12 @@ mod batch_write_size_manager;
 mod block_cache;
 mod block_cementer;
 mod block_cementer_logic;
+mod block_prefetcher;
 mod block_queue;
 mod cementation_queue;
 mod cementation_thread;
 mod cementation_walker;
+mod cemented_notification_buffer;
 mod ledger_data_requester;
 mod write_batcher;
 
@@ -20,6 +22,7 @@ use accounts_confirmed_map::{
 };
 use batch_write_size_manager::BatchWriteSizeManager;
 pub use block_cementer::BlockCementer;
+pub use cemented_notification_buffer::CementedNotificationBuffer;
 use ledger_data_requester::{LedgerAdapter, LedgerDataRequester};
 
 #[cfg(test)]
@@ -29,6 +32,7 @@ use block_cache::BlockCache;
 use block_cementer_logic::{
     BlockCementerContainerInfo, BlockCementerLogic, BlockCementerLogicOptions, FlushDecision,
 };
+use block_prefetcher::BlockPrefetcher;
 use cementation_queue::{CementationQueue, CementationQueueContainerInfo};
 pub use cementation_thread::{CementCallbackRefs, CementCallbacks, CementationThread};
 use cementation_walker::CementationWalker;
@@ -1,10 +1,10 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use super::{
     batch_write_size_manager::BatchWriteSizeManagerOptions, BatchWriteSizeManager,
     CementationQueue, CementationQueueContainerInfo, LedgerDataRequester,
 };
-use rsnano_core::{BlockChainSection, BlockEnum, BlockHash, ConfirmationHeightInfo};
+use rsnano_core::{Account, BlockChainSection, BlockEnum, BlockHash, ConfirmationHeightInfo};
 
 #[derive(Clone)]
 pub(crate) struct WriteBatcherOptions {
@@ -33,6 +33,10 @@ pub(crate) struct WriteBatcher {
     cemented_blocks: Vec<Arc<BlockEnum>>,
     current: Option<BlockChainSection>,
     pending_writes: CementationQueue,
+    /// Confirmation heights of the pending writes, which were loaded in one go.
+    /// An entry is removed as soon as its section gets cemented, because the
+    /// confirmation height changes afterwards.
+    prefetched_confirmation_heights: HashMap<Account, Option<ConfirmationHeightInfo>>,
     pub batch_write_size: Arc<BatchWriteSizeManager>,
     max_pending_writes: usize,
 
@@ -63,6 +67,7 @@ impl WriteBatcher {
             cemented_blocks: Vec::new(),
             current: None,
             pending_writes: CementationQueue::new(),
+            prefetched_confirmation_heights: HashMap::new(),
             batch_write_size: Arc::new(BatchWriteSizeManager::new(BatchWriteSizeManagerOptions {
                 min_size: options.min_batch_size,
             })),
@@ -94,6 +99,16 @@ impl WriteBatcher {
         !self.pending_writes.is_empty()
     }
 
+    /// The section which is currently cemented, followed by the pending writes
+    pub fn pending_sections(&self) -> impl Iterator<Item = &BlockChainSection> {
+        let current = if self.is_current_account_done() {
+            None
+        } else {
+            self.current.as_ref()
+        };
+        current.into_iter().chain(self.pending_writes.iter())
+    }
+
     pub fn enqueue(&mut self, write_details: BlockChainSection) {
         self.pending_writes.push_back(write_details);
     }
@@ -121,8 +136,8 @@ impl WriteBatcher {
         data_requester: &T,
         pending: BlockChainSection,
     ) {
-        let confirmation_height_info = data_requester
-            .get_confirmation_height(&pending.account)
+        let confirmation_height_info = self
+            .take_confirmation_height(data_requester, &pending.account)
             .unwrap_or_default();
 
         self.section_to_cement = pending;
@@ -136,6 +151,43 @@ impl WriteBatcher {
         self.new_cemented_frontier_block = None;
     }
 
+    fn take_confirmation_height<T: LedgerDataRequester>(
+        &mut self,
+        data_requester: &T,
+        account: &Account,
+    ) -> Option<ConfirmationHeightInfo> {
+        if !self.prefetched_confirmation_heights.contains_key(account) {
+            self.prefetch_confirmation_heights(data_requester, account);
+        }
+
+        self.prefetched_confirmation_heights
+            .remove(account)
+            .flatten()
+    }
+
+    /// Loads the confirmation heights of the given account and of all pending writes
+    /// which were not prefetched yet
+    fn prefetch_confirmation_heights<T: LedgerDataRequester>(
+        &mut self,
+        data_requester: &T,
+        account: &Account,
+    ) {
+        let mut accounts: Vec<Account> = self
+            .pending_writes
+            .accounts()
+            .filter(|a| !self.prefetched_confirmation_heights.contains_key(a))
+            .cloned()
+            .collect();
+        accounts.push(*account);
+        let mut loaded = data_requester.get_confirmation_heights(&accounts);
+        for account in accounts {
+            let info = loaded.remove(&account);
+            self.prefetched_confirmation_heights
+                .entry(account)
+                .or_insert(info);
+        }
+    }
+
     pub(crate) fn is_done(&self) -> bool {
         self.is_current_account_done() && self.pending_writes.is_empty()
     }
@@ -446,6 +498,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prefetch_confirmation_heights_of_all_pending_writes() {
+        let mut data_requester = LedgerDataRequesterStub::new();
+        let mut dest_chain = TestAccountChain::new();
+        let mut genesis_chain = data_requester.add_genesis_block();
+        genesis_chain.add_legacy_send_to(dest_chain.account(), Amount::raw(1));
+        data_requester.add_uncemented(&genesis_chain);
+        dest_chain.add_legacy_open_from_account(&genesis_chain);
+        data_requester.add_uncemented(&dest_chain);
+
+        let sections = [genesis_chain.section(2, 2), dest_chain.section(1, 1)];
+
+        assert_writes(
+            Default::default(),
+            &mut data_requester,
+            &sections,
+            &sections,
+        );
+        assert_eq!(data_requester.confirmation_height_batches_loaded(), 1);
+        assert_eq!(data_requester.confirmation_heights_loaded(), 0);
+    }
+
+    #[test]
+    fn reload_confirmation_height_if_account_is_enqueued_twice() {
+        let mut data_requester = LedgerDataRequesterStub::new();
+        let mut dest_chain = TestAccountChain::new();
+        let mut genesis_chain = data_requester.add_genesis_block();
+        genesis_chain.add_legacy_send_to(dest_chain.account(), Amount::raw(1));
+        dest_chain.add_legacy_open_from_account(&genesis_chain);
+        genesis_chain.add_legacy_send();
+        data_requester.add_uncemented(&genesis_chain);
+        data_requester.add_uncemented(&dest_chain);
+
+        let mut write_batcher = WriteBatcher::default();
+        write_batcher.enqueue(genesis_chain.section(2, 2));
+        write_batcher.enqueue(dest_chain.section(1, 1));
+        write_batcher.enqueue(genesis_chain.section(3, 3));
+
+        let write = write_batcher.next_write(&mut data_requester);
+        assert_eq!(write, Some(genesis_chain.section(2, 2)));
+        data_requester.cement(genesis_chain.block(2));
+
+        let write = write_batcher.next_write(&mut data_requester);
+        assert_eq!(write, Some(dest_chain.section(1, 1)));
+        data_requester.cement(dest_chain.block(1));
+
+        let write = write_batcher.next_write(&mut data_requester);
+        assert_eq!(write, Some(genesis_chain.section(3, 3)));
+        assert_eq!(data_requester.confirmation_height_batches_loaded(), 2);
+    }
+
+    #[test]
+    fn merge_sections_of_same_account() {
+        let mut data_requester = LedgerDataRequesterStub::new();
+        let mut genesis_chain = data_requester.add_genesis_block();
+        genesis_chain.add_legacy_send();
+        genesis_chain.add_legacy_send();
+        data_requester.add_uncemented(&genesis_chain);
+
+        let sections = [
+            genesis_chain.section(2, 2),
+            genesis_chain.section(2, 3),
+            genesis_chain.section(3, 3),
+        ];
+        let expected = [genesis_chain.section(2, 3)];
+
+        assert_writes(
+            Default::default(),
+            &mut data_requester,
+            &sections,
+            &expected,
+        );
+        assert_eq!(data_requester.confirmation_height_batches_loaded(), 1);
+    }
+
     fn assert_writes(
         options: WriteBatcherOptions,
         data_requester: &mut LedgerDataRequesterStub,
@@ -0,0 +1,265 @@
+use std::fmt::Display;
+
+use super::{DaemonConfig, NodeConfig};
+
+/// Bandwidth limits below this value are probably a typo (kilobytes instead of bytes)
+const MIN_BANDWIDTH_LIMIT: usize = 64 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigIssueKind {
+    /// The node can't run with this configuration
+    Error,
+    /// The node can run, but probably not the way it was intended
+    Warning,
+}
+
+/// A problem in the configuration which is reported at startup
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigIssue {
+    pub kind: ConfigIssueKind,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            kind: ConfigIssueKind::Error,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            kind: ConfigIssueKind::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.kind == ConfigIssueKind::Error
+    }
+}
+
+impl Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ConfigIssueKind::Error => write!(f, "Config error: {}", self.message),
+            ConfigIssueKind::Warning => write!(f, "Config warning: {}", self.message),
+        }
+    }
+}
+
+impl DaemonConfig {
+    /// Checks constraints between config values, which would otherwise only fail
+    /// when the subsystems are constructed. The node must not be started if one
+    /// of the returned issues is an error.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        validate_threads(&self.node, &mut issues);
+        validate_ports(&self.node, &mut issues);
+        validate_bandwidth(
+            ("node.bandwidth_limit", self.node.bandwidth_limit),
+            (
+                "node.bandwidth_limit_burst_ratio",
+                self.node.bandwidth_limit_burst_ratio,
+            ),
+            &mut issues,
+        );
+        validate_bandwidth(
+            (
+                "node.bootstrap_bandwidth_limit",
+                self.node.bootstrap_bandwidth_limit,
+            ),
+            (
+                "node.bootstrap_bandwidth_burst_ratio",
+                self.node.bootstrap_bandwidth_burst_ratio,
+            ),
+            &mut issues,
+        );
+        issues
+    }
+}
+
+impl NodeConfig {
+    /// Voting needs the representative keys of the wallets, so this can only be checked
+    /// after the wallets were loaded
+    pub fn validate_voting(&self, voting_representatives: usize) -> Option<ConfigIssue> {
+        if self.enable_voting && voting_representatives == 0 {
+            Some(ConfigIssue::warning(
+                "node.enable_voting is set, but no wallet contains a representative with enough weight to vote. Add the representative key to a wallet or disable voting",
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn validate_threads(config: &NodeConfig, issues: &mut Vec<ConfigIssue>) {
+    let threads = [
+        ("node.io_threads", config.io_threads),
+        ("node.network_threads", config.network_threads),
+    ];
+    for (key, count) in threads {
+        if count == 0 {
+            issues.push(ConfigIssue::error(format!(
+                "{} is 0, it must be at least 1",
+                key
+            )));
+        }
+    }
+}
+
+fn validate_ports(config: &NodeConfig, issues: &mut Vec<ConfigIssue>) {
+    let mut ports = Vec::new();
+    if let Some(port) = config.peering_port {
+        // Port 0 lets the OS choose a free port
+        if port != 0 {
+            ports.push(("node.peering_port", port));
+        }
+    }
+    if config.websocket_config.enabled {
+        ports.push(("node.websocket.port", config.websocket_config.port));
+    }
+    if config.ipc_config.transport_tcp.transport.enabled {
+        ports.push(("node.ipc.tcp.port", config.ipc_config.transport_tcp.port));
+    }
+
+    for (i, (key_a, port_a)) in ports.iter().enumerate() {
+        for (key_b, port_b) in &ports[i + 1..] {
+            if port_a == port_b {
+                issues.push(ConfigIssue::error(format!(
+                    "{} and {} both use port {}, choose different ports",
+                    key_a, key_b, port_a
+                )));
+            }
+        }
+    }
+}
+
+fn validate_bandwidth(
+    (limit_key, limit): (&str, usize),
+    (ratio_key, burst_ratio): (&str, f64),
+    issues: &mut Vec<ConfigIssue>,
+) {
+    if burst_ratio < 1.0 {
+        issues.push(ConfigIssue::error(format!(
+            "{} is {}, it must be at least 1",
+            ratio_key, burst_ratio
+        )));
+    }
+    // 0 means unlimited
+    if limit != 0 && limit < MIN_BANDWIDTH_LIMIT {
+        issues.push(ConfigIssue::warning(format!(
+            "{} is only {} bytes/s, the node will drop most messages. Use 0 for unlimited bandwidth or a limit of at least {} bytes/s",
+            limit_key, limit, MIN_BANDWIDTH_LIMIT
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEV_NETWORK_PARAMS;
+
+    fn default_config() -> DaemonConfig {
+        let mut config = DaemonConfig::new(&DEV_NETWORK_PARAMS).unwrap();
+        config.node.peering_port = Some(7075);
+        config
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(default_config().validate(), Vec::new());
+    }
+
+    #[test]
+    fn io_threads_must_not_be_zero() {
+        let mut config = default_config();
+        config.node.io_threads = 0;
+        assert_eq!(
+            config.validate(),
+            vec![ConfigIssue::error(
+                "node.io_threads is 0, it must be at least 1"
+            )]
+        );
+    }
+
+    #[test]
+    fn ports_must_not_collide() {
+        let mut config = default_config();
+        config.node.websocket_config.enabled = true;
+        config.node.websocket_config.port = 7075;
+        config.node.ipc_config.transport_tcp.transport.enabled = true;
+        config.node.ipc_config.transport_tcp.port = 7075;
+
+        let issues = config.validate();
+
+        assert_eq!(issues.len(), 3);
+        assert!(issues.iter().all(|i| i.is_error()));
+        assert_eq!(
+            issues[0].message,
+            "node.peering_port and node.websocket.port both use port 7075, choose different ports"
+        );
+    }
+
+    #[test]
+    fn disabled_servers_dont_collide() {
+        let mut config = default_config();
+        config.node.websocket_config.port = 7075;
+        config.node.ipc_config.transport_tcp.port = 7075;
+        assert_eq!(config.validate(), Vec::new());
+    }
+
+    #[test]
+    fn burst_ratio_below_one_is_an_error() {
+        let mut config = default_config();
+        config.node.bootstrap_bandwidth_burst_ratio = 0.5;
+        assert_eq!(
+            config.validate(),
+            vec![ConfigIssue::error(
+                "node.bootstrap_bandwidth_burst_ratio is 0.5, it must be at least 1"
+            )]
+        );
+    }
+
+    #[test]
+    fn name_the_burst_ratio_key() {
+        let mut config = default_config();
+        config.node.bandwidth_limit_burst_ratio = 0.0;
+        assert_eq!(
+            config.validate()[0].message,
+            "node.bandwidth_limit_burst_ratio is 0, it must be at least 1"
+        );
+    }
+
+    #[test]
+    fn warn_about_very_low_bandwidth_limit() {
+        let mut config = default_config();
+        config.node.bandwidth_limit = 1024;
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ConfigIssueKind::Warning);
+
+        config.node.bandwidth_limit = 0;
+        assert_eq!(config.validate(), Vec::new());
+    }
+
+    #[test]
+    fn voting_requires_a_representative_in_a_wallet() {
+        let mut config = default_config();
+        config.node.enable_voting = true;
+        assert!(config.node.validate_voting(0).is_some());
+        assert_eq!(config.node.validate_voting(1), None);
+
+        config.node.enable_voting = false;
+        assert_eq!(config.node.validate_voting(0), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ConfigIssue::warning("foo").to_string(),
+            "Config warning: foo"
+        );
+    }
+}
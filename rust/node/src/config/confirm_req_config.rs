@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use rsnano_core::utils::TomlWriter;
+
+use super::NetworkConstants;
+
+/// Controls how often an election asks the representatives for votes with confirm_req messages
+#[derive(Clone)]
+pub struct ConfirmReqConfig {
+    /// Minimum time between the first two confirm_req rounds of an election
+    pub base_interval_ms: u64,
+
+    /// Factor by which the interval grows after every confirm_req round. 1.0 disables the backoff
+    pub backoff_multiplier: f64,
+
+    /// Upper bound for the interval between two confirm_req rounds
+    pub max_interval_ms: u64,
+
+    /// Maximum number of confirm_req rounds per election. 0 means unlimited
+    pub max_requests: u32,
+}
+
+impl ConfirmReqConfig {
+    pub fn new(network: &NetworkConstants) -> Self {
+        Self {
+            base_interval_ms: if network.is_dev_network() { 125 } else { 5000 },
+            backoff_multiplier: 1.0,
+            max_interval_ms: 60 * 1000,
+            max_requests: 0,
+        }
+    }
+
+    /// Time to wait after the previous confirm_req round when `rounds` rounds were sent already
+    pub fn request_interval(&self, rounds: u32) -> Duration {
+        let base = self.base_interval_ms as f64;
+        let multiplier = self.backoff_multiplier.max(1.0);
+        let interval = base * multiplier.powi(rounds.min(i32::MAX as u32) as i32);
+        let max = self.max_interval_ms.max(self.base_interval_ms) as f64;
+        Duration::from_millis(interval.min(max) as u64)
+    }
+
+    pub fn may_request(&self, rounds: u32) -> bool {
+        self.max_requests == 0 || rounds < self.max_requests
+    }
+
+    pub(crate) fn serialize_toml(&self, toml: &mut dyn TomlWriter) -> anyhow::Result<()> {
+        toml.put_u64(
+            "base_interval",
+            self.base_interval_ms,
+            "Minimum time between the first two confirmation request rounds of an election. In milliseconds\ntype:milliseconds",
+        )?;
+        toml.put_f64(
+            "backoff_multiplier",
+            self.backoff_multiplier,
+            "Factor by which the interval between confirmation request rounds grows after every round. 1.0 disables the backoff\ntype:double,[1..]",
+        )?;
+        toml.put_u64(
+            "max_interval",
+            self.max_interval_ms,
+            "Upper bound for the interval between two confirmation request rounds. In milliseconds\ntype:milliseconds",
+        )?;
+        toml.put_u32(
+            "max_requests",
+            self.max_requests,
+            "Maximum number of confirmation request rounds per election. 0 means unlimited\ntype:uint32",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> ConfirmReqConfig {
+        ConfirmReqConfig {
+            base_interval_ms: 1000,
+            backoff_multiplier: 2.0,
+            max_interval_ms: 5000,
+            max_requests: 3,
+        }
+    }
+
+    #[test]
+    fn constant_interval_by_default() {
+        let config = ConfirmReqConfig::new(&NetworkConstants::empty());
+        assert_eq!(config.request_interval(0), Duration::from_secs(5));
+        assert_eq!(config.request_interval(100), Duration::from_secs(5));
+        assert!(config.may_request(u32::MAX - 1));
+    }
+
+    #[test]
+    fn backoff() {
+        let config = test_config();
+        assert_eq!(config.request_interval(0), Duration::from_millis(1000));
+        assert_eq!(config.request_interval(1), Duration::from_millis(2000));
+        assert_eq!(config.request_interval(2), Duration::from_millis(4000));
+        assert_eq!(config.request_interval(3), Duration::from_millis(5000));
+        assert_eq!(
+            config.request_interval(u32::MAX),
+            Duration::from_millis(5000)
+        );
+    }
+
+    #[test]
+    fn multiplier_below_one_doesnt_shrink_the_interval() {
+        let config = ConfirmReqConfig {
+            backoff_multiplier: 0.5,
+            ..test_config()
+        };
+        assert_eq!(config.request_interval(3), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn max_requests() {
+        let config = test_config();
+        assert!(config.may_request(0));
+        assert!(config.may_request(2));
+        assert!(!config.may_request(3));
+    }
+}
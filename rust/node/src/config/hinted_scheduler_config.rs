@@ -0,0 +1,42 @@
+use rsnano_core::utils::TomlWriter;
+
+use super::NetworkConstants;
+
+pub struct HintedSchedulerConfig {
+    pub enabled: bool,
+
+    /// Interval of wakeup to check the vote cache for candidates
+    pub check_interval_ms: u64,
+
+    /// Percentage of the quorum delta which the tally of a vote cache entry has to reach
+    /// for a hinted election to be started
+    pub hinting_threshold_percent: u32,
+}
+
+impl HintedSchedulerConfig {
+    pub fn new(network: &NetworkConstants) -> Self {
+        Self {
+            enabled: true,
+            check_interval_ms: if network.is_dev_network() { 100 } else { 1000 },
+            hinting_threshold_percent: 10,
+        }
+    }
+
+    pub(crate) fn serialize_toml(&self, toml: &mut dyn TomlWriter) -> anyhow::Result<()> {
+        toml.put_bool(
+            "enable",
+            self.enabled,
+            "Enable or disable hinted elections\ntype:bool",
+        )?;
+        toml.put_u64(
+            "check_interval",
+            self.check_interval_ms,
+            "Interval between scans of the vote cache for possible hinted elections. In milliseconds\ntype:milliseconds",
+        )?;
+        toml.put_u32(
+            "hinting_threshold",
+            self.hinting_threshold_percent,
+            "Percentage of the quorum delta a block needs in cached votes to start a hinted election\ntype:uint32,[0,100]",
+        )
+    }
+}
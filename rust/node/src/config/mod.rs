@@ -1,6 +1,9 @@
 mod bootstrap_config;
+mod config_validation;
+mod confirm_req_config;
 mod daemon_config;
 mod diagnostics_config;
+mod hinted_scheduler_config;
 mod logging;
 mod network_constants;
 mod node_config;
@@ -8,14 +11,20 @@ mod node_flags;
 mod node_rpc_config;
 mod opencl_config;
 mod optimistic_scheduler_config;
+mod peer;
+mod protocol_info;
 mod rpc_config;
+mod socks5_proxy_config;
 mod websocket_config;
 
 use std::path::{Path, PathBuf};
 
 pub use bootstrap_config::{AccountSetsConfig, BootstrapAscendingConfig};
+pub use config_validation::{ConfigIssue, ConfigIssueKind};
+pub use confirm_req_config::ConfirmReqConfig;
 pub use daemon_config::*;
 pub use diagnostics_config::*;
+pub use hinted_scheduler_config::HintedSchedulerConfig;
 pub use logging::*;
 pub use network_constants::*;
 pub use node_config::*;
@@ -23,8 +32,11 @@ pub use node_flags::*;
 pub use node_rpc_config::*;
 pub use opencl_config::*;
 pub use optimistic_scheduler_config::*;
+pub use peer::Peer;
+pub use protocol_info::{ProtocolFeature, ProtocolInfo};
 pub use rpc_config::*;
 use rsnano_core::Networks;
+pub use socks5_proxy_config::Socks5ProxyConfig;
 pub use websocket_config::*;
 
 pub fn get_node_toml_config_path(data_path: &Path) -> PathBuf {
@@ -7,6 +7,9 @@ use rsnano_core::{
 };
 use std::{sync::Mutex, time::Duration};
 
+use super::ProtocolInfo;
+use crate::NetworkDefinition;
+
 //todo: make configurable in builld script again!
 static ACTIVE_NETWORK: Lazy<Mutex<Networks>> = Lazy::new(|| Mutex::new(Networks::NanoDevNetwork));
 
@@ -37,16 +40,21 @@ pub struct NetworkConstants {
     pub peer_dump_interval_s: i64,
 
     pub current_network: Networks,
-    /** Current protocol version */
-    pub protocol_version: u8,
-    /** Minimum accepted protocol version */
-    pub protocol_version_min: u8,
-    /** Minimum accepted protocol version used when bootstrapping */
-    pub bootstrap_protocol_version_min: u8,
+    /// Id which is sent in the header of every message. It is the id of `current_network`,
+    /// unless a network definition with its own id is active.
+    pub network_id: u16,
+    /// Accept messages with the network id of another known network and only count them,
+    /// instead of dropping the connection and excluding the peer. For test setups only.
+    pub network_id_warn_only: bool,
+    pub protocol: ProtocolInfo,
     pub ipv6_subnetwork_prefix_for_limiting: usize,
     pub silent_connection_tolerance_time_s: i64,
     /// Time to wait before vote rebroadcasts for active elections (milliseconds)
     pub vote_broadcast_interval_ms: i64,
+    /// Minimum time between rebroadcasts of the winning block of an election root
+    pub block_broadcast_interval: Duration,
+    /// The block of a new election was just flooded, so it isn't rebroadcast before this delay passed
+    pub block_broadcast_initial_delay: Duration,
 
     /** We do not reply to telemetry requests made within cooldown period */
     pub telemetry_request_cooldown_ms: i64,
@@ -66,11 +74,25 @@ impl NetworkConstants {
     }
 
     pub fn new(work: WorkThresholds, network: Networks) -> Self {
-        match network {
+        let mut constants = match network {
             Networks::NanoDevNetwork => Self::dev(work),
             Networks::NanoBetaNetwork => Self::beta(work),
             Networks::NanoLiveNetwork | Networks::Invalid => Self::live(work),
             Networks::NanoTestNetwork => Self::test(work),
+        };
+        if let Some(definition) = NetworkDefinition::active() {
+            if definition.base_network == network {
+                constants.apply_definition(&definition);
+            }
+        }
+        constants
+    }
+
+    /// Uses the network id and port of a private network
+    pub fn apply_definition(&mut self, definition: &NetworkDefinition) {
+        self.network_id = definition.network_id;
+        if let Some(port) = definition.default_node_port {
+            self.default_node_port = port;
         }
     }
 
@@ -80,9 +102,12 @@ impl NetworkConstants {
         Self {
             work,
             current_network: Networks::NanoLiveNetwork,
-            protocol_version: 0x13,
-            protocol_version_min: 0x12,
-            bootstrap_protocol_version_min: 0x13,
+            network_id: Networks::NanoLiveNetwork as u16,
+            protocol: ProtocolInfo {
+                version_using: 0x13,
+                version_min: 0x12,
+                bootstrap_version_min: 0x13,
+            },
             principal_weight_factor: 1000, // 0.1%
             default_node_port: 7075,
             default_rpc_port: 7076,
@@ -100,6 +125,9 @@ impl NetworkConstants {
             ipv6_subnetwork_prefix_for_limiting: 64,
             silent_connection_tolerance_time_s: 120,
             vote_broadcast_interval_ms: 15 * 1000,
+            block_broadcast_interval: Duration::from_secs(15),
+            block_broadcast_initial_delay: Duration::from_secs(5),
+            network_id_warn_only: false,
             telemetry_request_cooldown_ms: 1000 * 15,
             telemetry_request_interval_ms: 1000 * 60,
             telemetry_broadcast_interval_ms: 1000 * 60,
@@ -111,6 +139,7 @@ impl NetworkConstants {
     fn beta(work: WorkThresholds) -> Self {
         Self {
             current_network: Networks::NanoBetaNetwork,
+            network_id: Networks::NanoBetaNetwork as u16,
             default_node_port: 54000,
             default_rpc_port: 55000,
             default_ipc_port: 56000,
@@ -122,6 +151,7 @@ impl NetworkConstants {
     fn test(work: WorkThresholds) -> Self {
         Self {
             current_network: Networks::NanoTestNetwork,
+            network_id: Networks::NanoTestNetwork as u16,
             default_node_port: test_node_port(),
             default_rpc_port: test_rpc_port(),
             default_ipc_port: test_ipc_port(),
@@ -135,6 +165,7 @@ impl NetworkConstants {
         let max_peers_per_ip = 20;
         Self {
             current_network: Networks::NanoDevNetwork,
+            network_id: Networks::NanoDevNetwork as u16,
             default_node_port: 44000,
             default_rpc_port: 45000,
             default_ipc_port: 46000,
@@ -147,6 +178,8 @@ impl NetworkConstants {
             max_peers_per_subnetwork: max_peers_per_ip * 4,
             peer_dump_interval_s: 1,
             vote_broadcast_interval_ms: 500,
+            block_broadcast_interval: Duration::from_millis(500),
+            block_broadcast_initial_delay: Duration::from_millis(100),
             telemetry_request_cooldown_ms: 500,
             telemetry_cache_cutoff_ms: 2000,
             telemetry_request_interval_ms: 500,
@@ -172,6 +205,11 @@ impl NetworkConstants {
         self.current_network == Networks::NanoTestNetwork
     }
 
+    /// A private network which is described by a network definition
+    pub fn is_custom_network(&self) -> bool {
+        self.network_id != self.current_network as u16
+    }
+
     /** Initial value is ACTIVE_NETWORK compile flag, but can be overridden by a CLI flag */
     pub fn active_network() -> Networks {
         *ACTIVE_NETWORK.lock().unwrap()
@@ -192,14 +230,7 @@ impl NetworkConstants {
      * @param network The new active network. Valid values are "live", "beta" and "dev"
      */
     pub fn set_active_network_from_str(network: impl AsRef<str>) -> Result<()> {
-        let net = match network.as_ref() {
-            "live" => Networks::NanoLiveNetwork,
-            "beta" => Networks::NanoBetaNetwork,
-            "dev" => Networks::NanoDevNetwork,
-            "test" => Networks::NanoTestNetwork,
-            _ => bail!("invalid network"),
-        };
-        Self::set_active_network(net);
+        Self::set_active_network(network.as_ref().parse()?);
         Ok(())
     }
 
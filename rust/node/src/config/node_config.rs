@@ -10,8 +10,8 @@ use rsnano_core::{
 use rsnano_store_lmdb::LmdbConfig;
 
 use super::{
-    BootstrapAscendingConfig, DiagnosticsConfig, Logging, Networks, OptimisticSchedulerConfig,
-    WebsocketConfig,
+    BootstrapAscendingConfig, ConfirmReqConfig, DiagnosticsConfig, HintedSchedulerConfig, Logging,
+    Networks, OptimisticSchedulerConfig, Peer, Socks5ProxyConfig, WebsocketConfig,
 };
 
 #[repr(u8)]
@@ -26,10 +26,11 @@ pub enum FrontiersConfirmationMode {
 pub struct NodeConfig {
     pub peering_port: Option<u16>,
     pub optimistic_scheduler: OptimisticSchedulerConfig,
+    pub hinted_scheduler: HintedSchedulerConfig,
+    pub confirm_req: ConfirmReqConfig,
     pub bootstrap_fraction_numerator: u32,
     pub receive_minimum: Amount,
     pub online_weight_minimum: Amount,
-    pub election_hint_weight_percent: u32,
     pub password_fanout: u32,
     pub io_threads: u32,
     pub network_threads: u32,
@@ -44,11 +45,46 @@ pub struct NodeConfig {
     pub bootstrap_frontier_request_count: u32,
     pub block_processor_batch_max_time_ms: i64,
     pub allow_local_peers: bool,
+    /// Connect to and accept peers with IPv4 addresses
+    pub allow_ipv4_peers: bool,
+    /// Connect to and accept peers with (non IPv4-mapped) IPv6 addresses
+    pub allow_ipv6_peers: bool,
+    pub socks5_proxy: Socks5ProxyConfig,
+    /// Maximum number of peers per IP address (or IPv6 /48 subnet)
+    pub max_peers_per_ip: usize,
+    /// Maximum number of peers per IPv4 /24 or IPv6 /32 subnetwork
+    pub max_peers_per_subnetwork: usize,
+    /// Peers which were not seen for this many seconds are removed from the peer history
+    pub peer_history_cutoff_time_s: i64,
+    /// Historic peers are contacted again while the node has fewer peers than this
+    pub min_peer_count: usize,
     pub vote_minimum: Amount,
     pub vote_generator_delay_ms: i64,
     pub vote_generator_threshold: u32,
     pub block_process_timeout_s: i64,
     pub unchecked_cutoff_time_s: i64,
+    /// Maximum number of blocks in the unchecked table. The oldest entries are evicted first.
+    pub max_unchecked_blocks: usize,
+    /// Maximum number of blocks a single rollback may remove
+    pub max_rollback_depth: usize,
+    /// Reject forks of cemented blocks in the block processor instead of starting an election
+    pub reject_confirmed_forks: bool,
+    /// Maximum number of state blocks of a single account which the block processor handles in one batch
+    pub max_blocks_per_account_per_batch: usize,
+    /// Maximum number of forks an election tracks for a single root
+    pub max_forks_per_root: usize,
+    /// Keep the receivable count and amount of every account in memory
+    pub receivable_cache: bool,
+    /// Log a warning if the free disk space in the data path drops below this
+    pub free_disk_warning_mb: u64,
+    /// Pause non-essential writes like pruning if the free disk space drops below this. 0 disables pausing
+    pub free_disk_pause_mb: u64,
+    /// Shut the node down if the free disk space drops below this. 0 disables the shutdown
+    pub free_disk_shutdown_mb: u64,
+    /// Log a warning if the resident memory of the process exceeds this. 0 disables the warning
+    pub memory_warning_mb: u64,
+    /// Replaces all work thresholds on the dev and test network. 0 disables the override
+    pub work_threshold_override: u64,
     pub tcp_io_timeout_s: i64,
     pub pow_sleep_interval_ns: i64,
     pub external_address: String,
@@ -95,20 +131,6 @@ pub struct NodeConfig {
     pub backlog_scan_frequency: u32,
 }
 
-pub struct Peer {
-    pub address: String,
-    pub port: u16,
-}
-
-impl Peer {
-    pub fn new(address: impl Into<String>, port: u16) -> Self {
-        Self {
-            address: address.into(),
-            port,
-        }
-    }
-}
-
 static DEFAULT_LIVE_PEER_NETWORK: Lazy<String> =
     Lazy::new(|| get_env_or_default_string("NANO_DEFAULT_PEER", "peering.nano.org"));
 
@@ -208,13 +230,17 @@ impl NodeConfig {
             }
             Networks::Invalid => panic!("invalid network"),
         }
+        if network_params.network.is_custom_network() {
+            // A private network must not connect to the peers of its base network
+            preconfigured_peers.clear();
+            preconfigured_representatives = vec![network_params.ledger.genesis_account];
+        }
 
         Self {
             peering_port,
             bootstrap_fraction_numerator: 1,
             receive_minimum: Amount::raw(*XRB_RATIO),
             online_weight_minimum: Amount::raw(60000 * *GXRB_RATIO),
-            election_hint_weight_percent: 50,
             password_fanout: 1024,
             io_threads: std::cmp::max(get_cpu_count() as u32, 4),
             network_threads: std::cmp::max(get_cpu_count() as u32, 4),
@@ -231,11 +257,29 @@ impl NodeConfig {
             block_processor_batch_max_time_ms: 500,
             allow_local_peers: !(network_params.network.is_live_network()
                 || network_params.network.is_test_network()), // disable by default for live network
+            allow_ipv4_peers: true,
+            allow_ipv6_peers: true,
+            socks5_proxy: Default::default(),
+            max_peers_per_ip: network_params.network.max_peers_per_ip,
+            max_peers_per_subnetwork: network_params.network.max_peers_per_subnetwork,
+            peer_history_cutoff_time_s: 7 * 24 * 60 * 60, // 7 days
+            min_peer_count: 16,
             vote_minimum: Amount::raw(*GXRB_RATIO),
             vote_generator_delay_ms: 100,
             vote_generator_threshold: 3,
             block_process_timeout_s: 15,
             unchecked_cutoff_time_s: 4 * 60 * 60, // 4 hours
+            max_unchecked_blocks: 64 * 1024,
+            max_rollback_depth: 100_000,
+            reject_confirmed_forks: true,
+            max_blocks_per_account_per_batch: 4096,
+            max_forks_per_root: 10,
+            receivable_cache: false,
+            free_disk_warning_mb: 10 * 1024,
+            free_disk_pause_mb: 0,
+            free_disk_shutdown_mb: 0,
+            memory_warning_mb: 16 * 1024,
+            work_threshold_override: 0,
             tcp_io_timeout_s: if network_params.network.is_dev_network() && !is_sanitizer_build() {
                 5
             } else {
@@ -289,6 +333,8 @@ impl NodeConfig {
             backlog_scan_batch_size: 10 * 1000,
             backlog_scan_frequency: 10,
             optimistic_scheduler: OptimisticSchedulerConfig::new(),
+            hinted_scheduler: HintedSchedulerConfig::new(&network_params.network),
+            confirm_req: ConfirmReqConfig::new(&network_params.network),
         }
     }
 
@@ -300,7 +346,6 @@ impl NodeConfig {
         toml.put_u32("bootstrap_fraction_numerator", self.bootstrap_fraction_numerator, "Change bootstrap threshold (online stake / 256 * bootstrap_fraction_numerator).\ntype:uint32")?;
         toml.put_str("receive_minimum", &self.receive_minimum.to_string_dec (), "Minimum receive amount. Only affects node wallets. A large amount is recommended to avoid automatic work generation for tiny transactions.\ntype:string,amount,raw")?;
         toml.put_str("online_weight_minimum", &self.online_weight_minimum.to_string_dec (), "When calculating online weight, the node is forced to assume at least this much voting weight is online, thus setting a floor for voting weight to confirm transactions at online_weight_minimum * \"quorum delta\".\ntype:string,amount,raw")?;
-        toml.put_u32("election_hint_weight_percent", self.election_hint_weight_percent, "Percentage of online weight to hint at starting an election. Defaults to 10.\ntype:uint32,[5,50]")?;
         toml.put_u32(
             "password_fanout",
             self.password_fanout,
@@ -323,10 +368,95 @@ impl NodeConfig {
             self.allow_local_peers,
             "Enable or disable local host peering.\ntype:bool",
         )?;
+        toml.put_bool(
+            "allow_ipv4_peers",
+            self.allow_ipv4_peers,
+            "Enable or disable peering with IPv4 addresses.\ntype:bool",
+        )?;
+        toml.put_bool(
+            "allow_ipv6_peers",
+            self.allow_ipv6_peers,
+            "Enable or disable peering with IPv6 addresses.\ntype:bool",
+        )?;
+        toml.put_usize(
+            "max_peers_per_ip",
+            self.max_peers_per_ip,
+            "Maximum number of peers per IP address.\ntype:uint64",
+        )?;
+        toml.put_usize(
+            "max_peers_per_subnetwork",
+            self.max_peers_per_subnetwork,
+            "Maximum number of peers per subnetwork.\ntype:uint64",
+        )?;
+        toml.put_i64(
+            "peer_history_cutoff_time",
+            self.peer_history_cutoff_time_s,
+            "Number of seconds before a peer which was not seen anymore is removed from the peer history.\ntype:seconds",
+        )?;
+        toml.put_usize(
+            "min_peer_count",
+            self.min_peer_count,
+            "Peers from the peer history are contacted again while the node has fewer peers than this. Peers with a recent successful handshake are tried first.\ntype:uint64",
+        )?;
         toml.put_str("vote_minimum", &self.vote_minimum.to_string_dec (), "Local representatives do not vote if the delegated weight is under this threshold. Saves on system resources.\ntype:string,amount,raw")?;
         toml.put_i64("vote_generator_delay", self.vote_generator_delay_ms, "Delay before votes are sent to allow for efficient bundling of hashes in votes.\ntype:milliseconds")?;
         toml.put_u32("vote_generator_threshold", self.vote_generator_threshold, "Number of bundled hashes required for an additional generator delay.\ntype:uint64,[1..11]")?;
         toml.put_i64("unchecked_cutoff_time", self.unchecked_cutoff_time_s, "Number of seconds before deleting an unchecked entry.\nWarning: lower values (e.g., 3600 seconds, or 1 hour) may result in unsuccessful bootstraps, especially a bootstrap from scratch.\ntype:seconds")?;
+        toml.put_usize(
+            "max_unchecked_blocks",
+            self.max_unchecked_blocks,
+            "Maximum number of unchecked blocks kept in memory. The oldest blocks are dropped first.\ntype:uint64",
+        )?;
+        toml.put_usize(
+            "max_rollback_depth",
+            self.max_rollback_depth,
+            "Maximum number of blocks which a single rollback may remove. Longer rollbacks fail without changing the ledger.\ntype:uint64",
+        )?;
+        toml.put_bool(
+            "reject_confirmed_forks",
+            self.reject_confirmed_forks,
+            "Reject forks of blocks which are already cemented, without starting an election.\ntype:bool",
+        )?;
+        toml.put_usize(
+            "max_blocks_per_account_per_batch",
+            self.max_blocks_per_account_per_batch,
+            "Maximum number of state blocks of a single account which are processed in one block processor batch. Further blocks of that account are deferred to the next batch, so that one long chain can't hold the write lock for too long.\ntype:uint64",
+        )?;
+        toml.put_usize(
+            "max_forks_per_root",
+            self.max_forks_per_root,
+            "Maximum number of forks an election keeps track of. Additional forks only replace existing ones if they have a higher vote tally.\ntype:uint64,[1..18446744073709551615]",
+        )?;
+        toml.put_bool(
+            "receivable_cache",
+            self.receivable_cache,
+            "Keep the receivable count and amount of every account in memory, so that account_balance doesn't need to scan the pending table. The cache is built on startup, which can take a while.\ntype:bool",
+        )?;
+        toml.put_u64(
+            "free_disk_warning_mb",
+            self.free_disk_warning_mb,
+            "Log a warning if the free disk space in the data path drops below this many megabytes.\ntype:uint64",
+        )?;
+        toml.put_u64(
+            "free_disk_pause_mb",
+            self.free_disk_pause_mb,
+            "Pause non-essential writes like ledger pruning and new unchecked blocks if the free disk space in the data path drops below this many megabytes. 0 disables pausing.\ntype:uint64",
+        )?;
+        toml.put_u64(
+            "free_disk_shutdown_mb",
+            self.free_disk_shutdown_mb,
+            "Shut the node down cleanly if the free disk space in the data path drops below this many megabytes. 0 disables the shutdown.\ntype:uint64",
+        )?;
+        toml.put_u64(
+            "memory_warning_mb",
+            self.memory_warning_mb,
+            "Log a warning if the resident memory of the node exceeds this many megabytes. 0 disables the warning.\ntype:uint64",
+        )?;
+        toml.put_u64(
+            "work_threshold_override",
+            self.work_threshold_override,
+            "Replaces all work thresholds with this value, so that tests can run with trivial work. Only the dev and test network allow this. 0 disables the override.\ntype:uint64",
+        )?;
         toml.put_i64(
             "block_process_timeout",
             self.block_process_timeout_s,
@@ -383,13 +513,13 @@ impl NodeConfig {
             "A list of \"address:port\" entries to identify work peers.",
             &mut |work_peers| {
                 for peer in &self.work_peers {
-                    work_peers.push_back_str(&format!("{}:{}", peer.address, peer.port))?;
+                    work_peers.push_back_str(&peer.to_string())?;
                 }
                 Ok(())
             },
         )?;
 
-        toml.create_array ("preconfigured_peers", "A list of \"address\" (hostname or ipv6 notation ip address) entries to identify preconfigured peers.\nThe contents of the NANO_DEFAULT_PEER environment variable are added to preconfigured_peers.",
+        toml.create_array ("preconfigured_peers", "A list of \"address\" or \"address:port\" (hostname or ipv6 notation ip address) entries to identify preconfigured peers. The default node port is used if the port is omitted.\nThe contents of the NANO_DEFAULT_PEER environment variable are added to preconfigured_peers.",
         &mut |peers| {
             for peer in &self.preconfigured_peers {
                 peers.push_back_str(peer)?;
@@ -409,7 +539,7 @@ impl NodeConfig {
             child.create_array ("secondary_work_peers", "A list of \"address:port\" entries to identify work peers for secondary work generation.",
         &mut |peers|{
             for p in &self.secondary_work_peers{
-                peers.push_back_str(&format!("{}:{}", p.address, p.port))?;
+                peers.push_back_str(&p.to_string())?;
             }
             Ok(())
         })?;
@@ -461,10 +591,22 @@ impl NodeConfig {
             self.optimistic_scheduler.serialize_toml(opt)
         })?;
 
+        toml.put_child("hinted_scheduler", &mut |writer| {
+            self.hinted_scheduler.serialize_toml(writer)
+        })?;
+
+        toml.put_child("confirm_req", &mut |writer| {
+            self.confirm_req.serialize_toml(writer)
+        })?;
+
         toml.put_child("bootstrap_ascending", &mut |writer| {
             self.bootstrap_ascending.serialize_toml(writer)
         })?;
 
+        toml.put_child("socks5_proxy", &mut |writer| {
+            self.socks5_proxy.serialize_toml(writer)
+        })?;
+
         Ok(())
     }
 }
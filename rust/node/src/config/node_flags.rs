@@ -1,5 +1,7 @@
 use rsnano_ledger::GenerateCache;
 
+use crate::vote_cache::VoteCacheEvictionPolicy;
+
 #[derive(Clone)]
 pub struct NodeFlags {
     pub config_overrides: Vec<String>,
@@ -21,6 +23,8 @@ pub struct NodeFlags {
     pub disable_unchecked_drop: bool,
     pub disable_providing_telemetry_metrics: bool,
     pub disable_ongoing_telemetry_requests: bool,
+    /// Don't warn when the majority of the peers uses a newer protocol version
+    pub disable_telemetry_upgrade_advisory: bool,
     pub disable_block_processor_unchecked_deletion: bool,
     pub disable_block_processor_republishing: bool,
     pub allow_bootstrap_peers_duplicates: bool,
@@ -32,13 +36,21 @@ pub struct NodeFlags {
     pub fast_bootstrap: bool,
     pub read_only: bool,
     pub disable_connection_cleanup: bool,
+    /// Deliver own flooded blocks and votes to the node itself through an in-process loopback
+    /// channel, so that a single node test network runs them through the inbound pipeline
+    pub enable_loopback_channel: bool, // For testing only
     pub generate_cache: GenerateCache,
     pub inactive_node: bool,
     pub block_processor_batch_size: usize,
     pub block_processor_full_size: usize,
     pub block_processor_verification_size: usize,
     pub inactive_votes_cache_size: usize,
+    pub inactive_votes_cache_eviction: VoteCacheEvictionPolicy,
+    /// Keep cached votes after they were transferred into a newly started election
+    pub inactive_votes_cache_retain: bool,
     pub vote_processor_capacity: usize,
+    /// Traces the propagation of one of N blocks. 0 disables the block tracer
+    pub block_trace_sample_interval: u64,
     pub bootstrap_interval: usize, // For testing only
 }
 
@@ -64,6 +76,7 @@ impl NodeFlags {
             disable_unchecked_drop: true,
             disable_providing_telemetry_metrics: false,
             disable_ongoing_telemetry_requests: false,
+            disable_telemetry_upgrade_advisory: false,
             disable_block_processor_unchecked_deletion: false,
             disable_block_processor_republishing: false,
             allow_bootstrap_peers_duplicates: false,
@@ -75,13 +88,17 @@ impl NodeFlags {
             fast_bootstrap: false,
             read_only: false,
             disable_connection_cleanup: false,
+            enable_loopback_channel: false,
             generate_cache: GenerateCache::new(),
             inactive_node: false,
             block_processor_batch_size: 0,
             block_processor_full_size: 65536,
             block_processor_verification_size: 0,
             inactive_votes_cache_size: 1024 * 128,
+            inactive_votes_cache_eviction: VoteCacheEvictionPolicy::LowestTally,
+            inactive_votes_cache_retain: true,
             vote_processor_capacity: 144 * 1024,
+            block_trace_sample_interval: 0,
             bootstrap_interval: 0,
         }
     }
@@ -0,0 +1,132 @@
+use std::{
+    fmt::Display,
+    net::{Ipv6Addr, ToSocketAddrs},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+
+use crate::transport::Endpoint;
+
+/// A configured peer. The address can be a hostname or an IP address
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Peer {
+    pub address: String,
+    pub port: u16,
+}
+
+impl Peer {
+    pub fn new(address: impl Into<String>, port: u16) -> Self {
+        Self {
+            address: address.into(),
+            port,
+        }
+    }
+
+    /// Parses `address`, `address:port`, `[ipv6]` or `[ipv6]:port`.
+    /// An IPv6 address without brackets is never split into address and port.
+    pub fn parse_with_default_port(s: &str, default_port: u16) -> anyhow::Result<Self> {
+        if s.parse::<Ipv6Addr>().is_ok() {
+            return Ok(Self::new(s, default_port));
+        }
+        if let Some(rest) = s.strip_prefix('[') {
+            let (address, port) = rest
+                .split_once(']')
+                .ok_or_else(|| anyhow!("missing ']' in peer: {}", s))?;
+            let port = match port.strip_prefix(':') {
+                Some(port) => parse_port(port, s)?,
+                None if port.is_empty() => default_port,
+                None => return Err(anyhow!("invalid peer: {}", s)),
+            };
+            return Ok(Self::new(address, port));
+        }
+        match s.rsplit_once(':') {
+            Some((address, port)) => Ok(Self::new(address, parse_port(port, s)?)),
+            None if !s.is_empty() => Ok(Self::new(s, default_port)),
+            None => Err(anyhow!("empty peer address")),
+        }
+    }
+
+    /// Resolves the address of the peer, which can take a while for hostnames
+    pub fn resolve(&self) -> anyhow::Result<Vec<Endpoint>> {
+        let endpoints = (self.address.as_str(), self.port)
+            .to_socket_addrs()?
+            .map(Endpoint::from)
+            .collect();
+        Ok(endpoints)
+    }
+}
+
+/// Parses `address:port`
+impl FromStr for Peer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, port) = s
+            .rsplit_once(':')
+            .ok_or_else(|| anyhow!("missing port in peer: {}", s))?;
+        if address.is_empty() {
+            return Err(anyhow!("empty peer address"));
+        }
+        Ok(Self::new(address, parse_port(port, s)?))
+    }
+}
+
+impl Display for Peer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.address, self.port)
+    }
+}
+
+fn parse_port(port: &str, peer: &str) -> anyhow::Result<u16> {
+    port.parse()
+        .map_err(|_| anyhow!("invalid port in peer: {}", peer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_address_and_port() {
+        assert_eq!(
+            "peering.nano.org:7075".parse::<Peer>().unwrap(),
+            Peer::new("peering.nano.org", 7075)
+        );
+        assert_eq!("::1:8076".parse::<Peer>().unwrap(), Peer::new("::1", 8076));
+        assert!("peering.nano.org".parse::<Peer>().is_err());
+        assert!(":7075".parse::<Peer>().is_err());
+        assert!("host:abc".parse::<Peer>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Peer::new("127.0.0.1", 8076).to_string(), "127.0.0.1:8076");
+    }
+
+    #[test]
+    fn parse_with_default_port() {
+        let parse = |s| Peer::parse_with_default_port(s, 7075).unwrap();
+        assert_eq!(
+            parse("peering.nano.org"),
+            Peer::new("peering.nano.org", 7075)
+        );
+        assert_eq!(
+            parse("peering.nano.org:54000"),
+            Peer::new("peering.nano.org", 54000)
+        );
+        assert_eq!(parse("::1"), Peer::new("::1", 7075));
+        assert_eq!(parse("[::1]"), Peer::new("::1", 7075));
+        assert_eq!(parse("[::1]:54000"), Peer::new("::1", 54000));
+        assert_eq!(parse("1.2.3.4:54000"), Peer::new("1.2.3.4", 54000));
+        assert!(Peer::parse_with_default_port("[::1", 7075).is_err());
+        assert!(Peer::parse_with_default_port("[::1]54000", 7075).is_err());
+        assert!(Peer::parse_with_default_port("", 7075).is_err());
+    }
+
+    #[test]
+    fn resolve_ip_address() {
+        let endpoints = Peer::new("1.2.3.4", 7075).resolve().unwrap();
+        assert_eq!(endpoints, vec!["1.2.3.4:7075".parse::<Endpoint>().unwrap()]);
+    }
+}
@@ -0,0 +1,104 @@
+/// Parts of the protocol which depend on the protocol version of a peer
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProtocolFeature {
+    /// Telemetry data which is larger than the fields known to the sender
+    TelemetryExtensions,
+}
+
+impl ProtocolFeature {
+    /// First protocol version which supports the feature
+    pub fn introduced_in(&self) -> u8 {
+        match self {
+            ProtocolFeature::TelemetryExtensions => 0x13,
+        }
+    }
+
+    /// First protocol version in which the feature isn't used anymore
+    pub fn deprecated_in(&self) -> Option<u8> {
+        match self {
+            ProtocolFeature::TelemetryExtensions => None,
+        }
+    }
+}
+
+/// The protocol versions of a network
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProtocolInfo {
+    /// Current protocol version
+    pub version_using: u8,
+    /// Minimum accepted protocol version
+    pub version_min: u8,
+    /// Minimum accepted protocol version used when bootstrapping
+    pub bootstrap_version_min: u8,
+}
+
+impl ProtocolInfo {
+    pub fn is_supported(&self, peer_version: u8) -> bool {
+        peer_version >= self.version_min
+    }
+
+    pub fn is_bootstrap_supported(&self, peer_version: u8) -> bool {
+        peer_version >= self.bootstrap_version_min
+    }
+
+    /// The version which is used for the communication with a peer,
+    /// or `None` if the peer is too old
+    pub fn negotiate(&self, peer_version_using: u8) -> Option<u8> {
+        if self.is_supported(peer_version_using) {
+            Some(peer_version_using.min(self.version_using))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the feature can be used in messages for a peer with the given protocol version
+    pub fn supports(&self, feature: ProtocolFeature, peer_version: u8) -> bool {
+        match self.negotiate(peer_version) {
+            Some(version) => is_active(feature.introduced_in(), feature.deprecated_in(), version),
+            None => false,
+        }
+    }
+}
+
+fn is_active(introduced_in: u8, deprecated_in: Option<u8>, version: u8) -> bool {
+    version >= introduced_in && deprecated_in.map(|d| version < d).unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTOCOL: ProtocolInfo = ProtocolInfo {
+        version_using: 0x13,
+        version_min: 0x12,
+        bootstrap_version_min: 0x13,
+    };
+
+    #[test]
+    fn negotiate_version() {
+        assert_eq!(PROTOCOL.negotiate(0x11), None);
+        assert_eq!(PROTOCOL.negotiate(0x12), Some(0x12));
+        assert_eq!(PROTOCOL.negotiate(0x13), Some(0x13));
+        assert_eq!(PROTOCOL.negotiate(0x14), Some(0x13));
+    }
+
+    #[test]
+    fn bootstrap_version() {
+        assert!(!PROTOCOL.is_bootstrap_supported(0x12));
+        assert!(PROTOCOL.is_bootstrap_supported(0x13));
+    }
+
+    #[test]
+    fn feature_support_depends_on_peer_version() {
+        assert!(!PROTOCOL.supports(ProtocolFeature::TelemetryExtensions, 0x12));
+        assert!(PROTOCOL.supports(ProtocolFeature::TelemetryExtensions, 0x13));
+        assert!(PROTOCOL.supports(ProtocolFeature::TelemetryExtensions, 0x14));
+    }
+
+    #[test]
+    fn deprecated_feature() {
+        assert!(is_active(0x12, Some(0x14), 0x13));
+        assert!(!is_active(0x12, Some(0x14), 0x14));
+        assert!(!is_active(0x12, Some(0x14), 0x11));
+    }
+}
@@ -0,0 +1,108 @@
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+
+use rsnano_core::utils::TomlWriter;
+
+/// Outbound peer connections are made through this SOCKS5 proxy if an address is configured
+#[derive(Clone, Default)]
+pub struct Socks5ProxyConfig {
+    /// IP address or host name of the proxy. An empty address disables the proxy
+    pub address: String,
+    pub port: u16,
+
+    /// Connect directly to the peer if the connection through the proxy fails
+    pub fallback_to_direct: bool,
+}
+
+impl Socks5ProxyConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.address.is_empty()
+    }
+
+    /// Returns `None` if the proxy is disabled or the address is a host name, which needs to be resolved first
+    pub fn endpoint(&self) -> Option<SocketAddr> {
+        let ip: IpAddr = self.address.parse().ok()?;
+        Some(SocketAddr::new(ip, self.port))
+    }
+
+    /// Resolves the proxy address. This blocks while the host name is looked up
+    pub fn resolve(&self) -> anyhow::Result<SocketAddr> {
+        if let Some(endpoint) = self.endpoint() {
+            return Ok(endpoint);
+        }
+        if !self.is_enabled() {
+            bail!("no SOCKS5 proxy configured");
+        }
+        (self.address.as_str(), self.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("{} did not resolve to any address", self.address))
+    }
+
+    pub(crate) fn serialize_toml(&self, toml: &mut dyn TomlWriter) -> anyhow::Result<()> {
+        toml.put_str(
+            "address",
+            &self.address,
+            "IP address or host name of a SOCKS5 proxy for outbound peer connections. Leave empty to connect directly.\ntype:string,host",
+        )?;
+        toml.put_u16("port", self.port, "Port of the SOCKS5 proxy.\ntype:uint16")?;
+        toml.put_bool(
+            "fallback_to_direct",
+            self.fallback_to_direct,
+            "Connect directly to a peer if the connection through the proxy fails or the proxy host name can't be resolved.\ntype:bool",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_by_default() {
+        let config = Socks5ProxyConfig::default();
+        assert!(!config.is_enabled());
+        assert_eq!(config.endpoint(), None);
+    }
+
+    #[test]
+    fn endpoint() {
+        let config = Socks5ProxyConfig {
+            address: "::ffff:127.0.0.1".to_string(),
+            port: 1080,
+            fallback_to_direct: false,
+        };
+        assert!(config.is_enabled());
+        assert_eq!(
+            config.endpoint(),
+            Some("[::ffff:127.0.0.1]:1080".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn resolve_ip_address() {
+        let config = Socks5ProxyConfig {
+            address: "127.0.0.1".to_string(),
+            port: 1080,
+            fallback_to_direct: false,
+        };
+        assert_eq!(config.resolve().unwrap(), "127.0.0.1:1080".parse().unwrap());
+    }
+
+    #[test]
+    fn resolve_host_name() {
+        let config = Socks5ProxyConfig {
+            address: "localhost".to_string(),
+            port: 1080,
+            fallback_to_direct: false,
+        };
+        assert_eq!(config.endpoint(), None);
+        let resolved = config.resolve().unwrap();
+        assert!(resolved.ip().is_loopback());
+        assert_eq!(resolved.port(), 1080);
+    }
+
+    #[test]
+    fn resolve_disabled_proxy_fails() {
+        assert!(Socks5ProxyConfig::default().resolve().is_err());
+    }
+}
@@ -13,6 +13,7 @@ extern crate core;
 
 pub mod block_processing;
 pub mod bootstrap;
+pub mod bootstrap_ascending;
 pub mod cementation;
 pub mod config;
 mod ipc;
@@ -23,6 +24,7 @@ pub use gap_cache::GapCache;
 pub use representatives::{OnlineReps, OnlineWeightSampler, ONLINE_WEIGHT_QUORUM};
 
 pub mod representatives;
+pub mod scheduler;
 mod secure;
 pub mod signatures;
 pub mod stats;
@@ -32,6 +34,8 @@ pub mod utils;
 pub mod vote_cache;
 pub mod voting;
 pub mod websocket;
+mod work_peer_health;
+pub use work_peer_health::{CircuitState, WorkPeerHealth, WorkPeerStats};
 
 pub use ipc::*;
 pub use secure::*;
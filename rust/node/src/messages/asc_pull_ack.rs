@@ -1,7 +1,7 @@
 use crate::config::NetworkConstants;
 use num_traits::FromPrimitive;
 use rsnano_core::{
-    deserialize_block_enum, serialize_block_enum,
+    deserialize_block_enum, serialize_block_enum, serialized_block_size,
     utils::{Deserialize, MemoryStream, Serialize, Stream, StreamExt},
     Account, BlockEnum, BlockHash, BlockType,
 };
@@ -178,6 +178,14 @@ impl AscPullAck {
         Self::PARTIAL_SIZE + payload_length
     }
 
+    /// Size of a blocks response with the maximum number of state blocks and the terminator
+    pub fn max_serialized_size() -> usize {
+        Self::PARTIAL_SIZE
+            + BlocksAckPayload::MAX_BLOCKS
+                * (size_of::<u8>() + serialized_block_size(BlockType::State))
+            + size_of::<u8>()
+    }
+
     fn update_header(&mut self) -> anyhow::Result<()> {
         let mut stream = MemoryStream::new();
         self.serialize_payload(&mut stream)?;
@@ -1,7 +1,7 @@
 use crate::config::NetworkConstants;
 use num_traits::FromPrimitive;
 use rsnano_core::{
-    utils::{Deserialize, MemoryStream, Stream, StreamExt},
+    utils::{Deserialize, MemoryStream, Serialize, Stream, StreamExt},
     HashOrAccount,
 };
 use std::{any::Any, mem::size_of};
@@ -180,6 +180,14 @@ impl AscPullReq {
         }
     }
 
+    /// Size of a blocks request, which is the largest payload
+    pub fn max_serialized_size() -> usize {
+        Self::partial_size()
+            + HashOrAccount::serialized_size()
+            + size_of::<u8>() // count
+            + size_of::<u8>() // start type
+    }
+
     /** Size of message without payload */
     const fn partial_size() -> usize {
         size_of::<u8>() // pull type
@@ -59,6 +59,12 @@ impl BulkPull {
             })
     }
 
+    pub fn max_serialized_size() -> usize {
+        HashOrAccount::serialized_size()
+            + BlockHash::serialized_size()
+            + BulkPull::EXTENDED_PARAMETERS_SIZE
+    }
+
     pub fn is_count_present(&self) -> bool {
         Self::is_count_present_in_header(&self.header)
     }
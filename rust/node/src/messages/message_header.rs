@@ -56,6 +56,28 @@ impl MessageType {
             MessageType::AscPullAck => "asc_pull_ack",
         }
     }
+
+    /// Largest payload a valid message of this type can have. Headers which announce
+    /// a larger payload are rejected before the payload is read.
+    pub fn max_payload_length(&self) -> usize {
+        let max_count = (COUNT_MASK >> 12) as u8;
+        match self {
+            MessageType::Keepalive => Keepalive::serialized_size(),
+            MessageType::Publish => serialized_block_size(BlockType::State),
+            MessageType::ConfirmReq => ConfirmReq::serialized_size(BlockType::NotABlock, max_count)
+                .max(serialized_block_size(BlockType::State)),
+            MessageType::ConfirmAck => ConfirmAck::serialized_size(max_count),
+            MessageType::BulkPull => BulkPull::max_serialized_size(),
+            MessageType::BulkPush | MessageType::TelemetryReq => 0,
+            MessageType::FrontierReq => FrontierReq::serialized_size(),
+            MessageType::NodeIdHandshake => NodeIdHandshake::max_serialized_size(),
+            MessageType::BulkPullAccount => BulkPullAccount::serialized_size(),
+            MessageType::TelemetryAck => TelemetryAck::max_size(),
+            MessageType::AscPullReq => AscPullReq::max_serialized_size(),
+            MessageType::AscPullAck => AscPullAck::max_serialized_size(),
+            MessageType::Invalid | MessageType::NotAType => 0,
+        }
+    }
 }
 
 impl Debug for MessageType {
@@ -73,13 +95,13 @@ pub struct MessageHeader {
     version_using: u8,
     version_max: u8,
     version_min: u8,
-    network: Networks,
+    network_id: u16,
     extensions: BitArray<u16>,
 }
 
 impl MessageHeader {
     pub fn new(constants: &NetworkConstants, message_type: MessageType) -> Self {
-        let version_using = constants.protocol_version;
+        let version_using = constants.protocol.version_using;
         Self::with_version_using(constants, message_type, version_using)
     }
 
@@ -89,7 +111,7 @@ impl MessageHeader {
             version_using: 0,
             version_max: 0,
             version_min: 0,
-            network: Networks::NanoDevNetwork,
+            network_id: Networks::NanoDevNetwork as u16,
             extensions: BitArray::ZERO,
         }
     }
@@ -108,9 +130,9 @@ impl MessageHeader {
         Self {
             message_type,
             version_using,
-            version_max: constants.protocol_version,
-            version_min: constants.protocol_version_min,
-            network: constants.current_network,
+            version_max: constants.protocol.version_using,
+            version_min: constants.protocol.version_min,
+            network_id: constants.network_id,
             extensions: BitArray::ZERO,
         }
     }
@@ -131,12 +153,24 @@ impl MessageHeader {
         self.version_min
     }
 
-    pub fn network(&self) -> Networks {
-        self.network
+    pub fn network_id(&self) -> u16 {
+        self.network_id
     }
 
-    pub fn set_network(&mut self, network: Networks) {
-        self.network = network;
+    pub fn set_network_id(&mut self, network_id: u16) {
+        self.network_id = network_id;
+    }
+
+    /// Name of the built-in network with the id of this header
+    pub fn network_name(&self) -> &'static str {
+        match Networks::from_u16(self.network_id) {
+            Some(Networks::NanoDevNetwork) => "dev",
+            Some(Networks::NanoBetaNetwork) => "beta",
+            Some(Networks::NanoLiveNetwork) => "live",
+            Some(Networks::NanoTestNetwork) => "test",
+            Some(Networks::Invalid) => "invalid",
+            None => "custom",
+        }
     }
 
     pub fn message_type(&self) -> MessageType {
@@ -201,8 +235,7 @@ impl MessageHeader {
         let mut buffer = [0; 2];
 
         stream.read_bytes(&mut buffer, 2)?;
-        self.network = Networks::from_u16(u16::from_be_bytes(buffer))
-            .ok_or_else(|| anyhow!("invalid network"))?;
+        self.network_id = u16::from_be_bytes(buffer);
 
         self.version_max = stream.read_u8()?;
         self.version_using = stream.read_u8()?;
@@ -216,7 +249,7 @@ impl MessageHeader {
     }
 
     pub fn serialize(&self, stream: &mut dyn Stream) -> Result<()> {
-        stream.write_bytes(&(self.network() as u16).to_be_bytes())?;
+        stream.write_bytes(&self.network_id.to_be_bytes())?;
         stream.write_u8(self.version_max())?;
         stream.write_u8(self.version_using())?;
         stream.write_u8(self.version_min())?;
@@ -265,8 +298,8 @@ impl Display for MessageHeader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
             "NetID: {:04X}({}), ",
-            self.network() as u16,
-            self.network().as_str()
+            self.network_id,
+            self.network_name()
         ))?;
         f.write_fmt(format_args!(
             "VerMaxUsingMin: {}/{}/{}, ",
@@ -328,7 +361,7 @@ mod tests {
             version_using: 2,
             version_max: 3,
             version_min: 1,
-            network: Networks::NanoDevNetwork,
+            network_id: Networks::NanoDevNetwork as u16,
             extensions: BitArray::from(14),
         };
         header
@@ -347,9 +380,9 @@ mod tests {
         assert_eq!(bytes.len(), 8);
         assert_eq!(bytes[0], 0x52);
         assert_eq!(bytes[1], 0x41);
-        assert_eq!(bytes[2], network.protocol_version);
-        assert_eq!(bytes[3], network.protocol_version);
-        assert_eq!(bytes[4], network.protocol_version_min);
+        assert_eq!(bytes[2], network.protocol.version_using);
+        assert_eq!(bytes[3], network.protocol.version_using);
+        assert_eq!(bytes[4], network.protocol.version_min);
         assert_eq!(bytes[5], 0x03); // publish
         assert_eq!(bytes[6], 0x00); // extensions
         assert_eq!(bytes[7], 0x06); // state block
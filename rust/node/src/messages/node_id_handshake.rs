@@ -227,6 +227,15 @@ impl NodeIdHandshake {
         size
     }
 
+    /// Size of a query together with a v2 response
+    pub fn max_serialized_size() -> usize {
+        32 // cookie
+        + Account::serialized_size()
+        + 32 // salt
+        + BlockHash::serialized_size()
+        + Signature::serialized_size()
+    }
+
     pub fn test_query() -> Self {
         let query = NodeIdHandshakeQuery { cookie: [42; 32] };
         Self::new(&STUB_NETWORK_CONSTANTS, Some(query), None)
@@ -1,4 +1,4 @@
-use crate::config::NetworkConstants;
+use crate::config::{NetworkConstants, ProtocolFeature, ProtocolInfo};
 use anyhow::Result;
 use rsnano_core::utils::{Deserialize, MemoryStream, Serialize, Stream, StreamExt};
 use rsnano_core::{
@@ -156,6 +156,14 @@ impl TelemetryData {
         Ok(())
     }
 
+    /// Removes the data which a peer with the given protocol version doesn't understand.
+    /// This has to be done before signing.
+    pub fn remove_unsupported_fields(&mut self, protocol: &ProtocolInfo, peer_version: u8) {
+        if !protocol.supports(ProtocolFeature::TelemetryExtensions, peer_version) {
+            self.unknown_data.clear();
+        }
+    }
+
     pub fn sign(&mut self, keys: &KeyPair) -> Result<()> {
         debug_assert!(keys.public_key() == self.node_id);
         let mut stream = MemoryStream::new();
@@ -294,6 +302,10 @@ impl TelemetryAck {
         (header.extensions() & TelemetryAck::SIZE_MASK) as usize
     }
 
+    pub fn max_size() -> usize {
+        TelemetryAck::SIZE_MASK as usize
+    }
+
     pub fn size(&self) -> usize {
         TelemetryAck::size_from_header(&self.header)
     }
@@ -385,6 +397,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn remove_extensions_for_old_peers() {
+        let protocol = ProtocolInfo {
+            version_using: 0x13,
+            version_min: 0x12,
+            bootstrap_version_min: 0x13,
+        };
+        let mut data = TelemetryData::new();
+        data.unknown_data = vec![1];
+
+        data.remove_unsupported_fields(&protocol, 0x13);
+        assert_eq!(data.unknown_data, vec![1]);
+
+        data.remove_unsupported_fields(&protocol, 0x12);
+        assert!(data.unknown_data.is_empty());
+    }
+
     fn test_data(keys: &KeyPair) -> TelemetryData {
         let mut data = TelemetryData::new();
         data.node_id = keys.public_key().into();
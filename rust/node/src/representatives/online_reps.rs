@@ -1,16 +1,11 @@
 use primitive_types::U256;
-use rsnano_core::utils::nano_seconds_since_epoch;
+use rsnano_core::utils::{system_time_as_nanoseconds, Clock, SystemClock};
 use rsnano_core::{Account, Amount};
 use rsnano_ledger::Ledger;
 use rsnano_store_lmdb::LmdbWriteTransaction;
 use std::time::Duration;
 use std::{cmp::max, sync::Arc};
 
-#[cfg(test)]
-use mock_instant::Instant;
-#[cfg(not(test))]
-use std::time::Instant;
-
 use super::online_reps_container::OnlineRepsContainer;
 
 pub const ONLINE_WEIGHT_QUORUM: u8 = 67;
@@ -24,6 +19,7 @@ pub struct OnlineReps {
     minimum: Amount,
     weight_period: Duration,
     online_weight_minimum: Amount,
+    clock: Arc<dyn Clock>,
 }
 
 impl OnlineReps {
@@ -36,9 +32,14 @@ impl OnlineReps {
             minimum: Amount::zero(),
             weight_period: Duration::from_secs(5 * 60),
             online_weight_minimum: DEFAULT_ONLINE_WEIGHT_MINIMUM,
+            clock: Arc::new(SystemClock::new()),
         }
     }
 
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
     pub fn set_weight_period(&mut self, period: Duration) {
         self.weight_period = period;
     }
@@ -54,8 +55,9 @@ impl OnlineReps {
     /** Add voting account rep_account to the set of online representatives */
     pub fn observe(&mut self, rep_account: Account) {
         if self.ledger.weight(&rep_account) > Amount::zero() {
-            let new_insert = self.reps.insert(rep_account, Instant::now());
-            let trimmed = self.reps.trim(self.weight_period);
+            let now = self.clock.instant();
+            let new_insert = self.reps.insert(rep_account, now);
+            let trimmed = self.reps.trim(self.weight_period, now);
 
             if new_insert || trimmed {
                 self.calculate_online();
@@ -118,6 +120,7 @@ pub struct OnlineWeightSampler {
     ledger: Arc<Ledger>,
     online_weight_minimum: Amount,
     max_samples: u64,
+    clock: Arc<dyn Clock>,
 }
 
 impl OnlineWeightSampler {
@@ -126,9 +129,14 @@ impl OnlineWeightSampler {
             ledger,
             online_weight_minimum: DEFAULT_ONLINE_WEIGHT_MINIMUM,
             max_samples: 4032,
+            clock: Arc::new(SystemClock::new()),
         }
     }
 
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
     pub fn set_online_weight_minimum(&mut self, minimum: Amount) {
         self.online_weight_minimum = minimum;
     }
@@ -178,7 +186,7 @@ impl OnlineWeightSampler {
     fn insert_new_sample(&self, txn: &mut LmdbWriteTransaction, current_online_weight: Amount) {
         self.ledger.store.online_weight.put(
             txn,
-            nano_seconds_since_epoch(),
+            system_time_as_nanoseconds(self.clock.now()),
             &current_online_weight,
         );
     }
@@ -1,11 +1,7 @@
-#[cfg(test)]
-use mock_instant::Instant;
-#[cfg(not(test))]
-use std::time::Instant;
 use std::{
     collections::{BTreeMap, HashMap},
     mem::size_of,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use rsnano_core::Account;
@@ -54,11 +50,12 @@ impl OnlineRepsContainer {
         new_insert
     }
 
-    pub fn trim(&mut self, upper_bound: Duration) -> bool {
+    /// Removes all reps that weren't observed within `upper_bound` before `now`
+    pub fn trim(&mut self, upper_bound: Duration, now: Instant) -> bool {
         let mut trimmed = false;
 
         while let Some((time, _)) = self.by_time.first_key_value() {
-            if time.elapsed() <= upper_bound {
+            if now.saturating_duration_since(*time) <= upper_bound {
                 break;
             }
 
@@ -84,7 +81,7 @@ impl OnlineRepsContainer {
 
 #[cfg(test)]
 mod tests {
-    use mock_instant::MockClock;
+    use rsnano_core::utils::{Clock, TestClock};
 
     use super::*;
 
@@ -98,8 +95,9 @@ mod tests {
     #[test]
     fn insert_one_rep() {
         let mut container = OnlineRepsContainer::new();
+        let clock = TestClock::new();
 
-        let new_insert = container.insert(Account::from(1), Instant::now());
+        let new_insert = container.insert(Account::from(1), clock.instant());
 
         assert_eq!(container.len(), 1);
         assert_eq!(container.iter().count(), 1);
@@ -110,9 +108,10 @@ mod tests {
     #[test]
     fn insert_two_reps() {
         let mut container = OnlineRepsContainer::new();
+        let clock = TestClock::new();
 
-        let new_insert_a = container.insert(Account::from(1), Instant::now());
-        let new_insert_b = container.insert(Account::from(2), Instant::now());
+        let new_insert_a = container.insert(Account::from(1), clock.instant());
+        let new_insert_b = container.insert(Account::from(2), clock.instant());
 
         assert_eq!(container.len(), 2);
         assert_eq!(container.iter().count(), 2);
@@ -123,8 +122,9 @@ mod tests {
     #[test]
     fn insert_same_rep_twice_with_same_time() {
         let mut container = OnlineRepsContainer::new();
+        let clock = TestClock::new();
 
-        let now = Instant::now();
+        let now = clock.instant();
         let new_insert_a = container.insert(Account::from(1), now);
         let new_insert_b = container.insert(Account::from(1), now);
 
@@ -137,10 +137,11 @@ mod tests {
     #[test]
     fn insert_same_rep_twice_with_different_time() {
         let mut container = OnlineRepsContainer::new();
+        let clock = TestClock::new();
 
-        let new_insert_a = container.insert(Account::from(1), Instant::now());
-        MockClock::advance(Duration::from_secs(1));
-        let new_insert_b = container.insert(Account::from(1), Instant::now());
+        let new_insert_a = container.insert(Account::from(1), clock.instant());
+        clock.advance(Duration::from_secs(1));
+        let new_insert_b = container.insert(Account::from(1), clock.instant());
 
         assert_eq!(container.len(), 1);
         assert_eq!(container.iter().count(), 1);
@@ -152,39 +153,55 @@ mod tests {
     #[test]
     fn trimming_empty_container_does_nothing() {
         let mut container = OnlineRepsContainer::new();
-        assert_eq!(container.trim(Duration::from_secs(1)), false);
+        let clock = TestClock::new();
+        assert_eq!(
+            container.trim(Duration::from_secs(1), clock.instant()),
+            false
+        );
     }
 
     #[test]
     fn dont_trim_if_upper_bound_not_reached() {
         let mut container = OnlineRepsContainer::new();
-        container.insert(Account::from(1), Instant::now());
-        assert_eq!(container.trim(Duration::from_secs(1)), false);
+        let clock = TestClock::new();
+        container.insert(Account::from(1), clock.instant());
+        assert_eq!(
+            container.trim(Duration::from_secs(1), clock.instant()),
+            false
+        );
     }
 
     #[test]
     fn trim_if_upper_bound_reached() {
         let mut container = OnlineRepsContainer::new();
-        container.insert(Account::from(1), Instant::now());
-        MockClock::advance(Duration::from_millis(1001));
-        assert_eq!(container.trim(Duration::from_secs(1)), true);
+        let clock = TestClock::new();
+        container.insert(Account::from(1), clock.instant());
+        clock.advance(Duration::from_millis(1001));
+        assert_eq!(
+            container.trim(Duration::from_secs(1), clock.instant()),
+            true
+        );
         assert_eq!(container.len(), 0);
     }
 
     #[test]
     fn trim_multiple_entries() {
         let mut container = OnlineRepsContainer::new();
+        let clock = TestClock::new();
 
-        container.insert(Account::from(1), Instant::now());
-        container.insert(Account::from(2), Instant::now());
+        container.insert(Account::from(1), clock.instant());
+        container.insert(Account::from(2), clock.instant());
 
-        MockClock::advance(Duration::from_millis(500));
-        container.insert(Account::from(3), Instant::now());
+        clock.advance(Duration::from_millis(500));
+        container.insert(Account::from(3), clock.instant());
 
-        MockClock::advance(Duration::from_millis(1001));
-        container.insert(Account::from(4), Instant::now());
+        clock.advance(Duration::from_millis(1001));
+        container.insert(Account::from(4), clock.instant());
 
-        assert_eq!(container.trim(Duration::from_secs(1)), true);
+        assert_eq!(
+            container.trim(Duration::from_secs(1), clock.instant()),
+            true
+        );
         assert_eq!(container.len(), 1);
         assert_eq!(container.iter().next().unwrap(), &Account::from(4));
         assert_eq!(container.by_time.len(), 1);
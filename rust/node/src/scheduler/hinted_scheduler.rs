@@ -0,0 +1,326 @@
+use std::{
+    collections::HashMap,
+    mem::size_of,
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use primitive_types::U256;
+use rsnano_core::{
+    utils::{ContainerInfo, ContainerInfoComponent},
+    Amount, BlockEnum, BlockHash,
+};
+use rsnano_ledger::Ledger;
+
+use crate::{
+    config::HintedSchedulerConfig,
+    representatives::OnlineReps,
+    stats::{DetailType, Direction, StatType, Stats},
+    utils::ThreadRunner,
+    vote_cache::{TopEntry, VoteCache},
+};
+
+/// Starts a hinted election for the given block. Returns true if the election was started
+pub type InsertHintedCallback = Box<dyn Fn(&Arc<BlockEnum>) -> bool + Send + Sync>;
+
+/// Returns true if an election for the given block hash is currently active
+pub type ElectionActiveCallback = Box<dyn Fn(&BlockHash) -> bool + Send + Sync>;
+
+/// Called for a candidate whose block is not in the ledger yet, so that it can be bootstrapped
+pub type MissingBlockCallback = Box<dyn Fn(&BlockHash) + Send + Sync>;
+
+/// Returns the number of hinted elections which can still be started in the active elections container
+pub type VacancyCallback = Box<dyn Fn() -> i64 + Send + Sync>;
+
+/// How long a handled candidate is ignored before it is considered again
+const COOLDOWN: Duration = Duration::from_secs(5);
+
+/// Monitors the vote cache and starts elections for the blocks with the highest cached vote tally,
+/// which are not yet being voted on by the active elections.
+pub struct HintedScheduler {
+    scheduler: Arc<HintedSchedulerImpl>,
+    thread: ThreadRunner,
+}
+
+impl HintedScheduler {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: HintedSchedulerConfig,
+        ledger: Arc<Ledger>,
+        vote_cache: Arc<Mutex<VoteCache>>,
+        online_reps: Arc<Mutex<OnlineReps>>,
+        stats: Arc<Stats>,
+        insert_hinted: InsertHintedCallback,
+        election_active: ElectionActiveCallback,
+        missing_block: MissingBlockCallback,
+        vacancy: VacancyCallback,
+    ) -> Self {
+        Self {
+            scheduler: Arc::new(HintedSchedulerImpl {
+                config,
+                ledger,
+                vote_cache,
+                online_reps,
+                stats,
+                insert_hinted,
+                election_active,
+                missing_block,
+                vacancy,
+                cooldowns: Mutex::new(Cooldowns::default()),
+                stopped: Mutex::new(false),
+                condition: Condvar::new(),
+            }),
+            thread: ThreadRunner::new("Sched Hinted"),
+        }
+    }
+
+    pub fn start(&mut self) {
+        if !self.scheduler.config.enabled {
+            return;
+        }
+
+        let scheduler = Arc::clone(&self.scheduler);
+        self.thread.start(move || scheduler.run()).unwrap();
+    }
+
+    pub fn stop(&mut self) {
+        *self.scheduler.stopped.lock().unwrap() = true;
+        self.notify();
+        self.thread.join().unwrap();
+    }
+
+    /// Notify about changes in AEC vacancy
+    pub fn notify(&self) {
+        self.scheduler.condition.notify_all();
+    }
+
+    /// Starts hinted elections for all eligible vote cache entries.
+    /// Returns the number of elections that were started
+    pub fn activate_candidates(&self) -> usize {
+        self.scheduler.activate_candidates()
+    }
+
+    pub fn collect_container_info(&self, name: String) -> ContainerInfoComponent {
+        ContainerInfoComponent::Composite(
+            name,
+            vec![ContainerInfoComponent::Leaf(ContainerInfo {
+                name: "cooldowns".to_owned(),
+                count: self.scheduler.cooldowns.lock().unwrap().len(),
+                sizeof_element: size_of::<(BlockHash, Instant)>(),
+            })],
+        )
+    }
+}
+
+impl Drop for HintedScheduler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+struct HintedSchedulerImpl {
+    config: HintedSchedulerConfig,
+    ledger: Arc<Ledger>,
+    vote_cache: Arc<Mutex<VoteCache>>,
+    online_reps: Arc<Mutex<OnlineReps>>,
+    stats: Arc<Stats>,
+    insert_hinted: InsertHintedCallback,
+    election_active: ElectionActiveCallback,
+    missing_block: MissingBlockCallback,
+    vacancy: VacancyCallback,
+    /// Candidates which were handled recently and are skipped until their cooldown expires
+    cooldowns: Mutex<Cooldowns>,
+    stopped: Mutex<bool>,
+    condition: Condvar,
+}
+
+impl HintedSchedulerImpl {
+    fn run(&self) {
+        let mut stopped = self.stopped.lock().unwrap();
+        while !*stopped {
+            // Periodically wakeup for condition checking
+            // We are not notified every time new vote arrives in the vote cache as that happens too often
+            stopped = self
+                .condition
+                .wait_timeout(
+                    stopped,
+                    Duration::from_millis(self.config.check_interval_ms),
+                )
+                .unwrap()
+                .0;
+
+            if !*stopped {
+                drop(stopped);
+                self.stats
+                    .inc(StatType::Hinting, DetailType::Loop, Direction::In);
+                self.activate_candidates();
+                stopped = self.stopped.lock().unwrap();
+            }
+        }
+    }
+
+    fn activate_candidates(&self) -> usize {
+        // It is possible that the tally becomes outdated due to changes in the trended online weight.
+        // However this is only used for hinting. Elections do an independent tally calculation
+        let now = Instant::now();
+        let cooling_down = {
+            let mut cooldowns = self.cooldowns.lock().unwrap();
+            cooldowns.purge(now);
+            cooldowns.len()
+        };
+        let vacancy = self.vacancy();
+        if vacancy == 0 {
+            return 0;
+        }
+
+        // Candidates in cooldown are skipped, so fetch enough entries to still fill the vacancy
+        let candidates = self
+            .vote_cache
+            .lock()
+            .unwrap()
+            .top(self.tally_threshold(), vacancy + cooling_down);
+        let mut started = 0;
+        for candidate in candidates {
+            if self.vacancy() == 0 {
+                self.stats
+                    .inc(StatType::Hinting, DetailType::Overfill, Direction::In);
+                break;
+            }
+            if !self.cooldowns.lock().unwrap().check(candidate.hash, now) {
+                self.stats
+                    .inc(StatType::Hinting, DetailType::Cooldown, Direction::In);
+                continue;
+            }
+            if self.activate(&candidate) {
+                started += 1;
+            }
+        }
+        started
+    }
+
+    fn activate(&self, candidate: &TopEntry) -> bool {
+        if (self.election_active)(&candidate.hash) {
+            return false;
+        }
+
+        let block = {
+            let txn = self.ledger.read_txn();
+            let Some(block) = self.ledger.get_block(&txn, &candidate.hash) else {
+                (self.missing_block)(&candidate.hash);
+                self.stats
+                    .inc(StatType::Hinting, DetailType::MissingBlock, Direction::In);
+                return false;
+            };
+
+            if self.ledger.block_confirmed(&txn, &candidate.hash) {
+                self.stats.inc(
+                    StatType::Hinting,
+                    DetailType::AlreadyConfirmed,
+                    Direction::In,
+                );
+                return false;
+            }
+
+            if !self.ledger.dependents_confirmed(&txn, &block) {
+                self.stats.inc(
+                    StatType::Hinting,
+                    DetailType::DependentUnconfirmed,
+                    Direction::In,
+                );
+                return false;
+            }
+            Arc::new(block)
+        };
+
+        let inserted = (self.insert_hinted)(&block);
+        self.stats.inc(
+            StatType::Hinting,
+            if inserted {
+                DetailType::Insert
+            } else {
+                DetailType::InsertFailed
+            },
+            Direction::In,
+        );
+        inserted
+    }
+
+    /// Number of hinted elections which can still be started
+    fn vacancy(&self) -> usize {
+        (self.vacancy)().max(0) as usize
+    }
+
+    fn tally_threshold(&self) -> Amount {
+        let quorum_delta = self.online_reps.lock().unwrap().delta();
+        tally_threshold(quorum_delta, self.config.hinting_threshold_percent)
+    }
+}
+
+/// Remembers when candidates were handled, so that the same vote cache entries are not
+/// activated or bootstrapped again on every loop
+#[derive(Default)]
+struct Cooldowns {
+    entries: HashMap<BlockHash, Instant>,
+}
+
+impl Cooldowns {
+    /// Returns true and starts the cooldown if the candidate is not cooling down yet
+    fn check(&mut self, hash: BlockHash, now: Instant) -> bool {
+        match self.entries.get(&hash) {
+            Some(until) if *until > now => false,
+            _ => {
+                self.entries.insert(hash, now + COOLDOWN);
+                true
+            }
+        }
+    }
+
+    fn purge(&mut self, now: Instant) {
+        self.entries.retain(|_, until| *until > now);
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+fn tally_threshold(quorum_delta: Amount, hinting_threshold_percent: u32) -> Amount {
+    let threshold =
+        U256::from(quorum_delta.number()) * U256::from(hinting_threshold_percent) / U256::from(100);
+    Amount::raw(threshold.as_u128())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_is_percentage_of_quorum_delta() {
+        assert_eq!(tally_threshold(Amount::raw(1000), 10), Amount::raw(100));
+        assert_eq!(tally_threshold(Amount::raw(1000), 0), Amount::zero());
+        assert_eq!(tally_threshold(Amount::MAX, 100), Amount::MAX);
+    }
+
+    #[test]
+    fn handled_candidate_cools_down() {
+        let mut cooldowns = Cooldowns::default();
+        let now = Instant::now();
+        let hash = BlockHash::from(1);
+
+        assert!(cooldowns.check(hash, now));
+        assert!(!cooldowns.check(hash, now + Duration::from_secs(1)));
+        assert!(cooldowns.check(BlockHash::from(2), now));
+        assert!(cooldowns.check(hash, now + COOLDOWN));
+    }
+
+    #[test]
+    fn purge_expired_cooldowns() {
+        let mut cooldowns = Cooldowns::default();
+        let now = Instant::now();
+        cooldowns.check(BlockHash::from(1), now);
+        cooldowns.check(BlockHash::from(2), now + Duration::from_secs(2));
+
+        cooldowns.purge(now + COOLDOWN);
+        assert_eq!(cooldowns.len(), 1);
+    }
+}
@@ -0,0 +1,3 @@
+mod hinted_scheduler;
+
+pub use hinted_scheduler::*;
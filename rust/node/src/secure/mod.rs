@@ -1,4 +1,5 @@
 mod bootstrap_constants;
+mod network_definition;
 mod network_params;
 mod node_constants;
 mod portmapping_constants;
@@ -6,6 +7,7 @@ mod utility;
 mod voting_constants;
 
 pub use bootstrap_constants::*;
+pub use network_definition::NetworkDefinition;
 pub use network_params::*;
 pub use node_constants::*;
 pub use portmapping_constants::*;
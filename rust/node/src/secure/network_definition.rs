@@ -0,0 +1,422 @@
+use std::{
+    path::{Component, Path},
+    str::FromStr,
+    sync::Mutex,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use num_traits::FromPrimitive;
+use once_cell::sync::Lazy;
+use rsnano_core::{
+    deserialize_block_json, utils::SerdePropertyTree, work::WorkThresholds, Account, BlockEnum,
+    Networks,
+};
+use serde_json::{Map, Value};
+use toml_edit::Document;
+
+use super::{default_work_thresholds, BUILT_IN_DATA_DIRS};
+use crate::config::NetworkConstants;
+
+static ACTIVE_DEFINITION: Lazy<Mutex<Option<NetworkDefinition>>> = Lazy::new(|| Mutex::new(None));
+
+/// Describes a private network which is based on one of the built-in networks,
+/// but has its own network id, data directory, genesis block, epoch signers and work thresholds.
+/// No default peers are used for a private network.
+///
+/// The definition is read from a JSON or TOML file with these keys:
+/// * `network`: the base network ("live", "beta", "dev" or "test")
+/// * `network_id`: two ASCII characters which identify the network in every message header,
+///   must differ from the ids of the built-in networks
+/// * `data_dir`: name of the data directory in the home directory, must differ from the
+///   directories of the built-in networks
+/// * `genesis`: the genesis open block in the JSON block format
+/// * `port`: optional peering port, defaults to the port of the base network
+/// * `epoch_v1_signer`, `epoch_v2_signer`: optional, default to the genesis account
+/// * `final_votes_canary_account`, `final_votes_canary_height`: optional, default to the genesis account and 1
+/// * `work`: optional table with the hex encoded thresholds `epoch_1`, `epoch_2` and `epoch_2_receive`
+#[derive(Clone)]
+pub struct NetworkDefinition {
+    pub base_network: Networks,
+    pub network_id: u16,
+    pub data_dir: String,
+    pub default_node_port: Option<u16>,
+    pub genesis: BlockEnum,
+    pub epoch_v1_signer: Account,
+    pub epoch_v2_signer: Account,
+    pub final_votes_canary_account: Account,
+    pub final_votes_canary_height: u64,
+    pub work: WorkThresholds,
+}
+
+impl NetworkDefinition {
+    /// Loads a definition file. Files with a `.json` extension are parsed as JSON, all others as TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read network definition {:?}", path))?;
+        let is_json = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("json"))
+            .unwrap_or(false);
+        if is_json {
+            Self::parse_json(&content)
+        } else {
+            Self::parse_toml(&content)
+        }
+    }
+
+    pub fn parse_json(content: &str) -> Result<Self> {
+        Self::from_value(&serde_json::from_str(content)?)
+    }
+
+    pub fn parse_toml(content: &str) -> Result<Self> {
+        let doc = Document::from_str(content)?;
+        Self::from_value(&toml_table_to_json(doc.as_table()))
+    }
+
+    fn from_value(value: &Value) -> Result<Self> {
+        let base_network: Networks = get_str(value, "network")?
+            .ok_or_else(|| anyhow!("network missing"))?
+            .parse()?;
+        let network_id = get_network_id(value)?;
+        let data_dir = get_data_dir(value)?;
+        let default_node_port = match value.get("port") {
+            Some(port) => Some(
+                port.as_u64()
+                    .and_then(|p| u16::try_from(p).ok())
+                    .ok_or_else(|| anyhow!("port is not a valid port number"))?,
+            ),
+            None => None,
+        };
+
+        let genesis = value
+            .get("genesis")
+            .ok_or_else(|| anyhow!("genesis missing"))?;
+        let genesis = deserialize_block_json(&SerdePropertyTree::parse(&genesis.to_string())?)
+            .context("invalid genesis block")?;
+        let genesis_account = genesis.account();
+
+        let epoch_v1_signer = get_account(value, "epoch_v1_signer")?.unwrap_or(genesis_account);
+        let epoch_v2_signer = get_account(value, "epoch_v2_signer")?.unwrap_or(genesis_account);
+        let final_votes_canary_account =
+            get_account(value, "final_votes_canary_account")?.unwrap_or(genesis_account);
+        let final_votes_canary_height = match value.get("final_votes_canary_height") {
+            Some(height) => height
+                .as_u64()
+                .ok_or_else(|| anyhow!("final_votes_canary_height is not a number"))?,
+            None => 1,
+        };
+
+        let base_work = default_work_thresholds(base_network);
+        let work = match value.get("work") {
+            Some(work) => WorkThresholds::new(
+                get_threshold(work, "epoch_1")?.unwrap_or(base_work.epoch_1),
+                get_threshold(work, "epoch_2")?.unwrap_or(base_work.epoch_2),
+                get_threshold(work, "epoch_2_receive")?.unwrap_or(base_work.epoch_2_receive),
+            ),
+            None => base_work.clone(),
+        };
+
+        Ok(Self {
+            base_network,
+            network_id,
+            data_dir,
+            default_node_port,
+            genesis,
+            epoch_v1_signer,
+            epoch_v2_signer,
+            final_votes_canary_account,
+            final_votes_canary_height,
+            work,
+        })
+    }
+
+    /// Makes this definition the one used for all network params of its base network
+    /// and switches the active network to the base network
+    pub fn activate(self) {
+        NetworkConstants::set_active_network(self.base_network);
+        *ACTIVE_DEFINITION.lock().unwrap() = Some(self);
+    }
+
+    /// The definition which was activated on startup, if any
+    pub fn active() -> Option<NetworkDefinition> {
+        ACTIVE_DEFINITION.lock().unwrap().clone()
+    }
+}
+
+fn get_str<'a>(value: &'a Value, key: &str) -> Result<Option<&'a str>> {
+    match value.get(key) {
+        Some(v) => Ok(Some(
+            v.as_str()
+                .ok_or_else(|| anyhow!("{} is not a string", key))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+fn get_network_id(value: &Value) -> Result<u16> {
+    let id = get_str(value, "network_id")?.ok_or_else(|| anyhow!("network_id missing"))?;
+    let [a, b] = id.as_bytes() else {
+        bail!("network_id must consist of two ASCII characters");
+    };
+    if !id.is_ascii() {
+        bail!("network_id must consist of two ASCII characters");
+    }
+    let network_id = u16::from_be_bytes([*a, *b]);
+    if Networks::from_u16(network_id).is_some() {
+        bail!("network_id must differ from the ids of the built-in networks");
+    }
+    Ok(network_id)
+}
+
+fn get_data_dir(value: &Value) -> Result<String> {
+    let data_dir = get_str(value, "data_dir")?.ok_or_else(|| anyhow!("data_dir missing"))?;
+    let mut components = Path::new(data_dir).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        bail!("data_dir must be a directory name");
+    }
+    if BUILT_IN_DATA_DIRS.contains(&data_dir) {
+        bail!("data_dir must differ from the data directories of the built-in networks");
+    }
+    Ok(data_dir.to_owned())
+}
+
+fn get_account(value: &Value, key: &str) -> Result<Option<Account>> {
+    match get_str(value, key)? {
+        Some(s) => {
+            let account = Account::decode_account(s)
+                .or_else(|_| Account::decode_hex(s))
+                .with_context(|| format!("{} is not a valid account", key))?;
+            Ok(Some(account))
+        }
+        None => Ok(None),
+    }
+}
+
+fn get_threshold(value: &Value, key: &str) -> Result<Option<u64>> {
+    match get_str(value, key)? {
+        Some(s) => {
+            let threshold = u64::from_str_radix(s.trim_start_matches("0x"), 16)
+                .with_context(|| format!("work threshold {} is not a hex number", key))?;
+            Ok(Some(threshold))
+        }
+        None => Ok(None),
+    }
+}
+
+fn toml_table_to_json<'a>(
+    entries: impl IntoIterator<Item = (&'a str, &'a toml_edit::Item)>,
+) -> Value {
+    let mut map = Map::new();
+    for (key, item) in entries {
+        if let Some(value) = toml_item_to_json(item) {
+            map.insert(key.to_owned(), value);
+        }
+    }
+    Value::Object(map)
+}
+
+fn toml_item_to_json(item: &toml_edit::Item) -> Option<Value> {
+    match item {
+        toml_edit::Item::None => None,
+        toml_edit::Item::Value(value) => Some(toml_value_to_json(value)),
+        toml_edit::Item::Table(table) => Some(toml_table_to_json(table.iter())),
+        toml_edit::Item::ArrayOfTables(tables) => Some(Value::Array(
+            tables
+                .iter()
+                .map(|t| toml_table_to_json(t.iter()))
+                .collect(),
+        )),
+    }
+}
+
+fn toml_value_to_json(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Value::from(*f.value()),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::String(d.value().to_string()),
+        toml_edit::Value::Array(array) => {
+            Value::Array(array.iter().map(toml_value_to_json).collect())
+        }
+        toml_edit::Value::InlineTable(table) => {
+            let mut map = Map::new();
+            for (key, value) in table.iter() {
+                map.insert(key.to_owned(), toml_value_to_json(value));
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NetworkParams;
+    use rsnano_core::{Epoch, DEV_GENESIS_KEY};
+
+    const GENESIS_JSON: &str = r###"{
+        "type": "open",
+        "source": "B0311EA55708D6A53C75CDBF88300259C6D018522FE3D4D0A242E431F9E8B6D0",
+        "representative": "xrb_3e3j5tkog48pnny9dmfzj1r16pg8t1e76dz5tmac6iq689wyjfpiij4txtdo",
+        "account": "xrb_3e3j5tkog48pnny9dmfzj1r16pg8t1e76dz5tmac6iq689wyjfpiij4txtdo",
+        "work": "7b42a00ee91d5810",
+        "signature": "ECDA914373A2F0CA1296475BAEE40500A7F0A7AD72A5A80C81D7FAB7F6C802B2CC7DB50F5DD0FB25B2EF11761FA7344A158DD5A700B21BD47DE5BD0F63153A02"
+    }"###;
+
+    const SIGNER: &str = "nano_1betagoxpxwykx4kw86dnhosc8t3s7ix8eeentwkcg1hbpez1outjrcyg4n1";
+
+    #[test]
+    fn parse_json_with_defaults() {
+        let definition = NetworkDefinition::parse_json(&format!(
+            r#"{{"network": "beta", "network_id": "XA", "data_dir": "NanoPrivate", "genesis": {}}}"#,
+            GENESIS_JSON
+        ))
+        .unwrap();
+
+        assert_eq!(definition.base_network, Networks::NanoBetaNetwork);
+        assert_eq!(definition.network_id, u16::from_be_bytes(*b"XA"));
+        assert_eq!(definition.data_dir, "NanoPrivate");
+        assert_eq!(definition.default_node_port, None);
+        assert_eq!(definition.genesis.account(), DEV_GENESIS_KEY.public_key());
+        assert_eq!(definition.epoch_v1_signer, DEV_GENESIS_KEY.public_key());
+        assert_eq!(definition.epoch_v2_signer, DEV_GENESIS_KEY.public_key());
+        assert_eq!(definition.final_votes_canary_height, 1);
+        assert_eq!(
+            definition.work.epoch_1,
+            WorkThresholds::publish_beta().epoch_1
+        );
+    }
+
+    #[test]
+    fn parse_toml() {
+        let definition = NetworkDefinition::parse_toml(&format!(
+            r#"
+            network = "test"
+            network_id = "XB"
+            data_dir = "NanoPrivate"
+            port = 7000
+            epoch_v2_signer = "{}"
+            final_votes_canary_height = 42
+
+            [genesis]
+            type = "open"
+            source = "B0311EA55708D6A53C75CDBF88300259C6D018522FE3D4D0A242E431F9E8B6D0"
+            representative = "xrb_3e3j5tkog48pnny9dmfzj1r16pg8t1e76dz5tmac6iq689wyjfpiij4txtdo"
+            account = "xrb_3e3j5tkog48pnny9dmfzj1r16pg8t1e76dz5tmac6iq689wyjfpiij4txtdo"
+            work = "7b42a00ee91d5810"
+            signature = "ECDA914373A2F0CA1296475BAEE40500A7F0A7AD72A5A80C81D7FAB7F6C802B2CC7DB50F5DD0FB25B2EF11761FA7344A158DD5A700B21BD47DE5BD0F63153A02"
+
+            [work]
+            epoch_1 = "ffffff0000000000"
+            "#,
+            SIGNER
+        ))
+        .unwrap();
+
+        assert_eq!(definition.base_network, Networks::NanoTestNetwork);
+        assert_eq!(definition.default_node_port, Some(7000));
+        assert_eq!(definition.genesis.account(), DEV_GENESIS_KEY.public_key());
+        assert_eq!(
+            definition.epoch_v2_signer,
+            Account::decode_account(SIGNER).unwrap()
+        );
+        assert_eq!(definition.final_votes_canary_height, 42);
+        assert_eq!(definition.work.epoch_1, 0xffffff0000000000);
+        assert_eq!(
+            definition.work.epoch_2,
+            WorkThresholds::publish_test().epoch_2
+        );
+    }
+
+    #[test]
+    fn genesis_missing() {
+        let result = NetworkDefinition::parse_json(
+            r#"{"network": "beta", "network_id": "XA", "data_dir": "NanoPrivate"}"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn network_id_is_required() {
+        let result = NetworkDefinition::parse_json(&format!(
+            r#"{{"network": "beta", "data_dir": "NanoPrivate", "genesis": {}}}"#,
+            GENESIS_JSON
+        ));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn network_id_of_built_in_network_is_rejected() {
+        let result = NetworkDefinition::parse_json(&format!(
+            r#"{{"network": "beta", "network_id": "RB", "data_dir": "NanoPrivate", "genesis": {}}}"#,
+            GENESIS_JSON
+        ));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn data_dir_is_required() {
+        let result = NetworkDefinition::parse_json(&format!(
+            r#"{{"network": "beta", "network_id": "XA", "genesis": {}}}"#,
+            GENESIS_JSON
+        ));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn data_dir_of_built_in_network_is_rejected() {
+        for data_dir in ["NanoBeta", "../Nano", "/tmp/private"] {
+            let result = NetworkDefinition::parse_json(&format!(
+                r#"{{"network": "beta", "network_id": "XA", "data_dir": "{}", "genesis": {}}}"#,
+                data_dir, GENESIS_JSON
+            ));
+            assert!(result.is_err(), "{} was accepted", data_dir);
+        }
+    }
+
+    #[test]
+    fn invalid_network() {
+        let result = NetworkDefinition::parse_json(&format!(
+            r#"{{"network": "foo", "network_id": "XA", "data_dir": "NanoPrivate", "genesis": {}}}"#,
+            GENESIS_JSON
+        ));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn create_network_params_from_definition() {
+        let definition = NetworkDefinition::parse_json(&format!(
+            r#"{{"network": "beta", "network_id": "XA", "data_dir": "NanoPrivate", "port": 7000, "genesis": {}, "epoch_v2_signer": "{}", "work": {{"epoch_2": "fffffff900000000"}}}}"#,
+            GENESIS_JSON, SIGNER
+        ))
+        .unwrap();
+
+        let params = NetworkParams::from_definition(&definition);
+
+        assert_eq!(params.network.current_network, Networks::NanoBetaNetwork);
+        assert_eq!(params.network.network_id, u16::from_be_bytes(*b"XA"));
+        assert!(params.network.is_custom_network());
+        assert_eq!(params.network.default_node_port, 7000);
+        assert_eq!(params.ledger.genesis_account, DEV_GENESIS_KEY.public_key());
+        assert_eq!(
+            params.ledger.genesis.read().unwrap().hash(),
+            definition.genesis.hash()
+        );
+        assert_eq!(
+            params.ledger.epochs.signer(Epoch::Epoch1),
+            Some(&DEV_GENESIS_KEY.public_key())
+        );
+        assert_eq!(
+            params.ledger.epochs.signer(Epoch::Epoch2),
+            Some(&Account::decode_account(SIGNER).unwrap())
+        );
+        assert_eq!(params.work.epoch_2, 0xfffffff900000000);
+        assert_eq!(params.ledger.work.epoch_2, 0xfffffff900000000);
+        assert_eq!(params.network.work.epoch_2, 0xfffffff900000000);
+    }
+}
@@ -1,9 +1,12 @@
+use super::NetworkDefinition;
 use crate::{
     config::NetworkConstants, BootstrapConstants, NodeConstants, PortmappingConstants,
     VotingConstants,
 };
 use once_cell::sync::Lazy;
-use rsnano_core::{work::WorkThresholds, Networks};
+use rsnano_core::{
+    epoch_v1_link, epoch_v2_link, utils::PropertyTreeWriter, work::WorkThresholds, Epoch, Networks,
+};
 use rsnano_ledger::LedgerConstants;
 
 pub static DEV_NETWORK_PARAMS: Lazy<NetworkParams> =
@@ -21,16 +24,101 @@ pub struct NetworkParams {
 }
 
 impl NetworkParams {
+    /// Creates the params of the given network. If a network definition for
+    /// that network was activated, its genesis, epochs and work thresholds are used.
     pub fn new(network: Networks) -> Self {
-        let work = if network == Networks::NanoLiveNetwork {
-            WorkThresholds::publish_full()
-        } else if network == Networks::NanoBetaNetwork {
-            WorkThresholds::publish_beta()
-        } else if network == Networks::NanoTestNetwork {
-            WorkThresholds::publish_test()
-        } else {
-            WorkThresholds::publish_dev()
-        };
+        match NetworkDefinition::active() {
+            Some(definition) if definition.base_network == network => {
+                Self::from_definition(&definition)
+            }
+            _ => Self::with_work(default_work_thresholds(network).clone(), network),
+        }
+    }
+
+    pub fn from_definition(definition: &NetworkDefinition) -> Self {
+        let mut params = Self::with_work(definition.work.clone(), definition.base_network);
+        params.network.apply_definition(definition);
+        let ledger = &mut params.ledger;
+        ledger.set_genesis(definition.genesis.clone());
+        ledger
+            .epochs
+            .add(Epoch::Epoch1, definition.epoch_v1_signer, epoch_v1_link());
+        ledger
+            .epochs
+            .add(Epoch::Epoch2, definition.epoch_v2_signer, epoch_v2_link());
+        ledger.final_votes_canary_account = definition.final_votes_canary_account;
+        ledger.final_votes_canary_height = definition.final_votes_canary_height;
+        params
+    }
+
+    /// Writes the constants which wallets and tools need to know about the network,
+    /// as returned by the `network_params` RPC
+    pub fn serialize_json(&self, writer: &mut dyn PropertyTreeWriter) -> anyhow::Result<()> {
+        let network = &self.network;
+        writer.put_string("network", network.current_network.as_str())?;
+        writer.put_string(
+            "protocol_version",
+            &network.protocol.version_using.to_string(),
+        )?;
+        writer.put_string(
+            "protocol_version_min",
+            &network.protocol.version_min.to_string(),
+        )?;
+        writer.put_string(
+            "bootstrap_protocol_version_min",
+            &network.protocol.bootstrap_version_min.to_string(),
+        )?;
+        writer.put_string(
+            "principal_weight_factor",
+            &network.principal_weight_factor.to_string(),
+        )?;
+        writer.put_string("kdf_work", &self.kdf_work.to_string())?;
+
+        let ledger = &self.ledger;
+        writer.put_string("genesis_account", &ledger.genesis_account.encode_account())?;
+        writer.put_string("genesis_amount", &ledger.genesis_amount.to_string_dec())?;
+        writer.put_string("burn_account", &ledger.burn_account.encode_account())?;
+        writer.put_string(
+            "final_votes_canary_account",
+            &ledger.final_votes_canary_account.encode_account(),
+        )?;
+        writer.put_string(
+            "final_votes_canary_height",
+            &ledger.final_votes_canary_height.to_string(),
+        )?;
+
+        let mut epochs = writer.new_writer();
+        for epoch in [Epoch::Epoch1, Epoch::Epoch2] {
+            let (Some(signer), Some(link)) = (ledger.epochs.signer(epoch), ledger.epochs.link(epoch)) else {
+                continue;
+            };
+            let mut entry = writer.new_writer();
+            entry.put_string("epoch", &(epoch as u8 - Epoch::Epoch0 as u8).to_string())?;
+            entry.put_string("signer", &signer.encode_account())?;
+            entry.put_string("link", &link.encode_hex())?;
+            epochs.push_back("", entry.as_ref());
+        }
+        writer.add_child("epochs", epochs.as_ref());
+
+        let mut work = writer.new_writer();
+        work.put_string("base", &format!("{:016x}", self.work.base))?;
+        work.put_string("entry", &format!("{:016x}", self.work.entry))?;
+        work.put_string("epoch_1", &format!("{:016x}", self.work.epoch_1))?;
+        work.put_string("epoch_2", &format!("{:016x}", self.work.epoch_2))?;
+        work.put_string(
+            "epoch_2_receive",
+            &format!("{:016x}", self.work.epoch_2_receive),
+        )?;
+        writer.add_child("work_thresholds", work.as_ref());
+
+        let mut voting = writer.new_writer();
+        voting.put_string("max_cache", &self.voting.max_cache.to_string())?;
+        voting.put_string("delay", &self.voting.delay_s.to_string())?;
+        writer.add_child("voting", voting.as_ref());
+        Ok(())
+    }
+
+    fn with_work(work: WorkThresholds, network: Networks) -> Self {
         let network_constants = NetworkConstants::new(work.clone(), network);
         let kdf_full_work = 64 * 1024;
         let kdf_dev_work = 8;
@@ -41,7 +129,7 @@ impl NetworkParams {
                 kdf_full_work
             },
             work: work.clone(),
-            ledger: LedgerConstants::new(work.clone(), network),
+            ledger: LedgerConstants::new(work, network),
             voting: VotingConstants::new(&network_constants),
             node: NodeConstants::new(&network_constants),
             portmapping: PortmappingConstants::new(&network_constants),
@@ -50,3 +138,42 @@ impl NetworkParams {
         }
     }
 }
+
+pub(crate) fn default_work_thresholds(network: Networks) -> &'static WorkThresholds {
+    match network {
+        Networks::NanoLiveNetwork => WorkThresholds::publish_full(),
+        Networks::NanoBetaNetwork => WorkThresholds::publish_beta(),
+        Networks::NanoTestNetwork => WorkThresholds::publish_test(),
+        _ => WorkThresholds::publish_dev(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::utils::SerdePropertyTree;
+
+    #[test]
+    fn serialize_json() {
+        let params = NetworkParams::new(Networks::NanoLiveNetwork);
+        let mut tree = SerdePropertyTree::new();
+
+        params.serialize_json(&mut tree).unwrap();
+
+        let json = tree.value();
+        assert_eq!(json["network"], "live");
+        assert_eq!(
+            json["protocol_version"],
+            params.network.protocol.version_using.to_string()
+        );
+        assert_eq!(json["work_thresholds"]["base"], "fffffff800000000");
+        assert_eq!(
+            json["work_thresholds"]["epoch_2_receive"],
+            "fffffe0000000000"
+        );
+        let epochs = json["epochs"].as_array().unwrap();
+        assert_eq!(epochs.len(), 2);
+        assert_eq!(epochs[1]["epoch"], "2");
+        assert_eq!(json["voting"]["delay"], "15");
+    }
+}
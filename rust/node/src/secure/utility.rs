@@ -3,6 +3,7 @@ use rsnano_core::Networks;
 use std::{path::PathBuf, sync::Mutex};
 use uuid::Uuid;
 
+use super::NetworkDefinition;
 use crate::config::NetworkConstants;
 
 //todo refactor: this global state thing is not a good solution
@@ -11,16 +12,28 @@ static ALL_UNIQUE_PATHS: Lazy<Mutex<Vec<PathBuf>>> = Lazy::new(|| Mutex::new(Vec
 pub fn working_path() -> Option<PathBuf> {
     working_path_for(NetworkConstants::active_network())
 }
+/// The data directories of the built-in networks, a private network must use another one
+pub(crate) const BUILT_IN_DATA_DIRS: [&str; 4] = ["NanoDev", "NanoBeta", "Nano", "NanoTest"];
+
+/// The data directory of the network. If a network definition based on that network
+/// is active, the data directory of the definition is used.
 pub fn working_path_for(network: Networks) -> Option<PathBuf> {
     dirs::home_dir().and_then(|mut path| {
-        let subdir = match network {
-            Networks::Invalid => return None,
-            Networks::NanoDevNetwork => "NanoDev",
-            Networks::NanoBetaNetwork => "NanoBeta",
-            Networks::NanoLiveNetwork => "Nano",
-            Networks::NanoTestNetwork => "NanoTest",
-        };
-        path.push(subdir);
+        match NetworkDefinition::active() {
+            Some(definition) if definition.base_network == network => {
+                path.push(definition.data_dir);
+            }
+            _ => {
+                let subdir = match network {
+                    Networks::Invalid => return None,
+                    Networks::NanoDevNetwork => BUILT_IN_DATA_DIRS[0],
+                    Networks::NanoBetaNetwork => BUILT_IN_DATA_DIRS[1],
+                    Networks::NanoLiveNetwork => BUILT_IN_DATA_DIRS[2],
+                    Networks::NanoTestNetwork => BUILT_IN_DATA_DIRS[3],
+                };
+                path.push(subdir);
+            }
+        }
         Some(path)
     })
 }
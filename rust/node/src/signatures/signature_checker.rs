@@ -1,7 +1,7 @@
 use anyhow::Result;
 use scoped_threadpool::Pool;
 
-use rsnano_core::{validate_message_batch, PublicKey, Signature};
+use rsnano_core::{validate_batch, PublicKey, Signature};
 use std::{
     hint::spin_loop,
     sync::{
@@ -125,15 +125,11 @@ impl SignatureChecker {
     }
 
     pub fn verify_batch(check_set: &mut SignatureCheckSetBatch) {
-        validate_message_batch(
-            check_set.messages,
-            check_set.pub_keys,
-            check_set.signatures,
-            check_set.verifications,
-        );
+        let results = validate_batch(check_set.messages, check_set.pub_keys, check_set.signatures);
 
-        let result = check_set.verifications.iter().all(|&x| x == 0 || x == 1);
-        assert!(result);
+        for (verification, is_valid) in check_set.verifications.iter_mut().zip(results) {
+            *verification = is_valid as i32;
+        }
     }
 
     fn verify_batch_async(&self, check_set: &mut SignatureCheckSet, pool: &mut Pool) {
@@ -2,7 +2,6 @@ use std::{
     collections::VecDeque,
     ops::Deref,
     sync::{Arc, Condvar, Mutex, RwLock},
-    thread::JoinHandle,
     time::Duration,
 };
 
@@ -12,6 +11,7 @@ use rsnano_core::{
 };
 
 use super::{SignatureCheckSet, SignatureChecker};
+use crate::utils::ThreadRunner;
 
 #[derive(Default)]
 pub struct Builder {
@@ -74,16 +74,10 @@ impl Builder {
         });
 
         let thread_clone = thread.clone();
-        let join_handle = std::thread::Builder::new()
-            .name("State block sig".to_string())
-            .spawn(move || {
-                thread_clone.run();
-            })?;
-
-        Ok(StateBlockSignatureVerification {
-            join_handle: Some(join_handle),
-            thread,
-        })
+        let mut runner = ThreadRunner::new("State block sig");
+        runner.start(move || thread_clone.run())?;
+
+        Ok(StateBlockSignatureVerification { runner, thread })
     }
 }
 
@@ -99,7 +93,7 @@ pub struct StateBlockSignatureVerificationResult {
 }
 
 pub struct StateBlockSignatureVerification {
-    join_handle: Option<JoinHandle<()>>,
+    runner: ThreadRunner,
     thread: Arc<StateBlockSignatureVerificationThread>,
 }
 
@@ -127,9 +121,9 @@ impl StateBlockSignatureVerification {
             lk.stopped = true;
         }
 
-        if let Some(handle) = self.join_handle.take() {
+        if self.runner.is_running() {
             self.thread.condition.notify_one();
-            handle.join()?;
+            self.runner.join()?;
         }
         Ok(())
     }
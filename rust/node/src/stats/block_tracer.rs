@@ -0,0 +1,304 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use num_derive::FromPrimitive;
+use rsnano_core::{utils::PropertyTreeWriter, BlockHash};
+
+/// Events in the life of a block, in the order in which they usually happen
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, FromPrimitive)]
+pub enum BlockTraceEvent {
+    FirstSeen,
+    Validated,
+    Inserted,
+    ElectionStarted,
+    Quorum,
+    Cemented,
+    Broadcast,
+}
+
+impl BlockTraceEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockTraceEvent::FirstSeen => "first_seen",
+            BlockTraceEvent::Validated => "validated",
+            BlockTraceEvent::Inserted => "inserted",
+            BlockTraceEvent::ElectionStarted => "election_started",
+            BlockTraceEvent::Quorum => "quorum",
+            BlockTraceEvent::Cemented => "cemented",
+            BlockTraceEvent::Broadcast => "broadcast",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BlockTrace {
+    pub hash: BlockHash,
+    /// Milliseconds since the unix epoch at which the block was seen first
+    pub first_seen_unix_ms: u64,
+    /// Time since the block was seen first. Each event is recorded only once
+    pub events: Vec<(BlockTraceEvent, Duration)>,
+}
+
+impl BlockTrace {
+    pub fn summary(&self) -> String {
+        let events: Vec<_> = self
+            .events
+            .iter()
+            .map(|(event, elapsed)| format!("{} +{}ms", event.as_str(), elapsed.as_millis()))
+            .collect();
+        format!("Block trace {}: {}", self.hash, events.join(", "))
+    }
+}
+
+/// Records the propagation of a sample of blocks through the node, from the
+/// first time the block is seen until it is cemented.
+/// Blocks are sampled by their hash, so that all nodes trace the same blocks.
+pub struct BlockTracer {
+    sample_interval: u64,
+    max_traces: usize,
+    data: Mutex<TraceData>,
+}
+
+#[derive(Default)]
+struct TraceData {
+    traces: HashMap<BlockHash, (Instant, BlockTrace)>,
+    /// Traced blocks in the order in which they were seen first
+    order: VecDeque<BlockHash>,
+}
+
+impl BlockTracer {
+    pub const DEFAULT_MAX_TRACES: usize = 1024;
+
+    /// Traces one of `sample_interval` blocks. A `sample_interval` of 0 disables the tracer
+    pub fn new(sample_interval: u64, max_traces: usize) -> Self {
+        Self {
+            sample_interval,
+            max_traces,
+            data: Mutex::new(TraceData::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sample_interval > 0
+    }
+
+    pub fn is_sampled(&self, hash: &BlockHash) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash.as_bytes()[..8]);
+        u64::from_le_bytes(bytes) % self.sample_interval == 0
+    }
+
+    /// Only `FirstSeen` starts a new trace. If too many blocks are traced, the
+    /// oldest trace is dropped. Returns true if the block is traced
+    pub fn record(
+        &self,
+        hash: &BlockHash,
+        event: BlockTraceEvent,
+        now: Instant,
+        unix_ms: u64,
+    ) -> bool {
+        if !self.is_sampled(hash) {
+            return false;
+        }
+
+        let mut data = self.data.lock().unwrap();
+        if let Some((first_seen, trace)) = data.traces.get_mut(hash) {
+            if !trace.events.iter().any(|(e, _)| *e == event) {
+                let elapsed = now.saturating_duration_since(*first_seen);
+                trace.events.push((event, elapsed));
+            }
+            return true;
+        }
+
+        if event != BlockTraceEvent::FirstSeen {
+            return false;
+        }
+        while data.traces.len() >= self.max_traces {
+            let Some(oldest) = data.order.pop_front() else {
+                break;
+            };
+            data.traces.remove(&oldest);
+        }
+        let trace = BlockTrace {
+            hash: *hash,
+            first_seen_unix_ms: unix_ms,
+            events: vec![(BlockTraceEvent::FirstSeen, Duration::ZERO)],
+        };
+        data.traces.insert(*hash, (now, trace));
+        data.order.push_back(*hash);
+        true
+    }
+
+    pub fn trace(&self, hash: &BlockHash) -> Option<BlockTrace> {
+        let data = self.data.lock().unwrap();
+        data.traces.get(hash).map(|(_, trace)| trace.clone())
+    }
+
+    /// All traces, the oldest first
+    pub fn traces(&self) -> Vec<BlockTrace> {
+        let data = self.data.lock().unwrap();
+        data.order
+            .iter()
+            .filter_map(|hash| data.traces.get(hash))
+            .map(|(_, trace)| trace.clone())
+            .collect()
+    }
+
+    pub fn serialize_json(&self, writer: &mut dyn PropertyTreeWriter) -> anyhow::Result<()> {
+        let mut traces = writer.new_writer();
+        for trace in self.traces() {
+            let mut entry = writer.new_writer();
+            entry.put_string("hash", &trace.hash.to_string())?;
+            entry.put_string("first_seen", &trace.first_seen_unix_ms.to_string())?;
+            let mut events = writer.new_writer();
+            for (event, elapsed) in &trace.events {
+                events.put_string(event.as_str(), &elapsed.as_millis().to_string())?;
+            }
+            entry.add_child("events", events.as_ref());
+            traces.push_back("", entry.as_ref());
+        }
+        writer.add_child("traces", traces.as_ref());
+        writer.put_string("sample_interval", &self.sample_interval.to_string())?;
+        Ok(())
+    }
+}
+
+impl Default for BlockTracer {
+    fn default() -> Self {
+        Self::new(0, Self::DEFAULT_MAX_TRACES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace_all() -> BlockTracer {
+        BlockTracer::new(1, BlockTracer::DEFAULT_MAX_TRACES)
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let tracer = BlockTracer::default();
+        assert!(!tracer.is_enabled());
+        assert!(!tracer.record(
+            &BlockHash::from(1),
+            BlockTraceEvent::FirstSeen,
+            Instant::now(),
+            0
+        ));
+        assert!(tracer.traces().is_empty());
+    }
+
+    #[test]
+    fn record_events() {
+        let tracer = trace_all();
+        let hash = BlockHash::from(1);
+        let start = Instant::now();
+
+        tracer.record(&hash, BlockTraceEvent::FirstSeen, start, 1000);
+        tracer.record(
+            &hash,
+            BlockTraceEvent::Inserted,
+            start + Duration::from_millis(5),
+            0,
+        );
+        tracer.record(
+            &hash,
+            BlockTraceEvent::Cemented,
+            start + Duration::from_millis(300),
+            0,
+        );
+
+        let trace = tracer.trace(&hash).unwrap();
+        assert_eq!(trace.first_seen_unix_ms, 1000);
+        assert_eq!(
+            trace.events,
+            vec![
+                (BlockTraceEvent::FirstSeen, Duration::ZERO),
+                (BlockTraceEvent::Inserted, Duration::from_millis(5)),
+                (BlockTraceEvent::Cemented, Duration::from_millis(300)),
+            ]
+        );
+        assert_eq!(
+            trace.summary(),
+            format!(
+                "Block trace {}: first_seen +0ms, inserted +5ms, cemented +300ms",
+                hash
+            )
+        );
+    }
+
+    #[test]
+    fn trace_starts_when_block_is_seen_first() {
+        let tracer = trace_all();
+        let hash = BlockHash::from(1);
+        assert!(!tracer.record(&hash, BlockTraceEvent::Cemented, Instant::now(), 0));
+        assert_eq!(tracer.trace(&hash), None);
+    }
+
+    #[test]
+    fn keep_first_occurrence_of_event() {
+        let tracer = trace_all();
+        let hash = BlockHash::from(1);
+        let start = Instant::now();
+        tracer.record(&hash, BlockTraceEvent::FirstSeen, start, 0);
+        tracer.record(
+            &hash,
+            BlockTraceEvent::Broadcast,
+            start + Duration::from_millis(1),
+            0,
+        );
+        tracer.record(
+            &hash,
+            BlockTraceEvent::Broadcast,
+            start + Duration::from_millis(2),
+            0,
+        );
+        tracer.record(
+            &hash,
+            BlockTraceEvent::FirstSeen,
+            start + Duration::from_millis(3),
+            0,
+        );
+
+        assert_eq!(
+            tracer.trace(&hash).unwrap().events,
+            vec![
+                (BlockTraceEvent::FirstSeen, Duration::ZERO),
+                (BlockTraceEvent::Broadcast, Duration::from_millis(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn sample_by_hash() {
+        let tracer = BlockTracer::new(4, BlockTracer::DEFAULT_MAX_TRACES);
+        let sampled = BlockHash::from_bytes([8; 32]);
+        let mut not_sampled = [8; 32];
+        not_sampled[0] = 9;
+        let not_sampled = BlockHash::from_bytes(not_sampled);
+
+        assert!(tracer.is_sampled(&sampled));
+        assert!(!tracer.is_sampled(&not_sampled));
+    }
+
+    #[test]
+    fn drop_oldest_trace_when_full() {
+        let tracer = BlockTracer::new(1, 2);
+        let now = Instant::now();
+        for i in 1..=3u64 {
+            tracer.record(&BlockHash::from(i), BlockTraceEvent::FirstSeen, now, 0);
+        }
+
+        let hashes: Vec<_> = tracer.traces().iter().map(|t| t.hash).collect();
+        assert_eq!(hashes, vec![BlockHash::from(2), BlockHash::from(3)]);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rsnano_core::{utils::PropertyTreeWriter, BlockHash};
+
+/// Latency percentiles of the blocks which were cemented within one minute
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct LatencyPercentiles {
+    /// Start of the minute in seconds since the unix epoch
+    pub minute: u64,
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Measures the time between the arrival of a block in the block processor
+/// and its cementation and aggregates the latencies in buckets of one minute
+pub struct ConfirmationLatency {
+    data: Mutex<LatencyData>,
+    max_tracked: usize,
+    max_buckets: usize,
+}
+
+struct LatencyData {
+    arrivals: HashMap<BlockHash, Instant>,
+    /// Arrivals in insertion order. Entries of cemented blocks are removed lazily
+    arrival_order: VecDeque<(BlockHash, Instant)>,
+    buckets: VecDeque<(u64, Vec<u64>)>,
+}
+
+impl ConfirmationLatency {
+    pub const DEFAULT_MAX_TRACKED: usize = 1024 * 64;
+    pub const DEFAULT_MAX_BUCKETS: usize = 60;
+
+    pub fn new(max_tracked: usize, max_buckets: usize) -> Self {
+        Self {
+            data: Mutex::new(LatencyData {
+                arrivals: HashMap::new(),
+                arrival_order: VecDeque::new(),
+                buckets: VecDeque::new(),
+            }),
+            max_tracked,
+            max_buckets,
+        }
+    }
+
+    /// Only the first arrival of a block is recorded. If too many blocks are
+    /// tracked, the oldest arrival is forgotten.
+    pub fn block_arrived(&self, hash: BlockHash, now: Instant) {
+        let mut data = self.data.lock().unwrap();
+        if data.arrivals.contains_key(&hash) {
+            return;
+        }
+        while data.arrivals.len() >= self.max_tracked {
+            let Some((oldest, arrival)) = data.arrival_order.pop_front() else {
+                break;
+            };
+            if data.arrivals.get(&oldest) == Some(&arrival) {
+                data.arrivals.remove(&oldest);
+            }
+        }
+        if data.arrival_order.len() >= self.max_tracked * 2 {
+            let LatencyData {
+                arrivals,
+                arrival_order,
+                ..
+            } = &mut *data;
+            arrival_order.retain(|(hash, arrival)| arrivals.get(hash) == Some(arrival));
+        }
+        data.arrivals.insert(hash, now);
+        data.arrival_order.push_back((hash, now));
+    }
+
+    /// Returns the confirmation latency if the arrival of the block was recorded.
+    /// `unix_secs` selects the one minute bucket
+    pub fn block_cemented(
+        &self,
+        hash: &BlockHash,
+        now: Instant,
+        unix_secs: u64,
+    ) -> Option<Duration> {
+        let mut data = self.data.lock().unwrap();
+        let arrival = data.arrivals.remove(hash)?;
+        let latency = now.saturating_duration_since(arrival);
+        let minute = unix_secs - unix_secs % 60;
+        match data.buckets.back_mut() {
+            Some((m, latencies)) if *m == minute => latencies.push(latency.as_millis() as u64),
+            _ => {
+                data.buckets
+                    .push_back((minute, vec![latency.as_millis() as u64]));
+                while data.buckets.len() > self.max_buckets {
+                    data.buckets.pop_front();
+                }
+            }
+        }
+        Some(latency)
+    }
+
+    /// Percentiles of each recorded minute, the oldest minute first
+    pub fn percentiles(&self) -> Vec<LatencyPercentiles> {
+        let data = self.data.lock().unwrap();
+        data.buckets
+            .iter()
+            .map(|(minute, latencies)| {
+                let mut sorted = latencies.clone();
+                sorted.sort_unstable();
+                LatencyPercentiles {
+                    minute: *minute,
+                    count: sorted.len(),
+                    p50_ms: percentile(&sorted, 50),
+                    p90_ms: percentile(&sorted, 90),
+                    p99_ms: percentile(&sorted, 99),
+                    max_ms: sorted.last().cloned().unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
+    /// Number of blocks which arrived but aren't cemented yet
+    pub fn tracked_len(&self) -> usize {
+        self.data.lock().unwrap().arrivals.len()
+    }
+
+    pub fn serialize_json(&self, writer: &mut dyn PropertyTreeWriter) -> anyhow::Result<()> {
+        let mut buckets = writer.new_writer();
+        for bucket in self.percentiles() {
+            let mut entry = writer.new_writer();
+            entry.put_string("minute", &bucket.minute.to_string())?;
+            entry.put_string("count", &bucket.count.to_string())?;
+            entry.put_string("p50", &bucket.p50_ms.to_string())?;
+            entry.put_string("p90", &bucket.p90_ms.to_string())?;
+            entry.put_string("p99", &bucket.p99_ms.to_string())?;
+            entry.put_string("max", &bucket.max_ms.to_string())?;
+            buckets.push_back("", entry.as_ref());
+        }
+        writer.add_child("buckets", buckets.as_ref());
+        writer.put_string("tracked", &self.tracked_len().to_string())?;
+        Ok(())
+    }
+}
+
+impl Default for ConfirmationLatency {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_TRACKED, Self::DEFAULT_MAX_BUCKETS)
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let latency = ConfirmationLatency::default();
+        assert!(latency.percentiles().is_empty());
+        assert_eq!(latency.tracked_len(), 0);
+    }
+
+    #[test]
+    fn measure_latency() {
+        let latency = ConfirmationLatency::default();
+        let start = Instant::now();
+        latency.block_arrived(BlockHash::from(1), start);
+
+        let result =
+            latency.block_cemented(&BlockHash::from(1), start + Duration::from_millis(250), 125);
+
+        assert_eq!(result, Some(Duration::from_millis(250)));
+        assert_eq!(latency.tracked_len(), 0);
+        assert_eq!(
+            latency.percentiles(),
+            vec![LatencyPercentiles {
+                minute: 120,
+                count: 1,
+                p50_ms: 250,
+                p90_ms: 250,
+                p99_ms: 250,
+                max_ms: 250
+            }]
+        );
+    }
+
+    #[test]
+    fn ignore_unknown_blocks() {
+        let latency = ConfirmationLatency::default();
+        assert_eq!(
+            latency.block_cemented(&BlockHash::from(1), Instant::now(), 0),
+            None
+        );
+        assert!(latency.percentiles().is_empty());
+    }
+
+    #[test]
+    fn keep_first_arrival() {
+        let latency = ConfirmationLatency::default();
+        let start = Instant::now();
+        latency.block_arrived(BlockHash::from(1), start);
+        latency.block_arrived(BlockHash::from(1), start + Duration::from_millis(100));
+
+        let result =
+            latency.block_cemented(&BlockHash::from(1), start + Duration::from_millis(300), 0);
+
+        assert_eq!(result, Some(Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn calculate_percentiles() {
+        let latency = ConfirmationLatency::default();
+        let start = Instant::now();
+        for i in 1..=100u64 {
+            latency.block_arrived(BlockHash::from(i), start);
+            latency.block_cemented(&BlockHash::from(i), start + Duration::from_millis(i), 60);
+        }
+
+        let buckets = latency.percentiles();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].count, 100);
+        assert_eq!(buckets[0].p50_ms, 50);
+        assert_eq!(buckets[0].p90_ms, 90);
+        assert_eq!(buckets[0].p99_ms, 99);
+        assert_eq!(buckets[0].max_ms, 100);
+    }
+
+    #[test]
+    fn one_bucket_per_minute() {
+        let latency = ConfirmationLatency::new(10, 2);
+        let start = Instant::now();
+        for (i, secs) in [(1u64, 0u64), (2, 59), (3, 60), (4, 130)] {
+            latency.block_arrived(BlockHash::from(i), start);
+            latency.block_cemented(&BlockHash::from(i), start, secs);
+        }
+
+        let minutes: Vec<_> = latency.percentiles().iter().map(|b| b.minute).collect();
+        assert_eq!(minutes, vec![60, 120]);
+    }
+
+    #[test]
+    fn forget_oldest_arrival_when_full() {
+        let latency = ConfirmationLatency::new(2, 60);
+        let now = Instant::now();
+        latency.block_arrived(BlockHash::from(1), now);
+        latency.block_arrived(BlockHash::from(2), now);
+        latency.block_arrived(BlockHash::from(3), now);
+
+        assert_eq!(latency.tracked_len(), 2);
+        assert_eq!(latency.block_cemented(&BlockHash::from(1), now, 0), None);
+    }
+
+    #[test]
+    fn cemented_blocks_dont_count_towards_the_limit() {
+        let latency = ConfirmationLatency::new(2, 60);
+        let now = Instant::now();
+        for i in 1..=10u64 {
+            latency.block_arrived(BlockHash::from(i), now);
+            latency.block_cemented(&BlockHash::from(i), now, 0);
+        }
+        latency.block_arrived(BlockHash::from(11), now);
+        latency.block_arrived(BlockHash::from(12), now);
+
+        assert_eq!(latency.tracked_len(), 2);
+        assert!(latency
+            .block_cemented(&BlockHash::from(11), now, 0)
+            .is_some());
+    }
+}
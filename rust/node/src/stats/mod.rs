@@ -1,3 +1,5 @@
+mod block_tracer;
+mod confirmation_latency;
 mod histogram;
 mod message_parse_status;
 mod socket_stats;
@@ -8,6 +10,8 @@ mod stats_log_sink;
 mod ledger_stats;
 pub use ledger_stats::LedgerStats;
 
+pub use block_tracer::{BlockTrace, BlockTraceEvent, BlockTracer};
+pub use confirmation_latency::{ConfirmationLatency, LatencyPercentiles};
 pub use socket_stats::SocketStats;
 pub use stats::{stat_type_as_str, DetailType, Direction, StatType, Stats};
 pub use stats_config::StatsConfig;
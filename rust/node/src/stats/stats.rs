@@ -147,6 +147,7 @@ pub enum StatType {
     BootstrapAscendingConnections,
     BootstrapAscendingThread,
     BootstrapAscendingAccounts,
+    Socks5,
 }
 
 impl StatType {
@@ -157,7 +158,7 @@ impl StatType {
 
 // Optional detail type
 #[repr(u8)]
-#[derive(FromPrimitive, Serialize, Clone, Copy)]
+#[derive(FromPrimitive, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum DetailType {
     All = 0,
@@ -209,6 +210,11 @@ pub enum DetailType {
     BalanceMismatch,
     RepresentativeMismatch,
     BlockPosition,
+    HeightOverflow,
+    TimestampInFuture,
+    ConfirmedFork,
+    AccountBatchLimit,
+    ForkLimit,
 
     // message specific
     NotAType,
@@ -251,6 +257,7 @@ pub enum DetailType {
     VoteIndeterminate,
     VoteInvalid,
     VoteOverflow,
+    VoteOverflowPrincipal,
 
     // election specific
     VoteNew,
@@ -267,6 +274,9 @@ pub enum DetailType {
     GenerateVote,
     GenerateVoteNormal,
     GenerateVoteFinal,
+    ElectionConfirmed,
+    ElectionConfirmReqs,
+    ElectionConfirmReqLimit,
 
     // election types
     Normal,
@@ -343,6 +353,7 @@ pub enum DetailType {
     EmptyPayload,
     CleanupOutdated,
     CleanupDead,
+    NewerProtocolMajority,
 
     // vote generator
     GeneratorBroadcasts,
@@ -352,6 +363,9 @@ pub enum DetailType {
 
     // hinting
     MissingBlock,
+    DependentUnconfirmed,
+    AlreadyConfirmed,
+    Cooldown,
 
     // bootstrap server
     Response,
@@ -393,6 +407,7 @@ pub enum DetailType {
     Track,
     Timeout,
     NothingNew,
+    InvalidBlocks,
 
     // bootstrap ascending connections
     Connect,
@@ -434,6 +449,29 @@ pub enum DetailType {
     // optimistic
     PopGap,
     PopLeaf,
+
+    // socks5
+    ProxyConnected,
+    ProxyConnectError,
+    ProxyHandshakeError,
+    ProxyResolveError,
+    ProxyFallback,
+
+    // election block broadcasting
+    BroadcastBlockInitial,
+    BroadcastBlockRepeat,
+    BroadcastBlockWinnerChanged,
+    BroadcastBlockConfirmed,
+
+    // network id enforcement
+    InvalidNetworkIgnored,
+    ExcludedWrongNetwork,
+
+    // pruning
+    RequestsPruned,
+
+    // vote packing
+    RequestsPackedVotes,
 }
 
 impl DetailType {
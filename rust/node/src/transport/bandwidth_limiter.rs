@@ -4,7 +4,7 @@ use std::sync::Mutex;
 impl From<TrafficType> for BandwidthLimitType {
     fn from(value: TrafficType) -> Self {
         match value {
-            TrafficType::Generic => BandwidthLimitType::Standard,
+            TrafficType::Generic | TrafficType::Priority => BandwidthLimitType::Standard,
             TrafficType::Bootstrap => BandwidthLimitType::Bootstrap,
         }
     }
@@ -4,10 +4,10 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex,
     },
-    time::{SystemTime, UNIX_EPOCH},
+    time::SystemTime,
 };
 
-use rsnano_core::Account;
+use rsnano_core::{utils::Timestamp, Account};
 
 use crate::{
     messages::Message,
@@ -21,9 +21,9 @@ use super::{
 };
 
 pub struct FakeChannelData {
-    last_bootstrap_attempt: SystemTime,
-    last_packet_received: SystemTime,
-    last_packet_sent: SystemTime,
+    last_bootstrap_attempt: Timestamp,
+    last_packet_received: Timestamp,
+    last_packet_sent: Timestamp,
     node_id: Option<Account>,
 }
 
@@ -54,9 +54,9 @@ impl ChannelFake {
             io_ctx,
             temporary: AtomicBool::new(false),
             channel_mutex: Mutex::new(FakeChannelData {
-                last_bootstrap_attempt: UNIX_EPOCH,
-                last_packet_received: now,
-                last_packet_sent: now,
+                last_bootstrap_attempt: Timestamp::EPOCH,
+                last_packet_received: now.into(),
+                last_packet_sent: now.into(),
                 node_id: None,
             }),
             limiter,
@@ -132,28 +132,28 @@ impl Channel for ChannelFake {
         self.temporary.store(temporary, Ordering::SeqCst)
     }
 
-    fn get_last_bootstrap_attempt(&self) -> SystemTime {
+    fn get_last_bootstrap_attempt(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_bootstrap_attempt
     }
 
-    fn set_last_bootstrap_attempt(&self, time: SystemTime) {
+    fn set_last_bootstrap_attempt(&self, time: Timestamp) {
         self.channel_mutex.lock().unwrap().last_bootstrap_attempt = time;
     }
 
-    fn get_last_packet_received(&self) -> SystemTime {
+    fn get_last_packet_received(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_packet_received
     }
 
-    fn set_last_packet_received(&self, instant: SystemTime) {
-        self.channel_mutex.lock().unwrap().last_packet_received = instant;
+    fn set_last_packet_received(&self, time: Timestamp) {
+        self.channel_mutex.lock().unwrap().last_packet_received = time;
     }
 
-    fn get_last_packet_sent(&self) -> SystemTime {
+    fn get_last_packet_sent(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_packet_sent
     }
 
-    fn set_last_packet_sent(&self, instant: SystemTime) {
-        self.channel_mutex.lock().unwrap().last_packet_sent = instant;
+    fn set_last_packet_sent(&self, time: Timestamp) {
+        self.channel_mutex.lock().unwrap().last_packet_sent = time;
     }
 
     fn get_node_id(&self) -> Option<Account> {
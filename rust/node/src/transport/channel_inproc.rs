@@ -4,11 +4,11 @@ use std::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex,
     },
-    time::{SystemTime, UNIX_EPOCH},
+    time::SystemTime,
 };
 
 use message_deserializer::MessageDeserializer;
-use rsnano_core::Account;
+use rsnano_core::{utils::Timestamp, Account};
 
 use crate::{
     config::NetworkConstants,
@@ -25,9 +25,9 @@ use super::{
 };
 
 pub struct InProcChannelData {
-    last_bootstrap_attempt: SystemTime,
-    last_packet_received: SystemTime,
-    last_packet_sent: SystemTime,
+    last_bootstrap_attempt: Timestamp,
+    last_packet_received: Timestamp,
+    last_packet_sent: Timestamp,
     node_id: Option<Account>,
 }
 
@@ -70,9 +70,9 @@ impl ChannelInProc {
             channel_id,
             temporary: AtomicBool::new(false),
             channel_mutex: Mutex::new(InProcChannelData {
-                last_bootstrap_attempt: UNIX_EPOCH,
-                last_packet_received: now,
-                last_packet_sent: now,
+                last_bootstrap_attempt: Timestamp::EPOCH,
+                last_packet_received: now.into(),
+                last_packet_sent: now.into(),
                 node_id: Some(source_node_id),
             }),
             network_constants,
@@ -89,6 +89,39 @@ impl ChannelInProc {
         }
     }
 
+    /// Creates a channel which delivers messages back to the local node.
+    /// Locally generated messages (own votes, own blocks) are processed by the same
+    /// inbound pipeline as messages from the network.
+    #[allow(clippy::too_many_arguments)]
+    pub fn loopback(
+        channel_id: usize,
+        now: SystemTime,
+        network_constants: NetworkConstants,
+        network_filter: Arc<NetworkFilter>,
+        stats: Arc<Stats>,
+        limiter: Arc<OutboundBandwidthLimiter>,
+        inbound: InboundCallback,
+        io_ctx: Arc<dyn IoContext>,
+        endpoint: SocketAddr,
+        node_id: Account,
+    ) -> Self {
+        Self::new(
+            channel_id,
+            now,
+            network_constants,
+            network_filter,
+            stats,
+            limiter,
+            inbound.clone(),
+            inbound,
+            io_ctx,
+            endpoint,
+            endpoint,
+            node_id,
+            node_id,
+        )
+    }
+
     pub fn send(
         &self,
         message_a: &dyn Message,
@@ -139,9 +172,12 @@ impl ChannelInProc {
             if ec.is_err() {
                 return;
             }
-            let Some(msg) = msg else { return; };
+            let Some(msg) = msg else {
+                return;
+            };
             let filter = Arc::new(NetworkFilter::new(100000));
-            // we create a temporary channel for the reply path, in case the receiver of the message wants to reply
+            // we create a temporary channel for the reply path, in case the receiver of the message wants to reply.
+            // The reply channel is owned by the receiver and delivers to the sender, so source and destination are swapped.
             let remote_channel = Arc::new(ChannelEnum::InProc(ChannelInProc::new(
                 1,
                 SystemTime::now(),
@@ -149,13 +185,13 @@ impl ChannelInProc {
                 filter,
                 stats.clone(),
                 limiter,
-                source_inbound,
                 destination_inbound.clone(),
+                source_inbound,
                 io_ctx,
-                source_endpoint,
                 destination_endpoint,
-                source_node_id,
+                source_endpoint,
                 destination_node_id,
+                source_node_id,
             )));
 
             // process message
@@ -171,6 +207,7 @@ impl ChannelInProc {
         });
 
         self.send_buffer_impl(buffer_a, callback_wrapper);
+        self.set_last_packet_sent(Timestamp::now());
 
         if let Some(cb) = callback_a {
             let buffer_size = buffer_a.len();
@@ -209,7 +246,7 @@ impl ChannelInProc {
     }
 
     pub fn network_version(&self) -> u8 {
-        self.network_constants.protocol_version
+        self.network_constants.protocol.version_using
     }
 }
 
@@ -223,28 +260,28 @@ impl Channel for ChannelInProc {
             .store(temporary, std::sync::atomic::Ordering::SeqCst);
     }
 
-    fn get_last_bootstrap_attempt(&self) -> SystemTime {
+    fn get_last_bootstrap_attempt(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_bootstrap_attempt
     }
 
-    fn set_last_bootstrap_attempt(&self, time: SystemTime) {
+    fn set_last_bootstrap_attempt(&self, time: Timestamp) {
         self.channel_mutex.lock().unwrap().last_bootstrap_attempt = time;
     }
 
-    fn get_last_packet_received(&self) -> SystemTime {
+    fn get_last_packet_received(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_packet_received
     }
 
-    fn set_last_packet_received(&self, instant: SystemTime) {
-        self.channel_mutex.lock().unwrap().last_packet_received = instant;
+    fn set_last_packet_received(&self, time: Timestamp) {
+        self.channel_mutex.lock().unwrap().last_packet_received = time;
     }
 
-    fn get_last_packet_sent(&self) -> SystemTime {
+    fn get_last_packet_sent(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_packet_sent
     }
 
-    fn set_last_packet_sent(&self, instant: SystemTime) {
-        self.channel_mutex.lock().unwrap().last_packet_sent = instant;
+    fn set_last_packet_sent(&self, time: Timestamp) {
+        self.channel_mutex.lock().unwrap().last_packet_sent = time;
     }
 
     fn get_node_id(&self) -> Option<Account> {
@@ -267,3 +304,139 @@ impl Channel for ChannelInProc {
         super::TransportType::Loopback
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::STUB_NETWORK_CONSTANTS,
+        messages::{Keepalive, MessageType},
+        utils::StubIoContext,
+    };
+    use std::net::{IpAddr, Ipv6Addr};
+
+    type Received = Arc<Mutex<Vec<(MessageType, Arc<ChannelEnum>)>>>;
+
+    fn recording_callback() -> (InboundCallback, Received) {
+        let received: Received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback: InboundCallback = Arc::new(move |msg, channel| {
+            received_clone
+                .lock()
+                .unwrap()
+                .push((msg.header().message_type(), channel));
+        });
+        (callback, received)
+    }
+
+    fn endpoint(port: u16) -> SocketAddr {
+        SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), port)
+    }
+
+    fn create_channel(
+        stats: Arc<Stats>,
+        source_inbound: InboundCallback,
+        destination_inbound: InboundCallback,
+    ) -> ChannelInProc {
+        ChannelInProc::new(
+            42,
+            SystemTime::now(),
+            STUB_NETWORK_CONSTANTS.clone(),
+            Arc::new(NetworkFilter::new(1000)),
+            stats,
+            Arc::new(OutboundBandwidthLimiter::default()),
+            source_inbound,
+            destination_inbound,
+            Arc::new(StubIoContext::new()),
+            endpoint(1000),
+            endpoint(2000),
+            Account::from(1),
+            Account::from(2),
+        )
+    }
+
+    fn send_keepalive(channel: &ChannelInProc) {
+        channel.send(
+            &Keepalive::new(&STUB_NETWORK_CONSTANTS),
+            None,
+            BufferDropPolicy::NoLimiterDrop,
+            TrafficType::Generic,
+        );
+    }
+
+    #[test]
+    fn delivers_message_to_destination() {
+        let stats = Arc::new(Stats::default());
+        let (source_inbound, source_received) = recording_callback();
+        let (destination_inbound, destination_received) = recording_callback();
+        let channel = create_channel(stats.clone(), source_inbound, destination_inbound);
+
+        assert_eq!(channel.get_node_id(), Some(Account::from(1)));
+        send_keepalive(&channel);
+
+        let received = destination_received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(received[0].0 == MessageType::Keepalive);
+        assert!(source_received.lock().unwrap().is_empty());
+        assert_eq!(
+            stats.count(StatType::Message, DetailType::Keepalive, Direction::In),
+            1
+        );
+        assert_eq!(
+            stats.count(StatType::Message, DetailType::Keepalive, Direction::Out),
+            1
+        );
+    }
+
+    #[test]
+    fn reply_channel_delivers_to_sender() {
+        let (source_inbound, source_received) = recording_callback();
+        let (destination_inbound, destination_received) = recording_callback();
+        let channel = create_channel(
+            Arc::new(Stats::default()),
+            source_inbound,
+            destination_inbound,
+        );
+
+        send_keepalive(&channel);
+
+        let reply_channel = destination_received.lock().unwrap()[0].1.clone();
+        let ChannelEnum::InProc(reply_channel) = reply_channel.as_ref() else {
+            panic!("expected inproc channel")
+        };
+        assert_eq!(reply_channel.source_endpoint, endpoint(2000));
+        assert_eq!(reply_channel.destination_endpoint, endpoint(1000));
+        assert_eq!(reply_channel.get_node_id(), Some(Account::from(2)));
+
+        send_keepalive(reply_channel);
+        assert_eq!(source_received.lock().unwrap().len(), 1);
+        assert_eq!(destination_received.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn loopback_delivers_to_own_node() {
+        let (inbound, received) = recording_callback();
+        let channel = ChannelInProc::loopback(
+            1,
+            SystemTime::now(),
+            STUB_NETWORK_CONSTANTS.clone(),
+            Arc::new(NetworkFilter::new(1000)),
+            Arc::new(Stats::default()),
+            Arc::new(OutboundBandwidthLimiter::default()),
+            inbound,
+            Arc::new(StubIoContext::new()),
+            endpoint(1000),
+            Account::from(1),
+        );
+
+        send_keepalive(&channel);
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let ChannelEnum::InProc(reply_channel) = received[0].1.as_ref() else {
+            panic!("expected inproc channel")
+        };
+        assert_eq!(reply_channel.source_endpoint, endpoint(1000));
+        assert_eq!(reply_channel.get_node_id(), Some(Account::from(1)));
+    }
+}
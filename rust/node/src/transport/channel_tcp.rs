@@ -5,10 +5,10 @@ use std::{
         atomic::{AtomicBool, AtomicU8, Ordering},
         Arc, Mutex, MutexGuard, Weak,
     },
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime},
 };
 
-use rsnano_core::Account;
+use rsnano_core::{utils::Timestamp, Account};
 
 use super::{
     write_queue::WriteCallback, BufferDropPolicy, Channel, OutboundBandwidthLimiter, Socket,
@@ -19,6 +19,9 @@ use crate::{
     utils::{ErrorCode, IoContext},
 };
 
+/// Maximum time a gracefully closed channel gets to flush its write queue
+pub const CLOSE_GRACEFULLY_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub trait IChannelTcpObserverWeakPtr: Send + Sync {
     fn lock(&self) -> Option<Arc<dyn ChannelTcpObserver>>;
 }
@@ -33,9 +36,9 @@ pub trait ChannelTcpObserver: Send + Sync {
 }
 
 pub struct TcpChannelData {
-    last_bootstrap_attempt: SystemTime,
-    last_packet_received: SystemTime,
-    last_packet_sent: SystemTime,
+    last_bootstrap_attempt: Timestamp,
+    last_packet_received: Timestamp,
+    last_packet_sent: Timestamp,
     node_id: Option<Account>,
     pub remote_endpoint: SocketAddr,
     pub peering_endpoint: Option<SocketAddr>,
@@ -67,9 +70,9 @@ impl ChannelTcp {
         Self {
             channel_id,
             channel_mutex: Mutex::new(TcpChannelData {
-                last_bootstrap_attempt: UNIX_EPOCH,
-                last_packet_received: now,
-                last_packet_sent: now,
+                last_bootstrap_attempt: Timestamp::EPOCH,
+                last_packet_received: now.into(),
+                last_packet_sent: now.into(),
                 node_id: None,
                 remote_endpoint: SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
                 peering_endpoint: None,
@@ -186,6 +189,14 @@ impl ChannelTcp {
         }
     }
 
+    /// Stops accepting new sends, flushes the already queued messages and closes
+    /// the socket afterwards, so that the peer doesn't receive a truncated message.
+    pub fn close_gracefully(&self) {
+        if let Some(socket) = self.socket.upgrade() {
+            socket.close_gracefully(CLOSE_GRACEFULLY_TIMEOUT);
+        }
+    }
+
     pub fn max(&self, traffic_type: TrafficType) -> bool {
         self.socket
             .upgrade()
@@ -237,28 +248,28 @@ impl Channel for ChannelTcp {
         self.temporary.store(temporary, Ordering::SeqCst);
     }
 
-    fn get_last_bootstrap_attempt(&self) -> SystemTime {
+    fn get_last_bootstrap_attempt(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_bootstrap_attempt
     }
 
-    fn set_last_bootstrap_attempt(&self, time: SystemTime) {
+    fn set_last_bootstrap_attempt(&self, time: Timestamp) {
         self.channel_mutex.lock().unwrap().last_bootstrap_attempt = time;
     }
 
-    fn get_last_packet_received(&self) -> SystemTime {
+    fn get_last_packet_received(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_packet_received
     }
 
-    fn set_last_packet_received(&self, instant: SystemTime) {
-        self.channel_mutex.lock().unwrap().last_packet_received = instant;
+    fn set_last_packet_received(&self, time: Timestamp) {
+        self.channel_mutex.lock().unwrap().last_packet_received = time;
     }
 
-    fn get_last_packet_sent(&self) -> SystemTime {
+    fn get_last_packet_sent(&self) -> Timestamp {
         self.channel_mutex.lock().unwrap().last_packet_sent
     }
 
-    fn set_last_packet_sent(&self, instant: SystemTime) {
-        self.channel_mutex.lock().unwrap().last_packet_sent = instant;
+    fn set_last_packet_sent(&self, time: Timestamp) {
+        self.channel_mutex.lock().unwrap().last_packet_sent = time;
     }
 
     fn get_node_id(&self) -> Option<Account> {
@@ -286,7 +297,8 @@ impl Drop for ChannelTcp {
     fn drop(&mut self) {
         // Close socket. Exception: socket is used by bootstrap_server
         if let Some(socket) = self.socket.upgrade() {
-            if !self.temporary.load(Ordering::Relaxed) {
+            // A draining socket closes itself as soon as the write queue is flushed
+            if !self.temporary.load(Ordering::Relaxed) && !socket.is_draining() {
                 socket.close();
             }
         }
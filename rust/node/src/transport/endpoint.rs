@@ -0,0 +1,201 @@
+use std::{
+    fmt::Display,
+    net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use rsnano_core::EndpointKey;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A peer endpoint in canonical form.
+/// IPv4 addresses are stored as IPv4-mapped IPv6 addresses and the flow info and scope id
+/// are dropped, so that the same peer always compares and hashes equally,
+/// no matter if its address was received as IPv4 or as IPv4-mapped IPv6 address.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Endpoint(SocketAddrV6);
+
+impl Endpoint {
+    pub fn new(ip: IpAddr, port: u16) -> Self {
+        Self(SocketAddrV6::new(canonical_ip(ip), port, 0, 0))
+    }
+
+    pub fn ip(&self) -> &Ipv6Addr {
+        self.0.ip()
+    }
+
+    pub fn port(&self) -> u16 {
+        self.0.port()
+    }
+
+    pub fn is_ipv4(&self) -> bool {
+        self.0.ip().to_ipv4_mapped().is_some()
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        !self.is_ipv4()
+    }
+
+    pub fn as_v6(&self) -> SocketAddrV6 {
+        self.0
+    }
+
+    pub fn as_socket_addr(&self) -> SocketAddr {
+        SocketAddr::V6(self.0)
+    }
+}
+
+impl From<SocketAddr> for Endpoint {
+    fn from(value: SocketAddr) -> Self {
+        Self::new(value.ip(), value.port())
+    }
+}
+
+impl From<SocketAddrV6> for Endpoint {
+    fn from(value: SocketAddrV6) -> Self {
+        Self::new(IpAddr::V6(*value.ip()), value.port())
+    }
+}
+
+impl From<Endpoint> for SocketAddr {
+    fn from(value: Endpoint) -> Self {
+        value.as_socket_addr()
+    }
+}
+
+impl From<Endpoint> for SocketAddrV6 {
+    fn from(value: Endpoint) -> Self {
+        value.as_v6()
+    }
+}
+
+impl From<Endpoint> for EndpointKey {
+    fn from(value: Endpoint) -> Self {
+        EndpointKey::new(value.ip().octets(), value.port())
+    }
+}
+
+impl From<&EndpointKey> for Endpoint {
+    fn from(value: &EndpointKey) -> Self {
+        Self::from(value.to_socket_addr())
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Parses `[::1]:7075` or `1.2.3.4:7075`. Hostnames are not resolved
+impl FromStr for Endpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let addr = SocketAddr::from_str(s).map_err(|_| anyhow!("invalid endpoint: {}", s))?;
+        Ok(Self::from(addr))
+    }
+}
+
+impl Serialize for Endpoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Endpoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Endpoint::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Converts an IPv4 address into an IPv4-mapped IPv6 address. IPv6 addresses are returned unchanged.
+pub fn canonical_ip(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::HashSet,
+        net::{Ipv4Addr, SocketAddrV4},
+    };
+
+    #[test]
+    fn ipv4_is_mapped() {
+        let endpoint = Endpoint::from(SocketAddr::V4(SocketAddrV4::new(
+            Ipv4Addr::new(1, 2, 3, 4),
+            7075,
+        )));
+        assert_eq!(*endpoint.ip(), Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped());
+        assert_eq!(endpoint.port(), 7075);
+        assert!(endpoint.is_ipv4());
+    }
+
+    #[test]
+    fn ipv4_and_mapped_ipv6_are_equal() {
+        let v4 = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(1, 2, 3, 4), 7075));
+        let mapped = SocketAddr::V6(SocketAddrV6::new(
+            Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped(),
+            7075,
+            0,
+            0,
+        ));
+
+        let mut set = HashSet::new();
+        set.insert(Endpoint::from(v4));
+        set.insert(Endpoint::from(mapped));
+
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn ignore_flow_info_and_scope_id() {
+        let a = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7075, 0, 0);
+        let b = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7075, 1, 2);
+        assert_eq!(Endpoint::from(a), Endpoint::from(b));
+    }
+
+    #[test]
+    fn ipv6() {
+        let endpoint = Endpoint::from(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7075, 0, 0));
+        assert!(endpoint.is_ipv6());
+        assert_eq!(endpoint.to_string(), "[::1]:7075");
+    }
+
+    #[test]
+    fn parse() {
+        let endpoint: Endpoint = "[::1]:7075".parse().unwrap();
+        assert_eq!(
+            endpoint.as_v6(),
+            SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7075, 0, 0)
+        );
+
+        let endpoint: Endpoint = "1.2.3.4:7075".parse().unwrap();
+        assert!(endpoint.is_ipv4());
+        assert_eq!(endpoint.to_string(), "[::ffff:1.2.3.4]:7075");
+
+        assert!("::1".parse::<Endpoint>().is_err());
+        assert!("example.com:7075".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn serde() {
+        let endpoint: Endpoint = "[::1]:7075".parse().unwrap();
+        let json = serde_json::to_string(&endpoint).unwrap();
+        assert_eq!(json, "\"[::1]:7075\"");
+        assert_eq!(serde_json::from_str::<Endpoint>(&json).unwrap(), endpoint);
+    }
+
+    #[test]
+    fn endpoint_key() {
+        let endpoint: Endpoint = "1.2.3.4:7075".parse().unwrap();
+        let key = EndpointKey::from(endpoint);
+        assert_eq!(Endpoint::from(&key), endpoint);
+    }
+}
@@ -1,6 +1,10 @@
 use std::sync::{Arc, Mutex};
 
-use rsnano_core::utils::{Stream, StreamAdapter};
+use num_traits::FromPrimitive;
+use rsnano_core::{
+    utils::{Stream, StreamAdapter},
+    Networks,
+};
 
 use crate::{
     config::NetworkConstants,
@@ -59,6 +63,16 @@ impl MessageDeserializer {
         *guard = status;
     }
 
+    /// In warn only mode the ids of all known networks are accepted,
+    /// otherwise only the id of the current network
+    fn accepts_network_id(&self, network_id: u16) -> bool {
+        if network_id == self.network_constants.network_id {
+            return true;
+        }
+        self.network_constants.network_id_warn_only
+            && matches!(Networks::from_u16(network_id), Some(n) if n != Networks::Invalid)
+    }
+
     fn received_message(&self, header: MessageHeader, payload_size: usize, callback: CallbackType) {
         match self.deserialize(header, payload_size) {
             Some(message) => {
@@ -351,6 +365,14 @@ impl MessageDeserializerExt for Arc<MessageDeserializer> {
 
     fn received_header(&self, callback: CallbackType) {
         let buffer = self.read_buffer.lock().unwrap();
+        // The network id is checked before parsing the header, so that unknown network ids
+        // are reported as invalid network instead of an unspecific parse error
+        if !self.accepts_network_id(u16::from_be_bytes([buffer[0], buffer[1]])) {
+            self.set_status(ParseStatus::InvalidNetwork);
+            callback(ErrorCode::fault(), None);
+            return;
+        }
+
         let mut stream = StreamAdapter::new(&buffer[..HEADER_SIZE]);
         let header = match MessageHeader::from_stream(&mut stream) {
             Ok(header) => header,
@@ -360,12 +382,11 @@ impl MessageDeserializerExt for Arc<MessageDeserializer> {
             }
         };
 
-        if header.network() != self.network_constants.current_network {
-            self.set_status(ParseStatus::InvalidNetwork);
-            callback(ErrorCode::fault(), None);
-            return;
-        }
-        if header.version_using() < self.network_constants.protocol_version_min {
+        if !self
+            .network_constants
+            .protocol
+            .is_supported(header.version_using())
+        {
             self.set_status(ParseStatus::OutdatedVersion);
             callback(ErrorCode::fault(), None);
             return;
@@ -377,7 +398,9 @@ impl MessageDeserializerExt for Arc<MessageDeserializer> {
         }
 
         let payload_size = header.payload_length();
-        if payload_size > MAX_MESSAGE_SIZE {
+        if payload_size > header.message_type().max_payload_length()
+            || payload_size > MAX_MESSAGE_SIZE
+        {
             self.set_status(ParseStatus::MessageSizeTooBig);
             callback(ErrorCode::fault(), None);
             return;
@@ -472,11 +495,12 @@ mod tests {
     use crate::{
         config::STUB_NETWORK_CONSTANTS,
         messages::{
-            AccountInfoAckPayload, AccountInfoReqPayload, NodeIdHandshakeQuery, TelemetryData,
+            AccountInfoAckPayload, AccountInfoReqPayload, BlocksAckPayload, NodeIdHandshakeQuery,
+            TelemetryData,
         },
         voting::Vote,
     };
-    use rsnano_core::{BlockBuilder, BlockHash, KeyPair};
+    use rsnano_core::{utils::MemoryStream, BlockBuilder, BlockHash, KeyPair};
     use std::{
         cell::RefCell,
         rc::Rc,
@@ -570,6 +594,90 @@ mod tests {
         test_deserializer(&message);
     }
 
+    #[test]
+    fn largest_asc_pull_ack() {
+        let key = KeyPair::new();
+        let blocks = (0..BlocksAckPayload::MAX_BLOCKS)
+            .map(|i| {
+                BlockBuilder::state()
+                    .previous(BlockHash::from(i as u64 + 1))
+                    .sign(&key)
+                    .build()
+            })
+            .collect();
+        let mut message = AscPullAck::new(&STUB_NETWORK_CONSTANTS);
+        message.request_blocks(BlocksAckPayload { blocks }).unwrap();
+        assert_eq!(
+            message.header().payload_length(),
+            MessageType::AscPullAck.max_payload_length()
+        );
+        test_deserializer(&message);
+    }
+
+    #[test]
+    fn reject_payload_larger_than_maximum_of_message_type() {
+        let mut header = MessageHeader::new(&STUB_NETWORK_CONSTANTS, MessageType::AscPullAck);
+        header.set_extensions(u16::MAX);
+        assert!(header.payload_length() < MAX_MESSAGE_SIZE);
+
+        let mut stream = MemoryStream::new();
+        header.serialize(&mut stream).unwrap();
+        // Only the header is available, so reading the payload would panic
+        let deserializer = create_message_deserializer(stream.to_vec());
+        let called = Rc::new(RefCell::new(false));
+        let called_clone = Rc::clone(&called);
+        deserializer.read(Box::new(move |ec, msg| {
+            assert!(ec.is_err());
+            assert!(msg.is_none());
+            *called_clone.borrow_mut() = true;
+        }));
+        assert!(*called.borrow());
+        assert_eq!(deserializer.status(), ParseStatus::MessageSizeTooBig);
+    }
+
+    #[test]
+    fn maximum_payloads_fit_into_read_buffer() {
+        for i in 0..=u8::MAX {
+            if let Some(message_type) = MessageType::from_u8(i) {
+                assert!(message_type.max_payload_length() <= MAX_MESSAGE_SIZE);
+            }
+        }
+    }
+
+    #[test]
+    fn reject_other_network() {
+        let status = read_keepalive_with_network_id(Networks::NanoLiveNetwork as u16, false);
+        assert_eq!(status, ParseStatus::InvalidNetwork);
+    }
+
+    #[test]
+    fn reject_unknown_network_id() {
+        let status = read_keepalive_with_network_id(0x1234, false);
+        assert_eq!(status, ParseStatus::InvalidNetwork);
+    }
+
+    #[test]
+    fn warn_only_accepts_other_known_network() {
+        let status = read_keepalive_with_network_id(Networks::NanoLiveNetwork as u16, true);
+        assert_eq!(status, ParseStatus::Success);
+    }
+
+    #[test]
+    fn warn_only_rejects_unknown_network_id() {
+        let status = read_keepalive_with_network_id(0x1234, true);
+        assert_eq!(status, ParseStatus::InvalidNetwork);
+    }
+
+    fn read_keepalive_with_network_id(network_id: u16, warn_only: bool) -> ParseStatus {
+        let mut bytes = Keepalive::new(&STUB_NETWORK_CONSTANTS).to_bytes();
+        bytes[..2].copy_from_slice(&network_id.to_be_bytes());
+        let mut constants = STUB_NETWORK_CONSTANTS.clone();
+        constants.network_id_warn_only = warn_only;
+        let deserializer = create_message_deserializer_with(constants, bytes);
+        deserializer.read(Box::new(|_, _| {}));
+        deserializer.status()
+    }
+
     fn test_deserializer(original_message: &dyn Message) {
         let deserializer = create_message_deserializer(original_message.to_bytes());
         let success = Rc::new(RefCell::new(false));
@@ -586,13 +694,20 @@ mod tests {
     }
 
     fn create_message_deserializer(input_source: Vec<u8>) -> Arc<MessageDeserializer> {
+        create_message_deserializer_with(STUB_NETWORK_CONSTANTS.clone(), input_source)
+    }
+
+    fn create_message_deserializer_with(
+        network_constants: NetworkConstants,
+        input_source: Vec<u8>,
+    ) -> Arc<MessageDeserializer> {
         let read_op = create_read_op(input_source);
         let network_filter = Arc::new(NetworkFilter::new(1));
         let block_uniquer = Arc::new(BlockUniquer::new());
         let vote_uniquer = Arc::new(VoteUniquer::new());
 
         Arc::new(MessageDeserializer::new(
-            STUB_NETWORK_CONSTANTS.clone(),
+            network_constants,
             network_filter,
             block_uniquer,
             vote_uniquer,
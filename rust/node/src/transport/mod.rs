@@ -2,10 +2,12 @@ mod bandwidth_limiter;
 mod channel_fake;
 mod channel_inproc;
 mod channel_tcp;
+mod endpoint;
 mod message_deserializer;
 mod network_filter;
 mod peer_exclusion;
 mod socket;
+mod socks5;
 mod syn_cookies;
 mod tcp_channels;
 mod tcp_message_manager;
@@ -14,20 +16,22 @@ mod tcp_server_factory;
 mod token_bucket;
 mod write_queue;
 
-use std::time::SystemTime;
-
 pub use bandwidth_limiter::{
     BandwidthLimitType, BandwidthLimiter, OutboundBandwidthLimiter, OutboundBandwidthLimiterConfig,
 };
 pub use channel_fake::ChannelFake;
 pub use channel_inproc::ChannelInProc;
-pub use channel_tcp::{ChannelTcp, ChannelTcpObserver, IChannelTcpObserverWeakPtr, TcpChannelData};
+pub use channel_tcp::{
+    ChannelTcp, ChannelTcpObserver, IChannelTcpObserverWeakPtr, TcpChannelData,
+    CLOSE_GRACEFULLY_TIMEOUT,
+};
+pub use endpoint::{canonical_ip, Endpoint};
 pub use message_deserializer::{
     MessageDeserializer, MessageDeserializerExt, ParseStatus, ReadQuery,
 };
 pub use network_filter::NetworkFilter;
 pub use peer_exclusion::PeerExclusion;
-use rsnano_core::Account;
+use rsnano_core::{utils::Timestamp, Account};
 pub use socket::*;
 pub use syn_cookies::{Cookie, SynCookies};
 pub use tcp_channels::{
@@ -56,12 +60,12 @@ pub trait Channel {
     fn channel_id(&self) -> usize;
     fn is_temporary(&self) -> bool;
     fn set_temporary(&self, temporary: bool);
-    fn get_last_bootstrap_attempt(&self) -> SystemTime; //todo switch back to Instant
-    fn set_last_bootstrap_attempt(&self, time: SystemTime); //todo switch back to Instant
-    fn get_last_packet_received(&self) -> SystemTime; //todo switch back to Instant
-    fn set_last_packet_received(&self, instant: SystemTime); //todo switch back to Instant
-    fn get_last_packet_sent(&self) -> SystemTime; //todo switch back to Instant
-    fn set_last_packet_sent(&self, instant: SystemTime); //todo switch back to Instant
+    fn get_last_bootstrap_attempt(&self) -> Timestamp;
+    fn set_last_bootstrap_attempt(&self, time: Timestamp);
+    fn get_last_packet_received(&self) -> Timestamp;
+    fn set_last_packet_received(&self, time: Timestamp);
+    fn get_last_packet_sent(&self) -> Timestamp;
+    fn set_last_packet_sent(&self, time: Timestamp);
     fn get_node_id(&self) -> Option<Account>;
     fn set_node_id(&self, id: Account);
     fn is_alive(&self) -> bool;
@@ -73,6 +77,8 @@ pub enum TrafficType {
     Generic,
     /** For bootstrap (asc_pull_ack, asc_pull_req) traffic */
     Bootstrap,
+    /** For liveness traffic (handshake, keepalive) which must not be starved by bulk transfers */
+    Priority,
 }
 
 pub enum ChannelEnum {
@@ -100,6 +106,7 @@ impl ChannelEnum {
         use std::{
             net::{IpAddr, Ipv6Addr, SocketAddr},
             sync::Arc,
+            time::SystemTime,
         };
 
         use crate::{stats::Stats, utils::StubIoContext};
@@ -8,6 +8,8 @@ use std::{
     time::Duration,
 };
 
+use super::{canonical_ip, Endpoint};
+
 /// Manages excluded peers.
 /// Peers are excluded for a while if they behave badly
 pub struct PeerExclusion {
@@ -33,7 +35,7 @@ impl PeerExclusion {
     /// excluded its exclusion duration gets increased.
     /// Returns the new score for the peer.
     pub fn peer_misbehaved(&mut self, endpoint: &SocketAddr) -> u64 {
-        if let Some(peer) = self.by_ip.get_mut(&endpoint.ip()) {
+        if let Some(peer) = self.by_ip.get_mut(&ip_key(endpoint)) {
             let old_exclution_end = peer.exclude_until;
             peer.misbehaved();
             if peer.exclude_until != old_exclution_end {
@@ -43,7 +45,7 @@ impl PeerExclusion {
             peer.score
         } else {
             self.clean_old_peers();
-            let peer = Peer::new(*endpoint);
+            let peer = Peer::new(Endpoint::from(*endpoint).as_socket_addr());
             self.insert(&peer);
             peer.score
         }
@@ -51,18 +53,18 @@ impl PeerExclusion {
 
     pub fn score(&self, endpoint: &SocketAddr) -> u64 {
         self.by_ip
-            .get(&endpoint.ip())
+            .get(&ip_key(endpoint))
             .map(|peer| peer.score)
             .unwrap_or_default()
     }
 
     pub fn contains(&self, endpoint: &SocketAddr) -> bool {
-        self.by_ip.contains_key(&endpoint.ip())
+        self.by_ip.contains_key(&ip_key(endpoint))
     }
 
     pub fn excluded_until(&self, endpoint: &SocketAddr) -> Option<Instant> {
         self.by_ip
-            .get(&endpoint.ip())
+            .get(&ip_key(endpoint))
             .map(|item| item.exclude_until)
     }
 
@@ -73,7 +75,7 @@ impl PeerExclusion {
 
     /// Checks if an endpoint is currently excluded.
     pub fn is_excluded(&mut self, endpoint: &SocketAddr) -> bool {
-        if let Some(peer) = self.by_ip.get(&endpoint.ip()).cloned() {
+        if let Some(peer) = self.by_ip.get(&ip_key(endpoint)).cloned() {
             if peer.has_expired() {
                 self.remove(&peer.address);
             }
@@ -84,7 +86,7 @@ impl PeerExclusion {
     }
 
     pub fn remove(&mut self, endpoint: &SocketAddr) {
-        if let Some(item) = self.by_ip.remove(&endpoint.ip()) {
+        if let Some(item) = self.by_ip.remove(&ip_key(endpoint)) {
             self.ordered_by_date
                 .remove(&item.address.ip(), item.exclude_until);
         }
@@ -112,6 +114,12 @@ impl PeerExclusion {
     }
 }
 
+/// Peers are tracked by their canonical IP address, so that an IPv4 peer and
+/// its IPv4-mapped IPv6 address share the same exclusion entry
+fn ip_key(endpoint: &SocketAddr) -> IpAddr {
+    IpAddr::V6(canonical_ip(endpoint.ip()))
+}
+
 impl Default for PeerExclusion {
     fn default() -> Self {
         Self::new()
@@ -210,7 +218,7 @@ static EXCLUDE_REMOVE_HOURS: Duration = Duration::from_secs(60 * 60 * 24);
 mod tests {
     use super::*;
     use mock_instant::MockClock;
-    use std::net::Ipv6Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     #[test]
     fn new_excluded_peers_excludes_nothing() {
@@ -291,6 +299,20 @@ mod tests {
         assert_eq!(excluded_peers.contains(&test_endpoint(6)), true);
     }
 
+    #[test]
+    fn ipv4_and_mapped_ipv6_share_entry() {
+        let mut excluded_peers = PeerExclusion::new();
+        let ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 7075);
+        let mapped = SocketAddr::new(IpAddr::V6(Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped()), 7075);
+
+        excluded_peers.peer_misbehaved(&ipv4);
+        excluded_peers.peer_misbehaved(&mapped);
+
+        assert_eq!(excluded_peers.size(), 1);
+        assert!(excluded_peers.is_excluded(&ipv4));
+        assert_eq!(excluded_peers.score(&mapped), 2);
+    }
+
     fn test_endpoint(i: usize) -> SocketAddr {
         SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, i as u16)), 0)
     }
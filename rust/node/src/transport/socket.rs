@@ -1,14 +1,14 @@
 use crate::utils::{BufferWrapper, ErrorCode, ThreadPool};
 use num_traits::FromPrimitive;
-use rsnano_core::utils::seconds_since_epoch;
+use rsnano_core::utils::{Clock, SystemClock};
 use std::{
     any::Any,
     net::SocketAddr,
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
-        Arc, Mutex,
+        Arc, Condvar, Mutex,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use super::{
@@ -213,11 +213,23 @@ pub struct Socket {
     /// Updated only from strand, but stored as atomic so it can be read from outside
     write_in_progress: AtomicBool,
 
+    /// Set by close_gracefully(). No new writes are accepted, but the queued ones are still sent
+    draining: AtomicBool,
+
+    /// Notified by close_internal(), so that shutdown can wait for draining sockets
+    closed_mutex: Mutex<()>,
+    closed_condition: Condvar,
+
     socket_type: AtomicU8,
 
     observer: Arc<dyn SocketObserver>,
 
     send_queue: WriteQueue,
+
+    /// Small queued buffers are joined into a single write of up to this many bytes
+    max_write_frame_size: usize,
+
+    clock: Arc<dyn Clock>,
 }
 
 impl Socket {
@@ -227,12 +239,12 @@ impl Socket {
 
     fn set_last_completion(&self) {
         self.last_completion_time_or_init
-            .store(seconds_since_epoch(), std::sync::atomic::Ordering::SeqCst);
+            .store(self.clock.seconds_since_epoch(), Ordering::SeqCst);
     }
 
     fn set_last_receive_time(&self) {
         self.last_receive_time_or_init
-            .store(seconds_since_epoch(), std::sync::atomic::Ordering::SeqCst);
+            .store(self.clock.seconds_since_epoch(), Ordering::SeqCst);
     }
 
     /// Set the current timeout of the socket.
@@ -261,9 +273,22 @@ impl Socket {
             if let Err(ec) = self.tcp_socket.close() {
                 self.observer.close_socket_failed(ec);
             }
+
+            let _guard = self.closed_mutex.lock().unwrap();
+            self.closed_condition.notify_all();
         }
     }
 
+    /// Blocks until the socket is closed or the timeout expired. Returns true if the socket is closed
+    pub fn wait_until_closed(&self, timeout: Duration) -> bool {
+        let guard = self.closed_mutex.lock().unwrap();
+        let _guard = self
+            .closed_condition
+            .wait_timeout_while(guard, timeout, |_| !self.is_closed())
+            .unwrap();
+        self.is_closed()
+    }
+
     pub fn socket_type(&self) -> SocketType {
         SocketType::from_u8(self.socket_type.load(Ordering::SeqCst)).unwrap()
     }
@@ -286,6 +311,7 @@ impl Socket {
     }
 
     const MAX_QUEUE_SIZE: usize = 128;
+    const MAX_WRITE_FRAME_SIZE: usize = 64 * 1024;
 
     pub fn max(&self, traffic_type: TrafficType) -> bool {
         self.send_queue.size(traffic_type) >= Self::MAX_QUEUE_SIZE
@@ -306,6 +332,10 @@ impl Socket {
     pub fn is_alive(&self) -> bool {
         !self.is_closed() && self.tcp_socket.is_open()
     }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
 }
 
 impl Drop for Socket {
@@ -336,6 +366,9 @@ pub trait SocketExtensions {
         traffic_type: TrafficType,
     );
     fn close(&self);
+    /// Stops accepting new writes, flushes the write queue and closes the socket afterwards.
+    /// If the queue isn't flushed before the deadline, the socket gets closed anyway.
+    fn close_gracefully(&self, deadline: Duration);
     fn ongoing_checkup(&self);
 
     fn get_remote(&self) -> Option<SocketAddr>;
@@ -458,7 +491,7 @@ impl SocketExtensions for Arc<Socket> {
         callback: Option<WriteCallback>,
         traffic_type: TrafficType,
     ) {
-        if self.is_closed() {
+        if self.is_closed() || self.is_draining() {
             if let Some(cb) = callback {
                 self.tcp_socket.post(Box::new(move || {
                     cb(ErrorCode::not_supported(), 0);
@@ -492,12 +525,18 @@ impl SocketExtensions for Arc<Socket> {
             return;
         }
 
-        let Some(mut next) = self.send_queue.pop() else { return; };
+        let Some(next) = self.send_queue.pop_coalesced(self.max_write_frame_size) else {
+            if self.is_draining() {
+                self.close();
+            }
+            return;
+        };
         self.set_default_timeout();
         self.write_in_progress.store(true, Ordering::SeqCst);
         let self_clone = Arc::clone(self);
+        let buffer = Arc::clone(&next.buffer);
         self.tcp_socket.async_write(
-            &next.buffer,
+            &buffer,
             Box::new(move |ec, size| {
                 self_clone.write_in_progress.store(false, Ordering::SeqCst);
 
@@ -509,9 +548,7 @@ impl SocketExtensions for Arc<Socket> {
                     self_clone.set_last_completion();
                 }
 
-                if let Some(cbk) = next.callback.take() {
-                    cbk(ec, size);
-                }
+                next.complete(ec);
 
                 if ec.is_ok() {
                     self_clone.write_queued_messages();
@@ -527,6 +564,27 @@ impl SocketExtensions for Arc<Socket> {
         }));
     }
 
+    fn close_gracefully(&self, deadline: Duration) {
+        if self.is_closed() || self.draining.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let self_clone = Arc::clone(self);
+        self.tcp_socket.post(Box::new(move || {
+            if !self_clone.write_in_progress.load(Ordering::SeqCst) {
+                self_clone.write_queued_messages();
+            }
+        }));
+
+        let self_clone = Arc::clone(self);
+        self.thread_pool.add_delayed_task(
+            deadline,
+            Box::new(move || {
+                self_clone.close();
+            }),
+        );
+    }
+
     fn ongoing_checkup(&self) {
         let socket = Arc::downgrade(self);
         self.thread_pool.add_delayed_task(
@@ -539,7 +597,7 @@ impl SocketExtensions for Arc<Socket> {
                         return;
                     }
 
-                    let now = seconds_since_epoch();
+                    let now = socket.clock.seconds_since_epoch();
                     let mut condition_to_disconnect = false;
 
                     // if this is a server socket, and no data is received for silent_connection_tolerance_time seconds then disconnect
@@ -630,6 +688,8 @@ pub struct SocketBuilder {
     idle_timeout: Duration,
     observer: Option<Arc<dyn SocketObserver>>,
     max_write_queue_len: usize,
+    max_write_frame_size: usize,
+    clock: Arc<dyn Clock>,
 }
 
 impl SocketBuilder {
@@ -647,6 +707,8 @@ impl SocketBuilder {
             idle_timeout: Duration::from_secs(120),
             observer: None,
             max_write_queue_len: Socket::MAX_QUEUE_SIZE,
+            max_write_frame_size: Socket::MAX_WRITE_FRAME_SIZE,
+            clock: Arc::new(SystemClock::new()),
         }
     }
 
@@ -675,15 +737,26 @@ impl SocketBuilder {
         self
     }
 
+    pub fn max_write_frame_size(mut self, max_size: usize) -> Self {
+        self.max_write_frame_size = max_size;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     pub fn build(self) -> Arc<Socket> {
         let observer = self
             .observer
             .unwrap_or_else(|| Arc::new(NullSocketObserver::new()));
+        let now = self.clock.seconds_since_epoch();
         Arc::new({
             Socket {
                 remote: Mutex::new(None),
-                last_completion_time_or_init: AtomicU64::new(seconds_since_epoch()),
-                last_receive_time_or_init: AtomicU64::new(seconds_since_epoch()),
+                last_completion_time_or_init: AtomicU64::new(now),
+                last_receive_time_or_init: AtomicU64::new(now),
                 tcp_socket: self.tcp_facade,
                 default_timeout: AtomicU64::new(self.default_timeout.as_secs()),
                 timeout_seconds: AtomicU64::new(u64::MAX),
@@ -698,8 +771,257 @@ impl SocketBuilder {
                 socket_type: AtomicU8::new(SocketType::Undefined as u8),
                 observer,
                 write_in_progress: AtomicBool::new(false),
+                draining: AtomicBool::new(false),
+                closed_mutex: Mutex::new(()),
+                closed_condition: Condvar::new(),
                 send_queue: WriteQueue::new(self.max_write_queue_len),
+                max_write_frame_size: self.max_write_frame_size,
+                clock: self.clock,
             }
         })
     }
 }
+
+/// Waits until gracefully closed sockets have flushed their write queues. Sockets which
+/// don't finish before the timeout are closed forcefully, so that none outlives the shutdown.
+pub fn wait_for_graceful_close(sockets: &[Arc<Socket>], timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    for socket in sockets {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if !socket.wait_until_closed(remaining) {
+            socket.close_internal();
+        }
+    }
+}
+
+/// Facade for tests which never completes any IO operation
+#[cfg(test)]
+pub(crate) struct NullTcpSocketFacade {}
+
+#[cfg(test)]
+impl TcpSocketFacade for NullTcpSocketFacade {
+    fn local_endpoint(&self) -> SocketAddr {
+        SocketAddr::new(std::net::IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED), 0)
+    }
+
+    fn async_connect(&self, _endpoint: SocketAddr, _callback: Box<dyn FnOnce(ErrorCode)>) {}
+
+    fn async_read(
+        &self,
+        _buffer: &Arc<dyn BufferWrapper>,
+        _len: usize,
+        _callback: Box<dyn FnOnce(ErrorCode, usize)>,
+    ) {
+    }
+
+    fn async_read2(
+        &self,
+        _buffer: &Arc<Mutex<Vec<u8>>>,
+        _len: usize,
+        _callback: Box<dyn FnOnce(ErrorCode, usize)>,
+    ) {
+    }
+
+    fn async_write(&self, _buffer: &Arc<Vec<u8>>, _callback: Box<dyn FnOnce(ErrorCode, usize)>) {}
+
+    fn remote_endpoint(&self) -> Result<SocketAddr, ErrorCode> {
+        Err(ErrorCode::not_supported())
+    }
+
+    fn post(&self, _f: Box<dyn FnOnce()>) {}
+
+    fn dispatch(&self, _f: Box<dyn FnOnce()>) {}
+
+    fn close(&self) -> Result<(), ErrorCode> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn is_open(&self) -> bool {
+        true
+    }
+}
+
+/// Factory for tests which creates sockets that never complete any IO operation
+#[cfg(test)]
+pub(crate) struct NullTcpSocketFacadeFactory {}
+
+#[cfg(test)]
+impl TcpSocketFacadeFactory for NullTcpSocketFacadeFactory {
+    fn create_tcp_socket(&self) -> Arc<dyn TcpSocketFacade> {
+        Arc::new(NullTcpSocketFacade {})
+    }
+}
+
+#[cfg(test)]
+impl Socket {
+    pub(crate) fn create_null() -> Arc<Socket> {
+        SocketBuilder::endpoint_type(
+            EndpointType::Client,
+            Arc::new(NullTcpSocketFacade {}),
+            Arc::new(crate::utils::ThreadPoolImpl::create_null()),
+        )
+        .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ThreadPoolImpl;
+    use std::cell::RefCell;
+
+    type WriteCallback = Box<dyn FnOnce(ErrorCode, usize)>;
+
+    thread_local! {
+        /// Write completion handlers aren't Send, so they are kept on the test thread,
+        /// which is the only thread that runs handlers of the manual facade
+        static PENDING_WRITES: RefCell<Vec<(usize, WriteCallback)>> = RefCell::new(Vec::new());
+    }
+
+    /// Facade which runs posted handlers immediately and completes writes on demand
+    #[derive(Default)]
+    struct ManualTcpSocketFacade {
+        written: Mutex<Vec<Vec<u8>>>,
+        closed: AtomicBool,
+    }
+
+    impl ManualTcpSocketFacade {
+        fn complete_writes(&self) {
+            loop {
+                let pending =
+                    PENDING_WRITES.with(|writes| std::mem::take(&mut *writes.borrow_mut()));
+                if pending.is_empty() {
+                    break;
+                }
+                for (len, callback) in pending {
+                    callback(ErrorCode::new(), len);
+                }
+            }
+        }
+    }
+
+    impl TcpSocketFacade for ManualTcpSocketFacade {
+        fn local_endpoint(&self) -> SocketAddr {
+            SocketAddr::new(std::net::IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED), 0)
+        }
+
+        fn async_connect(&self, _endpoint: SocketAddr, _callback: Box<dyn FnOnce(ErrorCode)>) {}
+
+        fn async_read(
+            &self,
+            _buffer: &Arc<dyn BufferWrapper>,
+            _len: usize,
+            _callback: Box<dyn FnOnce(ErrorCode, usize)>,
+        ) {
+        }
+
+        fn async_read2(
+            &self,
+            _buffer: &Arc<Mutex<Vec<u8>>>,
+            _len: usize,
+            _callback: Box<dyn FnOnce(ErrorCode, usize)>,
+        ) {
+        }
+
+        fn async_write(&self, buffer: &Arc<Vec<u8>>, callback: Box<dyn FnOnce(ErrorCode, usize)>) {
+            self.written.lock().unwrap().push(buffer.as_ref().clone());
+            PENDING_WRITES.with(|writes| writes.borrow_mut().push((buffer.len(), callback)));
+        }
+
+        fn remote_endpoint(&self) -> Result<SocketAddr, ErrorCode> {
+            Err(ErrorCode::not_supported())
+        }
+
+        fn post(&self, f: Box<dyn FnOnce()>) {
+            f();
+        }
+
+        fn dispatch(&self, f: Box<dyn FnOnce()>) {
+            f();
+        }
+
+        fn close(&self) -> Result<(), ErrorCode> {
+            self.closed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn is_open(&self) -> bool {
+            !self.closed.load(Ordering::SeqCst)
+        }
+    }
+
+    fn create_socket(facade: Arc<ManualTcpSocketFacade>) -> Arc<Socket> {
+        SocketBuilder::endpoint_type(
+            EndpointType::Client,
+            facade,
+            Arc::new(ThreadPoolImpl::create_null()),
+        )
+        .max_write_frame_size(1)
+        .build()
+    }
+
+    fn write(socket: &Arc<Socket>, data: u8) -> Arc<Mutex<Option<ErrorCode>>> {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = result.clone();
+        socket.async_write(
+            &Arc::new(vec![data]),
+            Some(Box::new(move |ec, _| {
+                *result_clone.lock().unwrap() = Some(ec);
+            })),
+            TrafficType::Generic,
+        );
+        result
+    }
+
+    #[test]
+    fn close_gracefully_flushes_queued_writes_before_closing() {
+        let facade = Arc::new(ManualTcpSocketFacade::default());
+        let socket = create_socket(facade.clone());
+        write(&socket, 1);
+        write(&socket, 2);
+
+        socket.close_gracefully(Duration::from_secs(5));
+        let rejected = write(&socket, 3);
+
+        assert!(socket.is_draining());
+        assert!(!socket.is_closed());
+        assert_eq!(*rejected.lock().unwrap(), Some(ErrorCode::not_supported()));
+
+        facade.complete_writes();
+
+        assert!(socket.is_closed());
+        assert!(facade.closed.load(Ordering::SeqCst));
+        assert_eq!(*facade.written.lock().unwrap(), vec![vec![1], vec![2]]);
+        assert!(socket.wait_until_closed(Duration::ZERO));
+    }
+
+    #[test]
+    fn close_gracefully_with_empty_queue_closes_immediately() {
+        let facade = Arc::new(ManualTcpSocketFacade::default());
+        let socket = create_socket(facade);
+        socket.close_gracefully(Duration::from_secs(5));
+        assert!(socket.is_closed());
+    }
+
+    #[test]
+    fn wait_for_graceful_close_closes_sockets_which_dont_drain_in_time() {
+        let facade = Arc::new(ManualTcpSocketFacade::default());
+        let socket = create_socket(facade.clone());
+        write(&socket, 1);
+        socket.close_gracefully(Duration::from_secs(5));
+        assert!(!socket.wait_until_closed(Duration::from_millis(1)));
+
+        wait_for_graceful_close(std::slice::from_ref(&socket), Duration::from_millis(1));
+
+        assert!(socket.is_closed());
+        assert!(facade.closed.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,241 @@
+//! Client side of the SOCKS5 protocol (RFC 1928).
+//! Only the CONNECT command without authentication is supported.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, bail};
+
+use super::{Endpoint, Socket, SocketExtensions, TrafficType};
+use crate::utils::ErrorCode;
+
+const VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const CMD_CONNECT: u8 = 1;
+const ATYP_IPV4: u8 = 1;
+const ATYP_DOMAIN: u8 = 3;
+const ATYP_IPV6: u8 = 4;
+
+const METHOD_SELECTION_SIZE: usize = 2;
+
+/// The first part of the connect reply, which is needed to calculate the size of the whole reply
+const CONNECT_REPLY_HEADER_SIZE: usize = 5;
+
+pub(crate) type Socks5Callback = Box<dyn FnOnce(anyhow::Result<()>) + Send>;
+
+/// Asks the SOCKS5 proxy, to which `socket` is already connected, to open a connection to `target`.
+/// After a successful handshake the socket can be used as if it was connected to `target` directly.
+pub(crate) fn socks5_connect(socket: Arc<Socket>, target: Endpoint, callback: Socks5Callback) {
+    let socket_clone = Arc::clone(&socket);
+    write(
+        &socket,
+        greeting(),
+        Box::new(move |result| {
+            if let Err(e) = result {
+                return callback(Err(e));
+            }
+            let socket = socket_clone;
+            let socket_clone = Arc::clone(&socket);
+            read(
+                &socket,
+                METHOD_SELECTION_SIZE,
+                Box::new(move |result| {
+                    if let Err(e) = result.and_then(|reply| check_method_selection(&reply)) {
+                        return callback(Err(e));
+                    }
+                    send_connect_request(socket_clone, target, callback);
+                }),
+            );
+        }),
+    );
+}
+
+fn send_connect_request(socket: Arc<Socket>, target: Endpoint, callback: Socks5Callback) {
+    let socket_clone = Arc::clone(&socket);
+    write(
+        &socket,
+        connect_request(&target),
+        Box::new(move |result| {
+            if let Err(e) = result {
+                return callback(Err(e));
+            }
+            let socket = socket_clone;
+            let socket_clone = Arc::clone(&socket);
+            read(
+                &socket,
+                CONNECT_REPLY_HEADER_SIZE,
+                Box::new(move |result| {
+                    let remaining = match result.and_then(|h| check_connect_reply_header(&h)) {
+                        Ok(remaining) => remaining,
+                        Err(e) => return callback(Err(e)),
+                    };
+                    // The address to which the proxy is bound isn't needed, but it has to be consumed
+                    read(
+                        &socket_clone,
+                        remaining,
+                        Box::new(move |result| callback(result.map(|_| ()))),
+                    );
+                }),
+            );
+        }),
+    );
+}
+
+fn write(socket: &Arc<Socket>, buffer: Vec<u8>, callback: Socks5Callback) {
+    socket.async_write(
+        &Arc::new(buffer),
+        Some(Box::new(move |ec, _size| callback(to_result(ec)))),
+        TrafficType::Priority,
+    );
+}
+
+fn read(socket: &Arc<Socket>, size: usize, callback: Box<dyn FnOnce(anyhow::Result<Vec<u8>>)>) {
+    let buffer = Arc::new(Mutex::new(vec![0; size]));
+    let buffer_clone = Arc::clone(&buffer);
+    socket.async_read2(
+        buffer,
+        size,
+        Box::new(move |ec, len| {
+            let result = to_result(ec).and_then(|_| {
+                if len == size {
+                    Ok(buffer_clone.lock().unwrap().clone())
+                } else {
+                    Err(anyhow!("incomplete SOCKS5 reply"))
+                }
+            });
+            callback(result);
+        }),
+    );
+}
+
+fn to_result(ec: ErrorCode) -> anyhow::Result<()> {
+    if ec.is_ok() {
+        Ok(())
+    } else {
+        Err(anyhow!("socket error: {:?}", ec))
+    }
+}
+
+fn greeting() -> Vec<u8> {
+    vec![VERSION, 1, NO_AUTHENTICATION]
+}
+
+fn check_method_selection(reply: &[u8]) -> anyhow::Result<()> {
+    match reply {
+        [VERSION, NO_AUTHENTICATION] => Ok(()),
+        [VERSION, _] => Err(anyhow!("proxy requires authentication")),
+        _ => Err(anyhow!("invalid SOCKS5 method selection reply")),
+    }
+}
+
+fn connect_request(target: &Endpoint) -> Vec<u8> {
+    let mut request = vec![VERSION, CMD_CONNECT, 0];
+    match target.ip().to_ipv4_mapped() {
+        Some(ipv4) => {
+            request.push(ATYP_IPV4);
+            request.extend_from_slice(&ipv4.octets());
+        }
+        None => {
+            request.push(ATYP_IPV6);
+            request.extend_from_slice(&target.ip().octets());
+        }
+    }
+    request.extend_from_slice(&target.port().to_be_bytes());
+    request
+}
+
+/// Returns the number of bytes of the connect reply that still have to be read
+fn check_connect_reply_header(header: &[u8]) -> anyhow::Result<usize> {
+    if header.len() != CONNECT_REPLY_HEADER_SIZE || header[0] != VERSION {
+        bail!("invalid SOCKS5 connect reply");
+    }
+
+    if header[1] != 0 {
+        bail!("proxy refused connection: {}", reply_description(header[1]));
+    }
+
+    // The last header byte is either the first byte of the bound address
+    // or the length of the bound domain name
+    let remaining_address_len = match header[3] {
+        ATYP_IPV4 => 4 - 1,
+        ATYP_IPV6 => 16 - 1,
+        ATYP_DOMAIN => header[4] as usize,
+        _ => bail!("invalid address type in SOCKS5 connect reply"),
+    };
+
+    Ok(remaining_address_len + 2) // + port
+}
+
+fn reply_description(reply: u8) -> &'static str {
+    match reply {
+        1 => "general SOCKS server failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV6};
+
+    #[test]
+    fn method_selection() {
+        assert!(check_method_selection(&[5, 0]).is_ok());
+        assert!(check_method_selection(&[5, 2]).is_err());
+        assert!(check_method_selection(&[4, 0]).is_err());
+    }
+
+    #[test]
+    fn connect_request_ipv4() {
+        let target = Endpoint::from(SocketAddrV6::new(
+            Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped(),
+            7075,
+            0,
+            0,
+        ));
+        assert_eq!(
+            connect_request(&target),
+            vec![5, 1, 0, ATYP_IPV4, 1, 2, 3, 4, 0x1b, 0xa3]
+        );
+    }
+
+    #[test]
+    fn connect_request_ipv6() {
+        let target = Endpoint::from(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7075, 0, 0));
+        let request = connect_request(&target);
+        assert_eq!(request.len(), 4 + 16 + 2);
+        assert_eq!(request[3], ATYP_IPV6);
+        assert_eq!(&request[4..20], &Ipv6Addr::LOCALHOST.octets());
+    }
+
+    #[test]
+    fn remaining_connect_reply_size() {
+        assert_eq!(
+            check_connect_reply_header(&[5, 0, 0, ATYP_IPV4, 1]).unwrap(),
+            5
+        );
+        assert_eq!(
+            check_connect_reply_header(&[5, 0, 0, ATYP_IPV6, 1]).unwrap(),
+            17
+        );
+        assert_eq!(
+            check_connect_reply_header(&[5, 0, 0, ATYP_DOMAIN, 10]).unwrap(),
+            12
+        );
+    }
+
+    #[test]
+    fn refused_connection() {
+        let error = check_connect_reply_header(&[5, 5, 0, ATYP_IPV4, 1]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "proxy refused connection: connection refused"
+        );
+    }
+}
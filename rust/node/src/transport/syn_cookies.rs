@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    net::{IpAddr, SocketAddr},
+    net::{Ipv6Addr, SocketAddr},
     sync::Mutex,
     time::{Duration, Instant},
 };
@@ -9,6 +9,8 @@ use anyhow::Result;
 use rand::Rng;
 use rsnano_core::{validate_message, Account, Signature};
 
+use super::Endpoint;
+
 /// Node ID cookies for node ID handshakes
 pub struct SynCookies {
     data: Mutex<LockedSynCookies>,
@@ -31,11 +33,11 @@ impl SynCookies {
     /// Returns `None` if the IP is rate capped on syn cookie requests,
     /// or if the endpoint already has a syn cookie query
     pub fn assign(&self, endpoint: &SocketAddr) -> Option<Cookie> {
-        let ip_addr = endpoint.ip();
-        debug_assert!(ip_addr.is_ipv6());
+        let endpoint = Endpoint::from(*endpoint);
+        let ip_addr = *endpoint.ip();
         let mut lock = self.data.lock().unwrap();
 
-        if lock.cookies.contains_key(endpoint) {
+        if lock.cookies.contains_key(&endpoint) {
             return None;
         }
 
@@ -44,7 +46,7 @@ impl SynCookies {
             *ip_cookies += 1;
             let cookie = rand::thread_rng().gen::<Cookie>();
             lock.cookies.insert(
-                *endpoint,
+                endpoint,
                 SynCookieInfo {
                     cookie,
                     created_at: Instant::now(),
@@ -64,12 +66,12 @@ impl SynCookies {
         node_id: &Account,
         signature: &Signature,
     ) -> Result<()> {
-        let ip_addr = endpoint.ip();
-        debug_assert!(ip_addr.is_ipv6());
+        let endpoint = Endpoint::from(*endpoint);
+        let ip_addr = *endpoint.ip();
         let mut lock = self.data.lock().unwrap();
-        if let Some(info) = lock.cookies.get(endpoint) {
+        if let Some(info) = lock.cookies.get(&endpoint) {
             validate_message(node_id, &info.cookie, signature)?;
-            lock.cookies.remove(endpoint);
+            lock.cookies.remove(&endpoint);
             lock.dec_cookie_count(ip_addr);
         }
         Ok(())
@@ -90,16 +92,16 @@ impl SynCookies {
 
         for endpoint in &removed_endpoints {
             lock.cookies.remove(endpoint);
-            lock.dec_cookie_count(endpoint.ip());
+            lock.dec_cookie_count(*endpoint.ip());
         }
     }
 
     /// Get cookie associated with endpoint and erases that cookie from this container
     pub fn cookie(&self, endpoint: &SocketAddr) -> Option<Cookie> {
-        let ip_addr = endpoint.ip();
-        debug_assert!(ip_addr.is_ipv6());
+        let endpoint = Endpoint::from(*endpoint);
+        let ip_addr = *endpoint.ip();
         let mut lock = self.data.lock().unwrap();
-        let info = lock.cookies.remove(endpoint);
+        let info = lock.cookies.remove(&endpoint);
         if info.is_some() {
             lock.dec_cookie_count(ip_addr);
         }
@@ -124,12 +126,12 @@ impl SynCookies {
 }
 
 struct LockedSynCookies {
-    cookies: HashMap<SocketAddr, SynCookieInfo>,
-    cookies_per_ip: HashMap<IpAddr, usize>,
+    cookies: HashMap<Endpoint, SynCookieInfo>,
+    cookies_per_ip: HashMap<Ipv6Addr, usize>,
 }
 
 impl LockedSynCookies {
-    fn dec_cookie_count(&mut self, ip_addr: IpAddr) {
+    fn dec_cookie_count(&mut self, ip_addr: Ipv6Addr) {
         let ip_cookies = self.cookies_per_ip.entry(ip_addr).or_default();
         if *ip_cookies > 0 {
             *ip_cookies -= 1;
@@ -149,3 +151,22 @@ impl SynCookieInfo {
         now.duration_since(self.created_at) > cutoff
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn ipv4_and_mapped_ipv6_share_cookie() {
+        let cookies = SynCookies::new(10);
+        let ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 7075);
+        let mapped = SocketAddr::new(IpAddr::V6(Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped()), 7075);
+
+        let cookie = cookies.assign(&ipv4).unwrap();
+
+        assert_eq!(cookies.assign(&mapped), None);
+        assert_eq!(cookies.cookie(&mapped), Some(cookie));
+        assert_eq!(cookies.cookies_count(), 0);
+    }
+}
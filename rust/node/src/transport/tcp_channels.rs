@@ -7,18 +7,18 @@ use std::{
         atomic::{AtomicBool, AtomicU16, AtomicUsize, Ordering},
         Arc, Mutex, Weak,
     },
-    time::{Duration, SystemTime},
+    time::Duration,
 };
 
-use rand::{thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng, Rng};
 use rsnano_core::{
-    utils::{ContainerInfo, ContainerInfoComponent, Logger},
+    utils::{Clock, ContainerInfo, ContainerInfoComponent, Logger, RateLimitedLogger, Timestamp},
     KeyPair, PublicKey,
 };
 
 use crate::{
     bootstrap::{BootstrapMessageVisitorFactory, ChannelTcpWrapper},
-    config::{NetworkConstants, NodeConfig, NodeFlags},
+    config::{NetworkConstants, NodeConfig, NodeFlags, Peer},
     messages::{
         Keepalive, Message, MessageType, NodeIdHandshake, NodeIdHandshakeQuery,
         NodeIdHandshakeResponse,
@@ -34,13 +34,21 @@ use crate::{
 };
 
 use super::{
-    BufferDropPolicy, ChannelEnum, ChannelTcp, ChannelTcpObserver, CompositeSocketObserver,
-    EndpointType, IChannelTcpObserverWeakPtr, MessageDeserializer, MessageDeserializerExt,
-    NetworkFilter, NullTcpServerObserver, OutboundBandwidthLimiter, PeerExclusion, Socket,
-    SocketBuilder, SocketExtensions, SocketObserver, SynCookies, TcpMessageManager, TcpServer,
-    TcpServerFactory, TcpServerObserver, TcpSocketFacadeFactory, TrafficType,
+    socks5::socks5_connect, wait_for_graceful_close, BufferDropPolicy, ChannelEnum, ChannelTcp,
+    ChannelTcpObserver, CompositeSocketObserver, Endpoint, EndpointType,
+    IChannelTcpObserverWeakPtr, MessageDeserializer, MessageDeserializerExt, NetworkFilter,
+    NullTcpServerObserver, OutboundBandwidthLimiter, PeerExclusion, Socket, SocketBuilder,
+    SocketExtensions, SocketObserver, SynCookies, TcpMessageManager, TcpServer, TcpServerFactory,
+    TcpServerObserver, TcpSocketFacadeFactory, TrafficType,
 };
 
+/// Number of peers in a keepalive message
+pub const KEEPALIVE_PEER_COUNT: usize = 8;
+
+/// Maximum time `stop` gives the channels to flush their write queues. Much shorter than
+/// `CLOSE_GRACEFULLY_TIMEOUT`, so that peers which don't read can't hold up the node shutdown
+const STOP_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
 pub struct TcpChannelsOptions {
     pub node_config: NodeConfig,
     pub logger: Arc<dyn Logger>,
@@ -60,6 +68,7 @@ pub struct TcpChannelsOptions {
     pub workers: Arc<dyn ThreadPool>,
     pub tcp_socket_factory: Arc<dyn TcpSocketFacadeFactory>,
     pub observer: Arc<dyn SocketObserver>,
+    pub clock: Arc<dyn Clock>,
 }
 
 pub struct TcpChannels {
@@ -78,6 +87,8 @@ pub struct TcpChannels {
     io_ctx: Arc<dyn IoContext>,
     node_config: Arc<NodeConfig>,
     logger: Arc<dyn Logger>,
+    /// For events which can flood the log, like dropped messages
+    rate_limited_logger: RateLimitedLogger,
     pub node_id: KeyPair,
     syn_cookies: Arc<SynCookies>,
     workers: Arc<dyn ThreadPool>,
@@ -88,12 +99,14 @@ pub struct TcpChannels {
     tcp_socket_factory: Arc<dyn TcpSocketFacadeFactory>,
     observer: Arc<dyn SocketObserver>,
     channel_observer: Mutex<Option<Arc<dyn ChannelTcpObserver>>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl TcpChannels {
     pub fn new(options: TcpChannelsOptions) -> Self {
         let node_config = Arc::new(options.node_config);
         let network = Arc::new(options.network);
+        let excluded_peers = Arc::new(Mutex::new(PeerExclusion::new()));
         let tcp_server_factory = Arc::new(Mutex::new(TcpServerFactory {
             config: node_config.clone(),
             logger: options.logger.clone(),
@@ -106,6 +119,7 @@ impl TcpChannels {
             vote_uniquer: options.vote_uniquer.clone(),
             tcp_message_manager: options.tcp_message_manager.clone(),
             message_visitor_factory: None,
+            excluded_peers: excluded_peers.clone(),
         }));
 
         Self {
@@ -122,13 +136,20 @@ impl TcpChannels {
                 network_constants: network.network.clone(),
                 new_channel_observer: None,
                 tcp_server_factory: tcp_server_factory.clone(),
+                clock: options.clock.clone(),
             }),
             sink: options.sink,
             next_channel_id: AtomicUsize::new(1),
             network,
-            excluded_peers: Arc::new(Mutex::new(PeerExclusion::new())),
+            excluded_peers,
             limiter: options.limiter,
             io_ctx: options.io_ctx,
+            rate_limited_logger: RateLimitedLogger::new(
+                options.logger.clone(),
+                options.clock.clone(),
+                RateLimitedLogger::DEFAULT_MAX_MESSAGES,
+                RateLimitedLogger::DEFAULT_INTERVAL,
+            ),
             logger: options.logger,
             node_id: options.node_id,
             syn_cookies: options.syn_cookies,
@@ -140,6 +161,7 @@ impl TcpChannels {
             observer: options.observer,
             channel_observer: Mutex::new(None),
             tcp_socket_factory: options.tcp_socket_factory,
+            clock: options.clock,
         }
     }
 
@@ -149,17 +171,69 @@ impl TcpChannels {
 
     pub fn stop(&self) {
         self.stopped.store(true, Ordering::SeqCst);
-        self.tcp_channels.lock().unwrap().close_channels();
+        let draining = self.tcp_channels.lock().unwrap().close_channels();
+        wait_for_graceful_close(&draining, STOP_DRAIN_TIMEOUT);
         self.tcp_message_manager.stop();
     }
 
     pub fn not_a_peer(&self, endpoint: &SocketAddrV6, allow_local_peers: bool) -> bool {
         endpoint.ip().is_unspecified()
             || reserved_address(endpoint, allow_local_peers)
+            || !self.address_family_allowed(&Endpoint::from(*endpoint))
             || endpoint
                 == &SocketAddrV6::new(Ipv6Addr::LOCALHOST, self.port.load(Ordering::SeqCst), 0, 0)
     }
 
+    fn address_family_allowed(&self, endpoint: &Endpoint) -> bool {
+        if endpoint.is_ipv4() {
+            self.node_config.allow_ipv4_peers
+        } else {
+            self.node_config.allow_ipv6_peers
+        }
+    }
+
+    fn create_outbound_channel(&self) -> (Arc<Socket>, Arc<ChannelEnum>) {
+        let socket_stats = Arc::new(SocketStats::new(
+            self.stats.clone(),
+            self.logger.clone(),
+            self.node_config.logging.network_timeout_logging(),
+        ));
+
+        let socket = SocketBuilder::endpoint_type(
+            EndpointType::Client,
+            self.tcp_socket_factory.create_tcp_socket(),
+            self.workers.clone(),
+        )
+        .default_timeout(Duration::from_secs(
+            self.node_config.tcp_io_timeout_s as u64,
+        ))
+        .silent_connection_tolerance_time(Duration::from_secs(
+            self.network.network.silent_connection_tolerance_time_s as u64,
+        ))
+        .idle_timeout(Duration::from_secs(
+            self.network.network.idle_timeout_s as u64,
+        ))
+        .observer(Arc::new(CompositeSocketObserver::new(vec![
+            socket_stats,
+            self.observer.clone(),
+        ])))
+        .clock(self.clock.clone())
+        .build();
+
+        let channel_id = self.get_next_channel_id();
+        let observer: Arc<dyn ChannelTcpObserver> =
+            Arc::clone(self.channel_observer.lock().unwrap().as_ref().unwrap());
+        let channel = Arc::new(ChannelEnum::Tcp(ChannelTcp::new(
+            &socket,
+            self.clock.now(),
+            Arc::new(ChannelTcpObserverWeakPtr(Arc::downgrade(&observer))),
+            self.limiter.clone(),
+            self.io_ctx.clone(),
+            channel_id,
+        )));
+        (socket, channel)
+    }
+
     pub fn on_new_channel(&self, callback: Arc<dyn Fn(Arc<ChannelEnum>) + Send + Sync>) {
         self.tcp_channels.lock().unwrap().new_channel_observer = Some(callback);
     }
@@ -174,9 +248,7 @@ impl TcpChannels {
             panic!("not a tcp channel")
         };
         let endpoint = tcp_channel.remote_endpoint();
-        let SocketAddr::V6(endpoint_v6) = endpoint else {
-            panic!("not a v6 address")
-        };
+        let endpoint_v6 = Endpoint::from(endpoint).as_v6();
         if !self.not_a_peer(&endpoint_v6, self.allow_local_peers)
             && !self.stopped.load(Ordering::SeqCst)
         {
@@ -192,6 +264,17 @@ impl TcpChannels {
                     socket.clone(),
                     server,
                 ));
+
+                // Checked while holding the lock, so that simultaneous inserts can't exceed the limits
+                if let Err(detail) = lock
+                    .channels
+                    .check_peer_limits(&wrapper, &self.peer_limits())
+                {
+                    drop(lock);
+                    self.stats.inc(StatType::Tcp, detail, Direction::In);
+                    return Err(());
+                }
+
                 lock.channels.insert(wrapper);
                 lock.attempts.remove(&endpoint_v6);
                 let observer = lock.new_channel_observer.clone();
@@ -252,6 +335,26 @@ impl TcpChannels {
         self.tcp_channels.lock().unwrap().random_fill(endpoints);
     }
 
+    /// Picks random realtime peers for a keepalive message which is sent to `recipient`.
+    /// If there are too few peers, the preconfigured peers are added
+    pub fn sample_keepalive_peers(
+        &self,
+        recipient: Option<&SocketAddr>,
+    ) -> [SocketAddr; KEEPALIVE_PEER_COUNT] {
+        let sampled = self
+            .tcp_channels
+            .lock()
+            .unwrap()
+            .channels
+            .sample_keepalive_peers(recipient, KEEPALIVE_PEER_COUNT);
+        keepalive_payload(
+            sampled,
+            &self.node_config.preconfigured_peers,
+            self.network.network.default_node_port,
+            recipient,
+        )
+    }
+
     pub fn set_observer(&self, observer: Arc<dyn TcpServerObserver>) {
         self.tcp_channels
             .lock()
@@ -275,6 +378,21 @@ impl TcpChannels {
             .message_visitor_factory = Some(visitor_factory);
     }
 
+    fn peer_limits(&self) -> PeerLimits {
+        PeerLimits {
+            max_per_ip: if self.flags.disable_max_peers_per_ip {
+                usize::MAX
+            } else {
+                self.node_config.max_peers_per_ip
+            },
+            max_per_subnetwork: if self.flags.disable_max_peers_per_subnetwork {
+                usize::MAX
+            } else {
+                self.node_config.max_peers_per_subnetwork
+            },
+        }
+    }
+
     pub fn max_ip_or_subnetwork_connections(&self, endpoint: &SocketAddrV6) -> bool {
         self.max_ip_connections(endpoint) || self.max_subnetwork_connections(endpoint)
     }
@@ -286,10 +404,9 @@ impl TcpChannels {
         let mut result;
         let address = ipv4_address_or_ipv6_subnet(endpoint.ip());
         let lock = self.tcp_channels.lock().unwrap();
-        result = lock.channels.count_by_ip(&address) >= lock.network_constants.max_peers_per_ip;
+        result = lock.channels.count_by_ip(&address) >= self.node_config.max_peers_per_ip;
         if !result {
-            result =
-                lock.attempts.count_by_address(&address) >= lock.network_constants.max_peers_per_ip;
+            result = lock.attempts.count_by_address(&address) >= self.node_config.max_peers_per_ip;
         }
         if result {
             self.stats
@@ -340,6 +457,12 @@ impl TcpChannels {
                 DetailType::InvalidSignature,
                 Direction::In,
             );
+            if self.node_config.logging.network_node_id_handshake_logging() {
+                self.rate_limited_logger.always_log(
+                    "invalid_handshake_signature",
+                    &format!("Invalid handshake signature from {}", remote_endpoint),
+                );
+            }
             return false; // Fail
         }
 
@@ -379,9 +502,9 @@ impl TcpChannels {
         let guard = self.tcp_channels.lock().unwrap();
 
         let is_max = guard.channels.count_by_subnet(&subnet)
-            >= self.network.network.max_peers_per_subnetwork
+            >= self.node_config.max_peers_per_subnetwork
             || guard.attempts.count_by_subnetwork(&subnet)
-                >= self.network.network.max_peers_per_subnetwork;
+                >= self.node_config.max_peers_per_subnetwork;
 
         if is_max {
             self.stats.inc(
@@ -455,11 +578,10 @@ impl ChannelTcpObserver for ChannelTcpObserverImpl {
                 .stats
                 .inc(StatType::Drop, detail_type, Direction::Out);
             if channels.node_config.logging.network_packet_logging() {
-                channels.logger.always_log(&format!(
-                    "{} of size {} dropped",
-                    detail_type.as_str(),
-                    buffer_size
-                ));
+                channels.rate_limited_logger.always_log(
+                    "bandwidth_drop",
+                    &format!("{} of size {} dropped", detail_type.as_str(), buffer_size),
+                );
             }
         });
     }
@@ -504,6 +626,10 @@ pub trait TcpChannelsExtension {
     fn ongoing_keepalive(&self);
     fn start_tcp_receive_node_id(&self, channel: &Arc<ChannelEnum>, endpoint: SocketAddrV6);
     fn start_tcp(&self, endpoint: SocketAddrV6);
+    fn connect_direct(&self, endpoint: SocketAddrV6);
+    fn connect_via_socks5_proxy(&self, endpoint: SocketAddrV6, proxy: SocketAddr);
+    fn proxy_connection_failed(&self, endpoint: SocketAddrV6, error: &str, detail: DetailType);
+    fn send_node_id_handshake(&self, channel: Arc<ChannelEnum>, endpoint: SocketAddrV6);
     fn observe(&self);
 }
 
@@ -531,19 +657,23 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
     ) {
         let socket_type = socket.socket_type();
         if !self.stopped.load(Ordering::SeqCst)
-            && message.header().version_using() >= self.network.network.protocol_version_min
+            && self
+                .network
+                .network
+                .protocol
+                .is_supported(message.header().version_using())
         {
             if let Some(channel) = self.find_channel(endpoint) {
                 (self.sink)(message.clone_box(), Arc::clone(&channel));
                 channel
                     .as_channel()
-                    .set_last_packet_received(SystemTime::now());
+                    .set_last_packet_received(self.clock.timestamp());
             } else {
                 if let Some(channel) = self.find_node_id(&node_id) {
                     (self.sink)(message.clone_box(), Arc::clone(&channel));
                     channel
                         .as_channel()
-                        .set_last_packet_received(SystemTime::now());
+                        .set_last_packet_received(self.clock.timestamp());
                 } else if !self.excluded_peers.lock().unwrap().is_excluded(endpoint) {
                     if !node_id.is_zero() {
                         // Add temporary channel
@@ -552,7 +682,7 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                             Arc::downgrade(self.channel_observer.lock().unwrap().as_ref().unwrap());
                         let temporary_channel = ChannelTcp::new(
                             socket,
-                            SystemTime::now(),
+                            self.clock.now(),
                             Arc::new(ChannelTcpObserverWeakPtr(channel_observer)),
                             self.limiter.clone(),
                             self.io_ctx.clone(),
@@ -561,7 +691,13 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                         temporary_channel.set_remote_endpoint();
                         debug_assert!(*endpoint == temporary_channel.remote_endpoint());
                         temporary_channel.set_node_id(node_id);
-                        temporary_channel.set_network_version(message.header().version_using());
+                        temporary_channel.set_network_version(
+                            self.network
+                                .network
+                                .protocol
+                                .negotiate(message.header().version_using())
+                                .unwrap_or_default(),
+                        );
                         temporary_channel.set_temporary(true);
                         let temporary_channel = Arc::new(ChannelEnum::Tcp(temporary_channel));
                         debug_assert!(
@@ -590,8 +726,7 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
     }
 
     fn ongoing_keepalive(&self) {
-        let mut peers = [SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0); 8];
-        self.random_fill(&mut peers);
+        let peers = self.sample_keepalive_peers(None);
         let message = Keepalive::new_with_peers(&self.network.network, peers);
         // Wake up channels
         let send_list = {
@@ -606,7 +741,7 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                 &message,
                 None,
                 BufferDropPolicy::Limiter,
-                TrafficType::Generic,
+                TrafficType::Priority,
             );
         }
 
@@ -692,14 +827,31 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                 return;
             };
 
-            if handshake.header().network() != this_l.network.network.current_network
-                || handshake.header().version_using() < this_l.network.network.protocol_version_min
+            let wrong_network = handshake.header().network_id()
+                != this_l.network.network.network_id
+                && !this_l.network.network.network_id_warn_only;
+            if wrong_network
+                || !this_l
+                    .network
+                    .network
+                    .protocol
+                    .is_supported(handshake.header().version_using())
             {
                 // error handling, either the networks bytes or the version is wrong
-                if message.header().network() == this_l.network.network.current_network {
+                if wrong_network {
                     this_l
                         .stats
                         .inc(StatType::Message, DetailType::InvalidNetwork, Direction::In);
+                    this_l
+                        .excluded_peers
+                        .lock()
+                        .unwrap()
+                        .peer_misbehaved(&SocketAddr::V6(endpoint));
+                    this_l.stats.inc(
+                        StatType::Tcp,
+                        DetailType::ExcludedWrongNetwork,
+                        Direction::In,
+                    );
                 } else {
                     this_l.stats.inc(
                         StatType::Message,
@@ -741,7 +893,14 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                 return;
             };
 
-            tcp.set_network_version(handshake.header().version_using());
+            tcp.set_network_version(
+                this_l
+                    .network
+                    .network
+                    .protocol
+                    .negotiate(handshake.header().version_using())
+                    .unwrap_or_default(),
+            );
 
             let node_id = response.node_id;
 
@@ -759,7 +918,7 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                 }
             }
             tcp.set_node_id(node_id);
-            tcp.set_last_packet_received(SystemTime::now());
+            tcp.set_last_packet_received(this_l.clock.timestamp());
 
             let response = this_l.prepare_handshake_response(query, handshake.is_v2());
             let handshake_response =
@@ -815,7 +974,7 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                     let _ = this_l.insert(&channel, &socket_l, Some(response_server));
                 })),
                 BufferDropPolicy::Limiter,
-                TrafficType::Generic,
+                TrafficType::Priority,
             );
         });
 
@@ -835,43 +994,36 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
     }
 
     fn start_tcp(&self, endpoint: SocketAddrV6) {
-        let socket_stats = Arc::new(SocketStats::new(
-            self.stats.clone(),
-            self.logger.clone(),
-            self.node_config.logging.network_timeout_logging(),
-        ));
-
-        let socket = SocketBuilder::endpoint_type(
-            EndpointType::Client,
-            self.tcp_socket_factory.create_tcp_socket(),
-            self.workers.clone(),
-        )
-        .default_timeout(Duration::from_secs(
-            self.node_config.tcp_io_timeout_s as u64,
-        ))
-        .silent_connection_tolerance_time(Duration::from_secs(
-            self.network.network.silent_connection_tolerance_time_s as u64,
-        ))
-        .idle_timeout(Duration::from_secs(
-            self.network.network.idle_timeout_s as u64,
-        ))
-        .observer(Arc::new(CompositeSocketObserver::new(vec![
-            socket_stats,
-            self.observer.clone(),
-        ])))
-        .build();
+        let proxy_config = &self.node_config.socks5_proxy;
+        if !proxy_config.is_enabled() {
+            self.connect_direct(endpoint);
+        } else if let Some(proxy) = proxy_config.endpoint() {
+            self.connect_via_socks5_proxy(endpoint, proxy);
+        } else {
+            // The proxy is configured by host name. Never fall back to a direct
+            // connection silently, because that would reveal the node's IP address
+            let this_w = Arc::downgrade(self);
+            self.workers.push_task(Box::new(move || {
+                let Some(this_l) = this_w.upgrade() else {
+                    return;
+                };
+                match this_l.node_config.socks5_proxy.resolve() {
+                    Ok(proxy) => this_l.connect_via_socks5_proxy(endpoint, proxy),
+                    Err(e) => this_l.proxy_connection_failed(
+                        endpoint,
+                        &format!(
+                            "Could not resolve SOCKS5 proxy {}: {}",
+                            this_l.node_config.socks5_proxy.address, e
+                        ),
+                        DetailType::ProxyResolveError,
+                    ),
+                }
+            }));
+        }
+    }
 
-        let channel_id = self.get_next_channel_id();
-        let observer: Arc<dyn ChannelTcpObserver> =
-            Arc::clone(self.channel_observer.lock().unwrap().as_ref().unwrap());
-        let channel = Arc::new(ChannelEnum::Tcp(ChannelTcp::new(
-            &socket,
-            SystemTime::now(),
-            Arc::new(ChannelTcpObserverWeakPtr(Arc::downgrade(&observer))),
-            self.limiter.clone(),
-            self.io_ctx.clone(),
-            channel_id,
-        )));
+    fn connect_direct(&self, endpoint: SocketAddrV6) {
+        let (socket, channel) = self.create_outbound_channel();
         let this_w = Arc::downgrade(self);
         let socket_clone = Arc::clone(&socket);
         socket.async_connect(
@@ -890,62 +1042,128 @@ impl TcpChannelsExtension for Arc<TcpChannels> {
                     return;
                 }
 
-                // TCP node ID handshake
-                let query = this_l.prepare_handshake_query(endpoint);
-                let message = NodeIdHandshake::new(&this_l.network.network, query.clone(), None);
+                this_l.send_node_id_handshake(channel, endpoint);
+            }),
+        );
+    }
 
-                if this_l
-                    .node_config
-                    .logging
-                    .network_node_id_handshake_logging()
-                {
-                    let query_string = query
-                        .map(|q| format!("{:?}", q.cookie))
-                        .unwrap_or_else(|| "not_set".to_string());
-                    this_l.logger.try_log(&format!(
-                        "Node ID handshake request sent with node ID {} to {}: query {}",
-                        this_l.node_id.public_key().to_node_id(),
+    fn connect_via_socks5_proxy(&self, endpoint: SocketAddrV6, proxy: SocketAddr) {
+        let (socket, channel) = self.create_outbound_channel();
+        let this_w = Arc::downgrade(self);
+        let socket_clone = Arc::clone(&socket);
+        socket.async_connect(
+            proxy,
+            Box::new(move |ec| {
+                let Some(this_l) = this_w.upgrade() else { return ;};
+
+                if ec.is_err() {
+                    this_l.proxy_connection_failed(
                         endpoint,
-                        query_string
-                    ));
+                        &format!("Error connecting to SOCKS5 proxy {}: {:?}", proxy, ec),
+                        DetailType::ProxyConnectError,
+                    );
+                    return;
                 }
 
-                let ChannelEnum::Tcp(tcp) = channel.as_ref() else { panic!("not a tcp channel")};
-                tcp.set_remote_endpoint();
                 let this_w = Arc::downgrade(&this_l);
-                let channel_clone = Arc::clone(&channel);
-                tcp.send(
-                    &message,
-                    Some(Box::new(move |ec, _size| {
-                        let channel = channel_clone;
-                        let ChannelEnum::Tcp(tcp) = channel.as_ref() else {return;};
-                        if let Some(this_l) = this_w.upgrade() {
-                            if ec.is_ok() {
-                                this_l.start_tcp_receive_node_id(&channel, endpoint);
-                            } else {
-                                if let Some(socket) = tcp.socket() {
-                                    socket.close();
-                                }
-                                if this_l
-                                    .node_config
-                                    .logging
-                                    .network_node_id_handshake_logging()
-                                {
-                                    this_l.logger.try_log(&format!(
-                                        "Error sending node_id_handshake to {}: {:?}",
-                                        endpoint, ec
-                                    ));
-                                }
+                let socket = socket_clone;
+                socks5_connect(
+                    Arc::clone(&socket),
+                    Endpoint::from(endpoint),
+                    Box::new(move |result| {
+                        let Some(this_l) = this_w.upgrade() else { return ;};
+                        match result {
+                            Ok(()) => {
+                                this_l.stats.inc(
+                                    StatType::Socks5,
+                                    DetailType::ProxyConnected,
+                                    Direction::Out,
+                                );
+                                socket.set_remote(SocketAddr::V6(endpoint));
+                                this_l.send_node_id_handshake(channel, endpoint);
+                            }
+                            Err(e) => {
+                                socket.close();
+                                this_l.proxy_connection_failed(
+                                    endpoint,
+                                    &format!(
+                                        "SOCKS5 proxy {} could not connect to {}: {:?}",
+                                        proxy, endpoint, e
+                                    ),
+                                    DetailType::ProxyHandshakeError,
+                                );
                             }
                         }
-                    })),
-                    BufferDropPolicy::Limiter,
-                    TrafficType::Generic,
+                    }),
                 );
             }),
         );
     }
 
+    fn proxy_connection_failed(&self, endpoint: SocketAddrV6, error: &str, detail: DetailType) {
+        self.stats.inc(StatType::Socks5, detail, Direction::Out);
+        if self.node_config.logging.network_logging_value {
+            self.logger.try_log(error);
+        }
+
+        if self.node_config.socks5_proxy.fallback_to_direct {
+            self.stats
+                .inc(StatType::Socks5, DetailType::ProxyFallback, Direction::Out);
+            self.connect_direct(endpoint);
+        }
+    }
+
+    fn send_node_id_handshake(&self, channel: Arc<ChannelEnum>, endpoint: SocketAddrV6) {
+        // TCP node ID handshake
+        let query = self.prepare_handshake_query(endpoint);
+        let message = NodeIdHandshake::new(&self.network.network, query.clone(), None);
+
+        if self.node_config.logging.network_node_id_handshake_logging() {
+            let query_string = query
+                .map(|q| format!("{:?}", q.cookie))
+                .unwrap_or_else(|| "not_set".to_string());
+            self.logger.try_log(&format!(
+                "Node ID handshake request sent with node ID {} to {}: query {}",
+                self.node_id.public_key().to_node_id(),
+                endpoint,
+                query_string
+            ));
+        }
+
+        let ChannelEnum::Tcp(tcp) = channel.as_ref() else { panic!("not a tcp channel")};
+        tcp.set_remote_endpoint();
+        let this_w = Arc::downgrade(self);
+        let channel_clone = Arc::clone(&channel);
+        tcp.send(
+            &message,
+            Some(Box::new(move |ec, _size| {
+                let channel = channel_clone;
+                let ChannelEnum::Tcp(tcp) = channel.as_ref() else {return;};
+                if let Some(this_l) = this_w.upgrade() {
+                    if ec.is_ok() {
+                        this_l.start_tcp_receive_node_id(&channel, endpoint);
+                    } else {
+                        if let Some(socket) = tcp.socket() {
+                            socket.close();
+                        }
+                        if this_l
+                            .node_config
+                            .logging
+                            .network_node_id_handshake_logging()
+                        {
+                            this_l.logger.try_log(&format!(
+                                "Error sending node_id_handshake to {}: {:?}",
+                                endpoint, ec
+                            ));
+                        }
+                    }
+                }
+            })),
+            BufferDropPolicy::Limiter,
+            TrafficType::Priority,
+        );
+    }
+
     fn observe(&self) {
         *self.channel_observer.lock().unwrap() =
             Some(Arc::new(ChannelTcpObserverImpl(Arc::downgrade(self))));
@@ -958,6 +1176,7 @@ pub struct TcpChannelsImpl {
     network_constants: NetworkConstants,
     new_channel_observer: Option<Arc<dyn Fn(Arc<ChannelEnum>) + Send + Sync>>,
     pub tcp_server_factory: Arc<Mutex<TcpServerFactory>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl TcpChannelsImpl {
@@ -965,7 +1184,11 @@ impl TcpChannelsImpl {
         let mut channel_endpoint = None;
         let mut peering_endpoint = None;
         for channel in self.channels.iter_by_last_bootstrap_attempt() {
-            if channel.network_version() >= self.network_constants.protocol_version_min {
+            if self
+                .network_constants
+                .protocol
+                .is_supported(channel.network_version())
+            {
                 if let ChannelEnum::Tcp(tcp) = channel.channel.as_ref() {
                     channel_endpoint = Some(channel.endpoint());
                     peering_endpoint = Some(tcp.peering_endpoint());
@@ -977,37 +1200,49 @@ impl TcpChannelsImpl {
         match (channel_endpoint, peering_endpoint) {
             (Some(ep), Some(peering)) => {
                 self.channels
-                    .set_last_bootstrap_attempt(&ep, SystemTime::now());
+                    .set_last_bootstrap_attempt(&ep, self.clock.timestamp());
                 peering
             }
             _ => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
         }
     }
 
-    pub fn close_channels(&mut self) {
+    /// Starts to close all channels gracefully and returns their sockets, so that
+    /// the caller can wait until the queued messages are flushed
+    pub fn close_channels(&mut self) -> Vec<Arc<Socket>> {
+        let mut draining = Vec::new();
         for channel in self.channels.iter() {
-            if let Some(socket) = channel.socket() {
-                socket.close();
-            }
+            // Let the peers receive the messages which are still queued
+            channel.tcp_channel().close_gracefully();
+            draining.extend(channel.socket());
             // Remove response server
             if let Some(server) = &channel.response_server {
                 server.stop();
             }
         }
         self.channels.clear();
+        draining
     }
 
-    pub fn purge(&mut self, cutoff: SystemTime) {
+    pub fn purge(&mut self, cutoff: Timestamp) {
         // Remove channels with dead underlying sockets
         self.channels.remove_dead();
-        self.channels.purge(cutoff);
+        let mut removed = self.channels.purge(cutoff);
 
         // Remove keepalive attempt tracking for attempts older than cutoff
         self.attempts.purge(cutoff);
 
         // Check if any tcp channels belonging to old protocol versions which may still be alive due to async operations
-        self.channels
-            .remove_old_protocol_versions(self.network_constants.protocol_version_min);
+        removed.extend(
+            self.channels
+                .remove_old_protocol_versions(self.network_constants.protocol.version_min),
+        );
+
+        for channel in removed {
+            if let ChannelEnum::Tcp(tcp) = channel.as_ref() {
+                tcp.close_gracefully();
+            }
+        }
     }
 
     pub fn list(&self, min_version: u8, include_temporary_channels: bool) -> Vec<Arc<ChannelEnum>> {
@@ -1022,7 +1257,7 @@ impl TcpChannelsImpl {
     }
 
     pub fn keepalive_list(&self) -> Vec<Arc<ChannelEnum>> {
-        let cutoff = SystemTime::now() - self.network_constants.keepalive_period;
+        let cutoff = self.clock.timestamp() - self.network_constants.keepalive_period;
         let mut result = Vec::new();
         for channel in self.channels.iter_by_last_packet_sent() {
             if channel.last_packet_sent() >= cutoff {
@@ -1036,10 +1271,10 @@ impl TcpChannelsImpl {
 
     pub fn update(&mut self, endpoint: &SocketAddr) {
         self.channels
-            .set_last_packet_sent(endpoint, SystemTime::now());
+            .set_last_packet_sent(endpoint, self.clock.timestamp());
     }
 
-    pub fn set_last_packet_sent(&mut self, endpoint: &SocketAddr, time: SystemTime) {
+    pub fn set_last_packet_sent(&mut self, endpoint: &SocketAddr, time: Timestamp) {
         self.channels.set_last_packet_sent(endpoint, time);
     }
 
@@ -1145,21 +1380,27 @@ impl TcpChannelsImpl {
     }
 }
 
+/// Maximum number of channels per IP address and per subnetwork
+pub struct PeerLimits {
+    pub max_per_ip: usize,
+    pub max_per_subnetwork: usize,
+}
+
 #[derive(Default)]
 pub struct ChannelContainer {
-    by_endpoint: HashMap<SocketAddr, Arc<ChannelTcpWrapper>>,
-    by_random_access: Vec<SocketAddr>,
-    by_bootstrap_attempt: BTreeMap<SystemTime, Vec<SocketAddr>>,
-    by_node_id: HashMap<PublicKey, Vec<SocketAddr>>,
-    by_last_packet_sent: BTreeMap<SystemTime, Vec<SocketAddr>>,
-    by_network_version: BTreeMap<u8, Vec<SocketAddr>>,
-    by_ip_address: HashMap<Ipv6Addr, Vec<SocketAddr>>,
-    by_subnet: HashMap<Ipv6Addr, Vec<SocketAddr>>,
+    by_endpoint: HashMap<Endpoint, Arc<ChannelTcpWrapper>>,
+    by_random_access: Vec<Endpoint>,
+    by_bootstrap_attempt: BTreeMap<Timestamp, Vec<Endpoint>>,
+    by_node_id: HashMap<PublicKey, Vec<Endpoint>>,
+    by_last_packet_sent: BTreeMap<Timestamp, Vec<Endpoint>>,
+    by_network_version: BTreeMap<u8, Vec<Endpoint>>,
+    by_ip_address: HashMap<Ipv6Addr, Vec<Endpoint>>,
+    by_subnet: HashMap<Ipv6Addr, Vec<Endpoint>>,
 }
 
 impl ChannelContainer {
     pub fn insert(&mut self, wrapper: Arc<ChannelTcpWrapper>) -> bool {
-        let endpoint = wrapper.endpoint();
+        let endpoint = Endpoint::from(wrapper.endpoint());
         if self.by_endpoint.contains_key(&endpoint) {
             return false;
         }
@@ -1189,7 +1430,7 @@ impl ChannelContainer {
             .entry(wrapper.subnetwork())
             .or_default()
             .push(endpoint);
-        self.by_endpoint.insert(wrapper.endpoint(), wrapper);
+        self.by_endpoint.insert(endpoint, wrapper);
         true
     }
 
@@ -1209,19 +1450,46 @@ impl ChannelContainer {
             .flat_map(|(_, v)| v.iter().map(|ep| self.by_endpoint.get(ep).unwrap()))
     }
 
+    /// Peering endpoints of up to `count` random channels. Temporary channels and the
+    /// recipient of the keepalive message are excluded
+    pub fn sample_keepalive_peers(
+        &self,
+        recipient: Option<&SocketAddr>,
+        count: usize,
+    ) -> Vec<SocketAddr> {
+        let is_recipient = |endpoint: &SocketAddr| recipient == Some(endpoint);
+        let candidates: Vec<_> = self
+            .iter()
+            .filter(|c| {
+                let channel = c.channel.as_channel();
+                channel.is_alive() && !channel.is_temporary() && !is_recipient(&c.endpoint())
+            })
+            .map(|c| c.tcp_channel().peering_endpoint())
+            .filter(|endpoint| !is_recipient(endpoint))
+            .collect();
+        candidates
+            .choose_multiple(&mut thread_rng(), count)
+            .cloned()
+            .collect()
+    }
+
     pub fn exists(&self, endpoint: &SocketAddr) -> bool {
-        self.by_endpoint.contains_key(endpoint)
+        self.by_endpoint.contains_key(&Endpoint::from(*endpoint))
     }
 
     pub fn remove_by_node_id(&mut self, node_id: &PublicKey) {
         if let Some(endpoints) = self.by_node_id.get(node_id).cloned() {
             for ep in endpoints {
-                self.remove_by_endpoint(&ep);
+                self.remove(&ep);
             }
         }
     }
 
     pub fn remove_by_endpoint(&mut self, endpoint: &SocketAddr) -> Option<Arc<ChannelEnum>> {
+        self.remove(&Endpoint::from(*endpoint))
+    }
+
+    fn remove(&mut self, endpoint: &Endpoint) -> Option<Arc<ChannelEnum>> {
         if let Some(wrapper) = self.by_endpoint.remove(endpoint) {
             self.by_random_access.retain(|x| x != endpoint); // todo: linear search is slow?
 
@@ -1258,7 +1526,7 @@ impl ChannelContainer {
     }
 
     pub fn get(&self, endpoint: &SocketAddr) -> Option<&Arc<ChannelTcpWrapper>> {
-        self.by_endpoint.get(endpoint)
+        self.by_endpoint.get(&Endpoint::from(*endpoint))
     }
 
     pub fn get_by_index(&self, index: usize) -> Option<&Arc<ChannelTcpWrapper>> {
@@ -1275,34 +1543,47 @@ impl ChannelContainer {
             .flatten()
     }
 
-    pub fn set_last_packet_sent(&mut self, endpoint: &SocketAddr, time: SystemTime) {
-        if let Some(channel) = self.by_endpoint.get(endpoint) {
+    pub fn set_last_packet_sent(&mut self, endpoint: &SocketAddr, time: Timestamp) {
+        let endpoint = Endpoint::from(*endpoint);
+        if let Some(channel) = self.by_endpoint.get(&endpoint) {
             let old_time = channel.last_packet_sent();
             channel.channel.as_channel().set_last_packet_sent(time);
-            remove_endpoint_btree(&mut self.by_last_packet_sent, &old_time, endpoint);
+            remove_endpoint_btree(&mut self.by_last_packet_sent, &old_time, &endpoint);
             self.by_last_packet_sent
                 .entry(time)
                 .or_default()
-                .push(*endpoint);
+                .push(endpoint);
         }
     }
 
-    pub fn set_last_bootstrap_attempt(&mut self, endpoint: &SocketAddr, attempt_time: SystemTime) {
-        if let Some(channel) = self.by_endpoint.get(endpoint) {
+    pub fn set_last_bootstrap_attempt(&mut self, endpoint: &SocketAddr, attempt_time: Timestamp) {
+        let endpoint = Endpoint::from(*endpoint);
+        if let Some(channel) = self.by_endpoint.get(&endpoint) {
             let old_time = channel.last_bootstrap_attempt();
             channel
                 .channel
                 .as_channel()
                 .set_last_bootstrap_attempt(attempt_time);
-            remove_endpoint_btree(
-                &mut self.by_bootstrap_attempt,
-                &old_time,
-                &channel.endpoint(),
-            );
+            remove_endpoint_btree(&mut self.by_bootstrap_attempt, &old_time, &endpoint);
             self.by_bootstrap_attempt
                 .entry(attempt_time)
                 .or_default()
-                .push(*endpoint);
+                .push(endpoint);
+        }
+    }
+
+    /// Returns the stat detail of the exceeded limit if the channel can't be inserted
+    pub fn check_peer_limits(
+        &self,
+        wrapper: &ChannelTcpWrapper,
+        limits: &PeerLimits,
+    ) -> Result<(), DetailType> {
+        if self.count_by_ip(&wrapper.ip_address()) >= limits.max_per_ip {
+            Err(DetailType::TcpMaxPerIp)
+        } else if self.count_by_subnet(&wrapper.subnetwork()) >= limits.max_per_subnetwork {
+            Err(DetailType::TcpMaxPerSubnetwork)
+        } else {
+            Ok(())
         }
     }
 
@@ -1331,17 +1612,19 @@ impl ChannelContainer {
         self.by_subnet.clear();
     }
 
-    pub fn purge(&mut self, cutoff: SystemTime) {
+    pub fn purge(&mut self, cutoff: Timestamp) -> Vec<Arc<ChannelEnum>> {
+        let mut removed = Vec::new();
         while let Some((time, endpoints)) = self.by_last_packet_sent.first_key_value() {
             if *time < cutoff {
                 let endpoints = endpoints.clone();
                 for ep in endpoints {
-                    self.remove_by_endpoint(&ep);
+                    removed.extend(self.remove(&ep));
                 }
             } else {
                 break;
             }
         }
+        removed
     }
 
     pub fn remove_dead(&mut self) {
@@ -1357,24 +1640,26 @@ impl ChannelContainer {
         }
     }
 
-    pub fn remove_old_protocol_versions(&mut self, min_version: u8) {
+    pub fn remove_old_protocol_versions(&mut self, min_version: u8) -> Vec<Arc<ChannelEnum>> {
+        let mut removed = Vec::new();
         while let Some((version, endpoints)) = self.by_network_version.first_key_value() {
             if *version < min_version {
                 let endpoints = endpoints.clone();
                 for ep in endpoints {
-                    self.remove_by_endpoint(&ep);
+                    removed.extend(self.remove(&ep));
                 }
             } else {
                 break;
             }
         }
+        removed
     }
 }
 
 fn remove_endpoint_btree<K: Ord>(
-    tree: &mut BTreeMap<K, Vec<SocketAddr>>,
+    tree: &mut BTreeMap<K, Vec<Endpoint>>,
     key: &K,
-    endpoint: &SocketAddr,
+    endpoint: &Endpoint,
 ) {
     let endpoints = tree.get_mut(key).unwrap();
     if endpoints.len() > 1 {
@@ -1385,9 +1670,9 @@ fn remove_endpoint_btree<K: Ord>(
 }
 
 fn remove_endpoint_map<K: Eq + PartialEq + Hash>(
-    map: &mut HashMap<K, Vec<SocketAddr>>,
+    map: &mut HashMap<K, Vec<Endpoint>>,
     key: &K,
-    endpoint: &SocketAddr,
+    endpoint: &Endpoint,
 ) {
     let endpoints = map.get_mut(key).unwrap();
     if endpoints.len() > 1 {
@@ -1397,11 +1682,48 @@ fn remove_endpoint_map<K: Eq + PartialEq + Hash>(
     }
 }
 
+/// Fills up the sampled peers with preconfigured peers. Only preconfigured peers which
+/// are IP addresses are used, because hostnames would have to be resolved first.
+/// Unused entries are null endpoints
+fn keepalive_payload(
+    sampled: Vec<SocketAddr>,
+    preconfigured_peers: &[String],
+    default_port: u16,
+    recipient: Option<&SocketAddr>,
+) -> [SocketAddr; KEEPALIVE_PEER_COUNT] {
+    let mut peers = sampled;
+    for entry in preconfigured_peers {
+        if peers.len() >= KEEPALIVE_PEER_COUNT {
+            break;
+        }
+        let Ok(peer) = Peer::parse_with_default_port(entry, default_port) else {
+            continue;
+        };
+        let Ok(address) = peer.address.parse::<IpAddr>() else {
+            continue;
+        };
+        let address = match address {
+            IpAddr::V4(v4) => IpAddr::V6(v4.to_ipv6_mapped()),
+            v6 => v6,
+        };
+        let endpoint = SocketAddr::new(address, peer.port);
+        if recipient != Some(&endpoint) && !peers.contains(&endpoint) {
+            peers.push(endpoint);
+        }
+    }
+
+    let mut result = [SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0); KEEPALIVE_PEER_COUNT];
+    for (target, peer) in result.iter_mut().zip(peers) {
+        *target = peer;
+    }
+    result
+}
+
 pub struct TcpEndpointAttempt {
     pub endpoint: SocketAddrV6,
     pub address: Ipv6Addr,
     pub subnetwork: Ipv6Addr,
-    pub last_attempt: SystemTime,
+    pub last_attempt: Timestamp,
 }
 
 impl TcpEndpointAttempt {
@@ -1410,7 +1732,7 @@ impl TcpEndpointAttempt {
             endpoint,
             address: ipv4_address_or_ipv6_subnet(endpoint.ip()),
             subnetwork: map_address_to_subnetwork(endpoint.ip()),
-            last_attempt: SystemTime::now(),
+            last_attempt: Timestamp::now(),
         }
     }
 }
@@ -1420,7 +1742,7 @@ pub struct TcpEndpointAttemptContainer {
     by_endpoint: HashMap<SocketAddrV6, TcpEndpointAttempt>,
     by_address: HashMap<Ipv6Addr, Vec<SocketAddrV6>>,
     by_subnetwork: HashMap<Ipv6Addr, Vec<SocketAddrV6>>,
-    by_time: BTreeMap<SystemTime, Vec<SocketAddrV6>>,
+    by_time: BTreeMap<Timestamp, Vec<SocketAddrV6>>,
 }
 
 impl TcpEndpointAttemptContainer {
@@ -1487,7 +1809,7 @@ impl TcpEndpointAttemptContainer {
         self.by_endpoint.len()
     }
 
-    pub fn purge(&mut self, cutoff: SystemTime) {
+    pub fn purge(&mut self, cutoff: Timestamp) {
         while let Some((time, endpoint)) = self.get_oldest() {
             if time >= cutoff {
                 return;
@@ -1497,8 +1819,263 @@ impl TcpEndpointAttemptContainer {
         }
     }
 
-    fn get_oldest(&self) -> Option<(SystemTime, SocketAddrV6)> {
+    fn get_oldest(&self) -> Option<(Timestamp, SocketAddrV6)> {
         let (time, endpoints) = self.by_time.first_key_value()?;
         Some((*time, endpoints[0]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Logging,
+        stats::StatsConfig,
+        transport::{NullSocketObserver, NullTcpSocketFacadeFactory, CLOSE_GRACEFULLY_TIMEOUT},
+        utils::{StubIoContext, ThreadPoolImpl},
+        DEV_NETWORK_PARAMS,
+    };
+    use rsnano_core::{
+        utils::{NullLogger, SystemClock},
+        Networks,
+    };
+    use std::{
+        net::Ipv4Addr,
+        thread,
+        time::{Instant, SystemTime},
+    };
+
+    #[test]
+    fn max_peers_per_ip() {
+        let mut channels = ChannelContainer::default();
+        let limits = PeerLimits {
+            max_per_ip: 2,
+            max_per_subnetwork: 100,
+        };
+        channels.insert(test_wrapper(test_endpoint([1, 2, 3, 4], 1000)));
+        channels.insert(test_wrapper(test_endpoint([1, 2, 3, 4], 1001)));
+
+        let same_ip = test_wrapper(test_endpoint([1, 2, 3, 4], 1002));
+        let other_ip = test_wrapper(test_endpoint([1, 2, 3, 5], 1000));
+
+        assert_eq!(
+            channels.check_peer_limits(&same_ip, &limits),
+            Err(DetailType::TcpMaxPerIp)
+        );
+        assert_eq!(channels.check_peer_limits(&other_ip, &limits), Ok(()));
+    }
+
+    #[test]
+    fn max_peers_per_subnetwork() {
+        let mut channels = ChannelContainer::default();
+        let limits = PeerLimits {
+            max_per_ip: 100,
+            max_per_subnetwork: 2,
+        };
+        channels.insert(test_wrapper(test_endpoint([1, 2, 3, 4], 1000)));
+        channels.insert(test_wrapper(test_endpoint([1, 2, 3, 5], 1000)));
+
+        let same_subnet = test_wrapper(test_endpoint([1, 2, 3, 6], 1000));
+        let other_subnet = test_wrapper(test_endpoint([1, 2, 4, 4], 1000));
+
+        assert_eq!(
+            channels.check_peer_limits(&same_subnet, &limits),
+            Err(DetailType::TcpMaxPerSubnetwork)
+        );
+        assert_eq!(channels.check_peer_limits(&other_subnet, &limits), Ok(()));
+    }
+
+    #[test]
+    fn simultaneous_inserts_dont_exceed_limit() {
+        let mut node_config = NodeConfig::new(None, Logging::new(), &DEV_NETWORK_PARAMS);
+        node_config.max_peers_per_ip = 3;
+        node_config.max_peers_per_subnetwork = 100;
+        let tcp_channels = Arc::new(create_tcp_channels(node_config));
+
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                let tcp_channels = Arc::clone(&tcp_channels);
+                thread::spawn(move || {
+                    let wrapper = test_wrapper(test_endpoint([1, 2, 3, 4], 1000 + i));
+                    wrapper
+                        .channel
+                        .as_channel()
+                        .set_node_id(PublicKey::from(i as u64 + 1));
+                    let socket = wrapper.socket().unwrap();
+                    tcp_channels.insert(&wrapper.channel, &socket, None).is_ok()
+                })
+            })
+            .collect();
+
+        let inserted = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|inserted| *inserted)
+            .count();
+
+        assert_eq!(inserted, 3);
+        let guard = tcp_channels.tcp_channels.lock().unwrap();
+        assert_eq!(guard.channels.len(), 3);
+        assert_eq!(
+            guard
+                .channels
+                .count_by_ip(&Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped()),
+            3
+        );
+    }
+
+    #[test]
+    fn sample_keepalive_peers() {
+        let mut channels = ChannelContainer::default();
+        for i in 0..10 {
+            channels.insert(test_wrapper(test_endpoint([1, 2, 3, i], 1000)));
+        }
+        let recipient = test_endpoint([1, 2, 3, 0], 1000);
+
+        let peers = channels.sample_keepalive_peers(Some(&recipient), KEEPALIVE_PEER_COUNT);
+
+        assert_eq!(peers.len(), KEEPALIVE_PEER_COUNT);
+        assert!(!peers.contains(&recipient));
+        let unique: HashSet<_> = peers.iter().collect();
+        assert_eq!(unique.len(), KEEPALIVE_PEER_COUNT);
+    }
+
+    #[test]
+    fn exclude_temporary_channels_from_keepalive() {
+        let mut channels = ChannelContainer::default();
+        let temporary = test_wrapper(test_endpoint([1, 2, 3, 4], 1000));
+        temporary.channel.as_channel().set_temporary(true);
+        channels.insert(temporary);
+        channels.insert(test_wrapper(test_endpoint([1, 2, 3, 5], 1000)));
+
+        assert_eq!(
+            channels.sample_keepalive_peers(None, KEEPALIVE_PEER_COUNT),
+            vec![test_endpoint([1, 2, 3, 5], 1000)]
+        );
+    }
+
+    #[test]
+    fn fill_keepalive_with_preconfigured_peers() {
+        let sampled = vec![test_endpoint([1, 2, 3, 4], 1000)];
+        let preconfigured = vec![
+            "peering.example.com".to_owned(),
+            "5.6.7.8".to_owned(),
+            "1.2.3.4:1000".to_owned(),
+            "[::1]:2000".to_owned(),
+            "9.9.9.9".to_owned(),
+        ];
+        let recipient = test_endpoint([9, 9, 9, 9], 7075);
+
+        let peers = keepalive_payload(sampled, &preconfigured, 7075, Some(&recipient));
+
+        let null_endpoint = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0);
+        assert_eq!(
+            peers,
+            [
+                test_endpoint([1, 2, 3, 4], 1000),
+                test_endpoint([5, 6, 7, 8], 7075),
+                SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 2000),
+                null_endpoint,
+                null_endpoint,
+                null_endpoint,
+                null_endpoint,
+                null_endpoint,
+            ]
+        );
+    }
+
+    #[test]
+    fn dont_use_preconfigured_peers_when_full() {
+        let sampled: Vec<_> = (0..8).map(|i| test_endpoint([1, 2, 3, i], 1000)).collect();
+        let peers = keepalive_payload(sampled.clone(), &["5.6.7.8".to_owned()], 7075, None);
+        assert_eq!(peers.to_vec(), sampled);
+    }
+
+    #[test]
+    fn purge_keeps_sub_second_precision() {
+        let mut channels = ChannelContainer::default();
+        let old = test_endpoint([1, 2, 3, 4], 1000);
+        let recent = test_endpoint([1, 2, 3, 5], 1000);
+        channels.insert(test_wrapper(old));
+        channels.insert(test_wrapper(recent));
+        let cutoff = Timestamp::from_secs(100) + Duration::from_millis(500);
+        channels.set_last_packet_sent(&old, cutoff - Duration::from_millis(1));
+        channels.set_last_packet_sent(&recent, cutoff);
+
+        let removed = channels.purge(cutoff);
+
+        assert_eq!(removed.len(), 1);
+        assert!(!channels.exists(&old));
+        assert!(channels.exists(&recent));
+    }
+
+    #[test]
+    fn stop_returns_promptly_when_a_peer_never_drains() {
+        let tcp_channels =
+            create_tcp_channels(NodeConfig::new(None, Logging::new(), &DEV_NETWORK_PARAMS));
+        let wrapper = test_wrapper(test_endpoint([1, 2, 3, 4], 1000));
+        let socket = wrapper.socket().unwrap();
+        // The null socket never completes a write, so the queue is never flushed
+        socket.async_write(&Arc::new(vec![1]), None, TrafficType::Generic);
+        tcp_channels
+            .tcp_channels
+            .lock()
+            .unwrap()
+            .channels
+            .insert(wrapper);
+
+        let start = Instant::now();
+        tcp_channels.stop();
+
+        assert!(start.elapsed() < CLOSE_GRACEFULLY_TIMEOUT / 2);
+        assert!(socket.is_closed());
+    }
+
+    fn create_tcp_channels(node_config: NodeConfig) -> TcpChannels {
+        TcpChannels::new(TcpChannelsOptions {
+            node_config,
+            logger: Arc::new(NullLogger::new()),
+            publish_filter: Arc::new(NetworkFilter::new(256)),
+            io_ctx: Arc::new(StubIoContext::new()),
+            network: NetworkParams::new(Networks::NanoDevNetwork),
+            stats: Arc::new(Stats::new(StatsConfig::new())),
+            block_uniquer: Arc::new(BlockUniquer::new()),
+            vote_uniquer: Arc::new(VoteUniquer::new()),
+            tcp_message_manager: Arc::new(TcpMessageManager::new(16)),
+            port: 7075,
+            flags: NodeFlags::new(),
+            sink: Box::new(|_, _| {}),
+            limiter: Arc::new(OutboundBandwidthLimiter::default()),
+            node_id: KeyPair::new(),
+            syn_cookies: Arc::new(SynCookies::new(1)),
+            workers: Arc::new(ThreadPoolImpl::create_null()),
+            tcp_socket_factory: Arc::new(NullTcpSocketFacadeFactory {}),
+            observer: Arc::new(NullSocketObserver {}),
+            clock: Arc::new(SystemClock::new()),
+        })
+    }
+
+    fn test_endpoint(ip: [u8; 4], port: u16) -> SocketAddr {
+        SocketAddr::new(IpAddr::V6(Ipv4Addr::from(ip).to_ipv6_mapped()), port)
+    }
+
+    fn test_wrapper(endpoint: SocketAddr) -> Arc<ChannelTcpWrapper> {
+        let socket = Socket::create_null();
+        socket.set_remote(endpoint);
+        let observer: Weak<dyn ChannelTcpObserver> = Weak::<ChannelTcpObserverImpl>::new();
+        let channel = ChannelTcp::new(
+            &socket,
+            SystemTime::now(),
+            Arc::new(ChannelTcpObserverWeakPtr(observer)),
+            Arc::new(OutboundBandwidthLimiter::default()),
+            Arc::new(StubIoContext::new()),
+            endpoint.port() as usize,
+        );
+        channel.set_remote_endpoint();
+        Arc::new(ChannelTcpWrapper::new(
+            Arc::new(ChannelEnum::Tcp(channel)),
+            socket,
+            None,
+        ))
+    }
+}
@@ -22,8 +22,8 @@ use crate::{
     },
     stats::{DetailType, Direction, StatType, Stats},
     transport::{
-        MessageDeserializer, MessageDeserializerExt, ParseStatus, Socket, SocketExtensions,
-        SocketType, SynCookies, TcpMessageItem, TcpMessageManager,
+        MessageDeserializer, MessageDeserializerExt, ParseStatus, PeerExclusion, Socket,
+        SocketExtensions, SocketType, SynCookies, TcpMessageItem, TcpMessageManager,
     },
     utils::{BlockUniquer, IoContext},
     voting::VoteUniquer,
@@ -91,6 +91,8 @@ pub struct TcpServer {
     message_deserializer: Arc<MessageDeserializer>,
     tcp_message_manager: Arc<TcpMessageManager>,
     allow_bootstrap: bool,
+    /// Peers that send messages for another network are added here
+    pub excluded_peers: Option<Arc<Mutex<PeerExclusion>>>,
 }
 
 static NEXT_UNIQUE_ID: AtomicUsize = AtomicUsize::new(0);
@@ -146,6 +148,7 @@ impl TcpServer {
             )),
             tcp_message_manager,
             allow_bootstrap,
+            excluded_peers: None,
         }
     }
 
@@ -241,6 +244,41 @@ impl TcpServer {
         self.socket.is_realtime_connection()
     }
 
+    /// Called when the peer sent a message with the id of another network
+    fn wrong_network(&self) {
+        let endpoint = self.remote_endpoint();
+        if let Some(excluded_peers) = &self.excluded_peers {
+            excluded_peers.lock().unwrap().peer_misbehaved(&endpoint);
+            self.stats.inc(
+                StatType::Tcp,
+                DetailType::ExcludedWrongNetwork,
+                Direction::In,
+            );
+        }
+        if self.config.logging.network_packet_logging() {
+            self.logger.try_log(&format!(
+                "Received message for a different network from {}",
+                endpoint
+            ));
+        }
+    }
+
+    /// Counts a message of another network, that was accepted because of warn only mode
+    fn wrong_network_ignored(&self, message: &dyn Message) {
+        self.stats.inc(
+            StatType::Filter,
+            DetailType::InvalidNetworkIgnored,
+            Direction::In,
+        );
+        if self.config.logging.network_packet_logging() {
+            self.logger.try_log(&format!(
+                "Accepted message for network {} from {}",
+                message.header().network_name(),
+                self.remote_endpoint()
+            ));
+        }
+    }
+
     pub fn queue_realtime(&self, message: Box<dyn Message>) {
         self.tcp_message_manager.put_message(TcpMessageItem {
             message: Some(message),
@@ -322,6 +360,9 @@ impl TcpServerExt for Arc<TcpServer> {
                     DetailType::from(self_clone.message_deserializer.status()),
                     Direction::In,
                 );
+                if self_clone.message_deserializer.status() == ParseStatus::InvalidNetwork {
+                    self_clone.wrong_network();
+                }
                 self_clone.stop();
                 return;
             }
@@ -333,6 +374,9 @@ impl TcpServerExt for Arc<TcpServer> {
         let mut should_continue = true;
         match message {
             Some(message) => {
+                if message.header().network_id() != self.network.network.network_id {
+                    self.wrong_network_ignored(message.as_ref());
+                }
                 should_continue = self.process_message(message);
             }
             None => {
@@ -518,7 +562,7 @@ impl HandshakeMessageVisitorImpl {
                     }
                 }
             })),
-            super::TrafficType::Generic,
+            super::TrafficType::Priority,
         );
     }
 
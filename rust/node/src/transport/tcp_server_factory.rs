@@ -1,6 +1,6 @@
-use std::{sync::Arc, time::SystemTime};
+use std::sync::{Arc, Mutex};
 
-use rsnano_core::utils::Logger;
+use rsnano_core::utils::{Logger, Timestamp};
 
 use crate::{
     bootstrap::BootstrapMessageVisitorFactory,
@@ -12,8 +12,8 @@ use crate::{
 };
 
 use super::{
-    Channel, ChannelTcp, NetworkFilter, Socket, SocketType, TcpMessageManager, TcpServer,
-    TcpServerExt, TcpServerObserver,
+    Channel, ChannelTcp, NetworkFilter, PeerExclusion, Socket, SocketType, TcpMessageManager,
+    TcpServer, TcpServerExt, TcpServerObserver,
 };
 
 pub struct TcpServerFactory {
@@ -28,11 +28,12 @@ pub struct TcpServerFactory {
     pub vote_uniquer: Arc<VoteUniquer>,
     pub tcp_message_manager: Arc<TcpMessageManager>,
     pub message_visitor_factory: Option<Arc<BootstrapMessageVisitorFactory>>,
+    pub excluded_peers: Arc<Mutex<PeerExclusion>>,
 }
 impl TcpServerFactory {
     pub fn create_tcp_server(&self, channel: &ChannelTcp, socket: Arc<Socket>) -> Arc<TcpServer> {
-        channel.set_last_packet_sent(SystemTime::now());
-        let response_server = TcpServer::new(
+        channel.set_last_packet_sent(Timestamp::now());
+        let mut response_server = TcpServer::new(
             socket,
             Arc::clone(&self.config),
             Arc::clone(&self.logger),
@@ -52,6 +53,7 @@ impl TcpServerFactory {
             ),
             true,
         );
+        response_server.excluded_peers = Some(Arc::clone(&self.excluded_peers));
         // Listen for possible responses
         response_server
             .socket
@@ -14,7 +14,27 @@ pub(crate) struct Entry {
     pub callback: Option<WriteCallback>,
 }
 
+/// Multiple queued entries which are written to the socket with a single write call
+pub(crate) struct CoalescedEntries {
+    pub buffer: Arc<Vec<u8>>,
+    pub entries: Vec<Entry>,
+}
+
+impl CoalescedEntries {
+    /// Calls the callbacks of all coalesced entries. Each callback receives
+    /// the size of its own buffer instead of the size of the whole write.
+    pub fn complete(self, ec: ErrorCode) {
+        for mut entry in self.entries {
+            if let Some(callback) = entry.callback.take() {
+                let size = if ec.is_ok() { entry.buffer.len() } else { 0 };
+                callback(ec, size);
+            }
+        }
+    }
+}
+
 struct Queues {
+    priority_queue: VecDeque<Entry>,
     generic_queue: VecDeque<Entry>,
     bootstrap_queue: VecDeque<Entry>,
 }
@@ -24,6 +44,7 @@ impl Queues {
         match traffic_type {
             TrafficType::Generic => &self.generic_queue,
             TrafficType::Bootstrap => &self.bootstrap_queue,
+            TrafficType::Priority => &self.priority_queue,
         }
     }
 
@@ -31,6 +52,22 @@ impl Queues {
         match traffic_type {
             TrafficType::Generic => &mut self.generic_queue,
             TrafficType::Bootstrap => &mut self.bootstrap_queue,
+            TrafficType::Priority => &mut self.priority_queue,
+        }
+    }
+
+    /// The queue from which the next entry will be written.
+    /// Liveness traffic (handshakes, keepalives) always goes first,
+    /// so that large bootstrap transfers can't starve it.
+    fn next_queue(&mut self) -> Option<&mut VecDeque<Entry>> {
+        if !self.priority_queue.is_empty() {
+            Some(&mut self.priority_queue)
+        } else if !self.generic_queue.is_empty() {
+            Some(&mut self.generic_queue)
+        } else if !self.bootstrap_queue.is_empty() {
+            Some(&mut self.bootstrap_queue)
+        } else {
+            None
         }
     }
 }
@@ -45,6 +82,7 @@ impl WriteQueue {
         Self {
             max_size,
             queues: Mutex::new(Queues {
+                priority_queue: VecDeque::new(),
                 generic_queue: VecDeque::new(),
                 bootstrap_queue: VecDeque::new(),
             }),
@@ -67,20 +105,40 @@ impl WriteQueue {
         }
     }
 
-    pub fn pop(&self) -> Option<Entry> {
+    /// Pops entries of the highest priority lane until `max_frame_size` bytes would be exceeded
+    /// and joins their buffers, so that they can be sent with a single write call.
+    /// A single entry which is bigger than `max_frame_size` is returned on its own.
+    pub fn pop_coalesced(&self, max_frame_size: usize) -> Option<CoalescedEntries> {
         let mut queues = self.queues.lock().unwrap();
+        let queue = queues.next_queue()?;
+        let first = queue.pop_front()?;
 
-        // TODO: This is a very basic prioritization, implement something more advanced and configurable
-        let item = queues.generic_queue.pop_front();
-        if item.is_some() {
-            item
-        } else {
-            queues.bootstrap_queue.pop_front()
+        let mut total_size = first.buffer.len();
+        let mut entries = vec![first];
+        while let Some(next) = queue.front() {
+            if total_size + next.buffer.len() > max_frame_size {
+                break;
+            }
+            total_size += next.buffer.len();
+            entries.push(queue.pop_front().unwrap());
         }
+
+        let buffer = if entries.len() == 1 {
+            Arc::clone(&entries[0].buffer)
+        } else {
+            let mut joined = Vec::with_capacity(total_size);
+            for entry in &entries {
+                joined.extend_from_slice(&entry.buffer);
+            }
+            Arc::new(joined)
+        };
+
+        Some(CoalescedEntries { buffer, entries })
     }
 
     pub fn clear(&self) {
         let mut queues = self.queues.lock().unwrap();
+        queues.priority_queue.clear();
         queues.generic_queue.clear();
         queues.bootstrap_queue.clear();
     }
@@ -92,6 +150,105 @@ impl WriteQueue {
 
     pub fn is_empty(&self) -> bool {
         let queues = self.queues.lock().unwrap();
-        queues.generic_queue.is_empty() && queues.bootstrap_queue.is_empty()
+        queues.priority_queue.is_empty()
+            && queues.generic_queue.is_empty()
+            && queues.bootstrap_queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(queue: &WriteQueue, bytes: &[u8], traffic_type: TrafficType) {
+        let (queued, _) = queue.insert(Arc::new(bytes.to_vec()), None, traffic_type);
+        assert!(queued);
+    }
+
+    #[test]
+    fn empty_queue() {
+        let queue = WriteQueue::new(10);
+        assert!(queue.is_empty());
+        assert!(queue.pop_coalesced(100).is_none());
+    }
+
+    #[test]
+    fn priority_lane_goes_first() {
+        let queue = WriteQueue::new(10);
+        insert(&queue, &[1], TrafficType::Bootstrap);
+        insert(&queue, &[2], TrafficType::Generic);
+        insert(&queue, &[3], TrafficType::Priority);
+
+        assert_eq!(*queue.pop_coalesced(0).unwrap().buffer, vec![3]);
+        assert_eq!(*queue.pop_coalesced(0).unwrap().buffer, vec![2]);
+        assert_eq!(*queue.pop_coalesced(0).unwrap().buffer, vec![1]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn reject_when_lane_full() {
+        let queue = WriteQueue::new(1);
+        insert(&queue, &[1], TrafficType::Bootstrap);
+        insert(&queue, &[2], TrafficType::Bootstrap);
+        let (queued, _) = queue.insert(Arc::new(vec![3]), None, TrafficType::Bootstrap);
+        assert!(!queued);
+
+        // other lanes are not affected
+        insert(&queue, &[4], TrafficType::Priority);
+    }
+
+    #[test]
+    fn coalesce_small_buffers() {
+        let queue = WriteQueue::new(10);
+        insert(&queue, &[1, 2], TrafficType::Generic);
+        insert(&queue, &[3, 4], TrafficType::Generic);
+        insert(&queue, &[5, 6], TrafficType::Generic);
+
+        let coalesced = queue.pop_coalesced(4).unwrap();
+        assert_eq!(*coalesced.buffer, vec![1, 2, 3, 4]);
+        assert_eq!(coalesced.entries.len(), 2);
+
+        let coalesced = queue.pop_coalesced(4).unwrap();
+        assert_eq!(*coalesced.buffer, vec![5, 6]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn oversized_buffer_is_written_alone() {
+        let queue = WriteQueue::new(10);
+        insert(&queue, &[1, 2, 3], TrafficType::Generic);
+        insert(&queue, &[4], TrafficType::Generic);
+
+        let coalesced = queue.pop_coalesced(2).unwrap();
+        assert_eq!(*coalesced.buffer, vec![1, 2, 3]);
+        assert_eq!(coalesced.entries.len(), 1);
+    }
+
+    #[test]
+    fn dont_coalesce_across_lanes() {
+        let queue = WriteQueue::new(10);
+        insert(&queue, &[1], TrafficType::Priority);
+        insert(&queue, &[2], TrafficType::Generic);
+
+        let coalesced = queue.pop_coalesced(100).unwrap();
+        assert_eq!(*coalesced.buffer, vec![1]);
+    }
+
+    #[test]
+    fn complete_calls_each_callback_with_own_size() {
+        let queue = WriteQueue::new(10);
+        let sizes = Arc::new(Mutex::new(Vec::new()));
+        for len in [2, 3] {
+            let sizes = sizes.clone();
+            queue.insert(
+                Arc::new(vec![0; len]),
+                Some(Box::new(move |_, size| sizes.lock().unwrap().push(size))),
+                TrafficType::Generic,
+            );
+        }
+
+        queue.pop_coalesced(100).unwrap().complete(ErrorCode::new());
+
+        assert_eq!(*sizes.lock().unwrap(), vec![2, 3]);
     }
 }
@@ -1,15 +1,23 @@
-use crate::stats::{DetailType, Direction, StatType, Stats};
-use rsnano_core::{BlockHash, HashOrAccount, UncheckedInfo, UncheckedKey};
+use crate::{
+    stats::{DetailType, Direction, StatType, Stats},
+    utils::ThreadRunner,
+};
+use rsnano_core::{
+    utils::{Clock, SystemClock},
+    BlockHash, HashOrAccount, UncheckedInfo, UncheckedKey,
+};
 use std::{
     cmp::Ordering,
     collections::{BTreeMap, VecDeque},
     mem::size_of,
     ops::DerefMut,
     sync::{Arc, Condvar, Mutex},
-    thread::JoinHandle,
+    time::Duration,
 };
 
-const MEM_BLOCK_COUNT_MAX: usize = 64 * 1024;
+/// Maximum number of unchecked blocks which wait for the same dependency.
+/// Without it, a single missing block could be used to fill the whole table.
+pub const MAX_ENTRIES_PER_DEPENDENCY: usize = 1024;
 
 struct UncheckedMapFlags {
     stopped: bool,
@@ -17,15 +25,18 @@ struct UncheckedMapFlags {
 }
 
 pub struct UncheckedMap {
-    join_handle: Option<JoinHandle<()>>,
+    runner: ThreadRunner,
     thread: Arc<UncheckedMapThread>,
     mutable: Arc<Mutex<ThreadMutableData>>,
     condition: Arc<Condvar>,
     stats: Arc<Stats>,
+    max_entries: usize,
+    clock: Arc<dyn Clock>,
 }
 
 impl UncheckedMap {
-    pub fn new(stats: Arc<Stats>, disable_delete: bool) -> Self {
+    /// The oldest entries get evicted when more than `max_entries` blocks are unchecked
+    pub fn new(stats: Arc<Stats>, disable_delete: bool, max_entries: usize) -> Self {
         let mutable = Arc::new(Mutex::new(ThreadMutableData::new()));
         let condition = Arc::new(Condvar::new());
 
@@ -38,22 +49,24 @@ impl UncheckedMap {
         });
 
         let thread_clone = thread.clone();
-        let join_handle = std::thread::Builder::new()
-            .name("Unchecked".to_string())
-            .spawn(move || {
-                thread_clone.run();
-            })
-            .unwrap();
+        let mut runner = ThreadRunner::new("Unchecked");
+        runner.start(move || thread_clone.run()).unwrap();
 
         Self {
-            join_handle: Some(join_handle),
+            runner,
             thread,
             mutable,
             condition,
             stats,
+            max_entries,
+            clock: Arc::new(SystemClock::new()),
         }
     }
 
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
     pub fn stop(&mut self) {
         let mut lock = self.mutable.lock().unwrap();
         if !lock.stopped {
@@ -61,9 +74,7 @@ impl UncheckedMap {
             self.condition.notify_all();
         }
         drop(lock);
-        if let Some(handle) = self.join_handle.take() {
-            handle.join().unwrap();
-        }
+        self.runner.join().unwrap();
     }
 
     pub fn exists(&self, key: &UncheckedKey) -> bool {
@@ -77,9 +88,21 @@ impl UncheckedMap {
             dependency.into(),
             info.block.clone().unwrap().read().unwrap().hash(),
         );
+        if !lock.entries_container.exists(&key)
+            && lock
+                .entries_container
+                .count_with_dependency(&dependency, MAX_ENTRIES_PER_DEPENDENCY)
+                >= MAX_ENTRIES_PER_DEPENDENCY
+        {
+            self.stats
+                .inc(StatType::Unchecked, DetailType::Drop, Direction::In);
+            return;
+        }
         let inserted = lock.entries_container.insert(Entry::new(key, info));
-        if lock.entries_container.len() > MEM_BLOCK_COUNT_MAX {
+        while lock.entries_container.len() > self.max_entries {
             lock.entries_container.pop_front();
+            self.stats
+                .inc(StatType::Unchecked, DetailType::EraseOldest, Direction::In);
         }
         if inserted {
             self.stats
@@ -105,6 +128,35 @@ impl UncheckedMap {
         lock.entries_container.clear();
     }
 
+    /// Removes all entries which were added more than `max_age` ago and calls
+    /// `action` for each removed entry. Returns the number of removed entries.
+    pub fn remove_older_than(
+        &self,
+        max_age: Duration,
+        action: &mut dyn FnMut(&UncheckedKey, &UncheckedInfo),
+    ) -> usize {
+        let cutoff = self
+            .clock
+            .seconds_since_epoch()
+            .saturating_sub(max_age.as_secs());
+        let mut lock = self.mutable.lock().unwrap();
+        let removed = lock
+            .entries_container
+            .remove_if(|entry| entry.info.modified < cutoff);
+        drop(lock);
+        for entry in &removed {
+            action(&entry.key, &entry.info);
+        }
+        self.stats.add(
+            StatType::Unchecked,
+            DetailType::Old,
+            Direction::In,
+            removed.len() as u64,
+            false,
+        );
+        removed.len()
+    }
+
     pub fn trigger(&self, dependency: &HashOrAccount) {
         let mut lock = self.mutable.lock().unwrap();
         lock.buffer.push_back(*dependency);
@@ -348,6 +400,33 @@ impl EntriesContainer {
         }
     }
 
+    fn remove_if(&mut self, predicate: impl Fn(&Entry) -> bool) -> Vec<Entry> {
+        let ids: Vec<usize> = self
+            .by_id
+            .iter()
+            .filter(|(_, entry)| predicate(entry))
+            .map(|(id, _)| *id)
+            .collect();
+        let mut removed = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(entry) = self.by_id.remove(&id) {
+                self.by_key.remove(&entry.key);
+                removed.push(entry);
+            }
+        }
+        removed
+    }
+
+    /// Counts the entries which wait for `dependency`, but stops counting at `limit`
+    fn count_with_dependency(&self, dependency: &HashOrAccount, limit: usize) -> usize {
+        let start = UncheckedKey::new(dependency.into(), BlockHash::zero());
+        self.by_key
+            .range(start..)
+            .take_while(|(key, _)| key.previous == dependency.into())
+            .take(limit)
+            .count()
+    }
+
     fn clear(&mut self) {
         self.by_id.clear();
         self.by_key.clear();
@@ -395,6 +474,8 @@ impl EntriesContainer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rsnano_core::{utils::TestClock, BlockBuilder};
+    use std::sync::RwLock;
 
     #[test]
     fn empty_container() {
@@ -494,6 +575,76 @@ mod tests {
         assert_eq!(container.exists(&entry.key), false);
     }
 
+    #[test]
+    fn remove_entries_with_predicate() {
+        let mut container = EntriesContainer::new();
+        container.insert(test_entry(1));
+        container.insert(test_entry(2));
+        container.insert(test_entry(3));
+
+        let removed = container.remove_if(|e| e.key.previous != BlockHash::from(2));
+
+        assert_eq!(removed.len(), 2);
+        assert_eq!(container.len(), 1);
+        assert_eq!(container.by_key.len(), 1);
+    }
+
+    #[test]
+    fn evict_oldest_entry_when_full() {
+        let map = UncheckedMap::new(Arc::new(Stats::default()), false, 2);
+        map.put(HashOrAccount::from(1), test_info(1));
+        map.put(HashOrAccount::from(1), test_info(2));
+        map.put(HashOrAccount::from(1), test_info(3));
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&HashOrAccount::from(1)).len(), 2);
+    }
+
+    #[test]
+    fn limit_entries_per_dependency() {
+        let map = UncheckedMap::new(Arc::new(Stats::default()), false, usize::MAX);
+        for i in 0..MAX_ENTRIES_PER_DEPENDENCY + 1 {
+            map.put(HashOrAccount::from(1), test_info(i as u64));
+        }
+        map.put(HashOrAccount::from(2), test_info(0));
+
+        assert_eq!(map.len(), MAX_ENTRIES_PER_DEPENDENCY + 1);
+        assert_eq!(
+            map.get(&HashOrAccount::from(1)).len(),
+            MAX_ENTRIES_PER_DEPENDENCY
+        );
+    }
+
+    #[test]
+    fn remove_old_entries() {
+        let clock = Arc::new(TestClock::new());
+        let mut map = UncheckedMap::new(Arc::new(Stats::default()), false, usize::MAX);
+        map.set_clock(clock.clone());
+        let mut old = test_info(1);
+        old.modified = clock.seconds_since_epoch();
+        map.put(HashOrAccount::from(1), old);
+        clock.advance(Duration::from_secs(100));
+        let mut new = test_info(2);
+        new.modified = clock.seconds_since_epoch();
+        map.put(HashOrAccount::from(1), new);
+
+        let mut removed = Vec::new();
+        let count = map.remove_older_than(Duration::from_secs(50), &mut |key, _| {
+            removed.push(key.clone())
+        });
+
+        assert_eq!(count, 1);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(map.len(), 1);
+    }
+
+    fn test_info(previous: u64) -> UncheckedInfo {
+        let block = BlockBuilder::legacy_send()
+            .previous(BlockHash::from(previous))
+            .build();
+        UncheckedInfo::new(Arc::new(RwLock::new(block)))
+    }
+
     fn test_entry<T: Into<BlockHash>>(hash: T) -> Entry {
         Entry::new(
             UncheckedKey::new(hash.into(), BlockHash::default()),
@@ -33,6 +33,14 @@ pub use toml::*;
 mod long_running_transaction_logger;
 pub use long_running_transaction_logger::{LongRunningTransactionLogger, TxnTrackingConfig};
 
+mod thread_runner;
+pub use thread_runner::{log_panics, ThreadRunner};
+
+mod resource_watchdog;
+pub use resource_watchdog::{
+    ResourceProbe, ResourceStatus, ResourceWatchdog, ResourceWatchdogConfig, SystemResourceProbe,
+};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ErrorCode {
     pub val: i32,
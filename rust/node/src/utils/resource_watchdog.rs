@@ -0,0 +1,338 @@
+use std::{
+    ffi::CString,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use rsnano_core::utils::Logger;
+
+const MB: u64 = 1024 * 1024;
+
+pub struct ResourceWatchdogConfig {
+    /// Log a warning if the free disk space drops below this
+    pub free_disk_warning_mb: u64,
+    /// Pause non-essential writes if the free disk space drops below this. 0 disables pausing
+    pub free_disk_pause_mb: u64,
+    /// Request a shutdown if the free disk space drops below this. 0 disables the shutdown
+    pub free_disk_shutdown_mb: u64,
+    /// Log a warning if the resident memory exceeds this. 0 disables the warning
+    pub memory_warning_mb: u64,
+}
+
+impl Default for ResourceWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            free_disk_warning_mb: 10 * 1024,
+            free_disk_pause_mb: 0,
+            free_disk_shutdown_mb: 0,
+            memory_warning_mb: 16 * 1024,
+        }
+    }
+}
+
+/// Reads the resource usage of the system
+pub trait ResourceProbe: Send + Sync {
+    /// Free bytes on the file system which contains `path`
+    fn free_disk_space(&self, path: &Path) -> Option<u64>;
+    /// Resident memory of this process in bytes
+    fn resident_memory(&self) -> Option<u64>;
+}
+
+#[derive(Default)]
+pub struct SystemResourceProbe {}
+
+impl ResourceProbe for SystemResourceProbe {
+    fn free_disk_space(&self, path: &Path) -> Option<u64> {
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+        Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+    }
+
+    fn resident_memory(&self) -> Option<u64> {
+        // The second field of statm is the number of resident pages
+        let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+        let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        Some(pages * page_size.max(0) as u64)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[repr(u8)]
+pub enum ResourceStatus {
+    Ok,
+    /// The free disk space is below the warning threshold
+    DiskLow,
+    /// Non-essential writes should be paused
+    DiskNearlyFull,
+    /// The node should shut down before the ledger runs out of disk space
+    DiskFull,
+}
+
+/// Monitors the free disk space in the data path and the memory usage of the node.
+/// The status is logged whenever it changes. Non-essential writes like pruning
+/// should check `writes_paused` and report skipped writes with `write_dropped`.
+/// The node should shut down if `check` returns `DiskFull`.
+/// By default the watchdog only warns, pausing and shutting down have to be enabled.
+pub struct ResourceWatchdog {
+    config: ResourceWatchdogConfig,
+    data_path: PathBuf,
+    probe: Box<dyn ResourceProbe>,
+    logger: Arc<dyn Logger>,
+    writes_paused: AtomicBool,
+    dropped_writes: AtomicU64,
+    state: Mutex<WatchdogState>,
+}
+
+struct WatchdogState {
+    status: ResourceStatus,
+    memory_warned: bool,
+}
+
+impl ResourceWatchdog {
+    pub fn new(
+        config: ResourceWatchdogConfig,
+        data_path: impl Into<PathBuf>,
+        logger: Arc<dyn Logger>,
+    ) -> Self {
+        Self::with_probe(
+            config,
+            data_path,
+            logger,
+            Box::new(SystemResourceProbe::default()),
+        )
+    }
+
+    pub fn with_probe(
+        config: ResourceWatchdogConfig,
+        data_path: impl Into<PathBuf>,
+        logger: Arc<dyn Logger>,
+        probe: Box<dyn ResourceProbe>,
+    ) -> Self {
+        Self {
+            config,
+            data_path: data_path.into(),
+            probe,
+            logger,
+            writes_paused: AtomicBool::new(false),
+            dropped_writes: AtomicU64::new(0),
+            state: Mutex::new(WatchdogState {
+                status: ResourceStatus::Ok,
+                memory_warned: false,
+            }),
+        }
+    }
+
+    pub fn writes_paused(&self) -> bool {
+        self.writes_paused.load(Ordering::SeqCst)
+    }
+
+    /// Counts a write which was skipped because writes are paused. The first one is logged
+    pub fn write_dropped(&self) {
+        if self.dropped_writes.fetch_add(1, Ordering::SeqCst) == 0 {
+            self.logger
+                .always_log("Dropping unchecked blocks until disk space is freed");
+        }
+    }
+
+    /// Number of writes which were skipped since writes were paused
+    pub fn dropped_writes(&self) -> u64 {
+        self.dropped_writes.load(Ordering::SeqCst)
+    }
+
+    pub fn status(&self) -> ResourceStatus {
+        self.state.lock().unwrap().status
+    }
+
+    /// Samples the resource usage and updates the status
+    pub fn check(&self) -> ResourceStatus {
+        let mut state = self.state.lock().unwrap();
+        self.check_memory(&mut state);
+
+        let Some(free) = self.probe.free_disk_space(&self.data_path) else {
+            return state.status;
+        };
+        let status = self.disk_status(free);
+        if status != state.status {
+            self.log_status_change(status, free);
+            state.status = status;
+        }
+        let paused = status >= ResourceStatus::DiskNearlyFull;
+        let was_paused = self.writes_paused.swap(paused, Ordering::SeqCst);
+        if was_paused && !paused {
+            let dropped = self.dropped_writes.swap(0, Ordering::SeqCst);
+            if dropped > 0 {
+                self.logger.always_log(&format!(
+                    "Resuming writes, {} unchecked blocks were dropped while writes were paused",
+                    dropped
+                ));
+            }
+        }
+        status
+    }
+
+    fn disk_status(&self, free: u64) -> ResourceStatus {
+        // A threshold of 0 is never reached, which disables it
+        if free < self.config.free_disk_shutdown_mb * MB {
+            ResourceStatus::DiskFull
+        } else if free < self.config.free_disk_pause_mb * MB {
+            ResourceStatus::DiskNearlyFull
+        } else if free < self.config.free_disk_warning_mb * MB {
+            ResourceStatus::DiskLow
+        } else {
+            ResourceStatus::Ok
+        }
+    }
+
+    fn log_status_change(&self, status: ResourceStatus, free: u64) {
+        let free_mb = free / MB;
+        let message = match status {
+            ResourceStatus::Ok => format!("Free disk space is back to {} MB", free_mb),
+            ResourceStatus::DiskLow => format!(
+                "Warning: only {} MB of disk space left in {:?}",
+                free_mb, self.data_path
+            ),
+            ResourceStatus::DiskNearlyFull => format!(
+                "Warning: only {} MB of disk space left in {:?}, pausing pruning and new unchecked blocks",
+                free_mb, self.data_path
+            ),
+            ResourceStatus::DiskFull => format!(
+                "Critical: only {} MB of disk space left in {:?}, shutting down",
+                free_mb, self.data_path
+            ),
+        };
+        self.logger.always_log(&message);
+    }
+
+    fn check_memory(&self, state: &mut WatchdogState) {
+        if self.config.memory_warning_mb == 0 {
+            return;
+        }
+        let Some(resident) = self.probe.resident_memory() else {
+            return;
+        };
+        let exceeded = resident > self.config.memory_warning_mb * MB;
+        if exceeded && !state.memory_warned {
+            self.logger.always_log(&format!(
+                "Warning: the node uses {} MB of memory",
+                resident / MB
+            ));
+        }
+        state.memory_warned = exceeded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::utils::NullLogger;
+
+    struct StubProbe {
+        free: Arc<AtomicU64>,
+    }
+
+    impl ResourceProbe for StubProbe {
+        fn free_disk_space(&self, _path: &Path) -> Option<u64> {
+            Some(self.free.load(Ordering::SeqCst))
+        }
+
+        fn resident_memory(&self) -> Option<u64> {
+            Some(0)
+        }
+    }
+
+    fn create_watchdog(free: &Arc<AtomicU64>) -> ResourceWatchdog {
+        ResourceWatchdog::with_probe(
+            ResourceWatchdogConfig {
+                free_disk_warning_mb: 300,
+                free_disk_pause_mb: 200,
+                free_disk_shutdown_mb: 100,
+                memory_warning_mb: 0,
+            },
+            "/tmp",
+            Arc::new(NullLogger::new()),
+            Box::new(StubProbe { free: free.clone() }),
+        )
+    }
+
+    #[test]
+    fn enough_disk_space() {
+        let free = Arc::new(AtomicU64::new(1000 * MB));
+        let watchdog = create_watchdog(&free);
+        assert_eq!(watchdog.check(), ResourceStatus::Ok);
+        assert!(!watchdog.writes_paused());
+    }
+
+    #[test]
+    fn pauses_writes_when_disk_nearly_full() {
+        let free = Arc::new(AtomicU64::new(250 * MB));
+        let watchdog = create_watchdog(&free);
+        assert_eq!(watchdog.check(), ResourceStatus::DiskLow);
+        assert!(!watchdog.writes_paused());
+
+        free.store(150 * MB, Ordering::SeqCst);
+        assert_eq!(watchdog.check(), ResourceStatus::DiskNearlyFull);
+        assert!(watchdog.writes_paused());
+
+        free.store(50 * MB, Ordering::SeqCst);
+        assert_eq!(watchdog.check(), ResourceStatus::DiskFull);
+        assert!(watchdog.writes_paused());
+    }
+
+    #[test]
+    fn resumes_writes_when_space_is_freed() {
+        let free = Arc::new(AtomicU64::new(150 * MB));
+        let watchdog = create_watchdog(&free);
+        watchdog.check();
+
+        free.store(1000 * MB, Ordering::SeqCst);
+
+        assert_eq!(watchdog.check(), ResourceStatus::Ok);
+        assert!(!watchdog.writes_paused());
+    }
+
+    #[test]
+    fn only_warns_by_default() {
+        let free = Arc::new(AtomicU64::new(0));
+        let watchdog = ResourceWatchdog::with_probe(
+            ResourceWatchdogConfig::default(),
+            "/tmp",
+            Arc::new(NullLogger::new()),
+            Box::new(StubProbe { free: free.clone() }),
+        );
+
+        assert_eq!(watchdog.check(), ResourceStatus::DiskLow);
+        assert!(!watchdog.writes_paused());
+    }
+
+    #[test]
+    fn counts_dropped_writes_until_resumed() {
+        let free = Arc::new(AtomicU64::new(150 * MB));
+        let watchdog = create_watchdog(&free);
+        watchdog.check();
+
+        watchdog.write_dropped();
+        watchdog.write_dropped();
+        assert_eq!(watchdog.dropped_writes(), 2);
+
+        free.store(1000 * MB, Ordering::SeqCst);
+        watchdog.check();
+        assert_eq!(watchdog.dropped_writes(), 0);
+    }
+
+    #[test]
+    fn system_probe_reads_disk_space() {
+        let probe = SystemResourceProbe::default();
+        assert!(probe.free_disk_space(Path::new("/")).is_some());
+        assert!(probe
+            .free_disk_space(Path::new("/does/not/exist"))
+            .is_none());
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    backtrace::Backtrace,
+    panic::{self, PanicHookInfo},
+    sync::{Arc, Mutex, Once},
+    thread::{self, JoinHandle},
+};
+
+use rsnano_core::utils::Logger;
+
+/// A named long running thread of a node subsystem, which is joined on shutdown
+pub struct ThreadRunner {
+    name: String,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ThreadRunner {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            handle: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn start(&mut self, f: impl FnOnce() + Send + 'static) -> std::io::Result<()> {
+        debug_assert!(
+            self.handle.is_none(),
+            "thread {} already started",
+            self.name
+        );
+        self.handle = Some(thread::Builder::new().name(self.name.clone()).spawn(f)?);
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    /// Waits until the thread has finished. The subsystem has to signal the thread to stop before.
+    /// Returns the panic payload if the thread panicked.
+    pub fn join(&mut self) -> thread::Result<()> {
+        match self.handle.take() {
+            Some(handle) => handle.join(),
+            None => Ok(()),
+        }
+    }
+}
+
+static PANIC_LOGGER: Mutex<Option<Arc<dyn Logger>>> = Mutex::new(None);
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// Writes panics of all threads with a backtrace to `logger`, so that they end up in the node log
+/// and not only on stderr. The previously installed panic hook is still called.
+pub fn log_panics(logger: Arc<dyn Logger>) {
+    *PANIC_LOGGER.lock().unwrap() = Some(logger);
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // try_lock prevents a deadlock if the logger itself panics
+            if let Ok(guard) = PANIC_LOGGER.try_lock() {
+                if let Some(logger) = guard.as_ref() {
+                    logger.always_log(&panic_message(info));
+                }
+            }
+            previous_hook(info);
+        }));
+    });
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let thread = thread::current();
+    let thread_name = thread.name().unwrap_or("unnamed");
+    let payload = if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "unknown panic payload"
+    };
+    let location = info
+        .location()
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "unknown location".to_owned());
+    format!(
+        "Thread '{}' panicked at {}: {}\n{}",
+        thread_name,
+        location,
+        payload,
+        Backtrace::force_capture()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn thread_has_name() {
+        let (tx, rx) = mpsc::channel();
+        let mut runner = ThreadRunner::new("Test thread");
+        runner
+            .start(move || {
+                tx.send(thread::current().name().map(|n| n.to_owned()))
+                    .unwrap()
+            })
+            .unwrap();
+        runner.join().unwrap();
+        assert_eq!(rx.recv().unwrap(), Some("Test thread".to_owned()));
+        assert!(!runner.is_running());
+    }
+
+    #[test]
+    fn join_reports_panic() {
+        let mut runner = ThreadRunner::new("Panicking thread");
+        runner.start(|| panic!("test panic")).unwrap();
+        assert!(runner.join().is_err());
+    }
+
+    #[test]
+    fn join_without_start() {
+        let mut runner = ThreadRunner::new("Not started");
+        assert!(runner.join().is_ok());
+    }
+}
@@ -3,7 +3,7 @@ use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
     Account, Amount, BlockHash,
 };
-use std::{fmt::Debug, mem::size_of};
+use std::{fmt::Debug, mem::size_of, sync::Arc};
 
 use crate::voting::Vote;
 
@@ -11,29 +11,59 @@ use crate::voting::Vote;
 ///	It keeps track of votes in two internal structures: cache and queue
 ///
 ///	Cache: Stores votes associated with a particular block hash with a bounded maximum number of votes per hash.
-///			When cache size exceeds `max_size` oldest entries are evicted first.
+///			When cache size exceeds `max_size` entries are evicted according to the `VoteCacheEvictionPolicy`.
 ///
 ///	Queue: Keeps track of block hashes ordered by total cached vote tally.
 ///			When inserting a new vote into cache, the queue is atomically updated.
-///			When queue size exceeds `max_size` oldest entries are evicted first.
+///			When queue size exceeds `max_size` entries are evicted according to the `VoteCacheEvictionPolicy`.
 pub struct VoteCache {
     max_size: usize,
+    eviction_policy: VoteCacheEvictionPolicy,
+    /// Keep the votes of a block in the cache after they were transferred into its election
+    retain_promoted: bool,
     cache: MultiIndexCacheEntryMap,
     queue: MultiIndexQueueEntryMap,
     next_id: usize,
 }
 
+/// Decides which entry is removed when the vote cache is full
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum VoteCacheEvictionPolicy {
+    /// Evict the oldest entry
+    Oldest,
+    /// Evict the entry with the lowest tally. Of entries with the same tally the oldest is evicted
+    #[default]
+    LowestTally,
+}
+
+impl VoteCacheEvictionPolicy {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Oldest),
+            1 => Some(Self::LowestTally),
+            _ => None,
+        }
+    }
+}
+
 impl VoteCache {
     pub fn new(max_size: usize) -> Self {
+        Self::with_eviction_policy(max_size, VoteCacheEvictionPolicy::default())
+    }
+
+    pub fn with_eviction_policy(max_size: usize, eviction_policy: VoteCacheEvictionPolicy) -> Self {
         VoteCache {
             max_size,
+            eviction_policy,
+            retain_promoted: true,
             cache: MultiIndexCacheEntryMap::default(),
             queue: MultiIndexQueueEntryMap::default(),
             next_id: 0,
         }
     }
 
-    pub fn vote(&mut self, hash: &BlockHash, vote: &Vote, rep_weight: Amount) {
+    pub fn vote(&mut self, hash: &BlockHash, vote: &Arc<Vote>, rep_weight: Amount) {
         /*
          * If there is no cache entry for the block hash, create a new entry for both cache and queue.
          * Otherwise update existing cache entry and, if queue contains entry for the block hash, update the queue entry
@@ -41,7 +71,7 @@ impl VoteCache {
         let cache_entry_exists = self
             .cache
             .modify_by_hash(hash, |existing| {
-                existing.vote(&vote.voting_account, vote.timestamp(), rep_weight);
+                existing.vote(vote, rep_weight);
 
                 self.queue
                     .modify_by_hash(hash, |ent| ent.tally = existing.tally);
@@ -52,7 +82,7 @@ impl VoteCache {
             let id = self.next_id;
             self.next_id += 1;
             let mut cache_entry = CacheEntry::new(id, *hash);
-            cache_entry.vote(&vote.voting_account, vote.timestamp(), rep_weight);
+            cache_entry.vote(vote, rep_weight);
 
             let queue_entry = QueueEntry::new(id, *hash, cache_entry.tally);
             self.cache.insert(cache_entry);
@@ -94,6 +124,26 @@ impl VoteCache {
         result
     }
 
+    /// Returns the cached votes of a block for which an election was just started,
+    /// so that the election begins with the accumulated tally.
+    /// The entry is removed from the cache unless promoted votes are retained
+    pub fn promote(&mut self, hash: &BlockHash) -> Option<CacheEntry> {
+        if self.retain_promoted {
+            self.find(hash).cloned()
+        } else {
+            self.queue.remove_by_hash(hash);
+            self.cache.remove_by_hash(hash)
+        }
+    }
+
+    pub fn retain_promoted(&self) -> bool {
+        self.retain_promoted
+    }
+
+    pub fn set_retain_promoted(&mut self, retain: bool) {
+        self.retain_promoted = retain;
+    }
+
     /// Returns an entry with the highest tally and removes it from container.
     pub fn pop(&mut self) -> Option<CacheEntry> {
         self.pop_min_tally(Amount::zero())
@@ -140,6 +190,21 @@ impl VoteCache {
         }
     }
 
+    /// Returns up to `max_count` queued entries with a tally of at least `min_tally`, ordered by
+    /// descending tally. In contrast to `pop` the entries stay in the queue.
+    pub fn top(&self, min_tally: Amount, max_count: usize) -> Vec<TopEntry> {
+        self.queue
+            .iter_by_tally()
+            .rev()
+            .take_while(|entry| entry.tally >= min_tally)
+            .take(max_count)
+            .map(|entry| TopEntry {
+                hash: entry.hash,
+                tally: entry.tally,
+            })
+            .collect()
+    }
+
     /// Reinserts a block into the queue.
     /// It is possible that we dequeue a hash that doesn't have a received block yet (for eg. if publish message was lost).
     /// We need a way to reinsert that hash into the queue when we finally receive the block
@@ -176,18 +241,34 @@ impl VoteCache {
         )
     }
 
+    pub fn eviction_policy(&self) -> VoteCacheEvictionPolicy {
+        self.eviction_policy
+    }
+
     fn trim_overflow_locked(&mut self) {
-        // When cache overflown remove the oldest entry
         if self.cache.len() > self.max_size {
-            self.cache.pop_front();
+            match self.eviction_policy {
+                VoteCacheEvictionPolicy::Oldest => self.cache.pop_front(),
+                VoteCacheEvictionPolicy::LowestTally => self.cache.pop_lowest_tally(),
+            };
         }
 
         if self.queue.len() > self.max_size {
-            self.queue.pop_front();
+            match self.eviction_policy {
+                VoteCacheEvictionPolicy::Oldest => self.queue.pop_front(),
+                VoteCacheEvictionPolicy::LowestTally => self.queue.pop_lowest_tally(),
+            };
         }
     }
 }
 
+/// A queued block hash together with its vote tally
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopEntry {
+    pub hash: BlockHash,
+    pub tally: Amount,
+}
+
 /// Stores votes associated with a single block hash
 #[derive(MultiIndexMap, Default, Debug, Clone)]
 pub struct CacheEntry {
@@ -197,6 +278,9 @@ pub struct CacheEntry {
     pub hash: BlockHash,
     /// <rep, timestamp> pair
     pub voters: Vec<(Account, u64)>,
+    /// The newest vote of each voter, in the same order as `voters`
+    votes: Vec<Arc<Vote>>,
+    #[multi_index(ordered_non_unique)]
     pub tally: Amount,
 }
 
@@ -208,35 +292,45 @@ impl CacheEntry {
             id,
             hash,
             voters: Vec::new(),
+            votes: Vec::new(),
             tally: Amount::zero(),
         }
     }
 
     /// Adds a vote into a list, checks for duplicates and updates timestamp if new one is greater
     /// returns true if current tally changed, false otherwise
-    pub fn vote(&mut self, representative: &Account, timestamp: u64, rep_weight: Amount) -> bool {
-        if let Some(existing) = self
+    pub fn vote(&mut self, vote: &Arc<Vote>, rep_weight: Amount) -> bool {
+        let timestamp = vote.timestamp();
+        if let Some(index) = self
             .voters
-            .iter_mut()
-            .find(|(key, _)| key == representative)
+            .iter()
+            .position(|(key, _)| *key == vote.voting_account)
         {
             // We already have a vote from this rep
             // Update timestamp if newer but tally remains unchanged as we already counted this rep weight
             // It is not essential to keep tally up to date if rep voting weight changes, elections do tally calculations independently, so in the worst case scenario only our queue ordering will be a bit off
-            if timestamp > existing.1 {
-                existing.1 = timestamp
+            if timestamp > self.voters[index].1 {
+                self.voters[index].1 = timestamp;
+                self.votes[index] = Arc::clone(vote);
             }
             return false;
         }
         // Vote from an unseen representative, add to list and update tally
         if self.voters.len() < Self::MAX_VOTERS {
-            self.voters.push((*representative, timestamp));
+            self.voters.push((vote.voting_account, timestamp));
+            self.votes.push(Arc::clone(vote));
             self.tally += rep_weight;
             return true;
         }
         false
     }
 
+    /// The cached votes, which can be rebroadcasted or replayed into an election as they are,
+    /// because they still carry the signatures of their representatives
+    pub fn votes(&self) -> &[Arc<Vote>] {
+        &self.votes
+    }
+
     pub fn size(&self) -> usize {
         self.voters.len()
     }
@@ -263,6 +357,16 @@ impl MultiIndexCacheEntryMap {
         let id = self.iter_by_id().next()?.id;
         self.remove_by_id(&id)
     }
+
+    fn pop_lowest_tally(&mut self) -> Option<CacheEntry> {
+        let lowest = self.iter_by_tally().next()?.tally;
+        let id = self
+            .iter_by_tally()
+            .take_while(|e| e.tally == lowest)
+            .map(|e| e.id)
+            .min()?;
+        self.remove_by_id(&id)
+    }
 }
 
 impl MultiIndexQueueEntryMap {
@@ -270,6 +374,16 @@ impl MultiIndexQueueEntryMap {
         let id = self.iter_by_id().next()?.id;
         self.remove_by_id(&id)
     }
+
+    fn pop_lowest_tally(&mut self) -> Option<QueueEntry> {
+        let lowest = self.iter_by_tally().next()?.tally;
+        let id = self
+            .iter_by_tally()
+            .take_while(|e| e.tally == lowest)
+            .map(|e| e.id)
+            .min()?;
+        self.remove_by_id(&id)
+    }
 }
 
 #[cfg(test)]
@@ -278,14 +392,14 @@ mod tests {
     use crate::voting::{DURATION_MAX, TIMESTAMP_MAX};
     use rsnano_core::KeyPair;
 
-    fn create_vote(rep: &KeyPair, hash: &BlockHash, timestamp_offset: u64) -> Vote {
-        Vote::new(
+    fn create_vote(rep: &KeyPair, hash: &BlockHash, timestamp_offset: u64) -> Arc<Vote> {
+        Arc::new(Vote::new(
             rep.public_key(),
             &rep.private_key(),
             timestamp_offset * 1024 * 1024,
             0,
             vec![*hash],
-        )
+        ))
     }
 
     #[test]
@@ -297,6 +411,37 @@ mod tests {
         assert!(cache.find(&hash).is_none());
     }
 
+    #[test]
+    fn promote_retains_entry_by_default() {
+        let mut cache = VoteCache::new(10);
+        let rep = KeyPair::new();
+        let hash = BlockHash::from(1);
+        cache.vote(&hash, &create_vote(&rep, &hash, 1), Amount::raw(7));
+
+        let promoted = cache.promote(&hash).unwrap();
+
+        assert_eq!(promoted.tally, Amount::raw(7));
+        assert_eq!(promoted.voters.len(), 1);
+        assert!(cache.find(&hash).is_some());
+        assert_eq!(cache.queue_size(), 1);
+    }
+
+    #[test]
+    fn promote_and_clear() {
+        let mut cache = VoteCache::new(10);
+        cache.set_retain_promoted(false);
+        let rep = KeyPair::new();
+        let hash = BlockHash::from(1);
+        cache.vote(&hash, &create_vote(&rep, &hash, 1), Amount::raw(7));
+
+        let promoted = cache.promote(&hash).unwrap();
+
+        assert_eq!(promoted.hash, hash);
+        assert!(cache.cache_empty());
+        assert!(cache.queue_empty());
+        assert!(cache.promote(&hash).is_none());
+    }
+
     #[test]
     fn insert_one_hash() {
         let mut cache = VoteCache::new(10);
@@ -438,13 +583,13 @@ mod tests {
         let vote1 = create_vote(&rep, &hash, 1);
         cache.vote(&hash, &vote1, Amount::raw(9));
 
-        let vote2 = Vote::new(
+        let vote2 = Arc::new(Vote::new(
             rep.public_key(),
             &rep.private_key(),
             TIMESTAMP_MAX,
             DURATION_MAX,
             vec![hash],
-        );
+        ));
         cache.vote(&hash, &vote2, Amount::raw(9));
 
         let peek2 = cache.peek().unwrap();
@@ -553,6 +698,119 @@ mod tests {
         assert_eq!(cache.pop().unwrap().tally, Amount::raw(2));
     }
 
+    /*
+     * Ensure that a high tally entry survives cache pressure, even if it is the oldest one
+     */
+    #[test]
+    fn overfill_evicts_lowest_tally() {
+        let mut cache = VoteCache::new(2);
+        let hash1 = BlockHash::from(1);
+        let hash2 = BlockHash::from(2);
+        let hash3 = BlockHash::from(3);
+        let rep = KeyPair::new();
+
+        cache.vote(&hash1, &create_vote(&rep, &hash1, 1), Amount::raw(100));
+        cache.vote(&hash2, &create_vote(&rep, &hash2, 1), Amount::raw(5));
+        cache.vote(&hash3, &create_vote(&rep, &hash3, 1), Amount::raw(10));
+
+        assert_eq!(cache.cache_size(), 2);
+        assert_eq!(cache.queue_size(), 2);
+        assert!(cache.find(&hash1).is_some());
+        assert!(cache.find(&hash2).is_none());
+        assert!(cache.find(&hash3).is_some());
+    }
+
+    /*
+     * Of entries with the same tally the oldest one is evicted
+     */
+    #[test]
+    fn overfill_with_equal_tally_evicts_oldest() {
+        let mut cache = VoteCache::new(2);
+        let hash1 = BlockHash::from(1);
+        let hash2 = BlockHash::from(2);
+        let hash3 = BlockHash::from(3);
+        let rep = KeyPair::new();
+
+        cache.vote(&hash1, &create_vote(&rep, &hash1, 1), Amount::raw(5));
+        cache.vote(&hash2, &create_vote(&rep, &hash2, 1), Amount::raw(5));
+        cache.vote(&hash3, &create_vote(&rep, &hash3, 1), Amount::raw(5));
+
+        assert!(cache.find(&hash1).is_none());
+        assert!(cache.find(&hash2).is_some());
+        assert!(cache.find(&hash3).is_some());
+    }
+
+    #[test]
+    fn overfill_with_oldest_policy() {
+        let mut cache = VoteCache::with_eviction_policy(2, VoteCacheEvictionPolicy::Oldest);
+        let hash1 = BlockHash::from(1);
+        let hash2 = BlockHash::from(2);
+        let hash3 = BlockHash::from(3);
+        let rep = KeyPair::new();
+
+        cache.vote(&hash1, &create_vote(&rep, &hash1, 1), Amount::raw(100));
+        cache.vote(&hash2, &create_vote(&rep, &hash2, 1), Amount::raw(5));
+        cache.vote(&hash3, &create_vote(&rep, &hash3, 1), Amount::raw(10));
+
+        assert!(cache.find(&hash1).is_none());
+        assert!(cache.find(&hash2).is_some());
+        assert!(cache.find(&hash3).is_some());
+    }
+
+    #[test]
+    fn top_entries() {
+        let mut cache = VoteCache::new(10);
+        let hash1 = BlockHash::from(1);
+        let hash2 = BlockHash::from(2);
+        let hash3 = BlockHash::from(3);
+        let rep = KeyPair::new();
+
+        cache.vote(&hash1, &create_vote(&rep, &hash1, 1), Amount::raw(5));
+        cache.vote(&hash2, &create_vote(&rep, &hash2, 1), Amount::raw(9));
+        cache.vote(&hash3, &create_vote(&rep, &hash3, 1), Amount::raw(1));
+
+        assert_eq!(
+            cache.top(Amount::raw(5), usize::MAX),
+            vec![
+                TopEntry {
+                    hash: hash2,
+                    tally: Amount::raw(9)
+                },
+                TopEntry {
+                    hash: hash1,
+                    tally: Amount::raw(5)
+                }
+            ]
+        );
+        // top doesn't remove anything from the queue
+        assert_eq!(cache.queue_size(), 3);
+
+        assert_eq!(
+            cache.top(Amount::zero(), 1),
+            vec![TopEntry {
+                hash: hash2,
+                tally: Amount::raw(9)
+            }]
+        );
+    }
+
+    #[test]
+    fn cached_votes_can_be_rebroadcasted() {
+        let mut cache = VoteCache::new(10);
+        let hash = BlockHash::from(1);
+        let rep = KeyPair::new();
+        let older = create_vote(&rep, &hash, 1);
+        let newer = create_vote(&rep, &hash, 2);
+
+        cache.vote(&hash, &older, Amount::raw(9));
+        cache.vote(&hash, &newer, Amount::raw(9));
+
+        let votes = cache.find(&hash).unwrap().votes();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].timestamp(), newer.timestamp());
+        assert!(votes[0].validate().is_ok());
+    }
+
     /*
      * Check that when a single vote cache entry is overfilled, it ignores any new votes
      */
@@ -0,0 +1,200 @@
+#[cfg(test)]
+use mock_instant::Instant;
+use rsnano_core::{BlockHash, QualifiedRoot};
+#[cfg(not(test))]
+use std::time::Instant;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::stats::DetailType;
+
+/// Why the winner of an election gets broadcast again
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BroadcastReason {
+    /// The winner wasn't broadcast for this root yet
+    Initial,
+    /// The broadcast interval passed since the last broadcast
+    Repeat,
+    /// A different block than the last broadcast one is winning now
+    WinnerChanged,
+    /// The election got confirmed, so peers which lack the winner should get it now
+    Confirmed,
+}
+
+impl From<BroadcastReason> for DetailType {
+    fn from(reason: BroadcastReason) -> Self {
+        match reason {
+            BroadcastReason::Initial => DetailType::BroadcastBlockInitial,
+            BroadcastReason::Repeat => DetailType::BroadcastBlockRepeat,
+            BroadcastReason::WinnerChanged => DetailType::BroadcastBlockWinnerChanged,
+            BroadcastReason::Confirmed => DetailType::BroadcastBlockConfirmed,
+        }
+    }
+}
+
+/// Limits how often the winning block of an election root is rebroadcast to peers.
+/// A new winner or a confirmation is broadcast right away, otherwise the winner
+/// is repeated once per broadcast interval.
+pub struct BlockBroadcastThrottle {
+    initial_delay: Duration,
+    interval: Duration,
+    entries: Mutex<HashMap<QualifiedRoot, Entry>>,
+}
+
+struct Entry {
+    hash: BlockHash,
+    time: Instant,
+    confirmed: bool,
+}
+
+impl BlockBroadcastThrottle {
+    pub fn new(initial_delay: Duration, interval: Duration) -> Self {
+        Self {
+            initial_delay,
+            interval,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the reason for broadcasting the current `winner` of the election for `root`,
+    /// or `None` if the broadcast should be skipped for now.
+    /// `election_age` is the time since the election was started.
+    pub fn check(
+        &self,
+        root: &QualifiedRoot,
+        winner: &BlockHash,
+        election_age: Duration,
+        confirmed: bool,
+    ) -> Option<BroadcastReason> {
+        let entries = self.entries.lock().unwrap();
+        match entries.get(root) {
+            Some(entry) if entry.hash != *winner => Some(BroadcastReason::WinnerChanged),
+            Some(entry) if confirmed && !entry.confirmed => Some(BroadcastReason::Confirmed),
+            Some(entry) if entry.time.elapsed() >= self.interval => Some(BroadcastReason::Repeat),
+            Some(_) => None,
+            None if confirmed => Some(BroadcastReason::Confirmed),
+            None if election_age >= self.initial_delay => Some(BroadcastReason::Initial),
+            None => None,
+        }
+    }
+
+    /// Records that `winner` was broadcast for `root`
+    pub fn flag(&self, root: &QualifiedRoot, winner: &BlockHash, confirmed: bool) {
+        self.entries.lock().unwrap().insert(
+            root.clone(),
+            Entry {
+                hash: *winner,
+                time: Instant::now(),
+                confirmed,
+            },
+        );
+    }
+
+    /// Forgets the root, because its election was cleaned up
+    pub fn erase(&self, root: &QualifiedRoot) {
+        self.entries.lock().unwrap().remove(root);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_instant::MockClock;
+
+    const INITIAL_DELAY: Duration = Duration::from_secs(5);
+    const INTERVAL: Duration = Duration::from_secs(15);
+
+    fn create_throttle() -> BlockBroadcastThrottle {
+        BlockBroadcastThrottle::new(INITIAL_DELAY, INTERVAL)
+    }
+
+    #[test]
+    fn wait_for_initial_delay() {
+        let throttle = create_throttle();
+        let root = QualifiedRoot::new(1.into(), 2.into());
+        let winner = BlockHash::from(3);
+
+        assert_eq!(
+            throttle.check(&root, &winner, Duration::from_secs(1), false),
+            None
+        );
+        assert_eq!(
+            throttle.check(&root, &winner, INITIAL_DELAY, false),
+            Some(BroadcastReason::Initial)
+        );
+    }
+
+    #[test]
+    fn throttle_repeated_broadcasts() {
+        let throttle = create_throttle();
+        let root = QualifiedRoot::new(1.into(), 2.into());
+        let winner = BlockHash::from(3);
+        throttle.flag(&root, &winner, false);
+
+        assert_eq!(throttle.check(&root, &winner, INITIAL_DELAY, false), None);
+        MockClock::advance(INTERVAL);
+        assert_eq!(
+            throttle.check(&root, &winner, INITIAL_DELAY, false),
+            Some(BroadcastReason::Repeat)
+        );
+    }
+
+    #[test]
+    fn broadcast_new_winner_immediately() {
+        let throttle = create_throttle();
+        let root = QualifiedRoot::new(1.into(), 2.into());
+        throttle.flag(&root, &BlockHash::from(3), false);
+
+        assert_eq!(
+            throttle.check(&root, &BlockHash::from(4), Duration::ZERO, false),
+            Some(BroadcastReason::WinnerChanged)
+        );
+    }
+
+    #[test]
+    fn broadcast_confirmed_winner_once() {
+        let throttle = create_throttle();
+        let root = QualifiedRoot::new(1.into(), 2.into());
+        let winner = BlockHash::from(3);
+        throttle.flag(&root, &winner, false);
+
+        assert_eq!(
+            throttle.check(&root, &winner, Duration::ZERO, true),
+            Some(BroadcastReason::Confirmed)
+        );
+        throttle.flag(&root, &winner, true);
+        assert_eq!(throttle.check(&root, &winner, Duration::ZERO, true), None);
+    }
+
+    #[test]
+    fn throttle_per_root() {
+        let throttle = create_throttle();
+        let root1 = QualifiedRoot::new(1.into(), 2.into());
+        let root2 = QualifiedRoot::new(3.into(), 4.into());
+        let winner = BlockHash::from(5);
+        throttle.flag(&root1, &winner, false);
+
+        assert_eq!(throttle.check(&root1, &winner, INITIAL_DELAY, false), None);
+        assert_eq!(
+            throttle.check(&root2, &winner, INITIAL_DELAY, false),
+            Some(BroadcastReason::Initial)
+        );
+    }
+
+    #[test]
+    fn erase_root() {
+        let throttle = create_throttle();
+        let root = QualifiedRoot::new(1.into(), 2.into());
+        throttle.flag(&root, &BlockHash::from(3), false);
+        assert_eq!(throttle.len(), 1);
+        throttle.erase(&root);
+        assert!(throttle.is_empty());
+    }
+}
@@ -1,9 +1,13 @@
+mod block_broadcast_throttle;
 mod local_vote_history;
 mod vote;
+mod vote_packer;
 mod vote_spacing;
 
+pub use block_broadcast_throttle::{BlockBroadcastThrottle, BroadcastReason};
 pub use local_vote_history::*;
 pub use vote::*;
+pub use vote_packer::VotePacker;
 pub use vote_spacing::VoteSpacing;
 
 pub type VoteUniquer = crate::utils::Uniquer<Vote>;
@@ -6,6 +6,8 @@ use rsnano_core::{
 };
 use std::time::Duration;
 
+use crate::messages::ConfirmAck;
+
 #[derive(Clone, Debug)]
 pub struct Vote {
     pub timestamp: u64,
@@ -23,6 +25,10 @@ pub struct Vote {
 static HASH_PREFIX: &str = "vote ";
 
 impl Vote {
+    /// A vote can cover at most as many hashes as a confirm_ack can carry, because the hash
+    /// count is sent in the 4 bit count field of the message header
+    pub const MAX_HASHES: usize = ConfirmAck::HASHES_MAX;
+
     pub fn null() -> Self {
         Self {
             timestamp: 0,
@@ -39,6 +45,7 @@ impl Vote {
         duration: u8,
         hashes: Vec<BlockHash>,
     ) -> Self {
+        debug_assert!(hashes.len() <= Self::MAX_HASHES);
         let mut result = Self {
             voting_account: account,
             timestamp: packed_timestamp(timestamp, duration),
@@ -113,6 +120,9 @@ impl Vote {
     }
 
     pub fn serialize(&self, stream: &mut dyn Stream) -> Result<()> {
+        if self.hashes.len() > Self::MAX_HASHES {
+            bail!("vote contains too many hashes: {}", self.hashes.len());
+        }
         self.voting_account.serialize(stream)?;
         self.signature.serialize(stream)?;
         stream.write_bytes(&self.timestamp.to_le_bytes())?;
@@ -130,6 +140,9 @@ impl Vote {
         self.timestamp = u64::from_le_bytes(buffer);
         self.hashes = Vec::new();
         while stream.in_avail()? > 0 {
+            if self.hashes.len() == Self::MAX_HASHES {
+                bail!("vote contains more than {} hashes", Self::MAX_HASHES);
+            }
             self.hashes.push(BlockHash::deserialize(stream)?);
         }
         Ok(())
@@ -179,3 +192,82 @@ fn packed_timestamp(timestamp: u64, duration: u8) -> u64 {
     debug_assert!(timestamp != TIMESTAMP_MAX || duration == DURATION_MAX);
     (timestamp & TIMESTAMP_MASK) | (duration as u64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rsnano_core::{utils::MemoryStream, KeyPair};
+
+    #[test]
+    fn serialized_layout() {
+        let keys = KeyPair::new();
+        let vote = Vote::new(
+            keys.public_key(),
+            &keys.private_key(),
+            0x1230,
+            2,
+            vec![BlockHash::from(7)],
+        );
+
+        let mut stream = MemoryStream::new();
+        vote.serialize(&mut stream).unwrap();
+        let bytes = stream.to_vec();
+
+        assert_eq!(bytes.len(), Vote::serialized_size(1));
+        assert_eq!(&bytes[..32], keys.public_key().as_bytes());
+        assert_eq!(&bytes[32..96], vote.signature.as_bytes());
+        assert_eq!(&bytes[96..104], &[0x32, 0x12, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(&bytes[104..], BlockHash::from(7).as_bytes());
+    }
+
+    #[test]
+    fn serialize_fails_with_too_many_hashes() {
+        let mut vote = Vote::null();
+        vote.hashes = vec![BlockHash::from(1); Vote::MAX_HASHES + 1];
+        let mut stream = MemoryStream::new();
+        assert!(vote.serialize(&mut stream).is_err());
+    }
+
+    #[test]
+    fn deserialize_fails_with_too_many_hashes() {
+        let mut stream = MemoryStream::new();
+        stream.write_bytes(&[0; 104]).unwrap();
+        for _ in 0..=Vote::MAX_HASHES {
+            BlockHash::from(1).serialize(&mut stream).unwrap();
+        }
+        assert!(Vote::null().deserialize(&mut stream).is_err());
+    }
+
+    #[test]
+    fn deserialize_fails_with_truncated_hash() {
+        let mut stream = MemoryStream::new();
+        stream.write_bytes(&[0; 104 + 31]).unwrap();
+        assert!(Vote::null().deserialize(&mut stream).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn serialization_roundtrip(
+            timestamp in any::<u64>(),
+            duration in 0..=DURATION_MAX,
+            hashes in prop::collection::vec(any::<[u8; 32]>(), 0..=Vote::MAX_HASHES)
+        ) {
+            let keys = KeyPair::new();
+            let hashes: Vec<BlockHash> = hashes.into_iter().map(BlockHash::from_bytes).collect();
+            // TIMESTAMP_MAX is reserved for final votes, which always have the maximum duration
+            let timestamp = timestamp.min(TIMESTAMP_MAX - 1);
+            let vote = Vote::new(keys.public_key(), &keys.private_key(), timestamp, duration, hashes);
+
+            let mut stream = MemoryStream::new();
+            vote.serialize(&mut stream).unwrap();
+            prop_assert_eq!(stream.bytes_written(), Vote::serialized_size(vote.hashes.len()));
+
+            let mut deserialized = Vote::null();
+            deserialized.deserialize(&mut stream).unwrap();
+            prop_assert_eq!(&deserialized, &vote);
+            prop_assert_eq!(deserialized.duration_bits(), duration);
+            prop_assert!(deserialized.validate().is_ok());
+        }
+    }
+}
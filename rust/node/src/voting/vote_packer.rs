@@ -0,0 +1,153 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use rsnano_core::{Account, BlockHash};
+
+use super::Vote;
+
+/// Reduces the cached votes which are about to be sent to the same peer to fewer
+/// confirm_ack messages. A confirm_ack carries a single signed vote, so votes can't be
+/// merged without signing them again. Instead, votes whose hashes are all covered by
+/// other votes of the same representative and kind (final or not) are dropped.
+pub struct VotePacker {}
+
+impl VotePacker {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Returns the smallest selection of `votes` (picked greedily) which still contains every
+    /// hash of every representative. The order of the selected votes is kept.
+    pub fn pack(&self, votes: Vec<Arc<RwLock<Vote>>>) -> Vec<Arc<RwLock<Vote>>> {
+        let mut groups: HashMap<(Account, bool), Vec<usize>> = HashMap::new();
+        let mut hashes = Vec::with_capacity(votes.len());
+        let mut timestamps = Vec::with_capacity(votes.len());
+        for (i, vote) in votes.iter().enumerate() {
+            let vote_lk = vote.read().unwrap();
+            groups
+                .entry((vote_lk.voting_account, vote_lk.timestamp() == u64::MAX))
+                .or_default()
+                .push(i);
+            hashes.push(vote_lk.hashes.clone());
+            timestamps.push(vote_lk.timestamp());
+        }
+
+        let mut selected = vec![false; votes.len()];
+        for group in groups.values() {
+            let mut covered: HashSet<BlockHash> = HashSet::new();
+            loop {
+                // Pick the vote which adds the most hashes; prefer newer votes on ties
+                let best = group
+                    .iter()
+                    .filter(|&&i| !selected[i])
+                    .map(|&i| {
+                        let new_hashes = hashes[i].iter().filter(|h| !covered.contains(h)).count();
+                        (new_hashes, timestamps[i], i)
+                    })
+                    .filter(|(new_hashes, _, _)| *new_hashes > 0)
+                    .max();
+                let Some((_, _, i)) = best else {
+                    break;
+                };
+                selected[i] = true;
+                covered.extend(hashes[i].iter().copied());
+            }
+        }
+
+        votes
+            .into_iter()
+            .zip(selected)
+            .filter_map(|(vote, selected)| selected.then_some(vote))
+            .collect()
+    }
+}
+
+impl Default for VotePacker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voting::DURATION_MAX;
+    use rsnano_core::KeyPair;
+
+    fn vote(key: &KeyPair, timestamp: u64, hashes: &[u64]) -> Arc<RwLock<Vote>> {
+        Arc::new(RwLock::new(Vote::new(
+            key.public_key(),
+            &key.private_key(),
+            timestamp,
+            0x9,
+            hashes.iter().map(|h| BlockHash::from(*h)).collect(),
+        )))
+    }
+
+    #[test]
+    fn drops_votes_covered_by_other_votes() {
+        let key = KeyPair::new();
+        let votes = vec![
+            vote(&key, 1000, &[1]),
+            vote(&key, 1000, &[1, 2, 3]),
+            vote(&key, 1000, &[3]),
+        ];
+
+        let packed = VotePacker::new().pack(votes.clone());
+
+        assert_eq!(packed.len(), 1);
+        assert!(Arc::ptr_eq(&packed[0], &votes[1]));
+    }
+
+    #[test]
+    fn keeps_votes_with_uncovered_hashes() {
+        let key = KeyPair::new();
+        let votes = vec![vote(&key, 1000, &[1, 2]), vote(&key, 1000, &[2, 3])];
+
+        let packed = VotePacker::new().pack(votes.clone());
+
+        assert_eq!(packed.len(), 2);
+        assert!(Arc::ptr_eq(&packed[0], &votes[0]));
+        assert!(Arc::ptr_eq(&packed[1], &votes[1]));
+    }
+
+    #[test]
+    fn prefers_newer_vote_for_same_hashes() {
+        let key = KeyPair::new();
+        let votes = vec![vote(&key, 1000, &[1]), vote(&key, 2000, &[1])];
+
+        let packed = VotePacker::new().pack(votes.clone());
+
+        assert_eq!(packed.len(), 1);
+        assert!(Arc::ptr_eq(&packed[0], &votes[1]));
+    }
+
+    #[test]
+    fn does_not_mix_representatives() {
+        let key_a = KeyPair::new();
+        let key_b = KeyPair::new();
+        let votes = vec![vote(&key_a, 1000, &[1]), vote(&key_b, 1000, &[1])];
+
+        let packed = VotePacker::new().pack(votes);
+
+        assert_eq!(packed.len(), 2);
+    }
+
+    #[test]
+    fn does_not_mix_final_and_normal_votes() {
+        let key = KeyPair::new();
+        let final_vote = Arc::new(RwLock::new(Vote::new(
+            key.public_key(),
+            &key.private_key(),
+            u64::MAX,
+            DURATION_MAX,
+            vec![BlockHash::from(1)],
+        )));
+
+        let packed = VotePacker::new().pack(vec![vote(&key, 1000, &[1]), final_vote]);
+
+        assert_eq!(packed.len(), 2);
+    }
+}
@@ -8,6 +8,8 @@ use std::{
     time::Duration,
 };
 
+/// Remembers the roots and hashes of recently generated votes, so that the vote generator
+/// doesn't vote for a conflicting fork of the same root within the spacing delay
 pub struct VoteSpacing {
     delay: Duration,
     recent: EntryContainer,
@@ -21,12 +23,15 @@ impl VoteSpacing {
         }
     }
 
+    /// A vote for `hash` is allowed if no vote for a different block with the same root
+    /// was generated within the spacing delay
     pub fn votable(&self, root: &Root, hash: &BlockHash) -> bool {
         self.recent
             .by_root(root)
             .all(|item| *hash == item.hash || item.time.elapsed() >= self.delay)
     }
 
+    /// Records that a vote for `hash` was generated. Entries older than the delay are pruned
     pub fn flag(&mut self, root: &Root, hash: &BlockHash) {
         self.trim();
         let time = Instant::now();
@@ -227,6 +232,33 @@ mod tests {
         assert_eq!(spacing.len(), 1);
     }
 
+    #[test]
+    fn conflicting_hash_is_votable_after_delay() {
+        let length = Duration::from_millis(100);
+        let mut spacing = VoteSpacing::new(length);
+        let root = Root::from(1);
+        spacing.flag(&root, &BlockHash::from(2));
+        assert!(!spacing.votable(&root, &BlockHash::from(3)));
+
+        MockClock::advance(length);
+        assert!(spacing.votable(&root, &BlockHash::from(3)));
+    }
+
+    #[test]
+    fn flag_refreshes_time_of_root() {
+        let length = Duration::from_millis(100);
+        let mut spacing = VoteSpacing::new(length);
+        let root = Root::from(1);
+        spacing.flag(&root, &BlockHash::from(2));
+
+        MockClock::advance(Duration::from_millis(60));
+        spacing.flag(&root, &BlockHash::from(2));
+        MockClock::advance(Duration::from_millis(60));
+
+        assert!(!spacing.votable(&root, &BlockHash::from(3)));
+        assert_eq!(spacing.len(), 1);
+    }
+
     mod entry_container_tests {
         use super::*;
 
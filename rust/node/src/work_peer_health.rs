@@ -0,0 +1,304 @@
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rsnano_core::utils::PropertyTreeWriter;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CircuitState {
+    /// Requests are sent to the peer
+    Closed,
+    /// The peer failed too often and is skipped until the cooldown has passed
+    Open,
+    /// The cooldown has passed and a single request probes whether the peer recovered
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WorkPeerStats {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub successes: u64,
+    pub failures: u64,
+    /// Number of work responses. Successful health checks don't have a latency
+    pub latency_count: u64,
+    pub total_latency: Duration,
+    pub min_latency: Option<Duration>,
+    pub max_latency: Option<Duration>,
+}
+
+impl WorkPeerStats {
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.latency_count == 0 {
+            None
+        } else {
+            Some(self.total_latency / self.latency_count as u32)
+        }
+    }
+}
+
+impl Default for WorkPeerStats {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            successes: 0,
+            failures: 0,
+            latency_count: 0,
+            total_latency: Duration::ZERO,
+            min_latency: None,
+            max_latency: None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PeerEntry {
+    stats: WorkPeerStats,
+    /// When the circuit was opened or the last probe was let through
+    opened_at: Option<Instant>,
+    cooldown: Duration,
+}
+
+/// Tracks the health of the work peers. A peer which fails `failure_threshold` times in
+/// a row is skipped until a cooldown has passed. Then a single request is let through
+/// to probe the peer. If the probe fails, the cooldown is doubled.
+pub struct WorkPeerHealth {
+    failure_threshold: u32,
+    initial_cooldown: Duration,
+    max_cooldown: Duration,
+    peers: Mutex<BTreeMap<String, PeerEntry>>,
+}
+
+impl WorkPeerHealth {
+    pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+    pub const DEFAULT_INITIAL_COOLDOWN: Duration = Duration::from_secs(30);
+    pub const DEFAULT_MAX_COOLDOWN: Duration = Duration::from_secs(60 * 10);
+
+    pub fn new(failure_threshold: u32, initial_cooldown: Duration, max_cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            initial_cooldown,
+            max_cooldown,
+            peers: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Whether a request should be sent to the peer. Lets a single probe
+    /// through when the cooldown of an open circuit has passed
+    pub fn is_available(&self, peer: &str, now: Instant) -> bool {
+        let mut peers = self.peers.lock().unwrap();
+        let Some(entry) = peers.get_mut(peer) else {
+            return true;
+        };
+        match entry.stats.state {
+            CircuitState::Closed => true,
+            CircuitState::Open | CircuitState::HalfOpen => {
+                // A probe which never reported back doesn't block the peer forever
+                let cooldown_passed = entry
+                    .opened_at
+                    .map(|opened| now.saturating_duration_since(opened) >= entry.cooldown)
+                    .unwrap_or(true);
+                if cooldown_passed {
+                    entry.stats.state = CircuitState::HalfOpen;
+                    entry.opened_at = Some(now);
+                }
+                cooldown_passed
+            }
+        }
+    }
+
+    /// `latency` is the time a work request took. It is `None` for health checks
+    pub fn record_success(&self, peer: &str, latency: Option<Duration>) {
+        let mut peers = self.peers.lock().unwrap();
+        let entry = peers.entry(peer.to_owned()).or_default();
+        let stats = &mut entry.stats;
+        stats.state = CircuitState::Closed;
+        stats.consecutive_failures = 0;
+        stats.successes += 1;
+        entry.opened_at = None;
+        entry.cooldown = Duration::ZERO;
+
+        if let Some(latency) = latency {
+            stats.latency_count += 1;
+            stats.total_latency += latency;
+            stats.min_latency = Some(stats.min_latency.map_or(latency, |l| l.min(latency)));
+            stats.max_latency = Some(stats.max_latency.map_or(latency, |l| l.max(latency)));
+        }
+    }
+
+    pub fn record_failure(&self, peer: &str, now: Instant) {
+        let mut peers = self.peers.lock().unwrap();
+        let entry = peers.entry(peer.to_owned()).or_default();
+        entry.stats.consecutive_failures += 1;
+        entry.stats.failures += 1;
+        match entry.stats.state {
+            CircuitState::Closed => {
+                if entry.stats.consecutive_failures >= self.failure_threshold {
+                    entry.stats.state = CircuitState::Open;
+                    entry.opened_at = Some(now);
+                    entry.cooldown = self.initial_cooldown;
+                }
+            }
+            CircuitState::HalfOpen => {
+                entry.stats.state = CircuitState::Open;
+                entry.opened_at = Some(now);
+                entry.cooldown = (entry.cooldown * 2).min(self.max_cooldown);
+            }
+            CircuitState::Open => {}
+        }
+    }
+
+    pub fn stats(&self, peer: &str) -> Option<WorkPeerStats> {
+        let peers = self.peers.lock().unwrap();
+        peers.get(peer).map(|entry| entry.stats.clone())
+    }
+
+    pub fn serialize_json(&self, writer: &mut dyn PropertyTreeWriter) -> anyhow::Result<()> {
+        let peers = self.peers.lock().unwrap();
+        let mut list = writer.new_writer();
+        for (peer, entry) in peers.iter() {
+            let stats = &entry.stats;
+            let mut item = writer.new_writer();
+            item.put_string("peer", peer)?;
+            item.put_string("state", stats.state.as_str())?;
+            item.put_u64("consecutive_failures", stats.consecutive_failures as u64)?;
+            item.put_u64("successes", stats.successes)?;
+            item.put_u64("failures", stats.failures)?;
+            item.put_u64("latency_count", stats.latency_count)?;
+            let as_ms = |d: Option<Duration>| d.map(|d| d.as_millis() as u64).unwrap_or_default();
+            item.put_u64("average_latency_ms", as_ms(stats.average_latency()))?;
+            item.put_u64("min_latency_ms", as_ms(stats.min_latency))?;
+            item.put_u64("max_latency_ms", as_ms(stats.max_latency))?;
+            list.push_back("", item.as_ref());
+        }
+        writer.add_child("work_peers", list.as_ref());
+        Ok(())
+    }
+}
+
+impl Default for WorkPeerHealth {
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_FAILURE_THRESHOLD,
+            Self::DEFAULT_INITIAL_COOLDOWN,
+            Self::DEFAULT_MAX_COOLDOWN,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER: &str = "127.0.0.1:7000";
+
+    fn health() -> WorkPeerHealth {
+        WorkPeerHealth::new(2, Duration::from_secs(10), Duration::from_secs(25))
+    }
+
+    #[test]
+    fn unknown_peer_is_available() {
+        assert!(health().is_available(PEER, Instant::now()));
+        assert_eq!(health().stats(PEER), None);
+    }
+
+    #[test]
+    fn open_circuit_after_consecutive_failures() {
+        let health = health();
+        let now = Instant::now();
+        health.record_failure(PEER, now);
+        assert!(health.is_available(PEER, now));
+
+        health.record_failure(PEER, now);
+        assert!(!health.is_available(PEER, now));
+        assert_eq!(health.stats(PEER).unwrap().state, CircuitState::Open);
+    }
+
+    #[test]
+    fn success_resets_consecutive_failures() {
+        let health = health();
+        let now = Instant::now();
+        health.record_failure(PEER, now);
+        health.record_success(PEER, None);
+        health.record_failure(PEER, now);
+
+        assert!(health.is_available(PEER, now));
+        let stats = health.stats(PEER).unwrap();
+        assert_eq!(stats.failures, 2);
+        assert_eq!(stats.consecutive_failures, 1);
+    }
+
+    #[test]
+    fn let_single_probe_through_after_cooldown() {
+        let health = health();
+        let now = Instant::now();
+        health.record_failure(PEER, now);
+        health.record_failure(PEER, now);
+
+        let later = now + Duration::from_secs(10);
+        assert!(health.is_available(PEER, later));
+        assert_eq!(health.stats(PEER).unwrap().state, CircuitState::HalfOpen);
+        assert!(!health.is_available(PEER, later));
+    }
+
+    #[test]
+    fn successful_probe_closes_circuit() {
+        let health = health();
+        let now = Instant::now();
+        health.record_failure(PEER, now);
+        health.record_failure(PEER, now);
+        assert!(health.is_available(PEER, now + Duration::from_secs(10)));
+
+        health.record_success(PEER, Some(Duration::from_millis(100)));
+
+        assert!(health.is_available(PEER, now + Duration::from_secs(10)));
+        assert_eq!(health.stats(PEER).unwrap().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn failed_probe_doubles_cooldown_up_to_max() {
+        let health = health();
+        let now = Instant::now();
+        health.record_failure(PEER, now);
+        health.record_failure(PEER, now);
+
+        let probe1 = now + Duration::from_secs(10);
+        assert!(health.is_available(PEER, probe1));
+        health.record_failure(PEER, probe1);
+        assert!(!health.is_available(PEER, probe1 + Duration::from_secs(19)));
+
+        let probe2 = probe1 + Duration::from_secs(20);
+        assert!(health.is_available(PEER, probe2));
+        health.record_failure(PEER, probe2);
+        assert!(!health.is_available(PEER, probe2 + Duration::from_secs(24)));
+        assert!(health.is_available(PEER, probe2 + Duration::from_secs(25)));
+    }
+
+    #[test]
+    fn latency_stats() {
+        let health = health();
+        health.record_success(PEER, Some(Duration::from_millis(100)));
+        health.record_success(PEER, Some(Duration::from_millis(300)));
+        health.record_success(PEER, None);
+
+        let stats = health.stats(PEER).unwrap();
+        assert_eq!(stats.successes, 3);
+        assert_eq!(stats.latency_count, 2);
+        assert_eq!(stats.average_latency(), Some(Duration::from_millis(200)));
+        assert_eq!(stats.min_latency, Some(Duration::from_millis(100)));
+        assert_eq!(stats.max_latency, Some(Duration::from_millis(300)));
+    }
+}
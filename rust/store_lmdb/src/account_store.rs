@@ -107,6 +107,7 @@ impl<T: Environment + 'static> LmdbAccountStore<T> {
         &self,
         action: &(dyn Fn(&LmdbReadTransaction<T>, AccountIterator, AccountIterator) + Send + Sync),
     ) {
+        let _scan = self.env.sequential_scan();
         parallel_traversal(&|start, end, is_last| {
             let txn = self.env.tx_begin_read();
             let begin_it = self.begin_account(&txn, &start.into());
@@ -0,0 +1,116 @@
+use std::{
+    ops::{Deref, DerefMut},
+    time::{Duration, Instant},
+};
+
+use crate::{Environment, LmdbWriteTransaction, Transaction};
+
+/// Wraps a write transaction for long running bulk writes. After every `max_operations`
+/// operations or after `max_duration` the transaction is committed and renewed,
+/// so that the write lock isn't held for the whole run.
+/// The operations have to be counted by calling `operation_done`.
+///
+/// Only suitable for writes which leave the ledger consistent after every operation, like
+/// pruning. Database upgrades and table rebuilds must not use it, because they have to be
+/// committed atomically.
+pub struct BatchWriteGuard<'a, T: Environment + 'static> {
+    txn: &'a mut LmdbWriteTransaction<T>,
+    max_operations: usize,
+    max_duration: Duration,
+    operations: usize,
+    batch_start: Instant,
+    refreshes: usize,
+}
+
+impl<'a, T: Environment + 'static> BatchWriteGuard<'a, T> {
+    pub fn new(
+        txn: &'a mut LmdbWriteTransaction<T>,
+        max_operations: usize,
+        max_duration: Duration,
+    ) -> Self {
+        Self {
+            txn,
+            max_operations: max_operations.max(1),
+            max_duration,
+            operations: 0,
+            batch_start: Instant::now(),
+            refreshes: 0,
+        }
+    }
+
+    /// Counts a write operation. Returns `true` if the transaction was committed and renewed.
+    pub fn operation_done(&mut self) -> bool {
+        self.operations += 1;
+        if self.operations >= self.max_operations || self.batch_start.elapsed() >= self.max_duration
+        {
+            self.refresh();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Commits and renews the transaction immediately
+    pub fn refresh(&mut self) {
+        self.txn.refresh();
+        self.operations = 0;
+        self.batch_start = Instant::now();
+        self.refreshes += 1;
+    }
+
+    /// Number of operations in the current batch
+    pub fn pending_operations(&self) -> usize {
+        self.operations
+    }
+
+    /// How often the transaction was committed and renewed
+    pub fn refreshes(&self) -> usize {
+        self.refreshes
+    }
+}
+
+impl<'a, T: Environment + 'static> Deref for BatchWriteGuard<'a, T> {
+    type Target = LmdbWriteTransaction<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.txn
+    }
+}
+
+impl<'a, T: Environment + 'static> DerefMut for BatchWriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.txn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnv;
+
+    #[test]
+    fn refreshes_after_max_operations() {
+        let env = LmdbEnv::create_null();
+        let mut txn = env.tx_begin_write();
+        let mut guard = BatchWriteGuard::new(&mut txn, 3, Duration::MAX);
+
+        assert!(!guard.operation_done());
+        assert!(!guard.operation_done());
+        assert!(guard.operation_done());
+        assert!(!guard.operation_done());
+
+        assert_eq!(guard.refreshes(), 1);
+        assert_eq!(guard.pending_operations(), 1);
+    }
+
+    #[test]
+    fn refreshes_after_max_duration() {
+        let env = LmdbEnv::create_null();
+        let mut txn = env.tx_begin_write();
+        let mut guard = BatchWriteGuard::new(&mut txn, usize::MAX, Duration::ZERO);
+
+        assert!(guard.operation_done());
+        assert!(guard.operation_done());
+        assert_eq!(guard.refreshes(), 2);
+    }
+}
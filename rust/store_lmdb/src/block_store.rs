@@ -6,18 +6,32 @@ use crate::{
 use lmdb::{DatabaseFlags, WriteFlags};
 use num_traits::FromPrimitive;
 use rsnano_core::{
-    deserialize_block_enum,
-    utils::{OutputListenerMt, OutputTrackerMt, Serialize, StreamAdapter},
-    Account, Amount, Block, BlockEnum, BlockHash, BlockSideband, BlockType, BlockVisitor,
-    BlockWithSideband, ChangeBlock, Epoch, OpenBlock, ReceiveBlock, SendBlock, StateBlock,
+    deserialize_block_enum, serialized_block_size,
+    utils::{
+        Deserialize, MemoryStream, OutputListenerMt, OutputTrackerMt, Serialize, Stream,
+        StreamAdapter,
+    },
+    Account, Amount, Block, BlockEnum, BlockHash, BlockType, BlockVisitor, BlockWithSideband,
+    ChangeBlock, Epoch, NoValue, OpenBlock, ReceiveBlock, SendBlock, StateBlock,
 };
-use std::sync::Arc;
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+    /// Reused for serializing blocks, so that storing a block doesn't allocate
+    static SERIALIZATION_BUFFER: RefCell<MemoryStream> = RefCell::new(MemoryStream::new());
+}
 
 pub type BlockIterator = Box<dyn DbIterator<BlockHash, BlockWithSideband>>;
 
 pub struct LmdbBlockStore<T: Environment = EnvironmentWrapper> {
     env: Arc<LmdbEnv<T>>,
     database: T::Database,
+    /// Secondary index: (account, height) -> block hash
+    heights_database: Option<T::Database>,
+    /// Secondary index: (local timestamp, block hash) -> no value
+    timestamps_database: T::Database,
+    /// Secondary index: (destination account, send block hash) -> no value
+    destinations_database: T::Database,
     #[cfg(feature = "output_tracking")]
     put_listener: OutputListenerMt<BlockEnum>,
 }
@@ -53,13 +67,31 @@ impl ConfiguredBlockDatabaseBuilder {
 }
 
 impl<T: Environment + 'static> LmdbBlockStore<T> {
-    pub fn new(env: Arc<LmdbEnv<T>>) -> anyhow::Result<Self> {
+    /// The `block_heights` table is only created and opened if `height_index` is set
+    pub fn new(env: Arc<LmdbEnv<T>>, height_index: bool) -> anyhow::Result<Self> {
         let database = env
             .environment
             .create_db(Some("blocks"), DatabaseFlags::empty())?;
+        let heights_database = if height_index {
+            Some(
+                env.environment
+                    .create_db(Some("block_heights"), DatabaseFlags::empty())?,
+            )
+        } else {
+            None
+        };
+        let timestamps_database = env
+            .environment
+            .create_db(Some("block_timestamps"), DatabaseFlags::empty())?;
+        let destinations_database = env
+            .environment
+            .create_db(Some("block_destinations"), DatabaseFlags::empty())?;
         Ok(Self {
             env,
             database,
+            heights_database,
+            timestamps_database,
+            destinations_database,
             #[cfg(feature = "output_tracking")]
             put_listener: OutputListenerMt::new(),
         })
@@ -69,12 +101,56 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
         self.database
     }
 
+    pub fn heights_database(&self) -> Option<T::Database> {
+        self.heights_database
+    }
+
+    pub fn height_index_enabled(&self) -> bool {
+        self.heights_database.is_some()
+    }
+
+    pub fn timestamps_database(&self) -> T::Database {
+        self.timestamps_database
+    }
+
+    pub fn destinations_database(&self) -> T::Database {
+        self.destinations_database
+    }
+
     #[cfg(feature = "output_tracking")]
     pub fn track_puts(&self) -> Arc<OutputTrackerMt<BlockEnum>> {
         self.put_listener.track()
     }
 
     pub fn put(&self, txn: &mut LmdbWriteTransaction<T>, block: &BlockEnum) {
+        SERIALIZATION_BUFFER.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            buffer.clear();
+            block.serialize_with_sideband_into(&mut buffer);
+            self.put_serialized(txn, block, buffer.as_bytes());
+        });
+    }
+
+    /// Stores the blocks in the given order. All blocks are serialized into one
+    /// contiguous buffer before the first block is written
+    pub fn put_batch(&self, txn: &mut LmdbWriteTransaction<T>, blocks: &[BlockEnum]) {
+        SERIALIZATION_BUFFER.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            buffer.clear();
+            let mut offsets = Vec::with_capacity(blocks.len() + 1);
+            for block in blocks {
+                offsets.push(buffer.bytes_written());
+                block.serialize_with_sideband_into(&mut buffer);
+            }
+            offsets.push(buffer.bytes_written());
+
+            for (block, range) in blocks.iter().zip(offsets.windows(2)) {
+                self.put_serialized(txn, block, &buffer.as_bytes()[range[0]..range[1]]);
+            }
+        });
+    }
+
+    fn put_serialized(&self, txn: &mut LmdbWriteTransaction<T>, block: &BlockEnum, data: &[u8]) {
         #[cfg(feature = "output_tracking")]
         self.put_listener.emit(block.clone());
 
@@ -84,7 +160,7 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
                 || self.exists(txn, &block.sideband().unwrap().successor)
         );
 
-        self.raw_put(txn, &block.serialize_with_sideband(), &hash);
+        self.raw_put(txn, data, &hash);
         {
             let mut predecessor = BlockPredecessorMdbSet::new(txn, self);
             block.visit(&mut predecessor);
@@ -107,7 +183,7 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
         self.block_raw_get(txn, hash).and_then(|data| {
             debug_assert!(data.len() >= 32);
             let block_type = BlockType::from_u8(data[0]).unwrap();
-            let offset = block_successor_offset(data.len(), block_type);
+            let offset = block_successor_offset(block_type);
             let successor = BlockHash::from_bytes(data[offset..offset + 32].try_into().unwrap());
             if successor.is_zero() {
                 None
@@ -122,7 +198,7 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
         let block_type = BlockType::from_u8(value[0]).unwrap();
 
         let mut data = value.to_vec();
-        let offset = block_successor_offset(value.len(), block_type);
+        let offset = block_successor_offset(block_type);
         data[offset..offset + BlockHash::serialized_size()].fill(0);
         self.raw_put(txn, &data, hash)
     }
@@ -236,6 +312,7 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
         &self,
         action: &(dyn Fn(&LmdbReadTransaction<T>, BlockIterator, BlockIterator) + Send + Sync),
     ) {
+        let _scan = self.env.sequential_scan();
         parallel_traversal(&|start, end, is_last| {
             let transaction = self.env.tx_begin_read();
             let begin_it = self.begin_at_hash(&transaction, &start.into());
@@ -259,6 +336,192 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
         }
     }
 
+    /// Adds the block at `height` of the `account` chain to the height index.
+    /// Does nothing if the height index is disabled
+    pub fn put_height(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        account: &Account,
+        height: u64,
+        hash: &BlockHash,
+    ) {
+        let Some(database) = self.heights_database else {
+            return;
+        };
+        txn.put(
+            database,
+            &height_key(account, height),
+            hash.as_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    /// Removes the height index entry. Blocks written before the index was enabled have no
+    /// entry, so a missing one is not an error
+    pub fn del_height(&self, txn: &mut LmdbWriteTransaction<T>, account: &Account, height: u64) {
+        let Some(database) = self.heights_database else {
+            return;
+        };
+        match txn.delete(database, &height_key(account, height), None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete height index entry: {:?}", e),
+        }
+    }
+
+    /// Looks up the hash of the block at `height` of the `account` chain in the height index
+    pub fn hash_at_height(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        height: u64,
+    ) -> Option<BlockHash> {
+        match txn.get(self.heights_database?, &height_key(account, height)) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok(bytes) => Some(BlockHash::from_bytes(bytes.try_into().unwrap())),
+            Err(e) => panic!("Could not load block height. {:?}", e),
+        }
+    }
+
+    /// Calls `action` for the entries of the height index of `account` with `from <= height <= to`
+    /// in height order, until `action` returns false. This is a single cursor walk
+    pub fn for_each_in_height_range(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+        from: u64,
+        to: u64,
+        action: &mut dyn FnMut(u64, &BlockHash) -> bool,
+    ) {
+        let Some(database) = self.heights_database else {
+            return;
+        };
+        let start = height_key(account, from);
+        let mut it = LmdbIteratorImpl::<T>::new_iterator::<HeightKey, BlockHash>(
+            txn,
+            database,
+            Some(&start),
+            true,
+        );
+        while let Some((key, hash)) = it.current() {
+            if key.account != *account || key.height > to || !action(key.height, hash) {
+                break;
+            }
+            it.next();
+        }
+    }
+
+    /// Adds the block to the local timestamp index
+    pub fn put_timestamp(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        timestamp: u64,
+        hash: &BlockHash,
+    ) {
+        txn.put(
+            self.timestamps_database,
+            &timestamp_key(timestamp, hash),
+            &[0; 0],
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del_timestamp(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        timestamp: u64,
+        hash: &BlockHash,
+    ) {
+        txn.delete(
+            self.timestamps_database,
+            &timestamp_key(timestamp, hash),
+            None,
+        )
+        .unwrap();
+    }
+
+    /// Calls `action` for the entries of the timestamp index with `from <= timestamp < to`
+    /// in timestamp order, until `action` returns false
+    pub fn for_each_in_time_range(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        from: u64,
+        to: u64,
+        action: &mut dyn FnMut(u64, &BlockHash) -> bool,
+    ) {
+        let start = timestamp_key(from, &BlockHash::zero());
+        let mut it = LmdbIteratorImpl::<T>::new_iterator::<TimestampKey, NoValue>(
+            txn,
+            self.timestamps_database,
+            Some(&start),
+            true,
+        );
+        while let Some((key, _)) = it.current() {
+            if key.timestamp >= to || !action(key.timestamp, &key.hash) {
+                break;
+            }
+            it.next();
+        }
+    }
+
+    /// Adds the send block to the destination index
+    pub fn put_destination(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        destination: &Account,
+        hash: &BlockHash,
+    ) {
+        txn.put(
+            self.destinations_database,
+            &destination_key(destination, hash),
+            &[0; 0],
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del_destination(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        destination: &Account,
+        hash: &BlockHash,
+    ) {
+        txn.delete(
+            self.destinations_database,
+            &destination_key(destination, hash),
+            None,
+        )
+        .unwrap();
+    }
+
+    /// Calls `action` for the send blocks to `destination` in the destination index, beginning
+    /// with the first hash after `start`, until `action` returns false. The blocks are ordered by hash
+    pub fn for_each_destination(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        destination: &Account,
+        start: &BlockHash,
+        action: &mut dyn FnMut(&BlockHash) -> bool,
+    ) {
+        let start_key = destination_key(destination, start);
+        let mut it = LmdbIteratorImpl::<T>::new_iterator::<DestinationKey, NoValue>(
+            txn,
+            self.destinations_database,
+            Some(&start_key),
+            true,
+        );
+        while let Some((key, _)) = it.current() {
+            if key.destination != *destination {
+                break;
+            }
+            if key.hash != *start && !action(&key.hash) {
+                break;
+            }
+            it.next();
+        }
+    }
+
     pub fn raw_put(&self, txn: &mut LmdbWriteTransaction<T>, data: &[u8], hash: &BlockHash) {
         txn.put(self.database, hash.as_bytes(), data, WriteFlags::empty())
             .unwrap();
@@ -277,6 +540,107 @@ impl<T: Environment + 'static> LmdbBlockStore<T> {
     }
 }
 
+/// The height is stored in big endian, so that the blocks of an account are ordered by height
+fn height_key(account: &Account, height: u64) -> [u8; 40] {
+    let mut key = [0; 40];
+    key[..32].copy_from_slice(account.as_bytes());
+    key[32..].copy_from_slice(&height.to_be_bytes());
+    key
+}
+
+/// The timestamp is stored in big endian, so that the blocks are ordered by their local timestamp
+fn timestamp_key(timestamp: u64, hash: &BlockHash) -> [u8; 40] {
+    let mut key = [0; 40];
+    key[..8].copy_from_slice(&timestamp.to_be_bytes());
+    key[8..].copy_from_slice(hash.as_bytes());
+    key
+}
+
+fn destination_key(destination: &Account, hash: &BlockHash) -> [u8; 64] {
+    let mut key = [0; 64];
+    key[..32].copy_from_slice(destination.as_bytes());
+    key[32..].copy_from_slice(hash.as_bytes());
+    key
+}
+
+struct HeightKey {
+    account: Account,
+    height: u64,
+}
+
+impl Serialize for HeightKey {
+    fn serialized_size() -> usize {
+        Account::serialized_size() + u64::serialized_size()
+    }
+
+    fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()> {
+        self.account.serialize(stream)?;
+        self.height.serialize(stream)
+    }
+}
+
+impl Deserialize for HeightKey {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let account = Account::deserialize(stream)?;
+        let height = u64::deserialize(stream)?;
+        Ok(Self { account, height })
+    }
+}
+
+struct TimestampKey {
+    timestamp: u64,
+    hash: BlockHash,
+}
+
+impl Serialize for TimestampKey {
+    fn serialized_size() -> usize {
+        u64::serialized_size() + BlockHash::serialized_size()
+    }
+
+    fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()> {
+        self.timestamp.serialize(stream)?;
+        self.hash.serialize(stream)
+    }
+}
+
+impl Deserialize for TimestampKey {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let timestamp = u64::deserialize(stream)?;
+        let hash = BlockHash::deserialize(stream)?;
+        Ok(Self { timestamp, hash })
+    }
+}
+
+struct DestinationKey {
+    destination: Account,
+    hash: BlockHash,
+}
+
+impl Serialize for DestinationKey {
+    fn serialized_size() -> usize {
+        Account::serialized_size() + BlockHash::serialized_size()
+    }
+
+    fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()> {
+        self.destination.serialize(stream)?;
+        self.hash.serialize(stream)
+    }
+}
+
+impl Deserialize for DestinationKey {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let destination = Account::deserialize(stream)?;
+        let hash = BlockHash::deserialize(stream)?;
+        Ok(Self { destination, hash })
+    }
+}
+
 /// Fill in our predecessors
 struct BlockPredecessorMdbSet<'a, T: Environment + 'static> {
     transaction: &'a mut LmdbWriteTransaction<T>,
@@ -303,7 +667,7 @@ impl<'a, T: Environment + 'static> BlockPredecessorMdbSet<'a, T> {
         let mut data = value.to_vec();
         let block_type = BlockType::from_u8(data[0]).unwrap();
 
-        let offset = block_successor_offset(data.len(), block_type);
+        let offset = block_successor_offset(block_type);
         data[offset..offset + hash.as_bytes().len()].copy_from_slice(hash.as_bytes());
 
         self.block_store
@@ -335,15 +699,17 @@ impl<'a, T: Environment> BlockVisitor for BlockPredecessorMdbSet<'a, T> {
     }
 }
 
-fn block_successor_offset(entry_size: usize, block_type: BlockType) -> usize {
-    entry_size - BlockSideband::serialized_size(block_type)
+/// The successor is the first field of the sideband. The offset is counted from the start
+/// of the entry, because the sideband grows if the block details have extended flags.
+fn block_successor_offset(block_type: BlockType) -> usize {
+    1 + serialized_block_size(block_type)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::lmdb_env::DatabaseStub;
     use crate::{EnvironmentStub, PutEvent};
-    use rsnano_core::BlockBuilder;
+    use rsnano_core::{BlockBuilder, BlockSideband};
 
     use super::*;
 
@@ -358,10 +724,18 @@ mod tests {
         }
 
         fn with_env(env: LmdbEnv<EnvironmentStub>) -> Self {
+            Self::create(env, false)
+        }
+
+        fn with_height_index(env: LmdbEnv<EnvironmentStub>) -> Self {
+            Self::create(env, true)
+        }
+
+        fn create(env: LmdbEnv<EnvironmentStub>, height_index: bool) -> Self {
             let env = Arc::new(env);
             Self {
                 env: env.clone(),
-                store: LmdbBlockStore::new(env).unwrap(),
+                store: LmdbBlockStore::new(env, height_index).unwrap(),
             }
         }
     }
@@ -413,6 +787,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn add_block_batch() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let blocks = vec![
+            BlockBuilder::legacy_open()
+                .source(BlockHash::from(1))
+                .with_sideband()
+                .build(),
+            BlockBuilder::legacy_open()
+                .source(BlockHash::from(2))
+                .with_sideband()
+                .build(),
+        ];
+
+        fixture.store.put_batch(&mut txn, &blocks);
+
+        let expected: Vec<_> = blocks
+            .iter()
+            .map(|block| PutEvent {
+                database: Default::default(),
+                key: block.hash().as_bytes().to_vec(),
+                value: block.serialize_with_sideband(),
+                flags: lmdb::WriteFlags::empty(),
+            })
+            .collect();
+        assert_eq!(put_tracker.output(), expected);
+    }
+
     #[test]
     fn clear_successor() {
         let mut block = BlockBuilder::legacy_open().build();
@@ -450,6 +854,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_successor_of_block_with_extended_details() {
+        let mut block = BlockBuilder::state().build();
+        let mut sideband = BlockSideband {
+            successor: BlockHash::from(123),
+            ..BlockSideband::create_test_instance()
+        };
+        sideband.details.is_self_send = true;
+        block.set_sideband(sideband);
+
+        let env = LmdbEnv::create_null_with()
+            .database("blocks", DatabaseStub(100))
+            .entry(block.hash().as_bytes(), &block.serialize_with_sideband())
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.successor(&txn, &block.hash()),
+            Some(BlockHash::from(123))
+        );
+        let loaded = fixture.store.get(&txn, &block.hash()).unwrap();
+        assert!(loaded.sideband().unwrap().details.is_self_send);
+    }
+
+    #[test]
+    fn add_block_height() {
+        let env = LmdbEnv::create_null_with()
+            .database("block_heights", DatabaseStub(101))
+            .build()
+            .build();
+        let fixture = Fixture::with_height_index(env);
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let account = Account::from(1);
+        let hash = BlockHash::from(2);
+
+        fixture.store.put_height(&mut txn, &account, 3, &hash);
+
+        let mut expected_key = account.as_bytes().to_vec();
+        expected_key.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 3]);
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: DatabaseStub(101),
+                key: expected_key,
+                value: hash.as_bytes().to_vec(),
+                flags: WriteFlags::empty(),
+            }]
+        );
+    }
+
+    #[test]
+    fn load_hash_at_height() {
+        let account = Account::from(1);
+        let hash = BlockHash::from(2);
+        let env = LmdbEnv::create_null_with()
+            .database("block_heights", DatabaseStub(101))
+            .entry(&height_key(&account, 3), hash.as_bytes())
+            .build()
+            .build();
+        let fixture = Fixture::with_height_index(env);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(fixture.store.hash_at_height(&txn, &account, 3), Some(hash));
+        assert_eq!(fixture.store.hash_at_height(&txn, &account, 4), None);
+    }
+
+    #[test]
+    fn height_index_disabled() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let account = Account::from(1);
+
+        fixture
+            .store
+            .put_height(&mut txn, &account, 3, &BlockHash::from(2));
+
+        assert!(!fixture.store.height_index_enabled());
+        assert!(put_tracker.output().is_empty());
+        assert_eq!(fixture.store.hash_at_height(&txn, &account, 3), None);
+    }
+
+    #[test]
+    fn add_block_timestamp() {
+        let env = LmdbEnv::create_null_with()
+            .database("block_timestamps", DatabaseStub(102))
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let hash = BlockHash::from(2);
+
+        fixture.store.put_timestamp(&mut txn, 3, &hash);
+
+        let mut expected_key = vec![0, 0, 0, 0, 0, 0, 0, 3];
+        expected_key.extend_from_slice(hash.as_bytes());
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: DatabaseStub(102),
+                key: expected_key,
+                value: Vec::new(),
+                flags: WriteFlags::empty(),
+            }]
+        );
+    }
+
+    #[test]
+    fn add_block_destination() {
+        let env = LmdbEnv::create_null_with()
+            .database("block_destinations", DatabaseStub(103))
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let destination = Account::from(1);
+        let hash = BlockHash::from(2);
+
+        fixture.store.put_destination(&mut txn, &destination, &hash);
+
+        let mut expected_key = destination.as_bytes().to_vec();
+        expected_key.extend_from_slice(hash.as_bytes());
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: DatabaseStub(103),
+                key: expected_key,
+                value: Vec::new(),
+                flags: WriteFlags::empty(),
+            }]
+        );
+    }
+
     #[test]
     fn random() -> anyhow::Result<()> {
         let block = BlockBuilder::legacy_open().with_sideband().build();
@@ -494,7 +1036,7 @@ mod tests {
             .configured_database(configured_responses)
             .build();
         let txn = env.tx_begin_read();
-        let block_store = LmdbBlockStore::new(Arc::new(env)).unwrap();
+        let block_store = LmdbBlockStore::new(Arc::new(env), false).unwrap();
         assert_eq!(block_store.get(&txn, &block.hash()), Some(block));
     }
 }
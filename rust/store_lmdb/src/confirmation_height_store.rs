@@ -1,13 +1,14 @@
 use crate::{
     iterator::DbIterator, parallel_traversal, Environment, EnvironmentWrapper, LmdbEnv,
-    LmdbIteratorImpl, LmdbReadTransaction, LmdbWriteTransaction, Transaction,
+    LmdbIteratorImpl, LmdbReadTransaction, LmdbWriteTransaction, RoCursor, Transaction,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::MDB_SET_RANGE;
 use rsnano_core::{
     utils::{Deserialize, StreamAdapter},
     Account, ConfirmationHeightInfo,
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 pub type ConfirmationHeightIterator = Box<dyn DbIterator<Account, ConfirmationHeightInfo>>;
 
@@ -102,12 +103,66 @@ impl<T: Environment + 'static> LmdbConfirmationHeightStore<T> {
         LmdbIteratorImpl::<T>::null_iterator()
     }
 
+    /// Iterates over all confirmation heights, ordered by account
+    pub fn iter(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> ConfirmationHeightEntries {
+        ConfirmationHeightEntries(self.begin(txn))
+    }
+
+    /// Loads the confirmation heights of many accounts with a single cursor, which walks over
+    /// the accounts in key order and only seeks when the next account lies behind the current
+    /// entry. Accounts without a confirmation height are missing in the result.
+    pub fn get_many(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        accounts: &[Account],
+    ) -> HashMap<Account, ConfirmationHeightInfo> {
+        let mut result = HashMap::new();
+        if accounts.is_empty() {
+            return result;
+        }
+
+        let mut sorted = accounts.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let cursor = txn.open_ro_cursor(self.database).unwrap();
+        let mut current: Option<(Account, &[u8])> = None;
+        for account in sorted {
+            let needs_seek = match &current {
+                Some((key, _)) => *key < account,
+                None => true,
+            };
+            if needs_seek {
+                current = match cursor.get(Some(account.as_bytes()), None, MDB_SET_RANGE) {
+                    Ok((Some(key), value)) => Some((Account::from_slice(key).unwrap(), value)),
+                    Ok((None, _)) => unreachable!(),
+                    Err(lmdb::Error::NotFound) => break,
+                    Err(e) => panic!("Could not load confirmation height info: {:?}", e),
+                };
+            }
+
+            if let Some((key, value)) = &current {
+                if *key == account {
+                    let mut stream = StreamAdapter::new(value);
+                    if let Ok(info) = ConfirmationHeightInfo::deserialize(&mut stream) {
+                        result.insert(account, info);
+                    }
+                }
+            }
+        }
+        result
+    }
+
     pub fn for_each_par(
         &self,
         action: &(dyn Fn(&LmdbReadTransaction<T>, ConfirmationHeightIterator, ConfirmationHeightIterator)
               + Send
               + Sync),
     ) {
+        let _scan = self.env.sequential_scan();
         parallel_traversal(&|start, end, is_last| {
             let transaction = self.env.tx_begin_read();
             let begin_it = self.begin_at_account(&transaction, &start.into());
@@ -121,6 +176,21 @@ impl<T: Environment + 'static> LmdbConfirmationHeightStore<T> {
     }
 }
 
+pub struct ConfirmationHeightEntries(ConfirmationHeightIterator);
+
+impl Iterator for ConfirmationHeightEntries {
+    type Item = (Account, ConfirmationHeightInfo);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self
+            .0
+            .current()
+            .map(|(account, info)| (*account, info.clone()));
+        self.0.next();
+        entry
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{lmdb_env::DatabaseStub, EnvironmentStub, PutEvent};
@@ -218,6 +288,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn iterate_ordered_by_account() {
+        let info = ConfirmationHeightInfo::new(1, BlockHash::from(2));
+        let env = LmdbEnv::create_null_with()
+            .database("confirmation_height", DatabaseStub(100))
+            .entry(Account::from(3).as_bytes(), &info.to_bytes())
+            .entry(Account::from(1).as_bytes(), &info.to_bytes())
+            .build()
+            .build();
+
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+        let accounts: Vec<_> = fixture
+            .store
+            .iter(&txn)
+            .map(|(account, _)| account)
+            .collect();
+
+        assert_eq!(accounts, vec![Account::from(1), Account::from(3)]);
+    }
+
+    #[test]
+    fn get_many() {
+        let info1 = ConfirmationHeightInfo::new(1, BlockHash::from(100));
+        let info3 = ConfirmationHeightInfo::new(3, BlockHash::from(300));
+        let info5 = ConfirmationHeightInfo::new(5, BlockHash::from(500));
+        let env = LmdbEnv::create_null_with()
+            .database("confirmation_height", DatabaseStub(100))
+            .entry(Account::from(1).as_bytes(), &info1.to_bytes())
+            .entry(Account::from(3).as_bytes(), &info3.to_bytes())
+            .entry(Account::from(5).as_bytes(), &info5.to_bytes())
+            .build()
+            .build();
+
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+        let result = fixture.store.get_many(
+            &txn,
+            &[
+                Account::from(5),
+                Account::from(2),
+                Account::from(1),
+                Account::from(5),
+                Account::from(6),
+            ],
+        );
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get(&Account::from(1)), Some(&info1));
+        assert_eq!(result.get(&Account::from(5)), Some(&info5));
+    }
+
+    #[test]
+    fn get_many_without_accounts() {
+        let fixture = Fixture::new();
+        let txn = fixture.env.tx_begin_read();
+        assert!(fixture.store.get_many(&txn, &[]).is_empty());
+    }
+
     #[test]
     fn clear() {
         let fixture = Fixture::new();
@@ -134,6 +134,7 @@ impl<T: Environment + 'static> LmdbFinalVoteStore<T> {
               + Send
               + Sync),
     ) {
+        let _scan = self.env.sequential_scan();
         parallel_traversal_u512(&|start, end, is_last| {
             let transaction = self.env.tx_begin_read();
             let begin_it = self.begin_at_root(&transaction, &start.into());
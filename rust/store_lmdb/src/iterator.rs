@@ -1,4 +1,4 @@
-use std::{any::Any, ffi::c_uint};
+use std::{any::Any, ffi::c_uint, marker::PhantomData};
 
 use crate::{lmdb_env::RoCursor, Environment, Transaction};
 
@@ -198,3 +198,60 @@ impl<E: Environment + 'static> PartialEq for LmdbIteratorImpl<E> {
         self.current.map(|(k, _)| k) == other.current.map(|(k, _)| k)
     }
 }
+
+/// Iterates over the entries of a table in ascending order and yields owned,
+/// deserialized key/value pairs. Unlike `DbIterator` it implements `Iterator`,
+/// so the usual adapters like `take_while` or `map` can be used.
+/// The iterator borrows the transaction, so it cannot outlive it. It is `Send`,
+/// because the environment is opened with `NO_TLS` and therefore cursors are not
+/// bound to the thread which opened the transaction.
+pub struct DbRangeIter<'txn, K, V, E: Environment + 'static> {
+    cursor: LmdbIteratorImpl<E>,
+    /// Serialized key at which the iteration stops (exclusive)
+    end: Option<Vec<u8>>,
+    _txn: PhantomData<&'txn ()>,
+    _entries: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'txn, K, V, E: Environment + 'static> DbRangeIter<'txn, K, V, E> {
+    /// Starts at the first key which is equal or greater than `start` and stops before `end`.
+    /// A `None` start iterates from the beginning, a `None` end until the end of the table.
+    pub fn new(
+        txn: &'txn dyn Transaction<Database = E::Database, RoCursor = E::RoCursor>,
+        dbi: E::Database,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+    ) -> Self {
+        Self {
+            cursor: LmdbIteratorImpl::new(txn, dbi, start, true),
+            end: end.map(|e| e.to_vec()),
+            _txn: PhantomData,
+            _entries: PhantomData,
+        }
+    }
+}
+
+impl<'txn, K, V, E> Iterator for DbRangeIter<'txn, K, V, E>
+where
+    K: Serialize + Deserialize<Target = K>,
+    V: Deserialize<Target = V>,
+    E: Environment + 'static,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, v) = self.cursor.current()?;
+        if let Some(end) = &self.end {
+            if k >= end.as_slice() {
+                return None;
+            }
+        }
+        if k.len() < K::serialized_size() {
+            return None;
+        }
+        let key = K::deserialize(&mut StreamAdapter::new(k)).unwrap();
+        let value = V::deserialize(&mut StreamAdapter::new(v)).unwrap();
+        DbIteratorImpl::next(&mut self.cursor);
+        Some((key, value))
+    }
+}
@@ -4,16 +4,23 @@ extern crate num_derive;
 #[macro_use]
 extern crate anyhow;
 
+mod batch_write_guard;
+pub use batch_write_guard::BatchWriteGuard;
+
 mod iterator;
-pub use iterator::{BinaryDbIterator, DbIterator, DbIteratorImpl, LmdbIteratorImpl};
+pub use iterator::{BinaryDbIterator, DbIterator, DbIteratorImpl, DbRangeIter, LmdbIteratorImpl};
 
 mod lmdb_config;
 pub use lmdb_config::{LmdbConfig, SyncStrategy};
 
+mod read_ahead;
+pub use read_ahead::{drop_file_cache, AccessPattern};
+
 mod lmdb_env;
 pub use lmdb_env::{
     ConfiguredDatabase, DatabaseStub, EnvOptions, Environment, EnvironmentOptions, EnvironmentStub,
-    EnvironmentWrapper, LmdbEnv, RoCursorWrapper, TestDbFile, TestLmdbEnv,
+    EnvironmentWrapper, LmdbEnv, MapUsage, RoCursorWrapper, SequentialScan, TableStats, TestDbFile,
+    TestLmdbEnv,
 };
 use lmdb_env::{InactiveTransaction, RoCursor, RoTransaction, RwTransaction};
 
@@ -24,7 +31,7 @@ mod block_store;
 pub use block_store::{ConfiguredBlockDatabaseBuilder, LmdbBlockStore};
 
 mod confirmation_height_store;
-pub use confirmation_height_store::LmdbConfirmationHeightStore;
+pub use confirmation_height_store::{ConfirmationHeightEntries, LmdbConfirmationHeightStore};
 
 mod final_vote_store;
 pub use final_vote_store::LmdbFinalVoteStore;
@@ -39,7 +46,9 @@ mod pending_store;
 pub use pending_store::{ConfiguredPendingDatabaseBuilder, LmdbPendingStore};
 
 mod peer_store;
-pub use peer_store::LmdbPeerStore;
+pub use peer_store::{LmdbPeerStore, PeerHistory};
+mod rollback_journal_store;
+pub use rollback_journal_store::{LmdbRollbackJournalStore, PlannedRollback};
 
 mod pruned_store;
 pub use pruned_store::{ConfiguredPrunedDatabaseBuilder, LmdbPrunedStore};
@@ -53,11 +62,14 @@ pub use wallet_store::{Fans, LmdbWalletStore, WalletValue};
 mod fan;
 pub use fan::Fan;
 
+mod wallet_backup;
+pub use wallet_backup::{decrypt_wallet_export, encrypt_wallet_export, rotate_backup_files};
+
 mod wallets;
 pub use wallets::LmdbWallets;
 
 mod store;
-pub use store::{create_backup_file, LmdbStore};
+pub use store::{create_backup_file, LmdbStore, VacuumOptions};
 
 use std::{
     any::Any,
@@ -89,6 +101,7 @@ pub trait Transaction {
     }
     fn open_ro_cursor(&self, database: Self::Database) -> lmdb::Result<Self::RoCursor>;
     fn count(&self, database: Self::Database) -> u64;
+    fn table_stats(&self, database: Self::Database) -> TableStats;
 }
 
 pub trait TransactionTracker: Send + Sync {
@@ -216,6 +229,10 @@ impl<T: Environment + 'static> Transaction for LmdbReadTransaction<T> {
     fn count(&self, database: Self::Database) -> u64 {
         self.txn().count(database)
     }
+
+    fn table_stats(&self, database: Self::Database) -> TableStats {
+        self.txn().table_stats(database)
+    }
 }
 
 enum RwTxnState<T: RwTransaction> {
@@ -404,6 +421,10 @@ impl<T: Environment> Transaction for LmdbWriteTransaction<T> {
     fn count(&self, database: Self::Database) -> u64 {
         self.rw_txn().count(database)
     }
+
+    fn table_stats(&self, database: Self::Database) -> TableStats {
+        self.rw_txn().table_stats(database)
+    }
 }
 
 pub enum Table {
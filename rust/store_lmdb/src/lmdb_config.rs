@@ -28,6 +28,8 @@ pub struct LmdbConfig {
     pub sync: SyncStrategy,
     pub max_databases: u32,
     pub map_size: usize,
+    /// Maintain the (account, height) -> block hash index of the `block_heights` table
+    pub block_height_index: bool,
 }
 
 impl Default for LmdbConfig {
@@ -36,6 +38,7 @@ impl Default for LmdbConfig {
             sync: SyncStrategy::Always,
             max_databases: 128,
             map_size: 256 * 1024 * 1024 * 1024,
+            block_height_index: false,
         }
     }
 }
@@ -60,6 +63,11 @@ impl LmdbConfig {
             self.map_size,
             "Maximum ledger database map size in bytes.\ntype:uint64",
         )?;
+        toml.put_bool(
+            "block_height_index",
+            self.block_height_index,
+            "Maintain an index of the blocks by account and height, which speeds up lookups of blocks at a given height. Blocks which were inserted while the index was disabled are found by walking the account chain.\ntype:bool",
+        )?;
         Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    LmdbConfig, LmdbReadTransaction, LmdbWriteTransaction, NullTransactionTracker, SyncStrategy,
-    TransactionTracker,
+    read_ahead, AccessPattern, LmdbConfig, LmdbReadTransaction, LmdbWriteTransaction,
+    NullTransactionTracker, SyncStrategy, TransactionTracker,
 };
 use anyhow::bail;
 use lmdb::{DatabaseFlags, EnvironmentFlags, Stat, Transaction};
@@ -11,6 +11,7 @@ use std::collections::BTreeMap;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
+use std::sync::Mutex;
 use std::sync::OnceLock;
 use std::{
     ffi::{c_char, CStr},
@@ -42,6 +43,10 @@ pub trait RoCursor {
 //todo don't use static lifetimes!
 pub struct RoCursorWrapper(lmdb::RoCursor<'static>);
 
+// The environment is opened with NO_TLS, so a cursor may be used from another thread
+// than the one which opened its transaction, as long as it isn't used concurrently.
+unsafe impl Send for RoCursorWrapper {}
+
 impl RoCursor for RoCursorWrapper {
     type Iter = lmdb::Iter<'static>;
     fn iter_start(&mut self) -> lmdb::Iter<'static> {
@@ -166,6 +171,7 @@ pub trait RwTransaction {
     fn clear_db(&mut self, database: Self::Database) -> lmdb::Result<()>;
     fn open_ro_cursor(&self, database: Self::Database) -> lmdb::Result<Self::RoCursor>;
     fn count(&self, database: Self::Database) -> u64;
+    fn table_stats(&self, database: Self::Database) -> TableStats;
     fn commit(self) -> lmdb::Result<()>;
 }
 
@@ -221,6 +227,10 @@ impl RwTransaction for RwTransactionWrapper {
         stat.unwrap().entries() as u64
     }
 
+    fn table_stats(&self, database: Self::Database) -> TableStats {
+        TableStats::from(&lmdb::Transaction::stat(&self.0, database).unwrap())
+    }
+
     unsafe fn drop_db(&mut self, database: Self::Database) -> lmdb::Result<()> {
         lmdb::RwTransaction::drop_db(&mut self.0, database)
     }
@@ -300,6 +310,10 @@ impl RwTransaction for RwTransactionStub {
         0
     }
 
+    fn table_stats(&self, _database: Self::Database) -> TableStats {
+        TableStats::default()
+    }
+
     unsafe fn drop_db(&mut self, _database: Self::Database) -> lmdb::Result<()> {
         Ok(())
     }
@@ -334,6 +348,7 @@ pub trait RoTransaction {
     fn get(&self, database: Self::Database, key: &[u8]) -> lmdb::Result<&[u8]>;
     fn open_ro_cursor(&self, database: Self::Database) -> lmdb::Result<Self::RoCursor>;
     fn count(&self, database: Self::Database) -> u64;
+    fn table_stats(&self, database: Self::Database) -> TableStats;
 }
 
 pub struct InactiveTransactionWrapper {
@@ -381,6 +396,10 @@ impl RoTransaction for RoTransactionWrapper {
         let stat = lmdb::Transaction::stat(&self.0, database);
         stat.unwrap().entries() as u64
     }
+
+    fn table_stats(&self, database: Self::Database) -> TableStats {
+        TableStats::from(&lmdb::Transaction::stat(&self.0, database).unwrap())
+    }
 }
 pub struct RoTransactionStub {
     databases: Vec<ConfiguredDatabase>,
@@ -445,6 +464,13 @@ impl RoTransaction for RoTransactionStub {
             .map(|db| db.entries.len())
             .unwrap_or_default() as u64
     }
+
+    fn table_stats(&self, database: Self::Database) -> TableStats {
+        TableStats {
+            entries: self.count(database),
+            ..Default::default()
+        }
+    }
 }
 
 impl InactiveTransaction for NullInactiveTransaction {
@@ -490,6 +516,51 @@ pub trait Environment: Send + Sync {
     fn open_db(&self, name: Option<&str>) -> lmdb::Result<Self::Database>;
     fn sync(&self, force: bool) -> lmdb::Result<()>;
     fn stat(&self) -> lmdb::Result<Stat>;
+    fn map_usage(&self) -> lmdb::Result<MapUsage>;
+    /// Advises the OS how the database file is going to be read
+    fn advise(&self, pattern: AccessPattern) -> std::io::Result<()>;
+}
+
+/// Size of the memory map and how much of it is used by the database file
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct MapUsage {
+    pub map_size: u64,
+    pub used: u64,
+}
+
+/// Statistics of a single database as reported by `mdb_stat`
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct TableStats {
+    pub entries: u64,
+    pub depth: u32,
+    pub branch_pages: u64,
+    pub leaf_pages: u64,
+    pub overflow_pages: u64,
+    pub page_size: u32,
+}
+
+impl TableStats {
+    pub fn pages(&self) -> u64 {
+        self.branch_pages + self.leaf_pages + self.overflow_pages
+    }
+
+    /// Estimated size on disk. Free pages aren't included
+    pub fn size_bytes(&self) -> u64 {
+        self.pages() * self.page_size as u64
+    }
+}
+
+impl From<&Stat> for TableStats {
+    fn from(stat: &Stat) -> Self {
+        Self {
+            entries: stat.entries() as u64,
+            depth: stat.depth(),
+            branch_pages: stat.branch_pages() as u64,
+            leaf_pages: stat.leaf_pages() as u64,
+            overflow_pages: stat.overflow_pages() as u64,
+            page_size: stat.page_size(),
+        }
+    }
 }
 
 pub struct EnvironmentWrapper(lmdb::Environment);
@@ -549,6 +620,19 @@ impl Environment for EnvironmentWrapper {
     fn stat(&self) -> lmdb::Result<Stat> {
         self.0.stat()
     }
+
+    fn map_usage(&self) -> lmdb::Result<MapUsage> {
+        let info = self.0.info()?;
+        let page_size = self.0.stat()?.page_size() as u64;
+        Ok(MapUsage {
+            map_size: info.map_size() as u64,
+            used: (info.last_pgno() as u64 + 1) * page_size,
+        })
+    }
+
+    fn advise(&self, pattern: AccessPattern) -> std::io::Result<()> {
+        read_ahead::advise(self.0.env(), pattern)
+    }
 }
 
 pub struct EnvironmentStub {
@@ -616,15 +700,35 @@ impl Environment for EnvironmentStub {
     fn stat(&self) -> lmdb::Result<Stat> {
         todo!()
     }
+
+    fn map_usage(&self) -> lmdb::Result<MapUsage> {
+        Ok(MapUsage::default())
+    }
+
+    fn advise(&self, _pattern: AccessPattern) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 // Environment
 // --------------------------------------------------------------------------------
 
-#[derive(Default)]
 pub struct EnvOptions {
     pub config: LmdbConfig,
     pub use_no_mem_init: bool,
+    /// Advise the OS to read ahead during full table scans. Otherwise the
+    /// database is always read with `MDB_NORDAHEAD`
+    pub read_ahead_scans: bool,
+}
+
+impl Default for EnvOptions {
+    fn default() -> Self {
+        Self {
+            config: LmdbConfig::default(),
+            use_no_mem_init: false,
+            read_ahead_scans: true,
+        }
+    }
 }
 
 pub struct NullLmdbEnvBuilder {
@@ -713,6 +817,9 @@ pub struct LmdbEnv<T: Environment = EnvironmentWrapper> {
     pub environment: T,
     next_txn_id: AtomicU64,
     txn_tracker: Arc<dyn TransactionTracker>,
+    read_ahead_scans: bool,
+    /// Number of running sequential scans
+    sequential_scans: Mutex<usize>,
 }
 
 impl LmdbEnv<EnvironmentStub> {
@@ -737,6 +844,8 @@ impl<T: Environment> LmdbEnv<T> {
             environment: env,
             next_txn_id: AtomicU64::new(0),
             txn_tracker: Arc::new(NullTransactionTracker::new()),
+            read_ahead_scans: true,
+            sequential_scans: Mutex::new(0),
         }
     }
 
@@ -745,6 +854,8 @@ impl<T: Environment> LmdbEnv<T> {
             environment: Self::init(path, options)?,
             next_txn_id: AtomicU64::new(0),
             txn_tracker: Arc::new(NullTransactionTracker::new()),
+            read_ahead_scans: options.read_ahead_scans,
+            sequential_scans: Mutex::new(0),
         };
         Ok(env)
     }
@@ -758,6 +869,8 @@ impl<T: Environment> LmdbEnv<T> {
             environment: Self::init(path, options)?,
             next_txn_id: AtomicU64::new(0),
             txn_tracker,
+            read_ahead_scans: options.read_ahead_scans,
+            sequential_scans: Mutex::new(0),
         };
         Ok(env)
     }
@@ -827,6 +940,24 @@ impl<T: Environment> LmdbEnv<T> {
         Ok(source_path)
     }
 
+    /// Advises the OS to read ahead until the returned guard is dropped.
+    /// Use it for scans over whole tables
+    pub fn sequential_scan(&self) -> SequentialScan<'_, T> {
+        if self.read_ahead_scans {
+            let mut scans = self.sequential_scans.lock().unwrap();
+            if *scans == 0 {
+                // Read-ahead is only an optimization, so the scan continues without it
+                let _ = self.environment.advise(AccessPattern::Sequential);
+            }
+            *scans += 1;
+        }
+        SequentialScan { env: self }
+    }
+
+    pub fn sequential_scans(&self) -> usize {
+        *self.sequential_scans.lock().unwrap()
+    }
+
     fn create_txn_callbacks(&self) -> Arc<dyn TransactionTracker> {
         Arc::clone(&self.txn_tracker)
     }
@@ -842,6 +973,24 @@ impl<T: Environment> LmdbEnv<T> {
     }
 }
 
+/// Switches back to random access when the last running scan is finished
+pub struct SequentialScan<'a, T: Environment> {
+    env: &'a LmdbEnv<T>,
+}
+
+impl<'a, T: Environment> Drop for SequentialScan<'a, T> {
+    fn drop(&mut self) {
+        if !self.env.read_ahead_scans {
+            return;
+        }
+        let mut scans = self.env.sequential_scans.lock().unwrap();
+        *scans -= 1;
+        if *scans == 0 {
+            let _ = self.env.environment.advise(AccessPattern::Random);
+        }
+    }
+}
+
 fn try_create_parent_dir(path: &Path) -> std::io::Result<()> {
     if let Some(parent) = path.parent() {
         if parent != Path::new("") && !parent.is_dir() {
@@ -935,6 +1084,26 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn count_nested_sequential_scans() {
+        let env = TestLmdbEnv::new();
+        let env = env.env();
+        {
+            let _outer = env.sequential_scan();
+            let _inner = env.sequential_scan();
+            assert_eq!(env.sequential_scans(), 2);
+        }
+        assert_eq!(env.sequential_scans(), 0);
+    }
+
+    #[test]
+    fn advise_real_env() {
+        let env = TestLmdbEnv::new();
+        let env = env.env();
+        env.environment.advise(AccessPattern::Sequential).unwrap();
+        env.environment.advise(AccessPattern::Random).unwrap();
+    }
+
     mod rw_txn {
         use lmdb::WriteFlags;
 
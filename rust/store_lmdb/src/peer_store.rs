@@ -3,11 +3,57 @@ use crate::{
     LmdbWriteTransaction, Transaction,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
-use rsnano_core::{EndpointKey, NoValue};
+use rsnano_core::{
+    utils::{Deserialize, Serialize, Stream, StreamAdapter},
+    EndpointKey, NoValue,
+};
 use std::sync::Arc;
 
 pub type PeerIterator = Box<dyn DbIterator<EndpointKey, NoValue>>;
 
+/// What the node remembers about a peer
+#[derive(Default, PartialEq, Eq, Debug, Clone)]
+pub struct PeerHistory {
+    /// Seconds since epoch when the peer was last connected
+    pub last_seen: u64,
+    /// Seconds since epoch of the last successful handshake with the peer
+    pub last_handshake: u64,
+}
+
+impl PeerHistory {
+    pub fn new(last_seen: u64, last_handshake: u64) -> Self {
+        Self {
+            last_seen,
+            last_handshake,
+        }
+    }
+}
+
+impl Serialize for PeerHistory {
+    fn serialized_size() -> usize {
+        16
+    }
+
+    fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()> {
+        self.last_seen.serialize(stream)?;
+        self.last_handshake.serialize(stream)
+    }
+}
+
+impl Deserialize for PeerHistory {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        // Entries which were written by older versions have no value
+        let last_seen = u64::deserialize(stream).unwrap_or_default();
+        let last_handshake = u64::deserialize(stream).unwrap_or_default();
+        Ok(Self {
+            last_seen,
+            last_handshake,
+        })
+    }
+}
+
 pub struct LmdbPeerStore<T: Environment = EnvironmentWrapper> {
     _env: Arc<LmdbEnv<T>>,
     database: T::Database,
@@ -29,16 +75,39 @@ impl<T: Environment + 'static> LmdbPeerStore<T> {
         self.database
     }
 
-    pub fn put(&self, txn: &mut LmdbWriteTransaction<T>, endpoint: &EndpointKey) {
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        endpoint: &EndpointKey,
+        history: &PeerHistory,
+    ) {
+        let mut value = [0; 16];
+        value[..8].copy_from_slice(&history.last_seen.to_be_bytes());
+        value[8..].copy_from_slice(&history.last_handshake.to_be_bytes());
         txn.put(
             self.database,
             &endpoint.to_bytes(),
-            &[0; 0],
+            &value,
             WriteFlags::empty(),
         )
         .unwrap();
     }
 
+    pub fn get(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        endpoint: &EndpointKey,
+    ) -> Option<PeerHistory> {
+        match txn.get(self.database, &endpoint.to_bytes()) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok(bytes) => {
+                let mut stream = StreamAdapter::new(bytes);
+                Some(PeerHistory::deserialize(&mut stream).unwrap())
+            }
+            Err(e) => panic!("Could not load peer. {:?}", e),
+        }
+    }
+
     pub fn del(&self, txn: &mut LmdbWriteTransaction<T>, endpoint: &EndpointKey) {
         txn.delete(self.database, &endpoint.to_bytes(), None)
             .unwrap();
@@ -69,6 +138,56 @@ impl<T: Environment + 'static> LmdbPeerStore<T> {
     ) -> PeerIterator {
         LmdbIteratorImpl::<T>::new_iterator(txn, self.database, None, true)
     }
+
+    fn all(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> Vec<(EndpointKey, PeerHistory)> {
+        let mut it = LmdbIteratorImpl::<T>::new_iterator::<EndpointKey, PeerHistory>(
+            txn,
+            self.database,
+            None,
+            true,
+        );
+        let mut result = Vec::new();
+        while let Some((endpoint, history)) = it.current() {
+            result.push((endpoint.clone(), history.clone()));
+            it.next();
+        }
+        result
+    }
+
+    /// Returns the peers which should be contacted first when connecting to the network:
+    /// Peers with the most recent successful handshake come first
+    pub fn prioritized(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        max: usize,
+    ) -> Vec<(EndpointKey, PeerHistory)> {
+        let mut peers = self.all(txn);
+        peers.sort_by(|(_, a), (_, b)| {
+            b.last_handshake
+                .cmp(&a.last_handshake)
+                .then(b.last_seen.cmp(&a.last_seen))
+        });
+        peers.truncate(max);
+        peers
+    }
+
+    /// Removes all peers which weren't seen since `cutoff` (in seconds since epoch).
+    /// Returns the number of removed peers
+    pub fn prune(&self, txn: &mut LmdbWriteTransaction<T>, cutoff: u64) -> usize {
+        let outdated: Vec<_> = self
+            .all(&*txn)
+            .into_iter()
+            .filter(|(_, history)| history.last_seen < cutoff)
+            .map(|(endpoint, _)| endpoint)
+            .collect();
+        for endpoint in &outdated {
+            self.del(txn, endpoint);
+        }
+        outdated.len()
+    }
 }
 
 #[cfg(test)]
@@ -126,14 +245,14 @@ mod tests {
         let put_tracker = txn.track_puts();
 
         let key = EndpointKey::create_test_instance();
-        fixture.store.put(&mut txn, &key);
+        fixture.store.put(&mut txn, &key, &PeerHistory::new(1, 2));
 
         assert_eq!(
             put_tracker.output(),
             vec![PutEvent {
                 database: Default::default(),
                 key: key.to_bytes().to_vec(),
-                value: Vec::new(),
+                value: vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2],
                 flags: WriteFlags::empty()
             }]
         )
@@ -196,4 +315,94 @@ mod tests {
             }]
         )
     }
+
+    #[test]
+    fn load_history() {
+        let endpoint = EndpointKey::new([1; 16], 1000);
+        let env = LmdbEnv::create_null_with()
+            .database("peers", DatabaseStub::default())
+            .entry(&endpoint.to_bytes(), &history_bytes(3, 4))
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.get(&txn, &endpoint),
+            Some(PeerHistory::new(3, 4))
+        );
+        assert_eq!(
+            fixture.store.get(&txn, &EndpointKey::new([2; 16], 1000)),
+            None
+        );
+    }
+
+    #[test]
+    fn load_entry_without_history() {
+        let endpoint = EndpointKey::new([1; 16], 1000);
+        let fixture = Fixture::with_stored_data(vec![endpoint.clone()]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.get(&txn, &endpoint),
+            Some(PeerHistory::default())
+        );
+    }
+
+    #[test]
+    fn prioritize_recent_handshakes() {
+        let endpoint_a = EndpointKey::new([1; 16], 1000);
+        let endpoint_b = EndpointKey::new([2; 16], 2000);
+        let endpoint_c = EndpointKey::new([3; 16], 3000);
+        let env = LmdbEnv::create_null_with()
+            .database("peers", DatabaseStub::default())
+            .entry(&endpoint_a.to_bytes(), &history_bytes(10, 5))
+            .entry(&endpoint_b.to_bytes(), &history_bytes(10, 9))
+            .entry(&endpoint_c.to_bytes(), &[])
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let txn = fixture.env.tx_begin_read();
+
+        let peers: Vec<_> = fixture
+            .store
+            .prioritized(&txn, 2)
+            .into_iter()
+            .map(|(endpoint, _)| endpoint)
+            .collect();
+
+        assert_eq!(peers, vec![endpoint_b, endpoint_a]);
+    }
+
+    #[test]
+    fn prune_outdated_peers() {
+        let endpoint_a = EndpointKey::new([1; 16], 1000);
+        let endpoint_b = EndpointKey::new([2; 16], 2000);
+        let env = LmdbEnv::create_null_with()
+            .database("peers", DatabaseStub::default())
+            .entry(&endpoint_a.to_bytes(), &history_bytes(10, 10))
+            .entry(&endpoint_b.to_bytes(), &history_bytes(100, 100))
+            .build()
+            .build();
+        let fixture = Fixture::with_env(env);
+        let mut txn = fixture.env.tx_begin_write();
+        let delete_tracker = txn.track_deletions();
+
+        let pruned = fixture.store.prune(&mut txn, 50);
+
+        assert_eq!(pruned, 1);
+        assert_eq!(
+            delete_tracker.output(),
+            vec![DeleteEvent {
+                database: Default::default(),
+                key: endpoint_a.to_bytes().to_vec()
+            }]
+        )
+    }
+
+    fn history_bytes(last_seen: u64, last_handshake: u64) -> Vec<u8> {
+        let mut bytes = last_seen.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&last_handshake.to_be_bytes());
+        bytes
+    }
 }
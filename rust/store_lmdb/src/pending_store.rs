@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    iterator::DbIterator, parallel_traversal_u512, ConfiguredDatabase, Environment,
+    iterator::DbIterator, parallel_traversal_u512, ConfiguredDatabase, DbRangeIter, Environment,
     EnvironmentWrapper, LmdbEnv, LmdbIteratorImpl, LmdbReadTransaction, LmdbWriteTransaction,
     Transaction, PENDING_TEST_DATABASE,
 };
@@ -107,6 +107,40 @@ impl<T: Environment + 'static> LmdbPendingStore<T> {
         LmdbIteratorImpl::<T>::new_iterator(txn, self.database, Some(&key_bytes), true)
     }
 
+    pub fn count(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> u64 {
+        txn.count(self.database)
+    }
+
+    /// Iterates over all pending entries
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> DbRangeIter<'txn, PendingKey, PendingInfo, T> {
+        DbRangeIter::new(txn, self.database, None, None)
+    }
+
+    /// Iterates over the pending entries of `account`
+    pub fn iter_account<'txn>(
+        &self,
+        txn: &'txn dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        account: &Account,
+    ) -> DbRangeIter<'txn, PendingKey, PendingInfo, T> {
+        let start = PendingKey::new(*account, BlockHash::zero()).to_bytes();
+        let end = account
+            .number()
+            .checked_add(1.into())
+            .map(|next| PendingKey::new(Account::from(next), BlockHash::zero()).to_bytes());
+        DbRangeIter::new(
+            txn,
+            self.database,
+            Some(&start),
+            end.as_ref().map(|e| &e[..]),
+        )
+    }
+
     pub fn exists(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
@@ -133,6 +167,7 @@ impl<T: Environment + 'static> LmdbPendingStore<T> {
         &self,
         action: &(dyn Fn(&LmdbReadTransaction<T>, PendingIterator, PendingIterator) + Send + Sync),
     ) {
+        let _scan = self.env.sequential_scan();
         parallel_traversal_u512(&|start, end, is_last| {
             let transaction = self.env.tx_begin_read();
             let begin_it = self.begin_at_key(&transaction, &start.into());
@@ -299,6 +334,50 @@ mod tests {
         assert!(it.is_end());
     }
 
+    #[test]
+    fn owned_iter() {
+        let key = PendingKey::create_test_instance();
+        let info = PendingInfo::create_test_instance();
+        let fixture = Fixture::with_stored_data(vec![(key.clone(), info.clone())]);
+        let txn = fixture.env.tx_begin_read();
+
+        let entries: Vec<_> = fixture.store.iter(&txn).collect();
+
+        assert_eq!(entries, vec![(key, info)]);
+    }
+
+    #[test]
+    fn iter_account_stops_at_next_account() {
+        let info = PendingInfo::create_test_instance();
+        let key1 = PendingKey::new(Account::from(1), BlockHash::from(1));
+        let key2 = PendingKey::new(Account::from(2), BlockHash::from(2));
+        let key3 = PendingKey::new(Account::from(2), BlockHash::from(3));
+        let key4 = PendingKey::new(Account::from(3), BlockHash::from(4));
+        let fixture = Fixture::with_stored_data(vec![
+            (key1, info.clone()),
+            (key2.clone(), info.clone()),
+            (key3.clone(), info.clone()),
+            (key4, info.clone()),
+        ]);
+        let txn = fixture.env.tx_begin_read();
+
+        let keys: Vec<_> = fixture
+            .store
+            .iter_account(&txn, &Account::from(2))
+            .map(|(k, _)| k)
+            .collect();
+
+        assert_eq!(keys, vec![key2, key3]);
+    }
+
+    #[test]
+    fn owned_iter_is_send() {
+        fn assert_send<T: Send>(_: &T) {}
+        let fixture = Fixture::new();
+        let txn = fixture.env.tx_begin_read();
+        assert_send(&fixture.store.iter(&txn));
+    }
+
     #[test]
     fn tracks_puts() {
         let fixture = Fixture::new();
@@ -92,6 +92,7 @@ impl<T: Environment + 'static> LmdbPrunedStore<T> {
         &self,
         action: &(dyn Fn(&LmdbReadTransaction<T>, PrunedIterator, PrunedIterator) + Send + Sync),
     ) {
+        let _scan = self.env.sequential_scan();
         parallel_traversal(&|start, end, is_last| {
             let transaction = self.env.tx_begin_read();
             let begin_it = self.begin_at_hash(&transaction, &start.into());
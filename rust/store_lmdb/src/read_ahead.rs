@@ -0,0 +1,116 @@
+use lmdb_sys::{MDB_env, MDB_SUCCESS};
+use std::{
+    ffi::CStr,
+    fs::File,
+    io,
+    os::{fd::AsRawFd, raw::c_char},
+    path::{Path, PathBuf},
+};
+
+/// How the database file is going to be read
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessPattern {
+    /// Point lookups. This is the default, because the environment is opened with `MDB_NORDAHEAD`
+    Random,
+    /// Full table scans, which profit from read-ahead
+    Sequential,
+}
+
+/// Advises the OS how the database file of `env` is going to be read.
+/// LMDB reads the file through a memory map, so the map is advised as well as the file.
+pub(crate) fn advise(env: *mut MDB_env, pattern: AccessPattern) -> io::Result<()> {
+    let mut fd = 0;
+    if unsafe { lmdb_sys::mdb_env_get_fd(env, &mut fd) } != MDB_SUCCESS {
+        return Err(io::Error::other("could not get env fd"));
+    }
+    let mut path: *const c_char = std::ptr::null();
+    if unsafe { lmdb_sys::mdb_env_get_path(env, &mut path) } != MDB_SUCCESS {
+        return Err(io::Error::other("could not get env path"));
+    }
+    let path = PathBuf::from(unsafe { CStr::from_ptr(path) }.to_string_lossy().as_ref());
+
+    let (file_advice, map_advice) = match pattern {
+        AccessPattern::Random => (libc::POSIX_FADV_RANDOM, libc::MADV_RANDOM),
+        AccessPattern::Sequential => (libc::POSIX_FADV_SEQUENTIAL, libc::MADV_SEQUENTIAL),
+    };
+    let result = unsafe { libc::posix_fadvise(fd, 0, 0, file_advice) };
+    if result != 0 {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+    for (start, end) in file_mappings(&path)? {
+        let result = unsafe { libc::madvise(start as *mut libc::c_void, end - start, map_advice) };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Removes the file from the page cache, so that the next read has to go to the disk.
+/// Used for benchmarking reads with a cold cache
+pub fn drop_file_cache(path: &Path) -> io::Result<()> {
+    let file = File::open(path)?;
+    let result = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    if result != 0 {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+    Ok(())
+}
+
+/// Address ranges at which `path` is mapped into this process
+fn file_mappings(path: &Path) -> io::Result<Vec<(usize, usize)>> {
+    let path = path.canonicalize()?;
+    let maps = match std::fs::read_to_string("/proc/self/maps") {
+        Ok(maps) => maps,
+        // Not every platform has procfs. The file advice is still applied
+        Err(_) => return Ok(Vec::new()),
+    };
+    Ok(parse_mappings(&maps, &path))
+}
+
+/// Parses lines of `/proc/self/maps` like
+/// `7f2c4a000000-7f2c8a000000 r--s 00000000 fd:01 1234   /path/to/data.ldb`
+fn parse_mappings(maps: &str, path: &Path) -> Vec<(usize, usize)> {
+    maps.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(6, ' ');
+            let range = fields.next()?;
+            let mapped_path = fields.nth(4)?.trim_start();
+            if Path::new(mapped_path) != path {
+                return None;
+            }
+            let (start, end) = range.split_once('-')?;
+            let start = usize::from_str_radix(start, 16).ok()?;
+            let end = usize::from_str_radix(end, 16).ok()?;
+            Some((start, end))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file_mappings() {
+        let maps = "\
+55d1c8a00000-55d1c8a21000 r--p 00000000 fd:01 4242      /usr/bin/node
+7f2c4a000000-7f2c8a000000 r--s 00000000 fd:01 1234      /data/data.ldb
+7f2c8a000000-7f2c8a001000 rw-p 00000000 00:00 0
+7f2c8b000000-7f2c8b100000 r--s 00000000 fd:01 1235      /data/data.ldb.bak";
+
+        assert_eq!(
+            parse_mappings(maps, Path::new("/data/data.ldb")),
+            vec![(0x7f2c4a000000, 0x7f2c8a000000)]
+        );
+    }
+
+    #[test]
+    fn paths_with_spaces() {
+        let maps = "7f2c4a000000-7f2c4a001000 r--s 00000000 fd:01 1234      /my data/data.ldb";
+        assert_eq!(
+            parse_mappings(maps, Path::new("/my data/data.ldb")),
+            vec![(0x7f2c4a000000, 0x7f2c4a001000)]
+        );
+    }
+}
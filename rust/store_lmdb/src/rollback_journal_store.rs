@@ -0,0 +1,202 @@
+use crate::{
+    Environment, EnvironmentWrapper, LmdbEnv, LmdbIteratorImpl, LmdbWriteTransaction, Transaction,
+};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsnano_core::{
+    utils::{Deserialize, Serialize, Stream, StreamAdapter},
+    BlockHash,
+};
+use std::sync::Arc;
+
+/// The blocks of a rollback which weren't rolled back yet, in rollback order
+#[derive(Default, PartialEq, Eq, Debug, Clone)]
+pub struct PlannedRollback(pub Vec<BlockHash>);
+
+impl Serialize for PlannedRollback {
+    fn serialized_size() -> usize {
+        // The size depends on the number of blocks
+        0
+    }
+
+    fn serialize(&self, stream: &mut dyn Stream) -> anyhow::Result<()> {
+        for hash in &self.0 {
+            hash.serialize(stream)?;
+        }
+        Ok(())
+    }
+}
+
+impl Deserialize for PlannedRollback {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let mut hashes = Vec::new();
+        while stream.in_avail()? > 0 {
+            hashes.push(BlockHash::deserialize(stream)?);
+        }
+        Ok(Self(hashes))
+    }
+}
+
+/// Records multi-block rollbacks before they are executed, so that a rollback which
+/// was interrupted by a crash can be completed when the node starts again.
+/// The key is the hash of the block which is rolled back.
+pub struct LmdbRollbackJournalStore<T: Environment = EnvironmentWrapper> {
+    _env: Arc<LmdbEnv<T>>,
+    database: T::Database,
+}
+
+impl<T: Environment + 'static> LmdbRollbackJournalStore<T> {
+    pub fn new(env: Arc<LmdbEnv<T>>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("rollback_journal"), DatabaseFlags::empty())?;
+
+        Ok(Self {
+            _env: env,
+            database,
+        })
+    }
+
+    pub fn database(&self) -> T::Database {
+        self.database
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        target: &BlockHash,
+        planned: &PlannedRollback,
+    ) {
+        let mut value = Vec::with_capacity(planned.0.len() * 32);
+        for hash in &planned.0 {
+            value.extend_from_slice(hash.as_bytes());
+        }
+        txn.put(
+            self.database,
+            target.as_bytes(),
+            &value,
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        target: &BlockHash,
+    ) -> Option<PlannedRollback> {
+        match txn.get(self.database, target.as_bytes()) {
+            Err(lmdb::Error::NotFound) => None,
+            Ok(bytes) => {
+                let mut stream = StreamAdapter::new(bytes);
+                Some(PlannedRollback::deserialize(&mut stream).unwrap())
+            }
+            Err(e) => panic!("Could not load rollback journal entry. {:?}", e),
+        }
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction<T>, target: &BlockHash) {
+        txn.delete(self.database, target.as_bytes(), None).unwrap();
+    }
+
+    pub fn count(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> u64 {
+        txn.count(self.database)
+    }
+
+    /// All interrupted rollbacks
+    pub fn entries(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> Vec<(BlockHash, PlannedRollback)> {
+        let mut it = LmdbIteratorImpl::<T>::new_iterator::<BlockHash, PlannedRollback>(
+            txn,
+            self.database,
+            None,
+            true,
+        );
+        let mut result = Vec::new();
+        while let Some((target, planned)) = it.current() {
+            result.push((*target, planned.clone()));
+            it.next();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lmdb_env::DatabaseStub, PutEvent};
+
+    struct Fixture {
+        env: Arc<LmdbEnv<crate::EnvironmentStub>>,
+        store: LmdbRollbackJournalStore<crate::EnvironmentStub>,
+    }
+
+    impl Fixture {
+        fn with_stored_data(entries: Vec<(BlockHash, Vec<u8>)>) -> Self {
+            let mut env =
+                LmdbEnv::create_null_with().database("rollback_journal", DatabaseStub::default());
+            for (key, value) in entries {
+                env = env.entry(key.as_bytes(), &value);
+            }
+            let env = Arc::new(env.build().build());
+            Self {
+                env: env.clone(),
+                store: LmdbRollbackJournalStore::new(env).unwrap(),
+            }
+        }
+    }
+
+    #[test]
+    fn empty_store() {
+        let fixture = Fixture::with_stored_data(Vec::new());
+        let txn = fixture.env.tx_begin_read();
+        assert_eq!(fixture.store.count(&txn), 0);
+        assert_eq!(fixture.store.get(&txn, &BlockHash::from(1)), None);
+        assert!(fixture.store.entries(&txn).is_empty());
+    }
+
+    #[test]
+    fn add_planned_rollback() {
+        let fixture = Fixture::with_stored_data(Vec::new());
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let target = BlockHash::from(1);
+
+        fixture.store.put(
+            &mut txn,
+            &target,
+            &PlannedRollback(vec![BlockHash::from(2), BlockHash::from(1)]),
+        );
+
+        let mut expected_value = BlockHash::from(2).as_bytes().to_vec();
+        expected_value.extend_from_slice(BlockHash::from(1).as_bytes());
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: DatabaseStub::default(),
+                key: target.as_bytes().to_vec(),
+                value: expected_value,
+                flags: WriteFlags::empty()
+            }]
+        );
+    }
+
+    #[test]
+    fn load_planned_rollbacks() {
+        let target = BlockHash::from(1);
+        let mut value = BlockHash::from(2).as_bytes().to_vec();
+        value.extend_from_slice(target.as_bytes());
+        let fixture = Fixture::with_stored_data(vec![(target, value)]);
+        let txn = fixture.env.tx_begin_read();
+
+        let expected = PlannedRollback(vec![BlockHash::from(2), target]);
+        assert_eq!(fixture.store.get(&txn, &target), Some(expected.clone()));
+        assert_eq!(fixture.store.entries(&txn), vec![(target, expected)]);
+    }
+}
@@ -7,11 +7,11 @@ use std::{
 
 use crate::{
     lmdb_env::{EnvironmentWrapper, RoCursor, RoTransaction, RwTransaction},
-    EnvOptions, Environment, EnvironmentStub, LmdbAccountStore, LmdbBlockStore,
-    LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore, LmdbFrontierStore,
-    LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction,
-    LmdbVersionStore, LmdbWriteTransaction, NullTransactionTracker, Table, TransactionTracker,
-    STORE_VERSION_MINIMUM,
+    DbIteratorImpl, EnvOptions, Environment, EnvironmentStub, LmdbAccountStore, LmdbBlockStore,
+    LmdbConfig, LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore, LmdbFrontierStore,
+    LmdbIteratorImpl, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore,
+    LmdbReadTransaction, LmdbRollbackJournalStore, LmdbVersionStore, LmdbWriteTransaction,
+    MapUsage, NullTransactionTracker, Table, TableStats, TransactionTracker, STORE_VERSION_MINIMUM,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
 use lmdb_sys::{MDB_CP_COMPACT, MDB_SUCCESS};
@@ -23,6 +23,17 @@ pub enum Vacuuming {
     NotNeeded,
 }
 
+#[derive(Default, Clone)]
+pub struct VacuumOptions {
+    /// Removes all cached peers before the database is copied.
+    /// The unchecked blocks don't have to be pruned, because they aren't persisted anymore
+    pub clear_peers: bool,
+
+    /// An existing file at the target path is moved here before the vacuumed copy takes
+    /// its place, and is kept afterwards
+    pub backup_path: Option<PathBuf>,
+}
+
 pub struct LmdbStore<T: Environment = EnvironmentWrapper> {
     pub env: Arc<LmdbEnv<T>>,
     pub block: Arc<LmdbBlockStore<T>>,
@@ -32,6 +43,7 @@ pub struct LmdbStore<T: Environment = EnvironmentWrapper> {
     pub online_weight: Arc<LmdbOnlineWeightStore<T>>,
     pub pruned: Arc<LmdbPrunedStore<T>>,
     pub peer: Arc<LmdbPeerStore<T>>,
+    pub rollback_journal: Arc<LmdbRollbackJournalStore<T>>,
     pub confirmation_height: Arc<LmdbConfirmationHeightStore<T>>,
     pub final_vote: Arc<LmdbFinalVoteStore<T>>,
     pub version: Arc<LmdbVersionStore<T>>,
@@ -123,13 +135,17 @@ impl<T: Environment + 'static> LmdbStore<T> {
         let env = Arc::new(LmdbEnv::<T>::with_txn_tracker(path, options, txn_tracker)?);
 
         Ok(Self {
-            block: Arc::new(LmdbBlockStore::new(env.clone())?),
+            block: Arc::new(LmdbBlockStore::new(
+                env.clone(),
+                options.config.block_height_index,
+            )?),
             frontier: Arc::new(LmdbFrontierStore::new(env.clone())?),
             account: Arc::new(LmdbAccountStore::new(env.clone())?),
             pending: Arc::new(LmdbPendingStore::new(env.clone())?),
             online_weight: Arc::new(LmdbOnlineWeightStore::new(env.clone())?),
             pruned: Arc::new(LmdbPrunedStore::new(env.clone())?),
             peer: Arc::new(LmdbPeerStore::new(env.clone())?),
+            rollback_journal: Arc::new(LmdbRollbackJournalStore::new(env.clone())?),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone())?),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone())?),
             version: Arc::new(LmdbVersionStore::new(env.clone())?),
@@ -143,6 +159,119 @@ impl<T: Environment + 'static> LmdbStore<T> {
         copy_db(&self.env, destination)
     }
 
+    /// Writes a compacted copy of the database to `target_path`.
+    /// The copy is written to a temporary file next to `target_path` and every entry of every
+    /// table is compared with the original before it replaces `target_path` with an atomic rename.
+    /// If `target_path` is the file of this store, the store has to be reopened afterwards.
+    pub fn vacuum(&self, target_path: &Path, options: &VacuumOptions) -> anyhow::Result<()> {
+        if options.clear_peers {
+            let mut txn = self.tx_begin_write();
+            self.peer.clear(&mut txn);
+        }
+
+        let mut temp_path = target_path.as_os_str().to_owned();
+        temp_path.push(".vacuum");
+        let temp_path = PathBuf::from(temp_path);
+
+        let result = copy_db(&self.env, &temp_path).and_then(|_| self.verify_copy(&temp_path));
+        let _ = std::fs::remove_file(lock_file_path(&temp_path));
+        if let Err(e) = result {
+            // The copy can be in an inconsistent state if there wasn't enough space to create it
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(e);
+        }
+
+        if let Some(backup_path) = &options.backup_path {
+            if target_path.exists() {
+                std::fs::rename(target_path, backup_path)?;
+            }
+        }
+
+        if let Err(e) = std::fs::rename(&temp_path, target_path) {
+            if let Some(backup_path) = &options.backup_path {
+                if backup_path.exists() && !target_path.exists() {
+                    std::fs::rename(backup_path, target_path)?;
+                }
+            }
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Checks that the copy has exactly the same entries in every table
+    fn verify_copy(&self, copy_path: &Path) -> anyhow::Result<()> {
+        let options = EnvOptions {
+            config: LmdbConfig {
+                block_height_index: self.block.height_index_enabled(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let copy = LmdbStore::<T>::new(
+            copy_path,
+            &options,
+            Arc::new(NullTransactionTracker::new()),
+            Arc::new(NullLogger::new()),
+            false,
+        )?;
+        let txn = self.tx_begin_read();
+        let copy_txn = copy.tx_begin_read();
+
+        if self.version.get(&txn) != copy.version.get(&copy_txn) {
+            bail!("version mismatch in vacuumed database");
+        }
+
+        for ((name, original_db), (_, copy_db)) in self.tables().into_iter().zip(copy.tables()) {
+            let mut original = LmdbIteratorImpl::<T>::new(&txn, original_db, None, true);
+            let mut copied = LmdbIteratorImpl::<T>::new(&copy_txn, copy_db, None, true);
+            let mut entries = 0;
+            loop {
+                match (original.current(), copied.current()) {
+                    (None, None) => break,
+                    (Some(a), Some(b)) if a == b => {}
+                    _ => bail!(
+                        "table {} differs from the vacuumed database at entry {}",
+                        name,
+                        entries
+                    ),
+                }
+                original.next();
+                copied.next();
+                entries += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// `mdb_stat` of every table of the ledger
+    pub fn table_stats(&self, txn: &LmdbReadTransaction<T>) -> Vec<(&'static str, TableStats)> {
+        self.tables()
+            .into_iter()
+            .map(|(name, db)| (name, crate::Transaction::table_stats(txn, db)))
+            .collect()
+    }
+
+    fn tables(&self) -> Vec<(&'static str, T::Database)> {
+        let mut tables = vec![
+            ("accounts", self.account.database()),
+            ("blocks", self.block.database()),
+            ("block_timestamps", self.block.timestamps_database()),
+            ("block_destinations", self.block.destinations_database()),
+            ("frontiers", self.frontier.database()),
+            ("pending", self.pending.database()),
+            ("online_weight", self.online_weight.database()),
+            ("pruned", self.pruned.database()),
+            ("peers", self.peer.database()),
+            ("rollback_journal", self.rollback_journal.database()),
+            ("confirmation_height", self.confirmation_height.database()),
+            ("final_votes", self.final_vote.database()),
+        ];
+        if let Some(heights) = self.block.heights_database() {
+            tables.push(("block_heights", heights));
+        }
+        tables
+    }
+
     pub fn tx_begin_write_for(&self, _to_lock: &[Table]) -> LmdbWriteTransaction<T> {
         // locking tables is not needed for LMDB because there can only ever be one write transaction at a time
         self.env.tx_begin_write()
@@ -163,6 +292,10 @@ impl<T: Environment + 'static> LmdbStore<T> {
         Ok(())
     }
 
+    pub fn map_usage(&self) -> anyhow::Result<MapUsage> {
+        Ok(self.env.environment.map_usage()?)
+    }
+
     pub fn serialize_memory_stats(&self, json: &mut dyn PropertyTreeWriter) -> anyhow::Result<()> {
         let stats = self.env.environment.stat()?;
         json.put_u64("branch_pages", stats.branch_pages() as u64)?;
@@ -171,6 +304,24 @@ impl<T: Environment + 'static> LmdbStore<T> {
         json.put_u64("leaf_pages", stats.leaf_pages() as u64)?;
         json.put_u64("overflow_pages", stats.overflow_pages() as u64)?;
         json.put_u64("page_size", stats.page_size() as u64)?;
+        let map_usage = self.map_usage()?;
+        json.put_u64("map_size", map_usage.map_size)?;
+        json.put_u64("map_used", map_usage.used)?;
+
+        let txn = self.tx_begin_read();
+        let mut tables = json.new_writer();
+        for (name, table) in self.table_stats(&txn) {
+            let mut entry = json.new_writer();
+            entry.put_string("name", name)?;
+            entry.put_u64("entries", table.entries)?;
+            entry.put_u64("depth", table.depth as u64)?;
+            entry.put_u64("branch_pages", table.branch_pages)?;
+            entry.put_u64("leaf_pages", table.leaf_pages)?;
+            entry.put_u64("overflow_pages", table.overflow_pages)?;
+            entry.put_u64("size", table.size_bytes())?;
+            tables.push_back("", entry.as_ref());
+        }
+        json.add_child("tables", tables.as_ref());
         Ok(())
     }
 
@@ -333,11 +484,19 @@ fn vacuum_after_upgrade<T: Environment>(env: Arc<LmdbEnv<T>>, path: &Path) -> an
 }
 fn copy_db<T: Environment>(env: &LmdbEnv<T>, destination: &Path) -> anyhow::Result<()> {
     let c_path = CString::new(destination.as_os_str().to_str().unwrap()).unwrap();
+    // Compacting walks through all tables
+    let _scan = env.sequential_scan();
     let status =
         unsafe { lmdb_sys::mdb_env_copy2(env.environment.env(), c_path.as_ptr(), MDB_CP_COMPACT) };
     ensure_success(status)
 }
 
+fn lock_file_path(db_path: &Path) -> PathBuf {
+    let mut lock_path = db_path.as_os_str().to_owned();
+    lock_path.push("-lock");
+    PathBuf::from(lock_path)
+}
+
 fn ensure_success(status: i32) -> Result<(), anyhow::Error> {
     if status == MDB_SUCCESS {
         Ok(())
@@ -414,7 +573,8 @@ fn backup_file_path(source_path: &Path) -> anyhow::Result<PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::TestDbFile;
+    use crate::{PeerHistory, TestDbFile};
+    use rsnano_core::{Amount, EndpointKey};
 
     #[test]
     fn create_store() -> anyhow::Result<()> {
@@ -449,6 +609,111 @@ mod tests {
         assert_eq!(store.version.get(&txn), Some(STORE_VERSION_MINIMUM));
     }
 
+    #[test]
+    fn vacuum_into_new_file() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let target = TestDbFile::random();
+        let store = LmdbStore::<EnvironmentWrapper>::open(&file.path).build()?;
+        {
+            let mut txn = store.tx_begin_write();
+            store.online_weight.put(&mut txn, 1, &Amount::raw(2));
+        }
+
+        store.vacuum(&target.path, &VacuumOptions::default())?;
+
+        let vacuumed = LmdbStore::<EnvironmentWrapper>::open(&target.path).build()?;
+        let txn = vacuumed.tx_begin_read();
+        assert_eq!(vacuumed.online_weight.count(&txn), 1);
+        assert!(!Path::new(&format!("{}.vacuum", target.path.display())).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn vacuum_keeps_backup_of_replaced_file() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let target = TestDbFile::random();
+        let backup = TestDbFile::random();
+        let store = LmdbStore::<EnvironmentWrapper>::open(&file.path).build()?;
+        {
+            let mut txn = store.tx_begin_write();
+            store.online_weight.put(&mut txn, 1, &Amount::raw(2));
+        }
+        std::fs::write(&target.path, b"previous")?;
+
+        store.vacuum(
+            &target.path,
+            &VacuumOptions {
+                backup_path: Some(backup.path.clone()),
+                ..Default::default()
+            },
+        )?;
+
+        assert_eq!(std::fs::read(&backup.path)?, b"previous");
+        let vacuumed = LmdbStore::<EnvironmentWrapper>::open(&target.path).build()?;
+        let txn = vacuumed.tx_begin_read();
+        assert_eq!(vacuumed.online_weight.count(&txn), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn vacuum_can_clear_peers() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let target = TestDbFile::random();
+        let store = LmdbStore::<EnvironmentWrapper>::open(&file.path).build()?;
+        {
+            let mut txn = store.tx_begin_write();
+            store.peer.put(
+                &mut txn,
+                &EndpointKey::new([1; 16], 7075),
+                &PeerHistory::default(),
+            );
+        }
+
+        store.vacuum(
+            &target.path,
+            &VacuumOptions {
+                clear_peers: true,
+                ..Default::default()
+            },
+        )?;
+
+        let vacuumed = LmdbStore::<EnvironmentWrapper>::open(&target.path).build()?;
+        let txn = vacuumed.tx_begin_read();
+        assert_eq!(vacuumed.peer.count(&txn), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn table_stats() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let store = LmdbStore::<EnvironmentWrapper>::open(&file.path).build()?;
+        {
+            let mut txn = store.tx_begin_write();
+            store.online_weight.put(&mut txn, 1, &Amount::raw(2));
+            store.online_weight.put(&mut txn, 2, &Amount::raw(3));
+        }
+
+        let txn = store.tx_begin_read();
+        let stats = store.table_stats(&txn);
+        let (_, online_weight) = stats
+            .iter()
+            .find(|(name, _)| *name == "online_weight")
+            .unwrap();
+        assert_eq!(online_weight.entries, 2);
+        assert_eq!(online_weight.leaf_pages, 1);
+        assert_eq!(online_weight.size_bytes(), online_weight.page_size as u64);
+        assert_eq!(
+            stats
+                .iter()
+                .find(|(name, _)| *name == "blocks")
+                .unwrap()
+                .1
+                .entries,
+            0
+        );
+        Ok(())
+    }
+
     fn assert_upgrade_fails(path: &Path, error_msg: &str) {
         match LmdbStore::<EnvironmentWrapper>::open(path).build() {
             Ok(_) => panic!("store should not be created!"),
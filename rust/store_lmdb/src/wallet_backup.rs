@@ -0,0 +1,190 @@
+use anyhow::Context;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hmac::{Hmac, Mac};
+use rand::{thread_rng, Rng};
+use rsnano_core::{KeyDerivationFunction, RawKey};
+use sha2::Sha256;
+use std::{
+    fs::{remove_file, rename},
+    io,
+    path::{Path, PathBuf},
+};
+
+type Aes256Ctr = ctr::Ctr64BE<aes::Aes256>;
+type HmacSha256 = Hmac<Sha256>;
+
+const EXPORT_VERSION: u64 = 1;
+
+/// Encrypts a serialized wallet with a key derived from `passphrase`.
+/// The result is a JSON object, which also contains the salt and the IV.
+pub fn encrypt_wallet_export(json: &str, passphrase: &str, kdf: &KeyDerivationFunction) -> String {
+    let salt: [u8; 32] = thread_rng().gen();
+    let iv: [u8; 16] = thread_rng().gen();
+    let key = kdf.hash_password(passphrase, &salt);
+    let mut data = json.as_bytes().to_vec();
+    Aes256Ctr::new(&(*key.as_bytes()).into(), &iv.into()).apply_keystream(&mut data);
+    let mac = export_mac(&key, &iv, &data).finalize().into_bytes();
+
+    serde_json::json!({
+        "version": EXPORT_VERSION,
+        "salt": hex::encode_upper(salt),
+        "iv": hex::encode_upper(iv),
+        "data": hex::encode_upper(&data),
+        "mac": hex::encode_upper(mac),
+    })
+    .to_string()
+}
+
+/// Returns the serialized wallet. Fails if the passphrase is wrong or the export was modified
+pub fn decrypt_wallet_export(
+    export: &str,
+    passphrase: &str,
+    kdf: &KeyDerivationFunction,
+) -> anyhow::Result<String> {
+    let export: serde_json::Value = serde_json::from_str(export)?;
+    if export["version"].as_u64() != Some(EXPORT_VERSION) {
+        bail!("unsupported wallet export version");
+    }
+    let salt: [u8; 32] = hex_field(&export, "salt")?
+        .try_into()
+        .map_err(|_| anyhow!("invalid salt length"))?;
+    let iv: [u8; 16] = hex_field(&export, "iv")?
+        .try_into()
+        .map_err(|_| anyhow!("invalid iv length"))?;
+    let mut data = hex_field(&export, "data")?;
+    let mac = hex_field(&export, "mac")?;
+
+    let key = kdf.hash_password(passphrase, &salt);
+    export_mac(&key, &iv, &data)
+        .verify_slice(&mac)
+        .map_err(|_| anyhow!("wrong passphrase or corrupted wallet export"))?;
+    Aes256Ctr::new(&(*key.as_bytes()).into(), &iv.into()).apply_keystream(&mut data);
+    Ok(String::from_utf8(data)?)
+}
+
+fn export_mac(key: &RawKey, iv: &[u8; 16], data: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).unwrap();
+    mac.update(iv);
+    mac.update(data);
+    mac
+}
+
+fn hex_field(export: &serde_json::Value, name: &str) -> anyhow::Result<Vec<u8>> {
+    let value = export[name]
+        .as_str()
+        .ok_or_else(|| anyhow!("{} missing", name))?;
+    hex::decode(value).with_context(|| format!("invalid {}", name))
+}
+
+/// Moves `path` to `path.1`, `path.1` to `path.2` and so on. Only `keep` old backups are kept
+pub fn rotate_backup_files(path: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 {
+        return Ok(());
+    }
+    let numbered = |i: usize| {
+        let mut numbered = path.as_os_str().to_owned();
+        numbered.push(format!(".{}", i));
+        PathBuf::from(numbered)
+    };
+
+    match remove_file(numbered(keep)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    for i in (1..keep).rev() {
+        if numbered(i).exists() {
+            rename(numbered(i), numbered(i + 1))?;
+        }
+    }
+    if path.exists() {
+        rename(path, numbered(1))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LmdbWalletStore, TestDbFile, TestLmdbEnv};
+    use rsnano_core::Account;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let kdf = KeyDerivationFunction::new(8);
+        let export = encrypt_wallet_export("{\"foo\": \"bar\"}", "secret", &kdf);
+        assert!(!export.contains("bar"));
+        assert_eq!(
+            decrypt_wallet_export(&export, "secret", &kdf).unwrap(),
+            "{\"foo\": \"bar\"}"
+        );
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let kdf = KeyDerivationFunction::new(8);
+        let export = encrypt_wallet_export("{}", "secret", &kdf);
+        assert!(decrypt_wallet_export(&export, "wrong", &kdf).is_err());
+    }
+
+    #[test]
+    fn detect_modified_data() {
+        let kdf = KeyDerivationFunction::new(8);
+        let export = encrypt_wallet_export("{\"foo\": \"bar\"}", "secret", &kdf);
+        let mut value: serde_json::Value = serde_json::from_str(&export).unwrap();
+        let mut data = hex::decode(value["data"].as_str().unwrap()).unwrap();
+        data[0] ^= 1;
+        value["data"] = hex::encode_upper(data).into();
+        assert!(decrypt_wallet_export(&value.to_string(), "secret", &kdf).is_err());
+    }
+
+    #[test]
+    fn restore_exported_wallet_store() {
+        let env = TestLmdbEnv::new();
+        let env = env.env();
+        let kdf = KeyDerivationFunction::new(8);
+        let mut txn = env.tx_begin_write();
+        let store = LmdbWalletStore::new(
+            1,
+            kdf.clone(),
+            &mut txn,
+            &Account::from(42),
+            Path::new("wallet_a"),
+        )
+        .unwrap();
+        store.insert_adhoc(&mut txn, &RawKey::from(1));
+
+        let export = store.export(&txn, "secret");
+        let restored = LmdbWalletStore::new_from_export(
+            1,
+            kdf,
+            &mut txn,
+            Path::new("wallet_b"),
+            &export,
+            "secret",
+        )
+        .unwrap();
+
+        assert_eq!(restored.serialize_json(&txn), store.serialize_json(&txn));
+    }
+
+    #[test]
+    fn rotate_backups() {
+        let file = TestDbFile::random();
+        let path = file.path.with_extension("json");
+        let numbered = |i: usize| PathBuf::from(format!("{}.{}", path.display(), i));
+        for content in ["1", "2", "3"] {
+            rotate_backup_files(&path, 2).unwrap();
+            std::fs::write(&path, content).unwrap();
+        }
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3");
+        assert_eq!(std::fs::read_to_string(numbered(1)).unwrap(), "2");
+        assert_eq!(std::fs::read_to_string(numbered(2)).unwrap(), "1");
+        rotate_backup_files(&path, 2).unwrap();
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(numbered(2)).unwrap(), "2");
+
+        remove_file(numbered(1)).unwrap();
+        remove_file(numbered(2)).unwrap();
+    }
+}
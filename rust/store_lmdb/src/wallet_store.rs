@@ -1,13 +1,14 @@
 use crate::{
-    iterator::DbIterator, lmdb_env::RwTransaction, Environment, EnvironmentWrapper, Fan,
-    LmdbIteratorImpl, LmdbWriteTransaction, Transaction,
+    decrypt_wallet_export, encrypt_wallet_export, iterator::DbIterator, lmdb_env::RwTransaction,
+    rotate_backup_files, Environment, EnvironmentWrapper, Fan, LmdbIteratorImpl,
+    LmdbWriteTransaction, Transaction,
 };
 use anyhow::bail;
 use lmdb::{DatabaseFlags, WriteFlags};
 use rsnano_core::{
     deterministic_key,
     utils::{Deserialize, MutStreamAdapter, Serialize, Stream, StreamAdapter, StreamExt},
-    Account, KeyDerivationFunction, PublicKey, RawKey,
+    Account, Amount, KeyDerivationFunction, PublicKey, RawKey,
 };
 use std::{
     fs::{set_permissions, File, Permissions},
@@ -198,6 +199,19 @@ impl<T: Environment + 'static> LmdbWalletStore<T> {
         Ok(store)
     }
 
+    /// Restores a wallet which was exported with `export`
+    pub fn new_from_export(
+        fanout: usize,
+        kdf: KeyDerivationFunction,
+        txn: &mut LmdbWriteTransaction<T>,
+        wallet: &Path,
+        export: &str,
+        passphrase: &str,
+    ) -> anyhow::Result<Self> {
+        let json = decrypt_wallet_export(export, passphrase, &kdf)?;
+        Self::new_from_json(fanout, kdf, txn, wallet, &json)
+    }
+
     fn ensure_key_exists(
         &self,
         txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
@@ -242,10 +256,15 @@ impl<T: Environment + 'static> LmdbWalletStore<T> {
         Account::from(6)
     }
 
-    pub fn special_count() -> Account {
+    /// Per wallet override of the minimum amount which is received automatically
+    pub fn receive_minimum_special() -> Account {
         Account::from(7)
     }
 
+    pub fn special_count() -> Account {
+        Account::from(8)
+    }
+
     pub fn initialize(&self, txn: &mut LmdbWriteTransaction<T>, path: &Path) -> anyhow::Result<()> {
         let path_str = path
             .as_os_str()
@@ -601,6 +620,43 @@ impl<T: Environment + 'static> LmdbWalletStore<T> {
         );
     }
 
+    /// Returns `None` if the wallet uses the receive minimum of the node config
+    pub fn receive_minimum(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+    ) -> Option<Amount> {
+        let bytes = txn
+            .get(self.db_handle(), Self::receive_minimum_special().as_bytes())
+            .ok()?;
+        let value = WalletValue::deserialize(&mut StreamAdapter::new(bytes)).ok()?;
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&value.key.as_bytes()[16..]);
+        Some(Amount::from_be_bytes(bytes))
+    }
+
+    pub fn receive_minimum_set(
+        &self,
+        txn: &mut LmdbWriteTransaction<T>,
+        receive_minimum: Option<Amount>,
+    ) {
+        match receive_minimum {
+            Some(amount) => {
+                let mut bytes = [0; 32];
+                bytes[16..].copy_from_slice(&amount.to_be_bytes());
+                self.entry_put_raw(
+                    txn,
+                    &Self::receive_minimum_special(),
+                    &WalletValue::new(RawKey::from_bytes(bytes), 0),
+                );
+            }
+            None => {
+                if self.receive_minimum(txn).is_some() {
+                    self.erase(txn, &Self::receive_minimum_special());
+                }
+            }
+        }
+    }
+
     pub fn insert_adhoc(&self, txn: &mut LmdbWriteTransaction<T>, prv: &RawKey) -> PublicKey {
         debug_assert!(self.valid_password(txn));
         let pub_key = PublicKey::try_from(prv).unwrap();
@@ -693,6 +749,35 @@ impl<T: Environment + 'static> LmdbWalletStore<T> {
         Ok(())
     }
 
+    /// Like `write_backup`, but the previous backups are kept as `path.1` to `path.<keep>`.
+    /// Nothing is written if the wallet didn't change since the last backup
+    pub fn write_rotated_backup(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        path: &Path,
+        keep: usize,
+    ) -> anyhow::Result<()> {
+        let json = self.serialize_json(txn);
+        if std::fs::read_to_string(path).ok().as_deref() == Some(json.as_str()) {
+            return Ok(());
+        }
+        rotate_backup_files(path, keep)?;
+        let mut file = File::create(path)?;
+        set_permissions(path, Permissions::from_mode(0o600))?;
+        write!(file, "{}", json)?;
+        Ok(())
+    }
+
+    /// The serialized wallet, encrypted with `passphrase`. The keys stay encrypted
+    /// by the wallet password, so it is needed as well to use the restored wallet
+    pub fn export(
+        &self,
+        txn: &dyn Transaction<Database = T::Database, RoCursor = T::RoCursor>,
+        passphrase: &str,
+    ) -> String {
+        encrypt_wallet_export(&self.serialize_json(txn), passphrase, &self.kdf)
+    }
+
     pub fn move_keys(
         &self,
         txn: &mut LmdbWriteTransaction<T>,
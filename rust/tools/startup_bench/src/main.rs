@@ -0,0 +1,84 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rsnano_ledger::{Ledger, LedgerConstants};
+use rsnano_store_lmdb::{drop_file_cache, EnvOptions, EnvironmentWrapper, LmdbStore};
+
+/// Measures how long the ledger cache generation at startup takes with a cold page cache,
+/// with and without read-ahead during the table scans.
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    let Some((ledger_path, constants, runs)) = parse_args(&args) else {
+        eprintln!("usage: startup_bench [live|beta] path/to/data.ldb [runs]");
+        return;
+    };
+
+    println!("Benchmarking ledger startup with {:?}", ledger_path);
+    for read_ahead_scans in [false, true] {
+        let mut total = Duration::ZERO;
+        for _ in 0..runs {
+            let elapsed = cold_startup(&ledger_path, &constants, read_ahead_scans);
+            println!(
+                "read-ahead {}: {} ms",
+                on_off(read_ahead_scans),
+                elapsed.as_millis()
+            );
+            total += elapsed;
+        }
+        println!(
+            "read-ahead {}: {} ms on average",
+            on_off(read_ahead_scans),
+            (total / runs).as_millis()
+        );
+    }
+}
+
+fn cold_startup(
+    ledger_path: &Path,
+    constants: &LedgerConstants,
+    read_ahead_scans: bool,
+) -> Duration {
+    drop_file_cache(ledger_path).expect("could not drop the page cache of the ledger file");
+    let options = EnvOptions {
+        read_ahead_scans,
+        ..Default::default()
+    };
+
+    let started = Instant::now();
+    let store = Arc::new(
+        LmdbStore::<EnvironmentWrapper>::open(ledger_path)
+            .options(&options)
+            .build()
+            .unwrap(),
+    );
+    let _ledger = Ledger::new(store, constants.clone()).unwrap();
+    started.elapsed()
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn parse_args(args: &[String]) -> Option<(PathBuf, LedgerConstants, u32)> {
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    let constants = match args[1].as_str() {
+        "live" => LedgerConstants::live(),
+        "beta" => LedgerConstants::beta(),
+        _ => return None,
+    };
+    let runs = match args.get(3) {
+        Some(runs) => runs.parse().ok().filter(|&r| r > 0)?,
+        None => 3,
+    };
+    Some((PathBuf::from(&args[2]), constants, runs))
+}